@@ -0,0 +1,129 @@
+//! Mock account provider for tests.
+//!
+//! Signs nothing: signatures are derived from the signed bytes, so that the
+//! same transaction or message always gets the same signature, and tests can
+//! tell signatures apart without any key material.
+
+use crate::{AccountError, AccountInterface};
+use async_trait::async_trait;
+use solver_types::testing::MockBehavior;
+use solver_types::{Address, ConfigSchema, Field, FieldType, Schema, Signature, Transaction};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Address of mock accounts not configured with one.
+const DEFAULT_ADDRESS: [u8; 20] = [0x50; 20];
+
+/// Account provider with a fixed address and derived signatures.
+///
+/// Scripted failures surface as [`AccountError::Provider`].
+pub struct MockAccount {
+	/// Address of the account.
+	address: Address,
+	/// Latencies, scripted failures and call counts.
+	behavior: Arc<MockBehavior>,
+}
+
+impl MockAccount {
+	/// Creates a mock account with `address`.
+	pub fn new(address: Address) -> Self {
+		Self {
+			address,
+			behavior: Arc::new(MockBehavior::new()),
+		}
+	}
+
+	/// Replaces the account's behaviour.
+	pub fn with_behavior(mut self, behavior: MockBehavior) -> Self {
+		self.behavior = Arc::new(behavior);
+		self
+	}
+
+	/// Returns the account's behaviour, to script failures and count calls
+	/// once the account is handed to the solver.
+	pub fn behavior(&self) -> Arc<MockBehavior> {
+		self.behavior.clone()
+	}
+
+	/// Plays a call to an operation.
+	async fn call(&self, operation: &str) -> Result<(), AccountError> {
+		self.behavior
+			.call(operation)
+			.await
+			.map_err(AccountError::Provider)
+	}
+}
+
+impl Default for MockAccount {
+	fn default() -> Self {
+		Self::new(Address(DEFAULT_ADDRESS.to_vec()))
+	}
+}
+
+/// Configuration schema for MockAccount.
+pub struct MockAccountSchema;
+
+impl ConfigSchema for MockAccountSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let mut optional = vec![Field::new("address", FieldType::String)];
+		optional.extend(MockBehavior::config_fields());
+		Schema::new(vec![], optional).validate(config)
+	}
+}
+
+#[async_trait]
+impl AccountInterface for MockAccount {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(MockAccountSchema)
+	}
+
+	async fn address(&self) -> Result<Address, AccountError> {
+		self.call("address").await?;
+		Ok(self.address.clone())
+	}
+
+	async fn sign_transaction(&self, tx: &Transaction) -> Result<Signature, AccountError> {
+		self.call("sign_transaction").await?;
+		let mut hasher = DefaultHasher::new();
+		tx.chain_id.hash(&mut hasher);
+		tx.to.hash(&mut hasher);
+		tx.data.hash(&mut hasher);
+		tx.value.hash(&mut hasher);
+		tx.nonce.hash(&mut hasher);
+		Ok(derived_signature(hasher.finish()))
+	}
+
+	async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		self.call("sign_message").await?;
+		let mut hasher = DefaultHasher::new();
+		message.hash(&mut hasher);
+		Ok(derived_signature(hasher.finish()))
+	}
+}
+
+/// Builds a 65-byte signature from a digest of the signed bytes.
+fn derived_signature(digest: u64) -> Signature {
+	let mut bytes = digest.to_be_bytes().repeat(8);
+	bytes.push(27);
+	Signature(bytes)
+}
+
+/// Factory function to create a mock account from configuration.
+///
+/// Optional configuration parameters:
+/// - `address`: Hex address of the account
+/// - `latency_ms`, `operation_latency_ms`, `fail_first`: see [`MockBehavior::from_config`]
+pub fn create_account(config: &toml::Value) -> Box<dyn AccountInterface> {
+	let address = config
+		.get("address")
+		.and_then(|v| v.as_str())
+		.map(|address| {
+			let address = hex::decode(address.trim_start_matches("0x"))
+				.expect("address of mock account must be hex");
+			Address(address)
+		})
+		.unwrap_or_else(|| Address(DEFAULT_ADDRESS.to_vec()));
+
+	Box::new(MockAccount::new(address).with_behavior(MockBehavior::from_config(config)))
+}
@@ -0,0 +1,121 @@
+//! Solana keypair account implementation.
+//!
+//! Solana accounts are ed25519 keys rather than secp256k1 keys, and their
+//! transactions are authorized by signing the compiled transaction message.
+//! This implementation holds such a key locally and signs through the
+//! ed25519 methods of the AccountInterface trait; it cannot sign EVM
+//! transactions.
+
+use crate::{AccountError, AccountInterface};
+use async_trait::async_trait;
+use ed25519_dalek::{Signer, SigningKey};
+use solver_types::{Address, ConfigSchema, Field, FieldType, Schema, Signature, Transaction};
+
+/// Local Solana keypair.
+pub struct SolanaKeypair {
+	/// The ed25519 key signing messages and transactions.
+	signing_key: SigningKey,
+}
+
+impl SolanaKeypair {
+	/// Creates a keypair from a base58-encoded key.
+	///
+	/// Accepts both the 64-byte keypair format of the Solana CLI, whose last
+	/// 32 bytes must be the public key of the first 32, and a bare 32-byte
+	/// secret key.
+	pub fn from_base58(key: &str) -> Result<Self, AccountError> {
+		let bytes = bs58::decode(key.trim())
+			.into_vec()
+			.map_err(|e| AccountError::InvalidKey(format!("Invalid base58 key: {}", e)))?;
+
+		let signing_key = match bytes.len() {
+			32 => SigningKey::from_bytes(&bytes.try_into().unwrap()),
+			64 => {
+				let keypair: [u8; 64] = bytes.try_into().unwrap();
+				SigningKey::from_keypair_bytes(&keypair).map_err(|e| {
+					AccountError::InvalidKey(format!("Invalid Solana keypair: {}", e))
+				})?
+			}
+			length => {
+				return Err(AccountError::InvalidKey(format!(
+					"Solana key must be 32 or 64 bytes, got {}",
+					length
+				)))
+			}
+		};
+
+		Ok(Self { signing_key })
+	}
+
+	/// Returns the public key of the keypair.
+	pub fn public_key(&self) -> [u8; 32] {
+		self.signing_key.verifying_key().to_bytes()
+	}
+}
+
+/// Configuration schema for SolanaKeypair.
+pub struct SolanaKeypairSchema;
+
+impl ConfigSchema for SolanaKeypairSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("private_key", FieldType::String).with_validator(|value| {
+					SolanaKeypair::from_base58(value.as_str().unwrap())
+						.map(|_| ())
+						.map_err(|e| e.to_string())
+				}),
+			],
+			// Optional fields
+			vec![],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl AccountInterface for SolanaKeypair {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(SolanaKeypairSchema)
+	}
+
+	async fn address(&self) -> Result<Address, AccountError> {
+		Ok(Address(self.public_key().to_vec()))
+	}
+
+	async fn sign_transaction(&self, _tx: &Transaction) -> Result<Signature, AccountError> {
+		Err(AccountError::Unsupported(
+			"Solana transactions are signed over their compiled message with sign_ed25519"
+				.to_string(),
+		))
+	}
+
+	async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		self.sign_ed25519(message).await
+	}
+
+	async fn ed25519_public_key(&self) -> Result<Address, AccountError> {
+		self.address().await
+	}
+
+	async fn sign_ed25519(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		Ok(Signature(
+			self.signing_key.sign(message).to_bytes().to_vec(),
+		))
+	}
+}
+
+/// Factory function to create a Solana account provider from configuration.
+///
+/// Required configuration parameters:
+/// - `private_key`: The base58-encoded keypair or secret key
+pub fn create_account(config: &toml::Value) -> Box<dyn AccountInterface> {
+	let private_key = config
+		.get("private_key")
+		.and_then(|v| v.as_str())
+		.expect("private_key is required for Solana keypair");
+
+	Box::new(SolanaKeypair::from_base58(private_key).expect("Failed to create Solana keypair"))
+}
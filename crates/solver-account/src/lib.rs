@@ -5,12 +5,17 @@
 //! such as address retrieval and transaction signing.
 
 use async_trait::async_trait;
-use solver_types::{Address, ConfigSchema, Signature, Transaction};
+use solver_types::{
+	Address, Classify, ConfigSchema, ErrorCategory, ErrorClass, Signature, Transaction,
+};
 use thiserror::Error;
 
 /// Re-export implementations
 pub mod implementations {
 	pub mod local;
+	#[cfg(feature = "testing")]
+	pub mod mock;
+	pub mod solana;
 }
 
 /// Errors that can occur during account operations.
@@ -25,6 +30,22 @@ pub enum AccountError {
 	/// Error that occurs when interacting with the account provider.
 	#[error("Provider error: {0}")]
 	Provider(String),
+	/// Error that occurs when the account cannot perform an operation.
+	#[error("Unsupported: {0}")]
+	Unsupported(String),
+}
+
+impl Classify for AccountError {
+	fn class(&self) -> ErrorClass {
+		match self {
+			// Remote signers may time out or throttle requests
+			Self::Provider(_) => ErrorClass::transient(ErrorCategory::Signing),
+			Self::SigningFailed(_) => ErrorClass::permanent(ErrorCategory::Signing),
+			Self::InvalidKey(_) | Self::Unsupported(_) => {
+				ErrorClass::permanent(ErrorCategory::Configuration)
+			}
+		}
+	}
 }
 
 /// Trait defining the interface for account providers.
@@ -57,6 +78,29 @@ pub trait AccountInterface: Send + Sync {
 	/// Takes a byte slice representing the message and returns a signature.
 	/// This is useful for message authentication and verification purposes.
 	async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError>;
+
+	/// Returns the account's ed25519 public key.
+	///
+	/// Chains outside the EVM family, such as Solana, identify accounts by an
+	/// ed25519 key. Accounts without one fail with
+	/// [`AccountError::Unsupported`].
+	async fn ed25519_public_key(&self) -> Result<Address, AccountError> {
+		Err(AccountError::Unsupported(
+			"account has no ed25519 key".to_string(),
+		))
+	}
+
+	/// Signs a message with the account's ed25519 key, returning the 64-byte
+	/// signature.
+	///
+	/// Solana transactions are authorized by such a signature over their
+	/// compiled message. Accounts without an ed25519 key fail with
+	/// [`AccountError::Unsupported`].
+	async fn sign_ed25519(&self, _message: &[u8]) -> Result<Signature, AccountError> {
+		Err(AccountError::Unsupported(
+			"account has no ed25519 key".to_string(),
+		))
+	}
 }
 
 /// Service that manages account operations.
@@ -90,4 +134,25 @@ impl AccountService {
 	pub async fn sign(&self, tx: &Transaction) -> Result<Signature, AccountError> {
 		self.provider.sign_transaction(tx).await
 	}
+
+	/// Signs a message using the managed account.
+	///
+	/// This method delegates to the underlying provider's sign_message method.
+	pub async fn sign_message(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		self.provider.sign_message(message).await
+	}
+
+	/// Retrieves the ed25519 public key of the managed account.
+	///
+	/// This method delegates to the underlying provider's ed25519_public_key method.
+	pub async fn ed25519_public_key(&self) -> Result<Address, AccountError> {
+		self.provider.ed25519_public_key().await
+	}
+
+	/// Signs a message with the managed account's ed25519 key.
+	///
+	/// This method delegates to the underlying provider's sign_ed25519 method.
+	pub async fn sign_ed25519(&self, message: &[u8]) -> Result<Signature, AccountError> {
+		self.provider.sign_ed25519(message).await
+	}
 }
@@ -4,6 +4,16 @@
 //! It supports loading configuration from TOML files and provides validation to ensure
 //! all required configuration values are properly set.
 
+mod lint;
+mod reload;
+mod secrets;
+
+pub use lint::ConfigWarning;
+pub use reload::RELOADABLE_SECTIONS;
+pub use secrets::{
+	EnvSecretProvider, FileSecretProvider, SecretProvider, SecretResolver, VaultSecretProvider,
+};
+
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -21,6 +31,9 @@ pub enum ConfigError {
 	/// Error that occurs when configuration validation fails.
 	#[error("Validation error: {0}")]
 	Validation(String),
+	/// Error that occurs when resolving an environment variable or secret.
+	#[error("Secret error: {0}")]
+	Secret(String),
 }
 
 /// Main configuration structure for the OIF solver.
@@ -46,6 +59,43 @@ pub struct Config {
 	pub settlement: SettlementConfig,
 	/// Configuration for the HTTP API server.
 	pub api: Option<ApiConfig>,
+	/// Configuration for liquidity depth checks.
+	pub liquidity: Option<LiquidityConfig>,
+	/// Configuration for the risk validators run on new orders.
+	pub validation: Option<ValidationConfig>,
+	/// Configuration for token approvals set up at startup and before fills.
+	pub approvals: Option<ApprovalConfig>,
+	/// Configuration for the inventory bootstrap assistant.
+	pub bootstrap: Option<BootstrapConfig>,
+	/// Configuration for ledger reconciliation.
+	pub accounting: Option<AccountingConfig>,
+	/// Configuration for shutdown checkpoints and crash recovery.
+	pub recovery: Option<RecoveryConfig>,
+	/// Configuration for chain reorg detection.
+	pub reorg: Option<ReorgConfig>,
+	/// Configuration for the execution context given to strategies.
+	pub execution_context: Option<ExecutionContextConfig>,
+	/// Configuration for the economic preconditions gating readiness.
+	pub readiness: Option<ReadinessConfig>,
+	/// Configuration for automatic re-driving of failed orders.
+	pub dead_letter: Option<DeadLetterConfig>,
+	/// Configuration for polling of the solver's token inventory.
+	pub inventory: Option<InventoryConfig>,
+	/// Configuration for watching the configuration file for changes.
+	/// The file is only reloaded on SIGHUP if not specified.
+	pub reload: Option<ReloadConfig>,
+	/// Configuration for the gas spend budget and failure circuit breaker.
+	pub gas_budget: Option<GasBudgetConfig>,
+	/// Configuration for pruning the data of finished orders.
+	/// Order data is kept forever if not specified.
+	pub retention: Option<RetentionConfig>,
+	/// Configuration for picking the destination chain of orders fillable on
+	/// several chains. Defaults apply if not specified.
+	pub routing: Option<RoutingConfig>,
+	/// Per-chain network metadata keyed by chain ID.
+	/// Chains without an entry are treated like Ethereum (18-decimal ETH gas).
+	#[serde(default)]
+	pub networks: HashMap<String, NetworkConfig>,
 }
 
 /// Configuration specific to the solver instance.
@@ -57,6 +107,16 @@ pub struct SolverConfig {
 	/// Defaults to 480 minutes (8 hours) if not specified.
 	#[serde(default = "default_monitoring_timeout_minutes")]
 	pub monitoring_timeout_minutes: u64,
+	/// Log filter, such as "info" or "solver_core=debug,info", replacing the
+	/// command-line log level. Ignored when RUST_LOG is set.
+	#[serde(default)]
+	pub log_level: Option<String>,
+	/// Runs the solver against live order flow without submitting anything.
+	/// Fills are generated and simulated, and logged along with their gas
+	/// and projected profit. Orders processed in a dry run are recorded as
+	/// finished, so dry runs should use storage of their own.
+	#[serde(default)]
+	pub dry_run: bool,
 }
 
 /// Returns the default monitoring timeout in minutes.
@@ -67,10 +127,52 @@ fn default_monitoring_timeout_minutes() -> u64 {
 /// Configuration for the storage backend.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct StorageConfig {
-	/// The type of storage backend to use (e.g., "memory", "redis", "postgres").
+	/// The type of storage backend to use (e.g., "file", "sqlite").
 	pub backend: String,
 	/// Backend-specific configuration parameters as raw TOML values.
 	pub config: toml::Value,
+	/// Hours that immutable RPC lookups such as final receipts and token
+	/// metadata stay cached. Set to 0 to disable caching.
+	#[serde(default = "default_rpc_cache_ttl_hours")]
+	pub rpc_cache_ttl_hours: u64,
+	/// Compression of stored payloads. Payloads are stored uncompressed if
+	/// not specified.
+	pub compression: Option<CompressionConfig>,
+}
+
+/// Returns the default time-to-live of cached RPC lookups in hours.
+fn default_rpc_cache_ttl_hours() -> u64 {
+	168 // One week
+}
+
+/// Configuration for zstd compression of stored payloads.
+///
+/// Payloads already stored are read back whether or not they are compressed,
+/// so compression can be enabled or changed on an existing store.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CompressionConfig {
+	/// zstd level (1-22) for namespaces without their own level.
+	/// Defaults to 3 if not specified.
+	#[serde(default = "default_compression_level")]
+	pub level: i32,
+	/// Payloads smaller than this many bytes are stored uncompressed.
+	/// Defaults to 256 bytes if not specified.
+	#[serde(default = "default_compression_min_size_bytes")]
+	pub min_size_bytes: usize,
+	/// Levels of specific storage namespaces, such as `orders`. A level of 0
+	/// stores the namespace uncompressed.
+	#[serde(default)]
+	pub namespaces: HashMap<String, i32>,
+}
+
+/// Returns the default zstd compression level.
+fn default_compression_level() -> i32 {
+	3
+}
+
+/// Returns the default minimum size of compressed payloads in bytes.
+fn default_compression_min_size_bytes() -> usize {
+	256
 }
 
 /// Configuration for delivery mechanisms.
@@ -83,6 +185,82 @@ pub struct DeliveryConfig {
 	/// Defaults to 12 confirmations if not specified.
 	#[serde(default = "default_confirmations")]
 	pub min_confirmations: u64,
+	/// Seconds to wait for confirmations before giving up.
+	/// Left to the providers if not specified.
+	#[serde(default)]
+	pub confirmation_timeout_seconds: Option<u64>,
+	/// Confirmation overrides keyed by chain ID, for chains whose finality
+	/// differs from the default.
+	#[serde(default)]
+	pub confirmations: HashMap<String, ConfirmationConfig>,
+	/// Whether transactions are simulated before submission, so that those
+	/// that would revert are dropped without paying gas.
+	/// Defaults to true if not specified.
+	#[serde(default = "default_simulate")]
+	pub simulate: bool,
+	/// Fee replacement of stuck transactions, disabled if not specified.
+	#[serde(default)]
+	pub replacement: Option<ReplacementConfig>,
+	/// Gas fee estimation, left to the providers if not specified.
+	#[serde(default)]
+	pub gas: Option<GasConfig>,
+	/// Bundling of fills into one transaction, disabled if not specified.
+	#[serde(default)]
+	pub bundling: Option<BundlingConfig>,
+}
+
+/// Returns whether transactions are simulated before submission by default.
+fn default_simulate() -> bool {
+	true
+}
+
+/// Configuration for gas fee estimation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GasConfig {
+	/// Strategy applying to chains without an override.
+	#[serde(flatten)]
+	pub default: GasStrategyConfig,
+	/// Strategy overrides keyed by chain ID.
+	#[serde(default)]
+	pub chains: HashMap<String, GasStrategyConfig>,
+}
+
+/// Fee estimation strategy for a chain.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GasStrategyConfig {
+	/// Fee model to price transactions with: "legacy" or "eip1559".
+	#[serde(default = "default_fee_mode")]
+	pub mode: String,
+	/// Number of recent blocks sampled for fee estimation.
+	#[serde(default = "default_fee_history_blocks")]
+	pub fee_history_blocks: u64,
+	/// Percentile of priority fees paid within each sampled block.
+	#[serde(default = "default_priority_fee_percentile")]
+	pub priority_fee_percentile: f64,
+	/// Multiplier applied to the next block's base fee for EIP-1559 max fees,
+	/// in basis points.
+	#[serde(default = "default_base_fee_multiplier_bps")]
+	pub base_fee_multiplier_bps: u64,
+}
+
+/// Returns the default fee mode.
+fn default_fee_mode() -> String {
+	"eip1559".to_string()
+}
+
+/// Returns the default number of blocks sampled for fee estimation.
+fn default_fee_history_blocks() -> u64 {
+	20
+}
+
+/// Returns the default priority fee percentile.
+fn default_priority_fee_percentile() -> f64 {
+	50.0
+}
+
+/// Returns the default base fee multiplier in basis points.
+fn default_base_fee_multiplier_bps() -> u64 {
+	20_000 // Survives several consecutive full blocks
 }
 
 /// Returns the default number of confirmations required.
@@ -90,6 +268,99 @@ fn default_confirmations() -> u64 {
 	12 // Default to 12 confirmations
 }
 
+/// Confirmation override for a chain.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConfirmationConfig {
+	/// Minimum number of confirmations required on the chain.
+	/// Defaults to `delivery.min_confirmations` if not specified.
+	#[serde(default)]
+	pub min_confirmations: Option<u64>,
+	/// Seconds to wait for confirmations on the chain before giving up.
+	/// Defaults to `delivery.confirmation_timeout_seconds` if not specified.
+	#[serde(default)]
+	pub timeout_seconds: Option<u64>,
+}
+
+impl DeliveryConfig {
+	/// Returns the number of confirmations required on a chain.
+	pub fn min_confirmations_for(&self, chain_id: u64) -> u64 {
+		self.confirmations
+			.get(&chain_id.to_string())
+			.and_then(|confirmation| confirmation.min_confirmations)
+			.unwrap_or(self.min_confirmations)
+	}
+
+	/// Returns the seconds to wait for confirmations on a chain, if bounded.
+	pub fn confirmation_timeout_for(&self, chain_id: u64) -> Option<u64> {
+		self.confirmations
+			.get(&chain_id.to_string())
+			.and_then(|confirmation| confirmation.timeout_seconds)
+			.or(self.confirmation_timeout_seconds)
+	}
+}
+
+/// Configuration for re-submitting stuck transactions with higher fees.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReplacementConfig {
+	/// Seconds a transaction may stay pending before it is replaced.
+	#[serde(default = "default_stuck_after_seconds")]
+	pub stuck_after_seconds: u64,
+	/// Fee increase over the current gas price per replacement, in basis points.
+	#[serde(default = "default_fee_bump_bps")]
+	pub fee_bump_bps: u64,
+	/// Maximum number of times a transaction is replaced.
+	#[serde(default = "default_max_replacements")]
+	pub max_replacements: u32,
+}
+
+/// Returns the default number of seconds before a transaction counts as stuck.
+fn default_stuck_after_seconds() -> u64 {
+	180
+}
+
+/// Returns the default fee increase per replacement in basis points.
+fn default_fee_bump_bps() -> u64 {
+	1250
+}
+
+/// Returns the default maximum number of replacements per transaction.
+fn default_max_replacements() -> u32 {
+	3
+}
+
+/// Configuration for bundling several fills into one transaction.
+///
+/// Orders are only bundled when their order implementation supports it and
+/// they have a single output; bundles group orders delivering the same token
+/// on the same destination chain.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BundlingConfig {
+	/// Maximum number of orders filled in one transaction.
+	/// Defaults to 5 if not specified.
+	#[serde(default = "default_bundle_max_orders")]
+	pub max_orders: usize,
+	/// Milliseconds the first order of a bundle waits for others to join.
+	/// Defaults to 2000 milliseconds if not specified.
+	#[serde(default = "default_bundle_window_ms")]
+	pub window_ms: u64,
+	/// Map of chain IDs to maps of token addresses to the largest output
+	/// amount bundled, as a decimal string in the token's smallest unit.
+	/// Larger orders are filled on their own; tokens without an entry are
+	/// bundled at any amount.
+	#[serde(default)]
+	pub max_output_amounts: HashMap<String, HashMap<String, String>>,
+}
+
+/// Returns the default maximum number of orders per bundle.
+fn default_bundle_max_orders() -> usize {
+	5
+}
+
+/// Returns the default bundling window in milliseconds.
+fn default_bundle_window_ms() -> u64 {
+	2000
+}
+
 /// Configuration for account management.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AccountConfig {
@@ -105,16 +376,206 @@ pub struct DiscoveryConfig {
 	/// Map of discovery source names to their configurations.
 	/// Each source has its own configuration format stored as raw TOML values.
 	pub sources: HashMap<String, toml::Value>,
+	/// Discovered intents buffered before the engine takes them on.
+	#[serde(default = "default_intake_capacity")]
+	pub intake_capacity: usize,
+	/// What happens to intents discovered while the intake is full:
+	/// "drop_oldest" evicts the longest-waiting intent, "reject" refuses the
+	/// new one and "spill" holds it in storage until there is room.
+	#[serde(default = "default_intake_overflow")]
+	pub intake_overflow: String,
+	/// Recently queued intent IDs remembered so that intents found by
+	/// several sources are only handled once. Set to 0 to disable.
+	/// Defaults to 10000 if not specified.
+	#[serde(default = "default_intake_dedup_window")]
+	pub intake_dedup_window: usize,
+	/// Intents per second queued from all sources together.
+	/// Not limited if not specified.
+	pub intake_max_intents_per_second: Option<f64>,
+	/// Map of source names, such as "eip7683" or "offchain", to the intents
+	/// per second queued from them.
+	#[serde(default)]
+	pub intake_source_rate_limits: HashMap<String, f64>,
+}
+
+/// Returns the default capacity of the discovery intake.
+fn default_intake_capacity() -> usize {
+	10_000
 }
 
+/// Returns the default overflow policy of the discovery intake.
+fn default_intake_overflow() -> String {
+	"spill".to_string()
+}
+
+/// Returns the default number of intent IDs the discovery intake remembers.
+fn default_intake_dedup_window() -> usize {
+	10_000
+}
+
+/// Order classes that class-specific configuration can refer to.
+const ORDER_CLASSES: [&str; 4] = ["same_chain", "cross_chain", "multi_output", "gasless"];
+
 /// Configuration for order processing.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct OrderConfig {
 	/// Map of order implementation names to their configurations.
 	/// Each implementation handles specific order types.
 	pub implementations: HashMap<String, toml::Value>,
+	/// Map of settler contract addresses to the implementation handling intents
+	/// opened on that settler. Intents from other settlers are routed to the
+	/// implementation named after their standard.
+	#[serde(default)]
+	pub settler_routes: HashMap<String, String>,
 	/// Strategy configuration for order execution.
 	pub execution_strategy: StrategyConfig,
+	/// Map of order classes (e.g. "gasless", "multi_output") to strategies that
+	/// replace the default execution strategy for orders of that class.
+	#[serde(default)]
+	pub class_strategies: HashMap<String, StrategyConfig>,
+	/// Fast decision path for orders swapping equivalent stablecoins.
+	pub stablecoin_fast_path: Option<StablecoinFastPathConfig>,
+	/// Time limits after which competitive orders are no longer filled.
+	pub latency_budget: Option<LatencyBudgetConfig>,
+	/// Map of order standards (e.g. "eip7683") to the risky capabilities
+	/// enabled for them. Standards without an entry have all of them disabled.
+	#[serde(default)]
+	pub features: HashMap<String, StandardFeaturesConfig>,
+	/// Map of chain IDs to tokens known to charge a fee on transfer.
+	#[serde(default)]
+	pub fee_on_transfer_tokens: HashMap<String, Vec<String>>,
+	/// Cheap filters rejecting spam orders before any RPC is spent on them.
+	pub intake: Option<IntakeConfig>,
+	/// Chains, tokens and addresses orders are rejected for.
+	pub policy: Option<PolicyConfig>,
+	/// Times a deferred order is decided again before it is skipped.
+	/// Defaults to 5 if not specified.
+	#[serde(default = "default_max_deferrals")]
+	pub max_deferrals: u32,
+}
+
+/// Returns the default number of times a deferred order is decided again.
+fn default_max_deferrals() -> u32 {
+	5
+}
+
+/// Configuration for order intake filters.
+///
+/// The filters only look at the order data, so orders failing them are
+/// rejected before the solver spends any RPC requests on them.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct IntakeConfig {
+	/// Map of chain IDs to maps of token addresses to the minimum input amount
+	/// accepted, as a decimal string in the token's smallest unit.
+	#[serde(default)]
+	pub min_input_amounts: HashMap<String, HashMap<String, String>>,
+	/// Orders accepted per user per minute. Unlimited if not specified.
+	pub max_orders_per_user_per_minute: Option<u32>,
+}
+
+/// Configuration for the intent policy.
+///
+/// Orders touching a chain, token or address the policy rules out are
+/// rejected from the order data alone, before any RPC request is made.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PolicyConfig {
+	/// Chain IDs orders may touch. Every chain is allowed if empty.
+	#[serde(default)]
+	pub allowed_chains: Vec<u64>,
+	/// Chain IDs orders may not touch.
+	#[serde(default)]
+	pub denied_chains: Vec<u64>,
+	/// Map of chain IDs to the only tokens orders may move on that chain.
+	/// Chains without an entry allow any token.
+	#[serde(default)]
+	pub allowed_tokens: HashMap<String, Vec<String>>,
+	/// Map of chain IDs to tokens orders may not move on that chain.
+	#[serde(default)]
+	pub denied_tokens: HashMap<String, Vec<String>>,
+	/// Addresses orders may not come from or pay out to.
+	#[serde(default)]
+	pub denied_addresses: Vec<String>,
+	/// URL of a JSON document with additional `denied_tokens` and
+	/// `denied_addresses`, such as a sanctions list.
+	pub remote_url: Option<String>,
+	/// Interval in seconds between fetches of the remote lists.
+	/// Defaults to 3600 seconds (1 hour) if not specified.
+	#[serde(default = "default_policy_refresh_interval_seconds")]
+	pub refresh_interval_seconds: u64,
+}
+
+/// Returns the default interval between fetches of the remote policy lists.
+fn default_policy_refresh_interval_seconds() -> u64 {
+	3600
+}
+
+/// Risky order capabilities enabled for a standard.
+///
+/// Every capability is disabled unless set to true, so that accepting a new
+/// kind of order is always an explicit configuration change.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct StandardFeaturesConfig {
+	/// Accept outputs that execute a call at the recipient.
+	#[serde(default)]
+	pub call_bearing_outputs: bool,
+	/// Accept orders moving tokens listed in `order.fee_on_transfer_tokens`.
+	#[serde(default)]
+	pub fee_on_transfer_tokens: bool,
+	/// Accept orders with more than one output.
+	#[serde(default)]
+	pub multi_output: bool,
+}
+
+/// Configuration for order latency budgets.
+///
+/// Budgets are measured from intent discovery. A source-specific budget takes
+/// precedence over a standard-specific one, which takes precedence over the
+/// default. Intents without any applicable budget are never skipped.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LatencyBudgetConfig {
+	/// Budget in milliseconds applying to all intents.
+	pub default_ms: Option<u64>,
+	/// Map of order standards (e.g. "eip7683") to budgets in milliseconds.
+	#[serde(default)]
+	pub standards: HashMap<String, u64>,
+	/// Map of intent sources, as reported by discovery, to budgets in milliseconds.
+	#[serde(default)]
+	pub sources: HashMap<String, u64>,
+}
+
+/// Configuration for the stablecoin-equivalence fast path.
+///
+/// Orders whose inputs and outputs all belong to the same group of equivalent
+/// stablecoins are decided by comparing amounts directly, skipping oracle
+/// lookups, liquidity checks, and the execution strategy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StablecoinFastPathConfig {
+	/// Minimum spread between inputs and outputs in basis points.
+	/// Defaults to 5 bps if not specified.
+	#[serde(default = "default_stablecoin_min_margin_bps")]
+	pub min_margin_bps: u64,
+	/// Gas price in gwei above which orders are deferred rather than filled,
+	/// as the margin does not account for gas. Defaults to the
+	/// `max_gas_price_gwei` of the execution strategy, if it sets one.
+	pub max_gas_price_gwei: Option<u64>,
+	/// Map of group names (e.g. "usdc") to the equivalent tokens in that group.
+	pub groups: HashMap<String, Vec<StablecoinTokenConfig>>,
+}
+
+/// A stablecoin deployment that belongs to an equivalence group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StablecoinTokenConfig {
+	/// Chain ID the token is deployed on.
+	pub chain_id: u64,
+	/// Token contract address.
+	pub address: String,
+	/// Number of decimals used by the token.
+	pub decimals: u8,
+}
+
+/// Returns the default minimum stablecoin margin in basis points.
+fn default_stablecoin_min_margin_bps() -> u64 {
+	5
 }
 
 /// Configuration for execution strategies.
@@ -132,6 +593,688 @@ pub struct SettlementConfig {
 	/// Map of settlement implementation names to their configurations.
 	/// Each implementation handles specific settlement mechanisms.
 	pub implementations: HashMap<String, toml::Value>,
+	/// Map of chain IDs to the address that claimed rewards are sent to.
+	/// Chains without an entry pay rewards out to the solver address.
+	#[serde(default)]
+	pub claim_recipients: HashMap<String, String>,
+	/// Calibration of claim gas limits from past claims.
+	pub claim_gas: Option<ClaimGasConfig>,
+	/// Times a fill proof is rebuilt after a claim reverts because the proof
+	/// went stale, before the order is given up on. Set to 0 to disable.
+	/// Defaults to 2 if not specified.
+	#[serde(default = "default_max_proof_regenerations")]
+	pub max_proof_regenerations: u32,
+	/// Claim bot claims are handed to instead of being submitted locally.
+	pub claim_delegation: Option<ClaimDelegationConfig>,
+	/// Holding back of claims while gas is expensive.
+	/// Claims are submitted as soon as they are ready if not specified.
+	pub claim_schedule: Option<ClaimScheduleConfig>,
+	/// Grouping of ready claims into batches.
+	/// Claims are processed one at a time as they become ready if not
+	/// specified.
+	pub claim_batching: Option<ClaimBatchingConfig>,
+}
+
+/// Configuration for holding back claims while gas is expensive.
+///
+/// Claims stop being held once the order's claim deadline comes within the
+/// margin, or once they were held for the longest delay allowed. Claims whose
+/// gas costs more than they pay out are held the same way, and abandoned
+/// rather than submitted when they can wait no longer.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClaimScheduleConfig {
+	/// Gas price in gwei above which claims are held back.
+	pub max_gas_price_gwei: f64,
+	/// Map of chain IDs to the gas price in gwei above which claims on the
+	/// chain are held back, overriding `max_gas_price_gwei`.
+	#[serde(default)]
+	pub chain_max_gas_price_gwei: HashMap<String, f64>,
+	/// Seconds before the claim deadline from which claims are submitted
+	/// whatever the gas price.
+	/// Defaults to 3600 seconds if not specified.
+	#[serde(default = "default_claim_schedule_deadline_margin_seconds")]
+	pub deadline_margin_seconds: u64,
+	/// Longest a claim is held back, in seconds.
+	/// Defaults to 86400 seconds (24 hours) if not specified.
+	#[serde(default = "default_claim_schedule_max_delay_seconds")]
+	pub max_delay_seconds: u64,
+	/// Seconds between gas price checks of held claims.
+	/// Defaults to 60 seconds if not specified.
+	#[serde(default = "default_claim_schedule_recheck_interval_seconds")]
+	pub recheck_interval_seconds: u64,
+}
+
+/// Returns the default margin before the claim deadline in seconds.
+fn default_claim_schedule_deadline_margin_seconds() -> u64 {
+	3600
+}
+
+/// Returns the default longest delay of a held claim in seconds.
+fn default_claim_schedule_max_delay_seconds() -> u64 {
+	86400
+}
+
+/// Returns the default interval between checks of held claims in seconds.
+fn default_claim_schedule_recheck_interval_seconds() -> u64 {
+	60
+}
+
+/// Configuration for batching ready claims.
+///
+/// Claims are grouped by the chain they are sent on and the settlement
+/// implementation sending them. A batch is processed once it holds enough
+/// claims, once its first claim waited long enough, or once the gas its
+/// claims are estimated to use would exceed the limit.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClaimBatchingConfig {
+	/// Number of claims that fills a batch.
+	/// Defaults to 10 if not specified.
+	#[serde(default = "default_claim_batching_max_size")]
+	pub max_size: usize,
+	/// Longest a claim waits for its batch to fill, in seconds.
+	/// Defaults to 30 seconds if not specified.
+	#[serde(default = "default_claim_batching_max_age_seconds")]
+	pub max_age_seconds: u64,
+	/// Estimated gas the claims of one batch may use together. Batches are
+	/// not limited by gas if not specified.
+	pub max_gas: Option<u64>,
+	/// Gas a claim is estimated to use before any claim along its path was
+	/// calibrated.
+	/// Defaults to 200000 if not specified.
+	#[serde(default = "default_claim_batching_default_claim_gas")]
+	pub default_claim_gas: u64,
+}
+
+/// Returns the default number of claims that fills a batch.
+fn default_claim_batching_max_size() -> usize {
+	10
+}
+
+/// Returns the default longest wait of a batched claim in seconds.
+fn default_claim_batching_max_age_seconds() -> u64 {
+	30
+}
+
+/// Returns the default gas estimate of an uncalibrated claim.
+fn default_claim_batching_default_claim_gas() -> u64 {
+	200_000
+}
+
+/// Configuration for delegating claims to a third-party claim bot.
+///
+/// Claims are POSTed to the bot with an authorization signed by the solver.
+/// Claims the bot does not accept are submitted locally instead.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClaimDelegationConfig {
+	/// URL delegated claims are POSTed to.
+	pub endpoint: String,
+	/// Bearer token sent to the claim bot, if it requires one.
+	pub auth_token: Option<String>,
+	/// Seconds to wait for the claim bot to respond.
+	/// Defaults to 10 seconds if not specified.
+	#[serde(default = "default_claim_delegation_timeout_seconds")]
+	pub timeout_seconds: u64,
+}
+
+/// Returns the default claim bot request timeout in seconds.
+fn default_claim_delegation_timeout_seconds() -> u64 {
+	10
+}
+
+/// Configuration for claim gas limit calibration.
+///
+/// Claims get a gas limit from a percentile of the gas used by recent claims
+/// along the same settlement path, plus a safety margin. Paths with too few
+/// recorded claims keep the gas limit set by their order standard.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClaimGasConfig {
+	/// Number of most recent claims kept per settlement path.
+	/// Defaults to 100 if not specified.
+	#[serde(default = "default_claim_gas_window")]
+	pub window: usize,
+	/// Percentile of recorded gas usage the limit is based on.
+	/// Defaults to 95 if not specified.
+	#[serde(default = "default_claim_gas_percentile")]
+	pub percentile: f64,
+	/// Safety margin added on top of the percentile in basis points.
+	/// Defaults to 2000 bps (20%) if not specified.
+	#[serde(default = "default_claim_gas_margin_bps")]
+	pub margin_bps: u64,
+	/// Claims that must be recorded along a path before it is calibrated.
+	/// Defaults to 10 if not specified.
+	#[serde(default = "default_claim_gas_min_samples")]
+	pub min_samples: usize,
+}
+
+/// Returns the default number of claims kept per settlement path.
+fn default_claim_gas_window() -> usize {
+	100
+}
+
+/// Returns the default percentile of recorded claim gas usage.
+fn default_claim_gas_percentile() -> f64 {
+	95.0
+}
+
+/// Returns the default claim gas safety margin in basis points.
+fn default_claim_gas_margin_bps() -> u64 {
+	2000
+}
+
+/// Returns the default number of claims required before calibration.
+fn default_claim_gas_min_samples() -> usize {
+	10
+}
+
+/// Returns the default number of fill proof regenerations per order.
+fn default_max_proof_regenerations() -> u32 {
+	2
+}
+
+impl SettlementConfig {
+	/// Returns the configured claim recipients keyed by numeric chain ID.
+	///
+	/// Entries with a chain ID that cannot be parsed are skipped; they are
+	/// rejected during configuration validation.
+	pub fn claim_recipients_by_chain(&self) -> HashMap<u64, String> {
+		self.claim_recipients
+			.iter()
+			.filter_map(|(chain_id, address)| {
+				chain_id.parse::<u64>().ok().map(|id| (id, address.clone()))
+			})
+			.collect()
+	}
+}
+
+/// Configuration for liquidity depth checks on received tokens.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LiquidityConfig {
+	/// Maximum acceptable price impact in basis points when re-swapping received tokens.
+	/// Defaults to 100 bps (1%) if not specified.
+	#[serde(default = "default_max_price_impact_bps")]
+	pub max_price_impact_bps: u64,
+	/// Map of chain IDs to token addresses liquid enough to skip depth checks.
+	#[serde(default)]
+	pub liquid_tokens: HashMap<String, Vec<String>>,
+	/// Map of liquidity source names to their configurations.
+	/// Each source names its `implementation` (e.g. "uniswap_v2") and has that
+	/// implementation's configuration format, stored as raw TOML values.
+	pub sources: HashMap<String, toml::Value>,
+}
+
+/// Returns the default maximum price impact in basis points.
+fn default_max_price_impact_bps() -> u64 {
+	100 // Default to 1%
+}
+
+/// Configuration for the risk validation pipeline.
+///
+/// Validated orders go through every configured validator before the
+/// execution strategy decides them. Orders breaking a limit are rejected,
+/// others are scored by how close they come to the limits.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ValidationConfig {
+	/// Map of validator names (e.g. "order_size") to their configurations.
+	/// Each validator has its own configuration format stored as raw TOML values.
+	pub validators: HashMap<String, toml::Value>,
+}
+
+/// Metadata for a single chain.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NetworkConfig {
+	/// Native token gas is paid in. Defaults to ETH if not specified.
+	pub gas_token: Option<GasTokenConfig>,
+	/// Base URL of a block explorer used to link transactions (e.g.
+	/// "https://polygonscan.com").
+	pub explorer_url: Option<String>,
+	/// Average seconds between blocks, used to compare how fast fills on
+	/// different chains are confirmed. Defaults to 12 seconds if not specified.
+	pub block_time_seconds: Option<f64>,
+	/// Seconds between checks of pending transactions. Defaults to the block
+	/// time, but at least 1 second, if not specified.
+	pub poll_interval_seconds: Option<f64>,
+	/// Longest seconds between checks of a transaction pending while the
+	/// chain stays on the same block. Defaults to 8 times the poll interval
+	/// if not specified.
+	pub max_poll_interval_seconds: Option<f64>,
+}
+
+/// Native gas token of a chain.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GasTokenConfig {
+	/// Ticker symbol of the token (e.g. "POL").
+	pub symbol: String,
+	/// Number of decimals used by the token.
+	/// Defaults to 18 decimals if not specified.
+	#[serde(default = "default_gas_token_decimals")]
+	pub decimals: u8,
+}
+
+/// Returns the default number of gas token decimals.
+fn default_gas_token_decimals() -> u8 {
+	18
+}
+
+/// Configuration for token approvals.
+///
+/// Every token listed for a chain is approved for every settler listed for
+/// the same chain at startup, so the first fill of a token never waits on an
+/// approval. Tokens that were not pre-warmed are approved before the fill
+/// that needs them.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApprovalConfig {
+	/// Map of chain IDs to the tokens the solver spends on that chain.
+	#[serde(default)]
+	pub tokens: HashMap<String, Vec<String>>,
+	/// Map of chain IDs to the settler contracts that pull tokens from the solver.
+	#[serde(default)]
+	pub settlers: HashMap<String, Vec<String>>,
+	/// Amount approved before fills: "max" approves unlimited allowances once,
+	/// "exact" approves only what each fill spends.
+	#[serde(default = "default_approval_mode")]
+	pub mode: String,
+}
+
+/// Returns the default approval mode.
+fn default_approval_mode() -> String {
+	"max".to_string()
+}
+
+/// Configuration for ledger reconciliation.
+///
+/// The ledger is always recorded; when configured, its invariants are checked
+/// and its inventory is compared against on-chain balances periodically.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccountingConfig {
+	/// Interval between reconciliations in seconds.
+	/// Defaults to 300 seconds (5 minutes) if not specified.
+	#[serde(default = "default_reconcile_interval_seconds")]
+	pub reconcile_interval_seconds: u64,
+}
+
+/// Returns the default ledger reconciliation interval in seconds.
+fn default_reconcile_interval_seconds() -> u64 {
+	300
+}
+
+/// Configuration for shutdown checkpoints and crash recovery.
+///
+/// The solver marks the checkpoint file while running and writes a checkpoint
+/// on graceful shutdown, so that a crashed run can be recognized at startup.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RecoveryConfig {
+	/// Path of the checkpoint file.
+	pub checkpoint_file: String,
+	/// Blocks discovery re-scans at startup after a clean shutdown.
+	/// Defaults to 0 if not specified.
+	#[serde(default)]
+	pub backfill_blocks: u64,
+	/// Blocks discovery re-scans at startup after a crash.
+	/// Defaults to 1000 if not specified.
+	#[serde(default = "default_crash_backfill_blocks")]
+	pub crash_backfill_blocks: u64,
+	/// Whether events are journaled as they are published, so that events
+	/// left unhandled by a crash are handled after the restart.
+	/// Defaults to false if not specified.
+	#[serde(default)]
+	pub persistent_events: bool,
+}
+
+/// Returns the default number of blocks re-scanned after a crash.
+fn default_crash_backfill_blocks() -> u64 {
+	1000
+}
+
+/// Configuration for the execution context given to strategies.
+///
+/// Fees, balances and prices are fetched live and reused for a short time.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExecutionContextConfig {
+	/// Seconds fetched values are reused for.
+	/// Defaults to 5 seconds if not specified.
+	#[serde(default = "default_context_cache_ttl_seconds")]
+	pub cache_ttl_seconds: u64,
+	/// Map of chain IDs to maps of token addresses to fixed USD prices per
+	/// whole token. Used unless a price oracle is plugged into the solver.
+	#[serde(default)]
+	pub token_prices_usd: HashMap<String, HashMap<String, f64>>,
+	/// Prices read from Chainlink feeds, taking precedence over fixed prices.
+	/// Disabled if not specified.
+	#[serde(default)]
+	pub chainlink: Option<ChainlinkConfig>,
+}
+
+impl Default for ExecutionContextConfig {
+	fn default() -> Self {
+		Self {
+			cache_ttl_seconds: default_context_cache_ttl_seconds(),
+			token_prices_usd: HashMap::new(),
+			chainlink: None,
+		}
+	}
+}
+
+/// Returns the default execution context cache TTL in seconds.
+fn default_context_cache_ttl_seconds() -> u64 {
+	5
+}
+
+/// Configuration for Chainlink price feeds.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChainlinkConfig {
+	/// Feeds pricing tokens in USD.
+	pub feeds: Vec<ChainlinkFeedConfig>,
+	/// Seconds after its last update that a feed's answer is no longer used.
+	/// Defaults to 3600 seconds if not specified.
+	#[serde(default = "default_chainlink_max_staleness_seconds")]
+	pub max_staleness_seconds: u64,
+}
+
+/// A Chainlink aggregator pricing a token in USD.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChainlinkFeedConfig {
+	/// Chain ID of the priced token.
+	pub chain_id: u64,
+	/// Address of the priced token, the zero address for the gas token.
+	pub token: String,
+	/// Address of the aggregator contract.
+	pub aggregator: String,
+	/// Chain the aggregator is deployed on, if not the token's chain.
+	#[serde(default)]
+	pub feed_chain_id: Option<u64>,
+}
+
+/// Returns the default maximum age of a Chainlink answer in seconds.
+fn default_chainlink_max_staleness_seconds() -> u64 {
+	3600
+}
+
+/// Configuration for chain reorg detection.
+///
+/// The blocks of recent intents and solver transactions are re-checked
+/// periodically; those dropped from the canonical chain are re-validated.
+/// Fills are re-checked during ledger reconciliation after they become
+/// final, so that a reorg deeper than expected is rolled back and alerted on.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReorgConfig {
+	/// Blocks below the head after which inclusion is considered final.
+	/// Defaults to 64 if not specified.
+	#[serde(default = "default_reorg_depth")]
+	pub depth: u64,
+	/// Maximum reorg depth of specific chains keyed by chain ID, overriding
+	/// `depth` for them.
+	#[serde(default)]
+	pub chain_depths: HashMap<String, u64>,
+	/// Interval between reorg checks in seconds.
+	/// Defaults to 12 seconds if not specified.
+	#[serde(default = "default_reorg_check_interval_seconds")]
+	pub check_interval_seconds: u64,
+}
+
+/// Returns the default reorg depth in blocks.
+fn default_reorg_depth() -> u64 {
+	64
+}
+
+/// Returns the default interval between reorg checks in seconds.
+fn default_reorg_check_interval_seconds() -> u64 {
+	12
+}
+
+/// Configuration for the inventory bootstrap assistant.
+///
+/// Used by the `bootstrap` command to size the solver's initial balances from
+/// the corridors it expects to serve.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BootstrapConfig {
+	/// Treasury address that funding transfers are pulled from.
+	/// The treasury must have approved the solver for every funded token.
+	pub treasury: Option<String>,
+	/// Hours of expected volume the initial inventory should cover.
+	/// Defaults to 24 hours if not specified.
+	#[serde(default = "default_coverage_hours")]
+	pub coverage_hours: u64,
+	/// Extra inventory on top of the expected volume, in basis points.
+	/// Defaults to 2000 (20%) if not specified.
+	#[serde(default = "default_buffer_bps")]
+	pub buffer_bps: u32,
+	/// Corridors the solver expects to serve.
+	pub corridors: Vec<CorridorConfig>,
+	/// Map of chain IDs to the gas token balance to hold, in the smallest unit.
+	#[serde(default)]
+	pub gas_reserves: HashMap<String, String>,
+}
+
+/// Returns the default number of hours the initial inventory covers.
+fn default_coverage_hours() -> u64 {
+	24
+}
+
+/// Returns the default inventory buffer in basis points.
+fn default_buffer_bps() -> u32 {
+	2000
+}
+
+/// Expected fill volume for a single corridor.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CorridorConfig {
+	/// Chain the intents originate from.
+	pub origin_chain_id: u64,
+	/// Chain the solver delivers outputs on.
+	pub destination_chain_id: u64,
+	/// Output token the solver delivers on the destination chain.
+	pub token: String,
+	/// Expected output volume per day in the token's smallest unit.
+	pub daily_volume: String,
+}
+
+/// Configuration for the economic preconditions gating readiness.
+///
+/// The solver only reports ready once it holds the configured balances and,
+/// unless disabled, every configured token approval is in place.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReadinessConfig {
+	/// Map of chain IDs to the minimum gas token balance, in the smallest unit.
+	#[serde(default)]
+	pub min_gas_balances: HashMap<String, String>,
+	/// Minimum balances of the tokens the solver delivers.
+	#[serde(default)]
+	pub min_inventory: Vec<InventoryRequirementConfig>,
+	/// Whether the approvals configured under `approvals` must be in place.
+	/// Defaults to true if not specified.
+	#[serde(default = "default_require_approvals")]
+	pub require_approvals: bool,
+	/// Seconds a readiness check is reused for before chains are queried again.
+	/// Defaults to 10 seconds if not specified.
+	#[serde(default = "default_readiness_cache_ttl_seconds")]
+	pub cache_ttl_seconds: u64,
+}
+
+/// Returns whether configured approvals gate readiness by default.
+fn default_require_approvals() -> bool {
+	true
+}
+
+/// Returns the default readiness cache TTL in seconds.
+fn default_readiness_cache_ttl_seconds() -> u64 {
+	10
+}
+
+/// Minimum balance of a token the solver delivers.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InventoryRequirementConfig {
+	/// Chain the token lives on.
+	pub chain_id: u64,
+	/// Token contract address.
+	pub token: String,
+	/// Minimum balance in the token's smallest unit.
+	pub amount: String,
+}
+
+/// Configuration for polling of the solver's token inventory.
+///
+/// Native balances are polled on every chain the solver delivers to, token
+/// balances for the listed tokens.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InventoryConfig {
+	/// Seconds between polls of the solver's balances.
+	/// Defaults to 30 seconds if not specified.
+	#[serde(default = "default_inventory_poll_interval_seconds")]
+	pub poll_interval_seconds: u64,
+	/// Map of chain IDs to the native balance, in the smallest unit, below
+	/// which a warning is logged.
+	#[serde(default)]
+	pub min_native_balances: HashMap<String, String>,
+	/// ERC-20 tokens to track.
+	#[serde(default)]
+	pub tokens: Vec<InventoryTokenConfig>,
+}
+
+/// Returns the default interval between inventory polls in seconds.
+fn default_inventory_poll_interval_seconds() -> u64 {
+	30
+}
+
+/// Configuration for reloading the configuration file at runtime.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReloadConfig {
+	/// Seconds between checks of the file's modification time.
+	/// Defaults to 5 seconds if not specified.
+	#[serde(default = "default_reload_watch_interval_seconds")]
+	pub watch_interval_seconds: u64,
+}
+
+/// Returns the default interval between configuration file checks in seconds.
+fn default_reload_watch_interval_seconds() -> u64 {
+	5
+}
+
+/// Configuration for the gas spend budget and failure circuit breaker.
+///
+/// New fills are paused on a chain whose gas spend within the rolling window
+/// reaches its limit, and on every chain once too many transactions failed
+/// in a row.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GasBudgetConfig {
+	/// Length of the rolling window gas spend is summed over, in seconds.
+	/// Defaults to 3600 seconds if not specified.
+	#[serde(default = "default_gas_budget_window_seconds")]
+	pub window_seconds: u64,
+	/// Map of chain IDs to the gas spend allowed per window, in the smallest
+	/// unit of the chain's gas token. Chains without a limit are not budgeted.
+	#[serde(default)]
+	pub limits: HashMap<String, String>,
+	/// Consecutive failed transactions after which fills are paused.
+	/// Failures never pause fills if not specified.
+	pub max_consecutive_failures: Option<u32>,
+	/// Seconds fills stay paused after too many consecutive failures.
+	/// Defaults to 300 seconds if not specified.
+	#[serde(default = "default_gas_budget_failure_cooldown_seconds")]
+	pub failure_cooldown_seconds: u64,
+}
+
+/// Returns the default gas budget window in seconds.
+fn default_gas_budget_window_seconds() -> u64 {
+	3600
+}
+
+/// Returns the default pause after consecutive failures in seconds.
+fn default_gas_budget_failure_cooldown_seconds() -> u64 {
+	300
+}
+
+/// Configuration for pruning the data of finished orders.
+///
+/// The stored data of completed and failed orders is deleted once they
+/// finished longer ago than their retention period, after being appended to
+/// the archive file if one is configured.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RetentionConfig {
+	/// Days the data of completed orders is kept.
+	/// Defaults to 7 days if not specified.
+	#[serde(default = "default_retention_completed_days")]
+	pub completed_days: u64,
+	/// Days the data of failed orders is kept.
+	/// Defaults to 30 days if not specified.
+	#[serde(default = "default_retention_failed_days")]
+	pub failed_days: u64,
+	/// Seconds between pruning passes.
+	/// Defaults to 3600 seconds if not specified.
+	#[serde(default = "default_retention_prune_interval_seconds")]
+	pub prune_interval_seconds: u64,
+	/// File the data of pruned orders is appended to as JSON lines.
+	/// Pruned data is discarded if not specified.
+	pub archive_path: Option<String>,
+}
+
+/// Configuration for picking the destination chain of orders fillable on
+/// several chains.
+///
+/// Routes the solver holds the outputs for are ranked by the USD cost of
+/// their fill's gas plus the cost of waiting for the fill to be confirmed.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RoutingConfig {
+	/// USD value of a minute of waiting for a fill to be confirmed.
+	/// Defaults to 0.05 if not specified.
+	#[serde(default = "default_routing_usd_per_minute")]
+	pub usd_per_minute: f64,
+}
+
+/// Returns the default USD value of a minute of waiting for confirmations.
+fn default_routing_usd_per_minute() -> f64 {
+	0.05
+}
+
+/// Returns the default retention of completed orders in days.
+fn default_retention_completed_days() -> u64 {
+	7
+}
+
+/// Returns the default retention of failed orders in days.
+fn default_retention_failed_days() -> u64 {
+	30
+}
+
+/// Returns the default interval between pruning passes in seconds.
+fn default_retention_prune_interval_seconds() -> u64 {
+	3600
+}
+
+/// An ERC-20 token tracked by the inventory.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InventoryTokenConfig {
+	/// Chain the token lives on.
+	pub chain_id: u64,
+	/// Token contract address.
+	pub token: String,
+	/// Balance, in the token's smallest unit, below which a warning is logged.
+	pub min_balance: Option<String>,
+}
+
+/// Configuration for automatic re-driving of dead-lettered orders.
+///
+/// Failed orders are always held in the dead-letter queue. Without this
+/// section they are only re-driven by hand.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeadLetterConfig {
+	/// Automatic re-drives per order, after which it is only re-driven by hand.
+	/// Defaults to 3 if not specified.
+	#[serde(default = "default_dead_letter_max_retries")]
+	pub max_retries: u32,
+	/// Seconds between a failure and its automatic re-drive.
+	/// Defaults to 300 seconds if not specified.
+	#[serde(default = "default_dead_letter_retry_after_seconds")]
+	pub retry_after_seconds: u64,
+}
+
+/// Returns the default number of automatic re-drives per order.
+fn default_dead_letter_max_retries() -> u32 {
+	3
+}
+
+/// Returns the default delay before an automatic re-drive in seconds.
+fn default_dead_letter_retry_after_seconds() -> u64 {
+	300
 }
 
 /// Configuration for the HTTP API server.
@@ -156,6 +1299,89 @@ pub struct ApiConfig {
 	pub rate_limiting: Option<RateLimitConfig>,
 	/// CORS configuration.
 	pub cors: Option<CorsConfig>,
+	/// Bearer token required for admin endpoints.
+	///
+	/// Admin endpoints are not served when no token is configured.
+	pub admin_token: Option<String>,
+	/// Pricing of quotes served by the quote endpoint.
+	#[serde(default)]
+	pub quote: QuoteConfig,
+	/// Peer instances aggregated into the cluster view.
+	///
+	/// The cluster endpoint is not served when no peers are configured.
+	pub cluster: Option<ClusterConfig>,
+}
+
+/// Aggregation of peer solver instances, such as one per region or chain
+/// group, into a combined cluster view.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClusterConfig {
+	/// Base URLs of the peers' APIs, such as `http://solver-eu:3000`.
+	pub peers: Vec<String>,
+	/// Timeout of requests to a peer in milliseconds.
+	/// Defaults to 2000 if not specified.
+	#[serde(default = "default_cluster_timeout_ms")]
+	pub timeout_ms: u64,
+}
+
+/// Pricing of quotes served by the API.
+///
+/// Quotes cover the gas of filling on the destination chain and claiming on
+/// the origin chain, plus a margin.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuoteConfig {
+	/// Margin kept on top of execution costs, in basis points of the input
+	/// value. Defaults to 30 if not specified.
+	#[serde(default = "default_quote_margin_bps")]
+	pub margin_bps: u64,
+	/// Seconds a quote remains valid.
+	/// Defaults to 60 seconds if not specified.
+	#[serde(default = "default_quote_validity_seconds")]
+	pub validity_seconds: u64,
+	/// Gas budgeted for a fill on the destination chain.
+	/// Defaults to 200000 if not specified.
+	#[serde(default = "default_quote_gas_limit")]
+	pub fill_gas_limit: u64,
+	/// Gas budgeted for a claim on the origin chain.
+	/// Defaults to 200000 if not specified.
+	#[serde(default = "default_quote_gas_limit")]
+	pub claim_gas_limit: u64,
+	/// Seconds quoted for an order to be filled after it is opened.
+	/// Defaults to 30 seconds if not specified.
+	#[serde(default = "default_quote_eta_seconds")]
+	pub eta_seconds: u64,
+}
+
+impl Default for QuoteConfig {
+	fn default() -> Self {
+		Self {
+			margin_bps: default_quote_margin_bps(),
+			validity_seconds: default_quote_validity_seconds(),
+			fill_gas_limit: default_quote_gas_limit(),
+			claim_gas_limit: default_quote_gas_limit(),
+			eta_seconds: default_quote_eta_seconds(),
+		}
+	}
+}
+
+/// Returns the default quote margin in basis points.
+fn default_quote_margin_bps() -> u64 {
+	30
+}
+
+/// Returns the default quote validity in seconds.
+fn default_quote_validity_seconds() -> u64 {
+	60
+}
+
+/// Returns the default gas budgeted per quoted transaction.
+fn default_quote_gas_limit() -> u64 {
+	200_000
+}
+
+/// Returns the default quoted fill time in seconds.
+fn default_quote_eta_seconds() -> u64 {
+	30
 }
 
 /// Rate limiting configuration.
@@ -165,6 +1391,10 @@ pub struct RateLimitConfig {
 	pub requests_per_minute: u32,
 	/// Burst allowance for requests.
 	pub burst_size: u32,
+	/// Per-minute quotas for clients identified by API key.
+	/// Requests carrying a listed key are limited by key instead of by IP.
+	#[serde(default)]
+	pub api_keys: HashMap<String, u32>,
 }
 
 /// CORS configuration.
@@ -198,14 +1428,39 @@ fn default_max_request_size() -> usize {
 	1024 * 1024 // 1MB
 }
 
+/// Returns the default timeout of requests to cluster peers in milliseconds.
+fn default_cluster_timeout_ms() -> u64 {
+	2000
+}
+
 impl Config {
 	/// Loads configuration from a file at the specified path.
 	///
 	/// This method reads the file content and parses it as TOML configuration.
-	/// The configuration is validated before being returned.
+	/// Environment variables and secrets are resolved with the default
+	/// [`SecretResolver`], and the configuration is validated before being
+	/// returned.
 	pub fn from_file(path: &str) -> Result<Self, ConfigError> {
+		Self::from_file_with_secrets(path, &SecretResolver::default())
+	}
+
+	/// Loads configuration from a file, resolving secrets through `secrets`.
+	pub fn from_file_with_secrets(
+		path: &str,
+		secrets: &SecretResolver,
+	) -> Result<Self, ConfigError> {
 		let content = std::fs::read_to_string(path)?;
-		content.parse()
+		Self::parse_with_secrets(&content, secrets)
+	}
+
+	/// Parses TOML configuration, resolving secrets through `secrets` before
+	/// validating it.
+	pub fn parse_with_secrets(s: &str, secrets: &SecretResolver) -> Result<Self, ConfigError> {
+		let mut value = toml::Value::Table(s.parse::<toml::Table>()?);
+		secrets.resolve_value(&mut value)?;
+		let config = Config::deserialize(value)?;
+		config.validate()?;
+		Ok(config)
 	}
 
 	/// Validates the configuration to ensure all required fields are properly set.
@@ -217,7 +1472,7 @@ impl Config {
 	/// - Verifies account provider is set
 	/// - Ensures at least one discovery source exists
 	/// - Validates order implementations and strategy are configured
-	/// - Checks that settlement implementations are present
+	/// - Checks that settlement implementations are present and claim recipients are well-formed
 	fn validate(&self) -> Result<(), ConfigError> {
 		// Validate solver config
 		if self.solver.id.is_empty() {
@@ -230,6 +1485,21 @@ impl Config {
 				"Storage backend cannot be empty".into(),
 			));
 		}
+		if let Some(compression) = &self.storage.compression {
+			if !(1..=22).contains(&compression.level) {
+				return Err(ConfigError::Validation(
+					"storage.compression.level must be between 1 and 22".into(),
+				));
+			}
+			for (namespace, level) in &compression.namespaces {
+				if !(0..=22).contains(level) {
+					return Err(ConfigError::Validation(format!(
+						"Compression level of namespace {} must be between 0 and 22",
+						namespace
+					)));
+				}
+			}
+		}
 
 		// Validate delivery config
 		if self.delivery.providers.is_empty() {
@@ -249,6 +1519,118 @@ impl Config {
 				"min_confirmations cannot exceed 100".into(),
 			));
 		}
+		if self.delivery.confirmation_timeout_seconds == Some(0) {
+			return Err(ConfigError::Validation(
+				"confirmation_timeout_seconds must be at least 1".into(),
+			));
+		}
+		for (chain, confirmation) in &self.delivery.confirmations {
+			if chain.parse::<u64>().is_err() {
+				return Err(ConfigError::Validation(format!(
+					"Invalid chain ID '{}' in delivery.confirmations",
+					chain
+				)));
+			}
+			if let Some(min_confirmations) = confirmation.min_confirmations {
+				if !(1..=100).contains(&min_confirmations) {
+					return Err(ConfigError::Validation(format!(
+						"min_confirmations for chain {} must be between 1 and 100",
+						chain
+					)));
+				}
+			}
+			if confirmation.timeout_seconds == Some(0) {
+				return Err(ConfigError::Validation(format!(
+					"timeout_seconds for chain {} must be at least 1",
+					chain
+				)));
+			}
+		}
+		if let Some(gas) = &self.delivery.gas {
+			let strategies = std::iter::once(("default", &gas.default)).chain(
+				gas.chains
+					.iter()
+					.map(|(chain, strategy)| (chain.as_str(), strategy)),
+			);
+			for (chain, strategy) in strategies {
+				if chain != "default" && chain.parse::<u64>().is_err() {
+					return Err(ConfigError::Validation(format!(
+						"Invalid chain ID '{}' in delivery.gas.chains",
+						chain
+					)));
+				}
+				if strategy.mode != "legacy" && strategy.mode != "eip1559" {
+					return Err(ConfigError::Validation(format!(
+						"Unknown gas fee mode '{}' for {}",
+						strategy.mode, chain
+					)));
+				}
+				if strategy.fee_history_blocks == 0 || strategy.fee_history_blocks > 1024 {
+					return Err(ConfigError::Validation(format!(
+						"fee_history_blocks for {} must be between 1 and 1024",
+						chain
+					)));
+				}
+				if !(0.0..=100.0).contains(&strategy.priority_fee_percentile) {
+					return Err(ConfigError::Validation(format!(
+						"priority_fee_percentile for {} must be between 0 and 100",
+						chain
+					)));
+				}
+				if strategy.base_fee_multiplier_bps < 10_000 {
+					return Err(ConfigError::Validation(format!(
+						"base_fee_multiplier_bps for {} must be at least 10000",
+						chain
+					)));
+				}
+			}
+		}
+		if let Some(replacement) = &self.delivery.replacement {
+			if replacement.stuck_after_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"delivery.replacement.stuck_after_seconds must be greater than 0".into(),
+				));
+			}
+			if replacement.fee_bump_bps == 0 {
+				return Err(ConfigError::Validation(
+					"delivery.replacement.fee_bump_bps must be greater than 0".into(),
+				));
+			}
+		}
+		if let Some(bundling) = &self.delivery.bundling {
+			if bundling.max_orders < 2 {
+				return Err(ConfigError::Validation(
+					"delivery.bundling.max_orders must be at least 2".into(),
+				));
+			}
+			if bundling.window_ms == 0 {
+				return Err(ConfigError::Validation(
+					"delivery.bundling.window_ms must be greater than 0".into(),
+				));
+			}
+			for (chain_id, tokens) in &bundling.max_output_amounts {
+				if chain_id.parse::<u64>().is_err() {
+					return Err(ConfigError::Validation(format!(
+						"Invalid chain ID '{}' in delivery.bundling.max_output_amounts",
+						chain_id
+					)));
+				}
+				for (token, amount) in tokens {
+					if token.len() != 42 || !token.starts_with("0x") {
+						return Err(ConfigError::Validation(format!(
+							"Invalid address '{}' in delivery.bundling.max_output_amounts for chain {}",
+							token, chain_id
+						)));
+					}
+					if amount.is_empty() || !amount.chars().all(|c| c.is_ascii_digit()) {
+						return Err(ConfigError::Validation(format!(
+							"Invalid maximum bundled amount '{}' for token {} on chain {}",
+							amount, token, chain_id
+						)));
+					}
+				}
+			}
+		}
 
 		// Validate account config
 		if self.account.provider.is_empty() {
@@ -263,6 +1645,35 @@ impl Config {
 				"At least one discovery source required".into(),
 			));
 		}
+		if self.discovery.intake_capacity == 0 {
+			return Err(ConfigError::Validation(
+				"Discovery intake capacity must be positive".into(),
+			));
+		}
+		if !matches!(
+			self.discovery.intake_overflow.as_str(),
+			"drop_oldest" | "reject" | "spill"
+		) {
+			return Err(ConfigError::Validation(format!(
+				"Unknown discovery intake overflow policy '{}'",
+				self.discovery.intake_overflow
+			)));
+		}
+		if let Some(rate) = self.discovery.intake_max_intents_per_second {
+			if !rate.is_finite() || rate <= 0.0 {
+				return Err(ConfigError::Validation(
+					"discovery.intake_max_intents_per_second must be positive".into(),
+				));
+			}
+		}
+		for (source, rate) in &self.discovery.intake_source_rate_limits {
+			if !rate.is_finite() || *rate <= 0.0 {
+				return Err(ConfigError::Validation(format!(
+					"Rate limit of discovery source '{}' must be positive",
+					source
+				)));
+			}
+		}
 
 		// Validate order config
 		if self.order.implementations.is_empty() {
@@ -275,6 +1686,154 @@ impl Config {
 				"Execution strategy type cannot be empty".into(),
 			));
 		}
+		for class in self.order.class_strategies.keys() {
+			if !ORDER_CLASSES.contains(&class.as_str()) {
+				return Err(ConfigError::Validation(format!(
+					"Unknown order class '{}' in order.class_strategies; expected one of: {}",
+					class,
+					ORDER_CLASSES.join(", ")
+				)));
+			}
+		}
+		for (settler, implementation) in &self.order.settler_routes {
+			if settler.len() != 42 || !settler.starts_with("0x") {
+				return Err(ConfigError::Validation(format!(
+					"Invalid settler address '{}' in order.settler_routes",
+					settler
+				)));
+			}
+			if !self.order.implementations.contains_key(implementation) {
+				return Err(ConfigError::Validation(format!(
+					"Settler {} is routed to unknown order implementation '{}'",
+					settler, implementation
+				)));
+			}
+		}
+		if let Some(latency_budget) = &self.order.latency_budget {
+			let budgets = latency_budget
+				.default_ms
+				.iter()
+				.chain(latency_budget.standards.values())
+				.chain(latency_budget.sources.values());
+			for budget in budgets {
+				if *budget == 0 {
+					return Err(ConfigError::Validation(
+						"Latency budgets must be at least 1 ms".into(),
+					));
+				}
+			}
+		}
+		for (chain_id, tokens) in &self.order.fee_on_transfer_tokens {
+			if chain_id.parse::<u64>().is_err() {
+				return Err(ConfigError::Validation(format!(
+					"Invalid chain ID '{}' in order.fee_on_transfer_tokens",
+					chain_id
+				)));
+			}
+			if let Some(token) = tokens
+				.iter()
+				.find(|token| token.len() != 42 || !token.starts_with("0x"))
+			{
+				return Err(ConfigError::Validation(format!(
+					"Invalid address '{}' in order.fee_on_transfer_tokens for chain {}",
+					token, chain_id
+				)));
+			}
+		}
+		if let Some(intake) = &self.order.intake {
+			for (chain_id, tokens) in &intake.min_input_amounts {
+				if chain_id.parse::<u64>().is_err() {
+					return Err(ConfigError::Validation(format!(
+						"Invalid chain ID '{}' in order.intake.min_input_amounts",
+						chain_id
+					)));
+				}
+				for (token, amount) in tokens {
+					if token.len() != 42 || !token.starts_with("0x") {
+						return Err(ConfigError::Validation(format!(
+							"Invalid address '{}' in order.intake.min_input_amounts for chain {}",
+							token, chain_id
+						)));
+					}
+					if amount.is_empty() || !amount.chars().all(|c| c.is_ascii_digit()) {
+						return Err(ConfigError::Validation(format!(
+							"Invalid minimum amount '{}' for token {} on chain {}",
+							amount, token, chain_id
+						)));
+					}
+				}
+			}
+			if intake.max_orders_per_user_per_minute == Some(0) {
+				return Err(ConfigError::Validation(
+					"order.intake.max_orders_per_user_per_minute must be at least 1".into(),
+				));
+			}
+		}
+		if let Some(policy) = &self.order.policy {
+			for (field, tokens) in [
+				("allowed_tokens", &policy.allowed_tokens),
+				("denied_tokens", &policy.denied_tokens),
+			] {
+				for (chain_id, tokens) in tokens {
+					if chain_id.parse::<u64>().is_err() {
+						return Err(ConfigError::Validation(format!(
+							"Invalid chain ID '{}' in order.policy.{}",
+							chain_id, field
+						)));
+					}
+					if let Some(token) = tokens
+						.iter()
+						.find(|token| token.len() != 42 || !token.starts_with("0x"))
+					{
+						return Err(ConfigError::Validation(format!(
+							"Invalid address '{}' in order.policy.{} for chain {}",
+							token, field, chain_id
+						)));
+					}
+				}
+			}
+			if let Some(address) = policy
+				.denied_addresses
+				.iter()
+				.find(|address| address.len() != 42 || !address.starts_with("0x"))
+			{
+				return Err(ConfigError::Validation(format!(
+					"Invalid address '{}' in order.policy.denied_addresses",
+					address
+				)));
+			}
+			if let Some(url) = &policy.remote_url {
+				if !url.starts_with("http://") && !url.starts_with("https://") {
+					return Err(ConfigError::Validation(format!(
+						"order.policy.remote_url {} must start with http:// or https://",
+						url
+					)));
+				}
+			}
+			if policy.refresh_interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"order.policy.refresh_interval_seconds must be greater than 0".into(),
+				));
+			}
+		}
+		if let Some(fast_path) = &self.order.stablecoin_fast_path {
+			for (group, tokens) in &fast_path.groups {
+				for token in tokens {
+					if token.address.len() != 42 || !token.address.starts_with("0x") {
+						return Err(ConfigError::Validation(format!(
+							"Stablecoin group '{}' contains an invalid address: {}",
+							group, token.address
+						)));
+					}
+					if token.decimals > 36 {
+						return Err(ConfigError::Validation(format!(
+							"Stablecoin group '{}' token decimals cannot exceed 36",
+							group
+						)));
+					}
+				}
+			}
+		}
 
 		// Validate settlement config
 		if self.settlement.implementations.is_empty() {
@@ -282,11 +1841,472 @@ impl Config {
 				"At least one settlement implementation required".into(),
 			));
 		}
+		if let Some(claim_gas) = &self.settlement.claim_gas {
+			if claim_gas.min_samples == 0 || claim_gas.window < claim_gas.min_samples {
+				return Err(ConfigError::Validation(
+					"settlement.claim_gas.window must be at least min_samples, which must be at least 1"
+						.into(),
+				));
+			}
+			if !(claim_gas.percentile > 0.0 && claim_gas.percentile <= 100.0) {
+				return Err(ConfigError::Validation(
+					"settlement.claim_gas.percentile must be in (0, 100]".into(),
+				));
+			}
+		}
+		for (chain_id, address) in &self.settlement.claim_recipients {
+			if chain_id.parse::<u64>().is_err() {
+				return Err(ConfigError::Validation(format!(
+					"Invalid chain ID '{}' in settlement.claim_recipients",
+					chain_id
+				)));
+			}
+			if !is_address(address) {
+				return Err(ConfigError::Validation(format!(
+					"Claim recipient for chain {} must be a valid Ethereum address",
+					chain_id
+				)));
+			}
+		}
+		if let Some(schedule) = &self.settlement.claim_schedule {
+			if !schedule.max_gas_price_gwei.is_finite() || schedule.max_gas_price_gwei < 0.0 {
+				return Err(ConfigError::Validation(
+					"settlement.claim_schedule.max_gas_price_gwei must be a non-negative number"
+						.into(),
+				));
+			}
+			for (chain_id, gwei) in &schedule.chain_max_gas_price_gwei {
+				if chain_id.parse::<u64>().is_err() {
+					return Err(ConfigError::Validation(format!(
+						"Invalid chain ID '{}' in settlement.claim_schedule.chain_max_gas_price_gwei",
+						chain_id
+					)));
+				}
+				if !gwei.is_finite() || *gwei < 0.0 {
+					return Err(ConfigError::Validation(format!(
+						"Claim gas price ceiling for chain {} must be a non-negative number",
+						chain_id
+					)));
+				}
+			}
+			if schedule.recheck_interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"settlement.claim_schedule.recheck_interval_seconds must be at least 1".into(),
+				));
+			}
+		}
+		if let Some(batching) = &self.settlement.claim_batching {
+			if batching.max_size == 0 {
+				return Err(ConfigError::Validation(
+					"settlement.claim_batching.max_size must be at least 1".into(),
+				));
+			}
+			if batching.max_age_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"settlement.claim_batching.max_age_seconds must be at least 1".into(),
+				));
+			}
+			if batching.max_gas == Some(0) {
+				return Err(ConfigError::Validation(
+					"settlement.claim_batching.max_gas must be at least 1".into(),
+				));
+			}
+		}
+
+		// Validate liquidity config
+		if let Some(liquidity) = &self.liquidity {
+			if liquidity.max_price_impact_bps > 10_000 {
+				return Err(ConfigError::Validation(
+					"max_price_impact_bps cannot exceed 10000".into(),
+				));
+			}
+			for chain_id in liquidity.liquid_tokens.keys() {
+				if chain_id.parse::<u64>().is_err() {
+					return Err(ConfigError::Validation(format!(
+						"Invalid chain ID '{}' in liquidity.liquid_tokens",
+						chain_id
+					)));
+				}
+			}
+		}
+
+		// Validate risk validation config
+		if let Some(validation) = &self.validation {
+			if validation.validators.is_empty() {
+				return Err(ConfigError::Validation(
+					"At least one validator must be configured in validation.validators".into(),
+				));
+			}
+		}
+
+		// Validate approval config
+		if let Some(approvals) = &self.approvals {
+			if !matches!(approvals.mode.as_str(), "exact" | "max") {
+				return Err(ConfigError::Validation(format!(
+					"Unknown approval mode '{}'",
+					approvals.mode
+				)));
+			}
+			for (section, entries) in [
+				("approvals.tokens", &approvals.tokens),
+				("approvals.settlers", &approvals.settlers),
+			] {
+				for (chain_id, addresses) in entries {
+					if chain_id.parse::<u64>().is_err() {
+						return Err(ConfigError::Validation(format!(
+							"Invalid chain ID '{}' in {}",
+							chain_id, section
+						)));
+					}
+					if let Some(address) = addresses
+						.iter()
+						.find(|address| address.len() != 42 || !address.starts_with("0x"))
+					{
+						return Err(ConfigError::Validation(format!(
+							"Invalid address '{}' in {} for chain {}",
+							address, section, chain_id
+						)));
+					}
+				}
+			}
+		}
+
+		// Validate accounting config
+		if let Some(accounting) = &self.accounting {
+			if accounting.reconcile_interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"accounting.reconcile_interval_seconds must be at least 1".into(),
+				));
+			}
+		}
+
+		// Validate recovery config
+		if let Some(recovery) = &self.recovery {
+			if recovery.checkpoint_file.is_empty() {
+				return Err(ConfigError::Validation(
+					"recovery.checkpoint_file cannot be empty".into(),
+				));
+			}
+			if recovery.crash_backfill_blocks < recovery.backfill_blocks {
+				return Err(ConfigError::Validation(
+					"recovery.crash_backfill_blocks cannot be less than backfill_blocks".into(),
+				));
+			}
+		}
+
+		// Validate execution context config
+		if let Some(context) = &self.execution_context {
+			for (chain_id, tokens) in &context.token_prices_usd {
+				if chain_id.parse::<u64>().is_err() {
+					return Err(ConfigError::Validation(format!(
+						"Invalid chain ID '{}' in execution_context.token_prices_usd",
+						chain_id
+					)));
+				}
+				for (token, price) in tokens {
+					if token.len() != 42 || !token.starts_with("0x") {
+						return Err(ConfigError::Validation(format!(
+							"Invalid address '{}' in execution_context.token_prices_usd for chain {}",
+							token, chain_id
+						)));
+					}
+					if !price.is_finite() || *price < 0.0 {
+						return Err(ConfigError::Validation(format!(
+							"Invalid price {} for token {} on chain {}",
+							price, token, chain_id
+						)));
+					}
+				}
+			}
+
+			if let Some(chainlink) = &context.chainlink {
+				if chainlink.max_staleness_seconds == 0 {
+					return Err(ConfigError::Validation(
+						"execution_context.chainlink.max_staleness_seconds must be greater than 0"
+							.into(),
+					));
+				}
+				for feed in &chainlink.feeds {
+					for address in [&feed.token, &feed.aggregator] {
+						if address.len() != 42 || !address.starts_with("0x") {
+							return Err(ConfigError::Validation(format!(
+								"Invalid address '{}' in execution_context.chainlink feed for chain {}",
+								address, feed.chain_id
+							)));
+						}
+					}
+				}
+			}
+		}
+
+		// Validate reorg config
+		if let Some(reorg) = &self.reorg {
+			if reorg.depth == 0 {
+				return Err(ConfigError::Validation(
+					"reorg.depth must be at least 1".into(),
+				));
+			}
+			if reorg.check_interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"reorg.check_interval_seconds must be at least 1".into(),
+				));
+			}
+			for (chain_id, depth) in &reorg.chain_depths {
+				if chain_id.parse::<u64>().is_err() {
+					return Err(ConfigError::Validation(format!(
+						"Invalid chain ID '{}' in reorg.chain_depths",
+						chain_id
+					)));
+				}
+				if *depth == 0 {
+					return Err(ConfigError::Validation(format!(
+						"reorg.chain_depths.{} must be at least 1",
+						chain_id
+					)));
+				}
+			}
+		}
+
+		// Validate bootstrap config
+		if let Some(bootstrap) = &self.bootstrap {
+			let is_amount =
+				|amount: &str| !amount.is_empty() && amount.chars().all(|c| c.is_ascii_digit());
+
+			if bootstrap.coverage_hours == 0 {
+				return Err(ConfigError::Validation(
+					"bootstrap.coverage_hours must be at least 1".into(),
+				));
+			}
+			if let Some(treasury) = &bootstrap.treasury {
+				if !is_address(treasury) {
+					return Err(ConfigError::Validation(format!(
+						"Invalid bootstrap treasury address '{}'",
+						treasury
+					)));
+				}
+			}
+			for corridor in &bootstrap.corridors {
+				if !is_address(&corridor.token) {
+					return Err(ConfigError::Validation(format!(
+						"Invalid token address '{}' in bootstrap corridor {} -> {}",
+						corridor.token, corridor.origin_chain_id, corridor.destination_chain_id
+					)));
+				}
+				if !is_amount(&corridor.daily_volume) {
+					return Err(ConfigError::Validation(format!(
+						"Invalid daily_volume '{}' in bootstrap corridor {} -> {}",
+						corridor.daily_volume,
+						corridor.origin_chain_id,
+						corridor.destination_chain_id
+					)));
+				}
+			}
+			for (chain_id, reserve) in &bootstrap.gas_reserves {
+				if chain_id.parse::<u64>().is_err() {
+					return Err(ConfigError::Validation(format!(
+						"Invalid chain ID '{}' in bootstrap.gas_reserves",
+						chain_id
+					)));
+				}
+				if !is_amount(reserve) {
+					return Err(ConfigError::Validation(format!(
+						"Invalid gas reserve '{}' for chain {}",
+						reserve, chain_id
+					)));
+				}
+			}
+		}
+
+		// Validate network config
+		for (chain_id, network) in &self.networks {
+			if chain_id.parse::<u64>().is_err() {
+				return Err(ConfigError::Validation(format!(
+					"Invalid chain ID '{}' in networks",
+					chain_id
+				)));
+			}
+			if let Some(gas_token) = &network.gas_token {
+				if gas_token.symbol.is_empty() {
+					return Err(ConfigError::Validation(format!(
+						"Gas token symbol for chain {} cannot be empty",
+						chain_id
+					)));
+				}
+				if gas_token.decimals > 36 {
+					return Err(ConfigError::Validation(format!(
+						"Gas token decimals for chain {} cannot exceed 36",
+						chain_id
+					)));
+				}
+			}
+		}
+
+		// Validate API rate limiting config
+		if let Some(rate_limiting) = self.api.as_ref().and_then(|api| api.rate_limiting.as_ref()) {
+			if rate_limiting.requests_per_minute == 0 {
+				return Err(ConfigError::Validation(
+					"requests_per_minute must be at least 1".into(),
+				));
+			}
+			if rate_limiting.api_keys.keys().any(|key| key.is_empty()) {
+				return Err(ConfigError::Validation(
+					"Rate limiting API keys cannot be empty".into(),
+				));
+			}
+		}
+
+		// Validate API admin token
+		if let Some(admin_token) = self.api.as_ref().and_then(|api| api.admin_token.as_ref()) {
+			if admin_token.is_empty() {
+				return Err(ConfigError::Validation(
+					"API admin token cannot be empty".into(),
+				));
+			}
+		}
+
+		// Validate inventory polling
+		if let Some(inventory) = &self.inventory {
+			if inventory.poll_interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"Inventory poll_interval_seconds must be at least 1".into(),
+				));
+			}
+			if let Some(token) = inventory
+				.tokens
+				.iter()
+				.find(|token| token.token.len() != 42 || !token.token.starts_with("0x"))
+			{
+				return Err(ConfigError::Validation(format!(
+					"Inventory token {} must be a valid Ethereum address",
+					token.token
+				)));
+			}
+		}
+
+		// Validate configuration reloading
+		if let Some(reload) = &self.reload {
+			if reload.watch_interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"Reload watch_interval_seconds must be at least 1".into(),
+				));
+			}
+		}
+
+		// Validate the gas budget
+		if let Some(gas_budget) = &self.gas_budget {
+			if gas_budget.window_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"Gas budget window_seconds must be at least 1".into(),
+				));
+			}
+			if gas_budget.failure_cooldown_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"Gas budget failure_cooldown_seconds must be at least 1".into(),
+				));
+			}
+			if gas_budget.max_consecutive_failures == Some(0) {
+				return Err(ConfigError::Validation(
+					"Gas budget max_consecutive_failures must be at least 1".into(),
+				));
+			}
+			if let Some(chain_id) = gas_budget
+				.limits
+				.keys()
+				.find(|chain_id| chain_id.parse::<u64>().is_err())
+			{
+				return Err(ConfigError::Validation(format!(
+					"Gas budget chain ID {} must be a number",
+					chain_id
+				)));
+			}
+		}
+
+		// Validate the retention of finished orders
+		if let Some(retention) = &self.retention {
+			if retention.prune_interval_seconds == 0 {
+				return Err(ConfigError::Validation(
+					"Retention prune_interval_seconds must be at least 1".into(),
+				));
+			}
+			if retention.archive_path.as_deref() == Some("") {
+				return Err(ConfigError::Validation(
+					"Retention archive_path cannot be empty".into(),
+				));
+			}
+		}
+
+		// Validate routing
+		if let Some(routing) = &self.routing {
+			if !routing.usd_per_minute.is_finite() || routing.usd_per_minute < 0.0 {
+				return Err(ConfigError::Validation(
+					"Routing usd_per_minute must be a non-negative number".into(),
+				));
+			}
+		}
+		for (chain_id, network) in &self.networks {
+			for (field, seconds) in [
+				("block_time_seconds", network.block_time_seconds),
+				("poll_interval_seconds", network.poll_interval_seconds),
+				(
+					"max_poll_interval_seconds",
+					network.max_poll_interval_seconds,
+				),
+			] {
+				if seconds.is_some_and(|seconds| !seconds.is_finite() || seconds <= 0.0) {
+					return Err(ConfigError::Validation(format!(
+						"Network {} {} must be positive",
+						chain_id, field
+					)));
+				}
+			}
+			if let (Some(poll), Some(max_poll)) = (
+				network.poll_interval_seconds,
+				network.max_poll_interval_seconds,
+			) {
+				if max_poll < poll {
+					return Err(ConfigError::Validation(format!(
+						"Network {} max_poll_interval_seconds must be at least poll_interval_seconds",
+						chain_id
+					)));
+				}
+			}
+		}
+
+		// Validate cluster peers
+		if let Some(cluster) = self.api.as_ref().and_then(|api| api.cluster.as_ref()) {
+			if cluster.peers.is_empty() {
+				return Err(ConfigError::Validation(
+					"Cluster must list at least one peer".into(),
+				));
+			}
+			if let Some(peer) = cluster
+				.peers
+				.iter()
+				.find(|peer| !peer.starts_with("http://") && !peer.starts_with("https://"))
+			{
+				return Err(ConfigError::Validation(format!(
+					"Cluster peer {} must start with http:// or https://",
+					peer
+				)));
+			}
+			if cluster.timeout_ms == 0 {
+				return Err(ConfigError::Validation(
+					"Cluster timeout_ms must be at least 1".into(),
+				));
+			}
+		}
 
 		Ok(())
 	}
 }
 
+/// Returns whether a string is a 0x-prefixed, 20-byte hex address.
+fn is_address(address: &str) -> bool {
+	address.len() == 42
+		&& address.starts_with("0x")
+		&& address[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
 /// Implementation of FromStr trait for Config to enable parsing from string.
 ///
 /// This allows configuration to be parsed from TOML strings using the standard
@@ -295,8 +2315,6 @@ impl FromStr for Config {
 	type Err = ConfigError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let config: Config = toml::from_str(s)?;
-		config.validate()?;
-		Ok(config)
+		Self::parse_with_secrets(s, &SecretResolver::default())
 	}
 }
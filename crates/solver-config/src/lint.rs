@@ -0,0 +1,170 @@
+//! Warnings for risky but valid configurations.
+//!
+//! Validation rejects configurations the solver cannot run with. Some valid
+//! configurations are still likely to lose funds or fail under load, such as
+//! acting on single-block confirmations on Ethereum mainnet. Linting reports
+//! these so operators can review them before going live; they never stop the
+//! solver from starting.
+
+use crate::Config;
+use std::fmt;
+
+/// Chain ID of Ethereum mainnet.
+const ETHEREUM_CHAIN_ID: i64 = 1;
+
+/// A risky setting found while linting a configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+	/// Stable identifier of the check that raised the warning.
+	pub code: &'static str,
+	/// Configuration path the warning applies to.
+	pub path: String,
+	/// Explanation of the risk.
+	pub message: String,
+}
+
+impl fmt::Display for ConfigWarning {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "[{}] {}: {}", self.code, self.path, self.message)
+	}
+}
+
+impl Config {
+	/// Returns warnings for risky settings in the configuration.
+	///
+	/// Checks for:
+	/// - Single-block confirmations on Ethereum mainnet
+	/// - Gas spending that no ceiling bounds
+	/// - A publicly bound API without rate limiting
+	/// - Chains served by a single RPC endpoint
+	pub fn lint(&self) -> Vec<ConfigWarning> {
+		let mut warnings = Vec::new();
+		self.lint_confirmations(&mut warnings);
+		self.lint_gas_ceilings(&mut warnings);
+		self.lint_api(&mut warnings);
+		self.lint_rpc_redundancy(&mut warnings);
+		warnings
+	}
+
+	/// Warns about acting on shallow confirmations on Ethereum mainnet.
+	fn lint_confirmations(&self, warnings: &mut Vec<ConfigWarning>) {
+		let confirmations = self
+			.delivery
+			.min_confirmations_for(ETHEREUM_CHAIN_ID as u64);
+		if confirmations > 1 {
+			return;
+		}
+		let on_mainnet = self.delivery.providers.values().any(|provider| {
+			provider.get("chain_id").and_then(|v| v.as_integer()) == Some(ETHEREUM_CHAIN_ID)
+		});
+		if on_mainnet {
+			let overridden = self
+				.delivery
+				.confirmations
+				.get(&ETHEREUM_CHAIN_ID.to_string())
+				.is_some_and(|confirmation| confirmation.min_confirmations.is_some());
+			let path = if overridden {
+				format!(
+					"delivery.confirmations.{}.min_confirmations",
+					ETHEREUM_CHAIN_ID
+				)
+			} else {
+				"delivery.min_confirmations".to_string()
+			};
+			warnings.push(ConfigWarning {
+				code: "shallow-confirmations",
+				path,
+				message: format!(
+					"{} confirmation on Ethereum mainnet; fills and claims may be reorged out",
+					confirmations
+				),
+			});
+		}
+	}
+
+	/// Warns about gas spending not bounded by a ceiling.
+	fn lint_gas_ceilings(&self, warnings: &mut Vec<ConfigWarning>) {
+		let strategies = std::iter::once((
+			"order.execution_strategy".to_string(),
+			&self.order.execution_strategy,
+		))
+		.chain(
+			self.order
+				.class_strategies
+				.iter()
+				.map(|(class, strategy)| (format!("order.class_strategies.{}", class), strategy)),
+		);
+		for (path, strategy) in strategies {
+			let has_urgency = strategy
+				.config
+				.get("urgency_curve")
+				.and_then(|v| v.as_array())
+				.is_some_and(|curve| !curve.is_empty());
+			if has_urgency {
+				warnings.push(ConfigWarning {
+					code: "unbounded-gas",
+					path: format!("{}.config.urgency_curve", path),
+					message: "urgent fills ignore max_gas_price_gwei and pay any gas price"
+						.to_string(),
+				});
+			}
+		}
+
+		if let Some(replacement) = &self.delivery.replacement {
+			let total_bump_bps = replacement
+				.fee_bump_bps
+				.saturating_mul(u64::from(replacement.max_replacements));
+			if total_bump_bps >= 10_000 {
+				warnings.push(ConfigWarning {
+					code: "unbounded-gas",
+					path: "delivery.replacement".to_string(),
+					message: format!(
+						"{} replacements of +{} bps can more than double the fees of a stuck transaction",
+						replacement.max_replacements, replacement.fee_bump_bps
+					),
+				});
+			}
+		}
+	}
+
+	/// Warns about a publicly reachable API without rate limiting.
+	fn lint_api(&self, warnings: &mut Vec<ConfigWarning>) {
+		let Some(api) = self.api.as_ref().filter(|api| api.enabled) else {
+			return;
+		};
+		let loopback = api.host == "localhost"
+			|| api
+				.host
+				.parse::<std::net::IpAddr>()
+				.is_ok_and(|ip| ip.is_loopback());
+		if !loopback && api.rate_limiting.is_none() {
+			warnings.push(ConfigWarning {
+				code: "no-rate-limit",
+				path: "api.rate_limiting".to_string(),
+				message: format!(
+					"API is bound to {} without rate limiting; quote and order endpoints can be flooded",
+					api.host
+				),
+			});
+		}
+	}
+
+	/// Warns about chains without a fallback RPC endpoint.
+	fn lint_rpc_redundancy(&self, warnings: &mut Vec<ConfigWarning>) {
+		let mut providers: Vec<_> = self.delivery.providers.iter().collect();
+		providers.sort_by(|a, b| a.0.cmp(b.0));
+		for (name, provider) in providers {
+			let has_fallback = provider
+				.get("fallback_rpc_urls")
+				.and_then(|v| v.as_array())
+				.is_some_and(|urls| !urls.is_empty());
+			if !has_fallback {
+				warnings.push(ConfigWarning {
+					code: "single-rpc",
+					path: format!("delivery.providers.{}", name),
+					message: "only one RPC endpoint; the chain stalls if it goes down".to_string(),
+				});
+			}
+		}
+	}
+}
@@ -0,0 +1,88 @@
+//! Comparison of configurations for reloading at runtime.
+//!
+//! Most settings are wired into long-lived services when the solver starts,
+//! so changing them requires a restart. A few sections are read again by the
+//! solver on every use and can be swapped while it runs. Reloading compares
+//! the new configuration with the running one setting by setting, and is
+//! refused as a whole if anything outside those sections changed.
+
+use crate::{Config, ConfigError};
+use std::collections::BTreeSet;
+
+/// Sections that can change while the solver runs.
+pub const RELOADABLE_SECTIONS: &[&str] = &[
+	"solver.log_level",
+	"order.execution_strategy",
+	"order.class_strategies",
+	"api.quote",
+	"api.rate_limiting",
+];
+
+impl Config {
+	/// Returns the reloadable sections that differ in `new`.
+	///
+	/// Fails, listing the offending settings, if any setting outside the
+	/// reloadable sections differs.
+	pub fn reload_changes(&self, new: &Config) -> Result<Vec<String>, ConfigError> {
+		let to_value = |config: &Config| {
+			toml::Value::try_from(config).map_err(|e| {
+				ConfigError::Validation(format!("Cannot compare configurations: {}", e))
+			})
+		};
+		let mut changes = Vec::new();
+		changed_paths(
+			"",
+			Some(&to_value(self)?),
+			Some(&to_value(new)?),
+			&mut changes,
+		);
+
+		let mut sections = BTreeSet::new();
+		let mut rejected = Vec::new();
+		for path in changes {
+			match RELOADABLE_SECTIONS
+				.iter()
+				.find(|section| path == **section || path.starts_with(&format!("{}.", section)))
+			{
+				Some(section) => {
+					sections.insert(section.to_string());
+				}
+				None => rejected.push(path),
+			}
+		}
+
+		if !rejected.is_empty() {
+			return Err(ConfigError::Validation(format!(
+				"Cannot reload changes to [{}] without a restart",
+				rejected.join(", ")
+			)));
+		}
+		Ok(sections.into_iter().collect())
+	}
+}
+
+/// Collects the paths of the settings that differ between two values.
+///
+/// Tables are compared key by key, anything else as a whole.
+fn changed_paths(
+	prefix: &str,
+	old: Option<&toml::Value>,
+	new: Option<&toml::Value>,
+	changes: &mut Vec<String>,
+) {
+	match (old, new) {
+		(Some(toml::Value::Table(old)), Some(toml::Value::Table(new))) => {
+			let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+			for key in keys {
+				let path = if prefix.is_empty() {
+					key.clone()
+				} else {
+					format!("{}.{}", prefix, key)
+				};
+				changed_paths(&path, old.get(key), new.get(key), changes);
+			}
+		}
+		(old, new) if old != new => changes.push(prefix.to_string()),
+		_ => {}
+	}
+}
@@ -0,0 +1,222 @@
+//! Resolution of environment variables and secrets in configuration values.
+//!
+//! Private keys and RPC URLs should not be committed alongside the rest of the
+//! configuration. Any string value may reference environment variables as
+//! `${NAME}`, which are substituted when the file is loaded. A string value of
+//! the form `secret://<provider>/<reference>` is replaced as a whole by the
+//! secret the named provider returns for the reference:
+//!
+//! - `secret://env/NAME` reads the environment variable `NAME`.
+//! - `secret://file/run/secrets/key` reads the file `/run/secrets/key`.
+//! - `secret://vault/secret/data/solver#private_key` reads the `private_key`
+//!   field of a Vault secret, if `VAULT_ADDR` and `VAULT_TOKEN` are set.
+//!
+//! Values are resolved before the configuration is deserialized, so resolved
+//! secrets are validated like any other setting.
+
+use crate::ConfigError;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Prefix of values resolved through a secret provider.
+const SECRET_SCHEME: &str = "secret://";
+
+/// Source of secrets referenced from the configuration.
+pub trait SecretProvider: Send + Sync {
+	/// Returns the secret identified by `reference`, the part of the URI
+	/// following the provider name.
+	fn resolve(&self, reference: &str) -> Result<String, ConfigError>;
+}
+
+/// Provider reading secrets from environment variables.
+pub struct EnvSecretProvider;
+
+impl SecretProvider for EnvSecretProvider {
+	fn resolve(&self, reference: &str) -> Result<String, ConfigError> {
+		std::env::var(reference).map_err(|_| {
+			ConfigError::Secret(format!("Environment variable {} is not set", reference))
+		})
+	}
+}
+
+/// Provider reading secrets from files, such as mounted container secrets.
+///
+/// References are absolute paths without their leading slash. Trailing
+/// whitespace is trimmed from the file contents.
+pub struct FileSecretProvider;
+
+impl SecretProvider for FileSecretProvider {
+	fn resolve(&self, reference: &str) -> Result<String, ConfigError> {
+		let path = PathBuf::from("/").join(reference);
+		std::fs::read_to_string(&path)
+			.map(|secret| secret.trim_end().to_string())
+			.map_err(|e| ConfigError::Secret(format!("Failed to read {}: {}", path.display(), e)))
+	}
+}
+
+/// Provider reading secrets from a HashiCorp Vault server.
+///
+/// References name the secret's API path and field as `<path>#<field>`, e.g.
+/// `secret/data/solver#private_key` for a KV version 2 mount.
+pub struct VaultSecretProvider {
+	/// Base URL of the Vault server.
+	address: String,
+	/// Token authenticating the solver.
+	token: String,
+}
+
+impl VaultSecretProvider {
+	/// Creates a provider reading from the Vault server at `address`.
+	pub fn new(address: impl Into<String>, token: impl Into<String>) -> Self {
+		Self {
+			address: address.into(),
+			token: token.into(),
+		}
+	}
+
+	/// Creates a provider from `VAULT_ADDR` and `VAULT_TOKEN`, if both are set.
+	pub fn from_env() -> Option<Self> {
+		Some(Self::new(
+			std::env::var("VAULT_ADDR").ok()?,
+			std::env::var("VAULT_TOKEN").ok()?,
+		))
+	}
+
+	/// Fetches the data of the secret at `path`.
+	fn fetch(&self, path: &str) -> Result<serde_json::Value, ConfigError> {
+		let url = format!("{}/v1/{}", self.address.trim_end_matches('/'), path);
+		let response = reqwest::blocking::Client::new()
+			.get(&url)
+			.header("X-Vault-Token", &self.token)
+			.send()
+			.and_then(|response| response.error_for_status())
+			.and_then(|response| response.json::<serde_json::Value>())
+			.map_err(|e| {
+				ConfigError::Secret(format!("Failed to read {} from Vault: {}", path, e))
+			})?;
+		Ok(response["data"].clone())
+	}
+}
+
+impl SecretProvider for VaultSecretProvider {
+	fn resolve(&self, reference: &str) -> Result<String, ConfigError> {
+		let (path, field) = reference.split_once('#').ok_or_else(|| {
+			ConfigError::Secret(format!(
+				"Vault secret {} must name a field as <path>#<field>",
+				reference
+			))
+		})?;
+
+		// The blocking client cannot run on an async runtime's thread, and
+		// configuration is loaded from within one
+		let data = std::thread::scope(|scope| {
+			scope
+				.spawn(|| self.fetch(path))
+				.join()
+				.unwrap_or_else(|_| Err(ConfigError::Secret("Vault request panicked".into())))
+		})?;
+
+		// KV version 2 nests the fields one level deeper than version 1
+		data["data"][field]
+			.as_str()
+			.or_else(|| data[field].as_str())
+			.map(|secret| secret.to_string())
+			.ok_or_else(|| {
+				ConfigError::Secret(format!("Vault secret {} has no field {}", path, field))
+			})
+	}
+}
+
+/// Resolves environment variables and secret URIs in configuration values.
+pub struct SecretResolver {
+	/// Providers keyed by the name used in secret URIs.
+	providers: HashMap<String, Box<dyn SecretProvider>>,
+}
+
+impl SecretResolver {
+	/// Creates a resolver without providers.
+	pub fn new() -> Self {
+		Self {
+			providers: HashMap::new(),
+		}
+	}
+
+	/// Resolves `secret://<name>/...` URIs through `provider`.
+	pub fn with_provider(
+		mut self,
+		name: impl Into<String>,
+		provider: impl SecretProvider + 'static,
+	) -> Self {
+		self.providers.insert(name.into(), Box::new(provider));
+		self
+	}
+
+	/// Resolves every string within `value` in place.
+	pub fn resolve_value(&self, value: &mut toml::Value) -> Result<(), ConfigError> {
+		match value {
+			toml::Value::String(s) => *s = self.resolve(s)?,
+			toml::Value::Array(values) => {
+				for value in values {
+					self.resolve_value(value)?;
+				}
+			}
+			toml::Value::Table(table) => {
+				for (_, value) in table.iter_mut() {
+					self.resolve_value(value)?;
+				}
+			}
+			_ => {}
+		}
+		Ok(())
+	}
+
+	/// Resolves a single string, interpolating environment variables before
+	/// looking up a secret URI.
+	fn resolve(&self, value: &str) -> Result<String, ConfigError> {
+		let value = interpolate(value)?;
+		let Some(uri) = value.strip_prefix(SECRET_SCHEME) else {
+			return Ok(value);
+		};
+
+		let (name, reference) = uri.split_once('/').unwrap_or((uri, ""));
+		let provider = self
+			.providers
+			.get(name)
+			.ok_or_else(|| ConfigError::Secret(format!("No secret provider named '{}'", name)))?;
+		provider.resolve(reference)
+	}
+}
+
+impl Default for SecretResolver {
+	/// Creates a resolver with the environment and file providers, and the
+	/// Vault provider if Vault is configured in the environment.
+	fn default() -> Self {
+		let resolver = Self::new()
+			.with_provider("env", EnvSecretProvider)
+			.with_provider("file", FileSecretProvider);
+		match VaultSecretProvider::from_env() {
+			Some(vault) => resolver.with_provider("vault", vault),
+			None => resolver,
+		}
+	}
+}
+
+/// Substitutes `${NAME}` references with the values of environment variables.
+fn interpolate(value: &str) -> Result<String, ConfigError> {
+	let mut result = String::with_capacity(value.len());
+	let mut rest = value;
+	while let Some(start) = rest.find("${") {
+		result.push_str(&rest[..start]);
+		let end = rest[start..].find('}').ok_or_else(|| {
+			ConfigError::Secret(format!("Unterminated variable reference in '{}'", value))
+		})?;
+		let name = &rest[start + 2..start + end];
+		let variable = std::env::var(name).map_err(|_| {
+			ConfigError::Secret(format!("Environment variable {} is not set", name))
+		})?;
+		result.push_str(&variable);
+		rest = &rest[start + end + 1..];
+	}
+	result.push_str(rest);
+	Ok(result)
+}
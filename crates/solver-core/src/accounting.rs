@@ -0,0 +1,462 @@
+//! Double-entry ledger for the solver's finances.
+//!
+//! Every movement of value the solver causes is journaled as a balanced entry:
+//! tokens spent filling orders, tokens received when claiming, and gas paid for
+//! transactions. Entries are persisted in storage and replayed on startup, so
+//! balances per account and asset survive restarts. Because each entry must
+//! balance per asset, the ledger can be audited with a trial balance, and its
+//! inventory accounts can be compared against on-chain balances to detect drift.
+
+use alloy_primitives::{hex, I256, U256};
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{Address, Classify, ErrorCategory, ErrorClass};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// Storage namespace and sequence name for journal entries.
+const LEDGER_NAMESPACE: &str = "ledger";
+
+/// Errors that can occur when recording or auditing the ledger.
+#[derive(Debug, Error)]
+pub enum LedgerError {
+	/// Error that occurs when an entry's debits and credits differ.
+	#[error("Unbalanced entry: {0}")]
+	Unbalanced(String),
+	/// Error that occurs when a ledger invariant does not hold.
+	#[error("Invariant violated: {0}")]
+	InvariantViolated(String),
+	/// Error that occurs when persisting or loading entries.
+	#[error("Storage error: {0}")]
+	Storage(#[from] StorageError),
+}
+
+impl Classify for LedgerError {
+	fn class(&self) -> ErrorClass {
+		match self {
+			Self::Storage(e) => e.class(),
+			Self::Unbalanced(_) | Self::InvariantViolated(_) => {
+				ErrorClass::permanent(ErrorCategory::Internal)
+			}
+		}
+	}
+}
+
+/// Accounts tracked by the ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerAccount {
+	/// Assets held by the solver.
+	Inventory,
+	/// Claimed rewards paid out to a configured treasury.
+	Treasury,
+	/// Balances the solver started with, or external funding and withdrawals.
+	Equity,
+	/// Tokens delivered to users when filling orders.
+	FillCost,
+	/// Tokens received when claiming orders.
+	Revenue,
+	/// Gas paid for transactions.
+	GasFees,
+}
+
+/// An asset on a specific chain.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LedgerAsset {
+	/// Chain the asset lives on.
+	pub chain_id: u64,
+	/// Token contract, or `None` for the chain's gas token.
+	pub token: Option<Address>,
+}
+
+impl fmt::Display for LedgerAsset {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match &self.token {
+			Some(token) => write!(f, "{}:0x{}", self.chain_id, hex::encode(&token.0)),
+			None => write!(f, "{}:gas", self.chain_id),
+		}
+	}
+}
+
+/// Side of a posting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+	/// Increases asset and expense accounts.
+	Debit,
+	/// Increases equity and income accounts.
+	Credit,
+}
+
+/// A single debit or credit to an account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Posting {
+	/// Account being posted to.
+	pub account: LedgerAccount,
+	/// Asset being posted.
+	pub asset: LedgerAsset,
+	/// Whether the posting debits or credits the account.
+	pub side: Side,
+	/// Amount in the asset's smallest unit.
+	pub amount: U256,
+}
+
+impl Posting {
+	/// Creates a debit posting.
+	pub fn debit(account: LedgerAccount, asset: LedgerAsset, amount: U256) -> Self {
+		Self {
+			account,
+			asset,
+			side: Side::Debit,
+			amount,
+		}
+	}
+
+	/// Creates a credit posting.
+	pub fn credit(account: LedgerAccount, asset: LedgerAsset, amount: U256) -> Self {
+		Self {
+			account,
+			asset,
+			side: Side::Credit,
+			amount,
+		}
+	}
+}
+
+/// What caused a journal entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+	/// Opening balance recorded when an asset is first reconciled.
+	Opening,
+	/// A confirmed fill transaction.
+	Fill,
+	/// A confirmed claim transaction.
+	Claim,
+	/// Gas paid for a transaction that reverted.
+	Gas,
+	/// Reversal of entries whose transaction was dropped from the chain.
+	Reversal,
+}
+
+/// A balanced set of postings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+	/// Position of the entry in the journal.
+	pub sequence: u64,
+	/// Unix timestamp at which the entry was recorded.
+	pub timestamp: u64,
+	/// What caused the entry.
+	pub kind: EntryKind,
+	/// Order the entry relates to, if any.
+	pub order_id: Option<String>,
+	/// Postings making up the entry.
+	pub postings: Vec<Posting>,
+	/// Sequences of the entries a reversal undoes.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub reverses: Vec<u64>,
+}
+
+/// Running debit and credit totals of an account.
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+	debits: U256,
+	credits: U256,
+}
+
+impl Totals {
+	/// Returns debits minus credits.
+	fn balance(&self) -> I256 {
+		I256::from_raw(self.debits) - I256::from_raw(self.credits)
+	}
+}
+
+/// Double-entry ledger backed by the storage service.
+pub struct Ledger {
+	/// Storage service persisting journal entries.
+	storage: Arc<StorageService>,
+	/// Running totals keyed by account and asset.
+	totals: Mutex<HashMap<(LedgerAccount, LedgerAsset), Totals>>,
+	/// Assets that have an opening balance.
+	opened: Mutex<HashSet<LedgerAsset>>,
+	/// Kind and sequence of the entries of each order that were not
+	/// reversed, keyed by order ID.
+	order_entries: Mutex<HashMap<String, Vec<(EntryKind, u64)>>>,
+}
+
+impl Ledger {
+	/// Creates an empty ledger.
+	pub fn new(storage: Arc<StorageService>) -> Self {
+		Self {
+			storage,
+			totals: Mutex::new(HashMap::new()),
+			opened: Mutex::new(HashSet::new()),
+			order_entries: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Replays persisted journal entries into the running totals.
+	///
+	/// Returns the number of entries loaded.
+	pub async fn load(&self) -> Result<u64, LedgerError> {
+		let last = self.storage.current_sequence(LEDGER_NAMESPACE).await?;
+		for sequence in 1..=last {
+			let entry: JournalEntry = self
+				.storage
+				.retrieve(LEDGER_NAMESPACE, &sequence.to_string())
+				.await?;
+			self.apply(&entry);
+		}
+		Ok(last)
+	}
+
+	/// Validates, persists, and applies a journal entry.
+	///
+	/// Entries must balance per asset; unbalanced entries are rejected.
+	pub async fn post(
+		&self,
+		kind: EntryKind,
+		order_id: Option<&str>,
+		postings: Vec<Posting>,
+	) -> Result<JournalEntry, LedgerError> {
+		self.record(kind, order_id, postings, Vec::new()).await
+	}
+
+	/// Undoes the entries of the given kind recorded for an order, e.g.
+	/// because the transaction they account for was dropped from the chain.
+	///
+	/// Entries are never removed from the journal; a reversal entry with every
+	/// posting on the opposite side is recorded instead. Returns `None` if the
+	/// order has no such entries that were not reversed already.
+	pub async fn reverse(
+		&self,
+		order_id: &str,
+		kind: EntryKind,
+	) -> Result<Option<JournalEntry>, LedgerError> {
+		let sequences: Vec<u64> = self
+			.order_entries
+			.lock()
+			.unwrap()
+			.get(order_id)
+			.into_iter()
+			.flatten()
+			.filter(|(entry_kind, _)| *entry_kind == kind)
+			.map(|(_, sequence)| *sequence)
+			.collect();
+		if sequences.is_empty() {
+			return Ok(None);
+		}
+
+		let mut postings = Vec::new();
+		for sequence in &sequences {
+			let entry: JournalEntry = self
+				.storage
+				.retrieve(LEDGER_NAMESPACE, &sequence.to_string())
+				.await?;
+			postings.extend(entry.postings.into_iter().map(|posting| Posting {
+				side: match posting.side {
+					Side::Debit => Side::Credit,
+					Side::Credit => Side::Debit,
+				},
+				..posting
+			}));
+		}
+		self.record(EntryKind::Reversal, Some(order_id), postings, sequences)
+			.await
+			.map(Some)
+	}
+
+	/// Returns the entries recorded for an order that were not reversed, in
+	/// journal order.
+	pub async fn order_journal(&self, order_id: &str) -> Result<Vec<JournalEntry>, LedgerError> {
+		let sequences: Vec<u64> = self
+			.order_entries
+			.lock()
+			.unwrap()
+			.get(order_id)
+			.into_iter()
+			.flatten()
+			.map(|(_, sequence)| *sequence)
+			.collect();
+
+		let mut entries = Vec::with_capacity(sequences.len());
+		for sequence in sequences {
+			entries.push(
+				self.storage
+					.retrieve(LEDGER_NAMESPACE, &sequence.to_string())
+					.await?,
+			);
+		}
+		Ok(entries)
+	}
+
+	/// Validates, persists, and applies a journal entry, recording the
+	/// entries it reverses.
+	async fn record(
+		&self,
+		kind: EntryKind,
+		order_id: Option<&str>,
+		postings: Vec<Posting>,
+		reverses: Vec<u64>,
+	) -> Result<JournalEntry, LedgerError> {
+		// Zero postings carry no information, except that an asset was opened
+		let postings: Vec<Posting> = postings
+			.into_iter()
+			.filter(|posting| kind == EntryKind::Opening || !posting.amount.is_zero())
+			.collect();
+		Self::check_entry_balanced(&postings)?;
+
+		let sequence = self.storage.next_sequence(LEDGER_NAMESPACE).await?;
+		let entry = JournalEntry {
+			sequence,
+			timestamp: std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.unwrap()
+				.as_secs(),
+			kind,
+			order_id: order_id.map(str::to_string),
+			postings,
+			reverses,
+		};
+		self.storage
+			.store(LEDGER_NAMESPACE, &sequence.to_string(), &entry)
+			.await?;
+
+		self.apply(&entry);
+		Ok(entry)
+	}
+
+	/// Returns the debit-normal balance of an account for an asset.
+	pub fn balance(&self, account: LedgerAccount, asset: &LedgerAsset) -> I256 {
+		self.totals
+			.lock()
+			.unwrap()
+			.get(&(account, asset.clone()))
+			.map(Totals::balance)
+			.unwrap_or(I256::ZERO)
+	}
+
+	/// Returns every asset the solver has held inventory of.
+	pub fn inventory_assets(&self) -> Vec<LedgerAsset> {
+		self.totals
+			.lock()
+			.unwrap()
+			.keys()
+			.filter(|(account, _)| *account == LedgerAccount::Inventory)
+			.map(|(_, asset)| asset.clone())
+			.collect()
+	}
+
+	/// Returns whether an asset has an opening balance.
+	pub fn is_opened(&self, asset: &LedgerAsset) -> bool {
+		self.opened.lock().unwrap().contains(asset)
+	}
+
+	/// Returns realized profit and loss per asset.
+	///
+	/// Profit is revenue minus fill costs and gas fees. Assets are reported
+	/// separately since the ledger does not price them against each other.
+	pub fn pnl(&self) -> HashMap<LedgerAsset, I256> {
+		let mut pnl: HashMap<LedgerAsset, I256> = HashMap::new();
+		for ((account, asset), totals) in self.totals.lock().unwrap().iter() {
+			if matches!(
+				account,
+				LedgerAccount::Revenue | LedgerAccount::FillCost | LedgerAccount::GasFees
+			) {
+				// Income and expense accounts are credit-normal for profit
+				*pnl.entry(asset.clone()).or_insert(I256::ZERO) -= totals.balance();
+			}
+		}
+		pnl
+	}
+
+	/// Checks the ledger's invariants.
+	///
+	/// Debits and credits must balance per asset across all accounts, and the
+	/// solver cannot hold a negative amount of an asset it has opened.
+	pub fn check_invariants(&self) -> Result<(), LedgerError> {
+		let totals = self.totals.lock().unwrap();
+
+		let mut trial_balance: HashMap<&LedgerAsset, I256> = HashMap::new();
+		for ((_, asset), account_totals) in totals.iter() {
+			*trial_balance.entry(asset).or_insert(I256::ZERO) += account_totals.balance();
+		}
+		if let Some((asset, imbalance)) = trial_balance.iter().find(|(_, sum)| !sum.is_zero()) {
+			return Err(LedgerError::InvariantViolated(format!(
+				"trial balance for {} is off by {}",
+				asset, imbalance
+			)));
+		}
+
+		let opened = self.opened.lock().unwrap();
+		for ((account, asset), account_totals) in totals.iter() {
+			if *account == LedgerAccount::Inventory
+				&& opened.contains(asset)
+				&& account_totals.balance().is_negative()
+			{
+				return Err(LedgerError::InvariantViolated(format!(
+					"inventory of {} is negative: {}",
+					asset,
+					account_totals.balance()
+				)));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Rejects postings whose debits and credits differ for any asset.
+	fn check_entry_balanced(postings: &[Posting]) -> Result<(), LedgerError> {
+		let mut sums: HashMap<&LedgerAsset, Totals> = HashMap::new();
+		for posting in postings {
+			let totals = sums.entry(&posting.asset).or_default();
+			match posting.side {
+				Side::Debit => totals.debits += posting.amount,
+				Side::Credit => totals.credits += posting.amount,
+			}
+		}
+
+		match sums
+			.iter()
+			.find(|(_, totals)| totals.debits != totals.credits)
+		{
+			Some((asset, totals)) => Err(LedgerError::Unbalanced(format!(
+				"{} debits {} but credits {}",
+				asset, totals.debits, totals.credits
+			))),
+			None => Ok(()),
+		}
+	}
+
+	/// Adds an entry's postings to the running totals.
+	fn apply(&self, entry: &JournalEntry) {
+		let mut totals = self.totals.lock().unwrap();
+		for posting in &entry.postings {
+			let account_totals = totals
+				.entry((posting.account, posting.asset.clone()))
+				.or_default();
+			match posting.side {
+				Side::Debit => account_totals.debits += posting.amount,
+				Side::Credit => account_totals.credits += posting.amount,
+			}
+		}
+
+		if entry.kind == EntryKind::Opening {
+			let mut opened = self.opened.lock().unwrap();
+			for posting in &entry.postings {
+				opened.insert(posting.asset.clone());
+			}
+		}
+
+		if let Some(order_id) = &entry.order_id {
+			let mut order_entries = self.order_entries.lock().unwrap();
+			let entries = order_entries.entry(order_id.clone()).or_default();
+			entries.retain(|(_, sequence)| !entry.reverses.contains(sequence));
+			if entry.kind != EntryKind::Reversal {
+				entries.push((entry.kind, entry.sequence));
+			}
+		}
+	}
+}
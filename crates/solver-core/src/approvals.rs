@@ -0,0 +1,272 @@
+//! Token approval management for the solver engine.
+//!
+//! Settlers pull output tokens from the solver with `transferFrom`, which
+//! requires an ERC-20 allowance. This module checks the solver's allowance for
+//! every configured (chain, token, settler) combination and submits unlimited
+//! approvals where they are missing, so fills are never blocked on approvals.
+//!
+//! Fills of tokens that were not pre-warmed have their allowance checked
+//! before submission, and approved for the fill alone or without limit
+//! depending on the approval mode. Known allowances are cached and drawn
+//! down by each fill, so that repeated fills do not query the chain.
+
+use crate::erc20::{token_transaction, IERC20};
+use alloy_primitives::{hex, Address as AlloyAddress, U256};
+use alloy_sol_types::SolCall;
+use solver_delivery::{DeliveryError, DeliveryService};
+use solver_types::{Address, TransactionHash};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Allowance at or above which an approval counts as unlimited.
+pub(crate) const UNLIMITED_ALLOWANCE: U256 = U256::MAX.wrapping_shr(1);
+
+/// A token the solver must approve for a spender on a specific chain.
+#[derive(Debug, Clone)]
+pub struct ApprovalTarget {
+	/// Chain ID the token and spender live on.
+	pub chain_id: u64,
+	/// Token contract address.
+	pub token: Address,
+	/// Contract allowed to spend the solver's tokens.
+	pub spender: Address,
+}
+
+/// Amount approved when a fill finds its allowance short.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalMode {
+	/// Approve exactly what the fill spends.
+	Exact,
+	/// Approve an unlimited allowance.
+	Max,
+}
+
+/// Cache key of an allowance: chain ID, token and spender.
+type AllowanceKey = (u64, Vec<u8>, Vec<u8>);
+
+/// Checks and sets token approvals through the delivery service.
+pub struct ApprovalManager {
+	/// Delivery service used for allowance calls and approval transactions.
+	delivery: Arc<DeliveryService>,
+	/// Token and spender combinations that must be approved.
+	targets: Vec<ApprovalTarget>,
+	/// Amount approved before fills.
+	mode: ApprovalMode,
+	/// Allowances known to be granted, net of the fills drawn from them.
+	allowances: Mutex<HashMap<AllowanceKey, U256>>,
+}
+
+impl ApprovalManager {
+	/// Creates a new ApprovalManager for the given targets.
+	pub fn new(
+		delivery: Arc<DeliveryService>,
+		targets: Vec<ApprovalTarget>,
+		mode: ApprovalMode,
+	) -> Self {
+		Self {
+			delivery,
+			targets,
+			mode,
+			allowances: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the token and spender combinations that must be approved.
+	pub fn targets(&self) -> &[ApprovalTarget] {
+		&self.targets
+	}
+
+	/// Returns the allowance `owner` has granted to the target's spender.
+	pub async fn allowance(
+		&self,
+		owner: &Address,
+		target: &ApprovalTarget,
+	) -> Result<U256, DeliveryError> {
+		let call_data = IERC20::allowanceCall {
+			owner: AlloyAddress::from_slice(&owner.0),
+			spender: AlloyAddress::from_slice(&target.spender.0),
+		}
+		.abi_encode();
+
+		let result = self
+			.delivery
+			.call(&token_transaction(
+				target.chain_id,
+				&target.token,
+				call_data,
+			))
+			.await?;
+
+		let decoded = IERC20::allowanceCall::abi_decode_returns(&result, true)
+			.map_err(|e| DeliveryError::Network(format!("Failed to decode allowance: {}", e)))?;
+		Ok(decoded._0)
+	}
+
+	/// Submits an unlimited approval for the target's spender.
+	pub async fn approve(&self, target: &ApprovalTarget) -> Result<TransactionHash, DeliveryError> {
+		self.approve_amount(target, U256::MAX).await
+	}
+
+	/// Submits an approval of `amount` for the target's spender.
+	async fn approve_amount(
+		&self,
+		target: &ApprovalTarget,
+		amount: U256,
+	) -> Result<TransactionHash, DeliveryError> {
+		let call_data = IERC20::approveCall {
+			spender: AlloyAddress::from_slice(&target.spender.0),
+			amount,
+		}
+		.abi_encode();
+
+		self.delivery
+			.deliver(token_transaction(target.chain_id, &target.token, call_data))
+			.await
+	}
+
+	/// Ensures the target's spender may pull `amount` of the token from
+	/// `owner`, and draws the amount from the cached allowance.
+	///
+	/// The allowance is read from the chain unless the cache covers the
+	/// amount. A short allowance is approved according to the approval mode;
+	/// the approval is submitted from the same account as the fill, so it
+	/// lands first. Returns the hash of the approval, if one was submitted.
+	pub async fn ensure(
+		&self,
+		owner: &Address,
+		target: &ApprovalTarget,
+		amount: U256,
+	) -> Result<Option<TransactionHash>, DeliveryError> {
+		let key = allowance_key(target);
+		if self.draw(&key, amount) {
+			return Ok(None);
+		}
+
+		let allowance = self.allowance(owner, target).await?;
+		self.allowances
+			.lock()
+			.unwrap()
+			.insert(key.clone(), allowance);
+		if self.draw(&key, amount) {
+			return Ok(None);
+		}
+
+		let approved = match self.mode {
+			ApprovalMode::Exact => amount,
+			ApprovalMode::Max => U256::MAX,
+		};
+		let tx_hash = self.approve_amount(target, approved).await?;
+		tracing::info!(
+			chain_id = target.chain_id,
+			token = %hex::encode(&target.token.0),
+			spender = %hex::encode(&target.spender.0),
+			amount = %approved,
+			tx_hash = %hex::encode(&tx_hash.0),
+			"Submitted approval for fill"
+		);
+		self.allowances
+			.lock()
+			.unwrap()
+			.insert(key.clone(), approved);
+		self.draw(&key, amount);
+		Ok(Some(tx_hash))
+	}
+
+	/// Draws `amount` from a cached allowance, returning whether it covered
+	/// the amount.
+	///
+	/// Unlimited allowances are not drawn down, since tokens leave them
+	/// untouched on transfer.
+	fn draw(&self, key: &AllowanceKey, amount: U256) -> bool {
+		let mut allowances = self.allowances.lock().unwrap();
+		match allowances.get_mut(key) {
+			Some(allowance) if *allowance >= UNLIMITED_ALLOWANCE => true,
+			Some(allowance) if *allowance >= amount => {
+				*allowance -= amount;
+				true
+			}
+			_ => false,
+		}
+	}
+
+	/// Returns whether both the target's token and spender are contracts.
+	async fn is_deployed(&self, target: &ApprovalTarget) -> Result<bool, DeliveryError> {
+		Ok(self
+			.delivery
+			.has_code(target.chain_id, &target.token)
+			.await? && self
+			.delivery
+			.has_code(target.chain_id, &target.spender)
+			.await?)
+	}
+
+	/// Ensures every target has an effectively unlimited allowance.
+	///
+	/// Allowances below half of the maximum are topped up. Targets whose token
+	/// or spender has no contract code are skipped. Failures are logged per
+	/// target so one misconfigured token does not block the others.
+	/// Returns the number of approval transactions submitted.
+	pub async fn prewarm(&self, owner: &Address) -> usize {
+		let mut submitted = 0;
+
+		for target in &self.targets {
+			let token = hex::encode(&target.token.0);
+			let spender = hex::encode(&target.spender.0);
+
+			match self.is_deployed(target).await {
+				Ok(true) => {}
+				Ok(false) => {
+					tracing::warn!(chain_id = target.chain_id, token = %token, spender = %spender, "Skipping approval for address without contract code");
+					continue;
+				}
+				Err(e) => {
+					tracing::warn!(chain_id = target.chain_id, token = %token, spender = %spender, error = %e, "Failed to check contract code");
+					continue;
+				}
+			}
+
+			match self.allowance(owner, target).await {
+				Ok(allowance) if allowance >= UNLIMITED_ALLOWANCE => {
+					self.allowances
+						.lock()
+						.unwrap()
+						.insert(allowance_key(target), allowance);
+					tracing::debug!(chain_id = target.chain_id, token = %token, spender = %spender, "Approval already set");
+				}
+				Ok(_) => match self.approve(target).await {
+					Ok(tx_hash) => {
+						submitted += 1;
+						self.allowances
+							.lock()
+							.unwrap()
+							.insert(allowance_key(target), U256::MAX);
+						tracing::info!(
+							chain_id = target.chain_id,
+							token = %token,
+							spender = %spender,
+							tx_hash = %hex::encode(&tx_hash.0),
+							"Submitted approval"
+						);
+					}
+					Err(e) => {
+						tracing::warn!(chain_id = target.chain_id, token = %token, spender = %spender, error = %e, "Failed to submit approval");
+					}
+				},
+				Err(e) => {
+					tracing::warn!(chain_id = target.chain_id, token = %token, spender = %spender, error = %e, "Failed to check allowance");
+				}
+			}
+		}
+
+		submitted
+	}
+}
+
+/// Returns the cache key of a target's allowance.
+fn allowance_key(target: &ApprovalTarget) -> AllowanceKey {
+	(
+		target.chain_id,
+		target.token.0.clone(),
+		target.spender.0.clone(),
+	)
+}
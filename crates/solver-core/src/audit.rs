@@ -0,0 +1,126 @@
+//! Append-only audit log of each order's lifecycle events.
+//!
+//! Disputes and postmortems need to know exactly what the solver did for an
+//! order. Every event the engine observes is appended to the log of each
+//! order it concerns, together with the time it was recorded and the
+//! transaction, gas and error details it carries. Unlike the order status,
+//! entries are never overwritten or removed, and unlike the event journal
+//! they can be read for a single order without replaying every event.
+
+use alloy_primitives::hex;
+use solver_storage::{StorageError, StorageService};
+use solver_types::{
+	AuditEntry, DeliveryEvent, DiscoveryEvent, OrderEvent, SettlementEvent, SolverEvent,
+	TransactionHash,
+};
+use std::sync::Arc;
+
+/// Storage namespace holding audit entries, keyed by order ID and sequence.
+const NAMESPACE: &str = "audit";
+
+/// Storage-backed audit log of order events.
+pub struct AuditLog {
+	/// Storage holding the entries and the per-order sequences.
+	storage: Arc<StorageService>,
+}
+
+impl AuditLog {
+	/// Creates an audit log backed by `storage`.
+	pub fn new(storage: Arc<StorageService>) -> Self {
+		Self { storage }
+	}
+
+	/// Appends an event to the log of each of `order_ids`.
+	pub async fn record(
+		&self,
+		order_ids: &[String],
+		event: &SolverEvent,
+	) -> Result<(), StorageError> {
+		let timestamp = crate::now_secs();
+		let (tx_hash, gas_used, error) = details(event);
+		for order_id in order_ids {
+			let sequence = self.storage.next_sequence(&sequence_name(order_id)).await?;
+			let entry = AuditEntry {
+				sequence,
+				timestamp,
+				kind: crate::metrics::event_kind(event).to_string(),
+				tx_hash: tx_hash.map(|hash| format!("0x{}", hex::encode(&hash.0))),
+				gas_used,
+				error: error.map(|error| error.to_string()),
+				event: event.clone(),
+			};
+			self.storage
+				.store(NAMESPACE, &entry_key(order_id, sequence), &entry)
+				.await?;
+		}
+		Ok(())
+	}
+
+	/// Returns the entries recorded for an order, oldest first.
+	pub async fn history(&self, order_id: &str) -> Result<Vec<AuditEntry>, StorageError> {
+		let last = self
+			.storage
+			.current_sequence(&sequence_name(order_id))
+			.await?;
+
+		let mut entries = Vec::new();
+		for sequence in 1..=last {
+			match self
+				.storage
+				.retrieve(NAMESPACE, &entry_key(order_id, sequence))
+				.await
+			{
+				Ok(entry) => entries.push(entry),
+				Err(StorageError::NotFound) => continue,
+				Err(e) => return Err(e),
+			}
+		}
+		Ok(entries)
+	}
+}
+
+/// Returns the name of the sequence numbering an order's entries.
+fn sequence_name(order_id: &str) -> String {
+	format!("audit:{}", order_id)
+}
+
+/// Returns the storage key of an entry.
+fn entry_key(order_id: &str, sequence: u64) -> String {
+	format!("{}:{}", order_id, sequence)
+}
+
+/// Extracts the transaction hash, gas used and error an event carries.
+fn details(event: &SolverEvent) -> (Option<&TransactionHash>, Option<u64>, Option<&str>) {
+	match event {
+		SolverEvent::Discovery(
+			DiscoveryEvent::IntentRejected { reason, .. }
+			| DiscoveryEvent::IntentInvalidated { reason, .. },
+		) => (None, None, Some(reason.as_str())),
+		SolverEvent::Order(OrderEvent::Skipped { reason, .. }) => {
+			(None, None, Some(reason.as_str()))
+		}
+		SolverEvent::Order(OrderEvent::FilledByOther { .. }) => {
+			(None, None, Some("Filled by another solver"))
+		}
+		SolverEvent::Delivery(
+			DeliveryEvent::TransactionPending { tx_hash, .. }
+			| DeliveryEvent::TransactionReorged { tx_hash, .. },
+		) => (Some(tx_hash), None, None),
+		SolverEvent::Delivery(DeliveryEvent::TransactionReplaced { new_tx_hash, .. }) => {
+			(Some(new_tx_hash), None, None)
+		}
+		SolverEvent::Delivery(DeliveryEvent::TransactionConfirmed {
+			tx_hash, receipt, ..
+		}) => {
+			let error = (!receipt.success).then_some("Transaction reverted");
+			(Some(tx_hash), Some(receipt.gas_used), error)
+		}
+		SolverEvent::Delivery(DeliveryEvent::TransactionFailed { tx_hash, error }) => {
+			(Some(tx_hash), None, Some(error.as_str()))
+		}
+		SolverEvent::Settlement(SettlementEvent::FillDetected { tx_hash, .. }) => {
+			(Some(tx_hash), None, None)
+		}
+		_ => (None, None, None),
+	}
+}
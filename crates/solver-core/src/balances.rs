@@ -0,0 +1,44 @@
+//! Balance reads backing the inventory service.
+
+use crate::erc20;
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_delivery::DeliveryService;
+use solver_liquidity::inventory::BalanceReader;
+use solver_liquidity::LiquidityError;
+use solver_types::Address;
+use std::sync::Arc;
+
+/// Reads balances through the delivery service's chain connections.
+pub(crate) struct DeliveryBalances {
+	/// Delivery service used to query chains.
+	delivery: Arc<DeliveryService>,
+}
+
+impl DeliveryBalances {
+	pub(crate) fn new(delivery: Arc<DeliveryService>) -> Self {
+		Self { delivery }
+	}
+}
+
+#[async_trait]
+impl BalanceReader for DeliveryBalances {
+	async fn native_balance(&self, chain_id: u64, owner: &Address) -> Result<U256, LiquidityError> {
+		self.delivery
+			.gas_balance(chain_id, owner)
+			.await
+			.map(|balance| balance.amount)
+			.map_err(|e| LiquidityError::Network(e.to_string()))
+	}
+
+	async fn token_balance(
+		&self,
+		chain_id: u64,
+		token: &Address,
+		owner: &Address,
+	) -> Result<U256, LiquidityError> {
+		erc20::balance_of(&self.delivery, chain_id, token, owner)
+			.await
+			.map_err(|e| LiquidityError::Network(e.to_string()))
+	}
+}
@@ -0,0 +1,190 @@
+//! Inventory bootstrap assistant for new solver deployments.
+//!
+//! Recommends the initial balance the solver should hold for every token it
+//! delivers, based on the corridors it expects to serve and their expected
+//! daily volume, plus a gas reserve per chain. Token shortfalls can be funded
+//! from a treasury that has approved the solver to pull its tokens.
+
+use crate::erc20::{balance_of, token_metadata, token_transaction, IERC20};
+use alloy_primitives::{Address as AlloyAddress, U256};
+use alloy_sol_types::SolCall;
+use solver_delivery::{DeliveryError, DeliveryService};
+use solver_types::{Address, TokenMetadata, TransactionHash};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Expected fill volume for a single corridor.
+#[derive(Debug, Clone)]
+pub struct Corridor {
+	/// Chain the solver delivers outputs on.
+	pub destination_chain_id: u64,
+	/// Output token the solver delivers.
+	pub token: Address,
+	/// Expected output volume per day in the token's smallest unit.
+	pub daily_volume: U256,
+}
+
+/// Parameters used to size the initial inventory.
+#[derive(Debug, Clone)]
+pub struct BootstrapPlan {
+	/// Corridors the solver expects to serve.
+	pub corridors: Vec<Corridor>,
+	/// Hours of expected volume the inventory should cover.
+	pub coverage_hours: u64,
+	/// Extra inventory on top of the expected volume, in basis points.
+	pub buffer_bps: u32,
+	/// Gas token balance to hold per chain.
+	pub gas_reserves: BTreeMap<u64, U256>,
+	/// Treasury that funding transfers are pulled from, if configured.
+	pub treasury: Option<Address>,
+}
+
+/// Recommended and current balance of a single asset.
+#[derive(Debug, Clone)]
+pub struct InventoryRecommendation {
+	/// Chain the asset lives on.
+	pub chain_id: u64,
+	/// Token contract, or `None` for the chain's gas token.
+	pub token: Option<Address>,
+	/// Symbol and decimals of the token, if they could be read.
+	pub metadata: Option<TokenMetadata>,
+	/// Balance the solver should hold.
+	pub recommended: U256,
+	/// Balance the solver currently holds.
+	pub current: U256,
+}
+
+impl InventoryRecommendation {
+	/// Returns how much the solver is missing to reach the recommendation.
+	pub fn shortfall(&self) -> U256 {
+		self.recommended.saturating_sub(self.current)
+	}
+}
+
+/// Outcome of a single funding transfer.
+#[derive(Debug)]
+pub struct FundingResult {
+	/// Recommendation the transfer was meant to satisfy.
+	pub recommendation: InventoryRecommendation,
+	/// Hash of the submitted transfer, or the reason it was not submitted.
+	pub result: Result<TransactionHash, DeliveryError>,
+}
+
+/// Computes inventory recommendations and funds shortfalls.
+pub struct InventoryBootstrapper {
+	/// Delivery service used for balance calls and funding transfers.
+	delivery: Arc<DeliveryService>,
+	/// Sizing parameters.
+	plan: BootstrapPlan,
+}
+
+impl InventoryBootstrapper {
+	/// Creates a new InventoryBootstrapper for the given plan.
+	pub fn new(delivery: Arc<DeliveryService>, plan: BootstrapPlan) -> Self {
+		Self { delivery, plan }
+	}
+
+	/// Returns the recommended balance per (chain, token).
+	///
+	/// Corridors delivering the same token on the same chain are summed. Gas
+	/// reserves are keyed with a `None` token.
+	pub fn targets(&self) -> BTreeMap<(u64, Option<Vec<u8>>), U256> {
+		let mut targets = BTreeMap::new();
+
+		for corridor in &self.plan.corridors {
+			let amount = corridor.daily_volume * U256::from(self.plan.coverage_hours)
+				/ U256::from(24u64)
+				* U256::from(10_000u64 + self.plan.buffer_bps as u64)
+				/ U256::from(10_000u64);
+			let entry = targets
+				.entry((
+					corridor.destination_chain_id,
+					Some(corridor.token.0.clone()),
+				))
+				.or_insert(U256::ZERO);
+			*entry = entry.saturating_add(amount);
+		}
+
+		for (chain_id, reserve) in &self.plan.gas_reserves {
+			targets.insert((*chain_id, None), *reserve);
+		}
+
+		targets
+	}
+
+	/// Compares the recommended inventory against the owner's balances.
+	pub async fn assess(
+		&self,
+		owner: &Address,
+	) -> Result<Vec<InventoryRecommendation>, DeliveryError> {
+		let mut recommendations = Vec::new();
+
+		for ((chain_id, token), recommended) in self.targets() {
+			let token = token.map(Address);
+			let current = match &token {
+				Some(token) => balance_of(&self.delivery, chain_id, token, owner).await?,
+				None => self.delivery.gas_balance(chain_id, owner).await?.amount,
+			};
+			let metadata = match &token {
+				Some(token) => token_metadata(&self.delivery, chain_id, token).await.ok(),
+				None => None,
+			};
+			recommendations.push(InventoryRecommendation {
+				chain_id,
+				token,
+				metadata,
+				recommended,
+				current,
+			});
+		}
+
+		Ok(recommendations)
+	}
+
+	/// Pulls token shortfalls from the treasury into the owner's account.
+	///
+	/// Gas token shortfalls cannot be pulled and must be funded manually, so
+	/// they are skipped. The treasury must have approved the owner for every
+	/// token it funds.
+	pub async fn fund(
+		&self,
+		owner: &Address,
+		recommendations: &[InventoryRecommendation],
+	) -> Vec<FundingResult> {
+		let mut results = Vec::new();
+
+		for recommendation in recommendations {
+			let Some(token) = &recommendation.token else {
+				continue;
+			};
+			let shortfall = recommendation.shortfall();
+			if shortfall.is_zero() {
+				continue;
+			}
+
+			let result = match &self.plan.treasury {
+				Some(treasury) => {
+					let call_data = IERC20::transferFromCall {
+						from: AlloyAddress::from_slice(&treasury.0),
+						to: AlloyAddress::from_slice(&owner.0),
+						amount: shortfall,
+					}
+					.abi_encode();
+					self.delivery
+						.deliver(token_transaction(recommendation.chain_id, token, call_data))
+						.await
+				}
+				None => Err(DeliveryError::Network(
+					"No treasury configured for funding".to_string(),
+				)),
+			};
+
+			results.push(FundingResult {
+				recommendation: recommendation.clone(),
+				result,
+			});
+		}
+
+		results
+	}
+}
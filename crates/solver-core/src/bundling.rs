@@ -0,0 +1,134 @@
+//! Bundling of fills into shared destination transactions.
+//!
+//! On a busy corridor many small orders deliver the same token on the same
+//! destination chain within seconds of each other. Filling each in its own
+//! transaction pays the base transaction cost every time, so orders executed
+//! close together are held back briefly and filled in a single transaction
+//! instead.
+
+use alloy_primitives::U256;
+use solver_types::{Address, ExecutionParams, Order, OrderAssets};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Orders that may be filled in the same transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BundleKey {
+	/// Order implementation generating the fill.
+	pub implementation: String,
+	/// Destination chain of the fill.
+	pub chain_id: u64,
+	/// Token delivered by the fill.
+	pub token: Address,
+}
+
+/// An order waiting to be filled as part of a bundle.
+#[derive(Debug, Clone)]
+pub struct QueuedFill {
+	/// Order to fill.
+	pub order: Arc<Order>,
+	/// Execution parameters decided by the strategy.
+	pub params: ExecutionParams,
+}
+
+/// Fills collected for one bundle.
+struct OpenBundle {
+	/// Fills in the order they were queued.
+	fills: Vec<QueuedFill>,
+	/// When the first fill was queued.
+	opened_at: Instant,
+}
+
+/// Collects fills into bundles until they are full or their window ends.
+pub struct FillBundler {
+	/// Maximum number of fills per bundle.
+	max_orders: usize,
+	/// How long a bundle stays open after its first fill.
+	window: Duration,
+	/// Largest output amount bundled, keyed by chain ID and token address.
+	max_output_amounts: HashMap<(u64, Address), U256>,
+	/// Open bundles.
+	bundles: Mutex<HashMap<BundleKey, OpenBundle>>,
+}
+
+impl FillBundler {
+	/// Creates a new bundler.
+	pub fn new(
+		max_orders: usize,
+		window: Duration,
+		max_output_amounts: HashMap<(u64, Address), U256>,
+	) -> Self {
+		Self {
+			max_orders,
+			window,
+			max_output_amounts,
+			bundles: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the bundle an order may join.
+	///
+	/// Only orders with a single output can be bundled, and only if that
+	/// output does not exceed the configured maximum for its token.
+	pub fn key(&self, implementation: &str, assets: &OrderAssets) -> Option<BundleKey> {
+		let [output] = assets.outputs.as_slice() else {
+			return None;
+		};
+		let too_large = self
+			.max_output_amounts
+			.get(&(output.chain_id, output.token.clone()))
+			.is_some_and(|maximum| output.amount > *maximum);
+		if too_large {
+			return None;
+		}
+
+		Some(BundleKey {
+			implementation: implementation.to_string(),
+			chain_id: output.chain_id,
+			token: output.token.clone(),
+		})
+	}
+
+	/// Adds a fill to its bundle.
+	///
+	/// Returns the bundle's fills if it is now full.
+	pub fn push(&self, key: BundleKey, fill: QueuedFill) -> Option<Vec<QueuedFill>> {
+		let mut bundles = self.bundles.lock().unwrap();
+		let bundle = bundles.entry(key.clone()).or_insert_with(|| OpenBundle {
+			fills: Vec::new(),
+			opened_at: Instant::now(),
+		});
+		bundle.fills.push(fill);
+		if bundle.fills.len() < self.max_orders {
+			return None;
+		}
+
+		bundles.remove(&key).map(|bundle| bundle.fills)
+	}
+
+	/// Removes and returns the bundles whose window has ended.
+	pub fn take_ready(&self) -> Vec<Vec<QueuedFill>> {
+		let mut bundles = self.bundles.lock().unwrap();
+		let ready: Vec<BundleKey> = bundles
+			.iter()
+			.filter(|(_, bundle)| bundle.opened_at.elapsed() >= self.window)
+			.map(|(key, _)| key.clone())
+			.collect();
+
+		ready
+			.iter()
+			.filter_map(|key| bundles.remove(key))
+			.map(|bundle| bundle.fills)
+			.collect()
+	}
+
+	/// Removes a queued fill, e.g. because its order expired.
+	pub fn cancel(&self, order_id: &str) {
+		let mut bundles = self.bundles.lock().unwrap();
+		bundles.retain(|_, bundle| {
+			bundle.fills.retain(|fill| fill.order.id != order_id);
+			!bundle.fills.is_empty()
+		});
+	}
+}
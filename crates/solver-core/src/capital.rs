@@ -0,0 +1,150 @@
+//! Capital utilization tracking.
+//!
+//! Filling an order spends the solver's own output tokens, which only come
+//! back, as the order's inputs, once the claim confirms. Until then that
+//! inventory is in flight. Comparing in-flight inventory with the idle
+//! inventory still held, along with how long round trips from fill to claim
+//! take, shows which assets are over- or under-provisioned.
+
+use crate::accounting::{Ledger, LedgerAccount, LedgerAsset};
+use alloy_primitives::{hex, U256};
+use solver_types::{Address, CapitalUtilization, TokenAmount};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Output inventory spent on a filled order that has not been claimed yet.
+struct InFlightOrder {
+	/// Outputs the fill delivered from the solver's inventory.
+	outputs: Vec<TokenAmount>,
+	/// When the fill confirmed.
+	filled_at: Instant,
+}
+
+/// Fill-to-claim round trips completed for an asset.
+#[derive(Default, Clone, Copy)]
+struct TurnaroundStats {
+	/// Number of completed round trips.
+	count: u64,
+	/// Total time of all round trips.
+	total: Duration,
+	/// Longest single round trip.
+	max: Duration,
+}
+
+/// Tracks the inventory committed to orders between fill and claim.
+#[derive(Default)]
+pub struct CapitalTracker {
+	/// Filled orders awaiting their claim, keyed by order ID.
+	in_flight: Mutex<HashMap<String, InFlightOrder>>,
+	/// Completed round trips keyed by output chain ID and token.
+	turnarounds: Mutex<HashMap<(u64, Address), TurnaroundStats>>,
+}
+
+impl CapitalTracker {
+	/// Records the outputs of a confirmed fill as in flight.
+	///
+	/// An order already in flight keeps its original fill time.
+	pub fn commit(&self, order_id: &str, outputs: Vec<TokenAmount>) {
+		self.in_flight
+			.lock()
+			.unwrap()
+			.entry(order_id.to_string())
+			.or_insert_with(|| InFlightOrder {
+				outputs,
+				filled_at: Instant::now(),
+			});
+	}
+
+	/// Releases the inventory of a claimed order.
+	///
+	/// Returns the time from fill to claim, or `None` if the order was not in
+	/// flight.
+	pub fn release(&self, order_id: &str) -> Option<Duration> {
+		let order = self.in_flight.lock().unwrap().remove(order_id)?;
+		let elapsed = order.filled_at.elapsed();
+
+		let mut turnarounds = self.turnarounds.lock().unwrap();
+		for output in &order.outputs {
+			let stats = turnarounds
+				.entry((output.chain_id, output.token.clone()))
+				.or_default();
+			stats.count += 1;
+			stats.total += elapsed;
+			stats.max = stats.max.max(elapsed);
+		}
+
+		Some(elapsed)
+	}
+
+	/// Computes the utilization of every token the solver holds or has in
+	/// flight.
+	///
+	/// Idle inventory is the ledger's inventory balance, so tokens never
+	/// reconciled into the ledger report no idle inventory.
+	pub fn utilization(&self, ledger: &Ledger) -> Vec<CapitalUtilization> {
+		// Sorted so that reports and metrics list assets in a stable order
+		let mut assets: BTreeMap<(u64, Vec<u8>), (U256, usize)> = BTreeMap::new();
+		for order in self.in_flight.lock().unwrap().values() {
+			let mut counted = Vec::new();
+			for output in &order.outputs {
+				let key = (output.chain_id, output.token.0.clone());
+				let (amount, orders) = assets.entry(key.clone()).or_default();
+				*amount = amount.saturating_add(output.amount);
+				// Orders with several outputs of the same token count once
+				if !counted.contains(&key) {
+					*orders += 1;
+					counted.push(key);
+				}
+			}
+		}
+		for asset in ledger.inventory_assets() {
+			if let Some(token) = asset.token {
+				assets.entry((asset.chain_id, token.0)).or_default();
+			}
+		}
+
+		let turnarounds = self.turnarounds.lock().unwrap();
+		assets
+			.into_iter()
+			.map(|((chain_id, token), (in_flight, in_flight_orders))| {
+				let token = Address(token);
+				let idle = ledger.balance(
+					LedgerAccount::Inventory,
+					&LedgerAsset {
+						chain_id,
+						token: Some(token.clone()),
+					},
+				);
+				let idle = if idle.is_negative() {
+					U256::ZERO
+				} else {
+					idle.unsigned_abs()
+				};
+				let total = in_flight.saturating_add(idle);
+				let utilization = if total.is_zero() {
+					0.0
+				} else {
+					f64::from(in_flight) / f64::from(total)
+				};
+				let stats = turnarounds
+					.get(&(chain_id, token.clone()))
+					.copied()
+					.unwrap_or_default();
+
+				CapitalUtilization {
+					chain_id,
+					token: format!("0x{}", hex::encode(&token.0)),
+					in_flight,
+					idle,
+					utilization,
+					in_flight_orders,
+					turnarounds: stats.count,
+					average_turnaround_seconds: (stats.count > 0)
+						.then(|| stats.total.as_secs_f64() / stats.count as f64),
+					max_turnaround_seconds: (stats.count > 0).then_some(stats.max.as_secs_f64()),
+				}
+			})
+			.collect()
+	}
+}
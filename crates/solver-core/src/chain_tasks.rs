@@ -0,0 +1,201 @@
+//! Per-chain task groups.
+//!
+//! Work that talks to a single chain, such as submitting transactions and
+//! monitoring them, runs as tasks in that chain's group rather than inline in
+//! the event loop. A task that fails transiently or panics only puts its own
+//! chain into backoff: further tasks for that chain are delayed with an
+//! exponentially growing pause, while other chains keep being served as
+//! usual. Permanent failures, such as an invalid order, say nothing about the
+//! chain and leave its backoff alone.
+//!
+//! Tasks doing work the event loop handed off, such as submitting a fill,
+//! report their failures back to the loop, so that the work is retried or
+//! dead-lettered like work failing in the loop itself.
+
+use crate::retry::Work;
+use crate::SolverError;
+use solver_types::Classify;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::Instrument;
+
+/// Backoff applied to a chain after its first consecutive task failure.
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound on the backoff applied to a failing chain.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Failure state of a single chain.
+#[derive(Default)]
+struct ChainGroup {
+	/// Number of tasks that failed since the last successful one.
+	consecutive_failures: u32,
+	/// Tasks started before this instant wait until it has passed.
+	paused_until: Option<Instant>,
+}
+
+/// Work handed back to the event loop after the chain task doing it failed.
+#[derive(Debug)]
+pub struct FailedTask {
+	/// The work the task did, one piece per order it concerned.
+	pub work: Vec<Work>,
+	/// Attempts made at the work, including the failed task.
+	pub attempts: u32,
+	/// Why the task failed.
+	pub error: SolverError,
+}
+
+/// Task groups keyed by chain ID.
+pub struct ChainTaskGroups {
+	/// Failure state of every chain that has run a task.
+	groups: Arc<Mutex<HashMap<u64, ChainGroup>>>,
+	/// Sender of failed work back to the event loop.
+	failures: mpsc::UnboundedSender<FailedTask>,
+}
+
+impl ChainTaskGroups {
+	/// Creates empty task groups, along with the receiver of the work whose
+	/// tasks failed.
+	pub fn new() -> (Self, mpsc::UnboundedReceiver<FailedTask>) {
+		let (failures, failed) = mpsc::unbounded_channel();
+		let groups = Self {
+			groups: Arc::new(Mutex::new(HashMap::new())),
+			failures,
+		};
+		(groups, failed)
+	}
+
+	/// Spawns a task in a chain's group.
+	///
+	/// The task starts once any backoff of the chain has passed. Transient
+	/// errors and panics are logged and extend the chain's backoff; a
+	/// successful task resets it.
+	pub fn spawn<F>(&self, chain_id: u64, name: &'static str, task: F)
+	where
+		F: Future<Output = Result<(), SolverError>> + Send + 'static,
+	{
+		self.spawn_inner(chain_id, name, None, task);
+	}
+
+	/// Spawns a task doing work handed off by the event loop in a chain's
+	/// group.
+	///
+	/// Behaves like [`ChainTaskGroups::spawn`], except that when the task
+	/// fails, permanently or not, the work is sent back to the event loop
+	/// along with the attempts made at it.
+	pub fn spawn_work<F>(
+		&self,
+		chain_id: u64,
+		name: &'static str,
+		work: Vec<Work>,
+		attempts: u32,
+		task: F,
+	) where
+		F: Future<Output = Result<(), SolverError>> + Send + 'static,
+	{
+		self.spawn_inner(chain_id, name, Some((work, attempts)), task);
+	}
+
+	/// Spawns a task, reporting the given work back if it fails.
+	fn spawn_inner<F>(
+		&self,
+		chain_id: u64,
+		name: &'static str,
+		work: Option<(Vec<Work>, u32)>,
+		task: F,
+	) where
+		F: Future<Output = Result<(), SolverError>> + Send + 'static,
+	{
+		let groups = self.groups.clone();
+		let failures = self.failures.clone();
+		let supervisor = async move {
+			if let Some(until) = Self::paused_until(&groups, chain_id) {
+				tracing::debug!(
+					delay_ms = until.saturating_duration_since(Instant::now()).as_millis() as u64,
+					"Delaying task while chain backs off"
+				);
+				tokio::time::sleep_until(until).await;
+			}
+
+			// Run the task separately so that a panic is caught by its handle
+			let failure = match tokio::spawn(task.in_current_span()).await {
+				Ok(Ok(())) => None,
+				Ok(Err(e)) if !e.class().is_transient() => {
+					tracing::error!(error = %e, "Chain task failed permanently");
+					report(&failures, work, e);
+					return;
+				}
+				Ok(Err(e)) => {
+					let message = e.to_string();
+					report(&failures, work, e);
+					Some(message)
+				}
+				Err(e) if e.is_panic() => {
+					report(
+						&failures,
+						work,
+						SolverError::Service("Task panicked".to_string()),
+					);
+					Some("task panicked".to_string())
+				}
+				Err(_) => return,
+			};
+
+			let mut groups = groups.lock().unwrap();
+			let group = groups.entry(chain_id).or_default();
+			match failure {
+				None => *group = ChainGroup::default(),
+				Some(error) => {
+					group.consecutive_failures += 1;
+					let backoff = BASE_BACKOFF
+						.saturating_mul(1 << (group.consecutive_failures - 1).min(16))
+						.min(MAX_BACKOFF);
+					group.paused_until = Some(Instant::now() + backoff);
+					tracing::warn!(
+						error = %error,
+						failures = group.consecutive_failures,
+						backoff_ms = backoff.as_millis() as u64,
+						"Chain task failed"
+					);
+				}
+			}
+		};
+
+		tokio::spawn(supervisor.instrument(tracing::info_span!(
+			"chain_task",
+			chain_id,
+			task = name
+		)));
+	}
+
+	/// Returns when a chain's backoff ends, if it is currently backing off.
+	fn paused_until(groups: &Mutex<HashMap<u64, ChainGroup>>, chain_id: u64) -> Option<Instant> {
+		groups
+			.lock()
+			.unwrap()
+			.get(&chain_id)
+			.and_then(|group| group.paused_until)
+			.filter(|until| *until > Instant::now())
+	}
+}
+
+/// Sends failed work back to the event loop, if the task did any.
+fn report(
+	failures: &mpsc::UnboundedSender<FailedTask>,
+	work: Option<(Vec<Work>, u32)>,
+	error: SolverError,
+) {
+	if let Some((work, attempts)) = work {
+		failures
+			.send(FailedTask {
+				work,
+				attempts,
+				error,
+			})
+			.ok();
+	}
+}
@@ -0,0 +1,135 @@
+//! Shutdown checkpoints and clean-restart detection.
+//!
+//! While the solver runs, its checkpoint file only records that a run is in
+//! progress. A graceful shutdown replaces that marker with a checkpoint of where
+//! the solver stopped. Finding the marker still in place at startup therefore
+//! means the previous run crashed, and the engine recovers more aggressively.
+
+use crate::SolverError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Contents of the checkpoint file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+enum Checkpoint {
+	/// A run is in progress, or crashed before it could shut down.
+	Running {
+		/// Unix timestamp at which the run started.
+		started_at: u64,
+	},
+	/// The last run shut down gracefully.
+	Stopped(ShutdownCheckpoint),
+}
+
+/// State of the solver at a graceful shutdown.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShutdownCheckpoint {
+	/// Unix timestamp at which the solver stopped.
+	pub stopped_at: u64,
+	/// Sequence of the last journaled event.
+	pub last_event_sequence: u64,
+	/// Number of orders the solver has stored.
+	pub orders: u64,
+	/// Fill transactions that were still awaiting confirmation.
+	pub pending_fills: usize,
+	/// Claim transactions that were still awaiting confirmation.
+	pub pending_claims: usize,
+}
+
+/// How the previous run of the solver ended.
+#[derive(Debug)]
+pub enum PreviousRun {
+	/// No previous run left a checkpoint behind.
+	FirstStart,
+	/// The previous run shut down gracefully.
+	Clean(ShutdownCheckpoint),
+	/// The previous run stopped without shutting down gracefully.
+	Crashed {
+		/// Unix timestamp at which the crashed run started, unless the
+		/// checkpoint file was unreadable.
+		started_at: Option<u64>,
+	},
+}
+
+/// Checkpoint file shared by consecutive runs of the solver.
+pub struct CheckpointFile {
+	/// Location of the checkpoint file.
+	path: PathBuf,
+}
+
+impl CheckpointFile {
+	/// Creates a checkpoint file at the given path.
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self { path: path.into() }
+	}
+
+	/// Determines how the previous run ended from the checkpoint it left.
+	///
+	/// A checkpoint that cannot be parsed was most likely cut short while being
+	/// written, so it is reported as a crash.
+	pub async fn previous_run(&self) -> Result<PreviousRun, SolverError> {
+		let contents = match tokio::fs::read(&self.path).await {
+			Ok(contents) => contents,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+				return Ok(PreviousRun::FirstStart)
+			}
+			Err(e) => {
+				return Err(SolverError::Service(format!(
+					"Failed to read checkpoint file: {}",
+					e
+				)))
+			}
+		};
+
+		Ok(match serde_json::from_slice(&contents) {
+			Ok(Checkpoint::Stopped(checkpoint)) => PreviousRun::Clean(checkpoint),
+			Ok(Checkpoint::Running { started_at }) => PreviousRun::Crashed {
+				started_at: Some(started_at),
+			},
+			Err(_) => PreviousRun::Crashed { started_at: None },
+		})
+	}
+
+	/// Marks a run as in progress.
+	pub async fn mark_running(&self) -> Result<(), SolverError> {
+		self.write(&Checkpoint::Running {
+			started_at: now_secs(),
+		})
+		.await
+	}
+
+	/// Records a graceful shutdown.
+	pub async fn write_shutdown(&self, checkpoint: ShutdownCheckpoint) -> Result<(), SolverError> {
+		self.write(&Checkpoint::Stopped(checkpoint)).await
+	}
+
+	/// Replaces the checkpoint file atomically, so that a crash mid-write
+	/// leaves either the old or the new checkpoint behind.
+	async fn write(&self, checkpoint: &Checkpoint) -> Result<(), SolverError> {
+		let contents = serde_json::to_vec_pretty(checkpoint)
+			.map_err(|e| SolverError::Service(e.to_string()))?;
+
+		if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+			tokio::fs::create_dir_all(parent)
+				.await
+				.map_err(|e| SolverError::Service(format!("Failed to write checkpoint: {}", e)))?;
+		}
+
+		let temp_path = self.path.with_extension("tmp");
+		tokio::fs::write(&temp_path, contents)
+			.await
+			.map_err(|e| SolverError::Service(format!("Failed to write checkpoint: {}", e)))?;
+		tokio::fs::rename(&temp_path, &self.path)
+			.await
+			.map_err(|e| SolverError::Service(format!("Failed to write checkpoint: {}", e)))
+	}
+}
+
+/// Returns the current Unix timestamp in seconds.
+pub(crate) fn now_secs() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
@@ -0,0 +1,201 @@
+//! Adaptive batching of ready claims.
+//!
+//! Claims that become ready are grouped by the chain they are sent on and the
+//! settlement implementation sending them, so that claims along the same
+//! path are handled together. A batch is flushed once it holds the configured
+//! number of claims, once its oldest claim waited for the longest age
+//! allowed, or once the gas its claims are estimated to use reaches the
+//! limit. A claim that would push a batch over the limit flushes the batch
+//! first and opens the next one.
+//!
+//! Gas estimates come from the calibrated gas limits of the claims processed
+//! along each path, so batches size themselves to what claims actually cost
+//! rather than to a fixed guess.
+
+use solver_config::ClaimBatchingConfig;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Why a batch of claims was flushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushReason {
+	/// The batch holds the configured number of claims.
+	Size,
+	/// The oldest claim of the batch waited for the longest age allowed.
+	Age,
+	/// The claims of the batch reached the gas limit.
+	Gas,
+	/// The engine is stopping.
+	Shutdown,
+}
+
+impl FlushReason {
+	/// Returns a stable label for the reason, used in metrics.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			FlushReason::Size => "size",
+			FlushReason::Age => "age",
+			FlushReason::Gas => "gas",
+			FlushReason::Shutdown => "shutdown",
+		}
+	}
+}
+
+/// Claims sent on the same chain by the same settlement implementation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BatchKey {
+	/// Chain the claims are sent on.
+	pub chain_id: u64,
+	/// Settlement implementation sending the claims.
+	pub implementation: String,
+}
+
+/// Claims flushed together.
+#[derive(Debug)]
+pub struct ClaimBatch {
+	/// Orders whose claims are in the batch, in the order they became ready.
+	pub orders: Vec<String>,
+	/// Why the batch was flushed.
+	pub reason: FlushReason,
+}
+
+/// Claims waiting for their batch to be flushed.
+struct PendingBatch {
+	/// Orders whose claims are waiting.
+	orders: Vec<String>,
+	/// Gas the waiting claims are estimated to use together.
+	gas: u64,
+	/// When the first claim of the batch became ready.
+	opened_at: Instant,
+}
+
+/// Groups ready claims into batches.
+pub struct ClaimBatcher {
+	/// Number of claims that fills a batch.
+	max_size: usize,
+	/// Longest a claim waits for its batch to fill.
+	max_age: Duration,
+	/// Estimated gas the claims of one batch may use together, if limited.
+	max_gas: Option<u64>,
+	/// Gas estimate of claims along paths not calibrated yet.
+	default_gas: u64,
+	/// Batches being filled.
+	pending: Mutex<HashMap<BatchKey, PendingBatch>>,
+	/// Latest calibrated claim gas limit per batch key.
+	gas_estimates: Mutex<HashMap<BatchKey, u64>>,
+}
+
+impl ClaimBatcher {
+	/// Creates a batcher from its configuration.
+	pub fn new(config: &ClaimBatchingConfig) -> Self {
+		Self {
+			max_size: config.max_size.max(1),
+			max_age: Duration::from_secs(config.max_age_seconds),
+			max_gas: config.max_gas,
+			default_gas: config.default_claim_gas,
+			pending: Mutex::new(HashMap::new()),
+			gas_estimates: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the interval at which batches are checked for their age.
+	///
+	/// Batches are checked a few times per maximum age, so that none waits
+	/// much longer than allowed.
+	pub fn check_interval(&self) -> Duration {
+		(self.max_age / 4).max(Duration::from_secs(1))
+	}
+
+	/// Records the gas limit a claim along `key` was given, to estimate the
+	/// gas of the claims batched after it.
+	pub fn observe(&self, key: BatchKey, gas_limit: u64) {
+		self.gas_estimates.lock().unwrap().insert(key, gas_limit);
+	}
+
+	/// Adds the ready claim of an order to its batch.
+	///
+	/// Returns the batches flushed by the claim: the batch it would have
+	/// pushed over the gas limit, and its own batch if the claim filled it.
+	pub fn add(&self, key: BatchKey, order_id: String) -> Vec<ClaimBatch> {
+		let gas = self
+			.gas_estimates
+			.lock()
+			.unwrap()
+			.get(&key)
+			.copied()
+			.unwrap_or(self.default_gas);
+
+		let mut pending = self.pending.lock().unwrap();
+		let mut flushed = Vec::new();
+		if let Some(max_gas) = self.max_gas {
+			let over_limit = pending
+				.get(&key)
+				.is_some_and(|batch| batch.gas.saturating_add(gas) > max_gas);
+			if over_limit {
+				if let Some(batch) = pending.remove(&key) {
+					flushed.push(ClaimBatch {
+						orders: batch.orders,
+						reason: FlushReason::Gas,
+					});
+				}
+			}
+		}
+
+		let batch = pending.entry(key.clone()).or_insert_with(|| PendingBatch {
+			orders: Vec::new(),
+			gas: 0,
+			opened_at: Instant::now(),
+		});
+		batch.orders.push(order_id);
+		batch.gas = batch.gas.saturating_add(gas);
+
+		let reason = if batch.orders.len() >= self.max_size {
+			Some(FlushReason::Size)
+		} else if self.max_gas.is_some_and(|max_gas| batch.gas >= max_gas) {
+			Some(FlushReason::Gas)
+		} else {
+			None
+		};
+		if let Some(reason) = reason {
+			if let Some(batch) = pending.remove(&key) {
+				flushed.push(ClaimBatch {
+					orders: batch.orders,
+					reason,
+				});
+			}
+		}
+		flushed
+	}
+
+	/// Flushes the batches whose oldest claim waited for the longest age
+	/// allowed by `now`.
+	pub fn due(&self, now: Instant) -> Vec<ClaimBatch> {
+		let mut pending = self.pending.lock().unwrap();
+		let due: Vec<BatchKey> = pending
+			.iter()
+			.filter(|(_, batch)| now.saturating_duration_since(batch.opened_at) >= self.max_age)
+			.map(|(key, _)| key.clone())
+			.collect();
+		due.into_iter()
+			.filter_map(|key| pending.remove(&key))
+			.map(|batch| ClaimBatch {
+				orders: batch.orders,
+				reason: FlushReason::Age,
+			})
+			.collect()
+	}
+
+	/// Flushes every batch, whatever its size or age.
+	pub fn drain(&self) -> Vec<ClaimBatch> {
+		self.pending
+			.lock()
+			.unwrap()
+			.drain()
+			.map(|(_, batch)| ClaimBatch {
+				orders: batch.orders,
+				reason: FlushReason::Shutdown,
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,168 @@
+//! Gas-price-aware timing of claims.
+//!
+//! A claim only has to land before the order's claim deadline, which is
+//! usually hours after the fill. Claims that become ready while gas on their
+//! chain is above the configured ceiling are held back and checked again
+//! periodically. A held claim is submitted whatever the gas price once its
+//! deadline comes within the safety margin, or once it was held for the
+//! longest delay allowed, so that rewards are never forfeited.
+//!
+//! Claims whose gas would cost more than they pay out are held the same way,
+//! in case gas or prices move in their favour. Those still not worth
+//! claiming when they can wait no longer are abandoned instead.
+
+use alloy_primitives::U256;
+use solver_config::ClaimScheduleConfig;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// When a ready claim is submitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimTiming {
+	/// Gas is within the ceiling, or unknown; the claim is submitted now.
+	Submit,
+	/// Gas is above the ceiling; the claim waits for the next check.
+	Hold,
+	/// Gas is above the ceiling, but the claim cannot wait any longer.
+	Forced,
+	/// The claim costs more than it pays out and cannot wait any longer.
+	Abandon,
+}
+
+/// Claim held back while gas is expensive or the claim unprofitable.
+struct HeldClaim {
+	/// Unix timestamp at which the claim was first held back.
+	held_since: u64,
+}
+
+/// Decides when ready claims are submitted.
+pub struct ClaimScheduler {
+	/// Gas price ceilings in wei, keyed by chain ID.
+	chain_ceilings: HashMap<u64, U256>,
+	/// Gas price ceiling in wei of chains without their own.
+	ceiling: U256,
+	/// Seconds before the claim deadline at which claims stop being held.
+	deadline_margin: u64,
+	/// Longest a claim is held, in seconds.
+	max_delay: u64,
+	/// Interval between checks of held claims.
+	recheck_interval: Duration,
+	/// Held claims keyed by order ID.
+	held: Mutex<HashMap<String, HeldClaim>>,
+	/// Orders whose next claim is submitted without checking gas.
+	expedited: Mutex<HashSet<String>>,
+}
+
+impl ClaimScheduler {
+	/// Creates a scheduler from its configuration.
+	///
+	/// Fails if a chain ID of the per-chain ceilings is not a number.
+	pub fn new(config: &ClaimScheduleConfig) -> Result<Self, String> {
+		let mut chain_ceilings = HashMap::new();
+		for (chain_id, gwei) in &config.chain_max_gas_price_gwei {
+			let chain_id = chain_id
+				.parse::<u64>()
+				.map_err(|e| format!("Invalid claim schedule chain ID {}: {}", chain_id, e))?;
+			chain_ceilings.insert(chain_id, gwei_to_wei(*gwei));
+		}
+		Ok(Self {
+			chain_ceilings,
+			ceiling: gwei_to_wei(config.max_gas_price_gwei),
+			deadline_margin: config.deadline_margin_seconds,
+			max_delay: config.max_delay_seconds,
+			recheck_interval: Duration::from_secs(config.recheck_interval_seconds),
+			held: Mutex::new(HashMap::new()),
+			expedited: Mutex::new(HashSet::new()),
+		})
+	}
+
+	/// Returns the interval between checks of held claims.
+	pub fn recheck_interval(&self) -> Duration {
+		self.recheck_interval
+	}
+
+	/// Decides whether the ready claim of an order is submitted now.
+	///
+	/// `gas_price` is the current price on the claim's chain, if it could be
+	/// estimated, `unprofitable` whether the claim's gas costs more than its
+	/// reward, and `claim_deadline` the time after which the order can no
+	/// longer be claimed, if its standard has one.
+	pub fn decide(
+		&self,
+		order_id: &str,
+		chain_id: u64,
+		gas_price: Option<U256>,
+		unprofitable: bool,
+		claim_deadline: Option<u64>,
+		now: u64,
+	) -> ClaimTiming {
+		if self.expedited.lock().unwrap().remove(order_id) {
+			self.release(order_id);
+			return ClaimTiming::Submit;
+		}
+
+		let ceiling = self
+			.chain_ceilings
+			.get(&chain_id)
+			.copied()
+			.unwrap_or(self.ceiling);
+		let expensive = gas_price.is_some_and(|gas_price| gas_price > ceiling);
+		if !expensive && !unprofitable {
+			self.release(order_id);
+			return ClaimTiming::Submit;
+		}
+
+		let mut held = self.held.lock().unwrap();
+		let claim = held.entry(order_id.to_string()).or_insert_with(|| {
+			tracing::info!(
+				order_id = %crate::truncate_id(order_id),
+				chain_id,
+				gas_price = ?gas_price,
+				ceiling = %ceiling,
+				unprofitable,
+				"Holding back claim"
+			);
+			HeldClaim { held_since: now }
+		});
+		let mut latest = claim.held_since.saturating_add(self.max_delay);
+		if let Some(deadline) = claim_deadline {
+			latest = latest.min(deadline.saturating_sub(self.deadline_margin));
+		}
+		if now < latest {
+			return ClaimTiming::Hold;
+		}
+
+		held.remove(order_id);
+		match unprofitable {
+			true => ClaimTiming::Abandon,
+			false => ClaimTiming::Forced,
+		}
+	}
+
+	/// Returns the orders whose claims are held, to be decided again.
+	pub fn held(&self) -> Vec<String> {
+		self.held.lock().unwrap().keys().cloned().collect()
+	}
+
+	/// Returns whether any claims are held.
+	pub fn is_empty(&self) -> bool {
+		self.held.lock().unwrap().is_empty()
+	}
+
+	/// Submits the next claim of an order without checking gas or its
+	/// profitability.
+	pub fn expedite(&self, order_id: &str) {
+		self.expedited.lock().unwrap().insert(order_id.to_string());
+	}
+
+	/// Stops holding the claim of an order.
+	pub fn release(&self, order_id: &str) {
+		self.held.lock().unwrap().remove(order_id);
+	}
+}
+
+/// Converts a gas price in gwei to wei.
+fn gwei_to_wei(gwei: f64) -> U256 {
+	U256::from((gwei * 1e9) as u128)
+}
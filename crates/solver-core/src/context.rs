@@ -0,0 +1,323 @@
+//! Execution context assembly for strategy decisions.
+//!
+//! Strategies decide on live market conditions: fee estimates on the chains an
+//! order touches, the solver's native and token balances there, and token
+//! prices when a price oracle is available. Every order needs the same handful
+//! of values, so each is cached for a short time instead of being fetched
+//! again for every order in a burst.
+
+use crate::erc20;
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_account::AccountService;
+use solver_delivery::DeliveryService;
+use solver_liquidity::inventory::InventoryService;
+use solver_oracles::{gas_token, OracleError, PriceOracle};
+use solver_types::{
+	whole_tokens, Address, ExecutionContext, GasParams, GasTokenAmount, OrderAssets, TokenAmount,
+};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Values fetched within the last TTL.
+struct TtlCache<K, V> {
+	/// Cached values with the instant they were fetched.
+	entries: Mutex<HashMap<K, (Instant, V)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+	fn new() -> Self {
+		Self {
+			entries: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns a value fetched less than `ttl` ago.
+	fn get(&self, key: &K, ttl: Duration) -> Option<V> {
+		self.entries
+			.lock()
+			.unwrap()
+			.get(key)
+			.filter(|(fetched_at, _)| fetched_at.elapsed() < ttl)
+			.map(|(_, value)| value.clone())
+	}
+
+	fn insert(&self, key: K, value: V) {
+		self.entries
+			.lock()
+			.unwrap()
+			.insert(key, (Instant::now(), value));
+	}
+}
+
+/// Builds execution contexts from live, briefly cached chain data.
+pub struct ContextBuilder {
+	/// Delivery service used to query chains.
+	delivery: Arc<DeliveryService>,
+	/// Account service providing the solver's address.
+	account: Arc<AccountService>,
+	/// Price oracle, if one is configured.
+	oracle: Option<Arc<dyn PriceOracle>>,
+	/// How long fetched values are reused.
+	ttl: Duration,
+	/// Polled inventory, whose balances are used while fresh enough.
+	inventory: Option<Arc<InventoryService>>,
+	/// Fee estimates keyed by chain ID.
+	gas: TtlCache<u64, GasParams>,
+	/// Native balances keyed by chain ID.
+	native_balances: TtlCache<u64, U256>,
+	/// Token balances keyed by chain ID and token address.
+	token_balances: TtlCache<(u64, Address), U256>,
+	/// USD prices keyed by chain ID and token address. Tokens the oracle
+	/// does not price are cached as `None`.
+	prices: TtlCache<(u64, Address), Option<f64>>,
+}
+
+impl ContextBuilder {
+	/// Creates a context builder reusing fetched values for `ttl`.
+	pub fn new(
+		delivery: Arc<DeliveryService>,
+		account: Arc<AccountService>,
+		oracle: Option<Arc<dyn PriceOracle>>,
+		ttl: Duration,
+	) -> Self {
+		Self {
+			delivery,
+			account,
+			oracle,
+			ttl,
+			inventory: None,
+			gas: TtlCache::new(),
+			native_balances: TtlCache::new(),
+			token_balances: TtlCache::new(),
+			prices: TtlCache::new(),
+		}
+	}
+
+	/// Reads balances from the polled inventory when it polled them less
+	/// than the cache TTL ago.
+	pub fn with_inventory(mut self, inventory: Option<Arc<InventoryService>>) -> Self {
+		self.inventory = inventory;
+		self
+	}
+
+	/// Builds the execution context for an order moving the given assets.
+	///
+	/// Fees and native balances cover every chain the order touches, token
+	/// balances cover the outputs the solver has to deliver, and prices cover
+	/// all of the order's tokens. Values that cannot be fetched are left out,
+	/// and the gas price falls back to `fallback_gas_price`.
+	pub async fn build(
+		&self,
+		assets: Option<&OrderAssets>,
+		fallback_gas_price: U256,
+	) -> ExecutionContext {
+		let chains: HashSet<u64> = assets
+			.into_iter()
+			.flat_map(|assets| assets.inputs.iter().chain(assets.outputs.iter()))
+			.map(|asset| asset.chain_id)
+			.collect();
+		let fill_chain = assets
+			.and_then(|assets| assets.outputs.first())
+			.map(|output| output.chain_id);
+
+		let mut gas_estimates = HashMap::new();
+		for chain_id in &chains {
+			if let Some(estimate) = self.gas_estimate(*chain_id).await {
+				gas_estimates.insert(*chain_id, estimate);
+			}
+		}
+		let gas_price = fill_chain
+			.and_then(|chain_id| gas_estimates.get(&chain_id))
+			.and_then(|estimate| estimate.max_price())
+			.unwrap_or(fallback_gas_price);
+
+		let mut native_balances = HashMap::new();
+		let mut token_balances = HashMap::new();
+		match self.account.get_address().await {
+			Ok(owner) => {
+				for chain_id in &chains {
+					if let Some(balance) = self.native_balance(*chain_id, &owner).await {
+						native_balances.insert(*chain_id, balance);
+					}
+				}
+				let outputs: Vec<(u64, Address)> = assets
+					.iter()
+					.flat_map(|assets| assets.outputs.iter())
+					.map(|output| (output.chain_id, output.token.clone()))
+					.collect();
+				for key in outputs {
+					if token_balances.contains_key(&key) {
+						continue;
+					}
+					if let Some(balance) = self.token_balance(&key, &owner).await {
+						token_balances.insert(key, balance);
+					}
+				}
+			}
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to resolve solver address for balances");
+			}
+		}
+
+		let mut token_prices = HashMap::new();
+		if self.oracle.is_some() {
+			let tokens: Vec<(u64, Address)> = assets
+				.into_iter()
+				.flat_map(|assets| assets.inputs.iter().chain(assets.outputs.iter()))
+				.map(|asset| (asset.chain_id, asset.token.clone()))
+				.collect();
+			for key in tokens {
+				if token_prices.contains_key(&key) {
+					continue;
+				}
+				if let Some(price) = self.usd_price(&key).await {
+					token_prices.insert(key, price);
+				}
+			}
+		}
+
+		ExecutionContext {
+			gas_price,
+			gas_estimates,
+			timestamp: std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.unwrap()
+				.as_secs(),
+			deadline: None,
+			native_balances,
+			token_balances,
+			token_prices,
+			remaining_outputs: assets
+				.map(|assets| assets.outputs.clone())
+				.unwrap_or_default(),
+			partial_fills: false,
+			risk_score: None,
+		}
+	}
+
+	/// Returns the fee estimate of a chain.
+	pub(crate) async fn gas_estimate(&self, chain_id: u64) -> Option<GasParams> {
+		if let Some(estimate) = self.gas.get(&chain_id, self.ttl) {
+			return Some(estimate);
+		}
+
+		match self.delivery.estimate_gas(chain_id).await {
+			Ok(estimate) => {
+				self.gas.insert(chain_id, estimate.clone());
+				Some(estimate)
+			}
+			Err(e) => {
+				tracing::warn!(chain_id, error = %e, "Failed to estimate gas");
+				None
+			}
+		}
+	}
+
+	/// Returns the solver's native balance on a chain.
+	pub(crate) async fn native_balance(&self, chain_id: u64, owner: &Address) -> Option<U256> {
+		if let Some(balance) = self.native_balances.get(&chain_id, self.ttl) {
+			return Some(balance);
+		}
+		if let Some(inventory) = &self.inventory {
+			if let Some(balance) = inventory.balance(chain_id, None, self.ttl) {
+				return Some(balance);
+			}
+		}
+
+		match self.delivery.gas_balance(chain_id, owner).await {
+			Ok(balance) => {
+				self.native_balances.insert(chain_id, balance.amount);
+				Some(balance.amount)
+			}
+			Err(e) => {
+				tracing::warn!(chain_id, error = %e, "Failed to fetch native balance");
+				None
+			}
+		}
+	}
+
+	/// Returns the solver's balance of a token.
+	pub(crate) async fn token_balance(
+		&self,
+		key: &(u64, Address),
+		owner: &Address,
+	) -> Option<U256> {
+		if let Some(balance) = self.token_balances.get(key, self.ttl) {
+			return Some(balance);
+		}
+
+		let (chain_id, token) = key;
+		if let Some(inventory) = &self.inventory {
+			if let Some(balance) = inventory.balance(*chain_id, Some(token), self.ttl) {
+				return Some(balance);
+			}
+		}
+		match erc20::balance_of(&self.delivery, *chain_id, token, owner).await {
+			Ok(balance) => {
+				self.token_balances.insert(key.clone(), balance);
+				Some(balance)
+			}
+			Err(e) => {
+				tracing::warn!(chain_id, error = %e, "Failed to fetch token balance");
+				None
+			}
+		}
+	}
+
+	/// Returns the USD price of a token, if the oracle prices it.
+	pub async fn usd_price(&self, key: &(u64, Address)) -> Option<f64> {
+		if let Some(price) = self.prices.get(key, self.ttl) {
+			return price;
+		}
+
+		let oracle = self.oracle.as_ref()?;
+		match oracle.usd_price(key.0, &key.1).await {
+			Ok(price) => {
+				self.prices.insert(key.clone(), price);
+				price
+			}
+			Err(e) => {
+				tracing::warn!(chain_id = key.0, error = %e, "Failed to fetch token price");
+				None
+			}
+		}
+	}
+
+	/// Returns the USD value of amounts of tokens, if every token is priced.
+	pub(crate) async fn tokens_usd(&self, amounts: &[TokenAmount]) -> Option<f64> {
+		let mut total = 0.0;
+		for amount in amounts {
+			let price = self
+				.usd_price(&(amount.chain_id, amount.token.clone()))
+				.await?;
+			let metadata = erc20::token_metadata(&self.delivery, amount.chain_id, &amount.token)
+				.await
+				.ok()?;
+			total += whole_tokens(amount.amount, metadata.decimals) * price;
+		}
+		Some(total)
+	}
+
+	/// Returns the USD value of amounts of gas tokens, if every gas token is
+	/// priced.
+	pub(crate) async fn gas_usd(&self, amounts: &[GasTokenAmount]) -> Option<f64> {
+		let mut total = 0.0;
+		for amount in amounts {
+			let price = self.usd_price(&(amount.chain_id, gas_token())).await?;
+			total += whole_tokens(amount.amount, amount.token.decimals) * price;
+		}
+		Some(total)
+	}
+}
+
+/// Serves prices from the cache, fetching missing ones from the configured
+/// oracle. Prices the oracle fails to fetch are reported as missing.
+#[async_trait]
+impl PriceOracle for ContextBuilder {
+	async fn usd_price(&self, chain_id: u64, token: &Address) -> Result<Option<f64>, OracleError> {
+		Ok(ContextBuilder::usd_price(self, &(chain_id, token.clone())).await)
+	}
+}
@@ -0,0 +1,63 @@
+//! Operator control of a running engine.
+//!
+//! Operators pause intent intake during incidents, drain the solver before
+//! maintenance, and push stuck claims along by hand. Commands are sent to the
+//! engine's event loop and applied there, between intents and events, so they
+//! never race with the loop's own handling of the same orders.
+
+use serde::Serialize;
+use solver_types::{ClaimActionResponse, IntakeStatusResponse};
+use thiserror::Error;
+use tokio::sync::oneshot;
+
+/// Command an operator sends to the running engine.
+#[derive(Debug, Clone)]
+pub enum ControlCommand {
+	/// Reports the intake state.
+	Status,
+	/// Holds newly discovered intents until intake resumes.
+	PauseIntake,
+	/// Handles held and newly discovered intents again.
+	ResumeIntake,
+	/// Holds newly discovered intents while in-flight orders finish.
+	Drain,
+	/// Checks again whether an order can be claimed, claiming it if so.
+	RecheckClaim { order_id: String },
+	/// Claims an order right away, without waiting for it to be ready.
+	Claim { order_id: String },
+}
+
+/// Reply of the engine to a control command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ControlReply {
+	/// Intake state after an intake command.
+	Intake(IntakeStatusResponse),
+	/// Outcome of a claim command.
+	Claim(ClaimActionResponse),
+}
+
+/// Errors that can occur handling a control command.
+#[derive(Debug, Error)]
+pub enum ControlError {
+	/// The order is not known to the solver.
+	#[error("Order {0} not found")]
+	OrderNotFound(String),
+	/// The order has no proven fill to claim with.
+	#[error("Order {0} has no proven fill")]
+	NotFilled(String),
+	/// The engine's event loop is not running.
+	#[error("Solver engine is not running")]
+	NotRunning,
+	/// The command failed while being handled.
+	#[error("Control command failed: {0}")]
+	Failed(String),
+}
+
+/// A command waiting to be handled by the event loop.
+pub(crate) struct ControlMessage {
+	/// Command to handle.
+	pub command: ControlCommand,
+	/// Channel the reply is sent back on.
+	pub reply: oneshot::Sender<Result<ControlReply, ControlError>>,
+}
@@ -0,0 +1,208 @@
+//! Dead-letter queue for orders that failed.
+//!
+//! Orders that fail validation, or whose fill or claim transaction fails,
+//! used to be dropped after a warning. They are instead held in storage with
+//! the stage they failed at and the error, so operators can inspect them and
+//! re-drive them once the cause is fixed. A retry policy can re-drive them
+//! automatically a limited number of times, unless their failure is known to
+//! be permanent.
+//!
+//! Re-driving only schedules an order; the engine picks scheduled orders up
+//! on its next check. This lets a separate process, such as the CLI, re-drive
+//! orders of a running solver sharing its storage.
+
+use solver_storage::{StorageError, StorageService};
+use solver_types::{DeadLetter, ErrorClass, FailureStage, Intent};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Storage namespace holding dead-lettered orders, keyed by order ID.
+const DEAD_LETTERS_NAMESPACE: &str = "dead_letters";
+
+/// Storage namespace holding the index of dead-lettered order IDs.
+const INDEX_NAMESPACE: &str = "dead_letter_index";
+
+/// Key of the index of dead-lettered order IDs.
+const INDEX_KEY: &str = "orders";
+
+/// Automatic re-driving of dead-lettered orders.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+	/// Re-drives after which orders are only re-driven by hand.
+	pub max_retries: u32,
+	/// Delay between a failure and its automatic re-drive.
+	pub retry_after: Duration,
+}
+
+/// Storage-backed queue of failed orders.
+pub struct DeadLetterQueue {
+	/// Storage holding dead letters and their index.
+	storage: Arc<StorageService>,
+	/// Automatic re-drive policy, if enabled.
+	policy: Option<RetryPolicy>,
+	/// Serializes updates of the index.
+	index: Mutex<()>,
+}
+
+impl DeadLetterQueue {
+	/// Creates a queue re-driving orders according to `policy`, or only by
+	/// hand if no policy is given.
+	pub fn new(storage: Arc<StorageService>, policy: Option<RetryPolicy>) -> Self {
+		Self {
+			storage,
+			policy,
+			index: Mutex::new(()),
+		}
+	}
+
+	/// Holds a failed order, scheduling its automatic re-drive if it has
+	/// retries left and its failure is not known to be permanent.
+	///
+	/// An order failing again keeps its retry count, and its intent unless a
+	/// new one is given.
+	pub async fn record(
+		&self,
+		order_id: &str,
+		stage: FailureStage,
+		error: String,
+		error_class: Option<ErrorClass>,
+		intent: Option<Intent>,
+	) -> Result<DeadLetter, StorageError> {
+		let _index = self.index.lock().await;
+		let previous = self.get(order_id).await?;
+		let now = now_secs();
+
+		let retries = previous.as_ref().map_or(0, |previous| previous.retries);
+		let next_retry_at = self
+			.policy
+			.filter(|policy| retries < policy.max_retries)
+			.filter(|_| error_class.is_none_or(|class| class.is_transient()))
+			.map(|policy| now + policy.retry_after.as_secs());
+		let dead_letter = DeadLetter {
+			order_id: order_id.to_string(),
+			stage,
+			error,
+			error_class,
+			failed_at: now,
+			retries,
+			next_retry_at,
+			intent: intent.or(previous
+				.as_ref()
+				.and_then(|previous| previous.intent.clone())),
+		};
+		self.storage
+			.store(DEAD_LETTERS_NAMESPACE, order_id, &dead_letter)
+			.await?;
+
+		if previous.is_none() {
+			let mut order_ids = self.order_ids().await?;
+			order_ids.push(order_id.to_string());
+			self.storage
+				.store(INDEX_NAMESPACE, INDEX_KEY, &order_ids)
+				.await?;
+		}
+		Ok(dead_letter)
+	}
+
+	/// Returns the dead letter of an order, if it is held.
+	pub async fn get(&self, order_id: &str) -> Result<Option<DeadLetter>, StorageError> {
+		match self
+			.storage
+			.retrieve(DEAD_LETTERS_NAMESPACE, order_id)
+			.await
+		{
+			Ok(dead_letter) => Ok(Some(dead_letter)),
+			Err(StorageError::NotFound) => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Returns all held orders, oldest failure first.
+	pub async fn list(&self) -> Result<Vec<DeadLetter>, StorageError> {
+		let mut dead_letters = Vec::new();
+		for order_id in self.order_ids().await? {
+			if let Some(dead_letter) = self.get(&order_id).await? {
+				dead_letters.push(dead_letter);
+			}
+		}
+		dead_letters.sort_by_key(|dead_letter| dead_letter.failed_at);
+		Ok(dead_letters)
+	}
+
+	/// Schedules a held order to be re-driven right away, regardless of its
+	/// retries left.
+	///
+	/// Returns the updated dead letter, or `None` if the order is not held.
+	pub async fn redrive(&self, order_id: &str) -> Result<Option<DeadLetter>, StorageError> {
+		let _index = self.index.lock().await;
+		let Some(mut dead_letter) = self.get(order_id).await? else {
+			return Ok(None);
+		};
+		dead_letter.next_retry_at = Some(now_secs());
+		self.storage
+			.store(DEAD_LETTERS_NAMESPACE, order_id, &dead_letter)
+			.await?;
+		Ok(Some(dead_letter))
+	}
+
+	/// Returns the held orders due to be re-driven, counting the re-drive.
+	///
+	/// Returned orders stay held, unscheduled, until they complete or fail
+	/// again.
+	pub async fn take_due(&self) -> Result<Vec<DeadLetter>, StorageError> {
+		let _index = self.index.lock().await;
+		let now = now_secs();
+
+		let mut due = Vec::new();
+		for order_id in self.order_ids().await? {
+			let Some(mut dead_letter) = self.get(&order_id).await? else {
+				continue;
+			};
+			if dead_letter.next_retry_at.is_none_or(|at| at > now) {
+				continue;
+			}
+			dead_letter.next_retry_at = None;
+			dead_letter.retries += 1;
+			self.storage
+				.store(DEAD_LETTERS_NAMESPACE, &order_id, &dead_letter)
+				.await?;
+			due.push(dead_letter);
+		}
+		Ok(due)
+	}
+
+	/// Releases an order that completed after being re-driven.
+	pub async fn resolve(&self, order_id: &str) -> Result<(), StorageError> {
+		let _index = self.index.lock().await;
+		if self.get(order_id).await?.is_none() {
+			return Ok(());
+		}
+
+		self.storage
+			.remove(DEAD_LETTERS_NAMESPACE, order_id)
+			.await?;
+		let mut order_ids = self.order_ids().await?;
+		order_ids.retain(|id| id != order_id);
+		self.storage
+			.store(INDEX_NAMESPACE, INDEX_KEY, &order_ids)
+			.await
+	}
+
+	/// Reads the IDs of the held orders.
+	async fn order_ids(&self) -> Result<Vec<String>, StorageError> {
+		match self.storage.retrieve(INDEX_NAMESPACE, INDEX_KEY).await {
+			Ok(order_ids) => Ok(order_ids),
+			Err(StorageError::NotFound) => Ok(Vec::new()),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+/// Returns the current unix timestamp in seconds.
+fn now_secs() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
@@ -0,0 +1,106 @@
+//! Scheduling of deferred orders.
+//!
+//! A strategy defers an order when it may become worth executing later, for
+//! example once gas prices drop. Deferred orders are held until their retry
+//! time and then decided again against a fresh execution context, up to a
+//! limited number of times before the solver gives up on them.
+
+use solver_types::Order;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// An order waiting to be decided again.
+struct Deferral {
+	/// The deferred order.
+	order: Arc<Order>,
+	/// Number of times the order has been deferred.
+	attempts: u32,
+}
+
+/// Orders waiting for their retry time.
+#[derive(Default)]
+struct Schedule {
+	/// Order IDs keyed by retry time. Several orders may share a time.
+	due: BTreeMap<Instant, Vec<String>>,
+	/// Deferred orders keyed by order ID.
+	orders: HashMap<String, (Instant, Deferral)>,
+}
+
+/// Holds deferred orders until they are due for re-evaluation.
+pub struct DeferredOrders {
+	/// Deferrals allowed per order before it is given up on.
+	max_deferrals: u32,
+	/// Orders waiting for their retry time.
+	schedule: Mutex<Schedule>,
+}
+
+impl DeferredOrders {
+	/// Creates a scheduler allowing each order `max_deferrals` deferrals.
+	pub fn new(max_deferrals: u32) -> Self {
+		Self {
+			max_deferrals,
+			schedule: Mutex::new(Schedule::default()),
+		}
+	}
+
+	/// Schedules an order to be decided again after `retry_after`.
+	///
+	/// `attempts` counts the deferrals so far, including this one. Returns
+	/// false, without scheduling the order, once the order has used up its
+	/// deferrals.
+	pub fn defer(&self, order: Arc<Order>, retry_after: Duration, attempts: u32) -> bool {
+		if attempts > self.max_deferrals {
+			return false;
+		}
+
+		let retry_at = Instant::now() + retry_after;
+		let mut schedule = self.schedule.lock().unwrap();
+		let order_id = order.id.clone();
+		if let Some((previous, _)) = schedule.orders.remove(&order_id) {
+			remove_due(&mut schedule.due, previous, &order_id);
+		}
+		schedule
+			.due
+			.entry(retry_at)
+			.or_default()
+			.push(order_id.clone());
+		schedule
+			.orders
+			.insert(order_id, (retry_at, Deferral { order, attempts }));
+		true
+	}
+
+	/// Removes and returns the orders whose retry time has come, along with
+	/// the number of times each has been deferred.
+	pub fn take_due(&self) -> Vec<(Arc<Order>, u32)> {
+		let now = Instant::now();
+		let mut schedule = self.schedule.lock().unwrap();
+		let later = schedule.due.split_off(&now);
+		let due = std::mem::replace(&mut schedule.due, later);
+
+		due.into_values()
+			.flatten()
+			.filter_map(|order_id| schedule.orders.remove(&order_id))
+			.map(|(_, deferral)| (deferral.order, deferral.attempts))
+			.collect()
+	}
+
+	/// Stops waiting for an order, e.g. because it expired or was invalidated.
+	pub fn cancel(&self, order_id: &str) {
+		let mut schedule = self.schedule.lock().unwrap();
+		if let Some((retry_at, _)) = schedule.orders.remove(order_id) {
+			remove_due(&mut schedule.due, retry_at, order_id);
+		}
+	}
+}
+
+/// Removes an order from the retry times, dropping times left without orders.
+fn remove_due(due: &mut BTreeMap<Instant, Vec<String>>, retry_at: Instant, order_id: &str) {
+	if let Some(order_ids) = due.get_mut(&retry_at) {
+		order_ids.retain(|id| id != order_id);
+		if order_ids.is_empty() {
+			due.remove(&retry_at);
+		}
+	}
+}
@@ -0,0 +1,108 @@
+//! Dry runs of the engine against live order flow.
+//!
+//! A dry run processes orders exactly like a live solver: intents are
+//! discovered and validated, the execution strategy decides them and fill
+//! transactions are generated. Instead of being submitted, fills are
+//! simulated from the solver's account and logged along with their gas and
+//! the profit they were projected to make, so that strategy changes can be
+//! judged on real orders without putting funds at risk.
+
+use crate::context::ContextBuilder;
+use crate::erc20;
+use alloy_primitives::hex;
+use solver_delivery::DeliveryService;
+use solver_oracles::gas_token;
+use solver_types::{whole_tokens, GasTokenAmount, OrderAssets, TokenAmount, Transaction};
+use std::sync::Arc;
+
+/// Reason orders are skipped with once their fill was simulated.
+pub const DRY_RUN_SKIP_REASON: &str = "Dry run: fill simulated, not submitted";
+
+/// Simulates and reports the fills of a dry run.
+pub struct DryRun {
+	/// Delivery service simulating fills and estimating their fees.
+	delivery: Arc<DeliveryService>,
+	/// Context builder pricing tokens in USD.
+	context: Arc<ContextBuilder>,
+}
+
+impl DryRun {
+	/// Creates a dry run simulating fills through `delivery`, with prices
+	/// from `context`.
+	pub fn new(delivery: Arc<DeliveryService>, context: Arc<ContextBuilder>) -> Self {
+		Self { delivery, context }
+	}
+
+	/// Simulates a fill of orders moving the given assets, and logs what it
+	/// would have cost and earned.
+	///
+	/// The projected profit is the USD value of the orders' inputs less that
+	/// of their outputs and of the fill's fee. It is only logged when the
+	/// oracle prices every token involved.
+	pub async fn fill(&self, assets: &[OrderAssets], tx: &Transaction) {
+		let simulation = match self.delivery.simulate(tx).await {
+			Ok(()) => "succeeded".to_string(),
+			Err(e) => e.to_string(),
+		};
+		let fee = match self.delivery.estimate_fee(tx).await {
+			Ok(fee) => Some(fee),
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to estimate fee of dry run fill");
+				None
+			}
+		};
+		let fee_usd = match &fee {
+			Some(fee) => self.fee_usd(fee).await,
+			None => None,
+		};
+		let profit_usd = match (self.spread_usd(assets).await, fee_usd) {
+			(Some(spread), Some(fee)) => Some(spread - fee),
+			_ => None,
+		};
+
+		tracing::info!(
+			chain_id = tx.chain_id,
+			to = %tx.to.as_ref().map(|to| format!("0x{}", hex::encode(&to.0))).unwrap_or_default(),
+			data = %format!("0x{}", hex::encode(&tx.data)),
+			value = %tx.value,
+			gas_limit = ?tx.gas_limit,
+			fee = %fee.map(|fee| fee.to_string()).unwrap_or_default(),
+			simulation = %simulation,
+			projected_profit_usd = ?profit_usd,
+			"Dry run: would submit fill"
+		);
+	}
+
+	/// Returns the USD value of the inputs of the orders less that of their
+	/// outputs.
+	async fn spread_usd(&self, assets: &[OrderAssets]) -> Option<f64> {
+		let mut spread = 0.0;
+		for assets in assets {
+			for input in &assets.inputs {
+				spread += self.value_usd(input).await?;
+			}
+			for output in &assets.outputs {
+				spread -= self.value_usd(output).await?;
+			}
+		}
+		Some(spread)
+	}
+
+	/// Returns the USD value of an amount of tokens.
+	async fn value_usd(&self, amount: &TokenAmount) -> Option<f64> {
+		let price = self
+			.context
+			.usd_price(&(amount.chain_id, amount.token.clone()))
+			.await?;
+		let metadata = erc20::token_metadata(&self.delivery, amount.chain_id, &amount.token)
+			.await
+			.ok()?;
+		Some(whole_tokens(amount.amount, metadata.decimals) * price)
+	}
+
+	/// Returns the USD value of a fee in a chain's gas token.
+	async fn fee_usd(&self, fee: &GasTokenAmount) -> Option<f64> {
+		let price = self.context.usd_price(&(fee.chain_id, gas_token())).await?;
+		Some(whole_tokens(fee.amount, fee.token.decimals) * price)
+	}
+}
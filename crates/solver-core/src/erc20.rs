@@ -0,0 +1,87 @@
+//! ERC-20 helpers shared by the engine's token-handling components.
+
+use alloy_primitives::{Address as AlloyAddress, U256};
+use alloy_sol_types::{sol, SolCall};
+use solver_delivery::{DeliveryError, DeliveryService};
+use solver_types::{Address, TokenMetadata, Transaction};
+
+// Solidity type definitions for the ERC-20 functions the solver uses.
+sol! {
+	interface IERC20 {
+		function balanceOf(address account) external view returns (uint256);
+		function allowance(address owner, address spender) external view returns (uint256);
+		function approve(address spender, uint256 amount) external returns (bool);
+		function transferFrom(address from, address to, uint256 amount) external returns (bool);
+		function decimals() external view returns (uint8);
+		function symbol() external view returns (string);
+	}
+}
+
+/// Builds a transaction calling a token contract.
+pub(crate) fn token_transaction(chain_id: u64, token: &Address, data: Vec<u8>) -> Transaction {
+	Transaction {
+		to: Some(token.clone()),
+		data,
+		value: U256::ZERO,
+		chain_id,
+		nonce: None,
+		gas_limit: None,
+		gas_price: None,
+		max_fee_per_gas: None,
+		max_priority_fee_per_gas: None,
+	}
+}
+
+/// Returns the token balance of an account.
+pub(crate) async fn balance_of(
+	delivery: &DeliveryService,
+	chain_id: u64,
+	token: &Address,
+	account: &Address,
+) -> Result<U256, DeliveryError> {
+	let call_data = IERC20::balanceOfCall {
+		account: AlloyAddress::from_slice(&account.0),
+	}
+	.abi_encode();
+
+	let result = delivery
+		.call(&token_transaction(chain_id, token, call_data))
+		.await?;
+
+	let decoded = IERC20::balanceOfCall::abi_decode_returns(&result, true)
+		.map_err(|e| DeliveryError::Network(format!("Failed to decode balance: {}", e)))?;
+	Ok(decoded._0)
+}
+
+/// Returns the symbol and decimals of a token.
+///
+/// Both are fixed once the token is deployed, so lookups are cached.
+pub(crate) async fn token_metadata(
+	delivery: &DeliveryService,
+	chain_id: u64,
+	token: &Address,
+) -> Result<TokenMetadata, DeliveryError> {
+	let decimals = delivery
+		.call_immutable(&token_transaction(
+			chain_id,
+			token,
+			IERC20::decimalsCall {}.abi_encode(),
+		))
+		.await?;
+	let symbol = delivery
+		.call_immutable(&token_transaction(
+			chain_id,
+			token,
+			IERC20::symbolCall {}.abi_encode(),
+		))
+		.await?;
+
+	Ok(TokenMetadata {
+		decimals: IERC20::decimalsCall::abi_decode_returns(&decimals, true)
+			.map_err(|e| DeliveryError::Network(format!("Failed to decode decimals: {}", e)))?
+			._0,
+		symbol: IERC20::symbolCall::abi_decode_returns(&symbol, true)
+			.map_err(|e| DeliveryError::Network(format!("Failed to decode symbol: {}", e)))?
+			._0,
+	})
+}
@@ -0,0 +1,141 @@
+//! Watching of fill deadlines of open orders.
+//!
+//! An order can wait a long time between validation and the submission of
+//! its fill: deferred by its strategy, queued in a fill bundle or waiting on
+//! its chain's task group. Its fill deadline may pass meanwhile, and a fill
+//! submitted after it can only revert. The watcher keeps the deadline of
+//! every open order until its fill is submitted, so that orders can be
+//! expired as soon as their deadline passes and fills already on their way
+//! can be stopped.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+
+/// How long an expired order is remembered, in seconds, so that fills still
+/// on their way for it are stopped.
+const EXPIRED_RETENTION_SECONDS: u64 = 3600;
+
+/// Deadlines of the watched orders.
+#[derive(Default)]
+struct Deadlines {
+	/// Order IDs keyed by fill deadline. Several orders may share a deadline.
+	due: BTreeMap<u64, HashSet<String>>,
+	/// Fill deadlines keyed by order ID.
+	orders: HashMap<String, u64>,
+	/// Fill deadlines of expired orders, keyed by order ID.
+	expired: HashMap<String, u64>,
+}
+
+impl Deadlines {
+	/// Stops watching an order, returning its deadline if it was watched.
+	fn remove(&mut self, order_id: &str) -> Option<u64> {
+		let deadline = self.orders.remove(order_id)?;
+		if let Some(order_ids) = self.due.get_mut(&deadline) {
+			order_ids.remove(order_id);
+			if order_ids.is_empty() {
+				self.due.remove(&deadline);
+			}
+		}
+		Some(deadline)
+	}
+}
+
+/// Tracks the fill deadlines of open orders.
+#[derive(Default)]
+pub struct ExpiryWatcher {
+	/// Deadlines of watched and recently expired orders.
+	deadlines: Mutex<Deadlines>,
+}
+
+impl ExpiryWatcher {
+	/// Creates a watcher with no orders.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Watches an order until its fill is submitted, it expires or it is
+	/// released. Watching an order again replaces its deadline.
+	pub fn track(&self, order_id: &str, fill_deadline: u64) {
+		let mut deadlines = self.deadlines.lock().unwrap();
+		deadlines.remove(order_id);
+		deadlines.expired.remove(order_id);
+		deadlines
+			.due
+			.entry(fill_deadline)
+			.or_default()
+			.insert(order_id.to_string());
+		deadlines.orders.insert(order_id.to_string(), fill_deadline);
+	}
+
+	/// Stops watching an order that will not be filled, e.g. because it was
+	/// skipped or invalidated.
+	pub fn release(&self, order_id: &str) {
+		self.deadlines.lock().unwrap().remove(order_id);
+	}
+
+	/// Stops watching an order whose fill is about to be submitted.
+	///
+	/// Returns false if the order expired or its deadline passed, in which
+	/// case the fill must not be submitted. An order past its deadline stays
+	/// watched so that it is reported by [`take_expired`](Self::take_expired).
+	/// Orders that were never watched are cleared.
+	pub fn clear_for_fill(&self, order_id: &str, now: u64) -> bool {
+		let mut deadlines = self.deadlines.lock().unwrap();
+		if deadlines.expired.contains_key(order_id) {
+			return false;
+		}
+		match deadlines.orders.get(order_id) {
+			Some(deadline) if *deadline <= now => false,
+			Some(_) => {
+				deadlines.remove(order_id);
+				true
+			}
+			None => true,
+		}
+	}
+
+	/// Returns whether an order expired or its deadline passed.
+	pub fn is_expired(&self, order_id: &str, now: u64) -> bool {
+		let deadlines = self.deadlines.lock().unwrap();
+		deadlines.expired.contains_key(order_id)
+			|| deadlines
+				.orders
+				.get(order_id)
+				.is_some_and(|deadline| *deadline <= now)
+	}
+
+	/// Marks an order as expired, returning whether it was not already.
+	pub fn expire(&self, order_id: &str, now: u64) -> bool {
+		let mut deadlines = self.deadlines.lock().unwrap();
+		if deadlines.expired.contains_key(order_id) {
+			return false;
+		}
+		let deadline = deadlines.remove(order_id).unwrap_or(now);
+		deadlines.expired.insert(order_id.to_string(), deadline);
+		true
+	}
+
+	/// Marks the watched orders whose deadline passed as expired and returns
+	/// them.
+	///
+	/// Expired orders are forgotten once their deadline is long past.
+	pub fn take_expired(&self, now: u64) -> Vec<String> {
+		let mut deadlines = self.deadlines.lock().unwrap();
+		deadlines
+			.expired
+			.retain(|_, deadline| deadline.saturating_add(EXPIRED_RETENTION_SECONDS) > now);
+
+		let later = deadlines.due.split_off(&(now + 1));
+		let due = std::mem::replace(&mut deadlines.due, later);
+
+		let mut expired = Vec::new();
+		for (deadline, order_ids) in due {
+			for order_id in order_ids {
+				deadlines.orders.remove(&order_id);
+				deadlines.expired.insert(order_id.clone(), deadline);
+				expired.push(order_id);
+			}
+		}
+		expired
+	}
+}
@@ -0,0 +1,214 @@
+//! Gas spend budget and failure circuit breaker.
+//!
+//! A misbehaving strategy or a misconfigured chain can burn through the
+//! solver's gas tokens long before anyone notices. The gas spent by confirmed
+//! transactions is summed per chain over a rolling window, and new fills on a
+//! chain pause once its spend reaches the configured limit, resuming as old
+//! spend leaves the window. Independently, a run of consecutive failed
+//! transactions pauses fills on every chain for a cooldown, since it usually
+//! means something is wrong beyond a single order.
+
+use alloy_primitives::U256;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Change in whether fills are paused, for a chain or for every chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PauseChange {
+	/// Fills were paused for the given reason.
+	Paused {
+		chain_id: Option<u64>,
+		reason: String,
+	},
+	/// Fills resumed.
+	Resumed { chain_id: Option<u64> },
+}
+
+/// Tracks gas spend and transaction failures, pausing fills when either
+/// exceeds its limit.
+pub struct GasBudget {
+	/// Length of the rolling window spend is summed over.
+	window: Duration,
+	/// Gas spend allowed per window, per chain.
+	limits: HashMap<u64, U256>,
+	/// Consecutive failures pausing fills, if failures pause them at all.
+	max_consecutive_failures: Option<u32>,
+	/// How long fills stay paused after too many consecutive failures.
+	failure_cooldown: Duration,
+	/// Spend, failures and reported pauses.
+	state: Mutex<BudgetState>,
+}
+
+/// Mutable state of a gas budget.
+#[derive(Default)]
+struct BudgetState {
+	/// Gas spent per chain within the window, oldest first.
+	spend: HashMap<u64, VecDeque<(Instant, U256)>>,
+	/// Transactions that failed since the last successful one.
+	consecutive_failures: u32,
+	/// When the pause caused by consecutive failures lifts, if paused.
+	failure_pause_until: Option<Instant>,
+	/// Pauses in effect as last reported, keyed by chain, or by `None` for
+	/// pauses of every chain.
+	paused: BTreeMap<Option<u64>, String>,
+}
+
+impl GasBudget {
+	/// Creates a budget allowing `limits` of gas spend per `window`.
+	pub fn new(
+		window: Duration,
+		limits: HashMap<u64, U256>,
+		max_consecutive_failures: Option<u32>,
+		failure_cooldown: Duration,
+	) -> Self {
+		Self {
+			window,
+			limits,
+			max_consecutive_failures,
+			failure_cooldown,
+			state: Mutex::new(BudgetState::default()),
+		}
+	}
+
+	/// Records a confirmed transaction spending `gas`, in the smallest unit of
+	/// the gas token of `chain_id` if the chain is known.
+	///
+	/// A reverted transaction counts as a failure, a successful one ends any
+	/// run of failures.
+	pub fn record_transaction(
+		&self,
+		chain_id: Option<u64>,
+		gas: U256,
+		success: bool,
+	) -> Vec<PauseChange> {
+		let now = Instant::now();
+		let mut state = self.state.lock().unwrap();
+		if let Some(chain_id) = chain_id {
+			state
+				.spend
+				.entry(chain_id)
+				.or_default()
+				.push_back((now, gas));
+		}
+		if success {
+			state.consecutive_failures = 0;
+		} else {
+			self.count_failure(&mut state, now);
+		}
+		self.update(&mut state, now)
+	}
+
+	/// Records a transaction that failed without being confirmed.
+	pub fn record_failure(&self) -> Vec<PauseChange> {
+		let now = Instant::now();
+		let mut state = self.state.lock().unwrap();
+		self.count_failure(&mut state, now);
+		self.update(&mut state, now)
+	}
+
+	/// Drops spend that left the window and lifts pauses that ended.
+	pub fn refresh(&self) -> Vec<PauseChange> {
+		let mut state = self.state.lock().unwrap();
+		self.update(&mut state, Instant::now())
+	}
+
+	/// Returns why fills on `chain_id` are paused, if they are.
+	///
+	/// Fills of an unknown chain are only paused by failures.
+	pub fn pause_reason(&self, chain_id: Option<u64>) -> Option<String> {
+		let state = self.state.lock().unwrap();
+		state
+			.paused
+			.get(&None)
+			.or_else(|| state.paused.get(&Some(chain_id?)))
+			.cloned()
+	}
+
+	/// Returns the pauses in effect, keyed by chain, or by `None` for pauses
+	/// of every chain.
+	pub fn pauses(&self) -> BTreeMap<Option<u64>, String> {
+		self.state.lock().unwrap().paused.clone()
+	}
+
+	/// Counts a failed transaction, starting the cooldown once the threshold
+	/// is reached.
+	fn count_failure(&self, state: &mut BudgetState, now: Instant) {
+		state.consecutive_failures += 1;
+		if self
+			.max_consecutive_failures
+			.is_some_and(|max| state.consecutive_failures >= max)
+		{
+			state.failure_pause_until = Some(now + self.failure_cooldown);
+		}
+	}
+
+	/// Recomputes the pauses in effect and returns how they changed since
+	/// they were last reported.
+	fn update(&self, state: &mut BudgetState, now: Instant) -> Vec<PauseChange> {
+		let mut paused = BTreeMap::new();
+
+		for (chain_id, spend) in state.spend.iter_mut() {
+			while spend
+				.front()
+				.is_some_and(|(at, _)| now.duration_since(*at) >= self.window)
+			{
+				spend.pop_front();
+			}
+			let Some(limit) = self.limits.get(chain_id) else {
+				continue;
+			};
+			let total = spend
+				.iter()
+				.fold(U256::ZERO, |total, (_, gas)| total.saturating_add(*gas));
+			if total >= *limit {
+				paused.insert(
+					Some(*chain_id),
+					format!(
+						"Gas spend of {} in the last {} seconds reached the limit of {}",
+						total,
+						self.window.as_secs(),
+						limit
+					),
+				);
+			}
+		}
+		state.spend.retain(|_, spend| !spend.is_empty());
+
+		match state.failure_pause_until {
+			Some(until) if until > now => {
+				paused.insert(
+					None,
+					format!(
+						"{} consecutive transactions failed",
+						state.consecutive_failures
+					),
+				);
+			}
+			Some(_) => {
+				state.failure_pause_until = None;
+				state.consecutive_failures = 0;
+			}
+			None => {}
+		}
+
+		let mut changes = Vec::new();
+		for chain_id in state.paused.keys() {
+			if !paused.contains_key(chain_id) {
+				changes.push(PauseChange::Resumed {
+					chain_id: *chain_id,
+				});
+			}
+		}
+		for (chain_id, reason) in &paused {
+			if !state.paused.contains_key(chain_id) {
+				changes.push(PauseChange::Paused {
+					chain_id: *chain_id,
+					reason: reason.clone(),
+				});
+			}
+		}
+		state.paused = paused;
+		changes
+	}
+}
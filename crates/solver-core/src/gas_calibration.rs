@@ -0,0 +1,124 @@
+//! Gas limit calibration for claim transactions.
+//!
+//! The gas a claim needs depends on the settlement path it takes: the settler
+//! contract, the chain, and the claim function called. The gas actually used by
+//! past claims is recorded per path, and future claims along the same path get
+//! a gas limit from a high percentile of those samples plus a safety margin,
+//! rather than a fixed limit that either wastes headroom or runs out of gas.
+
+use alloy_primitives::hex;
+use solver_storage::StorageService;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Storage namespace holding recorded samples per settlement path.
+const CLAIM_GAS_NAMESPACE: &str = "claim_gas";
+
+/// Basis point denominator used for the safety margin.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Calibrates claim gas limits from the gas used by past claims.
+pub struct ClaimGasCalibrator {
+	/// Storage service persisting samples across restarts.
+	storage: Arc<StorageService>,
+	/// Number of most recent samples kept per path.
+	window: usize,
+	/// Percentile of the samples the gas limit is based on.
+	percentile: f64,
+	/// Safety margin added on top of the percentile in basis points.
+	margin_bps: u64,
+	/// Samples required before a path's gas limit is calibrated.
+	min_samples: usize,
+	/// Recent samples keyed by settlement path, loaded lazily from storage.
+	samples: Mutex<HashMap<String, VecDeque<u64>>>,
+}
+
+impl ClaimGasCalibrator {
+	/// Creates a new calibrator.
+	pub fn new(
+		storage: Arc<StorageService>,
+		window: usize,
+		percentile: f64,
+		margin_bps: u64,
+		min_samples: usize,
+	) -> Self {
+		Self {
+			storage,
+			window,
+			percentile,
+			margin_bps,
+			min_samples,
+			samples: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the settlement path of a claim.
+	///
+	/// Claims sent by the same implementation on the same chain to the same
+	/// function share a path.
+	pub fn path(implementation: &str, chain_id: u64, data: &[u8]) -> String {
+		let selector = data.get(..4).unwrap_or(data);
+		format!("{}:{}:{}", implementation, chain_id, hex::encode(selector))
+	}
+
+	/// Records the gas used by a successful claim along a path.
+	pub async fn record(&self, path: &str, gas_used: u64) {
+		self.load(path).await;
+
+		let samples: Vec<u64> = {
+			let mut all = self.samples.lock().unwrap();
+			let samples = all.entry(path.to_string()).or_default();
+			samples.push_back(gas_used);
+			while samples.len() > self.window {
+				samples.pop_front();
+			}
+			samples.iter().copied().collect()
+		};
+
+		if let Err(e) = self
+			.storage
+			.store(CLAIM_GAS_NAMESPACE, path, &samples)
+			.await
+		{
+			tracing::warn!(path = %path, error = %e, "Failed to persist claim gas samples");
+		}
+	}
+
+	/// Returns the calibrated gas limit for claims along a path.
+	///
+	/// Returns `None` until enough claims along the path have been recorded.
+	pub async fn gas_limit(&self, path: &str) -> Option<u64> {
+		self.load(path).await;
+
+		let mut samples: Vec<u64> = {
+			let all = self.samples.lock().unwrap();
+			all.get(path)?.iter().copied().collect()
+		};
+		if samples.is_empty() || samples.len() < self.min_samples {
+			return None;
+		}
+
+		samples.sort_unstable();
+		let rank = (self.percentile / 100.0 * samples.len() as f64).ceil() as usize;
+		let base = samples[rank.clamp(1, samples.len()) - 1];
+		Some(base.saturating_mul(BPS_DENOMINATOR + self.margin_bps) / BPS_DENOMINATOR)
+	}
+
+	/// Loads a path's samples from storage unless they are already in memory.
+	async fn load(&self, path: &str) {
+		if self.samples.lock().unwrap().contains_key(path) {
+			return;
+		}
+
+		let stored: Vec<u64> = self
+			.storage
+			.retrieve(CLAIM_GAS_NAMESPACE, path)
+			.await
+			.unwrap_or_default();
+		self.samples
+			.lock()
+			.unwrap()
+			.entry(path.to_string())
+			.or_insert_with(|| stored.into_iter().collect());
+	}
+}
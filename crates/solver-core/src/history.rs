@@ -0,0 +1,228 @@
+//! Reconstruction of past engine state from the event journal.
+//!
+//! Every event the engine observes is journaled with the time it was
+//! recorded. Replaying the journal up to a point in time yields the stage
+//! each order had reached then, which answers incident questions such as
+//! which orders were in flight when an RPC endpoint went down.
+
+use solver_types::{
+	DeliveryEvent, DiscoveryEvent, OrderEvent, SequencedEvent, SettlementEvent, SolverEvent,
+	TransactionHash, TransactionType,
+};
+use std::collections::HashMap;
+
+/// Stage of an order's lifecycle as recorded in the event journal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStage {
+	/// The intent was discovered but not validated yet.
+	Discovered,
+	/// The intent was validated into an order awaiting a strategy decision.
+	Validated,
+	/// The strategy deferred the order.
+	Deferred,
+	/// The strategy decided to execute the order.
+	Executing,
+	/// A fill transaction was submitted.
+	FillPending,
+	/// The fill transaction confirmed.
+	Filled,
+	/// A fill proof was generated.
+	ProofReady,
+	/// The order can be claimed.
+	ClaimReady,
+	/// A claim transaction was submitted.
+	ClaimPending,
+	/// The intent failed validation.
+	Rejected,
+	/// A reorg dropped the intent from the origin chain.
+	Invalidated,
+	/// The strategy skipped the order.
+	Skipped,
+	/// The order passed its fill deadline without being executed.
+	Expired,
+	/// Another solver filled the order first.
+	FilledByOther,
+	/// A transaction for the order failed.
+	Failed,
+	/// The order was claimed.
+	Completed,
+}
+
+impl OrderStage {
+	/// Returns whether the order is finished with at this stage.
+	pub fn is_terminal(self) -> bool {
+		matches!(
+			self,
+			Self::Rejected
+				| Self::Invalidated
+				| Self::Skipped
+				| Self::Expired
+				| Self::FilledByOther
+				| Self::Failed
+				| Self::Completed
+		)
+	}
+}
+
+/// State of an order at a point in time.
+#[derive(Debug, Clone)]
+pub struct OrderState {
+	/// ID of the order.
+	pub order_id: String,
+	/// Stage the order had reached.
+	pub stage: OrderStage,
+	/// When the order entered its stage, in Unix seconds.
+	pub since: u64,
+	/// Latest transaction submitted for the order, if any.
+	pub tx_hash: Option<TransactionHash>,
+	/// Sequence of the last journaled event affecting the order.
+	pub last_sequence: u64,
+}
+
+/// Replays journaled events into the state of each order.
+#[derive(Default)]
+pub struct StateReconstruction {
+	/// Order states keyed by order ID.
+	orders: HashMap<String, OrderState>,
+	/// Order IDs keyed by the hash of transactions submitted for them.
+	tx_orders: HashMap<Vec<u8>, String>,
+}
+
+impl StateReconstruction {
+	/// Applies a journaled event.
+	///
+	/// Events must be applied in sequence order.
+	pub fn apply(&mut self, event: &SequencedEvent) {
+		let (order_id, stage, tx_hash) = match &event.event {
+			SolverEvent::Discovery(event) => match event {
+				DiscoveryEvent::IntentDiscovered { intent } => {
+					(intent.id.clone(), OrderStage::Discovered, None)
+				}
+				DiscoveryEvent::IntentValidated { order, .. } => {
+					(order.id.clone(), OrderStage::Validated, None)
+				}
+				DiscoveryEvent::IntentRejected { intent_id, .. } => {
+					(intent_id.clone(), OrderStage::Rejected, None)
+				}
+				DiscoveryEvent::IntentInvalidated { intent_id, .. } => {
+					(intent_id.clone(), OrderStage::Invalidated, None)
+				}
+				DiscoveryEvent::IntakeSaturated { .. } => return,
+			},
+			SolverEvent::Order(event) => match event {
+				OrderEvent::Executing { order, .. } => {
+					(order.id.clone(), OrderStage::Executing, None)
+				}
+				OrderEvent::Skipped { order_id, .. } => {
+					(order_id.clone(), OrderStage::Skipped, None)
+				}
+				OrderEvent::Deferred { order_id, .. } => {
+					(order_id.clone(), OrderStage::Deferred, None)
+				}
+				OrderEvent::Expired { order_id } => (order_id.clone(), OrderStage::Expired, None),
+				OrderEvent::FilledByOther { order_id } => {
+					(order_id.clone(), OrderStage::FilledByOther, None)
+				}
+			},
+			SolverEvent::Delivery(event) => match event {
+				DeliveryEvent::TransactionPending {
+					order_id,
+					tx_hash,
+					tx_type,
+					..
+				}
+				| DeliveryEvent::TransactionReorged {
+					order_id,
+					tx_hash,
+					tx_type,
+					..
+				} => (order_id.clone(), pending_stage(*tx_type), Some(tx_hash)),
+				DeliveryEvent::TransactionReplaced {
+					order_id,
+					new_tx_hash,
+					tx_type,
+					..
+				} => (order_id.clone(), pending_stage(*tx_type), Some(new_tx_hash)),
+				DeliveryEvent::TransactionConfirmed {
+					tx_hash, tx_type, ..
+				} => {
+					let Some(order_id) = self.tx_orders.get(&tx_hash.0) else {
+						return;
+					};
+					let stage = match tx_type {
+						TransactionType::Fill => OrderStage::Filled,
+						TransactionType::Claim => OrderStage::Completed,
+					};
+					(order_id.clone(), stage, Some(tx_hash))
+				}
+				DeliveryEvent::TransactionFailed { tx_hash, .. } => {
+					let Some(order_id) = self.tx_orders.get(&tx_hash.0) else {
+						return;
+					};
+					(order_id.clone(), OrderStage::Failed, Some(tx_hash))
+				}
+				DeliveryEvent::ProviderDegraded { .. }
+				| DeliveryEvent::FillsPaused { .. }
+				| DeliveryEvent::FillsResumed { .. } => return,
+			},
+			SolverEvent::Settlement(event) => match event {
+				SettlementEvent::FillDetected { order_id, tx_hash } => {
+					(order_id.clone(), OrderStage::Filled, Some(tx_hash))
+				}
+				SettlementEvent::ProofReady { order_id, .. } => {
+					(order_id.clone(), OrderStage::ProofReady, None)
+				}
+				SettlementEvent::ClaimReady { order_id } => {
+					(order_id.clone(), OrderStage::ClaimReady, None)
+				}
+				SettlementEvent::Completed { order_id } => {
+					(order_id.clone(), OrderStage::Completed, None)
+				}
+			},
+		};
+
+		if let Some(tx_hash) = tx_hash {
+			self.tx_orders.insert(tx_hash.0.clone(), order_id.clone());
+		}
+
+		let state = self
+			.orders
+			.entry(order_id.clone())
+			.or_insert_with(|| OrderState {
+				order_id,
+				stage,
+				since: event.timestamp,
+				tx_hash: None,
+				last_sequence: event.sequence,
+			});
+		if state.stage != stage {
+			state.stage = stage;
+			state.since = event.timestamp;
+		}
+		if let Some(tx_hash) = tx_hash {
+			state.tx_hash = Some(tx_hash.clone());
+		}
+		state.last_sequence = event.sequence;
+	}
+
+	/// Returns the orders that had not reached a terminal stage, oldest stage
+	/// first.
+	pub fn in_flight(self) -> Vec<OrderState> {
+		let mut orders: Vec<OrderState> = self
+			.orders
+			.into_values()
+			.filter(|state| !state.stage.is_terminal())
+			.collect();
+		orders.sort_by_key(|state| (state.since, state.last_sequence));
+		orders
+	}
+}
+
+/// Returns the stage of an order whose transaction of the given type is
+/// waiting to be mined.
+fn pending_stage(tx_type: TransactionType) -> OrderStage {
+	match tx_type {
+		TransactionType::Fill => OrderStage::FillPending,
+		TransactionType::Claim => OrderStage::ClaimPending,
+	}
+}
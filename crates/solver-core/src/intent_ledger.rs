@@ -0,0 +1,132 @@
+//! Ledger of the intents the solver has processed.
+//!
+//! Discovery sources deliver an intent more than once when they backfill
+//! blocks after a restart or re-read a range they had already processed. The
+//! ledger records how far the processing of each intent got, so that such an
+//! intent is only processed again if no fill could have been sent for it.
+//!
+//! Every entry records the run that wrote it. An intent still being
+//! processed by the current run is a duplicate. An intent a previous run was
+//! filling when it stopped is reported for recovery: whether its fill landed
+//! has to be checked on-chain before the intent may be filled again.
+
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageError, StorageService};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Storage namespace holding the ledger entries, keyed by intent ID.
+const LEDGER_NAMESPACE: &str = "intent_ledger";
+
+/// How far the processing of an intent got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntentState {
+	/// The intent is being validated and decided; no fill was sent for it.
+	Processing,
+	/// A fill for the intent may have been sent.
+	Filling,
+	/// The intent was rejected or withdrawn, or its fill failed, and may be
+	/// processed again.
+	Dropped,
+	/// The order of the intent completed, expired or was skipped.
+	Finished,
+}
+
+/// Ledger entry of an intent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LedgerEntry {
+	/// How far the processing of the intent got.
+	state: IntentState,
+	/// Run of the solver that recorded the state.
+	run: u64,
+	/// Unix timestamp at which the state was recorded.
+	updated_at: u64,
+}
+
+/// Whether a delivered intent may be processed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+	/// The intent is new, or no fill was sent for it; it is now recorded as
+	/// processing.
+	Process,
+	/// A previous run may have sent a fill for the intent before it stopped.
+	Recover,
+	/// The intent is already being processed, or was processed to the end.
+	Duplicate(IntentState),
+}
+
+/// Storage-backed ledger of processed intents.
+pub struct IntentLedger {
+	/// Storage holding the ledger entries.
+	storage: Arc<StorageService>,
+	/// Identifier of the current run of the solver.
+	run: u64,
+	/// Serializes admissions, so that concurrent deliveries of an intent are
+	/// not both admitted.
+	admissions: Mutex<()>,
+}
+
+impl IntentLedger {
+	/// Creates a ledger backed by `storage` for a new run of the solver.
+	pub fn new(storage: Arc<StorageService>) -> Self {
+		let run = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos() as u64;
+		Self {
+			storage,
+			run,
+			admissions: Mutex::new(()),
+		}
+	}
+
+	/// Decides whether a delivered intent may be processed, recording it as
+	/// processing if so.
+	pub async fn admit(&self, intent_id: &str) -> Result<Admission, StorageError> {
+		let _guard = self.admissions.lock().await;
+		let admission = match self.entry(intent_id).await? {
+			None => Admission::Process,
+			Some(entry) => match entry.state {
+				IntentState::Dropped => Admission::Process,
+				IntentState::Finished => Admission::Duplicate(entry.state),
+				_ if entry.run == self.run => Admission::Duplicate(entry.state),
+				IntentState::Processing => Admission::Process,
+				IntentState::Filling => Admission::Recover,
+			},
+		};
+		if admission == Admission::Process {
+			self.record(intent_id, IntentState::Processing).await?;
+		}
+		Ok(admission)
+	}
+
+	/// Returns how far the processing of an intent got, if it was recorded.
+	pub async fn state(&self, intent_id: &str) -> Result<Option<IntentState>, StorageError> {
+		Ok(self.entry(intent_id).await?.map(|entry| entry.state))
+	}
+
+	/// Records how far the processing of an intent got.
+	pub async fn record(&self, intent_id: &str, state: IntentState) -> Result<(), StorageError> {
+		let entry = LedgerEntry {
+			state,
+			run: self.run,
+			updated_at: std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.unwrap()
+				.as_secs(),
+		};
+		self.storage
+			.store(LEDGER_NAMESPACE, intent_id, &entry)
+			.await
+	}
+
+	/// Reads the ledger entry of an intent.
+	async fn entry(&self, intent_id: &str) -> Result<Option<LedgerEntry>, StorageError> {
+		match self.storage.retrieve(LEDGER_NAMESPACE, intent_id).await {
+			Ok(entry) => Ok(Some(entry)),
+			Err(StorageError::NotFound) => Ok(None),
+			Err(e) => Err(e),
+		}
+	}
+}
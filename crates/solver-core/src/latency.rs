@@ -0,0 +1,94 @@
+//! Latency budgets for competitive orders.
+//!
+//! Orders that other solvers race for are only worth filling if the fill lands
+//! first. Each intent gets a budget when it is discovered, configurable per
+//! order standard or discovery source; once the budget is spent the solver
+//! considers the race lost and skips the order instead of submitting a fill
+//! that is likely to revert.
+
+use solver_types::Intent;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Timing of an intent being processed.
+struct Tracked {
+	/// When the intent was discovered.
+	discovered: Instant,
+	/// Budget applying to the intent, if any.
+	budget: Option<Duration>,
+}
+
+/// Tracks how long intents have been in flight against their budgets.
+#[derive(Default)]
+pub struct LatencyBudgets {
+	/// Budget applying to intents without a more specific budget.
+	default: Option<Duration>,
+	/// Budgets keyed by order standard.
+	standards: HashMap<String, Duration>,
+	/// Budgets keyed by discovery source; these take precedence over standards.
+	sources: HashMap<String, Duration>,
+	/// In-flight intents keyed by order ID.
+	tracked: Mutex<HashMap<String, Tracked>>,
+}
+
+impl LatencyBudgets {
+	/// Creates budgets from a default and per-standard and per-source overrides.
+	pub fn new(
+		default: Option<Duration>,
+		standards: HashMap<String, Duration>,
+		sources: HashMap<String, Duration>,
+	) -> Self {
+		Self {
+			default,
+			standards,
+			sources,
+			tracked: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Returns the budget for an intent from the given source and standard.
+	pub fn budget_for(&self, source: &str, standard: &str) -> Option<Duration> {
+		self.sources
+			.get(source)
+			.or_else(|| self.standards.get(standard))
+			.copied()
+			.or(self.default)
+	}
+
+	/// Starts the clock for a newly discovered intent.
+	pub fn start(&self, intent: &Intent) {
+		let budget = self.budget_for(&intent.source, &intent.standard);
+		self.tracked.lock().unwrap().insert(
+			intent.id.clone(),
+			Tracked {
+				discovered: Instant::now(),
+				budget,
+			},
+		);
+	}
+
+	/// Checks whether an order is still within its budget.
+	///
+	/// Returns the elapsed time as the error when the budget is exceeded.
+	/// Orders that are not tracked or have no budget always pass.
+	pub fn check(&self, order_id: &str) -> Result<(), Duration> {
+		let tracked = self.tracked.lock().unwrap();
+		match tracked.get(order_id) {
+			Some(Tracked {
+				discovered,
+				budget: Some(budget),
+			}) if discovered.elapsed() > *budget => Err(discovered.elapsed()),
+			_ => Ok(()),
+		}
+	}
+
+	/// Stops tracking an order and returns the time since its discovery.
+	pub fn finish(&self, order_id: &str) -> Option<Duration> {
+		self.tracked
+			.lock()
+			.unwrap()
+			.remove(order_id)
+			.map(|tracked| tracked.discovered.elapsed())
+	}
+}
@@ -5,25 +5,126 @@
 //! to execute the complete order lifecycle. It includes the event-driven architecture
 //! and factory pattern for building solver instances.
 
-use alloy_primitives::{hex, U256};
-use solver_account::AccountService;
+use accounting::{EntryKind, Ledger, LedgerAccount, LedgerAsset, LedgerError, Posting};
+use alloy_primitives::{hex, I256, U256};
+use approvals::{ApprovalManager, ApprovalMode, ApprovalTarget};
+use audit::AuditLog;
+use bootstrap::{
+	BootstrapPlan, Corridor, FundingResult, InventoryBootstrapper, InventoryRecommendation,
+};
+use bundling::{FillBundler, QueuedFill};
+use capital::CapitalTracker;
+use chain_tasks::{ChainTaskGroups, FailedTask};
+use checkpoint::{CheckpointFile, PreviousRun, ShutdownCheckpoint};
+use claim_batch::{BatchKey, ClaimBatch, ClaimBatcher};
+use claim_schedule::{ClaimScheduler, ClaimTiming};
+use context::ContextBuilder;
+use control::{ControlCommand, ControlError, ControlMessage, ControlReply};
+use dead_letter::{DeadLetterQueue, RetryPolicy};
+use deferral::DeferredOrders;
+use dry_run::{DryRun, DRY_RUN_SKIP_REASON};
+use expiry::ExpiryWatcher;
+use gas_budget::{GasBudget, PauseChange};
+use gas_calibration::ClaimGasCalibrator;
+use history::{OrderState, StateReconstruction};
+use intent_ledger::{Admission, IntentLedger, IntentState};
+use latency::LatencyBudgets;
+use metrics::EngineMetrics;
+use pnl::PnlRecorder;
+use polling::PollBackoff;
+use priority::{IntentQueue, PriorityRegistry};
+use readiness::{InventoryRequirement, ReadinessChecker};
+use reorg::{ReorgMonitor, TrackedIntent, TrackedReceipt};
+use replacement::PendingTransactions;
+use retention::{OrderPruner, RetentionPolicy};
+use retry::{RetryQueue, Work};
+use routing::RouteSelector;
+use solver_account::{AccountError, AccountService};
 use solver_config::Config;
-use solver_delivery::{DeliveryError, DeliveryService};
-use solver_discovery::DiscoveryService;
-use solver_order::OrderService;
-use solver_settlement::SettlementService;
-use solver_storage::StorageService;
+use solver_delivery::gas::{FeeMode, GasEstimator, PercentileGasEstimator};
+use solver_delivery::{ConfirmationPolicy, DeliveryError, DeliveryService};
+use solver_discovery::{
+	intake, DiscoveryError, DiscoveryService, IntakeLimits, IntentReceiver, OverflowPolicy,
+};
+use solver_liquidity::inventory::InventoryService;
+use solver_liquidity::LiquidityService;
+use solver_oracles::implementations::chainlink::ChainlinkPriceOracle;
+use solver_oracles::implementations::fixed::FixedPriceOracle;
+use solver_oracles::PriceOracle;
+use solver_order::classification::OrderClass;
+use solver_order::features::StandardFeatures;
+use solver_order::implementations::strategies::composite::create_composite_strategy;
+use solver_order::intake::IntakeFilter;
+use solver_order::policy::{IntentPolicy, PolicyLists};
+use solver_order::stablecoin::{StablecoinFastPath, StablecoinToken};
+use solver_order::{OrderError, OrderService};
+use solver_settlement::delegation::{
+	authorization_message, ClaimDelegate, ClaimDelegation, HttpClaimDelegate,
+};
+use solver_settlement::{SettlementError, SettlementService};
+use solver_storage::cache::RpcCache;
+use solver_storage::compression::CompressionPolicy;
+use solver_storage::{StorageError, StorageService};
 use solver_types::{
-	DeliveryEvent, DiscoveryEvent, EventBus, ExecutionContext, ExecutionDecision, Intent, Order,
-	OrderEvent, SettlementEvent, SolverEvent, TransactionType,
+	ClaimActionResponse, ClaimEstimate, Classify, DeadLetter, DeliveryEvent, DetailedIntentStatus,
+	DiscoveryEvent, ErrorCategory, ErrorClass, EventBus, ExecutePreviewRequest,
+	ExecutePreviewResponse, ExecutionContext, ExecutionDecision, FailureStage, GasToken,
+	IntakeState, IntakeStatusResponse, Intent, NetworkInfo, NetworkRegistry, Order, OrderAssets,
+	OrderEvent, OrderHistoryResponse, OrderOutcome, OrderOutcomeStatus, OrderStatusResponse,
+	OrderTimelineResponse, Page, Precondition, PreviewDecision, PreviewSimulation,
+	PreviewTransaction, PriorityRule, PriorityTarget, ReadinessResponse, SequencedEvent,
+	SequencedEventRef, SetPrioritiesRequest, SettlementEvent, SolverCapabilities, SolverEvent,
+	SupportedStandard, Transaction, TransactionType, UnmetPrecondition,
 };
-use std::collections::HashMap;
-use std::sync::Arc;
+use solver_validators::implementations::liquidity::LiquidityValidator;
+use solver_validators::{ValidationPipeline, ValidationRequest, ValidatorInterface};
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use submissions::SubmissionLog;
 use thiserror::Error;
+use timeline::OrderTimeline;
 use tokio::sync::mpsc;
-use tracing::instrument;
+use tracing::{instrument, Instrument};
 
+pub mod accounting;
+pub mod approvals;
+pub mod audit;
+mod balances;
+pub mod bootstrap;
+pub mod bundling;
+pub mod capital;
+pub mod chain_tasks;
+pub mod checkpoint;
+pub mod claim_batch;
+pub mod claim_schedule;
+pub mod context;
+pub mod control;
+pub mod dead_letter;
+pub mod deferral;
+pub mod dry_run;
+mod erc20;
 pub mod event_bus;
+pub mod expiry;
+pub mod gas_budget;
+pub mod gas_calibration;
+pub mod history;
+pub mod intent_ledger;
+pub mod latency;
+pub mod metrics;
+pub mod pnl;
+pub mod polling;
+pub mod priority;
+pub mod readiness;
+pub mod reload;
+pub mod reorg;
+pub mod replacement;
+pub mod retention;
+pub mod retry;
+pub mod routing;
+pub mod submissions;
+pub mod timeline;
 
 /// Utility function to truncate a hex string for display purposes.
 ///
@@ -36,6 +137,523 @@ fn truncate_id(id: &str) -> String {
 	}
 }
 
+/// Appends an event to the event journal, returning its sequence number.
+async fn append_event(storage: &StorageService, event: &SolverEvent) -> Result<u64, StorageError> {
+	let sequence = storage.next_sequence("events").await?;
+	storage
+		.store(
+			"events",
+			&sequence.to_string(),
+			&SequencedEventRef {
+				sequence,
+				timestamp: now_secs(),
+				event,
+			},
+		)
+		.await?;
+	Ok(sequence)
+}
+
+/// Journals the events published on a persistent bus, then hands them to the
+/// engine and delivers them to the bus's subscribers.
+///
+/// Events that cannot be journaled are still handed on, with sequence number
+/// 0, rather than held back.
+async fn journal_published_events(
+	storage: Arc<StorageService>,
+	event_bus: EventBus,
+	mut published: mpsc::UnboundedReceiver<SolverEvent>,
+	journaled: mpsc::UnboundedSender<(u64, SolverEvent)>,
+) {
+	while let Some(event) = published.recv().await {
+		let sequence = match append_event(&storage, &event).await {
+			Ok(sequence) => sequence,
+			Err(e) => {
+				tracing::error!(error = %e, "Failed to journal event");
+				0
+			}
+		};
+		let _ = event_bus.deliver(event.clone());
+		if journaled.send((sequence, event)).is_err() {
+			break;
+		}
+	}
+}
+
+/// Returns the current unix timestamp in seconds.
+fn now_secs() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
+
+/// Skips an order whose latency budget ran out before its fill was submitted.
+fn skip_late_order(
+	latency: &LatencyBudgets,
+	metrics: &EngineMetrics,
+	event_bus: &EventBus,
+	order_id: String,
+	elapsed: Duration,
+) {
+	latency.finish(&order_id);
+	metrics.record_latency_budget_exceeded();
+	tracing::info!(
+		elapsed_ms = elapsed.as_millis() as u64,
+		"Skipping order after latency budget ran out"
+	);
+	event_bus
+		.publish(SolverEvent::Order(OrderEvent::Skipped {
+			order_id,
+			reason: format!("Latency budget exceeded after {} ms", elapsed.as_millis()),
+		}))
+		.ok();
+}
+
+/// Returns the stage the orders an event concerns failed at, when handling
+/// the event failed.
+fn failure_stage(event: &SolverEvent) -> FailureStage {
+	match event {
+		SolverEvent::Discovery(_) => FailureStage::Validation,
+		SolverEvent::Settlement(_) => FailureStage::Claim,
+		SolverEvent::Delivery(
+			DeliveryEvent::TransactionPending { tx_type, .. }
+			| DeliveryEvent::TransactionConfirmed { tx_type, .. }
+			| DeliveryEvent::TransactionReplaced { tx_type, .. }
+			| DeliveryEvent::TransactionReorged { tx_type, .. },
+		) if *tx_type == TransactionType::Claim => FailureStage::Claim,
+		_ => FailureStage::Fill,
+	}
+}
+
+/// Skips an order whose fill reverted in simulation, before it cost any gas.
+fn skip_reverting_fill(event_bus: &EventBus, order_id: &str, reason: String) {
+	tracing::warn!(
+		order_id = %truncate_id(order_id),
+		reason = %reason,
+		"Skipping order whose fill would revert"
+	);
+	event_bus
+		.publish(SolverEvent::Order(OrderEvent::Skipped {
+			order_id: order_id.to_string(),
+			reason: format!("Fill simulation failed: {}", reason),
+		}))
+		.ok();
+}
+
+/// Ensures the settler of a fill may pull the output tokens of the filled
+/// orders from the solver, approving them where the allowance falls short.
+///
+/// Outputs in the chain's gas token are sent along with the fill and need no
+/// approval.
+async fn approve_fill(
+	approvals: &ApprovalManager,
+	account: &AccountService,
+	order_service: &OrderService,
+	orders: &[Arc<Order>],
+	tx: &Transaction,
+) -> Result<(), String> {
+	let Some(spender) = &tx.to else {
+		return Ok(());
+	};
+	let mut required: HashMap<solver_types::Address, U256> = HashMap::new();
+	for order in orders {
+		let assets = order_service
+			.order_assets(order)
+			.map_err(|e| e.to_string())?;
+		for output in assets
+			.outputs
+			.into_iter()
+			.filter(|output| output.chain_id == tx.chain_id)
+			.filter(|output| output.token.0.iter().any(|byte| *byte != 0))
+		{
+			let amount = required.entry(output.token).or_default();
+			*amount = amount.saturating_add(output.amount);
+		}
+	}
+	if required.is_empty() {
+		return Ok(());
+	}
+
+	let owner = account.get_address().await.map_err(|e| e.to_string())?;
+	for (token, amount) in required {
+		let target = ApprovalTarget {
+			chain_id: tx.chain_id,
+			token,
+			spender: spender.clone(),
+		};
+		approvals
+			.ensure(&owner, &target, amount)
+			.await
+			.map_err(|e| e.to_string())?;
+	}
+	Ok(())
+}
+
+/// Returns whether another solver already filled an order on its
+/// destination chain.
+///
+/// A check that cannot be made is logged and treated as unfilled, so that a
+/// flaky RPC endpoint never holds up fills.
+async fn filled_by_other(
+	order_service: &OrderService,
+	delivery: &DeliveryService,
+	order: &Order,
+) -> bool {
+	let result = match order_service.fill_check_call(order) {
+		Ok(Some(call)) => delivery
+			.call(&call)
+			.await
+			.map_err(|e| e.to_string())
+			.and_then(|result| {
+				order_service
+					.is_filled(order, &result)
+					.map_err(|e| e.to_string())
+			}),
+		Ok(None) => return false,
+		Err(e) => Err(e.to_string()),
+	};
+	result.unwrap_or_else(|error| {
+		tracing::warn!(
+			order_id = %truncate_id(&order.id),
+			error = %error,
+			"Failed to check whether order was filled by another solver"
+		);
+		false
+	})
+}
+
+/// Gives up on an order another solver filled first, instead of paying gas
+/// for a fill that can only fail.
+fn flag_filled_by_other(latency: &LatencyBudgets, event_bus: &EventBus, order_id: &str) {
+	latency.finish(order_id);
+	tracing::info!(
+		order_id = %truncate_id(order_id),
+		"Order already filled by another solver"
+	);
+	event_bus
+		.publish(SolverEvent::Order(OrderEvent::FilledByOther {
+			order_id: order_id.to_string(),
+		}))
+		.ok();
+}
+
+/// Returns the postings paying a transaction's gas from inventory.
+fn gas_postings(chain_id: u64, receipt: &solver_types::TransactionReceipt) -> Vec<Posting> {
+	let gas = U256::from(receipt.gas_used).saturating_mul(receipt.effective_gas_price);
+	let gas_asset = LedgerAsset {
+		chain_id,
+		token: None,
+	};
+	vec![
+		Posting::debit(LedgerAccount::GasFees, gas_asset.clone(), gas),
+		Posting::credit(LedgerAccount::Inventory, gas_asset, gas),
+	]
+}
+
+/// Scales an order's outputs to a fill delivering `amount` of the first one.
+fn prorate_outputs(
+	outputs: &[solver_types::TokenAmount],
+	amount: U256,
+) -> Vec<solver_types::TokenAmount> {
+	let Some(total) = outputs.first().map(|output| output.amount) else {
+		return Vec::new();
+	};
+	if total.is_zero() {
+		return outputs.to_vec();
+	}
+	outputs
+		.iter()
+		.map(|output| solver_types::TokenAmount {
+			amount: output.amount.saturating_mul(amount) / total,
+			..output.clone()
+		})
+		.collect()
+}
+
+/// Hands a claim to a claim bot, signing the authorization the bot expects,
+/// and records the claim transaction the bot submitted against its order.
+async fn delegate_claim(
+	delegate: &dyn ClaimDelegate,
+	account: &AccountService,
+	storage: &StorageService,
+	event_bus: &EventBus,
+	order_id: &str,
+	claim_tx: &Transaction,
+	fill_proof: solver_types::FillProof,
+) -> Result<(), SolverError> {
+	let solver = account.get_address().await.map_err(SolverError::from)?;
+	let signature = account
+		.sign_message(&authorization_message(order_id, &fill_proof))
+		.await
+		.map_err(SolverError::from)?;
+
+	let delegation = ClaimDelegation::new(order_id, claim_tx, fill_proof, &solver, &signature);
+	let tx_hash = delegate
+		.delegate(&delegation)
+		.await
+		.map_err(SolverError::from)?;
+	tracing::info!(
+		tx_hash = %hex::encode(&tx_hash.0),
+		"Claim delegated"
+	);
+
+	event_bus
+		.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+			order_id: order_id.to_string(),
+			tx_hash: tx_hash.clone(),
+			tx_type: TransactionType::Claim,
+			chain_id: claim_tx.chain_id,
+		}))
+		.ok();
+	storage
+		.store("claims", order_id, &tx_hash)
+		.await
+		.map_err(SolverError::from)?;
+	storage
+		.store("tx_to_order", &hex::encode(&tx_hash.0), &order_id)
+		.await
+		.map_err(SolverError::from)
+}
+
+/// Submits a fill or claim transaction and records it against its order.
+///
+/// Private transactions go through the chain's private route, if it has one.
+/// Fills pass the intent ledger: the intent is recorded as filling before
+/// the transaction is delivered, so that a restart does not fill it again
+/// unchecked, and as processing again if delivery fails, since then nothing
+/// was broadcast.
+#[allow(clippy::too_many_arguments)]
+async fn submit_transaction(
+	delivery: &DeliveryService,
+	storage: &StorageService,
+	event_bus: &EventBus,
+	intent_ledger: Option<&IntentLedger>,
+	order_id: &str,
+	tx: Transaction,
+	tx_type: TransactionType,
+	private: bool,
+) -> Result<(), SolverError> {
+	let chain_id = tx.chain_id;
+	if let Some(intent_ledger) = intent_ledger {
+		intent_ledger
+			.record(order_id, IntentState::Filling)
+			.await
+			.map_err(SolverError::from)?;
+	}
+	let result = if private {
+		delivery.deliver_private(tx).await
+	} else {
+		delivery.deliver(tx).await
+	};
+	if let (Err(_), Some(intent_ledger)) = (&result, intent_ledger) {
+		intent_ledger
+			.record(order_id, IntentState::Processing)
+			.await
+			.map_err(SolverError::from)?;
+	}
+	let tx_hash = match result {
+		Ok(tx_hash) => tx_hash,
+		Err(DeliveryError::SimulationFailed(reason)) if tx_type == TransactionType::Fill => {
+			skip_reverting_fill(event_bus, order_id, reason);
+			return Ok(());
+		}
+		Err(e) => return Err(e.into()),
+	};
+
+	event_bus
+		.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+			order_id: order_id.to_string(),
+			tx_hash: tx_hash.clone(),
+			tx_type,
+			chain_id,
+		}))
+		.ok();
+
+	// Store the transaction hash under the order
+	let namespace = match tx_type {
+		TransactionType::Fill => "fills",
+		TransactionType::Claim => "claims",
+	};
+	storage
+		.store(namespace, order_id, &tx_hash)
+		.await
+		.map_err(SolverError::from)?;
+
+	// Store reverse mapping: tx_hash -> order_id
+	storage
+		.store("tx_to_order", &hex::encode(&tx_hash.0), &order_id)
+		.await
+		.map_err(SolverError::from)?;
+
+	Ok(())
+}
+
+/// Submits a transaction filling several orders and records it against each
+/// of them.
+///
+/// The transaction is monitored once, under the first order. The orders it
+/// fills are stored under its hash, so that its confirmation can be traced
+/// back to all of them. Its intents are recorded in the intent ledger like
+/// those of a single fill.
+async fn submit_bundle(
+	delivery: &DeliveryService,
+	storage: &StorageService,
+	event_bus: &EventBus,
+	intent_ledger: &IntentLedger,
+	order_ids: &[String],
+	tx: Transaction,
+) -> Result<(), SolverError> {
+	let Some(first) = order_ids.first() else {
+		return Ok(());
+	};
+	let chain_id = tx.chain_id;
+	for order_id in order_ids {
+		intent_ledger
+			.record(order_id, IntentState::Filling)
+			.await
+			.map_err(SolverError::from)?;
+	}
+	let result = delivery.deliver(tx).await;
+	if result.is_err() {
+		for order_id in order_ids {
+			intent_ledger
+				.record(order_id, IntentState::Processing)
+				.await
+				.map_err(SolverError::from)?;
+		}
+	}
+	let tx_hash = match result {
+		Ok(tx_hash) => tx_hash,
+		Err(DeliveryError::SimulationFailed(reason)) => {
+			for order_id in order_ids {
+				skip_reverting_fill(event_bus, order_id, reason.clone());
+			}
+			return Ok(());
+		}
+		Err(e) => return Err(e.into()),
+	};
+
+	let tx_key = hex::encode(&tx_hash.0);
+	storage
+		.store("bundles", &tx_key, &order_ids.to_vec())
+		.await
+		.map_err(SolverError::from)?;
+	for order_id in order_ids {
+		storage
+			.store("fills", order_id, &tx_hash)
+			.await
+			.map_err(SolverError::from)?;
+	}
+	storage
+		.store("tx_to_order", &tx_key, first)
+		.await
+		.map_err(SolverError::from)?;
+
+	event_bus
+		.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+			order_id: first.clone(),
+			tx_hash,
+			tx_type: TransactionType::Fill,
+			chain_id,
+		}))
+		.ok();
+
+	Ok(())
+}
+
+/// Checks the tracked blocks of a chain for reorgs.
+///
+/// Intents and transactions in reorganized blocks are tracked again if they
+/// were re-included elsewhere. Otherwise intents are invalidated and
+/// transactions are reported as reorged.
+async fn check_chain_reorgs(
+	reorgs: &ReorgMonitor,
+	delivery: &DeliveryService,
+	event_bus: &EventBus,
+	chain_id: u64,
+) -> Result<(), SolverError> {
+	let head = delivery
+		.block_number(chain_id)
+		.await
+		.map_err(SolverError::from)?;
+	let mut hashes = Vec::new();
+	for number in reorgs.heights(chain_id) {
+		let hash = delivery
+			.block_hash(chain_id, number)
+			.await
+			.map_err(SolverError::from)?;
+		hashes.push((number, hash));
+	}
+
+	let (intents, receipts) = reorgs.reconcile(chain_id, head, &hashes);
+	for intent in intents {
+		match delivery.receipt_on(chain_id, &intent.tx_hash).await {
+			Ok(receipt) if receipt.success => {
+				reorgs.track_intent(
+					chain_id,
+					TrackedIntent {
+						block_number: receipt.block_number,
+						..intent
+					},
+				);
+			}
+			_ => {
+				tracing::warn!(
+					order_id = %truncate_id(&intent.order_id),
+					block_number = intent.block_number,
+					"Intent dropped by reorg"
+				);
+				reorgs.invalidate(&intent.order_id);
+				event_bus
+					.publish(SolverEvent::Discovery(DiscoveryEvent::IntentInvalidated {
+						intent_id: intent.order_id,
+						reason: format!(
+							"Opening transaction dropped by reorg at block {}",
+							intent.block_number
+						),
+					}))
+					.ok();
+			}
+		}
+	}
+
+	for receipt in receipts {
+		// Receipts cached before the reorg no longer describe the chain
+		delivery.forget_receipt(&receipt.tx_hash).await;
+		match delivery.receipt_on(chain_id, &receipt.tx_hash).await {
+			Ok(included) => {
+				reorgs.track_receipt(
+					chain_id,
+					TrackedReceipt {
+						block_number: included.block_number,
+						..receipt
+					},
+				);
+			}
+			Err(_) => {
+				tracing::warn!(
+					order_id = %truncate_id(&receipt.order_id),
+					tx_hash = %truncate_id(&hex::encode(&receipt.tx_hash.0)),
+					block_number = receipt.block_number,
+					"Transaction dropped by reorg"
+				);
+				reorgs.mark_reorged(&receipt.tx_hash);
+				event_bus
+					.publish(SolverEvent::Delivery(DeliveryEvent::TransactionReorged {
+						order_id: receipt.order_id,
+						tx_hash: receipt.tx_hash,
+						tx_type: receipt.tx_type,
+						chain_id,
+					}))
+					.ok();
+			}
+		}
+	}
+
+	Ok(())
+}
+
 /// Errors that can occur during solver operations.
 #[derive(Debug, Error)]
 pub enum SolverError {
@@ -45,6 +663,80 @@ pub enum SolverError {
 	/// Error from one of the solver services.
 	#[error("Service error: {0}")]
 	Service(String),
+	/// Error from one of the solver services, classified by its cause.
+	#[error("{} error: {message}", .class.category)]
+	Classified {
+		/// Class of the error.
+		class: ErrorClass,
+		/// Description of the error.
+		message: String,
+	},
+}
+
+impl SolverError {
+	/// Wraps a service error, keeping its class.
+	fn classified<E: Classify + std::fmt::Display>(error: E) -> Self {
+		Self::Classified {
+			class: error.class(),
+			message: error.to_string(),
+		}
+	}
+}
+
+impl Classify for SolverError {
+	/// Returns the class of the error.
+	///
+	/// Errors not raised by a service, such as invalid configuration or an
+	/// inconsistency in the solver's own state, are permanent.
+	fn class(&self) -> ErrorClass {
+		match self {
+			Self::Config(_) => ErrorClass::permanent(ErrorCategory::Configuration),
+			Self::Service(_) => ErrorClass::permanent(ErrorCategory::Internal),
+			Self::Classified { class, .. } => *class,
+		}
+	}
+}
+
+impl From<StorageError> for SolverError {
+	fn from(error: StorageError) -> Self {
+		Self::classified(error)
+	}
+}
+
+impl From<AccountError> for SolverError {
+	fn from(error: AccountError) -> Self {
+		Self::classified(error)
+	}
+}
+
+impl From<DeliveryError> for SolverError {
+	fn from(error: DeliveryError) -> Self {
+		Self::classified(error)
+	}
+}
+
+impl From<DiscoveryError> for SolverError {
+	fn from(error: DiscoveryError) -> Self {
+		Self::classified(error)
+	}
+}
+
+impl From<OrderError> for SolverError {
+	fn from(error: OrderError) -> Self {
+		Self::classified(error)
+	}
+}
+
+impl From<SettlementError> for SolverError {
+	fn from(error: SettlementError) -> Self {
+		Self::classified(error)
+	}
+}
+
+impl From<LedgerError> for SolverError {
+	fn from(error: LedgerError) -> Self {
+		Self::classified(error)
+	}
 }
 
 /// Main solver engine that orchestrates the order execution lifecycle.
@@ -58,8 +750,15 @@ pub enum SolverError {
 pub struct SolverEngine {
 	/// Solver configuration.
 	config: Config,
+	/// Configuration in effect, including sections reloaded since startup.
+	reloaded: RwLock<Arc<Config>>,
+	/// Factories rebuilding execution strategies when their configuration is
+	/// reloaded, keyed by strategy type.
+	strategy_factories: HashMap<String, StrategyFactory>,
 	/// Storage service for persisting state.
 	storage: Arc<StorageService>,
+	/// Account service providing the solver's address.
+	account: Arc<AccountService>,
 	/// Delivery service for blockchain transactions.
 	delivery: Arc<DeliveryService>,
 	/// Discovery service for finding new orders.
@@ -68,71 +767,513 @@ pub struct SolverEngine {
 	order: Arc<OrderService>,
 	/// Settlement service for monitoring and claiming.
 	settlement: Arc<SettlementService>,
+	/// Risk validators run on new orders, if configured.
+	validation: Option<ValidationPipeline>,
 	/// Event bus for inter-service communication.
 	event_bus: EventBus,
+	/// Events published on a persistent bus awaiting the journal, until the
+	/// journal is started.
+	published_events: std::sync::Mutex<Option<mpsc::UnboundedReceiver<SolverEvent>>>,
+	/// Sender of operator commands to the event loop.
+	control: mpsc::UnboundedSender<ControlMessage>,
+	/// Operator commands awaiting the event loop, until it is started.
+	control_commands: std::sync::Mutex<Option<mpsc::UnboundedReceiver<ControlMessage>>>,
+	/// Event loop metrics.
+	metrics: Arc<EngineMetrics>,
+	/// Token approvals pre-warmed at startup and checked before fills, if
+	/// configured.
+	approvals: Option<Arc<ApprovalManager>>,
+	/// Externally injected intent priorities.
+	priorities: Arc<PriorityRegistry>,
+	/// Inventory bootstrap assistant, if configured.
+	bootstrapper: Option<InventoryBootstrapper>,
+	/// Economic readiness checks, if configured.
+	readiness: Option<ReadinessChecker>,
+	/// Polled balances of the solver's token inventory, if configured.
+	inventory: Option<Arc<InventoryService>>,
+	/// Latency budgets of in-flight orders.
+	latency: Arc<LatencyBudgets>,
+	/// Double-entry ledger of the solver's finances.
+	ledger: Ledger,
+	/// Profit and loss records of completed orders.
+	pnl: PnlRecorder,
+	/// Transactions awaiting confirmation.
+	pending: Arc<PendingTransactions>,
+	/// Task groups isolating the work of each chain.
+	chain_tasks: ChainTaskGroups,
+	/// Work whose chain task failed awaiting the event loop, until it is
+	/// started.
+	task_failures: std::sync::Mutex<Option<mpsc::UnboundedReceiver<FailedTask>>>,
+	/// Checkpoint file for detecting crashed runs, if configured.
+	checkpoints: Option<CheckpointFile>,
+	/// Claim gas limit calibration, if configured.
+	claim_gas: Option<ClaimGasCalibrator>,
+	/// Claim bot claims are delegated to, if configured.
+	claim_delegate: Option<Arc<dyn ClaimDelegate>>,
+	/// Holding back of claims while gas is expensive or claims unprofitable,
+	/// if configured.
+	claim_schedule: Option<ClaimScheduler>,
+	/// Batching of ready claims by chain and settlement implementation, if
+	/// configured.
+	claim_batcher: Option<ClaimBatcher>,
+	/// Reorg detection for recent intents and receipts, if configured.
+	reorgs: Option<Arc<ReorgMonitor>>,
+	/// Inventory committed to orders between fill and claim.
+	capital: CapitalTracker,
+	/// Builder of execution contexts from cached chain data.
+	context: Arc<ContextBuilder>,
+	/// Simulator standing in for fill submission, in dry runs.
+	dry_run: Option<Arc<DryRun>>,
+	/// Selector of the destination chain of orders fillable on several chains.
+	routes: RouteSelector,
+	/// Bundler holding back fills that can share a transaction, if configured.
+	bundler: Option<FillBundler>,
+	/// Deferred orders waiting to be decided again.
+	deferred: DeferredOrders,
+	/// Failed orders held for re-driving.
+	dead_letters: DeadLetterQueue,
+	/// Intents and events waiting to be handled again after failing
+	/// transiently.
+	retries: RetryQueue,
+	/// Submitted intents, persisted until handled so they can be acknowledged.
+	submissions: SubmissionLog,
+	/// Ledger of processed intents, keeping re-delivered intents from being
+	/// filled twice.
+	intent_ledger: Arc<IntentLedger>,
+	/// Append-only log of the events concerning each order.
+	audit: AuditLog,
+	/// Fill deadlines of orders whose fill has not been submitted.
+	expiry: Arc<ExpiryWatcher>,
+	/// Gas spend budget and failure circuit breaker, if configured.
+	gas_budget: Option<GasBudget>,
+	/// Pruner of the data of finished orders, if retention is configured.
+	pruner: Option<Arc<OrderPruner>>,
+	/// Implementations, features, standards and chains the solver was built with.
+	capabilities: SolverCapabilities,
+}
+
+/// Lets the intake accept an intent a reorg removed again, so that it is
+/// handled if the reorged chain includes it once more.
+fn forget_invalidated_intent(intent_rx: &IntentReceiver, event: &SolverEvent) {
+	if let SolverEvent::Discovery(DiscoveryEvent::IntentInvalidated { intent_id, .. }) = event {
+		intent_rx.forget(intent_id);
+	}
 }
 
-/// Number of orders to batch together for claim operations.
-static CLAIM_BATCH: usize = 1;
+/// Number of events the event bus buffers per subscriber.
+static EVENT_BUS_CAPACITY: usize = 1000;
+
+/// Interval between checks of whether paused fills can resume.
+static GAS_BUDGET_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Delay before an order held back by paused fills is decided again.
+static PAUSED_FILL_RETRY: Duration = Duration::from_secs(30);
+
+/// Interval between sweeps for intents that expired without being executed.
+static INTENT_EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Gas price assumed when the fill chain's gas price cannot be estimated.
+static FALLBACK_GAS_PRICE: u64 = 20_000_000_000; // 20 gwei
+
+/// Interval between checks for stuck transactions.
+static STUCK_TRANSACTION_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Interval between checks for fill bundles whose window ended.
+static BUNDLE_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Interval between checks for deferred orders due to be decided again.
+static DEFERRED_ORDER_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Interval between checks for open orders whose fill deadline passed.
+static EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Interval between checks for dead-lettered orders due to be re-driven.
+static DEAD_LETTER_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Interval between checks for failed intents and events due to be retried.
+static RETRY_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Interval between health checks of the delivery RPC endpoints.
+static ENDPOINT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Interval between checkpoints of the blocks discovery sources processed.
+static DISCOVERY_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Storage namespace holding the last processed block of each discovery
+/// source, keyed by source name.
+static DISCOVERY_CHECKPOINT_NAMESPACE: &str = "discovery_checkpoints";
+
+/// Maximum number of items returned in a single page of a list query.
+static MAX_PAGE_SIZE: usize = 100;
+
+/// Storage namespace holding how far the engine handled the event journal.
+static EVENT_CHECKPOINT_NAMESPACE: &str = "event_checkpoints";
+
+/// Key of the sequence of the last journaled event the engine handled.
+static EVENT_CHECKPOINT_KEY: &str = "handled";
 
 impl SolverEngine {
 	/// Main execution loop for the solver engine.
 	///
 	/// This method:
-	/// 1. Starts discovery monitoring to find new intents
-	/// 2. Subscribes to the event bus for inter-service communication
-	/// 3. Processes discovered intents and system events
-	/// 4. Handles graceful shutdown on Ctrl+C
+	/// 1. Loads the ledger and pre-warms configured token approvals
+	/// 2. Recovers according to how the previous run ended
+	/// 3. Starts discovery monitoring to find new intents
+	/// 4. Subscribes to the event bus for inter-service communication
+	/// 5. Processes discovered intents, system events and operator commands
+	/// 6. Handles graceful shutdown on Ctrl+C, writing a shutdown checkpoint
 	pub async fn run(&self) -> Result<(), SolverError> {
-		// Start discovery monitoring
-		let (intent_tx, mut intent_rx) = mpsc::unbounded_channel();
+		let entries = self.ledger.load().await.map_err(SolverError::from)?;
+		tracing::info!(entries, "Loaded ledger");
+
+		// Make sure settlers can pull tokens before the first fill
+		self.prewarm_approvals().await;
+
+		// Catch up on anything missed while the solver was down
+		self.restore_discovery_checkpoints().await?;
+		self.recover_previous_run().await?;
+
+		// Start discovery monitoring into a bounded intake
+		let intake_capacity = self.config.discovery.intake_capacity;
+		let overflow_policy = match self.config.discovery.intake_overflow.as_str() {
+			"drop_oldest" => OverflowPolicy::DropOldest,
+			"reject" => OverflowPolicy::Reject,
+			_ => OverflowPolicy::Spill,
+		};
+		let limits = IntakeLimits {
+			dedup_window: self.config.discovery.intake_dedup_window,
+			max_per_second: self.config.discovery.intake_max_intents_per_second,
+			source_max_per_second: self.config.discovery.intake_source_rate_limits.clone(),
+		};
+		let (intent_tx, mut intent_rx) = intake::channel(
+			intake_capacity,
+			overflow_policy,
+			limits,
+			self.storage.clone(),
+		)
+		.await
+		.map_err(SolverError::from)?;
 		self.discovery
 			.start_all(intent_tx)
 			.await
-			.map_err(|e| SolverError::Service(e.to_string()))?;
+			.map_err(SolverError::from)?;
+		let mut intake_saturations = 0;
+
+		// Discovered intents waiting to be handled, highest priority first
+		let mut intent_queue = IntentQueue::default();
+
+		// Handle submissions acknowledged before the last shutdown. They were
+		// accepted already, so they bypass the intake's overflow policy
+		let submitted = self.submissions.load().await.map_err(SolverError::from)?;
+		if !submitted.is_empty() {
+			tracing::info!(count = submitted.len(), "Resuming acknowledged submissions");
+		}
+		for intent in submitted {
+			self.enqueue_intent(&mut intent_queue, intent);
+		}
 
-		// Subscribe to events
+		// Subscribe to events, journaled before they are handled if the bus
+		// is persistent
 		let mut event_receiver = self.event_bus.subscribe();
+		let mut journaled = self.start_event_journal();
+
+		// Finish handling the events the previous run left unhandled
+		if journaled.is_some() {
+			self.replay_unhandled_events().await?;
+		} else {
+			self.storage
+				.remove(EVENT_CHECKPOINT_NAMESPACE, EVENT_CHECKPOINT_KEY)
+				.await
+				.map_err(SolverError::from)?;
+		}
+
+		// Periodic sweep for expired intents
+		let mut expiry_sweep = tokio::time::interval(INTENT_EXPIRY_SWEEP_INTERVAL);
+
+		// Periodic check of the fill deadlines of open orders
+		let mut expiry_check = tokio::time::interval(EXPIRY_CHECK_INTERVAL);
+
+		// Periodic ledger reconciliation, if configured
+		let reconcile_interval = self
+			.config
+			.accounting
+			.as_ref()
+			.map(|accounting| Duration::from_secs(accounting.reconcile_interval_seconds));
+		let mut reconciliation =
+			tokio::time::interval(reconcile_interval.unwrap_or(Duration::from_secs(300)));
+
+		// Periodic fee replacement of stuck transactions, if configured
+		let replacement_enabled = self.config.delivery.replacement.is_some();
+		let mut stuck_check = tokio::time::interval(STUCK_TRANSACTION_CHECK_INTERVAL);
+
+		// Periodic reorg checks of recent intents and receipts, if configured
+		let reorg_interval = self
+			.config
+			.reorg
+			.as_ref()
+			.map(|reorg| Duration::from_secs(reorg.check_interval_seconds));
+		let mut reorg_check =
+			tokio::time::interval(reorg_interval.unwrap_or(Duration::from_secs(12)));
+
+		// Periodic dispatch of fill bundles whose window ended, if configured
+		let bundling_enabled = self.bundler.is_some();
+		let mut bundle_flush = tokio::time::interval(BUNDLE_FLUSH_INTERVAL);
+
+		// Periodic re-evaluation of deferred orders
+		let mut deferred_check = tokio::time::interval(DEFERRED_ORDER_CHECK_INTERVAL);
+
+		// Periodic re-driving of dead-lettered orders
+		let mut dead_letter_check = tokio::time::interval(DEAD_LETTER_CHECK_INTERVAL);
+
+		// Periodic retries of intents and events that failed transiently
+		let mut retry_check = tokio::time::interval(RETRY_CHECK_INTERVAL);
+
+		// Periodic health checks of the delivery RPC endpoints
+		let mut endpoint_check = tokio::time::interval(ENDPOINT_HEALTH_CHECK_INTERVAL);
+
+		// Periodic polling of the solver's inventory, if configured
+		let inventory_interval = self
+			.config
+			.inventory
+			.as_ref()
+			.map(|inventory| Duration::from_secs(inventory.poll_interval_seconds));
+		let mut inventory_poll =
+			tokio::time::interval(inventory_interval.unwrap_or(Duration::from_secs(30)));
 
-		// Batch claim processing
-		let mut claim_batch = Vec::new();
+		// Periodic checkpoints of the blocks processed by discovery
+		let mut discovery_checkpoint = tokio::time::interval(DISCOVERY_CHECKPOINT_INTERVAL);
+
+		// Periodic checks of claims held back by gas, if a claim schedule is configured
+		let claim_recheck_interval = self
+			.claim_schedule
+			.as_ref()
+			.map(|schedule| schedule.recheck_interval());
+		let mut claim_recheck =
+			tokio::time::interval(claim_recheck_interval.unwrap_or(Duration::from_secs(60)));
+
+		// Periodic flush of claim batches that waited long enough, if claim
+		// batching is configured
+		let claim_flush_interval = self
+			.claim_batcher
+			.as_ref()
+			.map(|batcher| batcher.check_interval());
+		let mut claim_flush =
+			tokio::time::interval(claim_flush_interval.unwrap_or(Duration::from_secs(5)));
+
+		// Periodic resumption of paused fills, if a gas budget is configured
+		let gas_budget_enabled = self.gas_budget.is_some();
+		let mut gas_budget_check = tokio::time::interval(GAS_BUDGET_CHECK_INTERVAL);
+
+		// Periodic pruning of finished orders, if retention is configured
+		let prune_interval = self
+			.config
+			.retention
+			.as_ref()
+			.map(|retention| Duration::from_secs(retention.prune_interval_seconds));
+		let mut prune_check =
+			tokio::time::interval(prune_interval.unwrap_or(Duration::from_secs(3600)));
+
+		// Periodic refresh of the remote policy lists, if configured
+		let policy_refresh_interval = self
+			.config
+			.order
+			.policy
+			.as_ref()
+			.filter(|policy| policy.remote_url.is_some())
+			.map(|policy| Duration::from_secs(policy.refresh_interval_seconds));
+		let mut policy_refresh =
+			tokio::time::interval(policy_refresh_interval.unwrap_or(Duration::from_secs(3600)));
+
+		// Operator commands, and whether they let new intents through
+		let mut control_commands = self.control_commands.lock().unwrap().take();
+		let mut intake = IntakeState::Running;
+
+		// Work handed to chain tasks that failed there
+		let mut task_failures = self.task_failures.lock().unwrap().take();
 		loop {
+			self.metrics
+				.set_queue_depths(intent_rx.len() + intent_queue.len(), event_receiver.len());
+			let stats = intent_rx.stats();
+			self.metrics.set_intake(stats, intake_capacity);
+			if stats.saturations > intake_saturations {
+				intake_saturations = stats.saturations;
+				self.event_bus
+					.publish(SolverEvent::Discovery(DiscoveryEvent::IntakeSaturated {
+						depth: stats.depth + stats.spilled,
+						capacity: intake_capacity,
+						policy: overflow_policy.to_string(),
+					}))
+					.ok();
+			}
+
 			tokio::select! {
-				// Handle discovered intents
-				Some(intent) = intent_rx.recv() => {
-					tracing::info!(
-						order_id = %truncate_id(&intent.id),
-						"Discovered intent"
-					);
-					self.handle_intent(intent).await?;
+				// Queue discovered intents by priority, leaving them in the
+				// intake while the queue is full so that its overflow policy
+				// applies
+				Some(intent) = intent_rx.recv(), if intent_queue.len() < intake_capacity => {
+					self.enqueue_intent(&mut intent_queue, intent);
+					while intent_queue.len() < intake_capacity {
+						let Some(intent) = intent_rx.try_recv() else {
+							break;
+						};
+						self.enqueue_intent(&mut intent_queue, intent);
+					}
 				}
 
-				// Handle events
-				Ok(event) = event_receiver.recv() => {
-					match event {
-						SolverEvent::Order(OrderEvent::Executing { order, params }) => {
-							self.handle_order_execution(order, params).await?;
-						}
+				// Handle the highest priority queued intent
+				Some(intent) = async { intent_queue.pop() }, if !intent_queue.is_empty() && intake == IntakeState::Running => {
+					let started = Instant::now();
+					self.process_intent(intent, 1).await?;
+					self.metrics.record_handler("intent", started.elapsed());
+				}
 
-						SolverEvent::Delivery(DeliveryEvent::TransactionPending { order_id, tx_hash, tx_type }) => {
-							self.handle_transaction_pending(order_id, tx_hash, tx_type).await?;
-						}
+				// Apply operator commands between intents and events
+				Some(message) = async { control_commands.as_mut()?.recv().await }, if control_commands.is_some() => {
+					let reply = self
+						.handle_control(message.command, &mut intake, intent_queue.len())
+						.await;
+					message.reply.send(reply).ok();
+				}
+
+				// Handle events as they are published
+				Ok(event) = event_receiver.recv(), if journaled.is_none() => {
+					self.journal_event(&event).await?;
+					forget_invalidated_intent(&intent_rx, &event);
+					self.process_event(event, 1).await?;
+				}
+
+				// Handle events once a persistent bus journaled them
+				Some((sequence, event)) = async { journaled.as_mut()?.recv().await }, if journaled.is_some() => {
+					forget_invalidated_intent(&intent_rx, &event);
+					self.process_event(event, 1).await?;
+					self.mark_event_handled(sequence).await?;
+				}
+
+				// Expire intents that passed their fill deadline
+				_ = expiry_sweep.tick() => {
+					let started = Instant::now();
+					self.purge_expired_intents().await?;
+					self.metrics.record_handler("expiry_sweep", started.elapsed());
+				}
+
+				// Expire open orders as soon as their fill deadline passes
+				_ = expiry_check.tick() => {
+					let started = Instant::now();
+					self.expire_orders().await?;
+					self.metrics.record_handler("expiry_check", started.elapsed());
+				}
+
+				// Audit the ledger against its invariants and on-chain balances
+				_ = reconciliation.tick(), if reconcile_interval.is_some() => {
+					let started = Instant::now();
+					self.reconcile_ledger().await;
+					self.metrics.record_handler("reconciliation", started.elapsed());
+				}
+
+				// Re-submit transactions stuck in the mempool with higher fees
+				_ = stuck_check.tick(), if replacement_enabled => {
+					let started = Instant::now();
+					self.replace_stuck_transactions().await;
+					self.metrics.record_handler("stuck_check", started.elapsed());
+				}
+
+				// Re-check the blocks of recent intents and receipts
+				_ = reorg_check.tick(), if reorg_interval.is_some() => {
+					let started = Instant::now();
+					self.check_reorgs();
+					self.metrics.record_handler("reorg_check", started.elapsed());
+				}
+
+				// Fill bundles that stopped waiting for more orders
+				_ = bundle_flush.tick(), if bundling_enabled => {
+					let started = Instant::now();
+					self.flush_fill_bundles().await?;
+					self.metrics.record_handler("bundle_flush", started.elapsed());
+				}
+
+				// Decide deferred orders again once they are due
+				_ = deferred_check.tick() => {
+					let started = Instant::now();
+					self.retry_deferred_orders().await?;
+					self.metrics.record_handler("deferred_check", started.elapsed());
+				}
+
+				// Re-drive dead-lettered orders once they are due
+				_ = dead_letter_check.tick() => {
+					let started = Instant::now();
+					self.retry_dead_letters().await?;
+					self.metrics.record_handler("dead_letter_check", started.elapsed());
+				}
+
+				// Retry or dead-letter work whose chain task failed
+				Some(failed) = async { task_failures.as_mut()?.recv().await }, if task_failures.is_some() => {
+					self.process_failed_task(failed).await?;
+				}
+
+				// Handle intents and events that failed transiently again
+				_ = retry_check.tick(), if !self.retries.is_empty() => {
+					let started = Instant::now();
+					self.retry_failed_work().await?;
+					self.metrics.record_handler("retry_check", started.elapsed());
+				}
+
+				// Probe RPC endpoints and report those that stopped responding
+				_ = endpoint_check.tick() => {
+					let started = Instant::now();
+					self.check_delivery_endpoints();
+					self.metrics.record_handler("endpoint_check", started.elapsed());
+				}
+
+				// Poll the solver's balances for strategies and the API
+				_ = inventory_poll.tick(), if inventory_interval.is_some() => {
+					self.poll_inventory();
+				}
+
+				// Remember how far discovery got, to resume from there after a restart
+				_ = discovery_checkpoint.tick() => {
+					self.save_discovery_checkpoints().await?;
+				}
 
-						SolverEvent::Delivery(DeliveryEvent::TransactionConfirmed { tx_hash, receipt, tx_type }) => {
-							self.handle_transaction_confirmed(tx_hash, receipt, tx_type).await?;
+				// Submit held claims once gas drops or their deadline nears
+				_ = claim_recheck.tick(), if claim_recheck_interval.is_some() => {
+					if let Some(schedule) = &self.claim_schedule {
+						let mut held = schedule.held();
+						if !held.is_empty() {
+							let started = Instant::now();
+							self.process_claim_batch(&mut held, 1).await?;
+							self.metrics.record_handler("claim_recheck", started.elapsed());
 						}
+					}
+				}
 
-						SolverEvent::Settlement(SettlementEvent::ClaimReady { order_id }) => {
-							claim_batch.push(order_id);
-							if claim_batch.len() >= CLAIM_BATCH {
-								self.process_claim_batch(&mut claim_batch).await?;
-							}
+				// Process claim batches whose oldest claim waited long enough
+				_ = claim_flush.tick(), if claim_flush_interval.is_some() => {
+					if let Some(batcher) = &self.claim_batcher {
+						for batch in batcher.due(Instant::now()) {
+							let started = Instant::now();
+							self.flush_claim_batch(batch).await?;
+							self.metrics.record_handler("claim_flush", started.elapsed());
 						}
+					}
+				}
 
-						_ => {}
+				// Resume fills paused by the gas budget once spend left the window
+				_ = gas_budget_check.tick(), if gas_budget_enabled => {
+					if let Some(gas_budget) = &self.gas_budget {
+						self.publish_pause_changes(gas_budget.refresh());
 					}
 				}
 
+				// Delete the data of orders that finished before their retention period
+				_ = prune_check.tick(), if prune_interval.is_some() => {
+					self.prune_finished_orders();
+				}
+
+				// Fetch the latest remote policy lists
+				_ = policy_refresh.tick(), if policy_refresh_interval.is_some() => {
+					self.refresh_policy();
+				}
+
 				// Shutdown signal
 				_ = tokio::signal::ctrl_c() => {
 					break;
@@ -141,67 +1282,640 @@ impl SolverEngine {
 		}
 
 		// Cleanup
-		self.discovery
-			.stop_all()
-			.await
-			.map_err(|e| SolverError::Service(e.to_string()))?;
+		self.discovery.stop_all().await.map_err(SolverError::from)?;
 
-		Ok(())
+		// Claims still waiting for their batch are not journaled, so they are
+		// processed rather than lost
+		if let Some(batcher) = &self.claim_batcher {
+			for batch in batcher.drain() {
+				self.flush_claim_batch(batch).await?;
+			}
+		}
+
+		self.save_discovery_checkpoints().await?;
+		self.write_shutdown_checkpoint().await
 	}
 
-	/// Handles a newly discovered intent.
+	/// Applies an operator command, returning the reply to send back.
 	///
-	/// This method:
-	/// 1. Validates the intent to create an order
-	/// 2. Stores the validated order
-	/// 3. Checks the execution strategy to determine if/when to execute
-	/// 4. Publishes appropriate events based on the execution decision
-	#[instrument(skip_all, fields(order_id = %truncate_id(&intent.id)))]
-	async fn handle_intent(&self, intent: Intent) -> Result<(), SolverError> {
-		// Validate intent
-		match self.order.validate_intent(&intent).await {
-			Ok(order) => {
-				self.event_bus
-					.publish(SolverEvent::Discovery(DiscoveryEvent::IntentValidated {
-						intent_id: intent.id.clone(),
-						order: order.clone(),
-					}))
+	/// `intake` is the event loop's intake state, and `held_intents` the
+	/// number of intents it holds.
+	async fn handle_control(
+		&self,
+		command: ControlCommand,
+		intake: &mut IntakeState,
+		held_intents: usize,
+	) -> Result<ControlReply, ControlError> {
+		match command {
+			ControlCommand::Status => {}
+			ControlCommand::PauseIntake => {
+				tracing::info!(held_intents, "Paused intent intake");
+				*intake = IntakeState::Paused;
+			}
+			ControlCommand::ResumeIntake => {
+				tracing::info!(held_intents, "Resumed intent intake");
+				*intake = IntakeState::Running;
+			}
+			ControlCommand::Drain => {
+				tracing::info!(held_intents, "Draining in-flight orders");
+				*intake = IntakeState::Draining;
+			}
+			ControlCommand::RecheckClaim { order_id } => {
+				return self.recheck_claim(order_id).await.map(ControlReply::Claim);
+			}
+			ControlCommand::Claim { order_id } => {
+				return self.trigger_claim(order_id).await.map(ControlReply::Claim);
+			}
+		}
+
+		let pending_transactions =
+			self.pending.count(TransactionType::Fill) + self.pending.count(TransactionType::Claim);
+		Ok(ControlReply::Intake(IntakeStatusResponse {
+			intake: *intake,
+			held_intents,
+			pending_transactions,
+			drained: *intake == IntakeState::Draining && pending_transactions == 0,
+		}))
+	}
+
+	/// Checks whether an order can be claimed, and publishes it as ready to
+	/// claim if so and it has not been claimed yet.
+	///
+	/// The fill proof is fetched again if the order has none yet.
+	async fn recheck_claim(&self, order_id: String) -> Result<ClaimActionResponse, ControlError> {
+		let failed = |e: SolverError| ControlError::Failed(e.to_string());
+		let order = self
+			.optional::<Order>("orders", &order_id)
+			.await
+			.map_err(failed)?
+			.ok_or_else(|| ControlError::OrderNotFound(order_id.clone()))?;
+
+		let fill_proof = match self
+			.optional::<solver_types::FillProof>("fill_proofs", &order_id)
+			.await
+			.map_err(failed)?
+		{
+			Some(fill_proof) => fill_proof,
+			None => {
+				let fill_tx = self
+					.optional::<solver_types::TransactionHash>("fills", &order_id)
+					.await
+					.map_err(failed)?
+					.ok_or_else(|| ControlError::NotFilled(order_id.clone()))?;
+				let fill_proof = self
+					.settlement
+					.get_attestation(&order, &fill_tx)
+					.await
+					.map_err(|e| ControlError::Failed(format!("Failed to validate fill: {}", e)))?;
+				self.storage
+					.store("fill_proofs", &order_id, &fill_proof)
+					.await
+					.map_err(|e| ControlError::Failed(e.to_string()))?;
+				fill_proof
+			}
+		};
+
+		let claim_ready = self.settlement.can_claim(&order, &fill_proof).await;
+		let claimed = self
+			.optional::<solver_types::TransactionHash>("claims", &order_id)
+			.await
+			.map_err(failed)?
+			.is_some();
+		let claim_triggered = claim_ready && !claimed;
+		if claim_triggered {
+			tracing::info!(order_id = %truncate_id(&order_id), "Ready to claim after re-check");
+			self.event_bus
+				.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
+					order_id: order_id.clone(),
+				}))
+				.ok();
+		}
+
+		Ok(ClaimActionResponse {
+			order_id,
+			claim_ready,
+			claim_triggered,
+		})
+	}
+
+	/// Claims an order right away, even if it is not known to be ready.
+	async fn trigger_claim(&self, order_id: String) -> Result<ClaimActionResponse, ControlError> {
+		let failed = |e: SolverError| ControlError::Failed(e.to_string());
+		let order = self
+			.optional::<Order>("orders", &order_id)
+			.await
+			.map_err(failed)?
+			.ok_or_else(|| ControlError::OrderNotFound(order_id.clone()))?;
+		let fill_proof = self
+			.optional::<solver_types::FillProof>("fill_proofs", &order_id)
+			.await
+			.map_err(failed)?
+			.ok_or_else(|| ControlError::NotFilled(order_id.clone()))?;
+
+		let claim_ready = self.settlement.can_claim(&order, &fill_proof).await;
+		tracing::info!(order_id = %truncate_id(&order_id), claim_ready, "Claiming order on operator request");
+		if let Some(schedule) = &self.claim_schedule {
+			schedule.expedite(&order_id);
+		}
+		self.process_claim_batch(&mut vec![order_id.clone()], 1)
+			.await
+			.map_err(failed)?;
+
+		Ok(ClaimActionResponse {
+			order_id,
+			claim_ready,
+			claim_triggered: true,
+		})
+	}
+
+	/// Handles an event published on the event bus.
+	///
+	/// Claim-ready orders are added to their claim batch if claims are
+	/// batched, and processed right away otherwise. `attempts` counts the
+	/// attempts at handling the event, including this one; retried fills and
+	/// claims skip their bundle or batch, so that their attempts keep being
+	/// counted.
+	async fn handle_event(&self, event: SolverEvent, attempts: u32) -> Result<(), SolverError> {
+		let started = Instant::now();
+		let kind = metrics::event_kind(&event);
+		self.metrics.record_event(&event);
+		self.audit_event(&event).await?;
+		self.record_order_status(&event).await?;
+
+		match event {
+			SolverEvent::Order(OrderEvent::Executing { order, params }) => {
+				self.handle_order_execution(order, params, attempts).await?;
+			}
+
+			SolverEvent::Delivery(DeliveryEvent::TransactionPending {
+				order_id,
+				tx_hash,
+				tx_type,
+				chain_id,
+			}) => {
+				self.handle_transaction_pending(order_id, tx_hash, tx_type, chain_id)
+					.await?;
+			}
+
+			SolverEvent::Delivery(DeliveryEvent::TransactionConfirmed {
+				tx_hash,
+				receipt,
+				tx_type,
+			}) => {
+				self.handle_transaction_confirmed(tx_hash, receipt, tx_type)
+					.await?;
+			}
+
+			SolverEvent::Delivery(DeliveryEvent::TransactionFailed { tx_hash, error }) => {
+				self.handle_transaction_failed(tx_hash, error).await?;
+			}
+
+			SolverEvent::Delivery(DeliveryEvent::TransactionReplaced {
+				order_id,
+				old_tx_hash,
+				new_tx_hash,
+				tx_type,
+				chain_id,
+			}) => {
+				self.handle_transaction_replaced(
+					order_id,
+					old_tx_hash,
+					new_tx_hash,
+					tx_type,
+					chain_id,
+				)
+				.await?;
+			}
+
+			SolverEvent::Delivery(DeliveryEvent::TransactionReorged {
+				order_id,
+				tx_hash,
+				tx_type,
+				chain_id,
+			}) => {
+				self.handle_transaction_reorged(order_id, tx_hash, tx_type, chain_id)
+					.await?;
+			}
+
+			SolverEvent::Discovery(DiscoveryEvent::IntentInvalidated { intent_id, .. }) => {
+				self.handle_intent_invalidated(&intent_id).await?;
+			}
+
+			SolverEvent::Order(
+				OrderEvent::Skipped { order_id, .. } | OrderEvent::FilledByOther { order_id },
+			) => {
+				self.expiry.release(&order_id);
+				self.release_risk(&order_id);
+				self.record_intent_state(&order_id, IntentState::Finished)
+					.await?;
+			}
+
+			SolverEvent::Settlement(SettlementEvent::ClaimReady { order_id }) => {
+				match self.claim_batcher.as_ref().filter(|_| attempts == 1) {
+					Some(batcher) => {
+						let key = self.claim_batch_key(&order_id).await?;
+						for batch in batcher.add(key, order_id) {
+							self.flush_claim_batch(batch).await?;
+						}
+					}
+					None => {
+						self.process_claim_batch(&mut vec![order_id], attempts)
+							.await?
+					}
+				}
+			}
+
+			SolverEvent::Settlement(SettlementEvent::Completed { order_id }) => {
+				let profit_usd = self.record_pnl(&order_id).await;
+				self.handle_order_outcome(&order_id, OrderOutcomeStatus::Completed, profit_usd)
+					.await?;
+				if let Some(reorgs) = &self.reorgs {
+					reorgs.release_order(&order_id);
+				}
+				self.dead_letters
+					.resolve(&order_id)
+					.await
+					.map_err(SolverError::from)?;
+			}
+
+			_ => {}
+		}
+		self.metrics.record_handler(kind, started.elapsed());
+		Ok(())
+	}
+
+	/// Handles the events a persistent bus journaled that the previous run
+	/// did not handle before stopping.
+	///
+	/// The first run with a persistent bus handles no earlier events, since
+	/// they were handled as they were received. Replayed executions of
+	/// orders whose fill may have been sent are checked against the chain
+	/// before filling, like any other execution.
+	async fn replay_unhandled_events(&self) -> Result<(), SolverError> {
+		let last = self
+			.storage
+			.current_sequence("events")
+			.await
+			.map_err(SolverError::from)?;
+		let Some(handled) = self
+			.optional::<u64>(EVENT_CHECKPOINT_NAMESPACE, EVENT_CHECKPOINT_KEY)
+			.await?
+		else {
+			return self.mark_event_handled(last).await;
+		};
+
+		if handled < last {
+			tracing::info!(count = last - handled, "Replaying unhandled events");
+		}
+		for sequence in handled + 1..=last {
+			if let Some(event) = self
+				.optional::<SequencedEvent>("events", &sequence.to_string())
+				.await?
+			{
+				self.process_event(event.event, 1).await?;
+			}
+			self.mark_event_handled(sequence).await?;
+		}
+		Ok(())
+	}
+
+	/// Records that the journaled event at `sequence` was handled.
+	///
+	/// Events that could not be journaled have no sequence and are skipped.
+	async fn mark_event_handled(&self, sequence: u64) -> Result<(), SolverError> {
+		if sequence == 0 {
+			return Ok(());
+		}
+		self.storage
+			.store(EVENT_CHECKPOINT_NAMESPACE, EVENT_CHECKPOINT_KEY, &sequence)
+			.await
+			.map_err(SolverError::from)
+	}
+
+	/// Starts journaling the events published on a persistent bus.
+	///
+	/// Returns the journaled events with their sequence numbers, or `None`
+	/// if the bus is not persistent or the journal was already started.
+	fn start_event_journal(&self) -> Option<mpsc::UnboundedReceiver<(u64, SolverEvent)>> {
+		let published = self.published_events.lock().unwrap().take()?;
+		let (journaled_tx, journaled_rx) = mpsc::unbounded_channel();
+		tokio::spawn(journal_published_events(
+			self.storage.clone(),
+			self.event_bus.clone(),
+			published,
+			journaled_tx,
+		));
+		Some(journaled_rx)
+	}
+
+	/// Resumes discovery sources from the blocks they had processed when the
+	/// solver last stopped.
+	///
+	/// Sources without a checkpoint start from their configured block.
+	async fn restore_discovery_checkpoints(&self) -> Result<(), SolverError> {
+		for (name, _) in self.discovery.checkpoints().await {
+			if let Some(block) = self
+				.optional::<u64>(DISCOVERY_CHECKPOINT_NAMESPACE, &name)
+				.await?
+			{
+				self.discovery.resume(&name, block).await;
+			}
+		}
+		Ok(())
+	}
+
+	/// Persists the last processed block of each block-based discovery source.
+	async fn save_discovery_checkpoints(&self) -> Result<(), SolverError> {
+		for (name, block) in self.discovery.checkpoints().await {
+			self.storage
+				.store(DISCOVERY_CHECKPOINT_NAMESPACE, &name, &block)
+				.await
+				.map_err(SolverError::from)?;
+		}
+		Ok(())
+	}
+
+	/// Inspects the checkpoint left by the previous run and marks this run as
+	/// in progress.
+	///
+	/// After a crash, discovery re-scans further back and the ledger is
+	/// reconciled right away instead of waiting for the first interval.
+	async fn recover_previous_run(&self) -> Result<(), SolverError> {
+		let (Some(checkpoints), Some(recovery)) = (&self.checkpoints, &self.config.recovery) else {
+			return Ok(());
+		};
+
+		let backfill_blocks = match checkpoints.previous_run().await? {
+			PreviousRun::FirstStart => recovery.backfill_blocks,
+			PreviousRun::Clean(checkpoint) => {
+				tracing::info!(
+					stopped_at = checkpoint.stopped_at,
+					pending_fills = checkpoint.pending_fills,
+					pending_claims = checkpoint.pending_claims,
+					"Previous run shut down cleanly"
+				);
+				let last_event_sequence = self
+					.storage
+					.current_sequence("events")
+					.await
+					.map_err(SolverError::from)?;
+				if last_event_sequence != checkpoint.last_event_sequence {
+					tracing::warn!(
+						checkpoint_sequence = checkpoint.last_event_sequence,
+						storage_sequence = last_event_sequence,
+						"Event journal changed since the last shutdown"
+					);
+				}
+				recovery.backfill_blocks
+			}
+			PreviousRun::Crashed { started_at } => {
+				tracing::warn!(
+					started_at = ?started_at,
+					backfill_blocks = recovery.crash_backfill_blocks,
+					"Previous run did not shut down cleanly, recovering"
+				);
+				self.reconcile_ledger().await;
+				self.report_open_orders().await?;
+				recovery.crash_backfill_blocks
+			}
+		};
+
+		if backfill_blocks > 0 {
+			self.discovery.backfill_all(backfill_blocks).await;
+		}
+
+		checkpoints.mark_running().await
+	}
+
+	/// Returns the orders that have neither completed nor failed, with their
+	/// last recorded status.
+	pub async fn open_orders(&self) -> Result<Vec<(String, DetailedIntentStatus)>, SolverError> {
+		let statuses = self
+			.storage
+			.retrieve_all::<DetailedIntentStatus>("order_status")
+			.await
+			.map_err(SolverError::from)?;
+		Ok(statuses
+			.into_iter()
+			.filter(|(_, status)| {
+				!matches!(
+					status,
+					DetailedIntentStatus::Completed | DetailedIntentStatus::Failed
+				)
+			})
+			.collect())
+	}
+
+	/// Logs the orders a crashed run left open, so operators can follow up
+	/// on those stuck mid-flight.
+	async fn report_open_orders(&self) -> Result<(), SolverError> {
+		let open_orders = self.open_orders().await?;
+		let mut by_status: HashMap<String, usize> = HashMap::new();
+		for (order_id, status) in &open_orders {
+			tracing::debug!(order_id = %truncate_id(order_id), status = ?status, "Open order");
+			*by_status.entry(format!("{:?}", status)).or_default() += 1;
+		}
+		tracing::info!(
+			open_orders = open_orders.len(),
+			by_status = ?by_status,
+			"Enumerated orders left open by the previous run"
+		);
+		Ok(())
+	}
+
+	/// Records where the solver stopped so that the next run can tell it
+	/// shut down gracefully.
+	async fn write_shutdown_checkpoint(&self) -> Result<(), SolverError> {
+		let Some(checkpoints) = &self.checkpoints else {
+			return Ok(());
+		};
+
+		let sequence = |name: &'static str| async move {
+			self.storage
+				.current_sequence(name)
+				.await
+				.map_err(SolverError::from)
+		};
+		let checkpoint = ShutdownCheckpoint {
+			stopped_at: checkpoint::now_secs(),
+			last_event_sequence: sequence("events").await?,
+			orders: sequence("orders").await?,
+			pending_fills: self.pending.count(TransactionType::Fill),
+			pending_claims: self.pending.count(TransactionType::Claim),
+		};
+		checkpoints.write_shutdown(checkpoint).await?;
+		tracing::info!("Wrote shutdown checkpoint");
+
+		Ok(())
+	}
+
+	/// Checks and sets allowances for all configured approval targets.
+	///
+	/// Failures are logged rather than returned, since a missing approval only
+	/// affects fills of the token concerned.
+	async fn prewarm_approvals(&self) {
+		let Some(approvals) = &self.approvals else {
+			return;
+		};
+		if self.dry_run.is_some() {
+			tracing::info!("Skipping approval pre-warming in dry run");
+			return;
+		}
+
+		let owner = match self.account.get_address().await {
+			Ok(address) => address,
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to resolve solver address for approvals");
+				return;
+			}
+		};
+
+		let submitted = approvals.prewarm(&owner).await;
+		tracing::info!(submitted, "Pre-warmed token approvals");
+	}
+
+	/// Handles a newly discovered intent.
+	///
+	/// This method:
+	/// 1. Validates the intent to create an order
+	/// 2. Stores the validated order and keeps the intent until its fill deadline
+	/// 3. Checks the execution strategy to determine if/when to execute
+	/// 4. Publishes appropriate events based on the execution decision
+	#[instrument(skip_all, fields(order_id = %truncate_id(&intent.id)))]
+	async fn handle_intent(&self, intent: Intent) -> Result<(), SolverError> {
+		// Don't process an intent twice, within this run or across restarts
+		let admission = self
+			.intent_ledger
+			.admit(&intent.id)
+			.await
+			.map_err(SolverError::from)?;
+		match admission {
+			Admission::Process => {}
+			Admission::Recover => {
+				if !self.interrupted_fill_failed(&intent.id).await? {
+					self.latency.finish(&intent.id);
+					return Ok(());
+				}
+				self.record_intent_state(&intent.id, IntentState::Processing)
+					.await?;
+			}
+			Admission::Duplicate(state) => {
+				tracing::debug!(state = ?state, "Ignored intent that was already processed");
+				self.latency.finish(&intent.id);
+				return Ok(());
+			}
+		}
+
+		// Validate intent
+		match self.order.validate_intent(&intent).await {
+			Ok(mut order) => {
+				self.route_order(&mut order).await;
+				let order = Arc::new(order);
+				let mut context = self.build_execution_context(&order).await;
+
+				// Stablecoin-only orders skip liquidity and strategy checks
+				let fast_decision = self.order.evaluate_fast_path(&order, &context);
+
+				// Refuse orders over the risk limits before spending anything on them
+				if let Err(reason) = self
+					.assess_risk(&order, &mut context, fast_decision.is_some())
+					.await
+				{
+					tracing::info!(reason = %reason, "Rejected intent due to risk limits");
+					self.latency.finish(&intent.id);
+					self.record_intent_state(&intent.id, IntentState::Dropped)
+						.await?;
+					self.dead_letter_intent(&intent, &reason, None).await?;
+					self.event_bus
+						.publish(SolverEvent::Discovery(DiscoveryEvent::IntentRejected {
+							intent_id: intent.id,
+							reason,
+						}))
+						.ok();
+					return Ok(());
+				}
+
+				self.event_bus
+					.publish(SolverEvent::Discovery(DiscoveryEvent::IntentValidated {
+						intent_id: intent.id.clone(),
+						order: order.clone(),
+					}))
 					.ok();
 
 				// Store order
 				self.storage
 					.store("orders", &order.id, &order)
 					.await
-					.map_err(|e| SolverError::Service(e.to_string()))?;
+					.map_err(SolverError::from)?;
+
+				// Index the order by sequence for stable pagination
+				let sequence = self
+					.storage
+					.next_sequence("orders")
+					.await
+					.map_err(SolverError::from)?;
+				self.storage
+					.store("order_sequence", &sequence.to_string(), &order.id)
+					.await
+					.map_err(SolverError::from)?;
+
+				self.track_intent_block(&intent, &order);
+
+				// Keep the intent in hot storage until its fill deadline passes
+				let fill_deadline = self
+					.order
+					.fill_deadline(&order)
+					.map_err(SolverError::from)?;
+				let ttl = Duration::from_secs(fill_deadline.saturating_sub(context.timestamp));
+				self.storage
+					.store_with_ttl("intents", &order.id, &intent, Some(ttl))
+					.await
+					.map_err(SolverError::from)?;
+				self.expiry.track(&order.id, fill_deadline);
 
 				// Check execution strategy
-				let context = self.build_execution_context().await?;
-				match self.order.should_execute(&order, &context).await {
+				let decision = match fast_decision {
+					Some(decision) => decision,
+					None => self.order.should_execute(&order, &context).await,
+				};
+				match decision {
 					ExecutionDecision::Execute(params) => {
+						if let Err(elapsed) = self.latency.check(&order.id) {
+							skip_late_order(
+								&self.latency,
+								&self.metrics,
+								&self.event_bus,
+								order.id.clone(),
+								elapsed,
+							);
+							return Ok(());
+						}
 						tracing::info!("Executing order");
 						self.event_bus
 							.publish(SolverEvent::Order(OrderEvent::Executing { order, params }))
 							.ok();
 					}
 					ExecutionDecision::Skip(reason) => {
+						self.latency.finish(&order.id);
 						self.event_bus
 							.publish(SolverEvent::Order(OrderEvent::Skipped {
-								order_id: order.id,
+								order_id: order.id.clone(),
 								reason,
 							}))
 							.ok();
 					}
 					ExecutionDecision::Defer(duration) => {
-						self.event_bus
-							.publish(SolverEvent::Order(OrderEvent::Deferred {
-								order_id: order.id,
-								retry_after: duration,
-							}))
-							.ok();
+						self.latency.finish(&order.id);
+						self.defer_order(order, duration, 1);
 					}
 				}
 			}
 			Err(e) => {
+				self.latency.finish(&intent.id);
+				self.record_intent_state(&intent.id, IntentState::Dropped)
+					.await?;
+				self.dead_letter_intent(&intent, &e.to_string(), Some(e.class()))
+					.await?;
 				self.event_bus
 					.publish(SolverEvent::Discovery(DiscoveryEvent::IntentRejected {
 						intent_id: intent.id,
@@ -214,410 +1928,3561 @@ impl SolverEngine {
 		Ok(())
 	}
 
-	/// Handles order execution by generating and submitting a fill transaction.
+	/// Handles an intent, scheduling it to be handled again if handling fails
+	/// transiently.
 	///
-	/// This method:
-	/// 1. Generates a fill transaction for the order
-	/// 2. Submits the transaction through the delivery service
-	/// 3. Stores transaction hashes and mappings for later retrieval
-	#[instrument(skip_all, fields(order_id = %truncate_id(&order.id)))]
-	async fn handle_order_execution(
-		&self,
-		order: Order,
-		params: solver_types::ExecutionParams,
-	) -> Result<(), SolverError> {
-		// Generate fill transaction
-		let tx = self
-			.order
-			.generate_fill_transaction(&order, &params)
-			.await
-			.map_err(|e| SolverError::Service(e.to_string()))?;
-
-		// Submit transaction
-		let tx_hash = self
-			.delivery
-			.deliver(tx)
-			.await
-			.map_err(|e| SolverError::Service(e.to_string()))?;
-
-		self.event_bus
-			.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
-				order_id: order.id.clone(),
-				tx_hash: tx_hash.clone(),
-				tx_type: TransactionType::Fill,
-			}))
-			.ok();
+	/// `attempts` counts the attempts at handling the intent, including this
+	/// one. An intent failing permanently, or on its last attempt, is
+	/// dead-lettered. Its submission, if it was submitted to the solver, is
+	/// completed once it is handled or given up on.
+	async fn process_intent(&self, intent: Intent, attempts: u32) -> Result<(), SolverError> {
+		let intent_id = intent.id.clone();
+		if let Err(e) = self.handle_intent(intent.clone()).await {
+			// Let the intent ledger admit the intent again
+			if let Err(e) = self
+				.record_intent_state(&intent_id, IntentState::Dropped)
+				.await
+			{
+				tracing::warn!(error = %e, "Failed to release intent after failure");
+			}
+			if self.schedule_retry(Work::Intent(intent.clone()), attempts, &e) {
+				return Ok(());
+			}
 
-		// Store fill transaction and timestamp
-		self.storage
-			.store("fills", &order.id, &tx_hash)
+			self.latency.finish(&intent_id);
+			self.dead_letter_intent(&intent, &e.to_string(), Some(e.class()))
+				.await?;
+			self.event_bus
+				.publish(SolverEvent::Discovery(DiscoveryEvent::IntentRejected {
+					intent_id: intent_id.clone(),
+					reason: e.to_string(),
+				}))
+				.ok();
+		}
+		self.submissions
+			.complete(&intent_id)
 			.await
-			.map_err(|e| SolverError::Service(e.to_string()))?;
+			.map_err(SolverError::from)
+	}
 
-		// Store reverse mapping: tx_hash -> order_id
-		self.storage
-			.store("tx_to_order", &hex::encode(&tx_hash.0), &order.id)
-			.await
-			.map_err(|e| SolverError::Service(e.to_string()))?;
+	/// Handles an event, scheduling it to be handled again if handling fails
+	/// transiently.
+	///
+	/// `attempts` counts the attempts at handling the event, including this
+	/// one. When handling fails permanently, or on its last attempt, the
+	/// orders the event concerns are dead-lettered.
+	async fn process_event(&self, event: SolverEvent, attempts: u32) -> Result<(), SolverError> {
+		let Err(e) = self.handle_event(event.clone(), attempts).await else {
+			return Ok(());
+		};
+		if self.schedule_retry(Work::Event(event.clone()), attempts, &e) {
+			return Ok(());
+		}
+		self.dead_letter_event(&event, &e).await
+	}
 
+	/// Retries or dead-letters the work of a chain task that failed, like
+	/// work that failed in the event loop.
+	async fn process_failed_task(&self, failed: FailedTask) -> Result<(), SolverError> {
+		for work in failed.work {
+			if self.schedule_retry(work.clone(), failed.attempts, &failed.error) {
+				continue;
+			}
+			match work {
+				Work::Event(event) => self.dead_letter_event(&event, &failed.error).await?,
+				Work::Intent(intent) => {
+					self.dead_letter_intent(
+						&intent,
+						&failed.error.to_string(),
+						Some(failed.error.class()),
+					)
+					.await?
+				}
+			}
+		}
 		Ok(())
 	}
 
-	/// Monitors a pending transaction until it is confirmed or fails.
-	///
-	/// Spawns an async task that polls the transaction status at regular intervals
-	/// until the transaction is confirmed, fails, or the monitoring timeout is reached.
-	#[instrument(skip_all, fields(order_id = %truncate_id(&order_id), tx_hash = %truncate_id(&hex::encode(&tx_hash.0))))]
-	async fn handle_transaction_pending(
+	/// Dead-letters the orders an event concerns after handling it failed
+	/// for good.
+	async fn dead_letter_event(
 		&self,
-		order_id: String,
-		tx_hash: solver_types::TransactionHash,
-		tx_type: TransactionType,
+		event: &SolverEvent,
+		error: &SolverError,
 	) -> Result<(), SolverError> {
-		// Spawn a task to monitor the transaction
-		let delivery = self.delivery.clone();
-		let event_bus = self.event_bus.clone();
-		let timeout_minutes = self.config.solver.monitoring_timeout_minutes;
-
-		tokio::spawn(async move {
+		let stage = failure_stage(event);
+		for order_id in self.event_orders(event).await {
+			tracing::warn!(
+				order_id = %truncate_id(&order_id),
+				stage = ?stage,
+				error = %error,
+				"Dead-lettered order after failing to handle event"
+			);
+			self.dead_letters
+				.record(
+					&order_id,
+					stage,
+					error.to_string(),
+					Some(error.class()),
+					None,
+				)
+				.await
+				.map_err(SolverError::from)?;
+		}
+		Ok(())
+	}
+
+	/// Schedules work that failed to be handled again, if the failure is
+	/// transient and the work has attempts left.
+	///
+	/// Returns whether the work was scheduled.
+	fn schedule_retry(&self, work: Work, attempts: u32, error: &SolverError) -> bool {
+		let class = error.class();
+		if !class.is_transient() {
+			tracing::error!(
+				error = %error,
+				category = %class.category,
+				"Handling failed permanently"
+			);
+			return false;
+		}
+
+		match self.retries.schedule(work, attempts) {
+			Some(backoff) => {
+				tracing::warn!(
+					error = %error,
+					attempts,
+					backoff_ms = backoff.as_millis() as u64,
+					"Handling failed transiently, retrying"
+				);
+				true
+			}
+			None => {
+				tracing::error!(error = %error, attempts, "Handling kept failing, giving up");
+				false
+			}
+		}
+	}
+
+	/// Handles intents and events that failed transiently again once their
+	/// backoff has passed.
+	async fn retry_failed_work(&self) -> Result<(), SolverError> {
+		for (work, attempts) in self.retries.take_due() {
+			match work {
+				Work::Intent(intent) => self.process_intent(intent, attempts + 1).await?,
+				Work::Event(event) => self.process_event(event, attempts + 1).await?,
+			}
+		}
+		Ok(())
+	}
+
+	/// Records how far the processing of an intent got in the intent ledger.
+	async fn record_intent_state(
+		&self,
+		intent_id: &str,
+		state: IntentState,
+	) -> Result<(), SolverError> {
+		self.intent_ledger
+			.record(intent_id, state)
+			.await
+			.map_err(SolverError::from)
+	}
+
+	/// Checks on-chain whether the fill a previous run, or an earlier attempt
+	/// of this run, may have sent for an intent failed, so that the intent can
+	/// be filled again.
+	///
+	/// A fill that landed, or that may still land, must not be sent twice.
+	/// Neither must one whose transaction hash was never stored, since the
+	/// sender may have stopped between sending it and storing it.
+	async fn interrupted_fill_failed(&self, order_id: &str) -> Result<bool, SolverError> {
+		let Some(tx_hash) = self
+			.optional::<solver_types::TransactionHash>("fills", order_id)
+			.await?
+		else {
+			tracing::warn!("Not processing intent whose fill may have been sent already");
+			return Ok(false);
+		};
+
+		match self.delivery.get_status(&tx_hash).await {
+			Ok(false) => {
+				tracing::info!("Fill sent earlier reverted, processing intent again");
+				Ok(true)
+			}
+			Ok(true) => {
+				tracing::info!("Fill sent earlier landed, not filling intent again");
+				Ok(false)
+			}
+			Err(e) => {
+				tracing::warn!(
+					error = %e,
+					"Fill sent earlier is not mined, not filling intent again"
+				);
+				Ok(false)
+			}
+		}
+	}
+
+	/// Holds an intent that failed validation, or could not be handled, in the
+	/// dead-letter queue.
+	async fn dead_letter_intent(
+		&self,
+		intent: &Intent,
+		reason: &str,
+		error_class: Option<ErrorClass>,
+	) -> Result<(), SolverError> {
+		tracing::warn!(reason = %reason, "Dead-lettered intent");
+		self.dead_letters
+			.record(
+				&intent.id,
+				FailureStage::Validation,
+				reason.to_string(),
+				error_class,
+				Some(intent.clone()),
+			)
+			.await
+			.map(|_| ())
+			.map_err(SolverError::from)
+	}
+
+	/// Schedules a deferred order to be decided again, or skips it once it
+	/// has been deferred too often.
+	///
+	/// `attempts` counts the deferrals of the order, including this one.
+	fn defer_order(&self, order: Arc<Order>, retry_after: Duration, attempts: u32) {
+		let order_id = order.id.clone();
+		if self.deferred.defer(order, retry_after, attempts) {
+			self.event_bus
+				.publish(SolverEvent::Order(OrderEvent::Deferred {
+					order_id,
+					retry_after,
+				}))
+				.ok();
+		} else {
+			tracing::info!(order_id = %truncate_id(&order_id), attempts, "Skipping order deferred too often");
+			self.event_bus
+				.publish(SolverEvent::Order(OrderEvent::Skipped {
+					order_id,
+					reason: format!("Deferred more than {} times", attempts - 1),
+				}))
+				.ok();
+		}
+	}
+
+	/// Decides deferred orders again once their retry time has come.
+	///
+	/// Each order is decided against a freshly built execution context.
+	/// Orders whose fill deadline passed while they waited are dropped; the
+	/// expiry sweep reports them.
+	async fn retry_deferred_orders(&self) -> Result<(), SolverError> {
+		for (order, attempts) in self.deferred.take_due() {
+			self.decide_again(order, attempts).await;
+		}
+
+		Ok(())
+	}
+
+	/// Decides an order again against a freshly built execution context.
+	///
+	/// `deferrals` counts the times the order has been deferred so far.
+	/// Returns false, without deciding, if the order's fill deadline passed.
+	async fn decide_again(&self, order: Arc<Order>, deferrals: u32) -> bool {
+		let fill_deadline = match self.order.fill_deadline(&order) {
+			Ok(deadline) if deadline > now_secs() => deadline,
+			_ => return false,
+		};
+		self.expiry.track(&order.id, fill_deadline);
+
+		let context = self.build_execution_context(&order).await;
+		let decision = match self.order.evaluate_fast_path(&order, &context) {
+			Some(decision) => decision,
+			None => self.order.should_execute(&order, &context).await,
+		};
+		match decision {
+			ExecutionDecision::Execute(params) => {
+				tracing::info!(order_id = %truncate_id(&order.id), deferrals, "Executing order decided again");
+				self.event_bus
+					.publish(SolverEvent::Order(OrderEvent::Executing { order, params }))
+					.ok();
+			}
+			ExecutionDecision::Skip(reason) => {
+				self.event_bus
+					.publish(SolverEvent::Order(OrderEvent::Skipped {
+						order_id: order.id.clone(),
+						reason,
+					}))
+					.ok();
+			}
+			ExecutionDecision::Defer(duration) => {
+				self.defer_order(order, duration, deferrals + 1);
+			}
+		}
+		true
+	}
+
+	/// Re-drives the dead-lettered orders that are due.
+	///
+	/// Intents that failed validation are handled again from scratch, orders
+	/// whose fill failed are decided again, and orders whose claim failed are
+	/// claimed again. Orders whose fill deadline passed can no longer be
+	/// filled and stay held.
+	async fn retry_dead_letters(&self) -> Result<(), SolverError> {
+		let due = self
+			.dead_letters
+			.take_due()
+			.await
+			.map_err(SolverError::from)?;
+
+		for dead_letter in due {
+			let order_id = dead_letter.order_id;
+			tracing::info!(
+				order_id = %truncate_id(&order_id),
+				stage = ?dead_letter.stage,
+				retries = dead_letter.retries,
+				"Re-driving dead-lettered order"
+			);
+			match dead_letter.stage {
+				FailureStage::Validation => match dead_letter.intent {
+					Some(intent) => self.process_intent(intent, 1).await?,
+					None => tracing::warn!("Cannot re-drive intent that was not kept"),
+				},
+				FailureStage::Fill => {
+					let Some(order) = self.optional::<Order>("orders", &order_id).await? else {
+						tracing::warn!("Cannot re-drive fill of unknown order");
+						continue;
+					};
+					// Reopen an order the ledger holds as finished so its
+					// execution is not skipped as a duplicate
+					if matches!(
+						self.intent_ledger
+							.state(&order_id)
+							.await
+							.map_err(SolverError::from)?,
+						Some(IntentState::Finished)
+					) {
+						self.record_intent_state(&order_id, IntentState::Processing)
+							.await?;
+					}
+					if !self.decide_again(Arc::new(order), 0).await {
+						tracing::warn!("Cannot re-drive fill after the fill deadline");
+					}
+				}
+				FailureStage::Claim => {
+					if self
+						.optional::<solver_types::FillProof>("fill_proofs", &order_id)
+						.await?
+						.is_none()
+					{
+						tracing::warn!("Cannot re-drive claim without fill proof");
+						continue;
+					}
+					self.event_bus
+						.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
+							order_id,
+						}))
+						.ok();
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Starts watching the block an intent was opened in for reorgs.
+	///
+	/// Intents are opened on the chain their inputs come from. Intents without
+	/// a known block or opening transaction are not tracked.
+	fn track_intent_block(&self, intent: &Intent, order: &Order) {
+		let Some(reorgs) = &self.reorgs else {
+			return;
+		};
+		let (Some(block_number), Some(tx_hash)) = (
+			intent.metadata.block_number,
+			&intent.metadata.transaction_hash,
+		) else {
+			return;
+		};
+		let Ok(tx_hash) = hex::decode(tx_hash.trim_start_matches("0x")) else {
+			return;
+		};
+		let Some(chain_id) = self
+			.order
+			.order_assets(order)
+			.ok()
+			.and_then(|assets| assets.inputs.first().map(|input| input.chain_id))
+		else {
+			return;
+		};
+
+		reorgs.track_intent(
+			chain_id,
+			TrackedIntent {
+				order_id: order.id.clone(),
+				tx_hash: solver_types::TransactionHash(tx_hash),
+				block_number,
+			},
+		);
+	}
+
+	/// Handles an intent whose opening transaction was dropped by a reorg.
+	///
+	/// The intent is withdrawn from hot storage so that it neither executes
+	/// nor later expires.
+	#[instrument(skip_all, fields(order_id = %truncate_id(order_id)))]
+	async fn handle_intent_invalidated(&self, order_id: &str) -> Result<(), SolverError> {
+		self.latency.finish(order_id);
+		self.deferred.cancel(order_id);
+		self.expiry.release(order_id);
+		self.release_risk(order_id);
+		self.record_intent_state(order_id, IntentState::Dropped)
+			.await?;
+		self.storage
+			.remove("intents", order_id)
+			.await
+			.map_err(SolverError::from)
+	}
+
+	/// Runs the risk validators on a new order, recording the risk score it
+	/// passed with in the execution context.
+	///
+	/// Orders on the stablecoin fast path skip the market checks, such as
+	/// liquidity. Always passes when no validators are configured.
+	async fn assess_risk(
+		&self,
+		order: &Order,
+		context: &mut ExecutionContext,
+		fast_path: bool,
+	) -> Result<(), String> {
+		let Some(validation) = &self.validation else {
+			return Ok(());
+		};
+
+		let assets = self.order.order_assets(order).map_err(|e| e.to_string())?;
+		let user = self.order.user(order);
+		let assessment = validation
+			.assess(&ValidationRequest {
+				order,
+				assets: &assets,
+				user: user.as_ref(),
+				context,
+				fast_path,
+			})
+			.await
+			.map_err(|e| e.to_string())?;
+		tracing::debug!(
+			risk_score = assessment.score,
+			scores = ?assessment.scores,
+			"Assessed order risk"
+		);
+		context.risk_score = Some(assessment.score);
+		Ok(())
+	}
+
+	/// Stops counting a finished order against the risk limits.
+	fn release_risk(&self, order_id: &str) {
+		if let Some(validation) = &self.validation {
+			validation.release(order_id);
+		}
+	}
+
+	/// Picks the destination chain of an order fillable on several chains.
+	///
+	/// Orders keep the destination they were validated with when the solver
+	/// holds the outputs of none of their routes, or the route cannot be
+	/// applied.
+	async fn route_order(&self, order: &mut Order) {
+		if order.routes.len() < 2 {
+			return;
+		}
+		let solver = match self.account.get_address().await {
+			Ok(address) => address,
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to resolve solver address for routing");
+				return;
+			}
+		};
+
+		let routes = order.routes.clone();
+		let Some((route, cost)) = self.routes.select(&routes, &solver).await else {
+			tracing::info!(
+				routes = routes.len(),
+				"No route has the outputs in inventory, keeping the order's destination"
+			);
+			return;
+		};
+		match self.order.apply_route(order, route) {
+			Ok(()) => tracing::info!(
+				chain_id = route.chain_id,
+				gas_usd = ?cost.gas_usd,
+				confirmation_secs = cost.confirmation_time.as_secs(),
+				"Routed order"
+			),
+			Err(e) => tracing::warn!(
+				chain_id = route.chain_id,
+				error = %e,
+				"Failed to route order, keeping its destination"
+			),
+		}
+	}
+
+	/// Runs the risk validators' market checks on an order without
+	/// recording it, such as whether its inputs can be re-swapped within the
+	/// configured price impact.
+	///
+	/// Always passes when no validators are configured.
+	async fn check_market(&self, order: &Order, context: &ExecutionContext) -> Result<(), String> {
+		let Some(validation) = &self.validation else {
+			return Ok(());
+		};
+
+		let assets = self.order.order_assets(order).map_err(|e| e.to_string())?;
+		let user = self.order.user(order);
+		validation
+			.check_market(&ValidationRequest {
+				order,
+				assets: &assets,
+				user: user.as_ref(),
+				context,
+				fast_path: false,
+			})
+			.await
+			.map(|_| ())
+			.map_err(|e| e.to_string())
+	}
+
+	/// Handles order execution by filling the order.
+	///
+	/// Orders that can share a transaction with others are queued in their
+	/// fill bundle; all other orders, and orders whose fill is retried, are
+	/// filled right away.
+	#[instrument(skip_all, fields(order_id = %truncate_id(&order.id)))]
+	async fn handle_order_execution(
+		&self,
+		order: Arc<Order>,
+		params: solver_types::ExecutionParams,
+		attempts: u32,
+	) -> Result<(), SolverError> {
+		// Executions replayed from the journal or retried after a failed
+		// submission must not fill the order twice
+		match self
+			.intent_ledger
+			.state(&order.id)
+			.await
+			.map_err(SolverError::from)?
+		{
+			Some(IntentState::Finished) => {
+				tracing::info!("Skipping execution of finished order");
+				return Ok(());
+			}
+			Some(IntentState::Filling) => {
+				if !self.interrupted_fill_failed(&order.id).await? {
+					self.latency.finish(&order.id);
+					return Ok(());
+				}
+				self.record_intent_state(&order.id, IntentState::Processing)
+					.await?;
+			}
+			_ => {}
+		}
+
+		// Don't fill intents a reorg dropped from the chain
+		if self
+			.reorgs
+			.as_ref()
+			.is_some_and(|reorgs| reorgs.is_invalidated(&order.id))
+		{
+			tracing::info!("Skipping execution of order invalidated by reorg");
+			return Ok(());
+		}
+
+		// Don't fill orders whose fill deadline passed before they executed
+		if self.expiry.is_expired(&order.id, now_secs()) {
+			return self.expire_order(&order.id).await;
+		}
+
+		// Hold back fills while the gas budget or failure breaker pauses them
+		if let Some(gas_budget) = &self.gas_budget {
+			let chain_id = self
+				.order
+				.order_assets(&order)
+				.ok()
+				.and_then(|assets| assets.outputs.first().map(|output| output.chain_id));
+			if let Some(reason) = gas_budget.pause_reason(chain_id) {
+				tracing::info!(reason = %reason, "Deferring order while fills are paused");
+				self.latency.finish(&order.id);
+				self.defer_order(order, PAUSED_FILL_RETRY, 1);
+				return Ok(());
+			}
+		}
+
+		// The intent is being acted on, so it can no longer expire
+		self.storage
+			.remove("intents", &order.id)
+			.await
+			.map_err(SolverError::from)?;
+
+		// Hold back fills that can share a transaction with others, unless
+		// they cannot afford to wait for the bundle. Bundles only fill orders
+		// in full.
+		let partial = match self.bundler {
+			Some(_) => self.fill_amount(&order, &params).await?.1,
+			None => false,
+		};
+		if let Some(bundler) = self
+			.bundler
+			.as_ref()
+			.filter(|_| params.urgency.is_none() && !partial && attempts == 1)
+		{
+			let key = self
+				.order
+				.order_assets(&order)
+				.ok()
+				.filter(|_| self.order.supports_fill_bundles(&order))
+				.and_then(|assets| {
+					let implementation = order.implementation.as_ref().unwrap_or(&order.standard);
+					bundler.key(implementation, &assets)
+				});
+			if let Some(key) = key {
+				tracing::debug!(chain_id = key.chain_id, "Queued fill for bundling");
+				if let Some(fills) = bundler.push(key, QueuedFill { order, params }) {
+					self.dispatch_fill_bundle(fills).await?;
+				}
+				return Ok(());
+			}
+		}
+
+		self.dispatch_fill(order, params, attempts).await
+	}
+
+	/// Returns the amount of an order's first output its next fill delivers,
+	/// and whether that leaves part of the order unfilled.
+	///
+	/// Orders of standards without partial fills are filled in full. Others
+	/// are filled up to what earlier fills left, capped at the amount the
+	/// strategy chose.
+	async fn fill_amount(
+		&self,
+		order: &Order,
+		params: &solver_types::ExecutionParams,
+	) -> Result<(U256, bool), SolverError> {
+		let total = self
+			.order
+			.order_assets(order)
+			.map_err(SolverError::from)?
+			.outputs
+			.first()
+			.map(|output| output.amount)
+			.unwrap_or_default();
+		if !self.order.supports_partial_fill(order) {
+			return Ok((total, false));
+		}
+
+		let filled = self.filled_amount(&order.id).await?;
+		let amount = total.saturating_sub(filled);
+		let amount = params
+			.fill_amount
+			.map_or(amount, |fill_amount| fill_amount.min(amount));
+		Ok((amount, filled + amount < total))
+	}
+
+	/// Returns the amount of an order's first output delivered by its
+	/// confirmed partial fills.
+	async fn filled_amount(&self, order_id: &str) -> Result<U256, SolverError> {
+		Ok(self
+			.optional::<U256>("filled_amounts", order_id)
+			.await?
+			.unwrap_or_default())
+	}
+
+	/// Adds the amount delivered by an order's confirmed fill to the order's
+	/// filled amount.
+	///
+	/// Returns what remains to be filled, or `None` if the order is filled
+	/// in full at once, as orders of standards without partial fills are.
+	async fn record_fill_amount(&self, order: &Order) -> Result<Option<U256>, SolverError> {
+		let Some(amount) = self.optional::<U256>("fill_amounts", &order.id).await? else {
+			return Ok(None);
+		};
+		let total = self
+			.order
+			.order_assets(order)
+			.map_err(SolverError::from)?
+			.outputs
+			.first()
+			.map(|output| output.amount)
+			.unwrap_or_default();
+
+		let filled = self.filled_amount(&order.id).await? + amount;
+		self.storage
+			.store("filled_amounts", &order.id, &filled)
+			.await
+			.map_err(SolverError::from)?;
+		self.storage
+			.remove("fill_amounts", &order.id)
+			.await
+			.map_err(SolverError::from)?;
+		Ok(Some(total.saturating_sub(filled)))
+	}
+
+	/// Generates and submits the fill transaction of a single order.
+	///
+	/// This method:
+	/// 1. Generates a fill transaction for the order
+	/// 2. Submits the transaction through the delivery service
+	/// 3. Stores transaction hashes and mappings for later retrieval
+	///
+	/// Everything after generating the transaction talks to the fill chain, so
+	/// it runs in that chain's task group instead of blocking the event loop.
+	/// If it fails there, the execution of the order is handed back to the
+	/// event loop, counting `attempts` attempts. Urgent fills are priced
+	/// above the chain's fee estimate and, if the strategy asks for it,
+	/// submitted through the private route.
+	async fn dispatch_fill(
+		&self,
+		order: Arc<Order>,
+		params: solver_types::ExecutionParams,
+		attempts: u32,
+	) -> Result<(), SolverError> {
+		// Generate fill transaction
+		let (fill_amount, _) = self.fill_amount(&order, &params).await?;
+		let mut tx = self
+			.order
+			.generate_fill_transaction(&order, &params, fill_amount)
+			.await
+			.map_err(SolverError::from)?;
+
+		// Remember how much the fill delivers, to credit it once confirmed
+		if self.order.supports_partial_fill(&order) {
+			self.storage
+				.store("fill_amounts", &order.id, &fill_amount)
+				.await
+				.map_err(SolverError::from)?;
+		}
+
+		let chain_id = tx.chain_id;
+		let work = vec![Work::Event(SolverEvent::Order(OrderEvent::Executing {
+			order: order.clone(),
+			params: params.clone(),
+		}))];
+		let order_service = self.order.clone();
+		let delivery = self.delivery.clone();
+		let storage = self.storage.clone();
+		let event_bus = self.event_bus.clone();
+		let latency = self.latency.clone();
+		let metrics = self.metrics.clone();
+		let expiry = self.expiry.clone();
+		let intent_ledger = self.intent_ledger.clone();
+		let dry_run = self.dry_run.clone();
+		let approvals = self.approvals.clone();
+		let account = self.account.clone();
+
+		let dispatch = async move {
+			if let Some(urgency) = params.urgency {
+				if let Err(e) = delivery
+					.escalate_fees(&mut tx, urgency.priority_fee_bump_bps)
+					.await
+				{
+					tracing::warn!(error = %e, "Failed to escalate fees of urgent fill");
+				}
+			}
+
+			// Skip fills the solver cannot pay gas for in the chain's gas token
+			match delivery.ensure_gas_balance(&tx).await {
+				Ok(()) => {}
+				Err(DeliveryError::InsufficientGasBalance(reason)) => {
+					tracing::warn!(reason = %reason, "Skipping order due to insufficient gas balance");
+					latency.finish(&order.id);
+					event_bus
+						.publish(SolverEvent::Order(OrderEvent::Skipped {
+							order_id: order.id.clone(),
+							reason,
+						}))
+						.ok();
+					return Ok(());
+				}
+				Err(e) => {
+					tracing::warn!(error = %e, "Failed to check gas balance");
+				}
+			}
+
+			// Don't submit a fill once the race for the order is likely lost
+			if let Err(elapsed) = latency.check(&order.id) {
+				skip_late_order(&latency, &metrics, &event_bus, order.id.clone(), elapsed);
+				return Ok(());
+			}
+
+			// A fill submitted after the fill deadline can only revert
+			if !expiry.clear_for_fill(&order.id, now_secs()) {
+				tracing::info!("Dropping fill of order past its fill deadline");
+				return Ok(());
+			}
+			// A fill racing a competitor's landed fill can only revert
+			if filled_by_other(&order_service, &delivery, &order).await {
+				flag_filled_by_other(&latency, &event_bus, &order.id);
+				return Ok(());
+			}
+			if let Some(elapsed) = latency.finish(&order.id) {
+				metrics.record_fill_latency(elapsed);
+			}
+
+			if let Some(dry_run) = &dry_run {
+				let assets: Vec<OrderAssets> =
+					order_service.order_assets(&order).into_iter().collect();
+				dry_run.fill(&assets, &tx).await;
+				event_bus
+					.publish(SolverEvent::Order(OrderEvent::Skipped {
+						order_id: order.id.clone(),
+						reason: DRY_RUN_SKIP_REASON.to_string(),
+					}))
+					.ok();
+				return Ok(());
+			}
+
+			// The settler pulls the outputs, so it needs an allowance for them
+			if let Some(approvals) = &approvals {
+				let orders = std::slice::from_ref(&order);
+				if let Err(e) = approve_fill(approvals, &account, &order_service, orders, &tx).await
+				{
+					tracing::warn!(error = %e, "Skipping order whose outputs could not be approved");
+					event_bus
+						.publish(SolverEvent::Order(OrderEvent::Skipped {
+							order_id: order.id.clone(),
+							reason: format!("Failed to approve outputs: {}", e),
+						}))
+						.ok();
+					return Ok(());
+				}
+			}
+
+			submit_transaction(
+				&delivery,
+				&storage,
+				&event_bus,
+				Some(&intent_ledger),
+				&order.id,
+				tx,
+				TransactionType::Fill,
+				params.urgency.is_some_and(|urgency| urgency.private),
+			)
+			.await
+		};
+		self.chain_tasks.spawn_work(
+			chain_id,
+			"dispatch_fill",
+			work,
+			attempts,
+			dispatch.in_current_span(),
+		);
+
+		Ok(())
+	}
+
+	/// Dispatches the fill bundles whose window ended.
+	async fn flush_fill_bundles(&self) -> Result<(), SolverError> {
+		let Some(bundler) = &self.bundler else {
+			return Ok(());
+		};
+
+		for fills in bundler.take_ready() {
+			self.dispatch_fill_bundle(fills).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Generates and submits a single transaction filling a bundle of orders.
+	///
+	/// Orders whose latency budget ran out while they waited are skipped. A
+	/// bundle left with a single order, or whose transaction cannot be
+	/// generated, is filled order by order instead.
+	#[instrument(skip_all, fields(orders = fills.len()))]
+	async fn dispatch_fill_bundle(&self, fills: Vec<QueuedFill>) -> Result<(), SolverError> {
+		let mut live = Vec::with_capacity(fills.len());
+		for fill in fills {
+			if self.expiry.is_expired(&fill.order.id, now_secs()) {
+				self.expire_order(&fill.order.id).await?;
+				continue;
+			}
+			match self.latency.check(&fill.order.id) {
+				Ok(()) => live.push(fill),
+				Err(elapsed) => skip_late_order(
+					&self.latency,
+					&self.metrics,
+					&self.event_bus,
+					fill.order.id.clone(),
+					elapsed,
+				),
+			}
+		}
+
+		let bundle: Vec<(&Order, &solver_types::ExecutionParams)> = live
+			.iter()
+			.map(|fill| (fill.order.as_ref(), &fill.params))
+			.collect();
+		let tx = if bundle.len() > 1 {
+			self.order
+				.generate_bundle_fill_transaction(&bundle)
+				.await
+				.map_err(|e| {
+					tracing::warn!(error = %e, "Failed to generate fill bundle, filling orders separately");
+				})
+				.ok()
+		} else {
+			None
+		};
+		let Some(tx) = tx else {
+			for fill in live {
+				self.dispatch_fill(fill.order, fill.params, 1).await?;
+			}
+			return Ok(());
+		};
+
+		let work = live
+			.iter()
+			.map(|fill| {
+				Work::Event(SolverEvent::Order(OrderEvent::Executing {
+					order: fill.order.clone(),
+					params: fill.params.clone(),
+				}))
+			})
+			.collect();
+		let orders: Vec<Arc<Order>> = live.into_iter().map(|fill| fill.order).collect();
+		let order_ids: Vec<String> = orders.iter().map(|order| order.id.clone()).collect();
+		let chain_id = tx.chain_id;
+		let order_service = self.order.clone();
+		let delivery = self.delivery.clone();
+		let storage = self.storage.clone();
+		let event_bus = self.event_bus.clone();
+		let latency = self.latency.clone();
+		let metrics = self.metrics.clone();
+		let expiry = self.expiry.clone();
+		let intent_ledger = self.intent_ledger.clone();
+		let dry_run = self.dry_run.clone();
+		let approvals = self.approvals.clone();
+		let account = self.account.clone();
+
+		let dispatch = async move {
+			// Skip bundles the solver cannot pay gas for in the chain's gas token
+			match delivery.ensure_gas_balance(&tx).await {
+				Ok(()) => {}
+				Err(DeliveryError::InsufficientGasBalance(reason)) => {
+					tracing::warn!(reason = %reason, "Skipping fill bundle due to insufficient gas balance");
+					for order_id in order_ids {
+						latency.finish(&order_id);
+						event_bus
+							.publish(SolverEvent::Order(OrderEvent::Skipped {
+								order_id,
+								reason: reason.clone(),
+							}))
+							.ok();
+					}
+					return Ok(());
+				}
+				Err(e) => {
+					tracing::warn!(error = %e, "Failed to check gas balance");
+				}
+			}
+
+			// A bundle filling an order past its fill deadline can only revert
+			let now = now_secs();
+			let expired: Vec<&String> = order_ids
+				.iter()
+				.filter(|order_id| !expiry.clear_for_fill(order_id, now))
+				.collect();
+			if !expired.is_empty() {
+				tracing::info!(
+					expired = expired.len(),
+					"Dropping fill bundle with orders past their fill deadline"
+				);
+				for order_id in order_ids
+					.iter()
+					.filter(|order_id| !expired.contains(order_id))
+				{
+					latency.finish(order_id);
+					event_bus
+						.publish(SolverEvent::Order(OrderEvent::Skipped {
+							order_id: order_id.clone(),
+							reason: "Bundled with an order past its fill deadline".to_string(),
+						}))
+						.ok();
+				}
+				return Ok(());
+			}
+
+			// Drop bundles racing for an order another solver already filled,
+			// like bundles with an order past its fill deadline
+			let mut lost = Vec::new();
+			for order in &orders {
+				if filled_by_other(&order_service, &delivery, order).await {
+					lost.push(order.id.clone());
+				}
+			}
+			if !lost.is_empty() {
+				for order_id in &lost {
+					flag_filled_by_other(&latency, &event_bus, order_id);
+				}
+				for order_id in order_ids.iter().filter(|order_id| !lost.contains(order_id)) {
+					latency.finish(order_id);
+					event_bus
+						.publish(SolverEvent::Order(OrderEvent::Skipped {
+							order_id: order_id.clone(),
+							reason: "Bundled with an order filled by another solver".to_string(),
+						}))
+						.ok();
+				}
+				return Ok(());
+			}
+
+			if let Some(dry_run) = &dry_run {
+				for order_id in &order_ids {
+					if let Some(elapsed) = latency.finish(order_id) {
+						metrics.record_fill_latency(elapsed);
+					}
+				}
+				let assets: Vec<OrderAssets> = orders
+					.iter()
+					.filter_map(|order| order_service.order_assets(order).ok())
+					.collect();
+				dry_run.fill(&assets, &tx).await;
+				for order_id in order_ids {
+					event_bus
+						.publish(SolverEvent::Order(OrderEvent::Skipped {
+							order_id,
+							reason: DRY_RUN_SKIP_REASON.to_string(),
+						}))
+						.ok();
+				}
+				return Ok(());
+			}
+
+			// The settler pulls the outputs of every bundled order
+			if let Some(approvals) = &approvals {
+				if let Err(e) =
+					approve_fill(approvals, &account, &order_service, &orders, &tx).await
+				{
+					tracing::warn!(error = %e, "Skipping fill bundle whose outputs could not be approved");
+					for order_id in order_ids {
+						latency.finish(&order_id);
+						event_bus
+							.publish(SolverEvent::Order(OrderEvent::Skipped {
+								order_id,
+								reason: format!("Failed to approve outputs: {}", e),
+							}))
+							.ok();
+					}
+					return Ok(());
+				}
+			}
+
+			for order_id in &order_ids {
+				if let Some(elapsed) = latency.finish(order_id) {
+					metrics.record_fill_latency(elapsed);
+				}
+			}
+			tracing::info!(orders = order_ids.len(), "Executing fill bundle");
+
+			submit_bundle(
+				&delivery,
+				&storage,
+				&event_bus,
+				&intent_ledger,
+				&order_ids,
+				tx,
+			)
+			.await
+		};
+		self.chain_tasks.spawn_work(
+			chain_id,
+			"dispatch_fill_bundle",
+			work,
+			1,
+			dispatch.in_current_span(),
+		);
+
+		Ok(())
+	}
+
+	/// Monitors a pending transaction until it is confirmed or fails.
+	///
+	/// Spawns an async task that polls the transaction status at regular intervals
+	/// until the transaction is confirmed, fails, or the monitoring timeout is reached.
+	/// The task runs in a child span carrying the order and transaction context.
+	#[instrument(skip_all, fields(order_id = %truncate_id(&order_id), tx_hash = %truncate_id(&hex::encode(&tx_hash.0)), tx_type = ?tx_type, chain_id = chain_id))]
+	async fn handle_transaction_pending(
+		&self,
+		order_id: String,
+		tx_hash: solver_types::TransactionHash,
+		tx_type: TransactionType,
+		chain_id: u64,
+	) -> Result<(), SolverError> {
+		self.pending.track(&order_id, &tx_hash, tx_type, chain_id);
+		// Also covers transactions submitted before a restart
+		self.delivery.track_transaction(&tx_hash, chain_id);
+
+		// Spawn a task to monitor the transaction
+		let delivery = self.delivery.clone();
+		let event_bus = self.event_bus.clone();
+		let pending = self.pending.clone();
+		let reorgs = self.reorgs.clone();
+		let timeout_minutes = self.config.solver.monitoring_timeout_minutes;
+		let networks = self.delivery.networks();
+		let mut backoff = PollBackoff::new(
+			networks.poll_interval(chain_id),
+			networks.max_poll_interval(chain_id),
+		);
+
+		let monitor = async move {
+			let monitoring_timeout = tokio::time::Duration::from_secs(timeout_minutes * 60);
+
+			let start_time = tokio::time::Instant::now();
+
+			loop {
+				// A replacement or replaced transaction was mined instead
+				if !pending.is_tracked(&tx_hash) {
+					break;
+				}
+
+				// Check if we've exceeded the timeout
+				if start_time.elapsed() > monitoring_timeout {
+					tracing::warn!(
+						order_id = %truncate_id(&order_id),
+						tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
+						tx_type = ?tx_type,
+						"Transaction monitoring timeout reached after {} minutes",
+						timeout_minutes
+					);
+					pending.resolve(&tx_hash);
+					break;
+				}
+
+				// Try to get transaction status
+				match delivery.get_status_on(chain_id, &tx_hash).await {
+					Ok(true) => {
+						pending.resolve(&tx_hash);
+
+						// Transaction is confirmed and successful
+						// Get the full receipt for the event
+						match delivery.confirm_on(chain_id, &tx_hash).await {
+							Ok(receipt) => {
+								if let Some(reorgs) = &reorgs {
+									reorgs.track_receipt(
+										chain_id,
+										TrackedReceipt {
+											order_id: order_id.clone(),
+											tx_hash: tx_hash.clone(),
+											tx_type,
+											block_number: receipt.block_number,
+										},
+									);
+								}
+								tracing::info!(
+									order_id = %truncate_id(&order_id),
+									tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
+									"Confirmed {}",
+									match tx_type {
+										TransactionType::Fill => "fill",
+										TransactionType::Claim => "claim",
+									}
+								);
+								event_bus
+									.publish(SolverEvent::Delivery(
+										DeliveryEvent::TransactionConfirmed {
+											tx_hash: tx_hash.clone(),
+											receipt,
+											tx_type,
+										},
+									))
+									.ok();
+							}
+							Err(e) => {
+								tracing::error!(
+									order_id = %truncate_id(&order_id),
+									tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
+									tx_type = ?tx_type,
+									error = %e,
+									"Failed to wait for confirmations"
+								);
+							}
+						}
+						break;
+					}
+					Ok(false) => {
+						pending.resolve(&tx_hash);
+
+						// Transaction failed
+						event_bus
+							.publish(SolverEvent::Delivery(DeliveryEvent::TransactionFailed {
+								tx_hash: tx_hash.clone(),
+								error: "Transaction reverted".to_string(),
+							}))
+							.ok();
+						break;
+					}
+					Err(e) => {
+						// Transaction not yet confirmed or error
+						// Show user-friendly message for common cases
+						let message = match e {
+							DeliveryError::NoProviderAvailable => {
+								"Waiting for transaction to be mined"
+							}
+							_ => "Checking transaction status",
+						};
+
+						// Always log at info level so users see progress
+						tracing::info!(
+							order_id = %truncate_id(&order_id),
+							tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
+							tx_type = ?tx_type,
+							elapsed_secs = start_time.elapsed().as_secs(),
+							"{}",
+							message
+						);
+					}
+				}
+
+				// Back off while the chain stays on the same block
+				let block = delivery.block_number(chain_id).await.ok();
+				tokio::time::sleep(backoff.next_delay(block)).await;
+			}
+			Ok(())
+		};
+		self.chain_tasks.spawn(
+			chain_id,
+			"monitor_transaction",
+			monitor.instrument(tracing::info_span!("monitor_transaction")),
+		);
+
+		Ok(())
+	}
+
+	/// Re-submits transactions pending for longer than the configured threshold.
+	///
+	/// Each replacement raises fees further above the current gas price. Errors
+	/// are logged, since the original transaction may still be mined.
+	async fn replace_stuck_transactions(&self) {
+		let Some(config) = &self.config.delivery.replacement else {
+			return;
+		};
+
+		let threshold = Duration::from_secs(config.stuck_after_seconds);
+		for tx in self.pending.stuck(threshold, config.max_replacements) {
+			// Raising the fees of a fill another solver beat can only make it
+			// revert sooner
+			if tx.tx_type == TransactionType::Fill && self.fill_lost(&tx.tx_hash).await {
+				self.pending.abandon(&tx.tx_hash);
+				flag_filled_by_other(&self.latency, &self.event_bus, &tx.order_id);
+				continue;
+			}
+
+			let bump_bps = config.fee_bump_bps * (tx.replacements as u64 + 1);
+			let result = match self.delivery.escalated_gas(tx.chain_id, bump_bps).await {
+				Ok(gas) => self.delivery.replace(tx.chain_id, &tx.tx_hash, gas).await,
+				Err(e) => Err(e),
+			};
+
+			match result {
+				Ok(new_tx_hash) => {
+					self.pending.replaced(&tx.tx_hash, &new_tx_hash);
+					self.event_bus
+						.publish(SolverEvent::Delivery(DeliveryEvent::TransactionReplaced {
+							order_id: tx.order_id,
+							old_tx_hash: tx.tx_hash,
+							new_tx_hash,
+							tx_type: tx.tx_type,
+							chain_id: tx.chain_id,
+						}))
+						.ok();
+				}
+				Err(e) => {
+					tracing::warn!(
+						order_id = %truncate_id(&tx.order_id),
+						tx_hash = %truncate_id(&hex::encode(&tx.tx_hash.0)),
+						error = %e,
+						"Failed to replace stuck transaction"
+					);
+				}
+			}
+		}
+	}
+
+	/// Returns whether the order of a pending fill was filled by another
+	/// solver in the meantime.
+	///
+	/// Bundles are not checked: the settler skips their orders that were
+	/// already filled, and fills the rest.
+	async fn fill_lost(&self, tx_hash: &solver_types::TransactionHash) -> bool {
+		let order_ids = self.transaction_orders(tx_hash).await;
+		let [order_id] = order_ids.as_slice() else {
+			return false;
+		};
+		match self.storage.retrieve::<Order>("orders", order_id).await {
+			Ok(order) => filled_by_other(&self.order, &self.delivery, &order).await,
+			Err(_) => false,
+		}
+	}
+
+	/// Checks each chain with recent intents or receipts for reorgs.
+	///
+	/// Checks run in the chains' task groups; a chain still being checked from
+	/// the previous interval is skipped.
+	fn check_reorgs(&self) {
+		let Some(reorgs) = &self.reorgs else {
+			return;
+		};
+
+		for chain_id in reorgs.chains() {
+			if !reorgs.begin_check(chain_id) {
+				continue;
+			}
+
+			let reorgs = reorgs.clone();
+			let delivery = self.delivery.clone();
+			let event_bus = self.event_bus.clone();
+			let check = async move {
+				let result = check_chain_reorgs(&reorgs, &delivery, &event_bus, chain_id).await;
+				reorgs.end_check(chain_id);
+				result
+			};
+			self.chain_tasks.spawn(chain_id, "check_reorgs", check);
+		}
+	}
+
+	/// Probes every chain's RPC endpoints in the chain's task group.
+	///
+	/// Publishes a degradation event for each endpoint that stopped serving
+	/// requests since the last check.
+	fn check_delivery_endpoints(&self) {
+		for chain_id in self.delivery.chain_ids() {
+			let delivery = self.delivery.clone();
+			let event_bus = self.event_bus.clone();
+			let check = async move {
+				let failures = delivery
+					.check_endpoints(chain_id)
+					.await
+					.map_err(SolverError::from)?;
+				for failure in failures {
+					tracing::warn!(
+						chain_id,
+						endpoint = %failure.endpoint,
+						reason = %failure.reason,
+						"RPC endpoint degraded"
+					);
+					event_bus
+						.publish(SolverEvent::Delivery(DeliveryEvent::ProviderDegraded {
+							chain_id,
+							endpoint: failure.endpoint,
+							reason: failure.reason,
+						}))
+						.ok();
+				}
+				Ok(())
+			};
+			self.chain_tasks.spawn(chain_id, "check_endpoints", check);
+		}
+	}
+
+	/// Counts the gas a confirmed transaction spent against the budget of the
+	/// chain it was submitted to.
+	fn track_gas_spend(
+		&self,
+		tx_hash: &solver_types::TransactionHash,
+		receipt: &solver_types::TransactionReceipt,
+	) {
+		let Some(gas_budget) = &self.gas_budget else {
+			return;
+		};
+		let gas = U256::from(receipt.gas_used).saturating_mul(receipt.effective_gas_price);
+		let chain_id = self.delivery.transaction_chain(tx_hash);
+		self.publish_pause_changes(gas_budget.record_transaction(chain_id, gas, receipt.success));
+	}
+
+	/// Logs and publishes changes in whether fills are paused.
+	fn publish_pause_changes(&self, changes: Vec<PauseChange>) {
+		for change in changes {
+			let event = match change {
+				PauseChange::Paused { chain_id, reason } => {
+					tracing::warn!(chain_id = ?chain_id, reason = %reason, "Paused new fills");
+					DeliveryEvent::FillsPaused { chain_id, reason }
+				}
+				PauseChange::Resumed { chain_id } => {
+					tracing::info!(chain_id = ?chain_id, "Resumed new fills");
+					DeliveryEvent::FillsResumed { chain_id }
+				}
+			};
+			self.event_bus.publish(SolverEvent::Delivery(event)).ok();
+		}
+	}
+
+	/// Prunes the data of finished orders in the background.
+	///
+	/// A pass reads the status of every stored order, so it runs outside the
+	/// event loop; passes are not overlapped.
+	fn prune_finished_orders(&self) {
+		let Some(pruner) = self.pruner.clone() else {
+			return;
+		};
+		let metrics = self.metrics.clone();
+		tokio::spawn(async move {
+			match pruner.prune().await {
+				Ok(report) if report.orders > 0 => {
+					tracing::info!(
+						orders = report.orders,
+						entries = report.entries,
+						"Pruned finished orders"
+					);
+					metrics.record_pruned(report.orders, report.entries);
+				}
+				Ok(_) => {}
+				Err(e) => tracing::warn!(error = %e, "Failed to prune finished orders"),
+			}
+		});
+	}
+
+	/// Refreshes the remote policy lists in the background.
+	///
+	/// The lists fetched last stay in force when the remote URL cannot be
+	/// reached, so a failed refresh is only logged.
+	fn refresh_policy(&self) {
+		let order = self.order.clone();
+		tokio::spawn(async move {
+			match order.policy().refresh().await {
+				Ok(entries) => tracing::debug!(entries, "Refreshed remote policy lists"),
+				Err(e) => tracing::warn!(error = %e, "Failed to refresh remote policy lists"),
+			}
+		});
+	}
+
+	/// Polls the solver's balances in the background.
+	///
+	/// Polls touch every chain, so they run outside the event loop; a poll
+	/// still running when the next one is due is not overlapped.
+	fn poll_inventory(&self) {
+		let Some(inventory) = self.inventory.clone() else {
+			return;
+		};
+		let account = self.account.clone();
+		tokio::spawn(async move {
+			match account.get_address().await {
+				Ok(owner) => inventory.refresh(&owner).await,
+				Err(e) => {
+					tracing::warn!(error = %e, "Failed to resolve solver address for inventory");
+				}
+			}
+		});
+	}
+
+	/// Handles a transaction that replaced a stuck one.
+	///
+	/// Records the replacement against its orders and monitors it like a newly
+	/// submitted transaction.
+	#[instrument(skip_all, fields(order_id = %truncate_id(&order_id), tx_hash = %truncate_id(&hex::encode(&tx_hash.0))))]
+	async fn handle_transaction_replaced(
+		&self,
+		order_id: String,
+		old_tx_hash: solver_types::TransactionHash,
+		tx_hash: solver_types::TransactionHash,
+		tx_type: TransactionType,
+		chain_id: u64,
+	) -> Result<(), SolverError> {
+		let namespace = match tx_type {
+			TransactionType::Fill => "fills",
+			TransactionType::Claim => "claims",
+		};
+
+		// A replaced bundle still fills all of its orders
+		if let Ok(order_ids) = self
+			.storage
+			.retrieve::<Vec<String>>("bundles", &hex::encode(&old_tx_hash.0))
+			.await
+		{
+			for bundled in &order_ids {
+				self.storage
+					.store(namespace, bundled, &tx_hash)
+					.await
+					.map_err(SolverError::from)?;
+			}
+			self.storage
+				.store("bundles", &hex::encode(&tx_hash.0), &order_ids)
+				.await
+				.map_err(SolverError::from)?;
+		}
+
+		self.storage
+			.store(namespace, &order_id, &tx_hash)
+			.await
+			.map_err(SolverError::from)?;
+		self.storage
+			.store("tx_to_order", &hex::encode(&tx_hash.0), &order_id)
+			.await
+			.map_err(SolverError::from)?;
+
+		self.handle_transaction_pending(order_id, tx_hash, tx_type, chain_id)
+			.await
+	}
+
+	/// Handles a confirmed transaction that was dropped by a reorg.
+	///
+	/// A reorged fill must be proven again, so the fill proofs of the orders
+	/// it filled are discarded. The transaction is then monitored again until
+	/// it is re-included.
+	#[instrument(skip_all, fields(order_id = %truncate_id(&order_id), tx_hash = %truncate_id(&hex::encode(&tx_hash.0))))]
+	async fn handle_transaction_reorged(
+		&self,
+		order_id: String,
+		tx_hash: solver_types::TransactionHash,
+		tx_type: TransactionType,
+		chain_id: u64,
+	) -> Result<(), SolverError> {
+		if tx_type == TransactionType::Fill {
+			for filled in self.transaction_orders(&tx_hash).await {
+				self.storage
+					.remove("fill_proofs", &filled)
+					.await
+					.map_err(SolverError::from)?;
+			}
+		}
+
+		self.handle_transaction_pending(order_id, tx_hash, tx_type, chain_id)
+			.await
+	}
+
+	/// Handles confirmed transactions based on their type.
+	///
+	/// Routes handling to specific methods based on whether this is a fill
+	/// or claim transaction.
+	#[instrument(skip_all, fields(tx_hash = %truncate_id(&hex::encode(&tx_hash.0)), tx_type = ?tx_type, order_id = tracing::field::Empty))]
+	async fn handle_transaction_confirmed(
+		&self,
+		tx_hash: solver_types::TransactionHash,
+		_receipt: solver_types::TransactionReceipt,
+		tx_type: TransactionType,
+	) -> Result<(), SolverError> {
+		// A transaction re-included after a reorg was already accounted for
+		let reconfirmed = self
+			.reorgs
+			.as_ref()
+			.is_some_and(|reorgs| reorgs.take_reorged(&tx_hash));
+		if reconfirmed && tx_type == TransactionType::Claim {
+			tracing::info!("Claim re-confirmed after reorg");
+			return Ok(());
+		}
+		if !reconfirmed {
+			self.track_gas_spend(&tx_hash, &_receipt);
+		}
+
+		// Bundled fills are resolved order by order
+		if tx_type == TransactionType::Fill {
+			if let Ok(order_ids) = self
+				.storage
+				.retrieve::<Vec<String>>("bundles", &hex::encode(&tx_hash.0))
+				.await
+			{
+				return self
+					.handle_bundle_confirmed(tx_hash, _receipt, order_ids, reconfirmed)
+					.await;
+			}
+		}
+
+		if !reconfirmed {
+			if let Err(e) = self.record_transaction(&tx_hash, &_receipt, &tx_type).await {
+				tracing::warn!(error = %e, "Failed to record transaction in ledger");
+			}
+		}
+
+		if !_receipt.success {
+			// A claim may revert only because its proof went stale
+			if tx_type == TransactionType::Claim && self.regenerate_stale_proof(&tx_hash).await? {
+				return Ok(());
+			}
+			self.event_bus
+				.publish(SolverEvent::Delivery(DeliveryEvent::TransactionFailed {
+					tx_hash,
+					error: "Transaction reverted".to_string(),
+				}))
+				.ok();
+			return Ok(());
+		}
+
+		// Handle based on transaction type
+		match tx_type {
+			TransactionType::Fill => {
+				// For fill transactions, start settlement monitoring
+				self.handle_fill_confirmed(tx_hash, _receipt).await?;
+			}
+			TransactionType::Claim => {
+				// For claim transactions, mark order as completed
+				self.handle_claim_confirmed(tx_hash, _receipt).await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Handles confirmed fill transactions.
+	///
+	/// This method:
+	/// 1. Looks up the order associated with the transaction
+	/// 2. Spawns a task to validate the fill and monitor claim readiness
+	async fn handle_fill_confirmed(
+		&self,
+		tx_hash: solver_types::TransactionHash,
+		_receipt: solver_types::TransactionReceipt,
+	) -> Result<(), SolverError> {
+		// Look up the order ID from the transaction hash
+		let order_id = match self
+			.storage
+			.retrieve::<String>("tx_to_order", &hex::encode(&tx_hash.0))
+			.await
+		{
+			Ok(id) => id,
+			Err(_) => {
+				return Ok(()); // Don't fail the handler, just continue
+			}
+		};
+
+		tracing::Span::current()
+			.record("order_id", tracing::field::display(truncate_id(&order_id)));
+
+		// Retrieve the order
+		let order = match self.storage.retrieve::<Order>("orders", &order_id).await {
+			Ok(order) => order,
+			Err(_) => {
+				return Ok(());
+			}
+		};
+
+		// A partial fill leaves the order open for another fill
+		if let Some(remaining) = self.record_fill_amount(&order).await? {
+			if !remaining.is_zero() {
+				tracing::info!(remaining = %remaining, "Order partially filled");
+				self.decide_again(Arc::new(order), 0).await;
+				return Ok(());
+			}
+		}
+
+		self.monitor_fill(order, tx_hash);
+
+		Ok(())
+	}
+
+	/// Handles a confirmed fill bundle.
+	///
+	/// The order implementation decides from the receipt which of the bundled
+	/// orders were filled. Gas is recorded once for the whole bundle; filled
+	/// orders then proceed to settlement like individually filled ones, while
+	/// the rest are reported as failed.
+	async fn handle_bundle_confirmed(
+		&self,
+		tx_hash: solver_types::TransactionHash,
+		receipt: solver_types::TransactionReceipt,
+		order_ids: Vec<String>,
+		reconfirmed: bool,
+	) -> Result<(), SolverError> {
+		let mut orders = Vec::with_capacity(order_ids.len());
+		for order_id in &order_ids {
+			if let Ok(order) = self.storage.retrieve::<Order>("orders", order_id).await {
+				orders.push(order);
+			}
+		}
+		let filled = self
+			.order
+			.bundle_fill_results(&orders.iter().collect::<Vec<_>>(), &receipt);
+
+		if !reconfirmed {
+			if let Err(e) = self.record_bundle(&receipt, &orders, &filled).await {
+				tracing::warn!(error = %e, "Failed to record fill bundle in ledger");
+			}
+		}
+
+		if !receipt.success {
+			self.event_bus
+				.publish(SolverEvent::Delivery(DeliveryEvent::TransactionFailed {
+					tx_hash,
+					error: "Transaction reverted".to_string(),
+				}))
+				.ok();
+			return Ok(());
+		}
+
+		tracing::info!(
+			orders = orders.len(),
+			filled = filled.iter().filter(|filled| **filled).count(),
+			"Confirmed fill bundle"
+		);
+		for (order, filled) in orders.into_iter().zip(filled) {
+			if filled {
+				self.monitor_fill(order, tx_hash.clone());
+			} else if !reconfirmed {
+				tracing::info!(order_id = %truncate_id(&order.id), "Order not filled by bundle");
+				self.handle_order_outcome(&order.id, OrderOutcomeStatus::Failed, None)
+					.await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Spawns a task validating an order's confirmed fill and monitoring when
+	/// it can be claimed.
+	fn monitor_fill(&self, order: Order, tx_hash: solver_types::TransactionHash) {
+		let order_id = order.id.clone();
+
+		// The fill's outputs are in flight until the claim returns the inputs
+		let assets = self.order.order_assets(&order).ok();
+		if let Some(assets) = &assets {
+			self.capital.commit(&order.id, assets.outputs.clone());
+			self.refresh_capital_metrics();
+		}
+
+		// Claims are checked and submitted on the chain the inputs came from
+		let origin_chain_id = assets
+			.as_ref()
+			.and_then(|assets| assets.inputs.first().map(|input| input.chain_id));
+
+		self.event_bus
+			.publish(SolverEvent::Settlement(SettlementEvent::FillDetected {
+				order_id: order.id.clone(),
+				tx_hash: tx_hash.clone(),
+			}))
+			.ok();
+
+		// Spawn a task to validate fill and monitor claim readiness
+		let settlement = self.settlement.clone();
+		let storage = self.storage.clone();
+		let event_bus = self.event_bus.clone();
+		let timeout_minutes = self.config.solver.monitoring_timeout_minutes;
+
+		let monitor = async move {
+			// Retrieve and extract proof
+			let fill_proof = match settlement.get_attestation(&order, &tx_hash).await {
+				Ok(proof) => proof,
+				Err(e) => {
+					return Err(SolverError::Service(format!(
+						"Failed to validate fill: {}",
+						e
+					)));
+				}
+			};
+
+			// Store the fill proof
+			storage
+				.store("fill_proofs", &order.id, &fill_proof)
+				.await
+				.map_err(SolverError::from)?;
+
+			// Monitor claim readiness
 			let monitoring_timeout = tokio::time::Duration::from_secs(timeout_minutes * 60);
-			let poll_interval = tokio::time::Duration::from_secs(3); // Poll every 3 seconds for faster confirmation
+			let check_interval = tokio::time::Duration::from_secs(1); // Check every 1 second for faster claim detection
+			let start_time = tokio::time::Instant::now();
+
+			loop {
+				// Check if we've exceeded the timeout
+				if start_time.elapsed() > monitoring_timeout {
+					tracing::warn!(
+						order_id = %truncate_id(&order_id),
+						"Claim readiness monitoring timeout reached after {} minutes",
+						timeout_minutes
+					);
+					break;
+				}
+
+				// Check if we can claim
+				if settlement.can_claim(&order, &fill_proof).await {
+					tracing::info!(
+						order_id = %truncate_id(&order_id),
+						"Ready to claim"
+					);
+					event_bus
+						.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
+							order_id: order.id,
+						}))
+						.ok();
+					break;
+				}
+
+				// Wait before next check
+				tokio::time::sleep(check_interval).await;
+			}
+			Ok(())
+		};
+		self.chain_tasks.spawn(
+			origin_chain_id.unwrap_or_default(),
+			"monitor_claim_readiness",
+			monitor.instrument(tracing::info_span!(
+				"monitor_claim_readiness",
+				chain_id = ?origin_chain_id
+			)),
+		);
+	}
+
+	/// Handles confirmed claim transactions.
+	///
+	/// Marks the order as completed and publishes the completion event.
+	async fn handle_claim_confirmed(
+		&self,
+		tx_hash: solver_types::TransactionHash,
+		receipt: solver_types::TransactionReceipt,
+	) -> Result<(), SolverError> {
+		// Look up the order ID from the transaction hash
+		let order_id = match self
+			.storage
+			.retrieve::<String>("tx_to_order", &hex::encode(&tx_hash.0))
+			.await
+		{
+			Ok(id) => id,
+			Err(_) => {
+				return Ok(());
+			}
+		};
+		tracing::Span::current()
+			.record("order_id", tracing::field::display(truncate_id(&order_id)));
+
+		if let Some(turnaround) = self.capital.release(&order_id) {
+			tracing::debug!(
+				turnaround_secs = turnaround.as_secs(),
+				"Released capital of claimed order"
+			);
+			self.refresh_capital_metrics();
+		}
+
+		// Feed the gas actually used back into claim gas calibration
+		if let Some(calibrator) = self.claim_gas.as_ref().filter(|_| receipt.gas_used > 0) {
+			if let Ok(path) = self
+				.storage
+				.retrieve::<String>("claim_paths", &order_id)
+				.await
+			{
+				calibrator.record(&path, receipt.gas_used).await;
+			}
+		}
+
+		// Emit completed event
+		tracing::info!(
+			order_id = %truncate_id(&order_id),
+			"Completed"
+		);
+		self.event_bus
+			.publish(SolverEvent::Settlement(SettlementEvent::Completed {
+				order_id: order_id.clone(),
+			}))
+			.ok();
+
+		Ok(())
+	}
+
+	/// Returns whether a claim's gas costs more than the claim pays out.
+	///
+	/// The gas is sized from the claim transaction's gas limit, which is
+	/// calibrated from past claims when possible. Claims whose reward or gas
+	/// cannot be valued are not deemed unprofitable.
+	async fn claim_unprofitable(
+		&self,
+		estimate: &ClaimEstimate,
+		claim_tx: &Transaction,
+		gas_price: Option<U256>,
+	) -> bool {
+		let (Some(gas_limit), Some(gas_price)) =
+			(claim_tx.gas_limit.or(estimate.gas_estimate), gas_price)
+		else {
+			return false;
+		};
+		if estimate.reward.is_empty() {
+			return false;
+		}
+
+		let fee = gas_price.saturating_mul(U256::from(gas_limit));
+		let gas = self.delivery.networks().gas_amount(claim_tx.chain_id, fee);
+		let (Some(reward_usd), Some(gas_usd)) = (
+			self.context.tokens_usd(&estimate.reward).await,
+			self.context.gas_usd(&[gas]).await,
+		) else {
+			return false;
+		};
+		tracing::debug!(reward_usd, gas_usd, "Valued claim");
+		gas_usd > reward_usd
+	}
+
+	/// Completes an order whose protocol repaid the solver without a claim.
+	///
+	/// The repayment is journaled like a confirmed claim, without gas.
+	async fn complete_repaid_order(&self, order: &Order) -> Result<(), SolverError> {
+		match self.order.order_assets(order) {
+			Ok(assets) => {
+				let postings = self.value_postings(&assets.inputs, &TransactionType::Claim);
+				if let Err(e) = self
+					.ledger
+					.post(EntryKind::Claim, Some(&order.id), postings)
+					.await
+				{
+					tracing::warn!(error = %e, "Failed to record repayment in ledger");
+				}
+			}
+			Err(e) => tracing::warn!(error = %e, "Failed to resolve assets of repaid order"),
+		}
+
+		if let Some(turnaround) = self.capital.release(&order.id) {
+			tracing::debug!(
+				turnaround_secs = turnaround.as_secs(),
+				"Released capital of repaid order"
+			);
+			self.refresh_capital_metrics();
+		}
+
+		tracing::info!(order_id = %truncate_id(&order.id), "Completed without claim");
+		self.event_bus
+			.publish(SolverEvent::Settlement(SettlementEvent::Completed {
+				order_id: order.id.clone(),
+			}))
+			.ok();
+		Ok(())
+	}
+
+	/// Journals the value moved by a confirmed transaction.
+	///
+	/// Gas is always recorded. Successful fills move output tokens out of
+	/// inventory, and successful claims move input tokens into inventory, or
+	/// into the treasury when a claim recipient is configured for the chain.
+	async fn record_transaction(
+		&self,
+		tx_hash: &solver_types::TransactionHash,
+		receipt: &solver_types::TransactionReceipt,
+		tx_type: &TransactionType,
+	) -> Result<(), SolverError> {
+		let order_id = self
+			.storage
+			.retrieve::<String>("tx_to_order", &hex::encode(&tx_hash.0))
+			.await
+			.map_err(SolverError::from)?;
+		let order = self
+			.storage
+			.retrieve::<Order>("orders", &order_id)
+			.await
+			.map_err(SolverError::from)?;
+		let assets = self.order.order_assets(&order).map_err(SolverError::from)?;
+
+		// Fills execute on the output chain and claims on the input chain.
+		// A partial fill only delivers its share of the outputs.
+		let fill_amount = match tx_type {
+			TransactionType::Fill => self.optional::<U256>("fill_amounts", &order_id).await?,
+			TransactionType::Claim => None,
+		};
+		let outputs = match fill_amount {
+			Some(amount) => prorate_outputs(&assets.outputs, amount),
+			None => assets.outputs.clone(),
+		};
+		let (moved, kind) = match tx_type {
+			TransactionType::Fill => (&outputs, EntryKind::Fill),
+			TransactionType::Claim => (&assets.inputs, EntryKind::Claim),
+		};
+		let Some(chain_id) = moved.first().map(|asset| asset.chain_id) else {
+			return Ok(());
+		};
+
+		let mut postings = gas_postings(chain_id, receipt);
+
+		// Reverted transactions only cost gas
+		if !receipt.success {
+			self.ledger
+				.post(EntryKind::Gas, Some(&order_id), postings)
+				.await
+				.map_err(SolverError::from)?;
+			return Ok(());
+		}
+
+		postings.extend(self.value_postings(moved, tx_type));
+		self.ledger
+			.post(kind, Some(&order_id), postings)
+			.await
+			.map_err(SolverError::from)?;
+		Ok(())
+	}
+
+	/// Journals the value moved by a confirmed fill bundle.
+	///
+	/// The bundle's gas is not attributed to any single order. Output tokens
+	/// are recorded only for the orders the bundle filled.
+	async fn record_bundle(
+		&self,
+		receipt: &solver_types::TransactionReceipt,
+		orders: &[Order],
+		filled: &[bool],
+	) -> Result<(), SolverError> {
+		let mut chain_id = None;
+		let mut fills = Vec::new();
+		for (order, filled) in orders.iter().zip(filled) {
+			let assets = self.order.order_assets(order).map_err(SolverError::from)?;
+			chain_id = chain_id.or(assets.outputs.first().map(|output| output.chain_id));
+			if *filled {
+				fills.push((order, assets.outputs));
+			}
+		}
+		let Some(chain_id) = chain_id else {
+			return Ok(());
+		};
+
+		self.ledger
+			.post(EntryKind::Gas, None, gas_postings(chain_id, receipt))
+			.await
+			.map_err(SolverError::from)?;
+
+		for (order, outputs) in fills {
+			self.ledger
+				.post(
+					EntryKind::Fill,
+					Some(&order.id),
+					self.value_postings(&outputs, &TransactionType::Fill),
+				)
+				.await
+				.map_err(SolverError::from)?;
+		}
+		Ok(())
+	}
+
+	/// Returns the postings moving an order's tokens for a transaction.
+	///
+	/// Fills move output tokens out of inventory. Claims move input tokens
+	/// into inventory, or into the treasury when a claim recipient is
+	/// configured for the chain.
+	fn value_postings(
+		&self,
+		moved: &[solver_types::TokenAmount],
+		tx_type: &TransactionType,
+	) -> Vec<Posting> {
+		let mut postings = Vec::new();
+		for asset in moved {
+			let ledger_asset = LedgerAsset {
+				chain_id: asset.chain_id,
+				token: Some(asset.token.clone()),
+			};
+			let (debit, credit) = match tx_type {
+				TransactionType::Fill => (LedgerAccount::FillCost, LedgerAccount::Inventory),
+				TransactionType::Claim => {
+					let claim_recipients = &self.config.settlement.claim_recipients;
+					if claim_recipients.contains_key(&asset.chain_id.to_string()) {
+						(LedgerAccount::Treasury, LedgerAccount::Revenue)
+					} else {
+						(LedgerAccount::Inventory, LedgerAccount::Revenue)
+					}
+				}
+			};
+			postings.push(Posting::debit(debit, ledger_asset.clone(), asset.amount));
+			postings.push(Posting::credit(credit, ledger_asset, asset.amount));
+		}
+		postings
+	}
+
+	/// Checks that fills considered final are still in the block they were
+	/// finalized in.
+	///
+	/// A fill whose block changed was reorged deeper than its chain's maximum
+	/// reorg depth. If it was re-included it is tracked again. Otherwise its
+	/// ledger entries are reversed, its fill proofs discarded and the
+	/// transaction reported as failed, which dead-letters its orders.
+	async fn check_finalized_fills(&self) {
+		let Some(reorgs) = &self.reorgs else {
+			return;
+		};
+
+		for fill in reorgs.finalized_fills() {
+			let chain_id = fill.chain_id;
+			let tx_hash = fill.receipt.tx_hash.clone();
+			match self
+				.delivery
+				.block_hash(chain_id, fill.receipt.block_number)
+				.await
+			{
+				Ok(Some(hash)) if hash != fill.block_hash => {}
+				// Unchanged, or not known to the RPC right now
+				Ok(_) => continue,
+				Err(e) => {
+					tracing::warn!(chain_id, error = %e, "Failed to check block of finalized fill");
+					continue;
+				}
+			}
+			if reorgs.take_finalized(chain_id, &tx_hash).is_empty() {
+				continue;
+			}
+
+			self.delivery.forget_receipt(&tx_hash).await;
+			if let Ok(included) = self.delivery.receipt_on(chain_id, &tx_hash).await {
+				if included.success {
+					tracing::warn!(
+						chain_id,
+						tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
+						block_number = included.block_number,
+						"Finalized fill moved by deep reorg"
+					);
+					reorgs.track_receipt(
+						chain_id,
+						TrackedReceipt {
+							block_number: included.block_number,
+							..fill.receipt
+						},
+					);
+					continue;
+				}
+			}
+
+			let depth = reorgs.depth(chain_id);
+			tracing::error!(
+				chain_id,
+				depth,
+				tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
+				block_number = fill.receipt.block_number,
+				"Finalized fill dropped by reorg beyond the maximum depth"
+			);
+			self.metrics.record_deep_reorg();
+			for order_id in self.transaction_orders(&tx_hash).await {
+				if let Err(e) = self.ledger.reverse(&order_id, EntryKind::Fill).await {
+					tracing::error!(
+						order_id = %truncate_id(&order_id),
+						error = %e,
+						"Failed to reverse ledger entries of reorged fill"
+					);
+				}
+				if let Err(e) = self.storage.remove("fill_proofs", &order_id).await {
+					tracing::warn!(
+						order_id = %truncate_id(&order_id),
+						error = %e,
+						"Failed to discard fill proof of reorged fill"
+					);
+				}
+			}
+			self.event_bus
+				.publish(SolverEvent::Delivery(DeliveryEvent::TransactionFailed {
+					tx_hash,
+					error: format!(
+						"Fill reorged out beyond the maximum reorg depth of {} blocks on chain {}",
+						depth, chain_id
+					),
+				}))
+				.ok();
+		}
+	}
+
+	/// Checks the ledger's invariants and compares inventory with on-chain balances.
+	///
+	/// Assets reconciled for the first time get an opening entry for the
+	/// balance the solver held before the ledger saw it. Afterwards, any
+	/// difference between the ledger and the chain is reported as drift.
+	async fn reconcile_ledger(&self) {
+		self.check_finalized_fills().await;
+
+		if let Err(e) = self.ledger.check_invariants() {
+			tracing::error!(error = %e, "Ledger invariant check failed");
+		}
+
+		let owner = match self.account.get_address().await {
+			Ok(address) => address,
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to resolve solver address for reconciliation");
+				return;
+			}
+		};
+
+		let mut drifted = 0;
+		for asset in self.ledger.inventory_assets() {
+			let on_chain = match &asset.token {
+				Some(token) => {
+					erc20::balance_of(&self.delivery, asset.chain_id, token, &owner).await
+				}
+				None => self
+					.delivery
+					.gas_balance(asset.chain_id, &owner)
+					.await
+					.map(|balance| balance.amount),
+			};
+			let on_chain = match on_chain {
+				Ok(balance) => I256::from_raw(balance),
+				Err(e) => {
+					tracing::warn!(asset = %asset, error = %e, "Failed to fetch balance for reconciliation");
+					continue;
+				}
+			};
+
+			let recorded = self.ledger.balance(LedgerAccount::Inventory, &asset);
+			let difference = on_chain - recorded;
+			if self.ledger.is_opened(&asset) {
+				if !difference.is_zero() {
+					drifted += 1;
+					tracing::warn!(
+						asset = %asset,
+						ledger = %recorded,
+						on_chain = %on_chain,
+						"Ledger inventory drifted from on-chain balance"
+					);
+				}
+				continue;
+			}
+
+			let amount = difference.unsigned_abs();
+			let postings = if difference.is_negative() {
+				vec![
+					Posting::debit(LedgerAccount::Equity, asset.clone(), amount),
+					Posting::credit(LedgerAccount::Inventory, asset.clone(), amount),
+				]
+			} else {
+				vec![
+					Posting::debit(LedgerAccount::Inventory, asset.clone(), amount),
+					Posting::credit(LedgerAccount::Equity, asset.clone(), amount),
+				]
+			};
+			match self.ledger.post(EntryKind::Opening, None, postings).await {
+				Ok(_) => {
+					tracing::info!(asset = %asset, balance = %on_chain, "Opened ledger balance")
+				}
+				Err(e) => {
+					tracing::warn!(asset = %asset, error = %e, "Failed to open ledger balance")
+				}
+			}
+		}
+
+		self.metrics.set_ledger_drifted_assets(drifted);
+		self.refresh_capital_metrics();
+	}
+
+	/// Rebuilds the fill proof of an order whose claim reverted, and queues
+	/// the claim again if the proof had gone stale.
+	///
+	/// The proof is considered stale when a fresh attestation differs from the
+	/// one the claim used, for example because the oracle root was updated.
+	/// Returns `false` when the proof is unchanged, cannot be rebuilt, or the
+	/// order has used up its regenerations, so the claim fails as usual.
+	async fn regenerate_stale_proof(
+		&self,
+		tx_hash: &solver_types::TransactionHash,
+	) -> Result<bool, SolverError> {
+		let max_regenerations = self.config.settlement.max_proof_regenerations;
+		let Some(order_id) = self.transaction_orders(tx_hash).await.pop() else {
+			return Ok(false);
+		};
+
+		let regenerations = match self
+			.storage
+			.retrieve::<u32>("proof_regenerations", &order_id)
+			.await
+		{
+			Ok(count) => count,
+			Err(StorageError::NotFound) => 0,
+			Err(e) => return Err(e.into()),
+		};
+		if regenerations >= max_regenerations {
+			return Ok(false);
+		}
+
+		let (order, proof) = match (
+			self.storage.retrieve::<Order>("orders", &order_id).await,
+			self.storage
+				.retrieve::<solver_types::FillProof>("fill_proofs", &order_id)
+				.await,
+		) {
+			(Ok(order), Ok(proof)) => (order, proof),
+			_ => return Ok(false),
+		};
+
+		let fresh = match self
+			.settlement
+			.get_attestation(&order, &proof.tx_hash)
+			.await
+		{
+			Ok(fresh) => fresh,
+			Err(e) => {
+				tracing::warn!(
+					order_id = %truncate_id(&order_id),
+					error = %e,
+					"Failed to rebuild fill proof after claim revert"
+				);
+				return Ok(false);
+			}
+		};
+		if fresh == proof {
+			return Ok(false);
+		}
+
+		tracing::info!(
+			order_id = %truncate_id(&order_id),
+			attempt = regenerations + 1,
+			"Claim reverted with a stale proof, retrying with a rebuilt proof"
+		);
+		self.storage
+			.store("fill_proofs", &order_id, &fresh)
+			.await
+			.map_err(SolverError::from)?;
+		self.storage
+			.store("proof_regenerations", &order_id, &(regenerations + 1))
+			.await
+			.map_err(SolverError::from)?;
+
+		self.event_bus
+			.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
+				order_id,
+			}))
+			.ok();
+		Ok(true)
+	}
+
+	/// Persists the status an event moves its orders to.
+	///
+	/// Rejected intents never became orders, so no status is kept for them.
+	async fn record_order_status(&self, event: &SolverEvent) -> Result<(), SolverError> {
+		let (order_ids, status) = match event {
+			SolverEvent::Discovery(DiscoveryEvent::IntentValidated { order, .. }) => {
+				(vec![order.id.clone()], DetailedIntentStatus::Registered)
+			}
+			SolverEvent::Discovery(DiscoveryEvent::IntentInvalidated { intent_id, .. }) => {
+				(vec![intent_id.clone()], DetailedIntentStatus::Failed)
+			}
+			SolverEvent::Order(OrderEvent::Executing { order, .. }) => {
+				(vec![order.id.clone()], DetailedIntentStatus::Filling)
+			}
+			SolverEvent::Order(
+				OrderEvent::Skipped { order_id, .. }
+				| OrderEvent::Expired { order_id }
+				| OrderEvent::FilledByOther { order_id },
+			) => (vec![order_id.clone()], DetailedIntentStatus::Failed),
+			SolverEvent::Delivery(
+				DeliveryEvent::TransactionPending {
+					order_id, tx_type, ..
+				}
+				| DeliveryEvent::TransactionReplaced {
+					order_id, tx_type, ..
+				}
+				| DeliveryEvent::TransactionReorged {
+					order_id, tx_type, ..
+				},
+			) => {
+				let status = match tx_type {
+					TransactionType::Fill => DetailedIntentStatus::Filling,
+					TransactionType::Claim => DetailedIntentStatus::Claiming,
+				};
+				(vec![order_id.clone()], status)
+			}
+			SolverEvent::Delivery(DeliveryEvent::TransactionFailed { tx_hash, .. }) => (
+				self.transaction_orders(tx_hash).await,
+				DetailedIntentStatus::Failed,
+			),
+			SolverEvent::Settlement(SettlementEvent::FillDetected { order_id, .. }) => {
+				(vec![order_id.clone()], DetailedIntentStatus::Filled)
+			}
+			SolverEvent::Settlement(SettlementEvent::Completed { order_id }) => {
+				(vec![order_id.clone()], DetailedIntentStatus::Completed)
+			}
+			_ => return Ok(()),
+		};
+
+		for order_id in order_ids {
+			self.storage
+				.store("order_status", &order_id, &status)
+				.await
+				.map_err(SolverError::from)?;
+		}
+		Ok(())
+	}
+
+	/// Handles failed transactions by reporting the failure for the related
+	/// orders and holding them in the dead-letter queue.
+	async fn handle_transaction_failed(
+		&self,
+		tx_hash: solver_types::TransactionHash,
+		error: String,
+	) -> Result<(), SolverError> {
+		if let Some(gas_budget) = &self.gas_budget {
+			self.publish_pause_changes(gas_budget.record_failure());
+		}
+
+		for order_id in self.transaction_orders(&tx_hash).await {
+			self.handle_order_outcome(&order_id, OrderOutcomeStatus::Failed, None)
+				.await?;
+
+			// Only claims are stored under the claims namespace
+			let claim = self
+				.optional::<solver_types::TransactionHash>("claims", &order_id)
+				.await?;
+			let stage = if claim.as_ref() == Some(&tx_hash) {
+				FailureStage::Claim
+			} else {
+				FailureStage::Fill
+			};
+			tracing::warn!(
+				order_id = %truncate_id(&order_id),
+				stage = ?stage,
+				error = %error,
+				"Dead-lettered order after failed transaction"
+			);
+			self.dead_letters
+				.record(&order_id, stage, error.clone(), None, None)
+				.await
+				.map_err(SolverError::from)?;
+		}
+
+		Ok(())
+	}
+
+	/// Returns the orders a transaction was submitted for.
+	///
+	/// A fill bundle belongs to every order it fills; any other transaction
+	/// belongs to a single order.
+	async fn transaction_orders(&self, tx_hash: &solver_types::TransactionHash) -> Vec<String> {
+		let tx_key = hex::encode(&tx_hash.0);
+		if let Ok(order_ids) = self
+			.storage
+			.retrieve::<Vec<String>>("bundles", &tx_key)
+			.await
+		{
+			return order_ids;
+		}
+
+		self.storage
+			.retrieve::<String>("tx_to_order", &tx_key)
+			.await
+			.into_iter()
+			.collect()
+	}
+
+	/// Records the profit and loss of a completed order from its ledger
+	/// entries.
+	///
+	/// The order completed whether or not its record could be written, so
+	/// failures are only logged. Returns the order's profit in USD, if it
+	/// could be priced.
+	async fn record_pnl(&self, order_id: &str) -> Option<f64> {
+		let recorded = async {
+			let order: Order = self.storage.retrieve("orders", order_id).await?;
+			let assets = self.order.order_assets(&order)?;
+			let journal = self.ledger.order_journal(order_id).await?;
+			let pnl = self
+				.pnl
+				.record(order_id, &assets, &journal, now_secs())
+				.await?;
+			Ok::<_, SolverError>(pnl)
+		}
+		.await;
+		match recorded {
+			Ok(pnl) => {
+				tracing::info!(
+					order_id = %truncate_id(order_id),
+					profit_usd = ?pnl.profit_usd,
+					"Recorded order P&L"
+				);
+				pnl.profit_usd
+			}
+			Err(e) => {
+				tracing::warn!(
+					order_id = %truncate_id(order_id),
+					error = %e,
+					"Failed to record order P&L"
+				);
+				None
+			}
+		}
+	}
 
-			let start_time = tokio::time::Instant::now();
+	/// Feeds the realized outcome of an order back to the execution strategy.
+	///
+	/// Latency is measured from order creation until the outcome is observed.
+	/// Completed orders pass the profit recorded for them.
+	#[instrument(skip_all, fields(order_id = %truncate_id(order_id)))]
+	async fn handle_order_outcome(
+		&self,
+		order_id: &str,
+		status: OrderOutcomeStatus,
+		realized_profit_usd: Option<f64>,
+	) -> Result<(), SolverError> {
+		let order = match self.storage.retrieve::<Order>("orders", order_id).await {
+			Ok(order) => order,
+			Err(_) => {
+				return Ok(());
+			}
+		};
 
-			loop {
-				// Check if we've exceeded the timeout
-				if start_time.elapsed() > monitoring_timeout {
+		let outcome = OrderOutcome {
+			status,
+			realized_profit_usd,
+			latency: std::time::Duration::from_secs(now_secs().saturating_sub(order.created_at)),
+		};
+
+		self.order.on_order_outcome(&order, &outcome).await;
+		self.release_risk(order_id);
+
+		// Only completed orders are finished. An order whose fill failed may
+		// be filled again, while one whose claim failed was filled and stays
+		// recorded as such.
+		let state = match status {
+			OrderOutcomeStatus::Completed => Some(IntentState::Finished),
+			OrderOutcomeStatus::Failed
+				if self
+					.optional::<solver_types::TransactionHash>("claims", order_id)
+					.await?
+					.is_some() =>
+			{
+				None
+			}
+			OrderOutcomeStatus::Failed => Some(IntentState::Dropped),
+		};
+		if let Some(state) = state {
+			self.record_intent_state(order_id, state).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Appends an event to the persistent event journal.
+	///
+	/// Each event is stored under the next value of the "events" sequence,
+	/// allowing consumers to replay events in the order they were observed.
+	async fn journal_event(&self, event: &SolverEvent) -> Result<(), SolverError> {
+		append_event(&self.storage, event)
+			.await
+			.map(|_| ())
+			.map_err(SolverError::from)
+	}
+
+	/// Appends an event to the audit log of every order it concerns.
+	async fn audit_event(&self, event: &SolverEvent) -> Result<(), SolverError> {
+		let order_ids = self.event_orders(event).await;
+		self.audit
+			.record(&order_ids, event)
+			.await
+			.map_err(SolverError::from)
+	}
+
+	/// Returns the IDs of the orders an event concerns.
+	///
+	/// Confirmations and failures concern the orders of their transaction.
+	/// Events about the engine as a whole concern no order.
+	pub async fn event_orders(&self, event: &SolverEvent) -> Vec<String> {
+		match event {
+			SolverEvent::Discovery(DiscoveryEvent::IntentDiscovered { intent }) => {
+				vec![intent.id.clone()]
+			}
+			SolverEvent::Discovery(DiscoveryEvent::IntentValidated { order, .. })
+			| SolverEvent::Order(OrderEvent::Executing { order, .. }) => vec![order.id.clone()],
+			SolverEvent::Discovery(
+				DiscoveryEvent::IntentRejected { intent_id, .. }
+				| DiscoveryEvent::IntentInvalidated { intent_id, .. },
+			) => vec![intent_id.clone()],
+			SolverEvent::Discovery(DiscoveryEvent::IntakeSaturated { .. }) => Vec::new(),
+			SolverEvent::Order(
+				OrderEvent::Skipped { order_id, .. }
+				| OrderEvent::Deferred { order_id, .. }
+				| OrderEvent::Expired { order_id }
+				| OrderEvent::FilledByOther { order_id },
+			)
+			| SolverEvent::Delivery(
+				DeliveryEvent::TransactionPending { order_id, .. }
+				| DeliveryEvent::TransactionReplaced { order_id, .. }
+				| DeliveryEvent::TransactionReorged { order_id, .. },
+			)
+			| SolverEvent::Settlement(
+				SettlementEvent::FillDetected { order_id, .. }
+				| SettlementEvent::ProofReady { order_id, .. }
+				| SettlementEvent::ClaimReady { order_id }
+				| SettlementEvent::Completed { order_id },
+			) => vec![order_id.clone()],
+			SolverEvent::Delivery(
+				DeliveryEvent::TransactionConfirmed { tx_hash, .. }
+				| DeliveryEvent::TransactionFailed { tx_hash, .. },
+			) => self.transaction_orders(tx_hash).await,
+			SolverEvent::Delivery(
+				DeliveryEvent::ProviderDegraded { .. }
+				| DeliveryEvent::FillsPaused { .. }
+				| DeliveryEvent::FillsResumed { .. },
+			) => Vec::new(),
+		}
+	}
+
+	/// Returns the chain an event happened on, if it names one or concerns
+	/// a transaction whose chain is tracked.
+	pub fn event_chain(&self, event: &SolverEvent) -> Option<u64> {
+		match event {
+			SolverEvent::Delivery(
+				DeliveryEvent::TransactionPending { chain_id, .. }
+				| DeliveryEvent::TransactionReplaced { chain_id, .. }
+				| DeliveryEvent::TransactionReorged { chain_id, .. }
+				| DeliveryEvent::ProviderDegraded { chain_id, .. },
+			) => Some(*chain_id),
+			SolverEvent::Delivery(
+				DeliveryEvent::FillsPaused { chain_id, .. }
+				| DeliveryEvent::FillsResumed { chain_id },
+			) => *chain_id,
+			SolverEvent::Delivery(
+				DeliveryEvent::TransactionConfirmed { tx_hash, .. }
+				| DeliveryEvent::TransactionFailed { tx_hash, .. },
+			)
+			| SolverEvent::Settlement(SettlementEvent::FillDetected { tx_hash, .. }) => {
+				self.delivery.transaction_chain(tx_hash)
+			}
+			_ => None,
+		}
+	}
+
+	/// Removes intents whose fill deadline passed before they were executed.
+	///
+	/// Expires the orders of removed intents the watcher did not expire
+	/// already, such as those validated before a restart.
+	async fn purge_expired_intents(&self) -> Result<(), SolverError> {
+		let expired = self
+			.storage
+			.purge_expired("intents")
+			.await
+			.map_err(SolverError::from)?;
+
+		for order_id in expired {
+			self.expire_order(&order_id).await?;
+		}
+
+		self.submissions
+			.purge_expired()
+			.await
+			.map_err(SolverError::from)?;
+
+		Ok(())
+	}
+
+	/// Expires the open orders whose fill deadline passed.
+	async fn expire_orders(&self) -> Result<(), SolverError> {
+		for order_id in self.expiry.take_expired(now_secs()) {
+			self.announce_expiry(&order_id).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Expires an order whose fill deadline passed, unless it already expired.
+	async fn expire_order(&self, order_id: &str) -> Result<(), SolverError> {
+		if self.expiry.expire(order_id, now_secs()) {
+			self.announce_expiry(order_id).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Withdraws the work scheduled for an expired order and reports its
+	/// expiry.
+	///
+	/// Fills already on their way are stopped by the watcher before they are
+	/// submitted.
+	async fn announce_expiry(&self, order_id: &str) -> Result<(), SolverError> {
+		tracing::info!(order_id = %truncate_id(order_id), "Expired");
+		self.deferred.cancel(order_id);
+		if let Some(bundler) = &self.bundler {
+			bundler.cancel(order_id);
+		}
+		self.latency.finish(order_id);
+		self.record_intent_state(order_id, IntentState::Finished)
+			.await?;
+		self.storage
+			.remove("intents", order_id)
+			.await
+			.map_err(SolverError::from)?;
+
+		self.event_bus
+			.publish(SolverEvent::Order(OrderEvent::Expired {
+				order_id: order_id.to_string(),
+			}))
+			.ok();
+		Ok(())
+	}
+
+	/// Returns the claim batch the claim of an order joins.
+	///
+	/// Claims are sent on the chain the order's inputs are locked on. Orders
+	/// whose inputs cannot be resolved share the batch of chain 0.
+	async fn claim_batch_key(&self, order_id: &str) -> Result<BatchKey, SolverError> {
+		let order: Order = self
+			.storage
+			.retrieve("orders", order_id)
+			.await
+			.map_err(SolverError::from)?;
+		let chain_id = self
+			.order
+			.order_assets(&order)
+			.ok()
+			.and_then(|assets| assets.inputs.first().map(|input| input.chain_id))
+			.unwrap_or_default();
+		Ok(BatchKey {
+			chain_id,
+			implementation: order.implementation.unwrap_or(order.standard),
+		})
+	}
+
+	/// Processes a flushed claim batch.
+	async fn flush_claim_batch(&self, mut batch: ClaimBatch) -> Result<(), SolverError> {
+		tracing::debug!(
+			claims = batch.orders.len(),
+			reason = batch.reason.as_str(),
+			"Flushing claim batch"
+		);
+		self.metrics
+			.record_claim_batch(batch.reason.as_str(), batch.orders.len());
+		self.process_claim_batch(&mut batch.orders, 1).await
+	}
+
+	/// Processes a batch of orders ready for claiming.
+	///
+	/// For each order in the batch:
+	/// 1. Retrieves the order and fill proof from storage
+	/// 2. Generates a claim transaction
+	/// 3. Submits the claim transaction
+	/// 4. Stores transaction hashes and mappings
+	///
+	/// Claims failing to submit are handed back to the event loop as
+	/// claim-ready events, counting `attempts` attempts.
+	#[instrument(skip_all)]
+	async fn process_claim_batch(
+		&self,
+		batch: &mut Vec<String>,
+		attempts: u32,
+	) -> Result<(), SolverError> {
+		for order_id in batch.drain(..) {
+			// Retrieve order
+			let order: Order = self
+				.storage
+				.retrieve("orders", &order_id)
+				.await
+				.map_err(SolverError::from)?;
+
+			// Retrieve fill proof (already validated when ClaimReady was emitted)
+			let fill_proof: solver_types::FillProof =
+				match self.storage.retrieve("fill_proofs", &order_id).await {
+					Ok(proof) => proof,
+					// The fill was reorged after its claim became ready
+					Err(StorageError::NotFound) => {
+						tracing::warn!(
+							order_id = %truncate_id(&order_id),
+							"Skipping claim without fill proof"
+						);
+						continue;
+					}
+					Err(e) => return Err(e.into()),
+				};
+
+			// The protocol already repaid the solver, so there is nothing to claim
+			if !self.order.requires_claim(&order) {
+				self.complete_repaid_order(&order).await?;
+				continue;
+			}
+
+			// Generate claim transaction
+			let mut claim_tx = self
+				.order
+				.generate_claim_transaction(&order, &fill_proof)
+				.await
+				.map_err(SolverError::from)?;
+
+			// Size the gas limit from past claims along the same settlement path
+			let implementation = order.implementation.as_ref().unwrap_or(&order.standard);
+			if let Some(calibrator) = &self.claim_gas {
+				let path =
+					ClaimGasCalibrator::path(implementation, claim_tx.chain_id, &claim_tx.data);
+				if let Some(gas_limit) = calibrator.gas_limit(&path).await {
+					tracing::debug!(path = %path, gas_limit, "Calibrated claim gas limit");
+					claim_tx.gas_limit = Some(gas_limit);
+				}
+				self.storage
+					.store("claim_paths", &order.id, &path)
+					.await
+					.map_err(SolverError::from)?;
+			}
+			if let (Some(batcher), Some(gas_limit)) = (&self.claim_batcher, claim_tx.gas_limit) {
+				let key = BatchKey {
+					chain_id: claim_tx.chain_id,
+					implementation: implementation.clone(),
+				};
+				batcher.observe(key, gas_limit);
+			}
+
+			// Weigh what the claim pays out against what its gas costs
+			let gas_price = self
+				.context
+				.gas_estimate(claim_tx.chain_id)
+				.await
+				.and_then(|estimate| estimate.max_price());
+			let estimate = match self.settlement.estimate_claim(&order, &fill_proof).await {
+				Ok(estimate) => estimate,
+				Err(e) => {
 					tracing::warn!(
 						order_id = %truncate_id(&order_id),
-						tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
-						tx_type = ?tx_type,
-						"Transaction monitoring timeout reached after {} minutes",
-						timeout_minutes
+						error = %e,
+						"Failed to estimate claim"
 					);
-					break;
+					ClaimEstimate::default()
 				}
+			};
+			let unprofitable = self
+				.claim_unprofitable(&estimate, &claim_tx, gas_price)
+				.await;
 
-				// Try to get transaction status
-				match delivery.get_status(&tx_hash).await {
-					Ok(true) => {
-						// Transaction is confirmed and successful
-						// Get the full receipt for the event
-						match delivery.confirm_with_default(&tx_hash).await {
-							Ok(receipt) => {
-								tracing::info!(
-									order_id = %truncate_id(&order_id),
-									tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
-									"Confirmed {}",
-									match tx_type {
-										TransactionType::Fill => "fill",
-										TransactionType::Claim => "claim",
-									}
-								);
-								event_bus
-									.publish(SolverEvent::Delivery(
-										DeliveryEvent::TransactionConfirmed {
-											tx_hash: tx_hash.clone(),
-											receipt,
-											tx_type,
-										},
-									))
-									.ok();
-							}
-							Err(e) => {
-								tracing::error!(
-									order_id = %truncate_id(&order_id),
-									tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
-									tx_type = ?tx_type,
-									error = %e,
-									"Failed to wait for confirmations"
-								);
-							}
+			// Hold the claim back while gas on its chain is above the ceiling,
+			// or while it costs more than it pays out
+			match &self.claim_schedule {
+				Some(schedule) => {
+					// The earlier of the settlement's and the order's deadlines binds
+					let claim_deadline =
+						match (estimate.deadline, self.order.claim_deadline(&order)) {
+							(Some(settlement), Some(order)) => Some(settlement.min(order)),
+							(settlement, order) => settlement.or(order),
+						};
+					let timing = schedule.decide(
+						&order.id,
+						claim_tx.chain_id,
+						gas_price,
+						unprofitable,
+						claim_deadline,
+						now_secs(),
+					);
+					match timing {
+						ClaimTiming::Submit => {}
+						ClaimTiming::Hold => continue,
+						ClaimTiming::Forced => tracing::warn!(
+							order_id = %truncate_id(&order_id),
+							gas_price = ?gas_price,
+							"Claiming despite high gas to meet the claim deadline"
+						),
+						ClaimTiming::Abandon => {
+							tracing::warn!(
+								order_id = %truncate_id(&order_id),
+								"Abandoning claim that costs more gas than it pays out"
+							);
+							continue;
 						}
-						break;
-					}
-					Ok(false) => {
-						// Transaction failed
-						event_bus
-							.publish(SolverEvent::Delivery(DeliveryEvent::TransactionFailed {
-								tx_hash: tx_hash.clone(),
-								error: "Transaction reverted".to_string(),
-							}))
-							.ok();
-						break;
 					}
-					Err(e) => {
-						// Transaction not yet confirmed or error
-						// Show user-friendly message for common cases
-						let message = match e {
-							DeliveryError::NoProviderAvailable => {
-								"Waiting for transaction to be mined"
-							}
-							_ => "Checking transaction status",
-						};
+				}
+				None if unprofitable => {
+					tracing::warn!(
+						order_id = %truncate_id(&order_id),
+						"Skipping claim that costs more gas than it pays out"
+					);
+					continue;
+				}
+				None => {}
+			}
 
-						// Always log at info level so users see progress
-						tracing::info!(
-							order_id = %truncate_id(&order_id),
-							tx_hash = %truncate_id(&hex::encode(&tx_hash.0)),
-							tx_type = ?tx_type,
-							elapsed_secs = start_time.elapsed().as_secs(),
-							"{}",
-							message
-						);
+			// Submit claim transaction through the claim chain's task group
+			let chain_id = claim_tx.chain_id;
+			let work = vec![Work::Event(SolverEvent::Settlement(
+				SettlementEvent::ClaimReady {
+					order_id: order_id.clone(),
+				},
+			))];
+			let delivery = self.delivery.clone();
+			let storage = self.storage.clone();
+			let event_bus = self.event_bus.clone();
+			let account = self.account.clone();
+			let claim_delegate = self.claim_delegate.clone();
+			let dispatch = async move {
+				// Claims the bot does not accept are submitted locally
+				if let Some(delegate) = claim_delegate {
+					match delegate_claim(
+						delegate.as_ref(),
+						&account,
+						&storage,
+						&event_bus,
+						&order.id,
+						&claim_tx,
+						fill_proof,
+					)
+					.await
+					{
+						Ok(()) => return Ok(()),
+						Err(e) => {
+							tracing::warn!(error = %e, "Claim delegation failed, claiming locally")
+						}
 					}
 				}
 
-				tokio::time::sleep(poll_interval).await;
+				submit_transaction(
+					&delivery,
+					&storage,
+					&event_bus,
+					None,
+					&order.id,
+					claim_tx,
+					TransactionType::Claim,
+					false,
+				)
+				.await
+			};
+			self.chain_tasks.spawn_work(
+				chain_id,
+				"dispatch_claim",
+				work,
+				attempts,
+				dispatch.instrument(tracing::info_span!(
+					"dispatch_claim",
+					order_id = %truncate_id(&order_id)
+				)),
+			);
+		}
+		Ok(())
+	}
+
+	/// Builds the execution context for strategy decisions.
+	///
+	/// Fees, balances and prices come from the context builder's short-lived
+	/// cache. The gas price is that of the chain the order is filled on,
+	/// falling back to a default when it cannot be estimated.
+	async fn build_execution_context(&self, order: &Order) -> ExecutionContext {
+		let assets = match self.order.order_assets(order) {
+			Ok(assets) => Some(assets),
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to resolve order assets for execution context");
+				None
+			}
+		};
+
+		let mut context = self
+			.context
+			.build(assets.as_ref(), U256::from(FALLBACK_GAS_PRICE))
+			.await;
+		context.partial_fills = self.order.supports_partial_fill(order);
+		if context.partial_fills {
+			match (self.filled_amount(&order.id).await, assets.as_ref()) {
+				(Ok(filled), Some(assets)) if !filled.is_zero() => {
+					let total = assets.outputs.first().map(|output| output.amount);
+					context.remaining_outputs = prorate_outputs(
+						&assets.outputs,
+						total.unwrap_or_default().saturating_sub(filled),
+					);
+				}
+				(Err(e), _) => {
+					tracing::warn!(error = %e, "Failed to read filled amount for execution context");
+				}
+				_ => {}
+			}
+		}
+		context.deadline = self
+			.order
+			.fill_deadline(order)
+			.ok()
+			.into_iter()
+			.chain(order.exclusive_until)
+			.min();
+		context.risk_score = self
+			.validation
+			.as_ref()
+			.and_then(|validation| validation.risk_score(&order.id));
+		context
+	}
+
+	/// Lists orders in the order they were validated.
+	///
+	/// Returns orders with sequence numbers after `cursor`, up to `limit`
+	/// (capped at MAX_PAGE_SIZE).
+	pub async fn list_orders(
+		&self,
+		cursor: Option<u64>,
+		limit: usize,
+	) -> Result<Page<Order>, SolverError> {
+		let (range, next_cursor) = self.page_range("orders", cursor, limit).await?;
+
+		let mut items = Vec::new();
+		for sequence in range {
+			let order_id = match self
+				.storage
+				.retrieve::<String>("order_sequence", &sequence.to_string())
+				.await
+			{
+				Ok(id) => id,
+				// Sequence allocated but index not written
+				Err(StorageError::NotFound) => continue,
+				Err(e) => return Err(e.into()),
+			};
+
+			match self.storage.retrieve::<Order>("orders", &order_id).await {
+				Ok(order) => items.push(order),
+				Err(StorageError::NotFound) => continue,
+				Err(e) => return Err(e.into()),
+			}
+		}
+
+		Ok(Page { items, next_cursor })
+	}
+
+	/// Returns the execution status of a validated order, or `None` if it
+	/// has not been validated yet.
+	///
+	/// Combines the stored order with its latest transactions, its fill proof
+	/// and the status recorded from the events seen for it.
+	pub async fn order_status(
+		&self,
+		order_id: &str,
+	) -> Result<Option<OrderStatusResponse>, SolverError> {
+		let order = match self.storage.retrieve::<Order>("orders", order_id).await {
+			Ok(order) => order,
+			Err(StorageError::NotFound) => return Ok(None),
+			Err(e) => return Err(e.into()),
+		};
+
+		let fill_tx_hash = self
+			.optional::<solver_types::TransactionHash>("fills", order_id)
+			.await?;
+		let fill_proof = self.optional("fill_proofs", order_id).await?;
+		let claim_tx_hash = self
+			.optional::<solver_types::TransactionHash>("claims", order_id)
+			.await?;
+		// The order is stored before the event validating it is handled
+		let status = self
+			.optional("order_status", order_id)
+			.await?
+			.unwrap_or(DetailedIntentStatus::Registered);
+
+		Ok(Some(OrderStatusResponse {
+			order_id: order.id.clone(),
+			status,
+			order,
+			fill_tx_hash: fill_tx_hash.map(|hash| format!("0x{}", hex::encode(&hash.0))),
+			fill_proof,
+			claim_tx_hash: claim_tx_hash.map(|hash| format!("0x{}", hex::encode(&hash.0))),
+		}))
+	}
+
+	/// Retrieves a value from storage, or `None` if it is not stored.
+	async fn optional<T: serde::de::DeserializeOwned>(
+		&self,
+		namespace: &str,
+		key: &str,
+	) -> Result<Option<T>, SolverError> {
+		match self.storage.retrieve::<T>(namespace, key).await {
+			Ok(value) => Ok(Some(value)),
+			Err(StorageError::NotFound) => Ok(None),
+			Err(e) => Err(e.into()),
+		}
+	}
+
+	/// Accepts an intent submitted directly to the solver.
+	///
+	/// The intent is checked against its standard, including its signature,
+	/// so the submitter learns of invalid orders right away. It is then
+	/// persisted, handed to the off-chain discovery source and processed like
+	/// any discovered intent. A successful return acknowledges the intent:
+	/// it will be handled even if the solver restarts before getting to it.
+	///
+	/// Returns false if the intent was already received, in which case the
+	/// submission is acknowledged again but not processed a second time.
+	pub async fn submit_intent(&self, intent: Intent) -> Result<bool, SolverError> {
+		self.order
+			.verify_intent(&intent)
+			.await
+			.map_err(SolverError::from)?;
+
+		if self
+			.optional::<Order>("orders", &intent.id)
+			.await?
+			.is_some()
+		{
+			return Ok(false);
+		}
+		let intent_id = intent.id.clone();
+		if !self
+			.submissions
+			.record(&intent)
+			.await
+			.map_err(SolverError::from)?
+		{
+			return Ok(false);
+		}
+
+		if let Err(e) = self.discovery.submit(intent).await {
+			self.submissions
+				.release(&intent_id)
+				.await
+				.map_err(SolverError::from)?;
+			return Err(e.into());
+		}
+		Ok(true)
+	}
+
+	/// Replays journaled events in the order they were observed.
+	///
+	/// Returns events with sequence numbers after `cursor`, up to `limit`
+	/// (capped at MAX_PAGE_SIZE).
+	pub async fn list_events(
+		&self,
+		cursor: Option<u64>,
+		limit: usize,
+	) -> Result<Page<SequencedEvent>, SolverError> {
+		let (range, next_cursor) = self.page_range("events", cursor, limit).await?;
+
+		let mut items = Vec::new();
+		for sequence in range {
+			match self
+				.storage
+				.retrieve::<SequencedEvent>("events", &sequence.to_string())
+				.await
+			{
+				Ok(event) => items.push(event),
+				Err(StorageError::NotFound) => continue,
+				Err(e) => return Err(e.into()),
 			}
-		});
+		}
 
-		Ok(())
+		Ok(Page { items, next_cursor })
 	}
 
-	/// Handles confirmed transactions based on their type.
+	/// Lists the orders held in the dead-letter queue, oldest failure first.
+	pub async fn dead_letters(&self) -> Result<Vec<DeadLetter>, SolverError> {
+		self.dead_letters.list().await.map_err(SolverError::from)
+	}
+
+	/// Schedules a dead-lettered order to be re-driven on the next check.
 	///
-	/// Routes handling to specific methods based on whether this is a fill
-	/// or claim transaction.
-	#[instrument(skip_all, fields(tx_hash = %truncate_id(&hex::encode(&tx_hash.0))))]
-	async fn handle_transaction_confirmed(
+	/// Returns the updated dead letter, or `None` if the order is not held.
+	pub async fn redrive_dead_letter(
 		&self,
-		tx_hash: solver_types::TransactionHash,
-		_receipt: solver_types::TransactionReceipt,
-		tx_type: TransactionType,
-	) -> Result<(), SolverError> {
-		if !_receipt.success {
-			self.event_bus
-				.publish(SolverEvent::Delivery(DeliveryEvent::TransactionFailed {
-					tx_hash,
-					error: "Transaction reverted".to_string(),
-				}))
-				.ok();
-			return Ok(());
-		}
+		order_id: &str,
+	) -> Result<Option<DeadLetter>, SolverError> {
+		self.dead_letters
+			.redrive(order_id)
+			.await
+			.map_err(SolverError::from)
+	}
 
-		// Handle based on transaction type
-		match tx_type {
-			TransactionType::Fill => {
-				// For fill transactions, start settlement monitoring
-				self.handle_fill_confirmed(tx_hash, _receipt).await?;
+	/// Reconstructs the orders that were in flight at a past time.
+	///
+	/// Replays the event journal up to `timestamp`, in Unix seconds, and
+	/// returns every order that had not reached a terminal stage by then.
+	pub async fn orders_in_flight_at(
+		&self,
+		timestamp: u64,
+	) -> Result<Vec<OrderState>, SolverError> {
+		let mut reconstruction = StateReconstruction::default();
+		let mut cursor = None;
+		loop {
+			let page = self.list_events(cursor, MAX_PAGE_SIZE).await?;
+			for event in &page.items {
+				// Events are journaled in time order, so the rest are later too
+				if event.timestamp > timestamp {
+					return Ok(reconstruction.in_flight());
+				}
+				reconstruction.apply(event);
 			}
-			TransactionType::Claim => {
-				// For claim transactions, mark order as completed
-				self.handle_claim_confirmed(tx_hash, _receipt).await?;
+
+			match page.next_cursor {
+				Some(next) => cursor = Some(next),
+				None => return Ok(reconstruction.in_flight()),
 			}
 		}
+	}
 
-		Ok(())
+	/// Builds the timeline of an order from its audit log.
+	///
+	/// Returns `None` if no event for the order has been recorded.
+	pub async fn order_timeline(
+		&self,
+		order_id: &str,
+	) -> Result<Option<OrderTimelineResponse>, SolverError> {
+		let entries = self
+			.audit
+			.history(order_id)
+			.await
+			.map_err(SolverError::from)?;
+		let mut timeline = OrderTimeline::new(order_id);
+		for entry in &entries {
+			timeline.apply(entry);
+		}
+
+		if timeline.is_empty() {
+			return Ok(None);
+		}
+		Ok(Some(timeline.into_response(self.networks())))
 	}
 
-	/// Handles confirmed fill transactions.
+	/// Returns every event recorded in an order's audit log, oldest first.
 	///
-	/// This method:
-	/// 1. Looks up the order associated with the transaction
-	/// 2. Spawns a task to validate the fill and monitor claim readiness
-	async fn handle_fill_confirmed(
+	/// Returns `None` if nothing was recorded for the order.
+	pub async fn order_history(
 		&self,
-		tx_hash: solver_types::TransactionHash,
-		_receipt: solver_types::TransactionReceipt,
-	) -> Result<(), SolverError> {
-		// Look up the order ID from the transaction hash
-		let order_id = match self
-			.storage
-			.retrieve::<String>("tx_to_order", &hex::encode(&tx_hash.0))
+		order_id: &str,
+	) -> Result<Option<OrderHistoryResponse>, SolverError> {
+		let entries = self
+			.audit
+			.history(order_id)
 			.await
-		{
-			Ok(id) => id,
-			Err(_) => {
-				return Ok(()); // Don't fail the handler, just continue
-			}
-		};
+			.map_err(SolverError::from)?;
+		if entries.is_empty() {
+			return Ok(None);
+		}
+		Ok(Some(OrderHistoryResponse {
+			order_id: order_id.to_string(),
+			entries,
+		}))
+	}
 
-		// Retrieve the order
-		let order = match self.storage.retrieve::<Order>("orders", &order_id).await {
-			Ok(order) => order,
-			Err(_) => {
-				return Ok(());
+	/// Runs every step of an order's execution without submitting anything.
+	///
+	/// The order is given either as a raw intent or by ID. Known orders are
+	/// validated again from their intent while it is still held, and otherwise
+	/// previewed from the stored order. Validation does not count towards
+	/// intake quotas, and the fill transaction is only simulated with
+	/// `eth_call`. Returns `None` if no order with the given ID is known.
+	pub async fn preview_execution(
+		&self,
+		request: ExecutePreviewRequest,
+	) -> Result<Option<ExecutePreviewResponse>, SolverError> {
+		let (order_id, intent, stored) = match (request.intent, request.order_id) {
+			(Some(intent), _) => (intent.id.clone(), Some(intent), None),
+			(None, Some(order_id)) => {
+				let intent = match self.optional::<Intent>("intents", &order_id).await? {
+					Some(intent) => Some(intent),
+					None => self
+						.dead_letters
+						.get(&order_id)
+						.await
+						.map_err(SolverError::from)?
+						.and_then(|dead_letter| dead_letter.intent),
+				};
+				let stored = self.optional::<Order>("orders", &order_id).await?;
+				if intent.is_none() && stored.is_none() {
+					return Ok(None);
+				}
+				(order_id, intent, stored)
+			}
+			(None, None) => {
+				return Err(SolverError::Service(
+					"Either an order ID or an intent is required".into(),
+				))
 			}
 		};
 
-		// Spawn a task to validate fill and monitor claim readiness
-		let settlement = self.settlement.clone();
-		let storage = self.storage.clone();
-		let event_bus = self.event_bus.clone();
-		let timeout_minutes = self.config.solver.monitoring_timeout_minutes;
+		let mut preview = ExecutePreviewResponse {
+			order_id,
+			order: None,
+			validated: intent.is_some(),
+			validation_error: None,
+			liquidity_error: None,
+			fill_deadline: None,
+			expired: false,
+			gas_price: None,
+			fast_path: false,
+			decision: None,
+			transaction: None,
+			transaction_error: None,
+			estimated_fee: None,
+			gas_balance_error: None,
+			simulation: None,
+		};
 
-		tokio::spawn(async move {
-			// Retrieve and extract proof
-			let fill_proof = match settlement.get_attestation(&order, &tx_hash).await {
-				Ok(proof) => proof,
+		let order = match intent {
+			Some(intent) => match self.order.preview_intent(&intent).await {
+				Ok(order) => order,
 				Err(e) => {
-					tracing::error!(
-						order_id = %truncate_id(&order_id),
-						error = %e,
-						"Failed to validate fill"
-					);
-					return;
+					preview.validation_error = Some(e.to_string());
+					return Ok(Some(preview));
 				}
-			};
+			},
+			// Only reached for stored orders whose intent is gone
+			None => stored.expect("order is stored"),
+		};
+		preview.order_id = order.id.clone();
+		preview.fill_deadline = self.order.fill_deadline(&order).ok();
+		preview.expired = preview
+			.fill_deadline
+			.is_some_and(|deadline| deadline <= now_secs())
+			|| self.expiry.is_expired(&order.id, now_secs());
 
-			// Store the fill proof
-			if storage
-				.store("fill_proofs", &order.id, &fill_proof)
+		let context = self.build_execution_context(&order).await;
+		preview.gas_price = Some(context.gas_price);
+		let decision = match self.order.evaluate_fast_path(&order, &context) {
+			Some(decision) => {
+				preview.fast_path = true;
+				decision
+			}
+			None => {
+				preview.liquidity_error = self.check_market(&order, &context).await.err();
+				self.order.should_execute(&order, &context).await
+			}
+		};
+		let params = match decision {
+			ExecutionDecision::Execute(params) => {
+				preview.decision = Some(PreviewDecision::Execute {
+					params: params.clone(),
+				});
+				params
+			}
+			ExecutionDecision::Skip(reason) => {
+				preview.decision = Some(PreviewDecision::Skip { reason });
+				preview.order = Some(order);
+				return Ok(Some(preview));
+			}
+			ExecutionDecision::Defer(duration) => {
+				preview.decision = Some(PreviewDecision::Defer {
+					retry_after_seconds: duration.as_secs(),
+				});
+				preview.order = Some(order);
+				return Ok(Some(preview));
+			}
+		};
+
+		let fill_amount = match self.fill_amount(&order, &params).await {
+			Ok((fill_amount, _)) => fill_amount,
+			Err(e) => {
+				preview.transaction_error = Some(e.to_string());
+				preview.order = Some(order);
+				return Ok(Some(preview));
+			}
+		};
+		let mut tx = match self
+			.order
+			.generate_fill_transaction(&order, &params, fill_amount)
+			.await
+		{
+			Ok(tx) => tx,
+			Err(e) => {
+				preview.transaction_error = Some(e.to_string());
+				preview.order = Some(order);
+				return Ok(Some(preview));
+			}
+		};
+		if let Some(urgency) = params.urgency {
+			if let Err(e) = self
+				.delivery
+				.escalate_fees(&mut tx, urgency.priority_fee_bump_bps)
 				.await
-				.is_err()
 			{
-				return;
+				tracing::warn!(error = %e, "Failed to escalate fees of previewed fill");
 			}
+		}
+		preview.transaction = Some(PreviewTransaction {
+			chain_id: tx.chain_id,
+			to: tx.to.as_ref().map(|to| format!("0x{}", hex::encode(&to.0))),
+			data: format!("0x{}", hex::encode(&tx.data)),
+			value: tx.value,
+			gas_limit: tx.gas_limit,
+			max_fee_per_gas: tx.max_fee_per_gas,
+			max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+		});
+		preview.estimated_fee = self.delivery.estimate_fee(&tx).await.ok();
+		preview.gas_balance_error = self
+			.delivery
+			.ensure_gas_balance(&tx)
+			.await
+			.err()
+			.map(|e| e.to_string());
+		preview.simulation = Some(match self.delivery.call(&tx).await {
+			Ok(data) => PreviewSimulation {
+				success: true,
+				return_data: Some(format!("0x{}", hex::encode(&data))),
+				error: None,
+			},
+			Err(e) => PreviewSimulation {
+				success: false,
+				return_data: None,
+				error: Some(e.to_string()),
+			},
+		});
+		preview.order = Some(order);
+		Ok(Some(preview))
+	}
 
-			// Monitor claim readiness
-			let monitoring_timeout = tokio::time::Duration::from_secs(timeout_minutes * 60);
-			let check_interval = tokio::time::Duration::from_secs(1); // Check every 1 second for faster claim detection
-			let start_time = tokio::time::Instant::now();
+	/// Computes the sequence range covered by a page and the cursor for the next one.
+	async fn page_range(
+		&self,
+		sequence: &str,
+		cursor: Option<u64>,
+		limit: usize,
+	) -> Result<(RangeInclusive<u64>, Option<u64>), SolverError> {
+		let last = self
+			.storage
+			.current_sequence(sequence)
+			.await
+			.map_err(SolverError::from)?;
 
-			loop {
-				// Check if we've exceeded the timeout
-				if start_time.elapsed() > monitoring_timeout {
-					tracing::warn!(
-						order_id = %truncate_id(&order_id),
-						"Claim readiness monitoring timeout reached after {} minutes",
-						timeout_minutes
-					);
-					break;
-				}
+		let start = cursor.unwrap_or(0).saturating_add(1);
+		let limit = limit.clamp(1, MAX_PAGE_SIZE) as u64;
+		let end = start.saturating_add(limit - 1).min(last);
+		let next_cursor = (end < last).then_some(end);
 
-				// Check if we can claim
-				if settlement.can_claim(&order, &fill_proof).await {
-					tracing::info!(
-						order_id = %truncate_id(&order_id),
-						"Ready to claim"
-					);
-					event_bus
-						.publish(SolverEvent::Settlement(SettlementEvent::ClaimReady {
-							order_id: order.id,
-						}))
-						.ok();
-					break;
-				}
+		Ok((start..=end, next_cursor))
+	}
 
-				// Wait before next check
-				tokio::time::sleep(check_interval).await;
-			}
-		});
+	/// Queues a discovered intent with its current priority score.
+	fn enqueue_intent(&self, queue: &mut IntentQueue, intent: Intent) {
+		let score = self.priorities.score(&intent);
+		tracing::info!(
+			order_id = %truncate_id(&intent.id),
+			priority = score,
+			"Discovered intent"
+		);
+		self.latency.start(&intent);
+		queue.push(intent, score);
+	}
 
-		Ok(())
+	/// Sends an operator command to the event loop and waits for its reply.
+	///
+	/// Commands sent before the event loop starts are handled once it does.
+	pub async fn control(&self, command: ControlCommand) -> Result<ControlReply, ControlError> {
+		let (reply, response) = tokio::sync::oneshot::channel();
+		self.control
+			.send(ControlMessage { command, reply })
+			.map_err(|_| ControlError::NotRunning)?;
+		response.await.map_err(|_| ControlError::NotRunning)?
 	}
 
-	/// Handles confirmed claim transactions.
+	/// Boosts or suppresses orders and sources for a time window.
 	///
-	/// Marks the order as completed and publishes the completion event.
-	async fn handle_claim_confirmed(
-		&self,
-		tx_hash: solver_types::TransactionHash,
-		_receipt: solver_types::TransactionReceipt,
-	) -> Result<(), SolverError> {
-		// Look up the order ID from the transaction hash
-		let order_id = match self
-			.storage
-			.retrieve::<String>("tx_to_order", &hex::encode(&tx_hash.0))
+	/// Returns the rules active after the update.
+	pub fn set_priorities(&self, request: SetPrioritiesRequest) -> Vec<PriorityRule> {
+		let expires_at = now_secs().saturating_add(request.duration_seconds);
+		let rules = request
+			.order_ids
+			.into_iter()
+			.map(PriorityTarget::Order)
+			.chain(request.sources.into_iter().map(PriorityTarget::Source))
+			.map(|target| PriorityRule {
+				target,
+				weight: request.weight,
+				expires_at,
+			})
+			.collect();
+
+		self.priorities.apply(rules);
+		self.priorities.active_rules()
+	}
+
+	/// Returns the currently active priority rules.
+	pub fn priorities(&self) -> Vec<PriorityRule> {
+		self.priorities.active_rules()
+	}
+
+	/// Compares the recommended initial inventory against the solver's balances.
+	pub async fn assess_inventory(&self) -> Result<Vec<InventoryRecommendation>, SolverError> {
+		let bootstrapper = self.bootstrapper.as_ref().ok_or_else(|| {
+			SolverError::Config("No bootstrap configuration provided".to_string())
+		})?;
+		let owner = self
+			.account
+			.get_address()
 			.await
-		{
-			Ok(id) => id,
-			Err(_) => {
-				return Ok(());
-			}
-		};
+			.map_err(SolverError::from)?;
 
-		// Emit completed event
-		tracing::info!(
-			order_id = %truncate_id(&order_id),
-			"Completed"
-		);
-		self.event_bus
-			.publish(SolverEvent::Settlement(SettlementEvent::Completed {
-				order_id: order_id.clone(),
-			}))
-			.ok();
+		bootstrapper.assess(&owner).await.map_err(SolverError::from)
+	}
 
-		// Optional: Clean up storage for completed orders
+	/// Funds token shortfalls from the configured treasury.
+	pub async fn fund_inventory(
+		&self,
+		recommendations: &[InventoryRecommendation],
+	) -> Result<Vec<FundingResult>, SolverError> {
+		let bootstrapper = self.bootstrapper.as_ref().ok_or_else(|| {
+			SolverError::Config("No bootstrap configuration provided".to_string())
+		})?;
+		let owner = self
+			.account
+			.get_address()
+			.await
+			.map_err(SolverError::from)?;
 
-		Ok(())
+		Ok(bootstrapper.fund(&owner, recommendations).await)
 	}
 
-	/// Processes a batch of orders ready for claiming.
+	/// Reports whether the solver meets the economic preconditions for
+	/// filling orders, listing those it does not.
 	///
-	/// For each order in the batch:
-	/// 1. Retrieves the order and fill proof from storage
-	/// 2. Generates a claim transaction
-	/// 3. Submits the claim transaction
-	/// 4. Stores transaction hashes and mappings
-	#[instrument(skip_all)]
-	async fn process_claim_batch(&self, batch: &mut Vec<String>) -> Result<(), SolverError> {
-		for order_id in batch.drain(..) {
-			// Retrieve order
-			let order: Order = self
-				.storage
-				.retrieve("orders", &order_id)
-				.await
-				.map_err(|e| SolverError::Service(e.to_string()))?;
+	/// Fills paused by the gas budget are always reported. Without a
+	/// readiness configuration the solver is otherwise always ready.
+	pub async fn readiness(&self) -> ReadinessResponse {
+		let mut unmet: Vec<UnmetPrecondition> = self
+			.fill_pauses()
+			.into_iter()
+			.map(|(chain_id, reason)| UnmetPrecondition {
+				precondition: Precondition::GasBudget,
+				chain_id,
+				token: None,
+				message: reason,
+			})
+			.collect();
 
-			// Retrieve fill proof (already validated when ClaimReady was emitted)
-			let fill_proof: solver_types::FillProof = self
-				.storage
-				.retrieve("fill_proofs", &order_id)
-				.await
-				.map_err(|e| SolverError::Service(e.to_string()))?;
+		if let Some(readiness) = &self.readiness {
+			match self.account.get_address().await {
+				Ok(owner) => unmet.extend(readiness.check(&owner, self.approvals.as_deref()).await),
+				Err(e) => unmet.push(UnmetPrecondition {
+					precondition: Precondition::Account,
+					chain_id: None,
+					token: None,
+					message: format!("failed to resolve solver address: {}", e),
+				}),
+			}
+		}
+		ReadinessResponse {
+			ready: unmet.is_empty(),
+			unmet,
+		}
+	}
 
-			// Generate claim transaction
-			let claim_tx = self
-				.order
-				.generate_claim_transaction(&order, &fill_proof)
-				.await
-				.map_err(|e| SolverError::Service(e.to_string()))?;
+	/// Returns why new fills are paused, keyed by the paused chain, or by
+	/// `None` if fills on every chain are paused.
+	pub fn fill_pauses(&self) -> std::collections::BTreeMap<Option<u64>, String> {
+		self.gas_budget
+			.as_ref()
+			.map(|gas_budget| gas_budget.pauses())
+			.unwrap_or_default()
+	}
 
-			// Submit claim transaction through delivery service
-			let claim_tx_hash = self
-				.delivery
-				.deliver(claim_tx)
-				.await
-				.map_err(|e| SolverError::Service(e.to_string()))?;
+	/// Returns the profit and loss of the orders completed from `from` until
+	/// before `to`.
+	pub async fn pnl_report(
+		&self,
+		from: Option<u64>,
+		to: Option<u64>,
+	) -> Result<solver_types::PnlReport, SolverError> {
+		self.pnl.report(from, to).await.map_err(SolverError::from)
+	}
 
-			self.event_bus
-				.publish(SolverEvent::Delivery(DeliveryEvent::TransactionPending {
-					order_id: order.id.clone(),
-					tx_hash: claim_tx_hash.clone(),
-					tx_type: TransactionType::Claim,
-				}))
-				.ok();
+	/// Returns the capital utilization of every token the solver holds or
+	/// has in flight.
+	pub fn capital_utilization(&self) -> Vec<solver_types::CapitalUtilization> {
+		self.capital.utilization(&self.ledger)
+	}
 
-			// Store claim transaction hash
-			self.storage
-				.store("claims", &order.id, &claim_tx_hash)
-				.await
-				.map_err(|e| SolverError::Service(e.to_string()))?;
+	/// Returns the solver's balances as last polled, or `None` if inventory
+	/// polling is not configured.
+	pub fn inventory(&self) -> Option<Vec<solver_types::InventoryBalance>> {
+		self.inventory
+			.as_ref()
+			.map(|inventory| inventory.balances())
+	}
 
-			// Store reverse mapping: tx_hash -> order_id
-			self.storage
-				.store("tx_to_order", &hex::encode(&claim_tx_hash.0), &order.id)
-				.await
-				.map_err(|e| SolverError::Service(e.to_string()))?;
-		}
-		Ok(())
+	/// Publishes the current capital utilization to the metrics.
+	fn refresh_capital_metrics(&self) {
+		self.metrics
+			.set_capital_utilization(self.capital.utilization(&self.ledger));
+	}
+
+	/// Returns market conditions for moving the given assets: fee estimates,
+	/// the solver's balances and token prices, as strategies see them.
+	pub async fn market_context(&self, assets: &OrderAssets) -> ExecutionContext {
+		self.context
+			.build(Some(assets), U256::from(FALLBACK_GAS_PRICE))
+			.await
+	}
+
+	/// Returns the price oracle serving the prices execution strategies see.
+	///
+	/// Prices are cached like the rest of the execution context.
+	pub fn price_oracle(&self) -> &dyn PriceOracle {
+		self.context.as_ref()
+	}
+
+	/// Returns the symbol and decimals of a token.
+	pub async fn token_metadata(
+		&self,
+		chain_id: u64,
+		token: &solver_types::Address,
+	) -> Result<solver_types::TokenMetadata, SolverError> {
+		erc20::token_metadata(&self.delivery, chain_id, token)
+			.await
+			.map_err(SolverError::from)
 	}
 
-	/// Builds the execution context for strategy decisions.
-	///
-	/// TODO: this should fetch real-time data such as gas prices,
-	/// solver balances, and other relevant market conditions.
-	async fn build_execution_context(&self) -> Result<ExecutionContext, SolverError> {
-		Ok(ExecutionContext {
-			gas_price: U256::from(20_000_000_000u64), // 20 gwei
-			timestamp: std::time::SystemTime::now()
-				.duration_since(std::time::UNIX_EPOCH)
-				.unwrap()
-				.as_secs(),
-			solver_balance: HashMap::new(),
-		})
+	/// Returns the per-chain network metadata.
+	pub fn networks(&self) -> &NetworkRegistry {
+		self.delivery.networks()
+	}
+
+	/// Returns a reference to the ledger.
+	pub fn ledger(&self) -> &Ledger {
+		&self.ledger
 	}
 
 	/// Returns a reference to the event bus.
@@ -625,10 +5490,74 @@ impl SolverEngine {
 		&self.event_bus
 	}
 
+	/// Returns what the solver was built with.
+	pub fn capabilities(&self) -> &SolverCapabilities {
+		&self.capabilities
+	}
+
 	/// Returns a reference to the configuration.
 	pub fn config(&self) -> &Config {
 		&self.config
 	}
+
+	/// Returns the configuration in effect, including reloaded sections.
+	///
+	/// Components that support reloading read their settings from here
+	/// rather than from [`config`](Self::config).
+	pub fn current_config(&self) -> Arc<Config> {
+		self.reloaded.read().unwrap().clone()
+	}
+
+	/// Applies a reloaded configuration to the running solver.
+	///
+	/// Nothing is applied if settings outside the reloadable sections changed.
+	/// Execution strategies are rebuilt when their configuration changed; the
+	/// other reloadable sections are read from
+	/// [`current_config`](Self::current_config) on every use. Returns the
+	/// sections that changed.
+	pub fn reload_config(&self, config: Config) -> Result<Vec<String>, SolverError> {
+		let changes = self
+			.current_config()
+			.reload_changes(&config)
+			.map_err(|e| SolverError::Config(e.to_string()))?;
+		if changes.is_empty() {
+			return Ok(changes);
+		}
+
+		if changes.iter().any(|section| section.starts_with("order.")) {
+			let strategy = self.build_strategy(&config.order.execution_strategy)?;
+			let mut class_strategies = HashMap::new();
+			for (class_name, strategy_config) in &config.order.class_strategies {
+				let class = class_name
+					.parse::<OrderClass>()
+					.map_err(SolverError::Config)?;
+				class_strategies.insert(class, self.build_strategy(strategy_config)?);
+			}
+			self.order.replace_strategies(strategy, class_strategies);
+		}
+
+		*self.reloaded.write().unwrap() = Arc::new(config);
+		tracing::info!(sections = ?changes, "Reloaded configuration");
+		Ok(changes)
+	}
+
+	/// Builds an execution strategy, validating its configuration.
+	fn build_strategy(
+		&self,
+		config: &solver_config::StrategyConfig,
+	) -> Result<Box<dyn solver_order::ExecutionStrategy>, SolverError> {
+		build_strategy(&self.strategy_factories, config)
+	}
+
+	/// Returns a reference to the storage service.
+	pub fn storage(&self) -> &Arc<StorageService> {
+		&self.storage
+	}
+
+	/// Returns a reference to the event loop metrics.
+	pub fn metrics(&self) -> &Arc<EngineMetrics> {
+		&self.metrics
+	}
 }
 
 /// Type alias for storage backend factory function.
@@ -647,7 +5576,60 @@ type OrderFactory = Box<dyn Fn(&toml::Value) -> Box<dyn solver_order::OrderInter
 type SettlementFactory =
 	Box<dyn Fn(&toml::Value) -> Box<dyn solver_settlement::SettlementInterface> + Send>;
 /// Type alias for execution strategy factory function.
-type StrategyFactory = Box<dyn Fn(&toml::Value) -> Box<dyn solver_order::ExecutionStrategy> + Send>;
+type StrategyFactory =
+	Box<dyn Fn(&toml::Value) -> Box<dyn solver_order::ExecutionStrategy> + Send + Sync>;
+/// Strategy type combining the decisions of other strategies.
+const COMPOSITE_STRATEGY: &str = "composite";
+/// Name of the validator running the liquidity checks.
+const LIQUIDITY_VALIDATOR: &str = "liquidity";
+
+/// Builds an execution strategy, validating its configuration.
+fn build_strategy(
+	factories: &HashMap<String, StrategyFactory>,
+	config: &solver_config::StrategyConfig,
+) -> Result<Box<dyn solver_order::ExecutionStrategy>, SolverError> {
+	build_strategy_of_type(factories, &config.strategy_type, &config.config).map_err(|e| {
+		SolverError::Config(format!(
+			"Invalid configuration for strategy '{}': {}",
+			config.strategy_type, e
+		))
+	})
+}
+
+/// Builds an execution strategy of the given type.
+///
+/// Composite strategies have no factory of their own, since their children
+/// are built from the registered factories.
+fn build_strategy_of_type(
+	factories: &HashMap<String, StrategyFactory>,
+	strategy_type: &str,
+	config: &toml::Value,
+) -> Result<Box<dyn solver_order::ExecutionStrategy>, String> {
+	if strategy_type == COMPOSITE_STRATEGY {
+		return create_composite_strategy(config, &|child_type, child_config| {
+			build_strategy_of_type(factories, child_type, child_config)
+				.map_err(|e| format!("strategy '{}': {}", child_type, e))
+		});
+	}
+
+	let factory = factories
+		.get(strategy_type)
+		.ok_or_else(|| format!("Unknown strategy type '{}'", strategy_type))?;
+	let strategy = factory(config);
+	strategy
+		.config_schema()
+		.validate(config)
+		.map_err(|e| e.to_string())?;
+	Ok(strategy)
+}
+
+/// Type alias for liquidity source factory function.
+type LiquidityFactory =
+	Box<dyn Fn(&toml::Value) -> Box<dyn solver_liquidity::LiquidityInterface> + Send>;
+
+/// Type alias for risk validator factory function.
+type ValidatorFactory =
+	Box<dyn Fn(&toml::Value) -> Box<dyn solver_validators::ValidatorInterface> + Send>;
 
 /// Builder for constructing a SolverEngine with pluggable implementations.
 ///
@@ -656,13 +5638,16 @@ type StrategyFactory = Box<dyn Fn(&toml::Value) -> Box<dyn solver_order::Executi
 /// flexibility in supporting different blockchains, order types, and strategies.
 pub struct SolverBuilder {
 	config: Config,
-	storage_factory: Option<StorageFactory>,
+	storage_factories: HashMap<String, StorageFactory>,
 	account_factory: Option<AccountFactory>,
 	delivery_factories: HashMap<String, DeliveryFactory>,
 	discovery_factories: HashMap<String, DiscoveryFactory>,
 	order_factories: HashMap<String, OrderFactory>,
 	settlement_factories: HashMap<String, SettlementFactory>,
-	strategy_factory: Option<StrategyFactory>,
+	strategy_factories: HashMap<String, StrategyFactory>,
+	liquidity_factories: HashMap<String, LiquidityFactory>,
+	validator_factories: HashMap<String, ValidatorFactory>,
+	price_oracle: Option<Arc<dyn PriceOracle>>,
 }
 
 impl SolverBuilder {
@@ -670,22 +5655,28 @@ impl SolverBuilder {
 	pub fn new(config: Config) -> Self {
 		Self {
 			config,
-			storage_factory: None,
+			storage_factories: HashMap::new(),
 			account_factory: None,
 			delivery_factories: HashMap::new(),
 			discovery_factories: HashMap::new(),
 			order_factories: HashMap::new(),
 			settlement_factories: HashMap::new(),
-			strategy_factory: None,
+			strategy_factories: HashMap::new(),
+			liquidity_factories: HashMap::new(),
+			validator_factories: HashMap::new(),
+			price_oracle: None,
 		}
 	}
 
-	/// Sets the factory function for creating storage backends.
-	pub fn with_storage_factory<F>(mut self, factory: F) -> Self
+	/// Adds a factory function for creating storage backends.
+	///
+	/// The name parameter should match the storage backend in the configuration.
+	pub fn with_storage_factory<F>(mut self, name: &str, factory: F) -> Self
 	where
 		F: Fn(&toml::Value) -> Box<dyn solver_storage::StorageInterface> + Send + 'static,
 	{
-		self.storage_factory = Some(Box::new(factory));
+		self.storage_factories
+			.insert(name.to_string(), Box::new(factory));
 		self
 	}
 
@@ -746,12 +5737,50 @@ impl SolverBuilder {
 		self
 	}
 
-	/// Sets the factory function for creating execution strategies.
-	pub fn with_strategy_factory<F>(mut self, factory: F) -> Self
+	/// Adds a factory function for creating execution strategies of the given
+	/// type.
+	pub fn with_strategy_factory<F>(mut self, name: &str, factory: F) -> Self
+	where
+		F: Fn(&toml::Value) -> Box<dyn solver_order::ExecutionStrategy> + Send + Sync + 'static,
+	{
+		self.strategy_factories
+			.insert(name.to_string(), Box::new(factory));
+		self
+	}
+
+	/// Sets the price oracle execution strategies get token prices from.
+	///
+	/// Takes precedence over fixed prices from the configuration.
+	pub fn with_price_oracle<O>(mut self, oracle: O) -> Self
+	where
+		O: PriceOracle + 'static,
+	{
+		self.price_oracle = Some(Arc::new(oracle));
+		self
+	}
+
+	/// Adds a factory function for creating liquidity sources.
+	///
+	/// The name parameter should match the `implementation` of sources in the
+	/// configuration.
+	pub fn with_liquidity_factory<F>(mut self, name: &str, factory: F) -> Self
+	where
+		F: Fn(&toml::Value) -> Box<dyn solver_liquidity::LiquidityInterface> + Send + 'static,
+	{
+		self.liquidity_factories
+			.insert(name.to_string(), Box::new(factory));
+		self
+	}
+
+	/// Adds a factory function for creating risk validators.
+	///
+	/// The name parameter should match the validator name in the configuration.
+	pub fn with_validator_factory<F>(mut self, name: &str, factory: F) -> Self
 	where
-		F: Fn(&toml::Value) -> Box<dyn solver_order::ExecutionStrategy> + Send + 'static,
+		F: Fn(&toml::Value) -> Box<dyn solver_validators::ValidatorInterface> + Send + 'static,
 	{
-		self.strategy_factory = Some(Box::new(factory));
+		self.validator_factories
+			.insert(name.to_string(), Box::new(factory));
 		self
 	}
 
@@ -764,14 +5793,40 @@ impl SolverBuilder {
 	/// 4. Returns a fully configured SolverEngine ready to run
 	pub fn build(self) -> Result<SolverEngine, SolverError> {
 		// Create storage backend
-		let storage_backend = self
-			.storage_factory
-			.ok_or_else(|| SolverError::Config("Storage factory not provided".into()))?(
-			&self.config.storage.config,
-		);
-		let storage = Arc::new(StorageService::new(storage_backend));
+		let storage_factory = self
+			.storage_factories
+			.get(&self.config.storage.backend)
+			.ok_or_else(|| {
+				SolverError::Config(format!(
+					"Unknown storage backend: {}",
+					self.config.storage.backend
+				))
+			})?;
+		let storage_backend = storage_factory(&self.config.storage.config);
+		let mut storage = StorageService::new(storage_backend);
+		if let Some(compression) = &self.config.storage.compression {
+			let mut policy = CompressionPolicy::new(compression.level, compression.min_size_bytes);
+			for (namespace, level) in &compression.namespaces {
+				policy = policy.with_namespace(namespace, *level);
+			}
+			storage = storage.with_compression(policy);
+		}
+		let storage = Arc::new(storage);
 		tracing::info!(component = "storage", implementation = %self.config.storage.backend, "Loaded");
 
+		// Implementations are recorded as they load so the solver can describe itself
+		let mut capabilities = SolverCapabilities::default();
+		capabilities.add_component("storage", &self.config.storage.backend);
+
+		// Cache immutable RPC lookups in storage, unless disabled
+		let rpc_cache = match self.config.storage.rpc_cache_ttl_hours {
+			0 => None,
+			hours => Some(RpcCache::new(
+				storage.clone(),
+				Duration::from_secs(hours * 3600),
+			)),
+		};
+
 		// Create account provider
 		let account_provider = self
 			.account_factory
@@ -780,6 +5835,7 @@ impl SolverBuilder {
 		);
 		let account = Arc::new(AccountService::new(account_provider));
 		tracing::info!(component = "account", implementation = %self.config.account.provider, "Loaded");
+		capabilities.add_component("account", &self.config.account.provider);
 
 		// Create delivery providers
 		let mut delivery_providers = HashMap::new();
@@ -808,6 +5864,8 @@ impl SolverBuilder {
 
 				delivery_providers.insert(chain_id, provider);
 				tracing::info!(component = "delivery", implementation = %name, chain_id = %chain_id, "Loaded");
+				capabilities.add_component("delivery", name);
+				capabilities.chain_ids.push(chain_id);
 			}
 		}
 
@@ -817,11 +5875,370 @@ impl SolverBuilder {
 			));
 		}
 
-		let delivery = Arc::new(DeliveryService::new(
+		// Resolve per-chain network metadata
+		let mut networks = HashMap::new();
+		for (chain_id, network_config) in &self.config.networks {
+			let chain_id = chain_id.parse::<u64>().map_err(|e| {
+				SolverError::Config(format!("Invalid network chain ID {}: {}", chain_id, e))
+			})?;
+			let gas_token = network_config
+				.gas_token
+				.as_ref()
+				.map(|gas_token| GasToken {
+					symbol: gas_token.symbol.clone(),
+					decimals: gas_token.decimals,
+				})
+				.unwrap_or_default();
+			networks.insert(
+				chain_id,
+				NetworkInfo {
+					gas_token,
+					explorer_url: network_config.explorer_url.clone(),
+					block_time: network_config
+						.block_time_seconds
+						.map(Duration::from_secs_f64),
+					poll_interval: network_config
+						.poll_interval_seconds
+						.map(Duration::from_secs_f64),
+					max_poll_interval: network_config
+						.max_poll_interval_seconds
+						.map(Duration::from_secs_f64),
+				},
+			);
+		}
+
+		// Price transactions with the fee strategy configured for each chain
+		let gas_estimators = match &self.config.delivery.gas {
+			Some(gas_config) => delivery_providers
+				.keys()
+				.map(|chain_id| {
+					let strategy = gas_config
+						.chains
+						.get(&chain_id.to_string())
+						.unwrap_or(&gas_config.default);
+					let mode = match strategy.mode.as_str() {
+						"legacy" => FeeMode::Legacy,
+						_ => FeeMode::Eip1559,
+					};
+					let estimator: Box<dyn GasEstimator> = Box::new(PercentileGasEstimator::new(
+						mode,
+						strategy.fee_history_blocks,
+						strategy.priority_fee_percentile,
+						strategy.base_fee_multiplier_bps,
+					));
+					(*chain_id, estimator)
+				})
+				.collect(),
+			None => HashMap::new(),
+		};
+
+		// Confirmations and timeouts resolved per chain
+		let confirmation_policies = delivery_providers
+			.keys()
+			.map(|chain_id| {
+				let policy = ConfirmationPolicy {
+					confirmations: self.config.delivery.min_confirmations_for(*chain_id),
+					timeout: self
+						.config
+						.delivery
+						.confirmation_timeout_for(*chain_id)
+						.map(Duration::from_secs),
+				};
+				(*chain_id, policy)
+			})
+			.collect();
+
+		let mut delivery = DeliveryService::new(
 			delivery_providers,
 			account.clone(),
 			self.config.delivery.min_confirmations,
-		));
+		)
+		.with_confirmation_policies(confirmation_policies)
+		.with_networks(NetworkRegistry::new(networks))
+		.with_gas_estimators(gas_estimators)
+		.with_simulation(self.config.delivery.simulate)
+		.with_dry_run(self.config.solver.dry_run);
+		if let Some(cache) = &rpc_cache {
+			delivery = delivery.with_cache(cache.clone());
+		}
+		let delivery = Arc::new(delivery);
+
+		// Expand approval config into (chain, token, settler) targets
+		let approvals = match &self.config.approvals {
+			Some(approval_config) => {
+				let parse_address = |address: &str| {
+					hex::decode(address.trim_start_matches("0x"))
+						.map(solver_types::Address)
+						.map_err(|e| {
+							SolverError::Config(format!(
+								"Invalid approval address {}: {}",
+								address, e
+							))
+						})
+				};
+
+				let mut targets = Vec::new();
+				for (chain_id, tokens) in &approval_config.tokens {
+					let chain = chain_id.parse::<u64>().map_err(|e| {
+						SolverError::Config(format!(
+							"Invalid approval chain ID {}: {}",
+							chain_id, e
+						))
+					})?;
+					let settlers = approval_config
+						.settlers
+						.get(chain_id)
+						.map(Vec::as_slice)
+						.unwrap_or_default();
+					for token in tokens {
+						for settler in settlers {
+							targets.push(ApprovalTarget {
+								chain_id: chain,
+								token: parse_address(token)?,
+								spender: parse_address(settler)?,
+							});
+						}
+					}
+				}
+				let mode = match approval_config.mode.as_str() {
+					"exact" => ApprovalMode::Exact,
+					_ => ApprovalMode::Max,
+				};
+				tracing::info!(component = "approvals", targets = targets.len(), mode = ?mode, "Loaded");
+				Some(Arc::new(ApprovalManager::new(
+					delivery.clone(),
+					targets,
+					mode,
+				)))
+			}
+			None => None,
+		};
+
+		// Latency budgets for competitive orders
+		let latency = match &self.config.order.latency_budget {
+			Some(budget_config) => {
+				let to_durations = |budgets: &HashMap<String, u64>| {
+					budgets
+						.iter()
+						.map(|(name, ms)| (name.clone(), Duration::from_millis(*ms)))
+						.collect()
+				};
+				LatencyBudgets::new(
+					budget_config.default_ms.map(Duration::from_millis),
+					to_durations(&budget_config.standards),
+					to_durations(&budget_config.sources),
+				)
+			}
+			None => LatencyBudgets::default(),
+		};
+
+		// Size the initial inventory from the configured corridors
+		let bootstrapper = match &self.config.bootstrap {
+			Some(bootstrap_config) => {
+				let parse_address = |address: &str| {
+					hex::decode(address.trim_start_matches("0x"))
+						.map(solver_types::Address)
+						.map_err(|e| {
+							SolverError::Config(format!(
+								"Invalid bootstrap address {}: {}",
+								address, e
+							))
+						})
+				};
+				let parse_amount = |amount: &str| {
+					U256::from_str_radix(amount, 10).map_err(|e| {
+						SolverError::Config(format!("Invalid bootstrap amount {}: {}", amount, e))
+					})
+				};
+
+				let mut corridors = Vec::new();
+				for corridor in &bootstrap_config.corridors {
+					corridors.push(Corridor {
+						destination_chain_id: corridor.destination_chain_id,
+						token: parse_address(&corridor.token)?,
+						daily_volume: parse_amount(&corridor.daily_volume)?,
+					});
+				}
+
+				let mut gas_reserves = std::collections::BTreeMap::new();
+				for (chain_id, reserve) in &bootstrap_config.gas_reserves {
+					let chain = chain_id.parse::<u64>().map_err(|e| {
+						SolverError::Config(format!(
+							"Invalid bootstrap chain ID {}: {}",
+							chain_id, e
+						))
+					})?;
+					gas_reserves.insert(chain, parse_amount(reserve)?);
+				}
+
+				let treasury = bootstrap_config
+					.treasury
+					.as_deref()
+					.map(parse_address)
+					.transpose()?;
+
+				tracing::info!(
+					component = "bootstrap",
+					corridors = corridors.len(),
+					"Loaded"
+				);
+				Some(InventoryBootstrapper::new(
+					delivery.clone(),
+					BootstrapPlan {
+						corridors,
+						coverage_hours: bootstrap_config.coverage_hours,
+						buffer_bps: bootstrap_config.buffer_bps,
+						gas_reserves,
+						treasury,
+					},
+				))
+			}
+			None => None,
+		};
+
+		// Balances and approvals gating readiness
+		let readiness = match &self.config.readiness {
+			Some(readiness_config) => {
+				let parse_amount = |amount: &str| {
+					U256::from_str_radix(amount, 10).map_err(|e| {
+						SolverError::Config(format!("Invalid readiness amount {}: {}", amount, e))
+					})
+				};
+
+				let mut min_gas_balances = std::collections::BTreeMap::new();
+				for (chain_id, minimum) in &readiness_config.min_gas_balances {
+					let chain = chain_id.parse::<u64>().map_err(|e| {
+						SolverError::Config(format!(
+							"Invalid readiness chain ID {}: {}",
+							chain_id, e
+						))
+					})?;
+					min_gas_balances.insert(chain, parse_amount(minimum)?);
+				}
+
+				let mut min_inventory = Vec::new();
+				for requirement in &readiness_config.min_inventory {
+					let token = hex::decode(requirement.token.trim_start_matches("0x"))
+						.map(solver_types::Address)
+						.map_err(|e| {
+							SolverError::Config(format!(
+								"Invalid readiness token {}: {}",
+								requirement.token, e
+							))
+						})?;
+					min_inventory.push(InventoryRequirement {
+						chain_id: requirement.chain_id,
+						token,
+						amount: parse_amount(&requirement.amount)?,
+					});
+				}
+
+				tracing::info!(
+					component = "readiness",
+					gas_balances = min_gas_balances.len(),
+					inventory = min_inventory.len(),
+					"Loaded"
+				);
+				Some(ReadinessChecker::new(
+					delivery.clone(),
+					min_gas_balances,
+					min_inventory,
+					readiness_config.require_approvals,
+					Duration::from_secs(readiness_config.cache_ttl_seconds),
+				))
+			}
+			None => None,
+		};
+
+		// Gas spend budget and failure circuit breaker
+		let gas_budget = match &self.config.gas_budget {
+			Some(gas_budget_config) => {
+				let mut limits = HashMap::new();
+				for (chain_id, limit) in &gas_budget_config.limits {
+					let chain = chain_id.parse::<u64>().map_err(|e| {
+						SolverError::Config(format!(
+							"Invalid gas budget chain ID {}: {}",
+							chain_id, e
+						))
+					})?;
+					let limit = U256::from_str_radix(limit, 10).map_err(|e| {
+						SolverError::Config(format!("Invalid gas budget limit {}: {}", limit, e))
+					})?;
+					limits.insert(chain, limit);
+				}
+
+				tracing::info!(
+					component = "gas_budget",
+					chains = limits.len(),
+					window_seconds = gas_budget_config.window_seconds,
+					"Loaded"
+				);
+				Some(GasBudget::new(
+					Duration::from_secs(gas_budget_config.window_seconds),
+					limits,
+					gas_budget_config.max_consecutive_failures,
+					Duration::from_secs(gas_budget_config.failure_cooldown_seconds),
+				))
+			}
+			None => None,
+		};
+
+		// Polling of the solver's token inventory
+		let inventory = match &self.config.inventory {
+			Some(inventory_config) => {
+				let parse_amount = |amount: &str| {
+					U256::from_str_radix(amount, 10).map_err(|e| {
+						SolverError::Config(format!("Invalid inventory amount {}: {}", amount, e))
+					})
+				};
+
+				let mut tokens: HashMap<u64, Vec<solver_types::Address>> = HashMap::new();
+				let mut thresholds = Vec::new();
+				for token_config in &inventory_config.tokens {
+					let token = hex::decode(token_config.token.trim_start_matches("0x"))
+						.map(solver_types::Address)
+						.map_err(|e| {
+							SolverError::Config(format!(
+								"Invalid inventory token {}: {}",
+								token_config.token, e
+							))
+						})?;
+					if let Some(min_balance) = &token_config.min_balance {
+						thresholds.push((
+							token_config.chain_id,
+							Some(token.clone()),
+							parse_amount(min_balance)?,
+						));
+					}
+					tokens.entry(token_config.chain_id).or_default().push(token);
+				}
+				for (chain_id, min_balance) in &inventory_config.min_native_balances {
+					let chain = chain_id.parse::<u64>().map_err(|e| {
+						SolverError::Config(format!(
+							"Invalid inventory chain ID {}: {}",
+							chain_id, e
+						))
+					})?;
+					thresholds.push((chain, None, parse_amount(min_balance)?));
+				}
+
+				let mut inventory = InventoryService::new(
+					Box::new(balances::DeliveryBalances::new(delivery.clone())),
+					delivery.chain_ids(),
+					tokens,
+				);
+				for (chain_id, token, min_balance) in thresholds {
+					inventory = inventory.with_threshold(chain_id, token, min_balance);
+				}
+				tracing::info!(
+					component = "inventory",
+					tokens = inventory_config.tokens.len(),
+					"Loaded"
+				);
+				Some(Arc::new(inventory))
+			}
+			None => None,
+		};
 
 		// Create discovery sources
 		let mut discovery_sources = Vec::new();
@@ -837,8 +6254,9 @@ impl SolverBuilder {
 					))
 				})?;
 
-				discovery_sources.push(source);
+				discovery_sources.push((name.clone(), source));
 				tracing::info!(component = "discovery", implementation = %name, "Loaded");
+				capabilities.add_component("discovery", name);
 			}
 		}
 
@@ -863,18 +6281,212 @@ impl SolverBuilder {
 
 				order_impls.insert(name.clone(), implementation);
 				tracing::info!(component = "order", implementation = %name, "Loaded");
+				capabilities.add_component("order", name);
 			}
 		}
 
 		// Create execution strategy
-		let strategy = self
-			.strategy_factory
-			.ok_or_else(|| SolverError::Config("Strategy factory not provided".into()))?(
-			&self.config.order.execution_strategy.config,
-		);
+		let strategy_factories = self.strategy_factories;
+		let strategy = build_strategy(&strategy_factories, &self.config.order.execution_strategy)?;
 		tracing::info!(component = "strategy", implementation = %self.config.order.execution_strategy.strategy_type, "Loaded");
+		capabilities.add_component(
+			"strategy",
+			&self.config.order.execution_strategy.strategy_type,
+		);
+
+		// Resolve claim recipients and ensure every standard can honour them
+		let mut claim_recipients = HashMap::new();
+		for (chain_id, address) in self.config.settlement.claim_recipients_by_chain() {
+			let bytes = hex::decode(address.trim_start_matches("0x")).map_err(|e| {
+				SolverError::Config(format!(
+					"Invalid claim recipient for chain {}: {}",
+					chain_id, e
+				))
+			})?;
+			claim_recipients.insert(chain_id, solver_types::Address(bytes));
+		}
+
+		let mut order = OrderService::new(order_impls, strategy);
+
+		// Create class-specific execution strategies
+		for (class_name, strategy_config) in &self.config.order.class_strategies {
+			let class = class_name
+				.parse::<OrderClass>()
+				.map_err(SolverError::Config)?;
+			order = order
+				.with_class_strategy(class, build_strategy(&strategy_factories, strategy_config)?);
+			tracing::info!(component = "strategy", implementation = %strategy_config.strategy_type, class = %class_name, "Loaded");
+			capabilities.add_component("strategy", &strategy_config.strategy_type);
+		}
+
+		if !claim_recipients.is_empty() {
+			let unsupported = order.implementations_without_claim_recipient();
+			if !unsupported.is_empty() {
+				return Err(SolverError::Config(format!(
+					"Claim recipients are configured but order implementations [{}] do not permit claiming to a recipient",
+					unsupported.join(", ")
+				)));
+			}
+		}
+		// Resolve the risky capabilities enabled per standard
+		let features = self
+			.config
+			.order
+			.features
+			.iter()
+			.map(|(standard, flags)| {
+				(
+					standard.clone(),
+					StandardFeatures {
+						call_bearing_outputs: flags.call_bearing_outputs,
+						fee_on_transfer_tokens: flags.fee_on_transfer_tokens,
+						multi_output: flags.multi_output,
+					},
+				)
+			})
+			.collect();
+		let mut fee_on_transfer_tokens = HashSet::new();
+		for (chain_id, tokens) in &self.config.order.fee_on_transfer_tokens {
+			let chain_id = chain_id.parse::<u64>().map_err(|e| {
+				SolverError::Config(format!("Invalid chain ID '{}': {}", chain_id, e))
+			})?;
+			for token in tokens {
+				let bytes = hex::decode(token.trim_start_matches("0x")).map_err(|e| {
+					SolverError::Config(format!(
+						"Invalid fee-on-transfer token for chain {}: {}",
+						chain_id, e
+					))
+				})?;
+				fee_on_transfer_tokens.insert((chain_id, solver_types::Address(bytes)));
+			}
+		}
+
+		let mut order = order
+			.with_claim_recipients(claim_recipients)
+			.with_settler_routes(self.config.order.settler_routes.clone())
+			.with_features(features, fee_on_transfer_tokens);
+
+		// Reject spam orders before they consume RPC requests
+		if let Some(intake) = &self.config.order.intake {
+			let mut min_input_amounts = HashMap::new();
+			for (chain_id, tokens) in &intake.min_input_amounts {
+				let chain_id = chain_id.parse::<u64>().map_err(|e| {
+					SolverError::Config(format!("Invalid chain ID '{}': {}", chain_id, e))
+				})?;
+				for (token, amount) in tokens {
+					let bytes = hex::decode(token.trim_start_matches("0x")).map_err(|e| {
+						SolverError::Config(format!(
+							"Invalid intake token for chain {}: {}",
+							chain_id, e
+						))
+					})?;
+					let amount = U256::from_str_radix(amount, 10).map_err(|e| {
+						SolverError::Config(format!(
+							"Invalid minimum amount for token {} on chain {}: {}",
+							token, chain_id, e
+						))
+					})?;
+					min_input_amounts.insert((chain_id, solver_types::Address(bytes)), amount);
+				}
+			}
+			order = order.with_intake_filter(IntakeFilter::new(
+				min_input_amounts,
+				intake.max_orders_per_user_per_minute,
+			));
+			tracing::info!(component = "intake", "Loaded");
+		}
+
+		// Reject orders touching chains, tokens or addresses ruled out by policy
+		if let Some(policy_config) = &self.config.order.policy {
+			let parse_address = |address: &str| {
+				hex::decode(address.trim_start_matches("0x"))
+					.map(solver_types::Address)
+					.map_err(|e| {
+						SolverError::Config(format!("Invalid policy address {}: {}", address, e))
+					})
+			};
+			let parse_chain_id = |chain_id: &str| {
+				chain_id.parse::<u64>().map_err(|e| {
+					SolverError::Config(format!("Invalid chain ID '{}': {}", chain_id, e))
+				})
+			};
+
+			let mut lists = PolicyLists {
+				allowed_chains: policy_config.allowed_chains.iter().copied().collect(),
+				denied_chains: policy_config.denied_chains.iter().copied().collect(),
+				..Default::default()
+			};
+			for (chain_id, tokens) in &policy_config.allowed_tokens {
+				let chain_id = parse_chain_id(chain_id)?;
+				let allowed = lists.allowed_tokens.entry(chain_id).or_default();
+				for token in tokens {
+					allowed.insert(parse_address(token)?);
+				}
+			}
+			for (chain_id, tokens) in &policy_config.denied_tokens {
+				let chain_id = parse_chain_id(chain_id)?;
+				for token in tokens {
+					lists
+						.denied_tokens
+						.insert((chain_id, parse_address(token)?));
+				}
+			}
+			for address in &policy_config.denied_addresses {
+				lists.denied_addresses.insert(parse_address(address)?);
+			}
 
-		let order = Arc::new(OrderService::new(order_impls, strategy));
+			let mut policy = IntentPolicy::new(lists);
+			if let Some(url) = &policy_config.remote_url {
+				policy = policy.with_remote_list(url.clone());
+			}
+			order = order.with_policy(policy);
+			tracing::info!(component = "policy", "Loaded");
+		}
+
+		// Build the stablecoin fast path from the configured equivalence groups
+		if let Some(fast_path_config) = &self.config.order.stablecoin_fast_path {
+			let mut tokens = HashMap::new();
+			for (group, members) in &fast_path_config.groups {
+				for member in members {
+					let bytes =
+						hex::decode(member.address.trim_start_matches("0x")).map_err(|e| {
+							SolverError::Config(format!(
+								"Invalid stablecoin address in group {}: {}",
+								group, e
+							))
+						})?;
+					tokens.insert(
+						(member.chain_id, solver_types::Address(bytes)),
+						StablecoinToken {
+							group: group.clone(),
+							decimals: member.decimals,
+						},
+					);
+				}
+			}
+			tracing::info!(
+				component = "stablecoin_fast_path",
+				groups = fast_path_config.groups.len(),
+				"Loaded"
+			);
+			let mut fast_path = StablecoinFastPath::new(fast_path_config.min_margin_bps, tokens);
+			// Without its own limit, the fast path defers on the strategy's
+			let max_gas_price_gwei = fast_path_config.max_gas_price_gwei.or_else(|| {
+				self.config
+					.order
+					.execution_strategy
+					.config
+					.get("max_gas_price_gwei")
+					.and_then(|v| v.as_integer())
+					.map(|gwei| gwei as u64)
+			});
+			if let Some(gwei) = max_gas_price_gwei {
+				fast_path =
+					fast_path.with_max_gas_price(U256::from(gwei) * U256::from(10u64.pow(9)));
+			}
+			order = order.with_stablecoin_fast_path(fast_path);
+		}
+		let order = Arc::new(order);
 
 		// Create settlement implementations
 		let mut settlement_impls = HashMap::new();
@@ -895,19 +6507,521 @@ impl SolverBuilder {
 
 				settlement_impls.insert(name.clone(), implementation);
 				tracing::info!(component = "settlement", implementation = %name, "Loaded");
+				capabilities.add_component("settlement", name);
+			}
+		}
+
+		let mut settlement = SettlementService::new(settlement_impls);
+		if let Some(cache) = rpc_cache {
+			settlement = settlement.with_cache(cache);
+		}
+		let settlement = Arc::new(settlement);
+
+		// Create liquidity sources if liquidity checks are configured
+		let liquidity = match &self.config.liquidity {
+			Some(liquidity_config) => {
+				let mut liquidity_sources = HashMap::new();
+				for (name, config) in &liquidity_config.sources {
+					let implementation = config
+						.get("implementation")
+						.and_then(|v| v.as_str())
+						.ok_or_else(|| {
+							SolverError::Config(format!(
+								"implementation missing for liquidity source {}",
+								name
+							))
+						})?;
+					let factory =
+						self.liquidity_factories
+							.get(implementation)
+							.ok_or_else(|| {
+								SolverError::Config(format!(
+									"Unknown liquidity implementation '{}' for source {}",
+									implementation, name
+								))
+							})?;
+					let chain_id = config
+						.get("chain_id")
+						.and_then(|v| v.as_integer())
+						.ok_or_else(|| {
+							SolverError::Config(format!(
+								"chain_id missing for liquidity source {}",
+								name
+							))
+						})? as u64;
+
+					let source = factory(config);
+
+					// Validate the configuration using the source's schema
+					source.config_schema().validate(config).map_err(|e| {
+						SolverError::Config(format!(
+							"Invalid configuration for liquidity source '{}': {}",
+							name, e
+						))
+					})?;
+
+					liquidity_sources.insert(chain_id, source);
+					tracing::info!(component = "liquidity", source = %name, implementation = %implementation, chain_id = %chain_id, "Loaded");
+					capabilities.add_component("liquidity", implementation);
+				}
+
+				let mut liquid_tokens: HashMap<u64, HashSet<solver_types::Address>> =
+					HashMap::new();
+				for (chain_id, tokens) in &liquidity_config.liquid_tokens {
+					let chain_id = chain_id.parse::<u64>().map_err(|e| {
+						SolverError::Config(format!("Invalid liquid token chain ID: {}", e))
+					})?;
+					for token in tokens {
+						let bytes = hex::decode(token.trim_start_matches("0x")).map_err(|e| {
+							SolverError::Config(format!("Invalid liquid token address: {}", e))
+						})?;
+						liquid_tokens
+							.entry(chain_id)
+							.or_default()
+							.insert(solver_types::Address(bytes));
+					}
+				}
+
+				Some(Arc::new(LiquidityService::new(
+					liquidity_sources,
+					liquidity_config.max_price_impact_bps,
+					liquid_tokens,
+				)))
+			}
+			None => None,
+		};
+
+		// Create the risk validation pipeline if validators or liquidity checks
+		// are configured
+		let mut validators: Vec<(String, Box<dyn ValidatorInterface>)> = Vec::new();
+		if let Some(validation_config) = &self.config.validation {
+			for (name, config) in &validation_config.validators {
+				let factory = self
+					.validator_factories
+					.get(name)
+					.ok_or_else(|| SolverError::Config(format!("Unknown validator '{}'", name)))?;
+				let validator = factory(config);
+
+				// Validate the configuration using the validator's schema
+				validator.config_schema().validate(config).map_err(|e| {
+					SolverError::Config(format!(
+						"Invalid configuration for validator '{}': {}",
+						name, e
+					))
+				})?;
+
+				validators.push((name.clone(), validator));
+				tracing::info!(component = "validation", implementation = %name, "Loaded");
+				capabilities.add_component("validator", name);
 			}
 		}
+		// Liquidity checks are a validator built from the liquidity sources
+		if let Some(liquidity) = liquidity {
+			validators.push((
+				LIQUIDITY_VALIDATOR.to_string(),
+				Box::new(LiquidityValidator::new(liquidity)),
+			));
+			tracing::info!(
+				component = "validation",
+				implementation = LIQUIDITY_VALIDATOR,
+				"Loaded"
+			);
+			capabilities.add_component("validator", LIQUIDITY_VALIDATOR);
+		}
+		let validation = (self.config.validation.is_some() || !validators.is_empty())
+			.then(|| ValidationPipeline::new(validators));
+
+		let ledger = Ledger::new(storage.clone());
+
+		// Claim gas limits calibrated from past claims
+		let claim_gas = self.config.settlement.claim_gas.as_ref().map(|claim_gas| {
+			tracing::info!(component = "claim_gas", "Loaded");
+			ClaimGasCalibrator::new(
+				storage.clone(),
+				claim_gas.window,
+				claim_gas.percentile,
+				claim_gas.margin_bps,
+				claim_gas.min_samples,
+			)
+		});
+
+		// Claims handed to a shared claim bot
+		let claim_delegate = match &self.config.settlement.claim_delegation {
+			Some(delegation) => {
+				let delegate = HttpClaimDelegate::new(
+					delegation.endpoint.clone(),
+					delegation.auth_token.clone(),
+					Duration::from_secs(delegation.timeout_seconds),
+				)
+				.map_err(|e| SolverError::Config(e.to_string()))?;
+				tracing::info!(
+					component = "claim_delegation",
+					endpoint = %delegation.endpoint,
+					"Loaded"
+				);
+				Some(Arc::new(delegate) as Arc<dyn ClaimDelegate>)
+			}
+			None => None,
+		};
+
+		// Gas-price-aware timing of claims
+		let claim_schedule = match &self.config.settlement.claim_schedule {
+			Some(schedule) => {
+				tracing::info!(
+					component = "claim_schedule",
+					max_gas_price_gwei = schedule.max_gas_price_gwei,
+					"Loaded"
+				);
+				Some(ClaimScheduler::new(schedule).map_err(SolverError::Config)?)
+			}
+			None => None,
+		};
+
+		// Batching of ready claims
+		let claim_batcher = match &self.config.settlement.claim_batching {
+			Some(batching) => {
+				tracing::info!(
+					component = "claim_batching",
+					max_size = batching.max_size,
+					max_age_seconds = batching.max_age_seconds,
+					"Loaded"
+				);
+				Some(ClaimBatcher::new(batching))
+			}
+			None => None,
+		};
+
+		// Execution contexts from briefly cached fees, balances and prices
+		let context_config = self.config.execution_context.clone().unwrap_or_default();
+		let oracle_plugged_in = self.price_oracle.is_some();
+		let price_oracle = match self.price_oracle {
+			Some(oracle) => Some(oracle),
+			None if !context_config.token_prices_usd.is_empty() => {
+				let mut prices = HashMap::new();
+				for (chain_id, tokens) in &context_config.token_prices_usd {
+					let chain_id = chain_id.parse::<u64>().map_err(|e| {
+						SolverError::Config(format!("Invalid chain ID '{}': {}", chain_id, e))
+					})?;
+					for (token, price) in tokens {
+						let bytes = hex::decode(token.trim_start_matches("0x")).map_err(|e| {
+							SolverError::Config(format!(
+								"Invalid priced token for chain {}: {}",
+								chain_id, e
+							))
+						})?;
+						prices.insert((chain_id, solver_types::Address(bytes)), *price);
+					}
+				}
+				Some(Arc::new(FixedPriceOracle::new(prices)) as Arc<dyn PriceOracle>)
+			}
+			None => None,
+		};
+		let price_oracle = match &context_config.chainlink {
+			Some(chainlink) if !oracle_plugged_in => {
+				let parse_address = |address: &str| {
+					hex::decode(address.trim_start_matches("0x"))
+						.map(solver_types::Address)
+						.map_err(|e| {
+							SolverError::Config(format!(
+								"Invalid Chainlink feed address {}: {}",
+								address, e
+							))
+						})
+				};
+				let mut oracle = ChainlinkPriceOracle::new(
+					delivery.clone(),
+					Duration::from_secs(chainlink.max_staleness_seconds),
+				);
+				for feed in &chainlink.feeds {
+					oracle = oracle.with_feed(
+						feed.chain_id,
+						parse_address(&feed.token)?,
+						feed.feed_chain_id.unwrap_or(feed.chain_id),
+						parse_address(&feed.aggregator)?,
+					);
+				}
+				if let Some(fixed) = price_oracle {
+					oracle = oracle.with_fallback(fixed);
+				}
+				tracing::info!(
+					component = "price_oracle",
+					implementation = "chainlink",
+					feeds = chainlink.feeds.len(),
+					"Loaded"
+				);
+				Some(Arc::new(oracle) as Arc<dyn PriceOracle>)
+			}
+			_ => price_oracle,
+		};
+		tracing::info!(
+			component = "execution_context",
+			cache_ttl_seconds = context_config.cache_ttl_seconds,
+			price_oracle = price_oracle.is_some(),
+			"Loaded"
+		);
+		let context = Arc::new(
+			ContextBuilder::new(
+				delivery.clone(),
+				account.clone(),
+				price_oracle,
+				Duration::from_secs(context_config.cache_ttl_seconds),
+			)
+			.with_inventory(inventory.clone()),
+		);
+
+		// Fill simulation standing in for submission in dry runs
+		let dry_run = self.config.solver.dry_run.then(|| {
+			tracing::warn!(
+				component = "dry_run",
+				"Loaded, transactions will be simulated instead of submitted"
+			);
+			Arc::new(DryRun::new(delivery.clone(), context.clone()))
+		});
+
+		// Profit and loss of completed orders
+		let pnl = PnlRecorder::new(storage.clone(), delivery.clone(), context.clone());
+
+		// Destination selection for orders fillable on several chains
+		let routes = RouteSelector::new(
+			delivery.clone(),
+			context.clone(),
+			self.config
+				.routing
+				.as_ref()
+				.map(|routing| routing.usd_per_minute)
+				.unwrap_or(0.05),
+		);
+
+		// Reorg detection for recent intents and receipts
+		let reorgs = self.config.reorg.as_ref().map(|reorg| {
+			tracing::info!(component = "reorg", depth = reorg.depth, "Loaded");
+			let chain_depths = reorg
+				.chain_depths
+				.iter()
+				.filter_map(|(chain_id, depth)| Some((chain_id.parse().ok()?, *depth)))
+				.collect();
+			Arc::new(ReorgMonitor::new(reorg.depth, chain_depths))
+		});
+
+		// Bundling of fills sharing a destination chain and token
+		let bundler = match &self.config.delivery.bundling {
+			Some(bundling) => {
+				let mut max_output_amounts = HashMap::new();
+				for (chain_id, tokens) in &bundling.max_output_amounts {
+					let chain_id = chain_id.parse::<u64>().map_err(|e| {
+						SolverError::Config(format!("Invalid chain ID '{}': {}", chain_id, e))
+					})?;
+					for (token, amount) in tokens {
+						let bytes = hex::decode(token.trim_start_matches("0x")).map_err(|e| {
+							SolverError::Config(format!(
+								"Invalid bundling token for chain {}: {}",
+								chain_id, e
+							))
+						})?;
+						let amount = U256::from_str_radix(amount, 10).map_err(|e| {
+							SolverError::Config(format!(
+								"Invalid maximum bundled amount for token {} on chain {}: {}",
+								token, chain_id, e
+							))
+						})?;
+						max_output_amounts.insert((chain_id, solver_types::Address(bytes)), amount);
+					}
+				}
+				tracing::info!(
+					component = "bundling",
+					max_orders = bundling.max_orders,
+					window_ms = bundling.window_ms,
+					"Loaded"
+				);
+				Some(FillBundler::new(
+					bundling.max_orders,
+					Duration::from_millis(bundling.window_ms),
+					max_output_amounts,
+				))
+			}
+			None => None,
+		};
+
+		// Dead-letter queue, re-driving failed orders automatically if configured
+		let retry_policy = self.config.dead_letter.as_ref().map(|dead_letter| {
+			tracing::info!(
+				component = "dead_letter",
+				max_retries = dead_letter.max_retries,
+				retry_after_seconds = dead_letter.retry_after_seconds,
+				"Loaded"
+			);
+			RetryPolicy {
+				max_retries: dead_letter.max_retries,
+				retry_after: Duration::from_secs(dead_letter.retry_after_seconds),
+			}
+		});
+		let dead_letters = DeadLetterQueue::new(storage.clone(), retry_policy);
+		let submissions = SubmissionLog::new(storage.clone());
+		let intent_ledger = Arc::new(IntentLedger::new(storage.clone()));
+		let audit = AuditLog::new(storage.clone());
 
-		let settlement = Arc::new(SettlementService::new(settlement_impls));
+		// Pruning of the data of finished orders
+		let pruner = self.config.retention.as_ref().map(|retention_config| {
+			tracing::info!(
+				component = "retention",
+				completed_days = retention_config.completed_days,
+				failed_days = retention_config.failed_days,
+				archive = retention_config.archive_path.is_some(),
+				"Loaded"
+			);
+			Arc::new(OrderPruner::new(
+				storage.clone(),
+				RetentionPolicy {
+					completed: Duration::from_secs(retention_config.completed_days * 86_400),
+					failed: Duration::from_secs(retention_config.failed_days * 86_400),
+					archive_path: retention_config
+						.archive_path
+						.as_ref()
+						.map(std::path::PathBuf::from),
+				},
+			))
+		});
+
+		// Standards accepted, with the order features enabled for each
+		capabilities.standards = capabilities
+			.components
+			.get("order")
+			.into_iter()
+			.flatten()
+			.map(|name| {
+				let flags = self.config.order.features.get(name);
+				let features = [
+					(
+						"callBearingOutputs",
+						flags.is_some_and(|f| f.call_bearing_outputs),
+					),
+					(
+						"feeOnTransferTokens",
+						flags.is_some_and(|f| f.fee_on_transfer_tokens),
+					),
+					("multiOutput", flags.is_some_and(|f| f.multi_output)),
+				];
+				SupportedStandard {
+					name: name.clone(),
+					features: features
+						.into_iter()
+						.filter(|(_, enabled)| *enabled)
+						.map(|(feature, _)| feature.to_string())
+						.collect(),
+				}
+			})
+			.collect();
+		capabilities.chain_ids.sort_unstable();
+		capabilities.chain_ids.dedup();
+
+		// Optional subsystems turned on by configuration
+		let config = &self.config;
+		capabilities.features = [
+			("rpcCache", config.storage.rpc_cache_ttl_hours > 0),
+			("storageCompression", config.storage.compression.is_some()),
+			("approvals", config.approvals.is_some()),
+			("liquidityChecks", config.liquidity.is_some()),
+			("riskValidation", config.validation.is_some()),
+			("dryRun", config.solver.dry_run),
+			("inventory", config.inventory.is_some()),
+			("intakeFilter", config.order.intake.is_some()),
+			("intentPolicy", config.order.policy.is_some()),
+			("latencyBudget", config.order.latency_budget.is_some()),
+			(
+				"stablecoinFastPath",
+				config.order.stablecoin_fast_path.is_some(),
+			),
+			("fillBundling", config.delivery.bundling.is_some()),
+			("feeReplacement", config.delivery.replacement.is_some()),
+			("claimGasCalibration", config.settlement.claim_gas.is_some()),
+			(
+				"claimDelegation",
+				config.settlement.claim_delegation.is_some(),
+			),
+			("reorgDetection", config.reorg.is_some()),
+			("ledgerReconciliation", config.accounting.is_some()),
+			("crashRecovery", config.recovery.is_some()),
+			("readinessChecks", config.readiness.is_some()),
+			("inventoryBootstrap", config.bootstrap.is_some()),
+			("deadLetterRetries", config.dead_letter.is_some()),
+			("gasBudget", config.gas_budget.is_some()),
+			("retention", config.retention.is_some()),
+		]
+		.into_iter()
+		.filter(|(_, enabled)| *enabled)
+		.map(|(feature, _)| feature.to_string())
+		.collect();
+
+		// Shutdown checkpoints for crash detection
+		let checkpoints = self.config.recovery.as_ref().map(|recovery| {
+			tracing::info!(component = "recovery", file = %recovery.checkpoint_file, "Loaded");
+			CheckpointFile::new(&recovery.checkpoint_file)
+		});
+
+		let deferred = DeferredOrders::new(self.config.order.max_deferrals);
+		let (control, control_commands) = mpsc::unbounded_channel();
+		let (chain_tasks, task_failures) = ChainTaskGroups::new();
+		let reloaded = RwLock::new(Arc::new(self.config.clone()));
+		let persistent_events = self
+			.config
+			.recovery
+			.as_ref()
+			.is_some_and(|recovery| recovery.persistent_events);
+		let (event_bus, published_events) = if persistent_events {
+			let (event_bus, published) = EventBus::persistent(EVENT_BUS_CAPACITY);
+			(event_bus, Some(published))
+		} else {
+			(EventBus::new(EVENT_BUS_CAPACITY), None)
+		};
 
 		Ok(SolverEngine {
 			config: self.config,
+			reloaded,
+			strategy_factories,
 			storage,
+			account,
 			delivery,
 			discovery,
 			order,
 			settlement,
-			event_bus: EventBus::new(1000),
+			validation,
+			event_bus,
+			published_events: std::sync::Mutex::new(published_events),
+			control,
+			control_commands: std::sync::Mutex::new(Some(control_commands)),
+			metrics: Arc::new(EngineMetrics::new(EVENT_BUS_CAPACITY)),
+			approvals,
+			priorities: Arc::new(PriorityRegistry::new()),
+			bootstrapper,
+			readiness,
+			inventory,
+			latency: Arc::new(latency),
+			ledger,
+			pnl,
+			pending: Arc::new(PendingTransactions::default()),
+			chain_tasks,
+			task_failures: std::sync::Mutex::new(Some(task_failures)),
+			checkpoints,
+			claim_gas,
+			claim_delegate,
+			claim_schedule,
+			claim_batcher,
+			reorgs,
+			capital: CapitalTracker::default(),
+			context,
+			dry_run,
+			routes,
+			bundler,
+			deferred,
+			dead_letters,
+			retries: RetryQueue::default(),
+			submissions,
+			intent_ledger,
+			audit,
+			expiry: Arc::new(ExpiryWatcher::new()),
+			gas_budget,
+			pruner,
+			capabilities,
 		})
 	}
 }
@@ -0,0 +1,604 @@
+//! Runtime metrics for the solver engine.
+//!
+//! This module tracks how loaded the engine's main event loop is: the depth of
+//! the intent queue, the backlog on the event bus, how long each handler takes,
+//! and whether the loop is saturated, along with the discovery intake feeding
+//! it. It also tracks how quickly fills are
+//! submitted after discovery and how the solver's inventory is utilized.
+//! Metrics are rendered in the Prometheus text exposition format for the
+//! `/metrics` endpoint.
+
+use solver_discovery::IntakeStats;
+use solver_types::{
+	CapitalUtilization, DeliveryEvent, DiscoveryEvent, OrderEvent, SettlementEvent, SolverEvent,
+};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Intent queue depth above which the event loop is considered saturated.
+const INTENT_QUEUE_SATURATION_THRESHOLD: u64 = 100;
+
+/// Latency statistics for a single handler.
+#[derive(Debug, Default, Clone, Copy)]
+struct HandlerStats {
+	/// Number of handler invocations.
+	count: u64,
+	/// Total time spent in the handler in microseconds.
+	total_micros: u64,
+	/// Longest single invocation in microseconds.
+	max_micros: u64,
+}
+
+/// Metrics collected from the solver engine's event loop.
+pub struct EngineMetrics {
+	/// Capacity of the event bus, used to judge backlog saturation.
+	event_bus_capacity: u64,
+	/// Number of intents waiting to be handled.
+	intent_queue_depth: AtomicU64,
+	/// Number of events buffered on the bus for the engine's receiver.
+	event_bus_backlog: AtomicU64,
+	/// Discovery intake counters as of the last event loop iteration.
+	intake: Mutex<IntakeStats>,
+	/// Intents the discovery intake holds in memory before overflowing.
+	intake_capacity: AtomicU64,
+	/// Total time the event loop spent inside handlers in microseconds.
+	busy_micros: AtomicU64,
+	/// Latency statistics keyed by handler name.
+	handlers: Mutex<BTreeMap<&'static str, HandlerStats>>,
+	/// Number of events observed keyed by event kind.
+	events: Mutex<BTreeMap<&'static str, u64>>,
+	/// Time from intent discovery to fill submission.
+	fill_latency: Mutex<HandlerStats>,
+	/// Number of orders skipped because their latency budget ran out.
+	latency_budget_exceeded: AtomicU64,
+	/// Number of final fills rolled back because a reorg removed them.
+	deep_reorgs: AtomicU64,
+	/// Number of assets whose ledger inventory differs from on-chain balances.
+	ledger_drifted_assets: AtomicU64,
+	/// Number of finished orders whose data was pruned.
+	pruned_orders: AtomicU64,
+	/// Number of storage entries deleted by pruning.
+	pruned_entries: AtomicU64,
+	/// Capital utilization per token as of the last fill, claim or reconciliation.
+	capital: Mutex<Vec<CapitalUtilization>>,
+	/// Claim batches flushed and the claims they held, keyed by flush reason.
+	claim_batches: Mutex<BTreeMap<&'static str, (u64, u64)>>,
+}
+
+impl EngineMetrics {
+	/// Creates a new metrics collector for an event bus of the given capacity.
+	pub fn new(event_bus_capacity: usize) -> Self {
+		Self {
+			event_bus_capacity: event_bus_capacity as u64,
+			intent_queue_depth: AtomicU64::new(0),
+			event_bus_backlog: AtomicU64::new(0),
+			intake: Mutex::new(IntakeStats::default()),
+			intake_capacity: AtomicU64::new(0),
+			busy_micros: AtomicU64::new(0),
+			handlers: Mutex::new(BTreeMap::new()),
+			events: Mutex::new(BTreeMap::new()),
+			fill_latency: Mutex::new(HandlerStats::default()),
+			latency_budget_exceeded: AtomicU64::new(0),
+			deep_reorgs: AtomicU64::new(0),
+			ledger_drifted_assets: AtomicU64::new(0),
+			pruned_orders: AtomicU64::new(0),
+			pruned_entries: AtomicU64::new(0),
+			capital: Mutex::new(Vec::new()),
+			claim_batches: Mutex::new(BTreeMap::new()),
+		}
+	}
+
+	/// Records the current queue depths seen by the event loop.
+	pub fn set_queue_depths(&self, intent_queue_depth: usize, event_bus_backlog: usize) {
+		self.intent_queue_depth
+			.store(intent_queue_depth as u64, Ordering::Relaxed);
+		self.event_bus_backlog
+			.store(event_bus_backlog as u64, Ordering::Relaxed);
+	}
+
+	/// Records the counters of the discovery intake.
+	pub fn set_intake(&self, stats: IntakeStats, capacity: usize) {
+		*self.intake.lock().unwrap() = stats;
+		self.intake_capacity
+			.store(capacity as u64, Ordering::Relaxed);
+	}
+
+	/// Records the time taken by a handler invocation.
+	pub fn record_handler(&self, handler: &'static str, elapsed: Duration) {
+		let micros = elapsed.as_micros() as u64;
+		self.busy_micros.fetch_add(micros, Ordering::Relaxed);
+
+		let mut handlers = self.handlers.lock().unwrap();
+		let stats = handlers.entry(handler).or_default();
+		stats.count += 1;
+		stats.total_micros += micros;
+		stats.max_micros = stats.max_micros.max(micros);
+	}
+
+	/// Counts an event observed on the event bus.
+	pub fn record_event(&self, event: &SolverEvent) {
+		*self
+			.events
+			.lock()
+			.unwrap()
+			.entry(event_kind(event))
+			.or_default() += 1;
+	}
+
+	/// Records the time from discovery to fill submission for an order.
+	pub fn record_fill_latency(&self, elapsed: Duration) {
+		let micros = elapsed.as_micros() as u64;
+		let mut stats = self.fill_latency.lock().unwrap();
+		stats.count += 1;
+		stats.total_micros += micros;
+		stats.max_micros = stats.max_micros.max(micros);
+	}
+
+	/// Counts an order skipped because its latency budget ran out.
+	pub fn record_latency_budget_exceeded(&self) {
+		self.latency_budget_exceeded.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// Counts a final fill rolled back because a reorg removed it.
+	pub fn record_deep_reorg(&self) {
+		self.deep_reorgs.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// Records how many assets drifted from the ledger at the last reconciliation.
+	pub fn set_ledger_drifted_assets(&self, count: usize) {
+		self.ledger_drifted_assets
+			.store(count as u64, Ordering::Relaxed);
+	}
+
+	/// Counts finished orders pruned and the storage entries they took up.
+	pub fn record_pruned(&self, orders: usize, entries: usize) {
+		self.pruned_orders
+			.fetch_add(orders as u64, Ordering::Relaxed);
+		self.pruned_entries
+			.fetch_add(entries as u64, Ordering::Relaxed);
+	}
+
+	/// Records the current capital utilization per token.
+	pub fn set_capital_utilization(&self, capital: Vec<CapitalUtilization>) {
+		*self.capital.lock().unwrap() = capital;
+	}
+
+	/// Counts a flushed claim batch of `claims` claims.
+	pub fn record_claim_batch(&self, reason: &'static str, claims: usize) {
+		let mut batches = self.claim_batches.lock().unwrap();
+		let (count, total) = batches.entry(reason).or_default();
+		*count += 1;
+		*total += claims as u64;
+	}
+
+	/// Returns whether the event loop is falling behind its inputs.
+	///
+	/// The loop is saturated when intents queue up beyond a fixed threshold or
+	/// the event bus backlog exceeds half of the bus capacity, at which point
+	/// events risk being dropped.
+	pub fn is_saturated(&self) -> bool {
+		self.intent_queue_depth.load(Ordering::Relaxed) > INTENT_QUEUE_SATURATION_THRESHOLD
+			|| self.event_bus_backlog.load(Ordering::Relaxed) > self.event_bus_capacity / 2
+	}
+
+	/// Renders all metrics in the Prometheus text exposition format.
+	pub fn render(&self) -> String {
+		let mut out = String::new();
+
+		// Writing to a String cannot fail
+		write_header(
+			&mut out,
+			"solver_intent_queue_depth",
+			"gauge",
+			"Intents waiting to be handled.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_intent_queue_depth {}",
+			self.intent_queue_depth.load(Ordering::Relaxed)
+		);
+
+		write_header(
+			&mut out,
+			"solver_event_bus_backlog",
+			"gauge",
+			"Events buffered for the engine.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_event_bus_backlog {}",
+			self.event_bus_backlog.load(Ordering::Relaxed)
+		);
+
+		write_header(
+			&mut out,
+			"solver_event_bus_capacity",
+			"gauge",
+			"Capacity of the event bus.",
+		);
+		let _ = writeln!(out, "solver_event_bus_capacity {}", self.event_bus_capacity);
+
+		let intake = *self.intake.lock().unwrap();
+		write_header(
+			&mut out,
+			"solver_discovery_intake_depth",
+			"gauge",
+			"Discovered intents waiting in the intake, by where they are held.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_discovery_intake_depth{{location=\"memory\"}} {}",
+			intake.depth
+		);
+		let _ = writeln!(
+			out,
+			"solver_discovery_intake_depth{{location=\"storage\"}} {}",
+			intake.spilled
+		);
+
+		write_header(
+			&mut out,
+			"solver_discovery_intake_capacity",
+			"gauge",
+			"Intents the discovery intake holds in memory before overflowing.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_discovery_intake_capacity {}",
+			self.intake_capacity.load(Ordering::Relaxed)
+		);
+
+		write_header(
+			&mut out,
+			"solver_discovery_intake_saturations_total",
+			"counter",
+			"Times the discovery intake filled up.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_discovery_intake_saturations_total {}",
+			intake.saturations
+		);
+
+		write_header(
+			&mut out,
+			"solver_discovery_intake_overflow_total",
+			"counter",
+			"Intents discovered while the intake was full, by what happened to them.",
+		);
+		for (action, count) in [
+			("dropped", intake.dropped_total),
+			("rejected", intake.rejected_total),
+			("spilled", intake.spilled_total),
+		] {
+			let _ = writeln!(
+				out,
+				"solver_discovery_intake_overflow_total{{action=\"{}\"}} {}",
+				action, count
+			);
+		}
+
+		write_header(
+			&mut out,
+			"solver_discovery_intake_duplicates_total",
+			"counter",
+			"Intents refused because the same intent was queued recently.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_discovery_intake_duplicates_total {}",
+			intake.duplicate_total
+		);
+
+		write_header(
+			&mut out,
+			"solver_discovery_intake_rate_limited_total",
+			"counter",
+			"Intents refused because their source or the intake exceeded its rate limit.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_discovery_intake_rate_limited_total {}",
+			intake.rate_limited_total
+		);
+
+		write_header(
+			&mut out,
+			"solver_event_loop_saturated",
+			"gauge",
+			"Whether the event loop is falling behind its inputs.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_event_loop_saturated {}",
+			self.is_saturated() as u8
+		);
+
+		write_header(
+			&mut out,
+			"solver_event_loop_busy_seconds_total",
+			"counter",
+			"Time spent in event loop handlers.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_event_loop_busy_seconds_total {}",
+			self.busy_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0
+		);
+
+		let handlers = self.handlers.lock().unwrap().clone();
+		write_header(
+			&mut out,
+			"solver_handler_duration_seconds",
+			"summary",
+			"Time spent per event loop handler.",
+		);
+		for (handler, stats) in &handlers {
+			let _ = writeln!(
+				out,
+				"solver_handler_duration_seconds_sum{{handler=\"{}\"}} {}",
+				handler,
+				stats.total_micros as f64 / 1_000_000.0
+			);
+			let _ = writeln!(
+				out,
+				"solver_handler_duration_seconds_count{{handler=\"{}\"}} {}",
+				handler, stats.count
+			);
+		}
+
+		write_header(
+			&mut out,
+			"solver_handler_duration_seconds_max",
+			"gauge",
+			"Longest single handler invocation.",
+		);
+		for (handler, stats) in &handlers {
+			let _ = writeln!(
+				out,
+				"solver_handler_duration_seconds_max{{handler=\"{}\"}} {}",
+				handler,
+				stats.max_micros as f64 / 1_000_000.0
+			);
+		}
+
+		let fill_latency = *self.fill_latency.lock().unwrap();
+		write_header(
+			&mut out,
+			"solver_fill_latency_seconds",
+			"summary",
+			"Time from intent discovery to fill submission.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_fill_latency_seconds_sum {}",
+			fill_latency.total_micros as f64 / 1_000_000.0
+		);
+		let _ = writeln!(
+			out,
+			"solver_fill_latency_seconds_count {}",
+			fill_latency.count
+		);
+
+		write_header(
+			&mut out,
+			"solver_fill_latency_seconds_max",
+			"gauge",
+			"Longest time from intent discovery to fill submission.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_fill_latency_seconds_max {}",
+			fill_latency.max_micros as f64 / 1_000_000.0
+		);
+
+		write_header(
+			&mut out,
+			"solver_latency_budget_exceeded_total",
+			"counter",
+			"Orders skipped because their latency budget ran out.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_latency_budget_exceeded_total {}",
+			self.latency_budget_exceeded.load(Ordering::Relaxed)
+		);
+
+		write_header(
+			&mut out,
+			"solver_deep_reorgs_total",
+			"counter",
+			"Final fills rolled back because a reorg deeper than the chain's maximum depth removed them.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_deep_reorgs_total {}",
+			self.deep_reorgs.load(Ordering::Relaxed)
+		);
+
+		write_header(
+			&mut out,
+			"solver_ledger_drifted_assets",
+			"gauge",
+			"Assets whose ledger inventory differs from on-chain balances.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_ledger_drifted_assets {}",
+			self.ledger_drifted_assets.load(Ordering::Relaxed)
+		);
+
+		write_header(
+			&mut out,
+			"solver_pruned_orders_total",
+			"counter",
+			"Finished orders whose data was pruned after their retention period.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_pruned_orders_total {}",
+			self.pruned_orders.load(Ordering::Relaxed)
+		);
+
+		write_header(
+			&mut out,
+			"solver_pruned_entries_total",
+			"counter",
+			"Storage entries reclaimed by pruning finished orders.",
+		);
+		let _ = writeln!(
+			out,
+			"solver_pruned_entries_total {}",
+			self.pruned_entries.load(Ordering::Relaxed)
+		);
+
+		let capital = self.capital.lock().unwrap().clone();
+		write_header(
+			&mut out,
+			"solver_capital_in_flight",
+			"gauge",
+			"Inventory spent on filled orders awaiting their claim.",
+		);
+		for asset in &capital {
+			let _ = writeln!(
+				out,
+				"solver_capital_in_flight{{chain_id=\"{}\",token=\"{}\"}} {}",
+				asset.chain_id,
+				asset.token,
+				f64::from(asset.in_flight)
+			);
+		}
+
+		write_header(
+			&mut out,
+			"solver_capital_idle",
+			"gauge",
+			"Inventory held and not committed to any order.",
+		);
+		for asset in &capital {
+			let _ = writeln!(
+				out,
+				"solver_capital_idle{{chain_id=\"{}\",token=\"{}\"}} {}",
+				asset.chain_id,
+				asset.token,
+				f64::from(asset.idle)
+			);
+		}
+
+		write_header(
+			&mut out,
+			"solver_capital_utilization_ratio",
+			"gauge",
+			"Share of inventory that is in flight.",
+		);
+		for asset in &capital {
+			let _ = writeln!(
+				out,
+				"solver_capital_utilization_ratio{{chain_id=\"{}\",token=\"{}\"}} {}",
+				asset.chain_id, asset.token, asset.utilization
+			);
+		}
+
+		write_header(
+			&mut out,
+			"solver_capital_turnaround_seconds",
+			"summary",
+			"Time from fill confirmation to claim confirmation.",
+		);
+		for asset in &capital {
+			let _ = writeln!(
+				out,
+				"solver_capital_turnaround_seconds_sum{{chain_id=\"{}\",token=\"{}\"}} {}",
+				asset.chain_id,
+				asset.token,
+				asset.average_turnaround_seconds.unwrap_or_default() * asset.turnarounds as f64
+			);
+			let _ = writeln!(
+				out,
+				"solver_capital_turnaround_seconds_count{{chain_id=\"{}\",token=\"{}\"}} {}",
+				asset.chain_id, asset.token, asset.turnarounds
+			);
+		}
+
+		let claim_batches = self.claim_batches.lock().unwrap().clone();
+		write_header(
+			&mut out,
+			"solver_claim_batches_total",
+			"counter",
+			"Claim batches flushed, by why they were flushed.",
+		);
+		for (reason, (count, _)) in &claim_batches {
+			let _ = writeln!(
+				out,
+				"solver_claim_batches_total{{reason=\"{}\"}} {}",
+				reason, count
+			);
+		}
+
+		write_header(
+			&mut out,
+			"solver_claim_batch_claims_total",
+			"counter",
+			"Claims in flushed claim batches, by why the batches were flushed.",
+		);
+		for (reason, (_, claims)) in &claim_batches {
+			let _ = writeln!(
+				out,
+				"solver_claim_batch_claims_total{{reason=\"{}\"}} {}",
+				reason, claims
+			);
+		}
+
+		let events = self.events.lock().unwrap().clone();
+		write_header(
+			&mut out,
+			"solver_events_total",
+			"counter",
+			"Events observed on the event bus.",
+		);
+		for (kind, count) in &events {
+			let _ = writeln!(out, "solver_events_total{{kind=\"{}\"}} {}", kind, count);
+		}
+
+		out
+	}
+}
+
+/// Writes the HELP and TYPE lines that precede a metric.
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+	let _ = writeln!(out, "# HELP {} {}", name, help);
+	let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Returns a stable metric label for an event.
+pub fn event_kind(event: &SolverEvent) -> &'static str {
+	match event {
+		SolverEvent::Discovery(DiscoveryEvent::IntentDiscovered { .. }) => "intent_discovered",
+		SolverEvent::Discovery(DiscoveryEvent::IntentValidated { .. }) => "intent_validated",
+		SolverEvent::Discovery(DiscoveryEvent::IntentRejected { .. }) => "intent_rejected",
+		SolverEvent::Discovery(DiscoveryEvent::IntentInvalidated { .. }) => "intent_invalidated",
+		SolverEvent::Discovery(DiscoveryEvent::IntakeSaturated { .. }) => "intake_saturated",
+		SolverEvent::Order(OrderEvent::Executing { .. }) => "order_executing",
+		SolverEvent::Order(OrderEvent::Skipped { .. }) => "order_skipped",
+		SolverEvent::Order(OrderEvent::Deferred { .. }) => "order_deferred",
+		SolverEvent::Order(OrderEvent::Expired { .. }) => "order_expired",
+		SolverEvent::Order(OrderEvent::FilledByOther { .. }) => "order_filled_by_other",
+		SolverEvent::Delivery(DeliveryEvent::TransactionPending { .. }) => "transaction_pending",
+		SolverEvent::Delivery(DeliveryEvent::TransactionConfirmed { .. }) => {
+			"transaction_confirmed"
+		}
+		SolverEvent::Delivery(DeliveryEvent::TransactionFailed { .. }) => "transaction_failed",
+		SolverEvent::Delivery(DeliveryEvent::TransactionReplaced { .. }) => "transaction_replaced",
+		SolverEvent::Delivery(DeliveryEvent::TransactionReorged { .. }) => "transaction_reorged",
+		SolverEvent::Delivery(DeliveryEvent::ProviderDegraded { .. }) => "provider_degraded",
+		SolverEvent::Delivery(DeliveryEvent::FillsPaused { .. }) => "fills_paused",
+		SolverEvent::Delivery(DeliveryEvent::FillsResumed { .. }) => "fills_resumed",
+		SolverEvent::Settlement(SettlementEvent::FillDetected { .. }) => "fill_detected",
+		SolverEvent::Settlement(SettlementEvent::ProofReady { .. }) => "proof_ready",
+		SolverEvent::Settlement(SettlementEvent::ClaimReady { .. }) => "claim_ready",
+		SolverEvent::Settlement(SettlementEvent::Completed { .. }) => "settlement_completed",
+	}
+}
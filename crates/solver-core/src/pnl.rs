@@ -0,0 +1,238 @@
+//! Profit and loss of completed orders.
+//!
+//! When an order completes, the ledger entries journaled for it are
+//! summarized into a P&L record: the tokens received when claiming, the
+//! tokens delivered when filling, and the gas paid for each. Every amount is
+//! valued in USD with the oracle prices at completion, so that orders moving
+//! different tokens on different chains can be summed into one report.
+//!
+//! Records are persisted under their completion time, so that reports over a
+//! time range only read the records in it.
+
+use crate::accounting::{EntryKind, JournalEntry, LedgerAccount, Side};
+use crate::context::ContextBuilder;
+use alloy_primitives::U256;
+use solver_delivery::DeliveryService;
+use solver_storage::{StorageError, StorageService};
+use solver_types::{Address, GasTokenAmount, OrderAssets, OrderPnl, PnlReport, TokenAmount};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+/// Storage namespace holding P&L records, keyed by completion time and
+/// order ID.
+const PNL_NAMESPACE: &str = "order_pnl";
+
+/// Storage namespace mapping order IDs to the keys of their P&L records.
+const PNL_INDEX_NAMESPACE: &str = "order_pnl_index";
+
+/// Records and reports the profit and loss of completed orders.
+pub struct PnlRecorder {
+	/// Storage holding the records.
+	storage: Arc<StorageService>,
+	/// Delivery service resolving gas tokens.
+	delivery: Arc<DeliveryService>,
+	/// Context builder pricing tokens in USD.
+	context: Arc<ContextBuilder>,
+}
+
+impl PnlRecorder {
+	/// Creates a recorder persisting to `storage`, with prices from
+	/// `context`.
+	pub fn new(
+		storage: Arc<StorageService>,
+		delivery: Arc<DeliveryService>,
+		context: Arc<ContextBuilder>,
+	) -> Self {
+		Self {
+			storage,
+			delivery,
+			context,
+		}
+	}
+
+	/// Summarizes the ledger entries of a completed order into a P&L record
+	/// and persists it.
+	///
+	/// Gas of reverted transactions counts as claim gas on the chains the
+	/// order's inputs live on, and as fill gas elsewhere.
+	pub async fn record(
+		&self,
+		order_id: &str,
+		assets: &OrderAssets,
+		journal: &[JournalEntry],
+		completed_at: u64,
+	) -> Result<OrderPnl, StorageError> {
+		let input_chains: HashSet<u64> = assets.inputs.iter().map(|input| input.chain_id).collect();
+		let mut inputs = BTreeMap::new();
+		let mut outputs = BTreeMap::new();
+		let mut fill_gas = BTreeMap::new();
+		let mut claim_gas = BTreeMap::new();
+		for entry in journal {
+			for posting in &entry.postings {
+				let asset = &posting.asset;
+				let totals = match (posting.account, posting.side, &asset.token) {
+					(LedgerAccount::Revenue, Side::Credit, Some(token)) => {
+						inputs.entry((asset.chain_id, token.0.clone()))
+					}
+					(LedgerAccount::FillCost, Side::Debit, Some(token)) => {
+						outputs.entry((asset.chain_id, token.0.clone()))
+					}
+					(LedgerAccount::GasFees, Side::Debit, None) => {
+						let claim = match entry.kind {
+							EntryKind::Claim => true,
+							EntryKind::Gas => input_chains.contains(&asset.chain_id),
+							_ => false,
+						};
+						let gas = if claim { &mut claim_gas } else { &mut fill_gas };
+						let amount: &mut U256 = gas.entry(asset.chain_id).or_default();
+						*amount = amount.saturating_add(posting.amount);
+						continue;
+					}
+					_ => continue,
+				};
+				let amount: &mut U256 = totals.or_default();
+				*amount = amount.saturating_add(posting.amount);
+			}
+		}
+
+		let inputs = token_amounts(inputs);
+		let outputs = token_amounts(outputs);
+		let networks = self.delivery.networks();
+		let fill_gas: Vec<GasTokenAmount> = fill_gas
+			.into_iter()
+			.map(|(chain_id, amount)| networks.gas_amount(chain_id, amount))
+			.collect();
+		let claim_gas: Vec<GasTokenAmount> = claim_gas
+			.into_iter()
+			.map(|(chain_id, amount)| networks.gas_amount(chain_id, amount))
+			.collect();
+
+		let input_usd = self.context.tokens_usd(&inputs).await;
+		let output_usd = self.context.tokens_usd(&outputs).await;
+		let fill_gas_usd = self.context.gas_usd(&fill_gas).await;
+		let claim_gas_usd = self.context.gas_usd(&claim_gas).await;
+		let profit_usd = match (input_usd, output_usd, fill_gas_usd, claim_gas_usd) {
+			(Some(input), Some(output), Some(fill_gas), Some(claim_gas)) => {
+				Some(input - output - fill_gas - claim_gas)
+			}
+			_ => None,
+		};
+
+		let pnl = OrderPnl {
+			order_id: order_id.to_string(),
+			completed_at,
+			inputs,
+			outputs,
+			fill_gas,
+			claim_gas,
+			input_usd,
+			output_usd,
+			fill_gas_usd,
+			claim_gas_usd,
+			profit_usd,
+		};
+
+		// An order recorded again replaces its earlier record
+		let key = record_key(completed_at, order_id);
+		match self
+			.storage
+			.retrieve::<String>(PNL_INDEX_NAMESPACE, order_id)
+			.await
+		{
+			Ok(previous) if previous != key => {
+				self.storage.remove(PNL_NAMESPACE, &previous).await?;
+			}
+			Ok(_) | Err(StorageError::NotFound) => {}
+			Err(e) => return Err(e),
+		}
+		self.storage.store(PNL_NAMESPACE, &key, &pnl).await?;
+		self.storage
+			.store(PNL_INDEX_NAMESPACE, order_id, &key)
+			.await?;
+		Ok(pnl)
+	}
+
+	/// Sums the records of the orders completed from `from` until before
+	/// `to`.
+	///
+	/// Orders with an amount the oracle does not price are counted, but left
+	/// out of the USD totals.
+	pub async fn report(
+		&self,
+		from: Option<u64>,
+		to: Option<u64>,
+	) -> Result<PnlReport, StorageError> {
+		let mut report = PnlReport {
+			from,
+			to,
+			orders: 0,
+			priced_orders: 0,
+			unpriced_order_ids: Vec::new(),
+			input_usd: 0.0,
+			output_usd: 0.0,
+			fill_gas_usd: 0.0,
+			claim_gas_usd: 0.0,
+			profit_usd: 0.0,
+		};
+
+		let mut keys = self.storage.list_keys(PNL_NAMESPACE, "").await?;
+		keys.sort_unstable();
+		for key in keys {
+			let Some(completed_at) = key
+				.split_once('_')
+				.and_then(|(timestamp, _)| timestamp.parse::<u64>().ok())
+			else {
+				continue;
+			};
+			if from.is_some_and(|from| completed_at < from) {
+				continue;
+			}
+			if to.is_some_and(|to| completed_at >= to) {
+				break;
+			}
+
+			let pnl: OrderPnl = match self.storage.retrieve(PNL_NAMESPACE, &key).await {
+				Ok(pnl) => pnl,
+				Err(StorageError::NotFound) => continue,
+				Err(e) => return Err(e),
+			};
+			report.orders += 1;
+			match (
+				pnl.input_usd,
+				pnl.output_usd,
+				pnl.fill_gas_usd,
+				pnl.claim_gas_usd,
+				pnl.profit_usd,
+			) {
+				(Some(input), Some(output), Some(fill_gas), Some(claim_gas), Some(profit)) => {
+					report.priced_orders += 1;
+					report.input_usd += input;
+					report.output_usd += output;
+					report.fill_gas_usd += fill_gas;
+					report.claim_gas_usd += claim_gas;
+					report.profit_usd += profit;
+				}
+				_ => report.unpriced_order_ids.push(pnl.order_id),
+			}
+		}
+		Ok(report)
+	}
+}
+
+/// Converts totals keyed by chain and token address into token amounts.
+fn token_amounts(totals: BTreeMap<(u64, Vec<u8>), U256>) -> Vec<TokenAmount> {
+	totals
+		.into_iter()
+		.map(|((chain_id, token), amount)| TokenAmount {
+			chain_id,
+			token: Address(token),
+			amount,
+		})
+		.collect()
+}
+
+/// Returns the storage key of a record, padded so that keys sort by
+/// completion time.
+fn record_key(completed_at: u64, order_id: &str) -> String {
+	format!("{:020}_{}", completed_at, order_id)
+}
@@ -0,0 +1,66 @@
+//! Polling cadence of pending transactions.
+//!
+//! A pending transaction is checked at its chain's poll interval, which
+//! defaults to the chain's block time. While the chain stays on the same
+//! block, checks back off exponentially up to a ceiling, since the
+//! transaction cannot have landed in the meantime. Once a new block arrives
+//! the delay drops back to the poll interval. Delays carry some jitter, so
+//! that the monitors of transactions submitted together spread their
+//! requests instead of hitting the RPC endpoint at once.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Fraction a delay may be lengthened or shortened by as jitter.
+const JITTER: f64 = 0.1;
+
+/// Delays between checks of one pending transaction.
+pub struct PollBackoff {
+	/// Delay right after a new block.
+	base: Duration,
+	/// Longest delay the backoff grows to.
+	max: Duration,
+	/// Delay before the next check, without jitter.
+	delay: Duration,
+	/// Latest block seen, if any.
+	last_block: Option<u64>,
+}
+
+impl PollBackoff {
+	/// Creates a backoff starting at `base` and growing up to `max`.
+	pub fn new(base: Duration, max: Duration) -> Self {
+		Self {
+			base,
+			max: max.max(base),
+			delay: base,
+			last_block: None,
+		}
+	}
+
+	/// Returns the delay before the next check of a transaction that is
+	/// still pending, given the chain's latest block if it could be read.
+	///
+	/// The delay doubles with every check made on the same block, and drops
+	/// back to the base delay when a new block arrives.
+	pub fn next_delay(&mut self, block: Option<u64>) -> Duration {
+		if let Some(block) = block {
+			if self.last_block.is_none_or(|last| block > last) {
+				self.last_block = Some(block);
+				self.delay = self.base;
+			}
+		}
+
+		let delay = self.delay;
+		self.delay = self.delay.saturating_mul(2).min(self.max);
+		jitter(delay)
+	}
+}
+
+/// Lengthens or shortens a delay by up to [`JITTER`] of it.
+fn jitter(delay: Duration) -> Duration {
+	// Every `RandomState` is seeded differently, which is random enough here
+	let random = RandomState::new().build_hasher().finish();
+	let factor = 1.0 - JITTER + 2.0 * JITTER * (random % 1_000) as f64 / 1_000.0;
+	delay.mul_f64(factor)
+}
@@ -0,0 +1,143 @@
+//! Intent prioritization for the solver engine.
+//!
+//! External systems can boost or suppress specific orders or whole discovery
+//! sources for a time window. Discovered intents are queued and handled in
+//! order of their current priority score, falling back to arrival order for
+//! intents with equal scores.
+
+use solver_types::{Intent, PriorityRule, PriorityTarget};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::RwLock;
+
+/// Returns the current unix timestamp in seconds.
+fn now_secs() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
+
+/// Registry of externally injected priority rules.
+#[derive(Default)]
+pub struct PriorityRegistry {
+	/// Rules that may still be active; expired rules are pruned on write.
+	rules: RwLock<Vec<PriorityRule>>,
+}
+
+impl PriorityRegistry {
+	/// Creates an empty registry.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds rules, replacing any existing rule for the same target.
+	pub fn apply(&self, new_rules: Vec<PriorityRule>) {
+		let now = now_secs();
+		let mut rules = self.rules.write().unwrap();
+		rules.retain(|rule| {
+			rule.expires_at > now && !new_rules.iter().any(|new| new.target == rule.target)
+		});
+		rules.extend(new_rules);
+	}
+
+	/// Returns all rules that have not yet expired.
+	pub fn active_rules(&self) -> Vec<PriorityRule> {
+		let now = now_secs();
+		self.rules
+			.read()
+			.unwrap()
+			.iter()
+			.filter(|rule| rule.expires_at > now)
+			.cloned()
+			.collect()
+	}
+
+	/// Computes the priority score of an intent.
+	///
+	/// The score is the sum of the weights of all active rules matching the
+	/// intent's order ID or discovery source. Intents without rules score 0.
+	pub fn score(&self, intent: &Intent) -> i64 {
+		let now = now_secs();
+		self.rules
+			.read()
+			.unwrap()
+			.iter()
+			.filter(|rule| rule.expires_at > now)
+			.filter(|rule| match &rule.target {
+				PriorityTarget::Order(order_id) => *order_id == intent.id,
+				PriorityTarget::Source(source) => *source == intent.source,
+			})
+			.map(|rule| rule.weight)
+			.sum()
+	}
+}
+
+/// An intent waiting in the queue with its priority.
+struct QueuedIntent {
+	/// Priority score at the time the intent was queued.
+	score: i64,
+	/// Arrival position, used to keep equal-score intents in FIFO order.
+	sequence: u64,
+	/// The queued intent.
+	intent: Intent,
+}
+
+impl PartialEq for QueuedIntent {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for QueuedIntent {}
+
+impl PartialOrd for QueuedIntent {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for QueuedIntent {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// Higher scores first, then earlier arrivals first
+		self.score
+			.cmp(&other.score)
+			.then_with(|| other.sequence.cmp(&self.sequence))
+	}
+}
+
+/// Priority queue of discovered intents awaiting handling.
+#[derive(Default)]
+pub struct IntentQueue {
+	/// Queued intents ordered by priority.
+	heap: BinaryHeap<QueuedIntent>,
+	/// Arrival counter for FIFO tie-breaking.
+	next_sequence: u64,
+}
+
+impl IntentQueue {
+	/// Queues an intent with the given priority score.
+	pub fn push(&mut self, intent: Intent, score: i64) {
+		self.heap.push(QueuedIntent {
+			score,
+			sequence: self.next_sequence,
+			intent,
+		});
+		self.next_sequence += 1;
+	}
+
+	/// Removes and returns the highest-priority intent.
+	pub fn pop(&mut self) -> Option<Intent> {
+		self.heap.pop().map(|queued| queued.intent)
+	}
+
+	/// Returns the number of queued intents.
+	pub fn len(&self) -> usize {
+		self.heap.len()
+	}
+
+	/// Returns whether the queue is empty.
+	pub fn is_empty(&self) -> bool {
+		self.heap.is_empty()
+	}
+}
@@ -0,0 +1,143 @@
+//! Economic readiness checks for the solver.
+//!
+//! A solver that is up but holds no gas, no inventory or no approvals will
+//! accept orders it cannot fill. Readiness is therefore gated on the balances
+//! and approvals filling needs, so orchestration systems can hold traffic
+//! until those are in place. Each unmet precondition is reported on its own,
+//! telling operators exactly what to fund or approve.
+
+use crate::approvals::{ApprovalManager, UNLIMITED_ALLOWANCE};
+use crate::erc20::balance_of;
+use alloy_primitives::{hex, U256};
+use solver_delivery::DeliveryService;
+use solver_types::{Address, Precondition, UnmetPrecondition};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Minimum balance of a token the solver delivers.
+#[derive(Debug, Clone)]
+pub struct InventoryRequirement {
+	/// Chain the token lives on.
+	pub chain_id: u64,
+	/// Token contract address.
+	pub token: Address,
+	/// Minimum balance in the token's smallest unit.
+	pub amount: U256,
+}
+
+/// Checks the balances and approvals the solver needs to fill orders.
+pub struct ReadinessChecker {
+	/// Delivery service used for balance and allowance calls.
+	delivery: Arc<DeliveryService>,
+	/// Minimum gas token balance per chain.
+	min_gas_balances: BTreeMap<u64, U256>,
+	/// Minimum balances of delivered tokens.
+	min_inventory: Vec<InventoryRequirement>,
+	/// Whether configured approvals must be in place.
+	require_approvals: bool,
+	/// How long a check result is reused.
+	ttl: Duration,
+	/// Result of the last check and when it ran.
+	last: Mutex<Option<(Instant, Vec<UnmetPrecondition>)>>,
+}
+
+impl ReadinessChecker {
+	/// Creates a checker reusing each result for `ttl`.
+	pub fn new(
+		delivery: Arc<DeliveryService>,
+		min_gas_balances: BTreeMap<u64, U256>,
+		min_inventory: Vec<InventoryRequirement>,
+		require_approvals: bool,
+		ttl: Duration,
+	) -> Self {
+		Self {
+			delivery,
+			min_gas_balances,
+			min_inventory,
+			require_approvals,
+			ttl,
+			last: Mutex::new(None),
+		}
+	}
+
+	/// Returns the preconditions `owner` does not meet.
+	///
+	/// Balances and allowances that cannot be read count as unmet.
+	pub async fn check(
+		&self,
+		owner: &Address,
+		approvals: Option<&ApprovalManager>,
+	) -> Vec<UnmetPrecondition> {
+		if let Some((checked_at, unmet)) = self.last.lock().unwrap().as_ref() {
+			if checked_at.elapsed() < self.ttl {
+				return unmet.clone();
+			}
+		}
+
+		let mut unmet = Vec::new();
+
+		for (chain_id, minimum) in &self.min_gas_balances {
+			let message = match self.delivery.gas_balance(*chain_id, owner).await {
+				Ok(balance) if balance.amount >= *minimum => continue,
+				Ok(balance) => format!(
+					"holds {} but requires {}",
+					balance,
+					self.delivery.networks().gas_amount(*chain_id, *minimum)
+				),
+				Err(e) => format!("failed to read gas balance: {}", e),
+			};
+			unmet.push(UnmetPrecondition {
+				precondition: Precondition::GasBalance,
+				chain_id: Some(*chain_id),
+				token: None,
+				message,
+			});
+		}
+
+		for requirement in &self.min_inventory {
+			let message = match balance_of(
+				&self.delivery,
+				requirement.chain_id,
+				&requirement.token,
+				owner,
+			)
+			.await
+			{
+				Ok(balance) if balance >= requirement.amount => continue,
+				Ok(balance) => {
+					format!("holds {} but requires {}", balance, requirement.amount)
+				}
+				Err(e) => format!("failed to read token balance: {}", e),
+			};
+			unmet.push(UnmetPrecondition {
+				precondition: Precondition::Inventory,
+				chain_id: Some(requirement.chain_id),
+				token: Some(format!("0x{}", hex::encode(&requirement.token.0))),
+				message,
+			});
+		}
+
+		if let Some(approvals) = approvals.filter(|_| self.require_approvals) {
+			for target in approvals.targets() {
+				let message = match approvals.allowance(owner, target).await {
+					Ok(allowance) if allowance >= UNLIMITED_ALLOWANCE => continue,
+					Ok(_) => format!(
+						"spender 0x{} is not approved",
+						hex::encode(&target.spender.0)
+					),
+					Err(e) => format!("failed to read allowance: {}", e),
+				};
+				unmet.push(UnmetPrecondition {
+					precondition: Precondition::Approval,
+					chain_id: Some(target.chain_id),
+					token: Some(format!("0x{}", hex::encode(&target.token.0))),
+					message,
+				});
+			}
+		}
+
+		*self.last.lock().unwrap() = Some((Instant::now(), unmet.clone()));
+		unmet
+	}
+}
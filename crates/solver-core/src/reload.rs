@@ -0,0 +1,85 @@
+//! Detection of configuration reload requests.
+//!
+//! Operators ask the solver to reload its configuration by sending it SIGHUP,
+//! or, when watching is enabled, simply by saving the configuration file. The
+//! file is watched by polling its modification time, which works the same on
+//! every filesystem, including mounted volumes where change notifications are
+//! unreliable.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::signal::unix::{signal, Signal, SignalKind};
+use tokio::time::Interval;
+
+/// Watches for requests to reload a configuration file.
+pub struct ConfigWatcher {
+	/// Path of the configuration file.
+	path: PathBuf,
+	/// Modification time of the file when it was last loaded.
+	modified: Option<SystemTime>,
+	/// Interval the modification time is polled at, if watching is enabled.
+	poll: Option<Interval>,
+	/// Stream of SIGHUP signals, if the handler could be installed.
+	hangup: Option<Signal>,
+}
+
+impl ConfigWatcher {
+	/// Creates a watcher for the configuration file at `path`.
+	///
+	/// The file is checked for changes every `poll_interval`, or only
+	/// reloaded on SIGHUP if no interval is given.
+	pub fn new(path: impl Into<PathBuf>, poll_interval: Option<Duration>) -> Self {
+		let path = path.into();
+		let hangup = match signal(SignalKind::hangup()) {
+			Ok(hangup) => Some(hangup),
+			Err(e) => {
+				tracing::warn!(error = %e, "Failed to install SIGHUP handler");
+				None
+			}
+		};
+
+		Self {
+			modified: modified_at(&path),
+			poll: poll_interval.map(tokio::time::interval),
+			hangup,
+			path,
+		}
+	}
+
+	/// Waits until a reload is requested, either by SIGHUP or by the file
+	/// being modified.
+	pub async fn changed(&mut self) {
+		let Self {
+			path,
+			modified,
+			poll,
+			hangup,
+		} = self;
+
+		loop {
+			tokio::select! {
+				Some(()) = async { hangup.as_mut()?.recv().await }, if hangup.is_some() => {
+					tracing::info!("Received SIGHUP, reloading configuration");
+					*modified = modified_at(path);
+					return;
+				}
+				_ = async { poll.as_mut().unwrap().tick().await }, if poll.is_some() => {
+					let current = modified_at(path);
+					if current != *modified {
+						tracing::info!(path = %path.display(), "Configuration file changed, reloading");
+						*modified = current;
+						return;
+					}
+				}
+				else => std::future::pending().await,
+			}
+		}
+	}
+}
+
+/// Returns the modification time of a file, if it can be read.
+fn modified_at(path: &Path) -> Option<SystemTime> {
+	std::fs::metadata(path)
+		.and_then(|metadata| metadata.modified())
+		.ok()
+}
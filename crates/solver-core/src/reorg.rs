@@ -0,0 +1,320 @@
+//! Chain reorganization detection.
+//!
+//! Discovery and delivery treat an event or receipt as settled once they have
+//! seen it, but a reorg can drop the block it was included in. The monitor
+//! remembers the block of every recent intent and receipt along with the hash
+//! that block had when first checked. When a later check finds a different hash
+//! at one of those heights, everything at or above it is handed back to the
+//! engine for re-validation.
+//!
+//! Inclusion is considered final once it is buried deeper than the chain's
+//! maximum reorg depth. Fills are still remembered after that, along with the
+//! hash of their block, until their order completes: the engine has accounted
+//! for them, and a reorg beyond the expected depth during a chain incident
+//! would otherwise leave that accounting silently wrong.
+
+use alloy_primitives::hex;
+use solver_types::{TransactionHash, TransactionType};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+
+/// An intent whose opening transaction was included in a recent block.
+#[derive(Debug, Clone)]
+pub struct TrackedIntent {
+	/// Order the intent was validated into.
+	pub order_id: String,
+	/// Transaction that opened the intent.
+	pub tx_hash: TransactionHash,
+	/// Block the opening transaction was included in.
+	pub block_number: u64,
+}
+
+/// A solver transaction that was included in a recent block.
+#[derive(Debug, Clone)]
+pub struct TrackedReceipt {
+	/// Order the transaction belongs to.
+	pub order_id: String,
+	/// Hash of the transaction.
+	pub tx_hash: TransactionHash,
+	/// Whether the transaction is a fill or a claim.
+	pub tx_type: TransactionType,
+	/// Block the transaction was included in.
+	pub block_number: u64,
+}
+
+/// A fill whose inclusion became final.
+#[derive(Debug, Clone)]
+pub struct FinalizedFill {
+	/// Chain the fill was included on.
+	pub chain_id: u64,
+	/// The fill transaction as tracked before it became final.
+	pub receipt: TrackedReceipt,
+	/// Hash of the fill's block when it became final.
+	pub block_hash: Vec<u8>,
+}
+
+/// Recent history of a single chain.
+#[derive(Default)]
+struct ChainHistory {
+	/// Hashes of tracked heights as first observed.
+	blocks: BTreeMap<u64, Vec<u8>>,
+	/// Intents opened in recent blocks.
+	intents: Vec<TrackedIntent>,
+	/// Solver transactions included in recent blocks.
+	receipts: Vec<TrackedReceipt>,
+	/// Fills that became final, with the hash of their block at the time.
+	finalized: Vec<(TrackedReceipt, Vec<u8>)>,
+}
+
+impl ChainHistory {
+	/// Returns the heights referenced by tracked intents and receipts.
+	fn heights(&self) -> HashSet<u64> {
+		self.intents
+			.iter()
+			.map(|intent| intent.block_number)
+			.chain(self.receipts.iter().map(|receipt| receipt.block_number))
+			.collect()
+	}
+}
+
+/// Tracks recent blocks per chain and detects reorgs among them.
+pub struct ReorgMonitor {
+	/// Blocks after which inclusion is considered final.
+	depth: u64,
+	/// Chain-specific overrides of `depth` keyed by chain ID.
+	chain_depths: HashMap<u64, u64>,
+	/// Recent history keyed by chain ID.
+	chains: Mutex<HashMap<u64, ChainHistory>>,
+	/// Chains with a check in progress.
+	checking: Mutex<HashSet<u64>>,
+	/// Orders whose intent was dropped by a reorg.
+	invalidated: Mutex<HashSet<String>>,
+	/// Hex hashes of solver transactions dropped by a reorg and not yet
+	/// confirmed again.
+	reorged: Mutex<HashSet<String>>,
+}
+
+impl ReorgMonitor {
+	/// Creates a monitor treating blocks `depth` below the head as final,
+	/// or the depth given in `chain_depths` for the chains listed there.
+	pub fn new(depth: u64, chain_depths: HashMap<u64, u64>) -> Self {
+		Self {
+			depth,
+			chain_depths,
+			chains: Mutex::new(HashMap::new()),
+			checking: Mutex::new(HashSet::new()),
+			invalidated: Mutex::new(HashSet::new()),
+			reorged: Mutex::new(HashSet::new()),
+		}
+	}
+
+	/// Returns the maximum reorg depth of a chain.
+	pub fn depth(&self, chain_id: u64) -> u64 {
+		self.chain_depths
+			.get(&chain_id)
+			.copied()
+			.unwrap_or(self.depth)
+	}
+
+	/// Tracks an intent opened in a block.
+	pub fn track_intent(&self, chain_id: u64, intent: TrackedIntent) {
+		self.chains
+			.lock()
+			.unwrap()
+			.entry(chain_id)
+			.or_default()
+			.intents
+			.push(intent);
+	}
+
+	/// Tracks a solver transaction included in a block.
+	pub fn track_receipt(&self, chain_id: u64, receipt: TrackedReceipt) {
+		self.chains
+			.lock()
+			.unwrap()
+			.entry(chain_id)
+			.or_default()
+			.receipts
+			.push(receipt);
+	}
+
+	/// Returns the chains with tracked intents or receipts.
+	pub fn chains(&self) -> Vec<u64> {
+		self.chains
+			.lock()
+			.unwrap()
+			.iter()
+			.filter(|(_, history)| !history.intents.is_empty() || !history.receipts.is_empty())
+			.map(|(chain_id, _)| *chain_id)
+			.collect()
+	}
+
+	/// Returns the heights on a chain whose hashes need checking.
+	pub fn heights(&self, chain_id: u64) -> Vec<u64> {
+		let chains = self.chains.lock().unwrap();
+		let mut heights: Vec<u64> = chains
+			.get(&chain_id)
+			.map(|history| history.heights().into_iter().collect())
+			.unwrap_or_default();
+		heights.sort_unstable();
+		heights
+	}
+
+	/// Marks a check of a chain as started.
+	///
+	/// Returns false if a check of the chain is already in progress.
+	pub fn begin_check(&self, chain_id: u64) -> bool {
+		self.checking.lock().unwrap().insert(chain_id)
+	}
+
+	/// Marks a check of a chain as finished.
+	pub fn end_check(&self, chain_id: u64) {
+		self.checking.lock().unwrap().remove(&chain_id);
+	}
+
+	/// Compares the current hashes of tracked heights against the ones first
+	/// observed and forgets everything that has become final, except for
+	/// fills, which are kept as [`FinalizedFill`]s.
+	///
+	/// Returns the intents and receipts at or above the lowest height whose
+	/// hash changed; these are no longer tracked.
+	pub fn reconcile(
+		&self,
+		chain_id: u64,
+		head: u64,
+		hashes: &[(u64, Option<Vec<u8>>)],
+	) -> (Vec<TrackedIntent>, Vec<TrackedReceipt>) {
+		let mut chains = self.chains.lock().unwrap();
+		let Some(history) = chains.get_mut(&chain_id) else {
+			return (Vec::new(), Vec::new());
+		};
+
+		let mut fork = None;
+		for (number, hash) in hashes {
+			let changed = match (history.blocks.get(number), hash) {
+				(Some(known), Some(current)) => known != current,
+				// The chain no longer reaches a height it already had
+				(Some(_), None) => true,
+				(None, Some(current)) => {
+					history.blocks.insert(*number, current.clone());
+					false
+				}
+				(None, None) => false,
+			};
+			if changed {
+				fork = Some(fork.map_or(*number, |fork: u64| fork.min(*number)));
+			}
+		}
+
+		let mut affected = (Vec::new(), Vec::new());
+		if let Some(fork) = fork {
+			let (intents, kept) = std::mem::take(&mut history.intents)
+				.into_iter()
+				.partition(|intent| intent.block_number >= fork);
+			history.intents = kept;
+			affected.0 = intents;
+
+			let (receipts, kept) = std::mem::take(&mut history.receipts)
+				.into_iter()
+				.partition(|receipt| receipt.block_number >= fork);
+			history.receipts = kept;
+			affected.1 = receipts;
+
+			history.blocks.retain(|number, _| *number < fork);
+		}
+
+		// Inclusion deeper than the reorg depth is final
+		let finalized = head.saturating_sub(self.depth(chain_id));
+		history
+			.intents
+			.retain(|intent| intent.block_number > finalized);
+		let (buried, recent) = std::mem::take(&mut history.receipts)
+			.into_iter()
+			.partition(|receipt| receipt.block_number <= finalized);
+		history.receipts = recent;
+		for receipt in buried {
+			if receipt.tx_type != TransactionType::Fill {
+				continue;
+			}
+			if let Some(hash) = history.blocks.get(&receipt.block_number) {
+				let hash = hash.clone();
+				history.finalized.push((receipt, hash));
+			}
+		}
+		history.blocks.retain(|number, _| *number > finalized);
+
+		affected
+	}
+
+	/// Returns the fills that became final and are still remembered.
+	pub fn finalized_fills(&self) -> Vec<FinalizedFill> {
+		self.chains
+			.lock()
+			.unwrap()
+			.iter()
+			.flat_map(|(chain_id, history)| {
+				history
+					.finalized
+					.iter()
+					.map(|(receipt, block_hash)| FinalizedFill {
+						chain_id: *chain_id,
+						receipt: receipt.clone(),
+						block_hash: block_hash.clone(),
+					})
+			})
+			.collect()
+	}
+
+	/// Stops remembering the finalized fills of a transaction and returns
+	/// them.
+	pub fn take_finalized(&self, chain_id: u64, tx_hash: &TransactionHash) -> Vec<TrackedReceipt> {
+		let mut chains = self.chains.lock().unwrap();
+		let Some(history) = chains.get_mut(&chain_id) else {
+			return Vec::new();
+		};
+		let (taken, kept) = std::mem::take(&mut history.finalized)
+			.into_iter()
+			.partition(|(receipt, _)| receipt.tx_hash == *tx_hash);
+		history.finalized = kept;
+		taken.into_iter().map(|(receipt, _)| receipt).collect()
+	}
+
+	/// Stops remembering the finalized fill of an order once the order is
+	/// complete.
+	pub fn release_order(&self, order_id: &str) {
+		for history in self.chains.lock().unwrap().values_mut() {
+			history
+				.finalized
+				.retain(|(receipt, _)| receipt.order_id != order_id);
+		}
+	}
+
+	/// Records that an order's intent was dropped by a reorg.
+	pub fn invalidate(&self, order_id: &str) {
+		self.invalidated
+			.lock()
+			.unwrap()
+			.insert(order_id.to_string());
+	}
+
+	/// Returns whether an order's intent was dropped by a reorg.
+	pub fn is_invalidated(&self, order_id: &str) -> bool {
+		self.invalidated.lock().unwrap().contains(order_id)
+	}
+
+	/// Records that a solver transaction was dropped by a reorg.
+	pub fn mark_reorged(&self, tx_hash: &TransactionHash) {
+		self.reorged.lock().unwrap().insert(hex::encode(&tx_hash.0));
+	}
+
+	/// Returns whether a transaction being confirmed was previously dropped by
+	/// a reorg, and forgets it.
+	///
+	/// Such a confirmation repeats one the engine already acted on.
+	pub fn take_reorged(&self, tx_hash: &TransactionHash) -> bool {
+		self.reorged
+			.lock()
+			.unwrap()
+			.remove(&hex::encode(&tx_hash.0))
+	}
+}
@@ -0,0 +1,139 @@
+//! Tracking of pending transactions for fee replacement.
+//!
+//! Transactions submitted with fees that later fall below the market can sit in
+//! the mempool indefinitely. The engine tracks every pending transaction here so
+//! that ones pending for longer than a threshold can be re-submitted with higher
+//! fees. A replaced transaction stays tracked alongside its replacement, since
+//! either of them may end up being mined.
+
+use solver_types::{TransactionHash, TransactionType};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A transaction awaiting confirmation.
+#[derive(Debug, Clone)]
+pub struct PendingTransaction {
+	/// Order the transaction belongs to.
+	pub order_id: String,
+	/// Hash of the transaction.
+	pub tx_hash: TransactionHash,
+	/// Whether the transaction is a fill or a claim.
+	pub tx_type: TransactionType,
+	/// Chain the transaction was submitted on.
+	pub chain_id: u64,
+	/// Number of times the original transaction has been replaced.
+	pub replacements: u32,
+	/// When the transaction was submitted.
+	submitted: Instant,
+	/// Whether the transaction has been replaced by a newer one.
+	superseded: bool,
+	/// Whether the transaction is no longer worth replacing.
+	abandoned: bool,
+}
+
+/// Pending transactions keyed by hash.
+#[derive(Default)]
+pub struct PendingTransactions {
+	/// Tracked transactions, including superseded ones.
+	pending: Mutex<HashMap<Vec<u8>, PendingTransaction>>,
+}
+
+impl PendingTransactions {
+	/// Starts tracking a submitted transaction.
+	///
+	/// Transactions that are already tracked keep their submission time.
+	pub fn track(
+		&self,
+		order_id: &str,
+		tx_hash: &TransactionHash,
+		tx_type: TransactionType,
+		chain_id: u64,
+	) {
+		self.pending
+			.lock()
+			.unwrap()
+			.entry(tx_hash.0.clone())
+			.or_insert_with(|| PendingTransaction {
+				order_id: order_id.to_string(),
+				tx_hash: tx_hash.clone(),
+				tx_type,
+				chain_id,
+				replacements: 0,
+				submitted: Instant::now(),
+				superseded: false,
+				abandoned: false,
+			});
+	}
+
+	/// Returns whether a transaction is still awaiting confirmation.
+	pub fn is_tracked(&self, tx_hash: &TransactionHash) -> bool {
+		self.pending.lock().unwrap().contains_key(&tx_hash.0)
+	}
+
+	/// Returns the number of transactions of a type awaiting confirmation, not
+	/// counting ones that have been replaced.
+	pub fn count(&self, tx_type: TransactionType) -> usize {
+		self.pending
+			.lock()
+			.unwrap()
+			.values()
+			.filter(|tx| !tx.superseded && tx.tx_type == tx_type)
+			.count()
+	}
+
+	/// Stops tracking a transaction along with every transaction it replaced or
+	/// was replaced by.
+	pub fn resolve(&self, tx_hash: &TransactionHash) {
+		let mut pending = self.pending.lock().unwrap();
+		if let Some(resolved) = pending.remove(&tx_hash.0) {
+			pending
+				.retain(|_, tx| tx.order_id != resolved.order_id || tx.tx_type != resolved.tx_type);
+		}
+	}
+
+	/// Returns transactions pending for longer than `threshold` that have been
+	/// replaced fewer than `max_replacements` times.
+	pub fn stuck(&self, threshold: Duration, max_replacements: u32) -> Vec<PendingTransaction> {
+		self.pending
+			.lock()
+			.unwrap()
+			.values()
+			.filter(|tx| {
+				!tx.superseded
+					&& !tx.abandoned
+					&& tx.replacements < max_replacements
+					&& tx.submitted.elapsed() > threshold
+			})
+			.cloned()
+			.collect()
+	}
+
+	/// Records that a transaction has been replaced.
+	///
+	/// The replacement is tracked from now on, with the replacement count of
+	/// the transaction it replaces incremented.
+	pub fn replaced(&self, old_hash: &TransactionHash, new_hash: &TransactionHash) {
+		let mut pending = self.pending.lock().unwrap();
+		let Some(old) = pending.get_mut(&old_hash.0) else {
+			return;
+		};
+		old.superseded = true;
+
+		let replacement = PendingTransaction {
+			tx_hash: new_hash.clone(),
+			replacements: old.replacements + 1,
+			submitted: Instant::now(),
+			superseded: false,
+			..old.clone()
+		};
+		pending.insert(new_hash.0.clone(), replacement);
+	}
+
+	/// Stops replacing a transaction, leaving it tracked until it resolves.
+	pub fn abandon(&self, tx_hash: &TransactionHash) {
+		if let Some(tx) = self.pending.lock().unwrap().get_mut(&tx_hash.0) {
+			tx.abandoned = true;
+		}
+	}
+}
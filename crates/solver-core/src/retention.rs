@@ -0,0 +1,292 @@
+//! Retention of the data of finished orders.
+//!
+//! Every order leaves a trail of entries in storage: the order itself, its
+//! status, transaction hashes, proofs and amounts. Nothing needs them once the
+//! order completed or failed beyond answering status queries, so the pruner
+//! deletes them after a retention period, optionally appending them to an
+//! archive file first. The audit log and the order sequence index are kept;
+//! listing orders skips orders whose data was pruned.
+//!
+//! An order counts as finished from the first pruning pass that sees it
+//! completed or failed, so finish times are only as precise as the pruning
+//! interval. An order re-driven after failing is no longer finished and keeps
+//! its data until it finishes again.
+
+use alloy_primitives::hex;
+use serde::{Deserialize, Serialize};
+use solver_storage::{StorageError, StorageService};
+use solver_types::{DetailedIntentStatus, TransactionHash};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Storage namespace holding when each finished order finished, keyed by
+/// order ID.
+const FINISHED_NAMESPACE: &str = "order_finished";
+
+/// Storage namespace holding the status of each order, keyed by order ID.
+const STATUS_NAMESPACE: &str = "order_status";
+
+/// Namespaces holding data of a single order besides its status, keyed by
+/// order ID.
+const ORDER_NAMESPACES: &[&str] = &[
+	"orders",
+	"intents",
+	"fills",
+	"claims",
+	"fill_proofs",
+	"fill_amounts",
+	"filled_amounts",
+	"claim_paths",
+	"proof_regenerations",
+	"intent_ledger",
+];
+
+/// How long the data of finished orders is kept.
+#[derive(Debug, Clone)]
+pub struct RetentionPolicy {
+	/// Retention of completed orders.
+	pub completed: Duration,
+	/// Retention of failed orders.
+	pub failed: Duration,
+	/// File pruned orders are appended to as JSON lines, if archived.
+	pub archive_path: Option<PathBuf>,
+}
+
+/// Outcome of a pruning pass.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PruneReport {
+	/// Orders whose data was pruned.
+	pub orders: usize,
+	/// Storage entries deleted.
+	pub entries: usize,
+}
+
+/// When an order finished, and how.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FinishedOrder {
+	/// Status the order finished with.
+	status: DetailedIntentStatus,
+	/// Unix timestamp of when the order was first seen finished.
+	finished_at: u64,
+}
+
+/// Entry of the archive, holding the pruned data of one order.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedOrder<'a> {
+	order_id: &'a str,
+	status: &'a DetailedIntentStatus,
+	finished_at: u64,
+	pruned_at: u64,
+	/// Pruned entries keyed by namespace.
+	data: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Deletes the data of orders that finished longer ago than their retention.
+pub struct OrderPruner {
+	/// Storage holding the orders' data.
+	storage: Arc<StorageService>,
+	/// Retention periods and archive.
+	policy: RetentionPolicy,
+	/// Held while a pruning pass runs, so passes never overlap.
+	running: Mutex<()>,
+}
+
+impl OrderPruner {
+	/// Creates a pruner keeping finished orders according to `policy`.
+	pub fn new(storage: Arc<StorageService>, policy: RetentionPolicy) -> Self {
+		Self {
+			storage,
+			policy,
+			running: Mutex::new(()),
+		}
+	}
+
+	/// Prunes every order whose retention period ended.
+	///
+	/// Orders seen finished for the first time are only marked as finished.
+	/// An order whose data cannot be archived is left in place, so nothing is
+	/// deleted that was meant to be archived. Returns an empty report if
+	/// another pass is still running.
+	pub async fn prune(&self) -> Result<PruneReport, StorageError> {
+		let Ok(_running) = self.running.try_lock() else {
+			return Ok(PruneReport::default());
+		};
+		let now = now_secs();
+		let mut report = PruneReport::default();
+
+		for (order_id, status) in self
+			.storage
+			.retrieve_all::<DetailedIntentStatus>(STATUS_NAMESPACE)
+			.await?
+		{
+			let retention = match status {
+				DetailedIntentStatus::Completed => self.policy.completed,
+				DetailedIntentStatus::Failed => self.policy.failed,
+				_ => {
+					self.remove(FINISHED_NAMESPACE, &order_id).await?;
+					continue;
+				}
+			};
+
+			let finished = match self
+				.storage
+				.retrieve::<FinishedOrder>(FINISHED_NAMESPACE, &order_id)
+				.await
+			{
+				// Orders that failed again, or were completed after failing,
+				// finished anew
+				Ok(finished) if same_status(&finished.status, &status) => finished,
+				Ok(_) | Err(StorageError::NotFound) => {
+					let finished = FinishedOrder {
+						status,
+						finished_at: now,
+					};
+					self.storage
+						.store(FINISHED_NAMESPACE, &order_id, &finished)
+						.await?;
+					continue;
+				}
+				Err(e) => return Err(e),
+			};
+			if finished.finished_at.saturating_add(retention.as_secs()) > now {
+				continue;
+			}
+
+			report.entries += self.prune_order(&order_id, &finished, now).await?;
+			report.orders += 1;
+		}
+
+		Ok(report)
+	}
+
+	/// Archives and deletes the data of a finished order, returning the
+	/// number of entries deleted.
+	async fn prune_order(
+		&self,
+		order_id: &str,
+		finished: &FinishedOrder,
+		now: u64,
+	) -> Result<usize, StorageError> {
+		let mut entries = Vec::new();
+		for namespace in ORDER_NAMESPACES {
+			entries.push((namespace.to_string(), order_id.to_string()));
+		}
+
+		// Transaction lookups are only pruned once no other order of a
+		// bundled transaction is left
+		for namespace in ["fills", "claims"] {
+			let tx_hash = match self
+				.storage
+				.retrieve::<TransactionHash>(namespace, order_id)
+				.await
+			{
+				Ok(tx_hash) => tx_hash,
+				Err(StorageError::NotFound) => continue,
+				Err(e) => return Err(e),
+			};
+			let tx_key = hex::encode(&tx_hash.0);
+			let bundle = match self
+				.storage
+				.retrieve::<Vec<String>>("bundles", &tx_key)
+				.await
+			{
+				Ok(bundle) => bundle,
+				Err(StorageError::NotFound) => Vec::new(),
+				Err(e) => return Err(e),
+			};
+			let mut shared = false;
+			for other in bundle.iter().filter(|other| *other != order_id) {
+				if self.storage.exists("orders", other).await? {
+					shared = true;
+					break;
+				}
+			}
+			if !shared {
+				entries.push(("tx_to_order".to_string(), tx_key.clone()));
+				entries.push(("bundles".to_string(), tx_key));
+			}
+		}
+
+		if let Some(path) = &self.policy.archive_path {
+			let mut data = serde_json::Map::new();
+			for (namespace, id) in &entries {
+				let key = if id == order_id {
+					namespace.clone()
+				} else {
+					format!("{}:{}", namespace, id)
+				};
+				match self
+					.storage
+					.retrieve::<serde_json::Value>(namespace, id)
+					.await
+				{
+					Ok(value) => {
+						data.insert(key, value);
+					}
+					Err(StorageError::NotFound) => {}
+					Err(e) => return Err(e),
+				}
+			}
+			let archived = ArchivedOrder {
+				order_id,
+				status: &finished.status,
+				finished_at: finished.finished_at,
+				pruned_at: now,
+				data,
+			};
+			let mut line = serde_json::to_string(&archived)
+				.map_err(|e| StorageError::Serialization(e.to_string()))?;
+			line.push('\n');
+
+			let mut file = tokio::fs::OpenOptions::new()
+				.create(true)
+				.append(true)
+				.open(path)
+				.await
+				.map_err(|e| StorageError::Backend(e.to_string()))?;
+			file.write_all(line.as_bytes())
+				.await
+				.map_err(|e| StorageError::Backend(e.to_string()))?;
+		}
+
+		let mut deleted = 0;
+		for (namespace, id) in &entries {
+			if self.remove(namespace, id).await? {
+				deleted += 1;
+			}
+		}
+		// The status is deleted last, so an interrupted pass prunes the order
+		// again
+		self.remove(FINISHED_NAMESPACE, order_id).await?;
+		if self.remove(STATUS_NAMESPACE, order_id).await? {
+			deleted += 1;
+		}
+		Ok(deleted)
+	}
+
+	/// Removes an entry, returning whether it existed.
+	async fn remove(&self, namespace: &str, id: &str) -> Result<bool, StorageError> {
+		if !self.storage.exists(namespace, id).await? {
+			return Ok(false);
+		}
+		self.storage.remove(namespace, id).await?;
+		Ok(true)
+	}
+}
+
+/// Returns whether two statuses are the same.
+fn same_status(a: &DetailedIntentStatus, b: &DetailedIntentStatus) -> bool {
+	std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+/// Returns the current unix timestamp in seconds.
+fn now_secs() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
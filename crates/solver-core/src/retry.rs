@@ -0,0 +1,97 @@
+//! Retries of intents and events whose handling failed transiently.
+//!
+//! Handling an intent or an event can fail halfway, for example when an RPC
+//! request times out or the storage backend is briefly unavailable. Rather
+//! than stopping the engine, such work is handled again after an
+//! exponentially growing backoff. Work failing permanently, or still failing
+//! after its last attempt, is left to the dead-letter queue.
+//!
+//! Retries are held in memory only: work waiting for a retry when the solver
+//! stops is recovered by the event journal and intent discovery on restart,
+//! like any other work in flight.
+
+use solver_types::{Intent, SolverEvent};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Attempts at handling a piece of work before it is dead-lettered.
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Backoff before the first retry.
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound on the backoff between retries.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Work the engine handles that can be retried.
+#[derive(Debug, Clone)]
+pub enum Work {
+	/// A discovered intent.
+	Intent(Intent),
+	/// A published event.
+	Event(SolverEvent),
+}
+
+/// A piece of work waiting to be retried.
+struct PendingRetry {
+	/// When the work is due.
+	due: Instant,
+	/// Attempts made so far.
+	attempts: u32,
+	/// The failed work.
+	work: Work,
+}
+
+/// Work waiting to be handled again after a transient failure.
+#[derive(Default)]
+pub struct RetryQueue {
+	/// Work waiting for its retry time.
+	pending: Mutex<Vec<PendingRetry>>,
+}
+
+impl RetryQueue {
+	/// Schedules work that failed after `attempts` attempts to be handled
+	/// again.
+	///
+	/// Returns the backoff before the retry, or `None`, without scheduling
+	/// the work, once it has used up its attempts.
+	pub fn schedule(&self, work: Work, attempts: u32) -> Option<Duration> {
+		if attempts >= MAX_ATTEMPTS {
+			return None;
+		}
+
+		let backoff = BASE_BACKOFF
+			.saturating_mul(1 << (attempts.saturating_sub(1)).min(16))
+			.min(MAX_BACKOFF);
+		self.pending.lock().unwrap().push(PendingRetry {
+			due: Instant::now() + backoff,
+			attempts,
+			work,
+		});
+		Some(backoff)
+	}
+
+	/// Removes and returns the work whose retry time has come, along with the
+	/// attempts made at it so far.
+	pub fn take_due(&self) -> Vec<(Work, u32)> {
+		let now = Instant::now();
+		let mut pending = self.pending.lock().unwrap();
+		let (due, waiting): (Vec<_>, Vec<_>) =
+			pending.drain(..).partition(|retry| retry.due <= now);
+		*pending = waiting;
+		due.into_iter()
+			.map(|retry| (retry.work, retry.attempts))
+			.collect()
+	}
+
+	/// Returns the number of pieces of work waiting for a retry.
+	pub fn len(&self) -> usize {
+		self.pending.lock().unwrap().len()
+	}
+
+	/// Returns whether no work is waiting for a retry.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
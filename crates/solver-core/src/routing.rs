@@ -0,0 +1,155 @@
+//! Destination selection for orders fillable on several chains.
+//!
+//! Some orders let the solver pick which of several chains to fill them on,
+//! each chain with outputs of its own. Routes whose outputs the solver does
+//! not hold are ruled out. The remaining routes are ranked by what filling
+//! through them costs: the USD value of the fill's gas, plus the time the
+//! fill takes to gather the chain's required confirmations, valued at a
+//! configured rate.
+
+use crate::context::ContextBuilder;
+use alloy_primitives::U256;
+use solver_delivery::DeliveryService;
+use solver_oracles::gas_token;
+use solver_types::{whole_tokens, Address, FillRoute};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// What filling an order through a route costs.
+#[derive(Debug, Clone)]
+pub struct RouteCost {
+	/// Chain of the route.
+	pub chain_id: u64,
+	/// USD value of the fill's gas, if the chain's gas token is priced.
+	pub gas_usd: Option<f64>,
+	/// Time the fill takes to gather the chain's required confirmations.
+	pub confirmation_time: Duration,
+}
+
+impl RouteCost {
+	/// Returns the gas cost plus the confirmation time valued at
+	/// `usd_per_minute`, if the gas is priced.
+	fn total_usd(&self, usd_per_minute: f64) -> Option<f64> {
+		let waiting = self.confirmation_time.as_secs_f64() / 60.0 * usd_per_minute;
+		Some(self.gas_usd? + waiting)
+	}
+}
+
+/// Picks the route orders with several destinations are filled through.
+pub struct RouteSelector {
+	/// Delivery service providing confirmation policies and block times.
+	delivery: Arc<DeliveryService>,
+	/// Context builder providing cached balances, fee estimates and prices.
+	context: Arc<ContextBuilder>,
+	/// USD value of a minute of waiting for confirmations.
+	usd_per_minute: f64,
+}
+
+impl RouteSelector {
+	/// Creates a selector valuing a minute of waiting at `usd_per_minute`.
+	pub fn new(
+		delivery: Arc<DeliveryService>,
+		context: Arc<ContextBuilder>,
+		usd_per_minute: f64,
+	) -> Self {
+		Self {
+			delivery,
+			context,
+			usd_per_minute,
+		}
+	}
+
+	/// Picks the cheapest route whose outputs `solver` holds.
+	///
+	/// Routes are ranked by total cost when the gas of every candidate is
+	/// priced, and by confirmation time alone otherwise. Returns `None` if the
+	/// solver holds the outputs of no route.
+	pub async fn select<'a>(
+		&self,
+		routes: &'a [FillRoute],
+		solver: &Address,
+	) -> Option<(&'a FillRoute, RouteCost)> {
+		let mut candidates = Vec::with_capacity(routes.len());
+		for route in routes {
+			if !self.holds_outputs(route, solver).await {
+				tracing::debug!(
+					chain_id = route.chain_id,
+					"Ruled out route without the outputs in inventory"
+				);
+				continue;
+			}
+			candidates.push((route, self.cost(route).await));
+		}
+
+		let priced = candidates.iter().all(|(_, cost)| cost.gas_usd.is_some());
+		let rank = |cost: &RouteCost| match priced {
+			true => cost.total_usd(self.usd_per_minute).unwrap_or(f64::MAX),
+			false => cost.confirmation_time.as_secs_f64(),
+		};
+		candidates
+			.into_iter()
+			.min_by(|(_, a), (_, b)| rank(a).total_cmp(&rank(b)))
+	}
+
+	/// Returns whether the solver holds every output of a route.
+	///
+	/// Outputs paid in the zero address are paid in the chain's gas token.
+	async fn holds_outputs(&self, route: &FillRoute, solver: &Address) -> bool {
+		let mut required: HashMap<&Address, U256> = HashMap::new();
+		for output in &route.outputs {
+			let amount = required.entry(&output.token).or_default();
+			*amount = amount.saturating_add(output.amount);
+		}
+
+		for (token, amount) in required {
+			let balance = if token.0.iter().all(|byte| *byte == 0) {
+				self.context.native_balance(route.chain_id, solver).await
+			} else {
+				self.context
+					.token_balance(&(route.chain_id, token.clone()), solver)
+					.await
+			};
+			if balance.is_none_or(|balance| balance < amount) {
+				return false;
+			}
+		}
+		true
+	}
+
+	/// Estimates what filling through a route costs.
+	async fn cost(&self, route: &FillRoute) -> RouteCost {
+		let networks = self.delivery.networks();
+		let confirmations = self
+			.delivery
+			.confirmation_policy(route.chain_id)
+			.confirmations
+			.max(1);
+		let confirmation_time = networks
+			.block_time(route.chain_id)
+			.saturating_mul(confirmations as u32);
+
+		let gas_price = self
+			.context
+			.gas_estimate(route.chain_id)
+			.await
+			.and_then(|estimate| estimate.max_price());
+		let gas_usd = match (gas_price, route.gas_limit) {
+			(Some(gas_price), Some(gas_limit)) => {
+				let fee = gas_price.saturating_mul(U256::from(gas_limit));
+				let decimals = networks.gas_token(route.chain_id).decimals;
+				self.context
+					.usd_price(&(route.chain_id, gas_token()))
+					.await
+					.map(|price| whole_tokens(fee, decimals) * price)
+			}
+			_ => None,
+		};
+
+		RouteCost {
+			chain_id: route.chain_id,
+			gas_usd,
+			confirmation_time,
+		}
+	}
+}
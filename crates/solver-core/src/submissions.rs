@@ -0,0 +1,158 @@
+//! Acknowledgment of intents submitted to the solver.
+//!
+//! Order-flow providers submitting intents off-chain retry a submission
+//! until it is acknowledged. An intent is only acknowledged once it has been
+//! written to storage, so a solver crashing before it handled the intent
+//! picks it up again on restart instead of losing it.
+//!
+//! Retries of an acknowledged submission are recognized by intent ID and
+//! acknowledged again without being processed twice. Handled submissions are
+//! remembered for a retention period after which retries are no longer
+//! expected.
+
+use solver_storage::{StorageError, StorageService};
+use solver_types::Intent;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Storage namespace holding submitted intents not handled yet, keyed by
+/// intent ID.
+const PENDING_NAMESPACE: &str = "submissions";
+
+/// Storage namespace holding acknowledgments of handled submissions, keyed
+/// by intent ID.
+const ACKS_NAMESPACE: &str = "submission_acks";
+
+/// Storage namespace holding the index of pending submissions.
+const INDEX_NAMESPACE: &str = "submission_index";
+
+/// Key of the index of pending submissions.
+const INDEX_KEY: &str = "pending";
+
+/// How long handled submissions are remembered for recognizing retries.
+const ACK_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Storage-backed log of submitted intents.
+pub struct SubmissionLog {
+	/// Storage holding submissions, acknowledgments and the index.
+	storage: Arc<StorageService>,
+	/// IDs of the pending submissions, mirroring the stored index.
+	pending: Mutex<HashSet<String>>,
+}
+
+impl SubmissionLog {
+	/// Creates an empty log backed by `storage`.
+	pub fn new(storage: Arc<StorageService>) -> Self {
+		Self {
+			storage,
+			pending: Mutex::new(HashSet::new()),
+		}
+	}
+
+	/// Loads the pending submissions left by a previous run.
+	///
+	/// Returns their intents, which still have to be handled.
+	pub async fn load(&self) -> Result<Vec<Intent>, StorageError> {
+		let mut pending = self.pending.lock().await;
+		let mut intents: Vec<Intent> = Vec::new();
+		for intent_id in self.intent_ids().await? {
+			match self.storage.retrieve(PENDING_NAMESPACE, &intent_id).await {
+				Ok(intent) => intents.push(intent),
+				Err(StorageError::NotFound) => continue,
+				Err(e) => return Err(e),
+			}
+		}
+		pending.extend(intents.iter().map(|intent| intent.id.clone()));
+		Ok(intents)
+	}
+
+	/// Persists a submitted intent so that it can be acknowledged.
+	///
+	/// Returns false, without storing anything, if the intent was already
+	/// submitted and is pending or was handled within the retention period.
+	pub async fn record(&self, intent: &Intent) -> Result<bool, StorageError> {
+		let mut pending = self.pending.lock().await;
+		if pending.contains(&intent.id) || self.is_acknowledged(&intent.id).await? {
+			return Ok(false);
+		}
+
+		self.storage
+			.store(PENDING_NAMESPACE, &intent.id, intent)
+			.await?;
+		pending.insert(intent.id.clone());
+		self.store_index(&pending).await?;
+		Ok(true)
+	}
+
+	/// Drops a pending submission that could not be handed on, so that the
+	/// submitter's retry is accepted again.
+	pub async fn release(&self, intent_id: &str) -> Result<(), StorageError> {
+		let mut pending = self.pending.lock().await;
+		if !pending.remove(intent_id) {
+			return Ok(());
+		}
+		self.storage.remove(PENDING_NAMESPACE, intent_id).await?;
+		self.store_index(&pending).await
+	}
+
+	/// Marks a pending submission as handled, remembering it so that retries
+	/// are recognized.
+	///
+	/// Intents that were not submitted are ignored.
+	pub async fn complete(&self, intent_id: &str) -> Result<(), StorageError> {
+		let mut pending = self.pending.lock().await;
+		if !pending.remove(intent_id) {
+			return Ok(());
+		}
+		self.storage
+			.store_with_ttl(ACKS_NAMESPACE, intent_id, &now_secs(), Some(ACK_RETENTION))
+			.await?;
+		self.storage.remove(PENDING_NAMESPACE, intent_id).await?;
+		self.store_index(&pending).await
+	}
+
+	/// Forgets handled submissions whose retention period ended.
+	pub async fn purge_expired(&self) -> Result<usize, StorageError> {
+		Ok(self.storage.purge_expired(ACKS_NAMESPACE).await?.len())
+	}
+
+	/// Returns whether a submission was handled within the retention period.
+	async fn is_acknowledged(&self, intent_id: &str) -> Result<bool, StorageError> {
+		match self
+			.storage
+			.retrieve::<u64>(ACKS_NAMESPACE, intent_id)
+			.await
+		{
+			Ok(_) => Ok(true),
+			Err(StorageError::NotFound) => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Writes the index of pending submissions.
+	async fn store_index(&self, pending: &HashSet<String>) -> Result<(), StorageError> {
+		let intent_ids: Vec<&String> = pending.iter().collect();
+		self.storage
+			.store(INDEX_NAMESPACE, INDEX_KEY, &intent_ids)
+			.await
+	}
+
+	/// Reads the IDs of the pending submissions.
+	async fn intent_ids(&self) -> Result<Vec<String>, StorageError> {
+		match self.storage.retrieve(INDEX_NAMESPACE, INDEX_KEY).await {
+			Ok(intent_ids) => Ok(intent_ids),
+			Err(StorageError::NotFound) => Ok(Vec::new()),
+			Err(e) => Err(e),
+		}
+	}
+}
+
+/// Returns the current unix timestamp in seconds.
+fn now_secs() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
@@ -0,0 +1,222 @@
+//! Per-order timelines built from the audit log.
+//!
+//! Dashboards want to show what happened to an order, and how long each step
+//! took, without stitching together orders, events and transactions. Replaying
+//! the audit log of a single order yields its milestones in the order they
+//! were reached, annotated with the transactions involved and why it failed,
+//! if it did.
+
+use alloy_primitives::hex;
+use solver_types::{
+	AuditEntry, DeliveryEvent, DiscoveryEvent, Milestone, NetworkRegistry, OrderEvent,
+	OrderTimelineResponse, SettlementEvent, SolverEvent, TimelineEntry, TransactionHash,
+	TransactionType,
+};
+use std::collections::HashMap;
+
+/// Milestones of a single order, collected from its audit log.
+pub struct OrderTimeline {
+	/// ID of the order.
+	order_id: String,
+	/// Milestones reached so far.
+	entries: Vec<TimelineEntry>,
+	/// Chain of each transaction submitted for the order, keyed by hash.
+	transactions: HashMap<Vec<u8>, u64>,
+}
+
+impl OrderTimeline {
+	/// Creates an empty timeline for an order.
+	pub fn new(order_id: &str) -> Self {
+		Self {
+			order_id: order_id.to_string(),
+			entries: Vec::new(),
+			transactions: HashMap::new(),
+		}
+	}
+
+	/// Applies an audit entry, ignoring events for other orders.
+	///
+	/// Entries must be applied in sequence order.
+	pub fn apply(&mut self, entry: &AuditEntry) {
+		let target = self.order_id.clone();
+		let ours = |order_id: &str| order_id == target;
+
+		let (milestone, tx, failure) = match &entry.event {
+			SolverEvent::Discovery(event) => match event {
+				DiscoveryEvent::IntentDiscovered { intent } if ours(&intent.id) => {
+					(Milestone::Discovered, None, None)
+				}
+				DiscoveryEvent::IntentValidated { order, .. } if ours(&order.id) => {
+					(Milestone::Validated, None, None)
+				}
+				DiscoveryEvent::IntentRejected { intent_id, reason } if ours(intent_id) => {
+					(Milestone::Rejected, None, Some(reason.clone()))
+				}
+				DiscoveryEvent::IntentInvalidated { intent_id, reason } if ours(intent_id) => {
+					(Milestone::Invalidated, None, Some(reason.clone()))
+				}
+				_ => return,
+			},
+			SolverEvent::Order(event) => match event {
+				OrderEvent::Executing { order, .. } if ours(&order.id) => {
+					(Milestone::Executing, None, None)
+				}
+				OrderEvent::Skipped { order_id, reason } if ours(order_id) => {
+					(Milestone::Skipped, None, Some(reason.clone()))
+				}
+				OrderEvent::Deferred { order_id, .. } if ours(order_id) => {
+					(Milestone::Deferred, None, None)
+				}
+				OrderEvent::Expired { order_id } if ours(order_id) => (
+					Milestone::Expired,
+					None,
+					Some("Fill deadline passed before execution".to_string()),
+				),
+				OrderEvent::FilledByOther { order_id } if ours(order_id) => (
+					Milestone::FilledByOther,
+					None,
+					Some("Filled by another solver".to_string()),
+				),
+				_ => return,
+			},
+			SolverEvent::Delivery(event) => match event {
+				DeliveryEvent::TransactionPending {
+					order_id,
+					tx_hash,
+					tx_type,
+					chain_id,
+				} if ours(order_id) => {
+					self.transactions.insert(tx_hash.0.clone(), *chain_id);
+					let milestone = match tx_type {
+						TransactionType::Fill => Milestone::FillSubmitted,
+						TransactionType::Claim => Milestone::ClaimSubmitted,
+					};
+					(milestone, Some(tx_hash), None)
+				}
+				DeliveryEvent::TransactionReplaced {
+					order_id,
+					new_tx_hash,
+					tx_type,
+					chain_id,
+					..
+				} if ours(order_id) => {
+					self.transactions.insert(new_tx_hash.0.clone(), *chain_id);
+					let milestone = match tx_type {
+						TransactionType::Fill => Milestone::FillReplaced,
+						TransactionType::Claim => Milestone::ClaimReplaced,
+					};
+					(milestone, Some(new_tx_hash), None)
+				}
+				DeliveryEvent::TransactionReorged {
+					order_id,
+					tx_hash,
+					tx_type,
+					..
+				} if ours(order_id) => {
+					let milestone = match tx_type {
+						TransactionType::Fill => Milestone::FillReorged,
+						TransactionType::Claim => Milestone::ClaimReorged,
+					};
+					(milestone, Some(tx_hash), None)
+				}
+				DeliveryEvent::TransactionConfirmed {
+					tx_hash, tx_type, ..
+				} if self.transactions.contains_key(&tx_hash.0) => {
+					let milestone = match tx_type {
+						TransactionType::Fill => Milestone::Filled,
+						TransactionType::Claim => Milestone::Claimed,
+					};
+					(milestone, Some(tx_hash), None)
+				}
+				DeliveryEvent::TransactionFailed { tx_hash, error }
+					if self.transactions.contains_key(&tx_hash.0) =>
+				{
+					(
+						Milestone::TransactionFailed,
+						Some(tx_hash),
+						Some(error.clone()),
+					)
+				}
+				_ => return,
+			},
+			SolverEvent::Settlement(event) => match event {
+				SettlementEvent::FillDetected { order_id, tx_hash } if ours(order_id) => {
+					(Milestone::Filled, Some(tx_hash), None)
+				}
+				SettlementEvent::ProofReady { order_id, .. } if ours(order_id) => {
+					(Milestone::ProofReady, None, None)
+				}
+				SettlementEvent::ClaimReady { order_id } if ours(order_id) => {
+					(Milestone::ClaimReady, None, None)
+				}
+				SettlementEvent::Completed { order_id } if ours(order_id) => {
+					(Milestone::Completed, None, None)
+				}
+				_ => return,
+			},
+		};
+
+		self.push(entry.timestamp, milestone, tx, failure);
+	}
+
+	/// Returns whether no event for the order has been applied.
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Finishes the timeline, linking transactions to the block explorers
+	/// of their chains.
+	pub fn into_response(mut self, networks: &NetworkRegistry) -> OrderTimelineResponse {
+		for entry in &mut self.entries {
+			if let (Some(chain_id), Some(tx_hash)) = (entry.chain_id, &entry.tx_hash) {
+				entry.tx_url = networks.transaction_url(chain_id, tx_hash);
+			}
+		}
+
+		let total_seconds = match (self.entries.first(), self.entries.last()) {
+			(Some(first), Some(last)) => last.timestamp.saturating_sub(first.timestamp),
+			_ => 0,
+		};
+		OrderTimelineResponse {
+			order_id: self.order_id,
+			milestones: self.entries,
+			total_seconds,
+		}
+	}
+
+	/// Records a milestone.
+	///
+	/// The fill of an order is reported both by delivery and settlement, so a
+	/// milestone repeating the previous one for the same transaction is
+	/// dropped.
+	fn push(
+		&mut self,
+		timestamp: u64,
+		milestone: Milestone,
+		tx: Option<&TransactionHash>,
+		failure: Option<String>,
+	) {
+		let tx_hash = tx.map(|tx_hash| format!("0x{}", hex::encode(&tx_hash.0)));
+		let chain_id = tx.and_then(|tx_hash| self.transactions.get(&tx_hash.0).copied());
+
+		let previous = self.entries.last();
+		if previous
+			.is_some_and(|previous| previous.milestone == milestone && previous.tx_hash == tx_hash)
+		{
+			return;
+		}
+
+		let duration_seconds = previous
+			.map(|previous| timestamp.saturating_sub(previous.timestamp))
+			.unwrap_or(0);
+		self.entries.push(TimelineEntry {
+			milestone,
+			timestamp,
+			duration_seconds,
+			chain_id,
+			tx_hash,
+			tx_url: None,
+			failure,
+		});
+	}
+}
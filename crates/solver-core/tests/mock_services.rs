@@ -0,0 +1,596 @@
+//! Runs the engine against the mock service implementations.
+//!
+//! Every service is mocked, so these tests exercise the engine's order
+//! lifecycle, from discovery through fill and claim, without any chain.
+//! Storage is real: solvers restarted over the storage fixtures read them
+//! back through every backend.
+
+use alloy_primitives::U256;
+use solver_account::implementations::mock::MockAccount;
+use solver_config::Config;
+use solver_core::{SolverBuilder, SolverEngine};
+use solver_delivery::implementations::mock::{MockChainHandle, MockDelivery};
+use solver_discovery::implementations::mock::{MockDiscovery, MockIntents};
+use solver_order::implementations::mock::{MockOrder, MockOrderData};
+use solver_order::implementations::strategies::simple::create_strategy;
+use solver_settlement::implementations::mock::MockSettlement;
+use solver_storage::fixtures::seed_from_path;
+use solver_storage::implementations::{file, memory, sqlite};
+use solver_storage::StorageInterface;
+use solver_types::testing::MockBehavior;
+use solver_types::{
+	Address, DeliveryEvent, DetailedIntentStatus, DiscoveryEvent, Intent, IntentMetadata,
+	SettlementEvent, SolverEvent, TokenAmount,
+};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+/// Chain the mock orders take their inputs on.
+const ORIGIN_CHAIN: u64 = 1;
+
+/// Chain the mock orders deliver their outputs on.
+const DESTINATION_CHAIN: u64 = 2;
+
+/// How long a test waits for the engine to publish an event.
+const EVENT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Storage backends the fixtures are loaded into.
+const BACKENDS: [&str; 3] = ["memory", "file", "sqlite"];
+
+/// Returns the path of a snapshot in the storage crate's fixtures.
+fn fixture(name: &str) -> PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR"))
+		.join("../solver-storage/fixtures")
+		.join(name)
+}
+
+/// Returns the current Unix timestamp in seconds.
+fn now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
+
+/// Wraps a service so that a factory hands it to the engine once.
+fn once<T>(service: T) -> impl Fn(&toml::Value) -> T {
+	let service = Mutex::new(Some(service));
+	move |_| {
+		service
+			.lock()
+			.unwrap()
+			.take()
+			.expect("Service built more than once")
+	}
+}
+
+/// Configuration of a solver using the mock services and the `backend`
+/// storage, keeping its files under `storage_path`.
+fn config(backend: &str, storage_path: &str) -> Config {
+	format!(
+		r#"
+		[solver]
+		id = "mock-solver"
+		monitoring_timeout_minutes = 1
+
+		[storage]
+		backend = "{backend}"
+		[storage.config]
+		storage_path = "{storage_path}"
+		database_path = "{storage_path}/solver.db"
+
+		[recovery]
+		checkpoint_file = "{storage_path}/checkpoint.json"
+
+		[account]
+		provider = "mock"
+		[account.config]
+
+		[delivery]
+		min_confirmations = 1
+		[delivery.providers.origin]
+		chain_id = {ORIGIN_CHAIN}
+		[delivery.providers.destination]
+		chain_id = {DESTINATION_CHAIN}
+
+		[discovery]
+		[discovery.sources.mock]
+
+		[order]
+		[order.implementations.mock]
+		[order.execution_strategy]
+		strategy_type = "simple"
+		[order.execution_strategy.config]
+		max_gas_price_gwei = 100
+
+		[settlement]
+		[settlement.implementations.mock]
+		"#
+	)
+	.parse()
+	.expect("Mock configuration is valid")
+}
+
+/// Solver wired to mock services, with handles on them.
+struct MockSolver {
+	engine: Arc<SolverEngine>,
+	intents: MockIntents,
+	origin: MockChainHandle,
+	destination: MockChainHandle,
+	destination_delivery: Arc<MockBehavior>,
+	settlement: Arc<MockBehavior>,
+	storage_path: std::path::PathBuf,
+}
+
+impl MockSolver {
+	/// Builds a solver discovering `intents` as soon as it runs, whose
+	/// settlement behaves as `settlement` describes.
+	fn new(name: &str, intents: Vec<Intent>, settlement: MockSettlement) -> Self {
+		let storage_path = storage_path(name);
+		let config = config("file", storage_path.to_str().unwrap());
+		let storage = file::create_storage(&config.storage.config);
+		Self::with_storage(storage_path, config, storage, intents, settlement)
+	}
+
+	/// Builds a solver over the `backend` storage seeded with the `snapshot`
+	/// fixture, restarting after a crash if `crashed` is set.
+	async fn seeded(
+		name: &str,
+		backend: &str,
+		snapshot: &str,
+		crashed: bool,
+		intents: Vec<Intent>,
+	) -> Self {
+		let storage_path = storage_path(&format!("{}-{}", name, backend));
+		let config = config(backend, storage_path.to_str().unwrap());
+		let storage = match backend {
+			"memory" => memory::create_storage(&config.storage.config),
+			"file" => file::create_storage(&config.storage.config),
+			"sqlite" => sqlite::create_storage(&config.storage.config),
+			_ => panic!("Unknown storage backend {}", backend),
+		};
+		seed_from_path(storage.as_ref(), &fixture(snapshot))
+			.await
+			.expect("Fixture seeds the storage");
+
+		if crashed {
+			// A run still marked in progress did not shut down
+			std::fs::create_dir_all(&storage_path).unwrap();
+			std::fs::write(
+				storage_path.join("checkpoint.json"),
+				r#"{"state":"running","started_at":1714571600}"#,
+			)
+			.unwrap();
+		}
+
+		Self::with_storage(
+			storage_path,
+			config,
+			storage,
+			intents,
+			MockSettlement::new(),
+		)
+	}
+
+	/// Builds a solver from `config` over `storage`, keeping its files under
+	/// `storage_path`.
+	fn with_storage(
+		storage_path: PathBuf,
+		config: Config,
+		storage: Box<dyn StorageInterface>,
+		intents: Vec<Intent>,
+		settlement: MockSettlement,
+	) -> Self {
+		let backend = config.storage.backend.clone();
+		let discovery = MockDiscovery::new().with_intents(intents);
+		let intents = discovery.intents();
+		let origin = MockDelivery::new(ORIGIN_CHAIN);
+		let origin_handle = origin.handle();
+		let destination = MockDelivery::new(DESTINATION_CHAIN);
+		let destination_handle = destination.handle();
+		let destination_behavior = destination.behavior();
+		let settlement_behavior = settlement.behavior();
+
+		let engine = SolverBuilder::new(config)
+			.with_storage_factory(&backend, once(storage))
+			.with_account_factory(|_| Box::new(MockAccount::default()))
+			.with_delivery_factory("origin", {
+				let origin = once(origin);
+				move |config| Box::new(origin(config))
+			})
+			.with_delivery_factory("destination", {
+				let destination = once(destination);
+				move |config| Box::new(destination(config))
+			})
+			.with_discovery_factory("mock", {
+				let discovery = once(discovery);
+				move |config| Box::new(discovery(config))
+			})
+			.with_order_factory("mock", |_| Box::new(MockOrder::default()))
+			.with_settlement_factory("mock", {
+				let settlement = once(settlement);
+				move |config| Box::new(settlement(config))
+			})
+			.with_strategy_factory("simple", create_strategy)
+			.build()
+			.expect("Mock solver builds");
+
+		Self {
+			engine: Arc::new(engine),
+			intents,
+			origin: origin_handle,
+			destination: destination_handle,
+			destination_delivery: destination_behavior,
+			settlement: settlement_behavior,
+			storage_path,
+		}
+	}
+
+	/// Starts the engine's event loop.
+	fn start(&self) -> tokio::task::JoinHandle<()> {
+		let engine = self.engine.clone();
+		tokio::spawn(async move {
+			engine.run().await.expect("Engine runs");
+		})
+	}
+}
+
+/// Returns a fresh directory for the files of the test `name`.
+fn storage_path(name: &str) -> PathBuf {
+	std::env::temp_dir().join(format!(
+		"solver-core-{}-{}-{}",
+		name,
+		std::process::id(),
+		now()
+	))
+}
+
+impl Drop for MockSolver {
+	fn drop(&mut self) {
+		let _ = std::fs::remove_dir_all(&self.storage_path);
+	}
+}
+
+/// Builds an intent of the mock standard moving 100 units across chains.
+fn intent(id: &str) -> Intent {
+	let token = Address(vec![0x70; 20]);
+	intent_with(
+		id,
+		MockOrderData {
+			inputs: vec![TokenAmount {
+				chain_id: ORIGIN_CHAIN,
+				token: token.clone(),
+				amount: U256::from(101u64),
+			}],
+			outputs: vec![TokenAmount {
+				chain_id: DESTINATION_CHAIN,
+				token,
+				amount: U256::from(100u64),
+			}],
+			fill_deadline: now() + 600,
+		},
+	)
+}
+
+/// Builds an intent of the mock standard carrying `data`.
+fn intent_with(id: &str, data: MockOrderData) -> Intent {
+	Intent {
+		id: id.to_string(),
+		source: "mock".to_string(),
+		standard: "mock".to_string(),
+		metadata: IntentMetadata {
+			requires_auction: false,
+			exclusive_until: None,
+			discovered_at: now(),
+			settler: None,
+			block_number: None,
+			transaction_hash: None,
+			custom: Default::default(),
+		},
+		data: serde_json::to_value(data).unwrap(),
+	}
+}
+
+/// Waits until the engine publishes an event matching `expected`.
+async fn wait_for(
+	events: &mut broadcast::Receiver<SolverEvent>,
+	expected: impl Fn(&SolverEvent) -> bool,
+) -> SolverEvent {
+	tokio::time::timeout(EVENT_TIMEOUT, async {
+		loop {
+			match events.recv().await {
+				Ok(event) if expected(&event) => return event,
+				Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+				Err(e) => panic!("Event bus closed: {}", e),
+			}
+		}
+	})
+	.await
+	.expect("Expected event is published in time")
+}
+
+/// Waits until the engine completes the settlement of an order.
+async fn completed(events: &mut broadcast::Receiver<SolverEvent>, order_id: &str) {
+	wait_for(events, |event| {
+		matches!(
+			event,
+			SolverEvent::Settlement(SettlementEvent::Completed { order_id: id }) if id == order_id
+		)
+	})
+	.await;
+}
+
+#[tokio::test]
+async fn fills_and_claims_discovered_order() {
+	let solver = MockSolver::new("fill-claim", vec![intent("order-1")], MockSettlement::new());
+	let mut events = solver.engine.event_bus().subscribe();
+	let engine = solver.start();
+
+	completed(&mut events, "order-1").await;
+	engine.abort();
+
+	// The fill lands on the destination chain and the claim on the origin
+	let fills = solver.destination.submitted();
+	assert_eq!(fills.len(), 1);
+	assert_eq!(fills[0].data, b"order-1".to_vec());
+	let claims = solver.origin.submitted();
+	assert_eq!(claims.len(), 1);
+	assert!(claims[0].data.starts_with(b"order-1"));
+	assert_eq!(solver.settlement.calls("get_attestation"), 1);
+}
+
+#[tokio::test]
+async fn retries_fill_after_transient_submit_failure() {
+	let solver = MockSolver::new(
+		"submit-retry",
+		vec![intent("order-1")],
+		MockSettlement::new(),
+	);
+	solver
+		.destination_delivery
+		.fail_next("submit", "Connection reset");
+	let mut events = solver.engine.event_bus().subscribe();
+	let engine = solver.start();
+
+	completed(&mut events, "order-1").await;
+	engine.abort();
+
+	assert_eq!(solver.destination_delivery.calls("submit"), 2);
+	assert_eq!(solver.destination.submitted().len(), 1);
+	assert_eq!(solver.origin.submitted().len(), 1);
+}
+
+#[tokio::test]
+async fn waits_for_settlement_before_claiming() {
+	let settlement = MockSettlement::new();
+	settlement
+		.behavior()
+		.fail_times("can_claim", 2, "Dispute period");
+	let solver = MockSolver::new("claim-wait", vec![intent("order-1")], settlement);
+	let mut events = solver.engine.event_bus().subscribe();
+	let engine = solver.start();
+
+	completed(&mut events, "order-1").await;
+	engine.abort();
+
+	assert!(solver.settlement.calls("can_claim") >= 3);
+	assert_eq!(solver.origin.submitted().len(), 1);
+}
+
+#[tokio::test]
+async fn rejects_invalid_intent_without_filling() {
+	let invalid = intent_with(
+		"order-1",
+		MockOrderData {
+			inputs: Vec::new(),
+			outputs: Vec::new(),
+			fill_deadline: now() + 600,
+		},
+	);
+	let solver = MockSolver::new("invalid", vec![invalid], MockSettlement::new());
+	let mut events = solver.engine.event_bus().subscribe();
+	let engine = solver.start();
+
+	wait_for(&mut events, |event| {
+		matches!(
+			event,
+			SolverEvent::Discovery(DiscoveryEvent::IntentRejected { intent_id, .. })
+				if intent_id == "order-1"
+		)
+	})
+	.await;
+	engine.abort();
+
+	assert!(solver.destination.submitted().is_empty());
+	assert!(solver.origin.submitted().is_empty());
+}
+
+#[tokio::test]
+async fn does_not_claim_reverted_fill() {
+	let solver = MockSolver::new("revert", vec![intent("order-1")], MockSettlement::new());
+	solver.destination.revert_next(1);
+	let mut events = solver.engine.event_bus().subscribe();
+	let engine = solver.start();
+
+	wait_for(&mut events, |event| {
+		matches!(
+			event,
+			SolverEvent::Delivery(DeliveryEvent::TransactionFailed { .. })
+		)
+	})
+	.await;
+	engine.abort();
+
+	assert_eq!(solver.destination.submitted().len(), 1);
+	assert!(solver.origin.submitted().is_empty());
+	assert_eq!(solver.settlement.calls("get_attestation"), 0);
+}
+
+#[tokio::test]
+async fn refills_redriven_dead_letter() {
+	let solver = MockSolver::new("redrive", vec![intent("order-1")], MockSettlement::new());
+	solver.destination.revert_next(1);
+	let mut events = solver.engine.event_bus().subscribe();
+	let engine = solver.start();
+
+	wait_for(&mut events, |event| {
+		matches!(
+			event,
+			SolverEvent::Delivery(DeliveryEvent::TransactionFailed { .. })
+		)
+	})
+	.await;
+	// The failed fill is dead-lettered once the event is handled
+	tokio::time::timeout(EVENT_TIMEOUT, async {
+		while solver.engine.dead_letters().await.unwrap().is_empty() {
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	})
+	.await
+	.expect("Failed fill is dead-lettered in time");
+
+	assert!(solver
+		.engine
+		.redrive_dead_letter("order-1")
+		.await
+		.unwrap()
+		.is_some());
+	completed(&mut events, "order-1").await;
+	engine.abort();
+
+	assert_eq!(solver.destination.submitted().len(), 2);
+	assert_eq!(solver.origin.submitted().len(), 1);
+}
+
+#[tokio::test]
+async fn handles_intents_discovered_while_running() {
+	let solver = MockSolver::new("running", vec![intent("order-1")], MockSettlement::new());
+	let mut events = solver.engine.event_bus().subscribe();
+	let engine = solver.start();
+
+	completed(&mut events, "order-1").await;
+	solver.intents.emit(intent("order-2")).await.unwrap();
+	completed(&mut events, "order-2").await;
+	engine.abort();
+
+	let fills = solver.destination.submitted();
+	assert_eq!(fills.len(), 2);
+	assert_eq!(fills[1].data, b"order-2".to_vec());
+	assert_eq!(solver.origin.submitted().len(), 2);
+}
+
+#[tokio::test]
+async fn serves_seeded_orders_from_every_backend() {
+	let claiming = "44".repeat(32);
+	let completed = "55".repeat(32);
+	for backend in BACKENDS {
+		let solver = MockSolver::seeded("settled", backend, "settled.json", false, vec![]).await;
+
+		let status = solver
+			.engine
+			.order_status(&claiming)
+			.await
+			.unwrap()
+			.expect("Seeded order is found");
+		assert!(matches!(status.status, DetailedIntentStatus::Claiming));
+		assert_eq!(status.fill_tx_hash, Some(format!("0x{}", "dd".repeat(32))));
+		assert_eq!(status.claim_tx_hash, Some(format!("0x{}", "d1".repeat(32))));
+		assert_eq!(status.fill_proof.unwrap().block_number, 110);
+
+		// Orders are listed in the order they were validated
+		let page = solver.engine.list_orders(None, 10).await.unwrap();
+		let listed: Vec<_> = page.items.iter().map(|order| order.id.clone()).collect();
+		assert_eq!(
+			listed,
+			vec![completed.clone(), claiming.clone()],
+			"{}",
+			backend
+		);
+
+		let open = solver.engine.open_orders().await.unwrap();
+		assert_eq!(open.len(), 1, "{}", backend);
+		assert_eq!(open[0].0, claiming);
+	}
+}
+
+#[tokio::test]
+async fn reports_seeded_registered_and_failed_orders() {
+	for backend in BACKENDS {
+		let solver =
+			MockSolver::seeded("registered", backend, "registered.json", false, vec![]).await;
+		let status = solver
+			.engine
+			.order_status(&"33".repeat(32))
+			.await
+			.unwrap()
+			.expect("Seeded order is found");
+		// Orders without a recorded status have only been validated
+		assert!(matches!(status.status, DetailedIntentStatus::Registered));
+		assert!(status.fill_tx_hash.is_none());
+
+		let solver = MockSolver::seeded("failed", backend, "failed.json", false, vec![]).await;
+		let status = solver
+			.engine
+			.order_status(&"77".repeat(32))
+			.await
+			.unwrap()
+			.expect("Seeded order is found");
+		assert!(matches!(status.status, DetailedIntentStatus::Failed));
+		assert!(status.claim_tx_hash.is_none());
+		assert!(solver.engine.open_orders().await.unwrap().is_empty());
+	}
+}
+
+#[tokio::test]
+async fn recovers_in_flight_orders_after_crash() {
+	for backend in BACKENDS {
+		let solver = MockSolver::seeded(
+			"crash",
+			backend,
+			"in_flight.json",
+			true,
+			vec![intent("order-3")],
+		)
+		.await;
+		let mut events = solver.engine.event_bus().subscribe();
+		let engine = solver.start();
+
+		completed(&mut events, "order-3").await;
+		// The completion is recorded once the event is handled
+		tokio::time::timeout(EVENT_TIMEOUT, async {
+			while !matches!(
+				solver.engine.order_status("order-3").await.unwrap(),
+				Some(status) if matches!(status.status, DetailedIntentStatus::Completed)
+			) {
+				tokio::time::sleep(Duration::from_millis(10)).await;
+			}
+		})
+		.await
+		.expect("Completion is recorded in time");
+		engine.abort();
+
+		// Orders the crashed run left mid-flight are still open
+		let mut open: Vec<_> = solver
+			.engine
+			.open_orders()
+			.await
+			.unwrap()
+			.into_iter()
+			.map(|(order_id, _)| order_id)
+			.collect();
+		open.sort();
+		assert_eq!(open, vec!["11".repeat(32), "22".repeat(32)], "{}", backend);
+
+		// New orders are sequenced after the ones stored before the crash
+		let page = solver.engine.list_orders(None, 10).await.unwrap();
+		let listed: Vec<_> = page.items.iter().map(|order| order.id.clone()).collect();
+		assert_eq!(
+			listed,
+			vec!["22".repeat(32), "11".repeat(32), "order-3".to_string()],
+			"{}",
+			backend
+		);
+	}
+}
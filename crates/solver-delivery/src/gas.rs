@@ -0,0 +1,104 @@
+//! Gas fee estimation for transaction delivery.
+//!
+//! Estimators derive the fees a transaction should pay from the chain's recent
+//! fee market. They are configured per chain, so chains with different fee
+//! markets or without EIP-1559 support can each use a suitable strategy.
+
+use crate::{DeliveryError, DeliveryInterface};
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_types::GasParams;
+
+/// Trait defining the interface for gas fee estimators.
+#[async_trait]
+pub trait GasEstimator: Send + Sync {
+	/// Estimates fee parameters for a transaction submitted through a provider.
+	async fn estimate(&self, provider: &dyn DeliveryInterface) -> Result<GasParams, DeliveryError>;
+}
+
+/// Fee model of the transactions an estimator prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeMode {
+	/// A single gas price.
+	Legacy,
+	/// A max fee and a max priority fee per gas.
+	Eip1559,
+}
+
+/// Estimates fees from percentiles of recently paid priority fees.
+///
+/// The priority fee is the median, across recent blocks, of the configured
+/// percentile of priority fees paid in each block. EIP-1559 max fees leave
+/// headroom above the next block's base fee, since the base fee can rise
+/// before the transaction is mined; legacy prices pay the next block's base
+/// fee plus the priority fee.
+pub struct PercentileGasEstimator {
+	/// Fee model of the estimated parameters.
+	mode: FeeMode,
+	/// Number of recent blocks to sample.
+	blocks: u64,
+	/// Percentile of priority fees paid within each block.
+	percentile: f64,
+	/// Multiplier applied to the next block's base fee, in basis points.
+	base_fee_multiplier_bps: u64,
+}
+
+impl PercentileGasEstimator {
+	/// Creates a new PercentileGasEstimator.
+	pub fn new(mode: FeeMode, blocks: u64, percentile: f64, base_fee_multiplier_bps: u64) -> Self {
+		Self {
+			mode,
+			blocks,
+			percentile,
+			base_fee_multiplier_bps,
+		}
+	}
+}
+
+#[async_trait]
+impl GasEstimator for PercentileGasEstimator {
+	async fn estimate(&self, provider: &dyn DeliveryInterface) -> Result<GasParams, DeliveryError> {
+		let history = provider
+			.fee_history(self.blocks, &[self.percentile])
+			.await?;
+
+		// Chains without a base fee predate EIP-1559 and only report gas prices
+		let base_fee = history.base_fee_per_gas.last().copied().unwrap_or_default();
+		if base_fee.is_zero() {
+			return Ok(GasParams {
+				gas_price: Some(provider.get_gas_price().await?),
+				..Default::default()
+			});
+		}
+
+		// Empty blocks report zero priority fees and would drag the estimate down
+		let mut priority_fees: Vec<U256> = history
+			.priority_fees
+			.iter()
+			.filter_map(|fees| fees.first().copied())
+			.filter(|fee| !fee.is_zero())
+			.collect();
+		priority_fees.sort();
+		let priority_fee = priority_fees
+			.get(priority_fees.len() / 2)
+			.copied()
+			.unwrap_or_default();
+
+		Ok(match self.mode {
+			FeeMode::Legacy => GasParams {
+				gas_price: Some(base_fee.saturating_add(priority_fee)),
+				..Default::default()
+			},
+			FeeMode::Eip1559 => {
+				let max_base_fee = base_fee
+					.saturating_mul(U256::from(self.base_fee_multiplier_bps))
+					/ U256::from(10_000u64);
+				GasParams {
+					gas_price: None,
+					max_fee_per_gas: Some(max_base_fee.saturating_add(priority_fee)),
+					max_priority_fee_per_gas: Some(priority_fee),
+				}
+			}
+		})
+	}
+}
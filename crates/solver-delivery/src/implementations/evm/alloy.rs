@@ -2,21 +2,45 @@
 //!
 //! This module provides concrete implementations of the DeliveryInterface trait,
 //! supporting blockchain transaction submission and monitoring using the Alloy library.
+//! Each chain may be served by several RPC endpoints, in which case requests fail
+//! over to the next endpoint when one times out or stops responding.
 
 use crate::{DeliveryError, DeliveryInterface};
+use alloy_consensus::Transaction as _;
 use alloy_network::EthereumWallet;
-use alloy_primitives::FixedBytes;
+use alloy_primitives::{Address as AlloyAddress, FixedBytes, TxKind, U256};
 use alloy_provider::{Provider, ProviderBuilder};
-use alloy_rpc_types::TransactionRequest;
+use alloy_rpc_types::{
+	BlockNumberOrTag, BlockTransactionsKind, Transaction as RpcTransaction,
+	TransactionReceipt as RpcTransactionReceipt, TransactionRequest,
+};
 use alloy_signer::Signer;
 use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::decode_revert_reason;
+use alloy_transport::{TransportErrorKind, TransportResult};
 use alloy_transport_http::Http;
 use async_trait::async_trait;
 use solver_types::{
-	ConfigSchema, Field, FieldType, Schema, Signature, Transaction as SolverTransaction,
-	TransactionHash, TransactionReceipt,
+	Address, ConfigSchema, EndpointFailure, FeeHistory, Field, FieldType, GasParams, Schema,
+	Signature, Transaction as SolverTransaction, TransactionHash, TransactionLog,
+	TransactionReceipt,
 };
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Minimum fee increase, in basis points, nodes require to accept a replacement.
+const MIN_REPLACEMENT_BUMP_BPS: u128 = 1_000;
+
+/// Gas limit of a plain transfer, used for cancellations.
+const TRANSFER_GAS_LIMIT: u64 = 21_000;
+
+/// Time a single RPC endpoint has to answer a request before failing over.
+const ENDPOINT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Provider connected to a single RPC endpoint.
+pub(crate) type HttpProvider = Arc<dyn Provider<Http<reqwest::Client>> + Send + Sync>;
 
 /// Utility function to truncate a transaction hash for display.
 fn truncate_hash(hash: &TransactionHash) -> String {
@@ -28,14 +52,68 @@ fn truncate_hash(hash: &TransactionHash) -> String {
 	}
 }
 
+/// Returns the lowest fee a replacement of a transaction paying `fee` may pay.
+fn min_replacement_fee(fee: u128) -> u128 {
+	fee.saturating_mul(10_000 + MIN_REPLACEMENT_BUMP_BPS)
+		.div_ceil(10_000)
+}
+
+/// Returns the higher of the minimum replacement fee and a requested fee.
+fn replacement_fee(original: u128, requested: Option<U256>) -> u128 {
+	let requested = requested.map_or(0, |fee| u128::try_from(fee).unwrap_or(u128::MAX));
+	min_replacement_fee(original).max(requested)
+}
+
+/// Converts the logs of an RPC receipt.
+fn receipt_logs(receipt: &RpcTransactionReceipt) -> Vec<TransactionLog> {
+	receipt
+		.inner
+		.logs()
+		.iter()
+		.map(|log| TransactionLog {
+			address: Address(log.address().to_vec()),
+			topics: log.topics().iter().map(|topic| topic.to_vec()).collect(),
+			data: log.data().data.to_vec(),
+		})
+		.collect()
+}
+
+/// Returns the scheme and host of an RPC URL, dropping paths and query
+/// strings that commonly carry API keys.
+fn endpoint_label(url: &reqwest::Url) -> String {
+	match url.port() {
+		Some(port) => format!(
+			"{}://{}:{}",
+			url.scheme(),
+			url.host_str().unwrap_or_default(),
+			port
+		),
+		None => format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default()),
+	}
+}
+
+/// An RPC endpoint and its health.
+struct Endpoint {
+	/// Scheme and host of the endpoint, safe to log.
+	label: String,
+	/// Provider connected to the endpoint.
+	provider: HttpProvider,
+	/// Error that marked the endpoint unhealthy, or `None` while healthy.
+	failure: Mutex<Option<String>>,
+}
+
 /// Alloy-based EVM delivery implementation.
 ///
 /// This implementation uses the Alloy library to submit and monitor transactions
 /// on EVM-compatible blockchains. It handles transaction signing, submission,
 /// and confirmation tracking.
 pub struct AlloyDelivery {
-	/// The Alloy provider for blockchain interaction.
-	provider: Arc<dyn Provider<Http<reqwest::Client>> + Send + Sync>,
+	/// RPC endpoints of the chain, in order of preference.
+	endpoints: Vec<Endpoint>,
+	/// Index of the endpoint requests are sent to first.
+	active: AtomicUsize,
+	/// Endpoints that became unhealthy since the last health check.
+	degraded: Mutex<Vec<EndpointFailure>>,
 	/// The chain ID this delivery service is configured for.
 	_chain_id: u64,
 }
@@ -43,33 +121,213 @@ pub struct AlloyDelivery {
 impl AlloyDelivery {
 	/// Creates a new AlloyDelivery instance.
 	///
-	/// Configures an Alloy provider with the specified RPC URL and signer
-	/// for transaction submission on the given chain.
+	/// Configures an Alloy provider for each of the specified RPC URLs, in
+	/// order of preference, with the signer for transaction submission on the
+	/// given chain.
 	pub async fn new(
-		rpc_url: &str,
+		rpc_urls: &[String],
 		chain_id: u64,
 		mut signer: PrivateKeySigner,
 	) -> Result<Self, DeliveryError> {
-		// Create provider with wallet for automatic signing
-		let url = rpc_url
-			.parse()
-			.map_err(|e| DeliveryError::Network(format!("Invalid RPC URL: {}", e)))?;
+		if rpc_urls.is_empty() {
+			return Err(DeliveryError::Network("No RPC URL configured".to_string()));
+		}
 
 		// Set the chain ID on the signer
 		signer = signer.with_chain_id(Some(chain_id));
 
 		let wallet = EthereumWallet::from(signer);
 
-		let provider = ProviderBuilder::new()
-			.with_recommended_fillers()
-			.wallet(wallet)
-			.on_http(url);
+		let mut endpoints = Vec::with_capacity(rpc_urls.len());
+		for rpc_url in rpc_urls {
+			let url: reqwest::Url = rpc_url
+				.parse()
+				.map_err(|e| DeliveryError::Network(format!("Invalid RPC URL: {}", e)))?;
+
+			// Create provider with wallet for automatic signing
+			let provider = ProviderBuilder::new()
+				.with_recommended_fillers()
+				.wallet(wallet.clone())
+				.on_http(url.clone());
+
+			endpoints.push(Endpoint {
+				label: endpoint_label(&url),
+				provider: Arc::new(provider),
+				failure: Mutex::new(None),
+			});
+		}
 
 		Ok(Self {
-			provider: Arc::new(provider),
+			endpoints,
+			active: AtomicUsize::new(0),
+			degraded: Mutex::new(Vec::new()),
 			_chain_id: chain_id,
 		})
 	}
+
+	/// Returns the provider of the endpoint requests are currently sent to.
+	pub(crate) fn provider(&self) -> HttpProvider {
+		self.endpoints[self.active.load(Ordering::Relaxed)]
+			.provider
+			.clone()
+	}
+
+	/// Sends a request to the active endpoint, failing over to the others in
+	/// turn if it times out or the endpoint cannot be reached.
+	///
+	/// Error responses from a node, such as reverted calls, are returned
+	/// without failing over. The endpoint that answers becomes the active one.
+	pub(crate) async fn with_failover<T, F, Fut>(&self, request: F) -> TransportResult<T>
+	where
+		F: Fn(HttpProvider) -> Fut,
+		Fut: Future<Output = TransportResult<T>>,
+	{
+		let start = self.active.load(Ordering::Relaxed);
+		let mut last_error = None;
+		for offset in 0..self.endpoints.len() {
+			let index = (start + offset) % self.endpoints.len();
+			let endpoint = &self.endpoints[index];
+			let result =
+				match tokio::time::timeout(ENDPOINT_TIMEOUT, request(endpoint.provider.clone()))
+					.await
+				{
+					Ok(result) => result,
+					Err(_) => Err(TransportErrorKind::custom_str("request timed out")),
+				};
+
+			match result {
+				Err(e) if !e.is_error_resp() => {
+					self.mark_unhealthy(index, e.to_string());
+					last_error = Some(e);
+				}
+				result => {
+					if index != start {
+						self.active.store(index, Ordering::Relaxed);
+						tracing::info!(endpoint = %endpoint.label, "Failed over to RPC endpoint");
+					}
+					return result;
+				}
+			}
+		}
+
+		Err(last_error.expect("at least one endpoint is configured"))
+	}
+
+	/// Marks an endpoint unhealthy, queueing it to be reported if it was
+	/// healthy until now.
+	fn mark_unhealthy(&self, index: usize, reason: String) {
+		let endpoint = &self.endpoints[index];
+		let mut failure = endpoint.failure.lock().unwrap();
+		if failure.is_none() {
+			tracing::warn!(endpoint = %endpoint.label, error = %reason, "RPC endpoint failed");
+			self.degraded.lock().unwrap().push(EndpointFailure {
+				endpoint: endpoint.label.clone(),
+				reason: reason.clone(),
+			});
+		}
+		*failure = Some(reason);
+	}
+
+	/// Fetches a transaction that has not been mined yet.
+	async fn pending_transaction(
+		&self,
+		hash: &TransactionHash,
+	) -> Result<RpcTransaction, DeliveryError> {
+		let tx_hash = FixedBytes::<32>::from_slice(&hash.0);
+
+		let tx = self
+			.with_failover(
+				|provider| async move { provider.get_transaction_by_hash(tx_hash).await },
+			)
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get transaction: {}", e)))?
+			.ok_or_else(|| DeliveryError::Network("Transaction not found".to_string()))?;
+
+		if tx.block_hash.is_some() {
+			return Err(DeliveryError::TransactionFailed(
+				"Transaction already mined".to_string(),
+			));
+		}
+		Ok(tx)
+	}
+
+	/// Builds a request replacing the original transaction's fees.
+	///
+	/// Keeps the original fee model, so legacy transactions are replaced with
+	/// legacy transactions and EIP-1559 transactions with EIP-1559 transactions.
+	fn with_replacement_fees(
+		mut request: TransactionRequest,
+		original: &RpcTransaction,
+		gas: &GasParams,
+	) -> TransactionRequest {
+		match original.inner.gas_price() {
+			Some(gas_price) => {
+				request.gas_price = Some(replacement_fee(gas_price, gas.gas_price));
+			}
+			None => {
+				let max_fee =
+					replacement_fee(original.inner.max_fee_per_gas(), gas.max_fee_per_gas);
+				let priority_fee = replacement_fee(
+					original
+						.inner
+						.max_priority_fee_per_gas()
+						.unwrap_or_default(),
+					gas.max_priority_fee_per_gas,
+				);
+				request.max_fee_per_gas = Some(max_fee);
+				request.max_priority_fee_per_gas = Some(priority_fee.min(max_fee));
+			}
+		}
+		request
+	}
+
+	/// Traces a reverting call with `debug_traceCall` to recover its revert
+	/// reason, for nodes whose `eth_call` errors carry no revert data.
+	///
+	/// Returns `None` if the node does not expose the debug namespace.
+	async fn trace_revert_reason(&self, request: &TransactionRequest) -> Option<String> {
+		let params = serde_json::value::to_raw_value(&(
+			request,
+			"latest",
+			serde_json::json!({ "tracer": "callTracer" }),
+		))
+		.ok()?;
+		let trace = self
+			.with_failover(|provider| {
+				let params = params.clone();
+				async move {
+					provider
+						.raw_request_dyn("debug_traceCall".into(), &params)
+						.await
+				}
+			})
+			.await
+			.ok()?;
+		let trace: serde_json::Value = serde_json::from_str(trace.get()).ok()?;
+
+		let field = |name: &str| trace.get(name).and_then(|value| value.as_str());
+		field("output")
+			.and_then(|output| hex::decode(output.trim_start_matches("0x")).ok())
+			.and_then(|output| decode_revert_reason(&output))
+			.or_else(|| field("revertReason").map(str::to_string))
+			.or_else(|| field("error").map(str::to_string))
+	}
+
+	/// Sends a request and returns its transaction hash.
+	async fn send(&self, request: TransactionRequest) -> Result<TransactionHash, DeliveryError> {
+		let tx_hash = self
+			.with_failover(|provider| {
+				let request = request.clone();
+				async move {
+					let pending_tx = provider.send_transaction(request).await?;
+					Ok(*pending_tx.tx_hash())
+				}
+			})
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to send transaction: {}", e)))?;
+
+		Ok(TransactionHash(tx_hash.0.to_vec()))
+	}
 }
 
 /// Configuration schema for Alloy delivery provider.
@@ -111,7 +369,19 @@ impl ConfigSchema for AlloyDeliverySchema {
 				),
 			],
 			// Optional fields
-			vec![],
+			vec![Field::new(
+				"fallback_rpc_urls",
+				FieldType::Array(Box::new(FieldType::String)),
+			)
+			.with_validator(|value| {
+				for url in value.as_array().unwrap() {
+					let url = url.as_str().unwrap_or_default();
+					if !url.starts_with("http://") && !url.starts_with("https://") {
+						return Err("RPC URLs must start with http:// or https://".to_string());
+					}
+				}
+				Ok(())
+			})],
 		);
 
 		schema.validate(config)
@@ -133,13 +403,17 @@ impl DeliveryInterface for AlloyDelivery {
 		let request: TransactionRequest = tx.into();
 
 		// Send transaction - the provider's wallet will handle signing
-		let pending_tx =
-			self.provider.send_transaction(request).await.map_err(|e| {
-				DeliveryError::Network(format!("Failed to send transaction: {}", e))
-			})?;
+		let tx_hash = self
+			.with_failover(|provider| {
+				let request = request.clone();
+				async move {
+					let pending_tx = provider.send_transaction(request).await?;
+					Ok(*pending_tx.tx_hash())
+				}
+			})
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to send transaction: {}", e)))?;
 
-		// Get the transaction hash
-		let tx_hash = *pending_tx.tx_hash();
 		let hash_str = hex::encode(tx_hash.0);
 		let truncated = if hash_str.len() <= 8 {
 			hash_str.clone()
@@ -188,7 +462,12 @@ impl DeliveryInterface for AlloyDelivery {
 			}
 
 			// Get transaction receipt
-			let receipt = match self.provider.get_transaction_receipt(tx_hash).await {
+			let receipt = match self
+				.with_failover(
+					|provider| async move { provider.get_transaction_receipt(tx_hash).await },
+				)
+				.await
+			{
 				Ok(Some(receipt)) => receipt,
 				Ok(None) => {
 					// Transaction not yet mined, wait and retry
@@ -204,9 +483,7 @@ impl DeliveryInterface for AlloyDelivery {
 			};
 
 			// Get current block number
-			let current_block = self.provider.get_block_number().await.map_err(|e| {
-				DeliveryError::Network(format!("Failed to get block number: {}", e))
-			})?;
+			let current_block = self.get_block_number().await?;
 
 			let tx_block = receipt.block_number.unwrap_or(0);
 			let current_confirmations = current_block.saturating_sub(tx_block);
@@ -217,6 +494,9 @@ impl DeliveryInterface for AlloyDelivery {
 					hash: TransactionHash(receipt.transaction_hash.0.to_vec()),
 					block_number: tx_block,
 					success: receipt.status(),
+					gas_used: receipt.gas_used as u64,
+					effective_gas_price: U256::from(receipt.effective_gas_price),
+					logs: receipt_logs(&receipt),
 				});
 			}
 
@@ -237,8 +517,9 @@ impl DeliveryInterface for AlloyDelivery {
 		let tx_hash = FixedBytes::<32>::from_slice(&hash.0);
 
 		let receipt = self
-			.provider
-			.get_transaction_receipt(tx_hash)
+			.with_failover(
+				|provider| async move { provider.get_transaction_receipt(tx_hash).await },
+			)
 			.await
 			.map_err(|e| DeliveryError::Network(format!("Failed to get receipt: {}", e)))?
 			.ok_or_else(|| DeliveryError::Network("Transaction not found".to_string()))?;
@@ -247,8 +528,241 @@ impl DeliveryInterface for AlloyDelivery {
 			hash: TransactionHash(receipt.transaction_hash.0.to_vec()),
 			block_number: receipt.block_number.unwrap_or(0),
 			success: receipt.status(),
+			gas_used: receipt.gas_used as u64,
+			effective_gas_price: U256::from(receipt.effective_gas_price),
+			logs: receipt_logs(&receipt),
+		})
+	}
+
+	async fn replace(
+		&self,
+		hash: &TransactionHash,
+		gas: GasParams,
+	) -> Result<TransactionHash, DeliveryError> {
+		let original = self.pending_transaction(hash).await?;
+
+		let mut request = original.clone().into_request();
+		request.from = Some(original.from);
+		let request = Self::with_replacement_fees(request, &original, &gas);
+
+		let replacement = self.send(request).await?;
+		tracing::info!(
+			tx_hash = %truncate_hash(hash),
+			replacement = %truncate_hash(&replacement),
+			"Replaced transaction"
+		);
+		Ok(replacement)
+	}
+
+	async fn cancel(&self, hash: &TransactionHash) -> Result<TransactionHash, DeliveryError> {
+		let original = self.pending_transaction(hash).await?;
+
+		// A zero-value transfer to self consumes the nonce without side effects
+		let request = TransactionRequest {
+			from: Some(original.from),
+			to: Some(TxKind::Call(original.from)),
+			value: Some(U256::ZERO),
+			nonce: Some(original.inner.nonce()),
+			gas: Some(TRANSFER_GAS_LIMIT),
+			..Default::default()
+		};
+		let request = Self::with_replacement_fees(request, &original, &GasParams::default());
+
+		let cancellation = self.send(request).await?;
+		tracing::info!(
+			tx_hash = %truncate_hash(hash),
+			cancellation = %truncate_hash(&cancellation),
+			"Cancelled transaction"
+		);
+		Ok(cancellation)
+	}
+
+	async fn call(&self, tx: &SolverTransaction) -> Result<Vec<u8>, DeliveryError> {
+		let request: TransactionRequest = tx.clone().into();
+
+		let result = self
+			.with_failover(|provider| {
+				let request = request.clone();
+				async move { provider.call(&request).await }
+			})
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to execute call: {}", e)))?;
+
+		Ok(result.to_vec())
+	}
+
+	async fn simulate(&self, tx: &SolverTransaction, from: &Address) -> Result<(), DeliveryError> {
+		let mut request: TransactionRequest = tx.clone().into();
+		request.from = Some(AlloyAddress::from_slice(&from.0));
+
+		let error = match self
+			.with_failover(|provider| {
+				let request = request.clone();
+				async move { provider.call(&request).await }
+			})
+			.await
+		{
+			Ok(_) => return Ok(()),
+			Err(e) => e,
+		};
+
+		// Only error responses from the node mean the transaction would fail
+		let Some(payload) = error.as_error_resp() else {
+			return Err(DeliveryError::Network(format!(
+				"Failed to simulate transaction: {}",
+				error
+			)));
+		};
+		let reason = match payload
+			.as_revert_data()
+			.and_then(|data| decode_revert_reason(&data))
+		{
+			Some(reason) => reason,
+			None => self
+				.trace_revert_reason(&request)
+				.await
+				.unwrap_or_else(|| payload.message.to_string()),
+		};
+		Err(DeliveryError::SimulationFailed(reason))
+	}
+
+	async fn get_gas_price(&self) -> Result<U256, DeliveryError> {
+		let gas_price = self
+			.with_failover(|provider| async move { provider.get_gas_price().await })
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get gas price: {}", e)))?;
+
+		Ok(U256::from(gas_price))
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		let history = self
+			.with_failover(|provider| async move {
+				provider
+					.get_fee_history(block_count, BlockNumberOrTag::Latest, reward_percentiles)
+					.await
+			})
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get fee history: {}", e)))?;
+
+		Ok(FeeHistory {
+			base_fee_per_gas: history
+				.base_fee_per_gas
+				.into_iter()
+				.map(U256::from)
+				.collect(),
+			priority_fees: history
+				.reward
+				.unwrap_or_default()
+				.into_iter()
+				.map(|fees| fees.into_iter().map(U256::from).collect())
+				.collect(),
 		})
 	}
+
+	async fn get_native_balance(&self, address: &Address) -> Result<U256, DeliveryError> {
+		if address.0.len() != 20 {
+			return Err(DeliveryError::Network(format!(
+				"Invalid address length: {}",
+				address.0.len()
+			)));
+		}
+
+		let address = AlloyAddress::from_slice(&address.0);
+		self.with_failover(|provider| async move { provider.get_balance(address).await })
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get balance: {}", e)))
+	}
+
+	async fn get_code(&self, address: &Address) -> Result<Vec<u8>, DeliveryError> {
+		if address.0.len() != 20 {
+			return Err(DeliveryError::Network(format!(
+				"Invalid address length: {}",
+				address.0.len()
+			)));
+		}
+
+		let address = AlloyAddress::from_slice(&address.0);
+		let code = self
+			.with_failover(|provider| async move { provider.get_code_at(address).await })
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get code: {}", e)))?;
+
+		Ok(code.to_vec())
+	}
+
+	async fn get_block_number(&self) -> Result<u64, DeliveryError> {
+		self.with_failover(|provider| async move { provider.get_block_number().await })
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get block number: {}", e)))
+	}
+
+	async fn get_block_hash(&self, number: u64) -> Result<Option<Vec<u8>>, DeliveryError> {
+		let block = self
+			.with_failover(|provider| async move {
+				provider
+					.get_block_by_number(
+						BlockNumberOrTag::Number(number),
+						BlockTransactionsKind::Hashes,
+					)
+					.await
+			})
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get block: {}", e)))?;
+
+		Ok(block.map(|block| block.header.hash.to_vec()))
+	}
+
+	async fn check_endpoints(&self) -> Vec<EndpointFailure> {
+		for (index, endpoint) in self.endpoints.iter().enumerate() {
+			let probe = async { endpoint.provider.get_block_number().await };
+			match tokio::time::timeout(ENDPOINT_TIMEOUT, probe).await {
+				Ok(Ok(_)) => {
+					if endpoint.failure.lock().unwrap().take().is_some() {
+						tracing::info!(endpoint = %endpoint.label, "RPC endpoint recovered");
+					}
+				}
+				Ok(Err(e)) => self.mark_unhealthy(index, e.to_string()),
+				Err(_) => self.mark_unhealthy(index, "request timed out".to_string()),
+			}
+		}
+
+		// Return to the most preferred endpoint that is healthy again
+		if let Some(index) = self
+			.endpoints
+			.iter()
+			.position(|endpoint| endpoint.failure.lock().unwrap().is_none())
+		{
+			self.active.store(index, Ordering::Relaxed);
+		}
+
+		std::mem::take(&mut *self.degraded.lock().unwrap())
+	}
+}
+
+/// Returns the RPC URLs of a delivery provider's configuration, the primary
+/// `rpc_url` first, followed by any `fallback_rpc_urls`.
+pub(crate) fn rpc_urls(config: &toml::Value) -> Vec<String> {
+	let rpc_url = config
+		.get("rpc_url")
+		.and_then(|v| v.as_str())
+		.expect("rpc_url is required");
+
+	let fallbacks = config
+		.get("fallback_rpc_urls")
+		.and_then(|v| v.as_array())
+		.into_iter()
+		.flatten()
+		.filter_map(|v| v.as_str());
+
+	std::iter::once(rpc_url)
+		.chain(fallbacks)
+		.map(str::to_string)
+		.collect()
 }
 
 /// Factory function to create an HTTP-based delivery provider from configuration.
@@ -258,11 +772,11 @@ impl DeliveryInterface for AlloyDelivery {
 /// - `rpc_url`: The HTTP RPC endpoint URL
 /// - `chain_id`: The blockchain network chain ID
 /// - `private_key`: The private key for transaction signing
+///
+/// Optional configuration parameters:
+/// - `fallback_rpc_urls`: Further RPC endpoints to fail over to, in order
 pub fn create_http_delivery(config: &toml::Value) -> Box<dyn DeliveryInterface> {
-	let rpc_url = config
-		.get("rpc_url")
-		.and_then(|v| v.as_str())
-		.expect("rpc_url is required");
+	let rpc_urls = rpc_urls(config);
 
 	let chain_id = config
 		.get("chain_id")
@@ -280,7 +794,7 @@ pub fn create_http_delivery(config: &toml::Value) -> Box<dyn DeliveryInterface>
 	// Create delivery service synchronously, but the actual connection happens async
 	let delivery = tokio::task::block_in_place(|| {
 		tokio::runtime::Handle::current()
-			.block_on(async { AlloyDelivery::new(rpc_url, chain_id, signer).await })
+			.block_on(async { AlloyDelivery::new(&rpc_urls, chain_id, signer).await })
 	});
 
 	Box::new(delivery.expect("Failed to create delivery service"))
@@ -0,0 +1,512 @@
+//! Private mempool delivery through a Flashbots-compatible relay.
+//!
+//! Fills sent to the public mempool can be seen, and frontrun, before they are
+//! mined. This implementation signs transactions locally and hands them to a
+//! relay via `eth_sendPrivateTransaction`, which only shares them with block
+//! builders. If a transaction is not included within a configured number of
+//! blocks it is broadcast to the public mempool instead, so a slow relay
+//! delays a fill rather than losing it.
+//!
+//! The relay can also be reserved for urgent fills, with all other
+//! transactions broadcast publicly right away.
+
+use super::alloy::{rpc_urls, AlloyDelivery, AlloyDeliverySchema};
+use crate::{DeliveryError, DeliveryInterface};
+use alloy_network::eip2718::Encodable2718;
+use alloy_network::{EthereumWallet, TransactionBuilder};
+use alloy_primitives::{keccak256, Address as AlloyAddress, U256};
+use alloy_rpc_types::TransactionRequest;
+use alloy_signer::Signer;
+use alloy_signer_local::PrivateKeySigner;
+use async_trait::async_trait;
+use solver_types::{
+	Address, ConfigSchema, EndpointFailure, FeeHistory, Field, FieldType, GasParams, Schema,
+	Signature, Transaction as SolverTransaction, TransactionHash, TransactionReceipt,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Relay used when none is configured.
+const DEFAULT_RELAY_URL: &str = "https://relay.flashbots.net";
+
+/// Blocks a private transaction may wait for inclusion when not configured.
+const DEFAULT_FALLBACK_AFTER_BLOCKS: u64 = 25;
+
+/// Interval at which private transactions are checked for inclusion.
+const INCLUSION_POLL_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(5);
+
+/// A transaction sent to the relay that has not been seen on chain yet.
+struct PrivateTransaction {
+	/// The signed, encoded transaction.
+	raw: Vec<u8>,
+	/// Block number when the transaction was sent to the relay.
+	submitted_at: u64,
+}
+
+/// EVM delivery submitting transactions privately through a relay.
+///
+/// Queries, replacements, cancellations and, unless every transaction is to
+/// be private, regular submissions go through the public RPC provider. A private transaction that is replaced or cancelled is broadcast
+/// publicly first, so that the node knows the transaction being replaced.
+pub struct FlashbotsDelivery {
+	/// Public delivery used for everything except the initial submission.
+	public: AlloyDelivery,
+	/// Wallet signing transactions before they are sent to the relay.
+	wallet: EthereumWallet,
+	/// Address transactions are sent from.
+	from: AlloyAddress,
+	/// Key signing relay requests, identifying the solver to the relay.
+	auth_signer: PrivateKeySigner,
+	/// URL of the relay.
+	relay_url: String,
+	/// HTTP client for relay requests.
+	client: reqwest::Client,
+	/// Blocks to wait for inclusion before broadcasting publicly.
+	fallback_after_blocks: u64,
+	/// Whether regular submissions go through the relay as well, rather than
+	/// only those requested to be private.
+	always_private: bool,
+	/// Next nonce to use. The public node does not know about transactions
+	/// still held by the relay, so its pending nonce can lag behind.
+	next_nonce: tokio::sync::Mutex<Option<u64>>,
+	/// Transactions held by the relay, keyed by transaction hash.
+	pending: Mutex<HashMap<Vec<u8>, PrivateTransaction>>,
+}
+
+impl FlashbotsDelivery {
+	/// Creates a new FlashbotsDelivery instance.
+	///
+	/// Transactions are signed with `signer`, while relay requests are signed
+	/// with `auth_signer`.
+	pub async fn new(
+		rpc_urls: &[String],
+		chain_id: u64,
+		signer: PrivateKeySigner,
+		auth_signer: PrivateKeySigner,
+		relay_url: String,
+		fallback_after_blocks: u64,
+		always_private: bool,
+	) -> Result<Self, DeliveryError> {
+		let signer = signer.with_chain_id(Some(chain_id));
+		let from = signer.address();
+		let wallet = EthereumWallet::from(signer.clone());
+		let public = AlloyDelivery::new(rpc_urls, chain_id, signer).await?;
+
+		Ok(Self {
+			public,
+			wallet,
+			from,
+			auth_signer,
+			relay_url,
+			client: reqwest::Client::new(),
+			fallback_after_blocks,
+			always_private,
+			next_nonce: tokio::sync::Mutex::new(None),
+			pending: Mutex::new(HashMap::new()),
+		})
+	}
+
+	/// Fills in missing fields of a transaction and signs it.
+	///
+	/// Returns the transaction hash and the encoded transaction.
+	async fn sign(
+		&self,
+		mut request: TransactionRequest,
+		nonce: u64,
+	) -> Result<(Vec<u8>, Vec<u8>), DeliveryError> {
+		let provider = self.public.provider();
+		request.from = Some(self.from);
+		request.nonce = Some(nonce);
+
+		if request.gas.is_none() {
+			let gas = provider
+				.estimate_gas(&request)
+				.await
+				.map_err(|e| DeliveryError::Network(format!("Failed to estimate gas: {}", e)))?;
+			request.gas = Some(gas);
+		}
+		if request.gas_price.is_none() && request.max_fee_per_gas.is_none() {
+			let fees = provider
+				.estimate_eip1559_fees(None)
+				.await
+				.map_err(|e| DeliveryError::Network(format!("Failed to estimate fees: {}", e)))?;
+			request.max_fee_per_gas = Some(fees.max_fee_per_gas);
+			request.max_priority_fee_per_gas = Some(fees.max_priority_fee_per_gas);
+		}
+
+		let envelope = request
+			.build(&self.wallet)
+			.await
+			.map_err(|e| DeliveryError::Signing(format!("Failed to sign transaction: {}", e)))?;
+		Ok((envelope.tx_hash().to_vec(), envelope.encoded_2718()))
+	}
+
+	/// Sends an encoded transaction to the relay.
+	///
+	/// The relay drops the transaction after `max_block`, by which time it has
+	/// been broadcast publicly.
+	async fn send_private(&self, raw: &[u8], max_block: u64) -> Result<(), DeliveryError> {
+		let body = serde_json::json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": "eth_sendPrivateTransaction",
+			"params": [{
+				"tx": format!("0x{}", hex::encode(raw)),
+				"maxBlockNumber": format!("0x{:x}", max_block),
+				"preferences": { "fast": true },
+			}],
+		})
+		.to_string();
+
+		// Relays authenticate requests by a signature over the body's hash
+		let body_hash = format!("0x{}", hex::encode(keccak256(body.as_bytes())));
+		let signature = self
+			.auth_signer
+			.sign_message(body_hash.as_bytes())
+			.await
+			.map_err(|e| DeliveryError::Signing(format!("Failed to sign relay request: {}", e)))?;
+		let auth = format!(
+			"{}:0x{}",
+			self.auth_signer.address(),
+			hex::encode(signature.as_bytes())
+		);
+
+		let response = self
+			.client
+			.post(&self.relay_url)
+			.header("Content-Type", "application/json")
+			.header("X-Flashbots-Signature", auth)
+			.body(body)
+			.send()
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to reach relay: {}", e)))?
+			.text()
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to read relay response: {}", e)))?;
+
+		let response: serde_json::Value = serde_json::from_str(&response)
+			.map_err(|e| DeliveryError::Network(format!("Invalid relay response: {}", e)))?;
+		if let Some(error) = response.get("error") {
+			return Err(DeliveryError::Network(format!(
+				"Relay rejected transaction: {}",
+				error
+			)));
+		}
+		Ok(())
+	}
+
+	/// Signs a transaction at the next nonce and sends it to the relay or, if
+	/// `private` is not set, to the public mempool.
+	async fn submit_via(
+		&self,
+		tx: SolverTransaction,
+		private: bool,
+	) -> Result<TransactionHash, DeliveryError> {
+		let request: TransactionRequest = tx.into();
+
+		// Held across submission so that concurrent transactions get distinct nonces
+		let mut next_nonce = self.next_nonce.lock().await;
+		let nonce = match request.nonce {
+			Some(nonce) => nonce,
+			None => {
+				let on_chain = self
+					.public
+					.provider()
+					.get_transaction_count(self.from)
+					.pending()
+					.await
+					.map_err(|e| DeliveryError::Network(format!("Failed to get nonce: {}", e)))?;
+				next_nonce.map_or(on_chain, |next| next.max(on_chain))
+			}
+		};
+
+		let (hash, raw) = self.sign(request, nonce).await?;
+		if !private {
+			let _pending = self
+				.public
+				.provider()
+				.send_raw_transaction(&raw)
+				.await
+				.map_err(|e| {
+					DeliveryError::Network(format!("Failed to send transaction: {}", e))
+				})?;
+			*next_nonce = Some(nonce + 1);
+			return Ok(TransactionHash(hash));
+		}
+
+		let current_block = self.public.get_block_number().await?;
+		self.send_private(&raw, current_block + self.fallback_after_blocks)
+			.await?;
+		*next_nonce = Some(nonce + 1);
+
+		self.pending.lock().unwrap().insert(
+			hash.clone(),
+			PrivateTransaction {
+				raw,
+				submitted_at: current_block,
+			},
+		);
+		tracing::info!(tx_hash = %hex::encode(&hash), "Submitted private transaction");
+
+		Ok(TransactionHash(hash))
+	}
+
+	/// Broadcasts a private transaction publicly, if the relay still holds it.
+	///
+	/// Unless `force` is set, the transaction is only broadcast once it has
+	/// waited the configured number of blocks.
+	async fn broadcast_if_stale(&self, hash: &TransactionHash, force: bool) {
+		let submitted_at = match self.pending.lock().unwrap().get(&hash.0) {
+			Some(tx) => tx.submitted_at,
+			None => return,
+		};
+
+		if !force {
+			let Ok(current_block) = self.public.get_block_number().await else {
+				return;
+			};
+			if current_block < submitted_at + self.fallback_after_blocks {
+				return;
+			}
+		}
+
+		let Some(tx) = self.pending.lock().unwrap().remove(&hash.0) else {
+			return;
+		};
+		match self.public.provider().send_raw_transaction(&tx.raw).await {
+			Ok(_) => {
+				tracing::warn!(
+					tx_hash = %hex::encode(&hash.0),
+					"Private transaction not included, broadcast publicly"
+				);
+			}
+			// The relay may have landed the transaction in the meantime
+			Err(e) => {
+				tracing::warn!(
+					tx_hash = %hex::encode(&hash.0),
+					error = %e,
+					"Failed to broadcast private transaction publicly"
+				);
+			}
+		}
+	}
+}
+
+/// Configuration schema for the Flashbots delivery provider.
+pub struct FlashbotsDeliverySchema;
+
+impl ConfigSchema for FlashbotsDeliverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		AlloyDeliverySchema.validate(config)?;
+
+		let schema = Schema::new(
+			// Required fields
+			vec![],
+			// Optional fields
+			vec![
+				Field::new("relay_url", FieldType::String).with_validator(|value| {
+					let url = value.as_str().unwrap();
+					if url.starts_with("http://") || url.starts_with("https://") {
+						Ok(())
+					} else {
+						Err("Relay URL must start with http:// or https://".to_string())
+					}
+				}),
+				Field::new("auth_key", FieldType::String).with_validator(|value| {
+					let key = value.as_str().unwrap();
+					if key.parse::<PrivateKeySigner>().is_err() {
+						return Err("Auth key must be a valid private key".to_string());
+					}
+					Ok(())
+				}),
+				Field::new(
+					"fallback_after_blocks",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+				Field::new("private_only_when_urgent", FieldType::Boolean),
+			],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl DeliveryInterface for FlashbotsDelivery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(FlashbotsDeliverySchema)
+	}
+
+	async fn submit(
+		&self,
+		tx: SolverTransaction,
+		_signature: &Signature,
+	) -> Result<TransactionHash, DeliveryError> {
+		self.submit_via(tx, self.always_private).await
+	}
+
+	async fn submit_private(
+		&self,
+		tx: SolverTransaction,
+		_signature: &Signature,
+	) -> Result<TransactionHash, DeliveryError> {
+		self.submit_via(tx, true).await
+	}
+
+	async fn wait_for_confirmation(
+		&self,
+		hash: &TransactionHash,
+		confirmations: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		// Wait out the relay first, since the node only learns of the
+		// transaction once it is mined or broadcast publicly
+		while self.pending.lock().unwrap().contains_key(&hash.0) {
+			if self.get_receipt(hash).await.is_ok() {
+				break;
+			}
+			tokio::time::sleep(INCLUSION_POLL_INTERVAL).await;
+		}
+
+		self.public.wait_for_confirmation(hash, confirmations).await
+	}
+
+	async fn get_receipt(
+		&self,
+		hash: &TransactionHash,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		match self.public.get_receipt(hash).await {
+			Ok(receipt) => {
+				self.pending.lock().unwrap().remove(&hash.0);
+				Ok(receipt)
+			}
+			Err(e) => {
+				self.broadcast_if_stale(hash, false).await;
+				Err(e)
+			}
+		}
+	}
+
+	async fn replace(
+		&self,
+		hash: &TransactionHash,
+		gas: GasParams,
+	) -> Result<TransactionHash, DeliveryError> {
+		self.broadcast_if_stale(hash, true).await;
+		self.public.replace(hash, gas).await
+	}
+
+	async fn cancel(&self, hash: &TransactionHash) -> Result<TransactionHash, DeliveryError> {
+		self.broadcast_if_stale(hash, true).await;
+		self.public.cancel(hash).await
+	}
+
+	async fn call(&self, tx: &SolverTransaction) -> Result<Vec<u8>, DeliveryError> {
+		self.public.call(tx).await
+	}
+
+	async fn simulate(&self, tx: &SolverTransaction, from: &Address) -> Result<(), DeliveryError> {
+		self.public.simulate(tx, from).await
+	}
+
+	async fn get_gas_price(&self) -> Result<U256, DeliveryError> {
+		self.public.get_gas_price().await
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		self.public
+			.fee_history(block_count, reward_percentiles)
+			.await
+	}
+
+	async fn get_native_balance(&self, address: &Address) -> Result<U256, DeliveryError> {
+		self.public.get_native_balance(address).await
+	}
+
+	async fn get_code(&self, address: &Address) -> Result<Vec<u8>, DeliveryError> {
+		self.public.get_code(address).await
+	}
+
+	async fn get_block_number(&self) -> Result<u64, DeliveryError> {
+		self.public.get_block_number().await
+	}
+
+	async fn get_block_hash(&self, number: u64) -> Result<Option<Vec<u8>>, DeliveryError> {
+		self.public.get_block_hash(number).await
+	}
+
+	async fn check_endpoints(&self) -> Vec<EndpointFailure> {
+		self.public.check_endpoints().await
+	}
+}
+
+/// Factory function to create a private delivery provider from configuration.
+///
+/// Takes the same parameters as the HTTP delivery provider, plus:
+/// - `relay_url`: Relay accepting `eth_sendPrivateTransaction`, defaults to Flashbots
+/// - `auth_key`: Private key signing relay requests, defaults to `private_key`
+/// - `fallback_after_blocks`: Blocks to wait for inclusion before broadcasting
+///   publicly, defaults to 25
+/// - `private_only_when_urgent`: Broadcast transactions publicly unless they
+///   are urgent fills, defaults to false
+pub fn create_flashbots_delivery(config: &toml::Value) -> Box<dyn DeliveryInterface> {
+	let rpc_urls = rpc_urls(config);
+
+	let chain_id = config
+		.get("chain_id")
+		.and_then(|v| v.as_integer())
+		.expect("chain_id is required") as u64;
+
+	let private_key = config
+		.get("private_key")
+		.and_then(|v| v.as_str())
+		.expect("private_key is required");
+	let signer: PrivateKeySigner = private_key.parse().expect("Invalid private key");
+
+	let auth_signer: PrivateKeySigner = config
+		.get("auth_key")
+		.and_then(|v| v.as_str())
+		.unwrap_or(private_key)
+		.parse()
+		.expect("Invalid auth key");
+
+	let relay_url = config
+		.get("relay_url")
+		.and_then(|v| v.as_str())
+		.unwrap_or(DEFAULT_RELAY_URL)
+		.to_string();
+
+	let fallback_after_blocks = config
+		.get("fallback_after_blocks")
+		.and_then(|v| v.as_integer())
+		.map(|blocks| blocks as u64)
+		.unwrap_or(DEFAULT_FALLBACK_AFTER_BLOCKS);
+
+	let always_private = !config
+		.get("private_only_when_urgent")
+		.and_then(|v| v.as_bool())
+		.unwrap_or(false);
+
+	let delivery = tokio::task::block_in_place(|| {
+		tokio::runtime::Handle::current().block_on(async {
+			FlashbotsDelivery::new(
+				&rpc_urls,
+				chain_id,
+				signer,
+				auth_signer,
+				relay_url,
+				fallback_after_blocks,
+				always_private,
+			)
+			.await
+		})
+	});
+
+	Box::new(delivery.expect("Failed to create delivery service"))
+}
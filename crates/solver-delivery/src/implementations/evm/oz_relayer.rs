@@ -0,0 +1,683 @@
+//! Delivery through a managed relayer service.
+//!
+//! Instead of signing and broadcasting transactions itself, the solver hands
+//! them to a relayer service that holds the sending key, prices the
+//! transactions and resubmits them until they are mined. Two services are
+//! supported:
+//!
+//! - The OpenZeppelin Relayer, sending transactions from one of its
+//!   relayers' addresses.
+//! - Gelato Relay, executing sponsored calls paid for from the sponsor's
+//!   Gas Tank.
+//!
+//! Both identify a submission by a task ID rather than a transaction hash,
+//! and may change the hash as they reprice a transaction. A submission
+//! waits until the relayer broadcast the transaction and returns its hash,
+//! which settlement uses to look up fills. Confirmations follow the task
+//! rather than that hash, so that a repriced transaction is still found.
+//!
+//! Each provider serves one chain, so chains may use different relayers or
+//! API keys by configuring a provider per chain.
+
+use super::alloy::{rpc_urls, AlloyDelivery, AlloyDeliverySchema};
+use crate::{DeliveryError, DeliveryInterface, GasPayer};
+use alloy_primitives::{Address as AlloyAddress, B256, U256};
+use alloy_signer_local::PrivateKeySigner;
+use async_trait::async_trait;
+use reqwest::Method;
+use solver_types::{
+	Address, ConfigSchema, EndpointFailure, FeeHistory, Field, FieldType, GasParams, Schema,
+	Signature, Transaction as SolverTransaction, TransactionHash, TransactionReceipt,
+	ValidationError,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Gelato Relay API used when no URL is configured.
+const DEFAULT_GELATO_URL: &str = "https://api.gelato.digital";
+
+/// Transaction speed requested from the OpenZeppelin Relayer when not
+/// configured.
+const DEFAULT_SPEED: &str = "fast";
+
+/// Time the relayer may take to broadcast a transaction when not configured.
+const DEFAULT_BROADCAST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Interval at which the relayer is asked for the status of a task.
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Relayer service API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayerApi {
+	/// OpenZeppelin Relayer.
+	OpenZeppelin,
+	/// Gelato Relay sponsored calls.
+	Gelato,
+}
+
+/// State of a relayer task, in terms common to both APIs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TaskState {
+	/// The relayer has not broadcast the transaction yet.
+	Pending,
+	/// The transaction was broadcast and awaits inclusion.
+	Submitted,
+	/// The transaction was included in a block.
+	Mined,
+	/// The relayer gave up on the transaction, or it reverted.
+	Failed(String),
+}
+
+/// Status of a relayer task.
+#[derive(Debug, Clone)]
+struct TaskStatus {
+	/// State of the task.
+	state: TaskState,
+	/// Hash of the transaction the relayer last broadcast, if any.
+	hash: Option<B256>,
+}
+
+/// Task of a submitted transaction.
+#[derive(Debug, Clone)]
+struct Task {
+	/// ID the relayer identifies the task by.
+	id: String,
+	/// Hash of the transaction the relayer last broadcast for the task.
+	hash: B256,
+}
+
+/// EVM delivery submitting transactions through a relayer service.
+///
+/// Queries, simulations and receipts go through the public RPC provider.
+pub struct RelayerDelivery {
+	/// Public delivery used for everything except submissions.
+	public: AlloyDelivery,
+	/// API of the relayer service.
+	api: RelayerApi,
+	/// Base URL of the relayer service.
+	url: String,
+	/// Key authenticating requests to the relayer service.
+	api_key: String,
+	/// OpenZeppelin relayer sending the transactions.
+	relayer_id: Option<String>,
+	/// Address the OpenZeppelin relayer sends transactions from.
+	relayer_address: Option<AlloyAddress>,
+	/// Speed requested from the OpenZeppelin Relayer.
+	speed: String,
+	/// Chain ID transactions are relayed on.
+	chain_id: u64,
+	/// HTTP client for relayer requests.
+	client: reqwest::Client,
+	/// Time the relayer may take to broadcast a transaction.
+	broadcast_timeout: Duration,
+	/// Tasks of submitted transactions, keyed by the hash returned for them.
+	tasks: Mutex<HashMap<Vec<u8>, Task>>,
+}
+
+impl RelayerDelivery {
+	/// Creates a new RelayerDelivery instance.
+	///
+	/// For the OpenZeppelin Relayer, the address of the relayer is looked up
+	/// so that gas is charged and transactions are simulated from it.
+	#[allow(clippy::too_many_arguments)]
+	pub async fn new(
+		rpc_urls: &[String],
+		chain_id: u64,
+		signer: PrivateKeySigner,
+		api: RelayerApi,
+		url: String,
+		api_key: String,
+		relayer_id: Option<String>,
+		speed: String,
+		broadcast_timeout: Duration,
+	) -> Result<Self, DeliveryError> {
+		let public = AlloyDelivery::new(rpc_urls, chain_id, signer).await?;
+		let mut delivery = Self {
+			public,
+			api,
+			url: url.trim_end_matches('/').to_string(),
+			api_key,
+			relayer_id,
+			relayer_address: None,
+			speed,
+			chain_id,
+			client: reqwest::Client::new(),
+			broadcast_timeout,
+			tasks: Mutex::new(HashMap::new()),
+		};
+
+		if api == RelayerApi::OpenZeppelin {
+			let relayer = delivery
+				.request(Method::GET, &delivery.relayer_path(""), None)
+				.await?;
+			let address = relayer["data"]["address"]
+				.as_str()
+				.and_then(|address| address.parse().ok())
+				.ok_or_else(|| {
+					DeliveryError::Network("Relayer did not report its address".to_string())
+				})?;
+			delivery.relayer_address = Some(address);
+		}
+		Ok(delivery)
+	}
+
+	/// Returns the path of an OpenZeppelin Relayer resource of the relayer.
+	fn relayer_path(&self, resource: &str) -> String {
+		format!(
+			"/api/v1/relayers/{}{}",
+			self.relayer_id.as_deref().unwrap_or_default(),
+			resource
+		)
+	}
+
+	/// Sends a request to the relayer service and decodes its JSON response.
+	///
+	/// Rate limits and server errors are reported as network errors, so that
+	/// the request is retried, while other refusals fail the transaction.
+	async fn request(
+		&self,
+		method: Method,
+		path: &str,
+		body: Option<serde_json::Value>,
+	) -> Result<serde_json::Value, DeliveryError> {
+		let mut request = self.client.request(method, format!("{}{}", self.url, path));
+		if self.api == RelayerApi::OpenZeppelin {
+			request = request.bearer_auth(&self.api_key);
+		}
+		if let Some(body) = body {
+			request = request
+				.header("Content-Type", "application/json")
+				.body(body.to_string());
+		}
+
+		let response = request
+			.send()
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Relayer request failed: {}", e)))?;
+		let status = response.status();
+		let text = response
+			.text()
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Relayer request failed: {}", e)))?;
+		if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+			return Err(DeliveryError::Network(format!(
+				"Relayer responded {}: {}",
+				status, text
+			)));
+		}
+		if !status.is_success() {
+			return Err(DeliveryError::TransactionFailed(format!(
+				"Relayer refused request ({}): {}",
+				status, text
+			)));
+		}
+		serde_json::from_str(&text)
+			.map_err(|e| DeliveryError::Network(format!("Invalid relayer response: {}", e)))
+	}
+
+	/// Hands a transaction to the relayer, returning the ID of its task.
+	async fn create_task(&self, tx: &SolverTransaction) -> Result<String, DeliveryError> {
+		let to = tx
+			.to
+			.as_ref()
+			.map(|to| AlloyAddress::from_slice(&to.0))
+			.ok_or_else(|| {
+				DeliveryError::TransactionFailed("Relayers cannot deploy contracts".to_string())
+			})?;
+		let data = format!("0x{}", hex::encode(&tx.data));
+
+		let (path, body, id) = match self.api {
+			RelayerApi::OpenZeppelin => {
+				let mut body = serde_json::json!({
+					"to": to,
+					"value": tx.value,
+					"data": data,
+					"speed": self.speed,
+				});
+				if let Some(gas_limit) = tx.gas_limit {
+					body["gas_limit"] = gas_limit.into();
+				}
+				(self.relayer_path("/transactions"), body, "/data/id")
+			}
+			RelayerApi::Gelato => {
+				if !tx.value.is_zero() {
+					return Err(DeliveryError::TransactionFailed(
+						"Sponsored calls cannot transfer value".to_string(),
+					));
+				}
+				let mut body = serde_json::json!({
+					"chainId": self.chain_id.to_string(),
+					"target": to,
+					"data": data,
+					"sponsorApiKey": self.api_key,
+				});
+				if let Some(gas_limit) = tx.gas_limit {
+					body["gasLimit"] = gas_limit.to_string().into();
+				}
+				("/relays/v2/sponsored-call".to_string(), body, "/taskId")
+			}
+		};
+
+		let response = self.request(Method::POST, &path, Some(body)).await?;
+		response
+			.pointer(id)
+			.and_then(|id| id.as_str())
+			.map(str::to_string)
+			.ok_or_else(|| DeliveryError::Network("Relayer did not return a task ID".to_string()))
+	}
+
+	/// Asks the relayer for the status of a task.
+	async fn task_status(&self, task_id: &str) -> Result<TaskStatus, DeliveryError> {
+		let hash = |value: &serde_json::Value| value.as_str().and_then(|hash| hash.parse().ok());
+
+		match self.api {
+			RelayerApi::OpenZeppelin => {
+				let path = self.relayer_path(&format!("/transactions/{}", task_id));
+				let response = self.request(Method::GET, &path, None).await?;
+				let data = &response["data"];
+				let reason = || {
+					data["status_reason"]
+						.as_str()
+						.unwrap_or("no reason given")
+						.to_string()
+				};
+				let state = match data["status"].as_str().unwrap_or_default() {
+					"sent" | "submitted" => TaskState::Submitted,
+					"mined" | "confirmed" => TaskState::Mined,
+					"failed" => TaskState::Failed(reason()),
+					"canceled" => TaskState::Failed("Cancelled".to_string()),
+					"expired" => TaskState::Failed("Expired".to_string()),
+					_ => TaskState::Pending,
+				};
+				Ok(TaskStatus {
+					state,
+					hash: hash(&data["hash"]),
+				})
+			}
+			RelayerApi::Gelato => {
+				let path = format!("/tasks/status/{}", task_id);
+				let response = self.request(Method::GET, &path, None).await?;
+				let task = &response["task"];
+				let reason = || {
+					task["lastCheckMessage"]
+						.as_str()
+						.unwrap_or("no reason given")
+						.to_string()
+				};
+				let state = match task["taskState"].as_str().unwrap_or_default() {
+					"ExecPending" | "WaitingForConfirmation" => TaskState::Submitted,
+					"ExecSuccess" => TaskState::Mined,
+					"ExecReverted" => TaskState::Failed(format!("Reverted: {}", reason())),
+					"Cancelled" => TaskState::Failed(format!("Cancelled: {}", reason())),
+					_ => TaskState::Pending,
+				};
+				Ok(TaskStatus {
+					state,
+					hash: hash(&task["transactionHash"]),
+				})
+			}
+		}
+	}
+
+	/// Waits until the relayer broadcast a task's transaction, returning its
+	/// hash.
+	async fn wait_for_broadcast(&self, task_id: &str) -> Result<B256, DeliveryError> {
+		let deadline = tokio::time::Instant::now() + self.broadcast_timeout;
+		loop {
+			let status = self.task_status(task_id).await?;
+			if let TaskState::Failed(reason) = status.state {
+				return Err(DeliveryError::TransactionFailed(format!(
+					"Relayer task {} failed: {}",
+					task_id, reason
+				)));
+			}
+			if let Some(hash) = status.hash {
+				return Ok(hash);
+			}
+
+			if tokio::time::Instant::now() >= deadline {
+				return Err(DeliveryError::Network(format!(
+					"Relayer task {} not broadcast after {} seconds",
+					task_id,
+					self.broadcast_timeout.as_secs()
+				)));
+			}
+			tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+		}
+	}
+
+	/// Returns the task of a submitted transaction, if it was submitted by
+	/// this run.
+	fn task(&self, hash: &TransactionHash) -> Option<Task> {
+		self.tasks.lock().unwrap().get(&hash.0).cloned()
+	}
+
+	/// Returns the hash of the transaction currently standing for a
+	/// submitted one, which differs once the relayer repriced it.
+	fn current_hash(&self, hash: &TransactionHash) -> TransactionHash {
+		match self.task(hash) {
+			Some(task) => TransactionHash(task.hash.to_vec()),
+			None => hash.clone(),
+		}
+	}
+}
+
+/// Configuration schema for the relayer delivery provider.
+pub struct RelayerDeliverySchema;
+
+impl ConfigSchema for RelayerDeliverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), ValidationError> {
+		AlloyDeliverySchema.validate(config)?;
+
+		let schema = Schema::new(
+			// Required fields
+			vec![Field::new("api_key", FieldType::String)],
+			// Optional fields
+			vec![
+				Field::new("relayer_api", FieldType::String).with_validator(|value| {
+					match value.as_str().unwrap() {
+						"openzeppelin" | "gelato" => Ok(()),
+						other => Err(format!(
+							"Unknown relayer API '{}', expected openzeppelin or gelato",
+							other
+						)),
+					}
+				}),
+				Field::new("relayer_url", FieldType::String).with_validator(|value| {
+					let url = value.as_str().unwrap();
+					if url.starts_with("http://") || url.starts_with("https://") {
+						Ok(())
+					} else {
+						Err("URL must start with http:// or https://".to_string())
+					}
+				}),
+				Field::new("relayer_id", FieldType::String),
+				Field::new("speed", FieldType::String).with_validator(|value| {
+					match value.as_str().unwrap() {
+						"safeLow" | "average" | "fast" | "fastest" => Ok(()),
+						other => Err(format!(
+							"Unknown speed '{}', expected safeLow, average, fast or fastest",
+							other
+						)),
+					}
+				}),
+				Field::new(
+					"broadcast_timeout_seconds",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+			],
+		);
+		schema.validate(config)?;
+
+		if relayer_api(config) == RelayerApi::OpenZeppelin {
+			for field in ["relayer_url", "relayer_id"] {
+				if config.get(field).is_none() {
+					return Err(ValidationError::MissingField(field.to_string()));
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl DeliveryInterface for RelayerDelivery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(RelayerDeliverySchema)
+	}
+
+	fn signs_transactions(&self) -> bool {
+		true
+	}
+
+	fn gas_payer(&self) -> GasPayer {
+		match self.relayer_address {
+			Some(address) => GasPayer::Address(Address(address.to_vec())),
+			None => GasPayer::Sponsored,
+		}
+	}
+
+	async fn submit(
+		&self,
+		tx: SolverTransaction,
+		_signature: &Signature,
+	) -> Result<TransactionHash, DeliveryError> {
+		let task_id = self.create_task(&tx).await?;
+		tracing::info!(task_id = %task_id, "Submitted transaction to relayer");
+
+		let hash = self.wait_for_broadcast(&task_id).await?;
+		tracing::info!(task_id = %task_id, tx_hash = %hash, "Relayer broadcast transaction");
+		self.tasks
+			.lock()
+			.unwrap()
+			.insert(hash.to_vec(), Task { id: task_id, hash });
+		Ok(TransactionHash(hash.to_vec()))
+	}
+
+	async fn wait_for_confirmation(
+		&self,
+		hash: &TransactionHash,
+		confirmations: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		// Transactions submitted before a restart are followed by hash
+		let Some(task) = self.task(hash) else {
+			return self.public.wait_for_confirmation(hash, confirmations).await;
+		};
+
+		let mut current = task.hash;
+		let mined = loop {
+			let status = self.task_status(&task.id).await?;
+			if let Some(latest) = status.hash.filter(|latest| *latest != current) {
+				tracing::info!(
+					task_id = %task.id,
+					tx_hash = %latest,
+					"Relayer repriced transaction"
+				);
+				current = latest;
+				if let Some(task) = self.tasks.lock().unwrap().get_mut(&hash.0) {
+					task.hash = latest;
+				}
+			}
+			match status.state {
+				TaskState::Mined => break current,
+				TaskState::Failed(reason) => {
+					self.tasks.lock().unwrap().remove(&hash.0);
+					return Err(DeliveryError::TransactionFailed(format!(
+						"Relayer task {} failed: {}",
+						task.id, reason
+					)));
+				}
+				TaskState::Pending | TaskState::Submitted => {
+					tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+				}
+			}
+		};
+
+		self.public
+			.wait_for_confirmation(&TransactionHash(mined.to_vec()), confirmations)
+			.await
+	}
+
+	async fn get_receipt(
+		&self,
+		hash: &TransactionHash,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		self.public.get_receipt(&self.current_hash(hash)).await
+	}
+
+	async fn replace(
+		&self,
+		_hash: &TransactionHash,
+		_gas: GasParams,
+	) -> Result<TransactionHash, DeliveryError> {
+		Err(DeliveryError::TransactionFailed(
+			"Relayed transactions are repriced by the relayer".to_string(),
+		))
+	}
+
+	async fn cancel(&self, hash: &TransactionHash) -> Result<TransactionHash, DeliveryError> {
+		let task = match (self.api, self.task(hash)) {
+			(RelayerApi::OpenZeppelin, Some(task)) => task,
+			(RelayerApi::OpenZeppelin, None) => {
+				return Err(DeliveryError::TransactionFailed(
+					"Transaction was not submitted through this relayer".to_string(),
+				))
+			}
+			(RelayerApi::Gelato, _) => {
+				return Err(DeliveryError::TransactionFailed(
+					"Sponsored calls cannot be cancelled".to_string(),
+				))
+			}
+		};
+
+		let path = self.relayer_path(&format!("/transactions/{}", task.id));
+		let response = self.request(Method::DELETE, &path, None).await?;
+		let cancelling = response["data"]["hash"]
+			.as_str()
+			.and_then(|hash| hash.parse::<B256>().ok())
+			.unwrap_or(task.hash);
+		tracing::info!(task_id = %task.id, tx_hash = %cancelling, "Relayer cancelling transaction");
+		Ok(TransactionHash(cancelling.to_vec()))
+	}
+
+	async fn call(&self, tx: &SolverTransaction) -> Result<Vec<u8>, DeliveryError> {
+		self.public.call(tx).await
+	}
+
+	async fn simulate(&self, tx: &SolverTransaction, from: &Address) -> Result<(), DeliveryError> {
+		match self.relayer_address {
+			Some(address) => self.public.simulate(tx, &Address(address.to_vec())).await,
+			None => self.public.simulate(tx, from).await,
+		}
+	}
+
+	async fn get_gas_price(&self) -> Result<U256, DeliveryError> {
+		self.public.get_gas_price().await
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		self.public
+			.fee_history(block_count, reward_percentiles)
+			.await
+	}
+
+	async fn get_native_balance(&self, address: &Address) -> Result<U256, DeliveryError> {
+		self.public.get_native_balance(address).await
+	}
+
+	async fn get_code(&self, address: &Address) -> Result<Vec<u8>, DeliveryError> {
+		self.public.get_code(address).await
+	}
+
+	async fn get_block_number(&self) -> Result<u64, DeliveryError> {
+		self.public.get_block_number().await
+	}
+
+	async fn get_block_hash(&self, number: u64) -> Result<Option<Vec<u8>>, DeliveryError> {
+		self.public.get_block_hash(number).await
+	}
+
+	async fn check_endpoints(&self) -> Vec<EndpointFailure> {
+		self.public.check_endpoints().await
+	}
+}
+
+/// Returns the relayer API a provider is configured for.
+fn relayer_api(config: &toml::Value) -> RelayerApi {
+	match config.get("relayer_api").and_then(|v| v.as_str()) {
+		Some("gelato") => RelayerApi::Gelato,
+		_ => RelayerApi::OpenZeppelin,
+	}
+}
+
+/// Factory function to create a relayer delivery provider from
+/// configuration.
+///
+/// Takes the same parameters as the HTTP delivery provider, with
+/// `private_key` only used for queries, plus:
+/// - `api_key`: Key authenticating the solver to the relayer service; the
+///   Gelato sponsor API key for Gelato Relay
+/// - `relayer_api`: `openzeppelin` (default) or `gelato`
+/// - `relayer_url`: Base URL of the relayer service, required for the
+///   OpenZeppelin Relayer and defaulting to Gelato's public API
+/// - `relayer_id`: OpenZeppelin relayer sending the transactions
+/// - `speed`: Speed requested from the OpenZeppelin Relayer, defaults to
+///   `fast`
+/// - `broadcast_timeout_seconds`: Time the relayer may take to broadcast a
+///   transaction, defaults to 60
+pub fn create_relayer_delivery(config: &toml::Value) -> Box<dyn DeliveryInterface> {
+	let rpc_urls = rpc_urls(config);
+
+	let chain_id = config
+		.get("chain_id")
+		.and_then(|v| v.as_integer())
+		.expect("chain_id is required") as u64;
+
+	let signer: PrivateKeySigner = config
+		.get("private_key")
+		.and_then(|v| v.as_str())
+		.expect("private_key is required")
+		.parse()
+		.expect("Invalid private key");
+
+	let api = relayer_api(config);
+
+	let url = config
+		.get("relayer_url")
+		.and_then(|v| v.as_str())
+		.map(str::to_string)
+		.unwrap_or_else(|| match api {
+			RelayerApi::Gelato => DEFAULT_GELATO_URL.to_string(),
+			RelayerApi::OpenZeppelin => panic!("relayer_url is required"),
+		});
+
+	let api_key = config
+		.get("api_key")
+		.and_then(|v| v.as_str())
+		.expect("api_key is required")
+		.to_string();
+
+	let relayer_id = config
+		.get("relayer_id")
+		.and_then(|v| v.as_str())
+		.map(str::to_string);
+
+	let speed = config
+		.get("speed")
+		.and_then(|v| v.as_str())
+		.unwrap_or(DEFAULT_SPEED)
+		.to_string();
+
+	let broadcast_timeout = config
+		.get("broadcast_timeout_seconds")
+		.and_then(|v| v.as_integer())
+		.map(|seconds| Duration::from_secs(seconds as u64))
+		.unwrap_or(DEFAULT_BROADCAST_TIMEOUT);
+
+	let delivery = tokio::task::block_in_place(|| {
+		tokio::runtime::Handle::current().block_on(async {
+			RelayerDelivery::new(
+				&rpc_urls,
+				chain_id,
+				signer,
+				api,
+				url,
+				api_key,
+				relayer_id,
+				speed,
+				broadcast_timeout,
+			)
+			.await
+		})
+	});
+
+	Box::new(delivery.expect("Failed to create delivery service"))
+}
@@ -0,0 +1,768 @@
+//! ERC-4337 delivery from a smart account through a bundler.
+//!
+//! Rather than sending transactions from the solver's key, this
+//! implementation wraps each call in a UserOperation of a smart account owned
+//! by that key and submits it to a bundler, which includes it on chain
+//! through the EntryPoint. A paymaster service following ERC-7677 can be
+//! configured to sponsor the operations' gas.
+//!
+//! The solver tracks transactions by their on-chain hash, which settlement
+//! uses to look up fills, so a submission waits until the bundler included
+//! the operation and returns the hash of the bundle transaction. Receipts of
+//! bundle transactions report the outcome of the smart account's operations
+//! rather than of the bundle, which succeeds even if an operation reverted.
+//!
+//! Calls are made through the account's `execute(address,uint256,bytes)`
+//! function, as provided by SimpleAccount and compatible accounts, and the
+//! account must already be deployed. Tokens spent by fills must be held by
+//! the smart account.
+
+use super::alloy::{rpc_urls, AlloyDelivery, AlloyDeliverySchema};
+use crate::{DeliveryError, DeliveryInterface, GasPayer};
+use alloy_primitives::{keccak256, Address as AlloyAddress, Bytes, B256, U256};
+use alloy_rpc_types::{TransactionInput, TransactionRequest};
+use alloy_signer::Signer;
+use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::{sol, SolCall, SolEvent, SolValue};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use solver_types::{
+	Address, ConfigSchema, EndpointFailure, FeeHistory, Field, FieldType, GasParams, Schema,
+	Signature, Transaction as SolverTransaction, TransactionHash, TransactionReceipt,
+};
+use std::time::Duration;
+
+/// EntryPoint v0.7, deployed at the same address on every chain.
+const DEFAULT_ENTRY_POINT: &str = "0x0000000071727De22E5E9d8BAf0edAc6f37da032";
+
+/// Time an operation may take to be included when not configured.
+const DEFAULT_INCLUSION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Interval at which the bundler is asked whether an operation was included.
+const INCLUSION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Signature of the right length and shape for gas estimation, which runs
+/// validation before the operation is signed.
+const DUMMY_SIGNATURE: &str = "0xfffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c";
+
+sol! {
+	/// Entry point of smart account calls.
+	function execute(address dest, uint256 value, bytes func);
+
+	/// Next nonce of a smart account for a nonce key.
+	function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+
+	/// Emitted by the EntryPoint for every operation it executed.
+	event UserOperationEvent(
+		bytes32 indexed userOpHash,
+		address indexed sender,
+		address indexed paymaster,
+		uint256 nonce,
+		bool success,
+		uint256 actualGasCost,
+		uint256 actualGasUsed
+	);
+}
+
+/// UserOperation of EntryPoint v0.7 in its unpacked RPC form.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UserOperation {
+	sender: AlloyAddress,
+	nonce: U256,
+	call_data: Bytes,
+	call_gas_limit: U256,
+	verification_gas_limit: U256,
+	pre_verification_gas: U256,
+	max_fee_per_gas: U256,
+	max_priority_fee_per_gas: U256,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	paymaster: Option<AlloyAddress>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	paymaster_verification_gas_limit: Option<U256>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	paymaster_post_op_gas_limit: Option<U256>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	paymaster_data: Option<Bytes>,
+	signature: Bytes,
+}
+
+impl UserOperation {
+	/// Returns the hash the smart account's owner signs, as computed by the
+	/// EntryPoint from the packed operation.
+	fn hash(&self, entry_point: AlloyAddress, chain_id: u64) -> B256 {
+		let pack = |high: U256, low: U256| {
+			let mut word = [0u8; 32];
+			word[..16].copy_from_slice(&high.to_be_bytes::<32>()[16..]);
+			word[16..].copy_from_slice(&low.to_be_bytes::<32>()[16..]);
+			B256::from(word)
+		};
+
+		let mut paymaster_and_data = Vec::new();
+		if let Some(paymaster) = self.paymaster {
+			paymaster_and_data.extend_from_slice(paymaster.as_slice());
+			for limit in [
+				self.paymaster_verification_gas_limit,
+				self.paymaster_post_op_gas_limit,
+			] {
+				paymaster_and_data
+					.extend_from_slice(&limit.unwrap_or_default().to_be_bytes::<32>()[16..]);
+			}
+			if let Some(data) = &self.paymaster_data {
+				paymaster_and_data.extend_from_slice(data);
+			}
+		}
+
+		let packed = (
+			self.sender,
+			self.nonce,
+			keccak256([]),
+			keccak256(&self.call_data),
+			pack(self.verification_gas_limit, self.call_gas_limit),
+			self.pre_verification_gas,
+			pack(self.max_priority_fee_per_gas, self.max_fee_per_gas),
+			keccak256(&paymaster_and_data),
+		)
+			.abi_encode();
+		keccak256((keccak256(packed), entry_point, U256::from(chain_id)).abi_encode())
+	}
+}
+
+/// Gas limits estimated by the bundler.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GasEstimate {
+	call_gas_limit: U256,
+	verification_gas_limit: U256,
+	pre_verification_gas: U256,
+	paymaster_verification_gas_limit: Option<U256>,
+	paymaster_post_op_gas_limit: Option<U256>,
+}
+
+/// Paymaster fields returned by a paymaster service.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PaymasterFields {
+	paymaster: AlloyAddress,
+	paymaster_data: Bytes,
+	paymaster_verification_gas_limit: Option<U256>,
+	paymaster_post_op_gas_limit: Option<U256>,
+	/// Whether the stub data is already final, so that no second request is
+	/// needed after gas estimation.
+	#[serde(default)]
+	is_final: bool,
+}
+
+/// Receipt of an included operation, as returned by the bundler.
+#[derive(Deserialize)]
+struct UserOperationReceipt {
+	receipt: BundleReceipt,
+}
+
+/// Receipt of the bundle transaction that included an operation.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleReceipt {
+	transaction_hash: B256,
+}
+
+/// JSON-RPC endpoint of a bundler or paymaster service.
+struct RpcEndpoint {
+	/// HTTP client for requests.
+	client: reqwest::Client,
+	/// URL of the endpoint.
+	url: String,
+}
+
+impl RpcEndpoint {
+	/// Sends a JSON-RPC request and decodes its result.
+	async fn request<T: DeserializeOwned>(
+		&self,
+		method: &str,
+		params: serde_json::Value,
+	) -> Result<T, DeliveryError> {
+		let body = serde_json::json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": method,
+			"params": params,
+		})
+		.to_string();
+
+		let response = self
+			.client
+			.post(&self.url)
+			.header("Content-Type", "application/json")
+			.body(body)
+			.send()
+			.await
+			.map_err(|e| DeliveryError::Network(format!("{} failed: {}", method, e)))?
+			.text()
+			.await
+			.map_err(|e| DeliveryError::Network(format!("{} failed: {}", method, e)))?;
+
+		let mut response: serde_json::Value = serde_json::from_str(&response)
+			.map_err(|e| DeliveryError::Network(format!("Invalid {} response: {}", method, e)))?;
+		if let Some(error) = response.get("error") {
+			return Err(DeliveryError::TransactionFailed(format!(
+				"{} rejected: {}",
+				method, error
+			)));
+		}
+		serde_json::from_value(response["result"].take())
+			.map_err(|e| DeliveryError::Network(format!("Invalid {} response: {}", method, e)))
+	}
+}
+
+/// EVM delivery submitting transactions as UserOperations of a smart account.
+///
+/// Queries go through the public RPC provider, with calls and simulations
+/// made from the smart account.
+pub struct UserOperationDelivery {
+	/// Public delivery used for queries and receipts.
+	public: AlloyDelivery,
+	/// Owner of the smart account, signing its operations.
+	owner: PrivateKeySigner,
+	/// Smart account the operations are sent from.
+	smart_account: AlloyAddress,
+	/// EntryPoint the bundler submits operations to.
+	entry_point: AlloyAddress,
+	/// Chain ID operations are signed for.
+	chain_id: u64,
+	/// Bundler accepting operations.
+	bundler: RpcEndpoint,
+	/// Paymaster service sponsoring operations, if any.
+	paymaster: Option<RpcEndpoint>,
+	/// Context passed to the paymaster service with every request.
+	paymaster_context: serde_json::Value,
+	/// Time an operation may take to be included.
+	inclusion_timeout: Duration,
+	/// Held from building an operation until it is included, since operations
+	/// share the smart account's nonce sequence.
+	submission: tokio::sync::Mutex<()>,
+}
+
+impl UserOperationDelivery {
+	/// Creates a new UserOperationDelivery instance.
+	///
+	/// Fails if no code is deployed at the smart account's address.
+	#[allow(clippy::too_many_arguments)]
+	pub async fn new(
+		rpc_urls: &[String],
+		chain_id: u64,
+		owner: PrivateKeySigner,
+		smart_account: AlloyAddress,
+		entry_point: AlloyAddress,
+		bundler_url: String,
+		paymaster_url: Option<String>,
+		paymaster_context: serde_json::Value,
+		inclusion_timeout: Duration,
+	) -> Result<Self, DeliveryError> {
+		let owner = owner.with_chain_id(Some(chain_id));
+		let public = AlloyDelivery::new(rpc_urls, chain_id, owner.clone()).await?;
+
+		if public
+			.get_code(&Address(smart_account.to_vec()))
+			.await?
+			.is_empty()
+		{
+			return Err(DeliveryError::Network(format!(
+				"Smart account {} is not deployed",
+				smart_account
+			)));
+		}
+
+		let client = reqwest::Client::new();
+		Ok(Self {
+			public,
+			owner,
+			smart_account,
+			entry_point,
+			chain_id,
+			bundler: RpcEndpoint {
+				client: client.clone(),
+				url: bundler_url,
+			},
+			paymaster: paymaster_url.map(|url| RpcEndpoint { client, url }),
+			paymaster_context,
+			inclusion_timeout,
+			submission: tokio::sync::Mutex::new(()),
+		})
+	}
+
+	/// Returns the smart account's next nonce.
+	async fn nonce(&self) -> Result<U256, DeliveryError> {
+		let call = getNonceCall {
+			sender: self.smart_account,
+			key: Default::default(),
+		};
+		let request = TransactionRequest::default()
+			.to(self.entry_point)
+			.input(TransactionInput::new(call.abi_encode().into()));
+		let output = self
+			.public
+			.with_failover(|provider| {
+				let request = request.clone();
+				async move { provider.call(&request).await }
+			})
+			.await
+			.map_err(|e| DeliveryError::Network(format!("Failed to get nonce: {}", e)))?;
+		getNonceCall::abi_decode_returns(&output, true)
+			.map(|decoded| decoded.nonce)
+			.map_err(|e| DeliveryError::Network(format!("Invalid nonce: {}", e)))
+	}
+
+	/// Requests paymaster fields for an operation from the paymaster service.
+	async fn paymaster_fields(
+		&self,
+		paymaster: &RpcEndpoint,
+		method: &str,
+		op: &UserOperation,
+	) -> Result<PaymasterFields, DeliveryError> {
+		paymaster
+			.request(
+				method,
+				serde_json::json!([
+					op,
+					self.entry_point,
+					format!("0x{:x}", self.chain_id),
+					self.paymaster_context,
+				]),
+			)
+			.await
+	}
+
+	/// Builds and signs the operation executing a transaction.
+	///
+	/// Gas limits are estimated by the bundler, with paymaster stub data in
+	/// place if a paymaster sponsors the operation, while fees are taken from
+	/// the transaction or else estimated by the node.
+	async fn build(&self, tx: &SolverTransaction) -> Result<UserOperation, DeliveryError> {
+		let to = tx
+			.to
+			.as_ref()
+			.map(|to| AlloyAddress::from_slice(&to.0))
+			.ok_or_else(|| {
+				DeliveryError::TransactionFailed(
+					"Smart accounts cannot deploy contracts".to_string(),
+				)
+			})?;
+		let call_data = executeCall {
+			dest: to,
+			value: tx.value,
+			func: tx.data.clone().into(),
+		}
+		.abi_encode();
+
+		let (max_fee_per_gas, max_priority_fee_per_gas) = match tx.max_fee_per_gas.or(tx.gas_price)
+		{
+			Some(max_fee) => (
+				max_fee,
+				tx.max_priority_fee_per_gas.unwrap_or(max_fee).min(max_fee),
+			),
+			None => {
+				let fees = self
+					.public
+					.provider()
+					.estimate_eip1559_fees(None)
+					.await
+					.map_err(|e| {
+						DeliveryError::Network(format!("Failed to estimate fees: {}", e))
+					})?;
+				(fees.max_fee_per_gas, fees.max_priority_fee_per_gas)
+			}
+		};
+
+		let mut op = UserOperation {
+			sender: self.smart_account,
+			nonce: self.nonce().await?,
+			call_data: call_data.into(),
+			call_gas_limit: U256::ZERO,
+			verification_gas_limit: U256::ZERO,
+			pre_verification_gas: U256::ZERO,
+			max_fee_per_gas: U256::from(max_fee_per_gas),
+			max_priority_fee_per_gas: U256::from(max_priority_fee_per_gas),
+			paymaster: None,
+			paymaster_verification_gas_limit: None,
+			paymaster_post_op_gas_limit: None,
+			paymaster_data: None,
+			signature: DUMMY_SIGNATURE.parse().unwrap(),
+		};
+
+		let mut stub_is_final = true;
+		if let Some(paymaster) = &self.paymaster {
+			let stub = self
+				.paymaster_fields(paymaster, "pm_getPaymasterStubData", &op)
+				.await?;
+			op.paymaster = Some(stub.paymaster);
+			op.paymaster_data = Some(stub.paymaster_data);
+			op.paymaster_verification_gas_limit = stub.paymaster_verification_gas_limit;
+			op.paymaster_post_op_gas_limit = stub.paymaster_post_op_gas_limit;
+			stub_is_final = stub.is_final;
+		}
+
+		let estimate: GasEstimate = self
+			.bundler
+			.request(
+				"eth_estimateUserOperationGas",
+				serde_json::json!([op, self.entry_point]),
+			)
+			.await?;
+		op.call_gas_limit = estimate.call_gas_limit;
+		op.verification_gas_limit = estimate.verification_gas_limit;
+		op.pre_verification_gas = estimate.pre_verification_gas;
+		if op.paymaster.is_some() {
+			op.paymaster_verification_gas_limit = estimate
+				.paymaster_verification_gas_limit
+				.or(op.paymaster_verification_gas_limit);
+			op.paymaster_post_op_gas_limit = estimate
+				.paymaster_post_op_gas_limit
+				.or(op.paymaster_post_op_gas_limit);
+		}
+
+		if let (Some(paymaster), false) = (&self.paymaster, stub_is_final) {
+			let fields = self
+				.paymaster_fields(paymaster, "pm_getPaymasterData", &op)
+				.await?;
+			op.paymaster = Some(fields.paymaster);
+			op.paymaster_data = Some(fields.paymaster_data);
+		}
+
+		let hash = op.hash(self.entry_point, self.chain_id);
+		let signature = self
+			.owner
+			.sign_message(hash.as_slice())
+			.await
+			.map_err(|e| DeliveryError::Signing(format!("Failed to sign UserOperation: {}", e)))?;
+		op.signature = signature.as_bytes().to_vec().into();
+		Ok(op)
+	}
+
+	/// Waits until the bundler included an operation, returning the hash of
+	/// the bundle transaction.
+	async fn wait_for_inclusion(&self, op_hash: B256) -> Result<B256, DeliveryError> {
+		let deadline = tokio::time::Instant::now() + self.inclusion_timeout;
+		loop {
+			let receipt: Option<UserOperationReceipt> = self
+				.bundler
+				.request("eth_getUserOperationReceipt", serde_json::json!([op_hash]))
+				.await?;
+			if let Some(receipt) = receipt {
+				return Ok(receipt.receipt.transaction_hash);
+			}
+
+			if tokio::time::Instant::now() >= deadline {
+				return Err(DeliveryError::Network(format!(
+					"UserOperation {} not included after {} seconds",
+					op_hash,
+					self.inclusion_timeout.as_secs()
+				)));
+			}
+			tokio::time::sleep(INCLUSION_POLL_INTERVAL).await;
+		}
+	}
+
+	/// Reports the outcome of the smart account's operations in a bundle
+	/// transaction's receipt.
+	///
+	/// The operations succeeded if the EntryPoint reported all of them as
+	/// successful. Gas is what the operations were charged, at no cost to the
+	/// solver if a paymaster paid for them.
+	fn operation_receipt(&self, mut receipt: TransactionReceipt) -> TransactionReceipt {
+		let entry_point = Address(self.entry_point.to_vec());
+		let mut found = false;
+		let mut success = true;
+		let mut gas_used = U256::ZERO;
+		let mut gas_cost = U256::ZERO;
+
+		for log in &receipt.logs {
+			if log.address != entry_point {
+				continue;
+			}
+			let topics = log.topics.iter().map(|topic| B256::from_slice(topic));
+			let Ok(event) = UserOperationEvent::decode_raw_log(topics, &log.data, true) else {
+				continue;
+			};
+			if event.sender != self.smart_account {
+				continue;
+			}
+			found = true;
+			success &= event.success;
+			gas_used = gas_used.saturating_add(event.actualGasUsed);
+			if event.paymaster.is_zero() {
+				gas_cost = gas_cost.saturating_add(event.actualGasCost);
+			}
+		}
+
+		if found {
+			receipt.success &= success;
+			receipt.gas_used = u64::try_from(gas_used).unwrap_or(u64::MAX);
+			receipt.effective_gas_price = if gas_used.is_zero() {
+				U256::ZERO
+			} else {
+				gas_cost / gas_used
+			};
+		}
+		receipt
+	}
+}
+
+/// Configuration schema for the UserOperation delivery provider.
+pub struct UserOperationDeliverySchema;
+
+impl ConfigSchema for UserOperationDeliverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		AlloyDeliverySchema.validate(config)?;
+
+		let url_validator = |value: &toml::Value| {
+			let url = value.as_str().unwrap();
+			if url.starts_with("http://") || url.starts_with("https://") {
+				Ok(())
+			} else {
+				Err("URL must start with http:// or https://".to_string())
+			}
+		};
+		let address_validator = |value: &toml::Value| {
+			if value.as_str().unwrap().parse::<AlloyAddress>().is_err() {
+				return Err("Must be a valid address".to_string());
+			}
+			Ok(())
+		};
+
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("bundler_url", FieldType::String).with_validator(url_validator),
+				Field::new("smart_account", FieldType::String).with_validator(address_validator),
+			],
+			// Optional fields
+			vec![
+				Field::new("entry_point", FieldType::String).with_validator(address_validator),
+				Field::new("paymaster_url", FieldType::String).with_validator(url_validator),
+				Field::new(
+					"paymaster_context",
+					FieldType::Table(Schema::new(vec![], vec![])),
+				),
+				Field::new(
+					"inclusion_timeout_seconds",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+			],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl DeliveryInterface for UserOperationDelivery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(UserOperationDeliverySchema)
+	}
+
+	fn signs_transactions(&self) -> bool {
+		true
+	}
+
+	fn gas_payer(&self) -> GasPayer {
+		if self.paymaster.is_some() {
+			GasPayer::Sponsored
+		} else {
+			GasPayer::Address(Address(self.smart_account.to_vec()))
+		}
+	}
+
+	async fn submit(
+		&self,
+		tx: SolverTransaction,
+		_signature: &Signature,
+	) -> Result<TransactionHash, DeliveryError> {
+		let _submission = self.submission.lock().await;
+
+		let op = self.build(&tx).await?;
+		let op_hash: B256 = self
+			.bundler
+			.request(
+				"eth_sendUserOperation",
+				serde_json::json!([op, self.entry_point]),
+			)
+			.await?;
+		tracing::info!(user_op_hash = %op_hash, "Submitted UserOperation");
+
+		let tx_hash = self.wait_for_inclusion(op_hash).await?;
+		tracing::info!(
+			user_op_hash = %op_hash,
+			tx_hash = %tx_hash,
+			"UserOperation included"
+		);
+		Ok(TransactionHash(tx_hash.to_vec()))
+	}
+
+	async fn wait_for_confirmation(
+		&self,
+		hash: &TransactionHash,
+		confirmations: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		let receipt = self
+			.public
+			.wait_for_confirmation(hash, confirmations)
+			.await?;
+		Ok(self.operation_receipt(receipt))
+	}
+
+	async fn get_receipt(
+		&self,
+		hash: &TransactionHash,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		let receipt = self.public.get_receipt(hash).await?;
+		Ok(self.operation_receipt(receipt))
+	}
+
+	async fn replace(
+		&self,
+		_hash: &TransactionHash,
+		_gas: GasParams,
+	) -> Result<TransactionHash, DeliveryError> {
+		Err(DeliveryError::TransactionFailed(
+			"Bundle transactions cannot be replaced".to_string(),
+		))
+	}
+
+	async fn cancel(&self, _hash: &TransactionHash) -> Result<TransactionHash, DeliveryError> {
+		Err(DeliveryError::TransactionFailed(
+			"Bundle transactions cannot be cancelled".to_string(),
+		))
+	}
+
+	async fn call(&self, tx: &SolverTransaction) -> Result<Vec<u8>, DeliveryError> {
+		self.public.call(tx).await
+	}
+
+	async fn simulate(&self, tx: &SolverTransaction, _from: &Address) -> Result<(), DeliveryError> {
+		self.public
+			.simulate(tx, &Address(self.smart_account.to_vec()))
+			.await
+	}
+
+	async fn get_gas_price(&self) -> Result<U256, DeliveryError> {
+		self.public.get_gas_price().await
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		self.public
+			.fee_history(block_count, reward_percentiles)
+			.await
+	}
+
+	async fn get_native_balance(&self, address: &Address) -> Result<U256, DeliveryError> {
+		self.public.get_native_balance(address).await
+	}
+
+	async fn get_code(&self, address: &Address) -> Result<Vec<u8>, DeliveryError> {
+		self.public.get_code(address).await
+	}
+
+	async fn get_block_number(&self) -> Result<u64, DeliveryError> {
+		self.public.get_block_number().await
+	}
+
+	async fn get_block_hash(&self, number: u64) -> Result<Option<Vec<u8>>, DeliveryError> {
+		self.public.get_block_hash(number).await
+	}
+
+	async fn check_endpoints(&self) -> Vec<EndpointFailure> {
+		self.public.check_endpoints().await
+	}
+}
+
+/// Factory function to create a UserOperation delivery provider from
+/// configuration.
+///
+/// Takes the same parameters as the HTTP delivery provider, with
+/// `private_key` being the smart account's owner, plus:
+/// - `bundler_url`: Bundler accepting `eth_sendUserOperation`
+/// - `smart_account`: Address of the deployed smart account
+/// - `entry_point`: EntryPoint address, defaults to EntryPoint v0.7
+/// - `paymaster_url`: ERC-7677 paymaster service sponsoring gas, if any
+/// - `paymaster_context`: Table passed to the paymaster service, such as a
+///   sponsorship policy ID
+/// - `inclusion_timeout_seconds`: Time an operation may take to be included,
+///   defaults to 120
+pub fn create_user_operation_delivery(config: &toml::Value) -> Box<dyn DeliveryInterface> {
+	let rpc_urls = rpc_urls(config);
+
+	let chain_id = config
+		.get("chain_id")
+		.and_then(|v| v.as_integer())
+		.expect("chain_id is required") as u64;
+
+	let owner: PrivateKeySigner = config
+		.get("private_key")
+		.and_then(|v| v.as_str())
+		.expect("private_key is required")
+		.parse()
+		.expect("Invalid private key");
+
+	let smart_account: AlloyAddress = config
+		.get("smart_account")
+		.and_then(|v| v.as_str())
+		.expect("smart_account is required")
+		.parse()
+		.expect("Invalid smart account address");
+
+	let entry_point: AlloyAddress = config
+		.get("entry_point")
+		.and_then(|v| v.as_str())
+		.unwrap_or(DEFAULT_ENTRY_POINT)
+		.parse()
+		.expect("Invalid EntryPoint address");
+
+	let bundler_url = config
+		.get("bundler_url")
+		.and_then(|v| v.as_str())
+		.expect("bundler_url is required")
+		.to_string();
+
+	let paymaster_url = config
+		.get("paymaster_url")
+		.and_then(|v| v.as_str())
+		.map(str::to_string);
+
+	let paymaster_context = config
+		.get("paymaster_context")
+		.map(|context| serde_json::to_value(context).expect("Invalid paymaster context"))
+		.unwrap_or_else(|| serde_json::json!({}));
+
+	let inclusion_timeout = config
+		.get("inclusion_timeout_seconds")
+		.and_then(|v| v.as_integer())
+		.map(|seconds| Duration::from_secs(seconds as u64))
+		.unwrap_or(DEFAULT_INCLUSION_TIMEOUT);
+
+	let delivery = tokio::task::block_in_place(|| {
+		tokio::runtime::Handle::current().block_on(async {
+			UserOperationDelivery::new(
+				&rpc_urls,
+				chain_id,
+				owner,
+				smart_account,
+				entry_point,
+				bundler_url,
+				paymaster_url,
+				paymaster_context,
+				inclusion_timeout,
+			)
+			.await
+		})
+	});
+
+	Box::new(delivery.expect("Failed to create delivery service"))
+}
@@ -0,0 +1,352 @@
+//! Mock delivery provider for tests.
+//!
+//! Simulates a chain in memory: every submitted transaction is mined into a
+//! block of its own right away, and succeeds unless a revert was scripted.
+//! Read-only calls return a configurable result, so that allowance, balance
+//! and fill checks can be steered by tests.
+
+use crate::{DeliveryError, DeliveryInterface};
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_types::testing::MockBehavior;
+use solver_types::{
+	Address, ConfigSchema, FeeHistory, Field, FieldType, GasParams, Schema, Signature, Transaction,
+	TransactionHash, TransactionReceipt,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Gas used by mined transactions without a gas limit.
+const DEFAULT_GAS_USED: u64 = 21_000;
+
+/// In-memory state of the simulated chain.
+struct MockChain {
+	/// Number of the latest block.
+	block_number: u64,
+	/// Transactions submitted so far, oldest first.
+	submitted: Vec<Transaction>,
+	/// Receipts of mined transactions keyed by hash.
+	receipts: HashMap<Vec<u8>, TransactionReceipt>,
+	/// Number of upcoming transactions that revert.
+	reverts: usize,
+	/// Gas price of the chain.
+	gas_price: U256,
+	/// Native balance of every address.
+	native_balance: U256,
+	/// Return data of read-only calls.
+	call_result: Vec<u8>,
+}
+
+/// Delivery provider mining transactions into an in-memory chain.
+///
+/// Scripted failures surface as [`DeliveryError::Network`].
+pub struct MockDelivery {
+	/// Chain the provider delivers to.
+	chain_id: u64,
+	/// State of the simulated chain.
+	chain: Arc<Mutex<MockChain>>,
+	/// Latencies, scripted failures and call counts.
+	behavior: Arc<MockBehavior>,
+}
+
+impl MockDelivery {
+	/// Creates a provider for a chain at block 1, with a 1 gwei gas price
+	/// and 1000 native tokens on every address.
+	pub fn new(chain_id: u64) -> Self {
+		Self {
+			chain_id,
+			chain: Arc::new(Mutex::new(MockChain {
+				block_number: 1,
+				submitted: Vec::new(),
+				receipts: HashMap::new(),
+				reverts: 0,
+				gas_price: U256::from(1_000_000_000u64),
+				native_balance: U256::from(10u64).pow(U256::from(21u64)),
+				call_result: vec![0; 32],
+			})),
+			behavior: Arc::new(MockBehavior::new()),
+		}
+	}
+
+	/// Sets the gas price of the chain.
+	pub fn with_gas_price(self, gas_price: U256) -> Self {
+		self.chain.lock().unwrap().gas_price = gas_price;
+		self
+	}
+
+	/// Sets the native balance of every address.
+	pub fn with_native_balance(self, balance: U256) -> Self {
+		self.chain.lock().unwrap().native_balance = balance;
+		self
+	}
+
+	/// Sets the return data of read-only calls.
+	pub fn with_call_result(self, result: Vec<u8>) -> Self {
+		self.chain.lock().unwrap().call_result = result;
+		self
+	}
+
+	/// Replaces the provider's behaviour.
+	pub fn with_behavior(mut self, behavior: MockBehavior) -> Self {
+		self.behavior = Arc::new(behavior);
+		self
+	}
+
+	/// Returns the provider's behaviour, to script failures and count calls
+	/// once the provider is handed to the solver.
+	pub fn behavior(&self) -> Arc<MockBehavior> {
+		self.behavior.clone()
+	}
+
+	/// Returns a handle on the simulated chain, kept once the provider is
+	/// handed to the solver.
+	pub fn handle(&self) -> MockChainHandle {
+		MockChainHandle {
+			chain: self.chain.clone(),
+		}
+	}
+
+	/// Plays a call to an operation.
+	async fn call_operation(&self, operation: &str) -> Result<(), DeliveryError> {
+		self.behavior
+			.call(operation)
+			.await
+			.map_err(DeliveryError::Network)
+	}
+
+	/// Mines a transaction into a new block, returning its hash.
+	fn mine(&self, tx: Option<Transaction>, gas_used: u64) -> TransactionHash {
+		let mut chain = self.chain.lock().unwrap();
+		chain.block_number += 1;
+		let success = match chain.reverts {
+			0 => true,
+			_ => {
+				chain.reverts -= 1;
+				false
+			}
+		};
+
+		let hash = TransactionHash(transaction_hash(self.chain_id, chain.block_number));
+		let receipt = TransactionReceipt {
+			hash: hash.clone(),
+			block_number: chain.block_number,
+			success,
+			gas_used,
+			effective_gas_price: chain.gas_price,
+			logs: Vec::new(),
+		};
+		chain.receipts.insert(hash.0.clone(), receipt);
+		if let Some(tx) = tx {
+			chain.submitted.push(tx);
+		}
+		hash
+	}
+
+	/// Returns the receipt of a mined transaction.
+	fn receipt(&self, hash: &TransactionHash) -> Result<TransactionReceipt, DeliveryError> {
+		self.chain
+			.lock()
+			.unwrap()
+			.receipts
+			.get(&hash.0)
+			.cloned()
+			.ok_or_else(|| DeliveryError::Network("Transaction not found".to_string()))
+	}
+}
+
+/// Handle on the chain simulated by a mock delivery provider.
+#[derive(Clone)]
+pub struct MockChainHandle {
+	/// State of the simulated chain.
+	chain: Arc<Mutex<MockChain>>,
+}
+
+impl MockChainHandle {
+	/// Returns the transactions submitted so far, oldest first.
+	pub fn submitted(&self) -> Vec<Transaction> {
+		self.chain.lock().unwrap().submitted.clone()
+	}
+
+	/// Makes the next `count` transactions mined revert.
+	pub fn revert_next(&self, count: usize) {
+		self.chain.lock().unwrap().reverts += count;
+	}
+
+	/// Sets the gas price of the chain.
+	pub fn set_gas_price(&self, gas_price: U256) {
+		self.chain.lock().unwrap().gas_price = gas_price;
+	}
+
+	/// Sets the return data of read-only calls.
+	pub fn set_call_result(&self, result: Vec<u8>) {
+		self.chain.lock().unwrap().call_result = result;
+	}
+
+	/// Advances the chain by `blocks` empty blocks.
+	pub fn advance(&self, blocks: u64) {
+		self.chain.lock().unwrap().block_number += blocks;
+	}
+}
+
+/// Configuration schema for MockDelivery.
+pub struct MockDeliverySchema;
+
+impl ConfigSchema for MockDeliverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let mut optional = vec![Field::new(
+			"gas_price_gwei",
+			FieldType::Integer {
+				min: Some(0),
+				max: None,
+			},
+		)];
+		optional.extend(MockBehavior::config_fields());
+		Schema::new(
+			vec![Field::new(
+				"chain_id",
+				FieldType::Integer {
+					min: Some(1),
+					max: None,
+				},
+			)],
+			optional,
+		)
+		.validate(config)
+	}
+}
+
+#[async_trait]
+impl DeliveryInterface for MockDelivery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(MockDeliverySchema)
+	}
+
+	async fn submit(
+		&self,
+		tx: Transaction,
+		_signature: &Signature,
+	) -> Result<TransactionHash, DeliveryError> {
+		self.call_operation("submit").await?;
+		let gas_used = tx.gas_limit.unwrap_or(DEFAULT_GAS_USED);
+		Ok(self.mine(Some(tx), gas_used))
+	}
+
+	async fn wait_for_confirmation(
+		&self,
+		hash: &TransactionHash,
+		_confirmations: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		self.call_operation("wait_for_confirmation").await?;
+		self.receipt(hash)
+	}
+
+	async fn get_receipt(
+		&self,
+		hash: &TransactionHash,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		self.call_operation("get_receipt").await?;
+		self.receipt(hash)
+	}
+
+	async fn replace(
+		&self,
+		hash: &TransactionHash,
+		_gas: GasParams,
+	) -> Result<TransactionHash, DeliveryError> {
+		self.call_operation("replace").await?;
+		let receipt = self.receipt(hash)?;
+		Ok(self.mine(None, receipt.gas_used))
+	}
+
+	async fn cancel(&self, hash: &TransactionHash) -> Result<TransactionHash, DeliveryError> {
+		self.call_operation("cancel").await?;
+		self.receipt(hash)?;
+		Ok(self.mine(None, DEFAULT_GAS_USED))
+	}
+
+	async fn call(&self, _tx: &Transaction) -> Result<Vec<u8>, DeliveryError> {
+		self.call_operation("call").await?;
+		Ok(self.chain.lock().unwrap().call_result.clone())
+	}
+
+	async fn simulate(&self, _tx: &Transaction, _from: &Address) -> Result<(), DeliveryError> {
+		self.call_operation("simulate").await
+	}
+
+	async fn get_gas_price(&self) -> Result<U256, DeliveryError> {
+		self.call_operation("get_gas_price").await?;
+		Ok(self.chain.lock().unwrap().gas_price)
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		self.call_operation("fee_history").await?;
+		let gas_price = self.chain.lock().unwrap().gas_price;
+		Ok(FeeHistory {
+			base_fee_per_gas: vec![gas_price; block_count as usize],
+			priority_fees: vec![vec![U256::ZERO; reward_percentiles.len()]; block_count as usize],
+		})
+	}
+
+	async fn get_native_balance(&self, _address: &Address) -> Result<U256, DeliveryError> {
+		self.call_operation("get_native_balance").await?;
+		Ok(self.chain.lock().unwrap().native_balance)
+	}
+
+	async fn get_code(&self, _address: &Address) -> Result<Vec<u8>, DeliveryError> {
+		self.call_operation("get_code").await?;
+		Ok(Vec::new())
+	}
+
+	async fn get_block_number(&self) -> Result<u64, DeliveryError> {
+		self.call_operation("get_block_number").await?;
+		Ok(self.chain.lock().unwrap().block_number)
+	}
+
+	async fn get_block_hash(&self, number: u64) -> Result<Option<Vec<u8>>, DeliveryError> {
+		self.call_operation("get_block_hash").await?;
+		let block_number = self.chain.lock().unwrap().block_number;
+		Ok((number <= block_number).then(|| block_hash(self.chain_id, number)))
+	}
+}
+
+/// Returns the hash of the transaction mined into a block.
+fn transaction_hash(chain_id: u64, block_number: u64) -> Vec<u8> {
+	let mut hash = vec![0x7a; 16];
+	hash.extend_from_slice(&chain_id.to_be_bytes());
+	hash.extend_from_slice(&block_number.to_be_bytes());
+	hash
+}
+
+/// Returns the hash of a block, which never changes since the simulated
+/// chain does not reorg.
+fn block_hash(chain_id: u64, number: u64) -> Vec<u8> {
+	let mut hash = vec![0xb1; 16];
+	hash.extend_from_slice(&chain_id.to_be_bytes());
+	hash.extend_from_slice(&number.to_be_bytes());
+	hash
+}
+
+/// Factory function to create a mock delivery provider from configuration.
+///
+/// Required configuration parameters:
+/// - `chain_id`: Chain the provider delivers to
+///
+/// Optional configuration parameters:
+/// - `gas_price_gwei`: Gas price of the chain (default: 1)
+/// - `latency_ms`, `operation_latency_ms`, `fail_first`: see [`MockBehavior::from_config`]
+pub fn create_delivery(config: &toml::Value) -> Box<dyn DeliveryInterface> {
+	let chain_id = config
+		.get("chain_id")
+		.and_then(|v| v.as_integer())
+		.expect("chain_id is required for mock delivery") as u64;
+
+	let mut delivery = MockDelivery::new(chain_id).with_behavior(MockBehavior::from_config(config));
+	if let Some(gwei) = config.get("gas_price_gwei").and_then(|v| v.as_integer()) {
+		delivery = delivery.with_gas_price(U256::from(gwei as u64) * U256::from(1_000_000_000u64));
+	}
+	Box::new(delivery)
+}
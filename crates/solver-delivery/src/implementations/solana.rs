@@ -0,0 +1,1107 @@
+//! Solana delivery implementation.
+//!
+//! Delivers transactions to a Solana cluster over its JSON-RPC API. The
+//! solver's transactions carry a Solana instruction (see
+//! [`SolanaInstruction`]), which this provider compiles into a legacy
+//! transaction message paid for by its own keypair, signs through the
+//! account's ed25519 key and submits. Gas limits and prices map onto the
+//! compute budget: the limit caps the compute units of the transaction and the
+//! price is its priority fee in micro-lamports per compute unit.
+//!
+//! Transactions count as confirmed once the cluster reports them at the
+//! configured commitment level. Solana transactions cannot be replaced; one
+//! that is not included before its blockhash expires fails instead.
+
+use crate::{DeliveryError, DeliveryInterface, GasPayer};
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use solver_account::implementations::solana::SolanaKeypair;
+use solver_account::AccountInterface;
+use solver_types::{
+	program_data_logs, Address, ConfigSchema, FeeHistory, Field, FieldType, GasParams, Schema,
+	Signature, SolanaInstruction, Transaction, TransactionHash, TransactionReceipt,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Program setting the compute unit limit and price of a transaction.
+const COMPUTE_BUDGET_PROGRAM: &str = "ComputeBudget111111111111111111111111111111";
+
+/// Instruction of the compute budget program setting the compute unit limit.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+
+/// Instruction of the compute budget program setting the compute unit price.
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// Interval between checks of a transaction's status.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time the RPC endpoint has to answer a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON-RPC error codes of slots without an available block.
+const MISSING_BLOCK_CODES: [i64; 3] = [-32004, -32007, -32009];
+
+/// JSON-RPC error code of transactions rejected by preflight simulation.
+const PREFLIGHT_FAILURE_CODE: i64 = -32002;
+
+/// Commitment level of the cluster's view of a transaction or block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Commitment {
+	/// Processed by the connected node, and may still be rolled back.
+	Processed,
+	/// Voted on by a supermajority of the cluster.
+	Confirmed,
+	/// Rooted by a supermajority of the cluster.
+	Finalized,
+}
+
+impl Commitment {
+	/// Returns the name of the commitment level in the JSON-RPC API.
+	fn as_str(&self) -> &'static str {
+		match self {
+			Commitment::Processed => "processed",
+			Commitment::Confirmed => "confirmed",
+			Commitment::Finalized => "finalized",
+		}
+	}
+}
+
+impl FromStr for Commitment {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"processed" => Ok(Commitment::Processed),
+			"confirmed" => Ok(Commitment::Confirmed),
+			"finalized" => Ok(Commitment::Finalized),
+			_ => Err(format!(
+				"commitment must be processed, confirmed or finalized, got {}",
+				s
+			)),
+		}
+	}
+}
+
+/// Error returned by a JSON-RPC request.
+#[derive(Debug)]
+struct RpcError {
+	/// Error code reported by the node, if the request reached it.
+	code: Option<i64>,
+	/// Description of the error.
+	message: String,
+}
+
+impl RpcError {
+	/// Creates an error for a request that did not get a valid response.
+	fn transport(error: impl std::fmt::Display) -> Self {
+		Self {
+			code: None,
+			message: error.to_string(),
+		}
+	}
+}
+
+impl From<RpcError> for DeliveryError {
+	fn from(error: RpcError) -> Self {
+		DeliveryError::Network(error.message)
+	}
+}
+
+/// JSON-RPC response envelope.
+#[derive(Deserialize)]
+struct RpcResponse {
+	result: Option<serde_json::Value>,
+	error: Option<RpcErrorObject>,
+}
+
+/// Error object of a JSON-RPC response.
+#[derive(Deserialize)]
+struct RpcErrorObject {
+	code: i64,
+	message: String,
+}
+
+/// Result of a request reporting the slot it was served at.
+#[derive(Deserialize)]
+struct WithContext<T> {
+	value: T,
+}
+
+/// Recent blockhash a transaction is built against.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LatestBlockhash {
+	blockhash: String,
+	last_valid_block_height: u64,
+}
+
+/// Status of a submitted transaction.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignatureStatus {
+	/// Confirmed blocks built on top of the transaction's, `None` once rooted.
+	confirmations: Option<u64>,
+	/// Commitment level the cluster reached for the transaction.
+	confirmation_status: Option<Commitment>,
+}
+
+/// Transaction as returned by `getTransaction`.
+#[derive(Deserialize)]
+struct RpcTransaction {
+	slot: u64,
+	meta: Option<RpcTransactionMeta>,
+}
+
+/// Execution outcome of a transaction.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTransactionMeta {
+	err: Option<serde_json::Value>,
+	fee: u64,
+	log_messages: Option<Vec<String>>,
+}
+
+/// Outcome of a simulated transaction.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulationResult {
+	err: Option<serde_json::Value>,
+	logs: Option<Vec<String>>,
+	return_data: Option<ReturnData>,
+}
+
+/// Data returned by the program a simulated transaction invoked last.
+#[derive(Deserialize)]
+struct ReturnData {
+	/// Base64-encoded data and its encoding.
+	data: (String, String),
+}
+
+/// Account as returned by `getAccountInfo`.
+#[derive(Deserialize)]
+struct AccountInfo {
+	/// Base64-encoded data and its encoding.
+	data: (String, String),
+	executable: bool,
+}
+
+/// Priority fee paid in a recent slot.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrioritizationFee {
+	prioritization_fee: u64,
+}
+
+/// Block as returned by `getBlock` without transactions.
+#[derive(Deserialize)]
+struct RpcBlock {
+	blockhash: String,
+}
+
+/// Appends a length in Solana's compact-u16 encoding.
+fn push_length(out: &mut Vec<u8>, length: usize) {
+	let mut remaining = length;
+	loop {
+		let byte = (remaining & 0x7f) as u8;
+		remaining >>= 7;
+		if remaining == 0 {
+			out.push(byte);
+			return;
+		}
+		out.push(byte | 0x80);
+	}
+}
+
+/// Decodes a base58 string into a 32-byte key or hash.
+fn decode_key(value: &str) -> Result<[u8; 32], DeliveryError> {
+	bs58::decode(value)
+		.into_vec()
+		.ok()
+		.and_then(|bytes| bytes.try_into().ok())
+		.ok_or_else(|| DeliveryError::Network(format!("Invalid base58 key {}", value)))
+}
+
+/// Encodes a transaction hash, its first signature, as base58.
+fn encode_signature(hash: &TransactionHash) -> String {
+	bs58::encode(&hash.0).into_string()
+}
+
+/// Compiles instructions into a legacy transaction message paid for by
+/// `fee_payer`.
+///
+/// Accounts are ordered as the runtime expects: signers before non-signers
+/// and writable accounts before read-only ones, with the fee payer first.
+/// Within each group keys are sorted, as the Solana SDK compiles them, so
+/// messages match the SDK's byte for byte. Only the fee payer can sign, since
+/// no other key is available.
+fn compile_message(
+	fee_payer: [u8; 32],
+	instructions: &[SolanaInstruction],
+	blockhash: [u8; 32],
+) -> Result<Vec<u8>, DeliveryError> {
+	let mut keys: Vec<([u8; 32], bool, bool)> = vec![(fee_payer, true, true)];
+	let references = instructions.iter().flat_map(|instruction| {
+		instruction
+			.accounts
+			.iter()
+			.map(|account| (account.pubkey, account.is_signer, account.is_writable))
+			.chain(std::iter::once((instruction.program_id, false, false)))
+	});
+	for (pubkey, is_signer, is_writable) in references {
+		match keys.iter().position(|(key, _, _)| *key == pubkey) {
+			Some(index) => {
+				keys[index].1 |= is_signer;
+				keys[index].2 |= is_writable;
+			}
+			None => keys.push((pubkey, is_signer, is_writable)),
+		}
+	}
+
+	if keys.iter().skip(1).any(|(_, is_signer, _)| *is_signer) {
+		return Err(DeliveryError::TransactionFailed(
+			"Instructions signed by accounts other than the fee payer are not supported".into(),
+		));
+	}
+	if keys.len() > u8::MAX as usize {
+		return Err(DeliveryError::TransactionFailed(
+			"Transaction references too many accounts".into(),
+		));
+	}
+	keys[1..].sort_by_key(|(pubkey, is_signer, is_writable)| (!is_signer, !is_writable, *pubkey));
+
+	let index_of = |pubkey: &[u8; 32]| {
+		keys.iter()
+			.position(|(key, _, _)| key == pubkey)
+			.expect("every referenced key was added") as u8
+	};
+
+	// Header: signatures required, then read-only signed and unsigned accounts
+	let mut message = vec![
+		1,
+		0,
+		keys.iter()
+			.filter(|(_, is_signer, is_writable)| !is_signer && !is_writable)
+			.count() as u8,
+	];
+	push_length(&mut message, keys.len());
+	for (pubkey, _, _) in &keys {
+		message.extend_from_slice(pubkey);
+	}
+	message.extend_from_slice(&blockhash);
+	push_length(&mut message, instructions.len());
+	for instruction in instructions {
+		message.push(index_of(&instruction.program_id));
+		push_length(&mut message, instruction.accounts.len());
+		for account in &instruction.accounts {
+			message.push(index_of(&account.pubkey));
+		}
+		push_length(&mut message, instruction.data.len());
+		message.extend_from_slice(&instruction.data);
+	}
+	Ok(message)
+}
+
+/// Serializes a signed transaction from its message and fee payer signature.
+fn serialize_transaction(signature: &[u8], message: &[u8]) -> Vec<u8> {
+	let mut transaction = Vec::with_capacity(1 + signature.len() + message.len());
+	push_length(&mut transaction, 1);
+	transaction.extend_from_slice(signature);
+	transaction.extend_from_slice(message);
+	transaction
+}
+
+/// Solana delivery implementation.
+pub struct SolanaDelivery {
+	/// HTTP client for JSON-RPC requests.
+	client: reqwest::Client,
+	/// JSON-RPC endpoint of the cluster.
+	rpc_url: String,
+	/// Chain ID the solver knows the cluster by.
+	chain_id: u64,
+	/// Commitment level transactions are confirmed at.
+	commitment: Commitment,
+	/// Account paying for and signing transactions.
+	signer: Box<dyn AccountInterface>,
+	/// Public key of the signer.
+	fee_payer: [u8; 32],
+	/// Last block height the blockhash of each pending transaction is valid
+	/// at, keyed by transaction hash.
+	expiries: Mutex<HashMap<Vec<u8>, u64>>,
+}
+
+impl SolanaDelivery {
+	/// Creates a provider for the cluster at `rpc_url`, signing with `signer`.
+	///
+	/// The signer must hold an ed25519 key.
+	pub async fn new(
+		rpc_url: &str,
+		chain_id: u64,
+		commitment: Commitment,
+		signer: Box<dyn AccountInterface>,
+	) -> Result<Self, DeliveryError> {
+		let fee_payer = signer
+			.ed25519_public_key()
+			.await
+			.ok()
+			.and_then(|key| key.0.try_into().ok())
+			.ok_or_else(|| {
+				DeliveryError::Network("Solana signer must hold an ed25519 key".into())
+			})?;
+		let client = reqwest::Client::builder()
+			.timeout(REQUEST_TIMEOUT)
+			.build()
+			.map_err(|e| DeliveryError::Network(e.to_string()))?;
+
+		let delivery = Self {
+			client,
+			rpc_url: rpc_url.to_string(),
+			chain_id,
+			commitment,
+			signer,
+			fee_payer,
+			expiries: Mutex::new(HashMap::new()),
+		};
+		delivery.get_block_number().await?;
+		Ok(delivery)
+	}
+
+	/// Sends a JSON-RPC request and decodes its result.
+	async fn rpc<T: DeserializeOwned>(
+		&self,
+		method: &str,
+		params: serde_json::Value,
+	) -> Result<T, RpcError> {
+		let request = json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": method,
+			"params": params,
+		});
+		let body = self
+			.client
+			.post(&self.rpc_url)
+			.header(reqwest::header::CONTENT_TYPE, "application/json")
+			.body(request.to_string())
+			.send()
+			.await
+			.map_err(RpcError::transport)?
+			.bytes()
+			.await
+			.map_err(RpcError::transport)?;
+
+		let response: RpcResponse = serde_json::from_slice(&body).map_err(RpcError::transport)?;
+		if let Some(error) = response.error {
+			return Err(RpcError {
+				code: Some(error.code),
+				message: format!("{} failed: {}", method, error.message),
+			});
+		}
+		serde_json::from_value(response.result.unwrap_or_default()).map_err(RpcError::transport)
+	}
+
+	/// Commitment level transaction and block lookups are made at, which
+	/// cannot be lower than confirmed.
+	fn lookup_commitment(&self) -> Commitment {
+		self.commitment.max(Commitment::Confirmed)
+	}
+
+	/// Returns the instructions of a transaction, preceded by the compute
+	/// budget instructions its gas limit and price translate into.
+	fn instructions(&self, tx: &Transaction) -> Result<Vec<SolanaInstruction>, DeliveryError> {
+		let instruction = SolanaInstruction::from_transaction(tx)
+			.map_err(|e| DeliveryError::TransactionFailed(e.to_string()))?;
+		let compute_budget = decode_key(COMPUTE_BUDGET_PROGRAM)?;
+
+		let mut instructions = Vec::new();
+		if let Some(limit) = tx.gas_limit {
+			let mut data = vec![SET_COMPUTE_UNIT_LIMIT];
+			data.extend_from_slice(&u32::try_from(limit).unwrap_or(u32::MAX).to_le_bytes());
+			instructions.push(SolanaInstruction {
+				program_id: compute_budget,
+				accounts: Vec::new(),
+				data,
+			});
+		}
+		if let Some(price) = tx.gas_price.or(tx.max_priority_fee_per_gas) {
+			let mut data = vec![SET_COMPUTE_UNIT_PRICE];
+			data.extend_from_slice(&u64::try_from(price).unwrap_or(u64::MAX).to_le_bytes());
+			instructions.push(SolanaInstruction {
+				program_id: compute_budget,
+				accounts: Vec::new(),
+				data,
+			});
+		}
+		instructions.push(instruction);
+		Ok(instructions)
+	}
+
+	/// Simulates a transaction against the latest state without signing it.
+	async fn simulate_transaction(
+		&self,
+		tx: &Transaction,
+	) -> Result<SimulationResult, DeliveryError> {
+		// The node replaces the blockhash and skips signature verification
+		let message = compile_message(self.fee_payer, &self.instructions(tx)?, [0; 32])?;
+		let transaction = serialize_transaction(&[0; 64], &message);
+		let result: WithContext<SimulationResult> = self
+			.rpc(
+				"simulateTransaction",
+				json!([
+					base64::engine::general_purpose::STANDARD.encode(transaction),
+					{
+						"encoding": "base64",
+						"sigVerify": false,
+						"replaceRecentBlockhash": true,
+						"commitment": self.commitment.as_str(),
+					}
+				]),
+			)
+			.await?;
+		Ok(result.value)
+	}
+
+	/// Returns whether the blockhash of a transaction submitted by this
+	/// provider expired, so it can no longer be included.
+	async fn expired(&self, hash: &TransactionHash) -> Result<bool, DeliveryError> {
+		let Some(last_valid) = self.expiries.lock().unwrap().get(&hash.0).copied() else {
+			return Ok(false);
+		};
+		let block_height: u64 = self
+			.rpc(
+				"getBlockHeight",
+				json!([{ "commitment": self.commitment.as_str() }]),
+			)
+			.await?;
+		Ok(block_height > last_valid)
+	}
+}
+
+/// Describes why a simulated transaction failed, with the last log line the
+/// program wrote.
+fn simulation_error(err: &serde_json::Value, logs: Option<&Vec<String>>) -> String {
+	match logs.and_then(|logs| logs.last()) {
+		Some(log) => format!("{} ({})", err, log),
+		None => err.to_string(),
+	}
+}
+
+/// Configuration schema for Solana delivery provider.
+pub struct SolanaDeliverySchema;
+
+impl ConfigSchema for SolanaDeliverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("rpc_url", FieldType::String).with_validator(|value| {
+					let url = value.as_str().unwrap();
+					if url.starts_with("http://") || url.starts_with("https://") {
+						Ok(())
+					} else {
+						Err("RPC URL must start with http:// or https://".to_string())
+					}
+				}),
+				Field::new("private_key", FieldType::String).with_validator(|value| {
+					SolanaKeypair::from_base58(value.as_str().unwrap())
+						.map(|_| ())
+						.map_err(|e| e.to_string())
+				}),
+				Field::new(
+					"chain_id",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+			],
+			// Optional fields
+			vec![Field::new("commitment", FieldType::String)
+				.with_validator(|value| value.as_str().unwrap().parse::<Commitment>().map(|_| ()))],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl DeliveryInterface for SolanaDelivery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(SolanaDeliverySchema)
+	}
+
+	fn signs_transactions(&self) -> bool {
+		true
+	}
+
+	fn gas_payer(&self) -> GasPayer {
+		GasPayer::Address(Address(self.fee_payer.to_vec()))
+	}
+
+	async fn submit(
+		&self,
+		tx: Transaction,
+		_signature: &Signature,
+	) -> Result<TransactionHash, DeliveryError> {
+		let instructions = self.instructions(&tx)?;
+		let latest: WithContext<LatestBlockhash> = self
+			.rpc(
+				"getLatestBlockhash",
+				json!([{ "commitment": self.commitment.as_str() }]),
+			)
+			.await?;
+		let blockhash = decode_key(&latest.value.blockhash)?;
+
+		let message = compile_message(self.fee_payer, &instructions, blockhash)?;
+		let signature = self
+			.signer
+			.sign_ed25519(&message)
+			.await
+			.map_err(|e| DeliveryError::TransactionFailed(e.to_string()))?;
+		let transaction = serialize_transaction(&signature.0, &message);
+
+		let result: Result<String, RpcError> = self
+			.rpc(
+				"sendTransaction",
+				json!([
+					base64::engine::general_purpose::STANDARD.encode(transaction),
+					{
+						"encoding": "base64",
+						"preflightCommitment": self.commitment.as_str(),
+					}
+				]),
+			)
+			.await;
+		match result {
+			Ok(_) => {}
+			Err(RpcError {
+				code: Some(PREFLIGHT_FAILURE_CODE),
+				message,
+			}) => return Err(DeliveryError::TransactionFailed(message)),
+			Err(e) => return Err(e.into()),
+		}
+
+		let hash = TransactionHash(signature.0);
+		self.expiries
+			.lock()
+			.unwrap()
+			.insert(hash.0.clone(), latest.value.last_valid_block_height);
+		tracing::info!(
+			chain_id = self.chain_id,
+			signature = %encode_signature(&hash),
+			"Submitted transaction"
+		);
+		Ok(hash)
+	}
+
+	async fn wait_for_confirmation(
+		&self,
+		hash: &TransactionHash,
+		confirmations: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		let signature = encode_signature(hash);
+		loop {
+			let statuses: WithContext<Vec<Option<SignatureStatus>>> = self
+				.rpc(
+					"getSignatureStatuses",
+					json!([[signature], { "searchTransactionHistory": true }]),
+				)
+				.await?;
+
+			match statuses.value.into_iter().next().flatten() {
+				Some(status) => {
+					let reached = status
+						.confirmation_status
+						.is_some_and(|commitment| commitment >= self.commitment);
+					let buried = status
+						.confirmations
+						.is_none_or(|count| count >= confirmations);
+					// The receipt may not be served at the lookup commitment yet
+					if reached && buried {
+						if let Ok(receipt) = self.get_receipt(hash).await {
+							self.expiries.lock().unwrap().remove(&hash.0);
+							return Ok(receipt);
+						}
+					}
+				}
+				None => {
+					if self.expired(hash).await? {
+						self.expiries.lock().unwrap().remove(&hash.0);
+						return Err(DeliveryError::TransactionFailed(format!(
+							"Blockhash of transaction {} expired before it was included",
+							signature
+						)));
+					}
+				}
+			}
+
+			tokio::time::sleep(POLL_INTERVAL).await;
+		}
+	}
+
+	/// Returns the receipt of a transaction, with its fee in lamports reported
+	/// as the gas used at a gas price of one.
+	async fn get_receipt(
+		&self,
+		hash: &TransactionHash,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		let transaction: Option<RpcTransaction> = self
+			.rpc(
+				"getTransaction",
+				json!([
+					encode_signature(hash),
+					{
+						"encoding": "json",
+						"commitment": self.lookup_commitment().as_str(),
+						"maxSupportedTransactionVersion": 0,
+					}
+				]),
+			)
+			.await?;
+		let transaction = transaction
+			.ok_or_else(|| DeliveryError::Network("Transaction not found".to_string()))?;
+		let meta = transaction
+			.meta
+			.ok_or_else(|| DeliveryError::Network("Transaction has no status".to_string()))?;
+
+		Ok(TransactionReceipt {
+			hash: hash.clone(),
+			block_number: transaction.slot,
+			success: meta.err.is_none(),
+			gas_used: meta.fee,
+			effective_gas_price: U256::from(1),
+			logs: program_data_logs(&meta.log_messages.unwrap_or_default()),
+		})
+	}
+
+	async fn replace(
+		&self,
+		_hash: &TransactionHash,
+		_gas: GasParams,
+	) -> Result<TransactionHash, DeliveryError> {
+		Err(DeliveryError::TransactionFailed(
+			"Solana transactions cannot be replaced, they expire with their blockhash".into(),
+		))
+	}
+
+	async fn cancel(&self, _hash: &TransactionHash) -> Result<TransactionHash, DeliveryError> {
+		Err(DeliveryError::TransactionFailed(
+			"Solana transactions cannot be cancelled, they expire with their blockhash".into(),
+		))
+	}
+
+	async fn call(&self, tx: &Transaction) -> Result<Vec<u8>, DeliveryError> {
+		let result = self.simulate_transaction(tx).await?;
+		if let Some(err) = &result.err {
+			return Err(DeliveryError::TransactionFailed(simulation_error(
+				err,
+				result.logs.as_ref(),
+			)));
+		}
+
+		let Some(return_data) = result.return_data else {
+			return Ok(Vec::new());
+		};
+		base64::engine::general_purpose::STANDARD
+			.decode(return_data.data.0)
+			.map_err(|e| DeliveryError::Network(format!("Invalid return data: {}", e)))
+	}
+
+	/// Simulates a transaction paid for by the provider's keypair; `from` is
+	/// ignored since only that keypair can sign.
+	async fn simulate(&self, tx: &Transaction, _from: &Address) -> Result<(), DeliveryError> {
+		let result = self.simulate_transaction(tx).await?;
+		match &result.err {
+			Some(err) => Err(DeliveryError::SimulationFailed(simulation_error(
+				err,
+				result.logs.as_ref(),
+			))),
+			None => Ok(()),
+		}
+	}
+
+	/// Returns the median priority fee of recent slots, in micro-lamports per
+	/// compute unit.
+	async fn get_gas_price(&self) -> Result<U256, DeliveryError> {
+		let fees: Vec<PrioritizationFee> =
+			self.rpc("getRecentPrioritizationFees", json!([])).await?;
+		let mut fees: Vec<u64> = fees.into_iter().map(|fee| fee.prioritization_fee).collect();
+		fees.sort_unstable();
+		Ok(U256::from(fees.get(fees.len() / 2).copied().unwrap_or(0)))
+	}
+
+	async fn fee_history(
+		&self,
+		_block_count: u64,
+		_reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError> {
+		Err(DeliveryError::Network(
+			"Fee history is not available on Solana".into(),
+		))
+	}
+
+	async fn get_native_balance(&self, address: &Address) -> Result<U256, DeliveryError> {
+		let balance: WithContext<u64> = self
+			.rpc(
+				"getBalance",
+				json!([
+					bs58::encode(&address.0).into_string(),
+					{ "commitment": self.commitment.as_str() }
+				]),
+			)
+			.await?;
+		Ok(U256::from(balance.value))
+	}
+
+	/// Returns the data of a program account, empty for accounts that are not
+	/// executable.
+	async fn get_code(&self, address: &Address) -> Result<Vec<u8>, DeliveryError> {
+		let account: WithContext<Option<AccountInfo>> = self
+			.rpc(
+				"getAccountInfo",
+				json!([
+					bs58::encode(&address.0).into_string(),
+					{ "encoding": "base64", "commitment": self.commitment.as_str() }
+				]),
+			)
+			.await?;
+		match account.value {
+			Some(account) if account.executable => base64::engine::general_purpose::STANDARD
+				.decode(account.data.0)
+				.map_err(|e| DeliveryError::Network(format!("Invalid account data: {}", e))),
+			_ => Ok(Vec::new()),
+		}
+	}
+
+	/// Returns the latest slot at the configured commitment level.
+	async fn get_block_number(&self) -> Result<u64, DeliveryError> {
+		Ok(self
+			.rpc(
+				"getSlot",
+				json!([{ "commitment": self.commitment.as_str() }]),
+			)
+			.await?)
+	}
+
+	/// Returns the blockhash of a slot, or `None` if the slot was skipped or
+	/// its block is not available.
+	async fn get_block_hash(&self, number: u64) -> Result<Option<Vec<u8>>, DeliveryError> {
+		let block: Result<Option<RpcBlock>, RpcError> = self
+			.rpc(
+				"getBlock",
+				json!([
+					number,
+					{
+						"commitment": self.lookup_commitment().as_str(),
+						"transactionDetails": "none",
+						"rewards": false,
+						"maxSupportedTransactionVersion": 0,
+					}
+				]),
+			)
+			.await;
+		match block {
+			Ok(Some(block)) => Ok(Some(decode_key(&block.blockhash)?.to_vec())),
+			Ok(None) => Ok(None),
+			Err(RpcError {
+				code: Some(code), ..
+			}) if MISSING_BLOCK_CODES.contains(&code) => Ok(None),
+			Err(e) => Err(e.into()),
+		}
+	}
+}
+
+/// Factory function to create a Solana delivery provider from configuration.
+///
+/// Required configuration parameters:
+/// - `rpc_url`: The JSON-RPC endpoint URL of the cluster
+/// - `chain_id`: The chain ID the solver knows the cluster by
+/// - `private_key`: The base58-encoded keypair paying for and signing transactions
+///
+/// Optional configuration parameters:
+/// - `commitment`: Commitment level transactions are confirmed at (default: "confirmed")
+pub fn create_solana_delivery(config: &toml::Value) -> Box<dyn DeliveryInterface> {
+	let rpc_url = config
+		.get("rpc_url")
+		.and_then(|v| v.as_str())
+		.expect("rpc_url is required");
+
+	let chain_id = config
+		.get("chain_id")
+		.and_then(|v| v.as_integer())
+		.expect("chain_id is required") as u64;
+
+	let private_key = config
+		.get("private_key")
+		.and_then(|v| v.as_str())
+		.expect("private_key is required");
+	let signer = SolanaKeypair::from_base58(private_key).expect("Invalid Solana private key");
+
+	let commitment = config
+		.get("commitment")
+		.and_then(|v| v.as_str())
+		.map(|commitment| commitment.parse().expect("Invalid commitment"))
+		.unwrap_or(Commitment::Confirmed);
+
+	let delivery = tokio::task::block_in_place(|| {
+		tokio::runtime::Handle::current().block_on(async {
+			SolanaDelivery::new(rpc_url, chain_id, commitment, Box::new(signer)).await
+		})
+	});
+
+	Box::new(delivery.expect("Failed to create Solana delivery service"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use solver_types::SolanaAccountMeta;
+
+	/// Fee payer of the test transactions.
+	const PAYER: [u8; 32] = [1; 32];
+
+	/// Recent blockhash of the test transactions.
+	const BLOCKHASH: [u8; 32] = [9; 32];
+
+	/// System program, whose public key is all zeros.
+	const SYSTEM_PROGRAM: [u8; 32] = [0; 32];
+
+	/// Returns an account reference.
+	fn account(pubkey: [u8; 32], is_signer: bool, is_writable: bool) -> SolanaAccountMeta {
+		SolanaAccountMeta {
+			pubkey,
+			is_signer,
+			is_writable,
+		}
+	}
+
+	/// Returns a system program transfer of `lamports` from the fee payer.
+	fn transfer(to: [u8; 32], lamports: u64) -> SolanaInstruction {
+		let mut data = 2u32.to_le_bytes().to_vec();
+		data.extend_from_slice(&lamports.to_le_bytes());
+		SolanaInstruction {
+			program_id: SYSTEM_PROGRAM,
+			accounts: vec![account(PAYER, true, true), account(to, false, true)],
+			data,
+		}
+	}
+
+	/// Reads a compact-u16 length, advancing `offset` past it.
+	fn read_length(bytes: &[u8], offset: &mut usize) -> usize {
+		let mut length = 0;
+		for shift in 0..3 {
+			let byte = bytes[*offset];
+			*offset += 1;
+			length |= ((byte & 0x7f) as usize) << (7 * shift);
+			if byte & 0x80 == 0 {
+				break;
+			}
+		}
+		length
+	}
+
+	/// Reads `count` bytes, advancing `offset` past them.
+	fn read<'a>(bytes: &'a [u8], offset: &mut usize, count: usize) -> &'a [u8] {
+		let slice = &bytes[*offset..*offset + count];
+		*offset += count;
+		slice
+	}
+
+	/// Decodes a serialized transaction back into its signature, blockhash and
+	/// instructions, recovering the account flags from the message header.
+	fn decode_transaction(bytes: &[u8]) -> (Vec<u8>, [u8; 32], Vec<SolanaInstruction>) {
+		let mut offset = 0;
+		assert_eq!(read_length(bytes, &mut offset), 1);
+		let signature = read(bytes, &mut offset, 64).to_vec();
+
+		let header = read(bytes, &mut offset, 3).to_vec();
+		let (signed, readonly_signed, readonly_unsigned) =
+			(header[0] as usize, header[1] as usize, header[2] as usize);
+		let key_count = read_length(bytes, &mut offset);
+		let keys: Vec<SolanaAccountMeta> = (0..key_count)
+			.map(|index| {
+				let pubkey = read(bytes, &mut offset, 32).try_into().unwrap();
+				let is_signer = index < signed;
+				let is_writable = if is_signer {
+					index < signed - readonly_signed
+				} else {
+					index < key_count - readonly_unsigned
+				};
+				account(pubkey, is_signer, is_writable)
+			})
+			.collect();
+		let blockhash = read(bytes, &mut offset, 32).try_into().unwrap();
+
+		let instructions = (0..read_length(bytes, &mut offset))
+			.map(|_| {
+				let program_id = keys[read(bytes, &mut offset, 1)[0] as usize].pubkey;
+				let account_count = read_length(bytes, &mut offset);
+				let accounts = read(bytes, &mut offset, account_count)
+					.iter()
+					.map(|&index| keys[index as usize].clone())
+					.collect();
+				let data_length = read_length(bytes, &mut offset);
+				let data = read(bytes, &mut offset, data_length).to_vec();
+				SolanaInstruction {
+					program_id,
+					accounts,
+					data,
+				}
+			})
+			.collect();
+		assert_eq!(offset, bytes.len(), "trailing bytes");
+		(signature, blockhash, instructions)
+	}
+
+	#[test]
+	fn encodes_lengths_as_compact_u16() {
+		// Vectors of the Solana SDK's short_vec encoding
+		let cases: [(usize, &[u8]); 7] = [
+			(0x0, &[0x00]),
+			(0x7f, &[0x7f]),
+			(0x80, &[0x80, 0x01]),
+			(0xff, &[0xff, 0x01]),
+			(0x100, &[0x80, 0x02]),
+			(0x3fff, &[0xff, 0x7f]),
+			(0x4000, &[0x80, 0x80, 0x01]),
+		];
+		for (length, expected) in cases {
+			let mut out = Vec::new();
+			push_length(&mut out, length);
+			assert_eq!(out, expected, "length {:#x}", length);
+		}
+	}
+
+	#[test]
+	fn decodes_compute_budget_program() {
+		assert_eq!(
+			hex::encode(decode_key(COMPUTE_BUDGET_PROGRAM).unwrap()),
+			"0306466fe5211732ffecadba72c39be7bc8ce5bbc5f7126b2c439b3a40000000"
+		);
+		assert!(decode_key("not base58 0OIl").is_err());
+		assert!(decode_key("11111111").is_err());
+	}
+
+	#[test]
+	fn compiles_transfer_as_the_sdk_does() {
+		let recipient = [2; 32];
+		let message = compile_message(PAYER, &[transfer(recipient, 1_000_000)], BLOCKHASH).unwrap();
+
+		// Legacy message of a system transfer, laid out as the SDK serializes it
+		let mut expected = vec![1, 0, 1, 3];
+		expected.extend_from_slice(&PAYER);
+		expected.extend_from_slice(&recipient);
+		expected.extend_from_slice(&SYSTEM_PROGRAM);
+		expected.extend_from_slice(&BLOCKHASH);
+		expected.extend_from_slice(&[1, 2, 2, 0, 1, 12, 2, 0, 0, 0]);
+		expected.extend_from_slice(&1_000_000u64.to_le_bytes());
+		assert_eq!(message, expected);
+
+		let transaction = serialize_transaction(&[7; 64], &message);
+		assert_eq!(transaction[0], 1);
+		assert_eq!(&transaction[1..65], &[7; 64]);
+		assert_eq!(&transaction[65..], &message[..]);
+	}
+
+	#[test]
+	fn orders_accounts_as_the_sdk_does() {
+		let compute_budget = decode_key(COMPUTE_BUDGET_PROGRAM).unwrap();
+		let program = [0xee; 32];
+		let (readonly_low, readonly_high) = ([0x30; 32], [0x03; 32]);
+		let (writable_low, writable_high) = ([0x50; 32], [0x05; 32]);
+		let instructions = vec![
+			SolanaInstruction {
+				program_id: compute_budget,
+				accounts: Vec::new(),
+				data: vec![SET_COMPUTE_UNIT_LIMIT, 0x40, 0x0d, 0x03, 0x00],
+			},
+			SolanaInstruction {
+				program_id: program,
+				accounts: vec![
+					account(readonly_low, false, false),
+					account(writable_low, false, true),
+					account(PAYER, true, true),
+					account(readonly_high, false, false),
+					account(writable_high, false, true),
+					// Writable in one reference makes the account writable
+					account(readonly_high, false, true),
+				],
+				data: vec![0xaa],
+			},
+		];
+		let message = compile_message(PAYER, &instructions, BLOCKHASH).unwrap();
+
+		// Payer, then writable and read-only accounts each in key order
+		let keys: Vec<[u8; 32]> = message[4..4 + 7 * 32]
+			.chunks(32)
+			.map(|key| key.try_into().unwrap())
+			.collect();
+		assert_eq!(&message[..4], &[1, 0, 3, 7]);
+		assert_eq!(
+			keys,
+			vec![
+				PAYER,
+				readonly_high,
+				writable_high,
+				writable_low,
+				compute_budget,
+				readonly_low,
+				program,
+			]
+		);
+		// Instruction count and the compute unit limit, then the program call
+		let compiled = [
+			vec![2, 4, 0, 5, SET_COMPUTE_UNIT_LIMIT, 0x40, 0x0d, 0x03, 0x00],
+			vec![6, 6, 5, 3, 0, 1, 2, 1, 1, 0xaa],
+		];
+		assert_eq!(&message[4 + 8 * 32..], compiled.concat());
+	}
+
+	#[test]
+	fn round_trips_serialized_transactions() {
+		let compute_budget = decode_key(COMPUTE_BUDGET_PROGRAM).unwrap();
+		let mut price = vec![SET_COMPUTE_UNIT_PRICE];
+		price.extend_from_slice(&50_000u64.to_le_bytes());
+		let instructions = vec![
+			SolanaInstruction {
+				program_id: compute_budget,
+				accounts: Vec::new(),
+				data: price,
+			},
+			transfer([2; 32], 42),
+			SolanaInstruction {
+				program_id: [0xee; 32],
+				accounts: vec![
+					account([0x30; 32], false, false),
+					account([2; 32], false, true),
+				],
+				// Longer than a single-byte compact-u16 length
+				data: vec![0x5a; 300],
+			},
+		];
+		let message = compile_message(PAYER, &instructions, BLOCKHASH).unwrap();
+		let transaction = serialize_transaction(&[7; 64], &message);
+
+		let (signature, blockhash, decoded) = decode_transaction(&transaction);
+		assert_eq!(signature, vec![7; 64]);
+		assert_eq!(blockhash, BLOCKHASH);
+		assert_eq!(decoded, instructions);
+	}
+
+	#[test]
+	fn rejects_instructions_signed_by_other_accounts() {
+		let mut instruction = transfer([2; 32], 1);
+		instruction.accounts[1].is_signer = true;
+		assert!(matches!(
+			compile_message(PAYER, &[instruction], BLOCKHASH),
+			Err(DeliveryError::TransactionFailed(_))
+		));
+	}
+}
@@ -4,17 +4,34 @@
 //! It provides abstractions for different delivery mechanisms across multiple
 //! blockchain networks, managing transaction signing, submission, and confirmation.
 
+use alloy_primitives::U256;
 use async_trait::async_trait;
+use gas::GasEstimator;
 use solver_account::AccountService;
-use solver_types::{ConfigSchema, Signature, Transaction, TransactionHash, TransactionReceipt};
-use std::sync::Arc;
+use solver_storage::cache::RpcCache;
+use solver_types::{
+	Address, Classify, ConfigSchema, EndpointFailure, ErrorCategory, ErrorClass, FeeHistory,
+	GasParams, GasTokenAmount, NetworkRegistry, Signature, Transaction, TransactionHash,
+	TransactionReceipt,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use thiserror::Error;
 
+pub mod gas;
+
 /// Re-export implementations
 pub mod implementations {
 	pub mod evm {
 		pub mod alloy;
+		pub mod flashbots;
+		pub mod oz_relayer;
+		pub mod user_operation;
 	}
+	#[cfg(feature = "testing")]
+	pub mod mock;
+	pub mod solana;
 }
 
 /// Errors that can occur during transaction delivery operations.
@@ -29,6 +46,35 @@ pub enum DeliveryError {
 	/// Error that occurs when no suitable provider is available for the operation.
 	#[error("No provider available")]
 	NoProviderAvailable,
+	/// Error that occurs when the solver cannot cover the gas cost of a transaction.
+	#[error("Insufficient gas balance: {0}")]
+	InsufficientGasBalance(String),
+	/// Error that occurs when simulating a transaction shows it would revert.
+	#[error("Simulation failed: {0}")]
+	SimulationFailed(String),
+	/// Error that occurs when a transaction is submitted during a dry run.
+	#[error("Transaction submission is disabled in dry runs")]
+	DryRun,
+	/// Error that occurs when a transaction or request cannot be signed.
+	#[error("Signing failed: {0}")]
+	Signing(String),
+}
+
+impl Classify for DeliveryError {
+	fn class(&self) -> ErrorClass {
+		match self {
+			Self::Network(_) => ErrorClass::transient(ErrorCategory::Chain),
+			// Clears up once the solver's gas balance is topped up
+			Self::InsufficientGasBalance(_) => ErrorClass::transient(ErrorCategory::Chain),
+			Self::TransactionFailed(_) | Self::SimulationFailed(_) => {
+				ErrorClass::permanent(ErrorCategory::Chain)
+			}
+			Self::NoProviderAvailable | Self::DryRun => {
+				ErrorClass::permanent(ErrorCategory::Configuration)
+			}
+			Self::Signing(_) => ErrorClass::permanent(ErrorCategory::Signing),
+		}
+	}
 }
 
 /// Trait defining the interface for transaction delivery providers.
@@ -55,6 +101,38 @@ pub trait DeliveryInterface: Send + Sync {
 		signature: &Signature,
 	) -> Result<TransactionHash, DeliveryError>;
 
+	/// Returns whether the provider signs transactions with a key of its own.
+	///
+	/// Providers of chains outside the EVM family, such as Solana, sign the
+	/// message they compile from a transaction, which only exists once the
+	/// provider built it. Transactions for these providers are not signed by
+	/// the account service, and `submit` receives an empty signature.
+	fn signs_transactions(&self) -> bool {
+		false
+	}
+
+	/// Returns who pays for the gas of transactions submitted through this
+	/// provider.
+	///
+	/// The solver's account pays by default. Providers sending transactions
+	/// from another account, or having them sponsored, report so here, so that
+	/// the gas balance checked before submission is the one actually charged.
+	fn gas_payer(&self) -> GasPayer {
+		GasPayer::Account
+	}
+
+	/// Submits a signed transaction without exposing it to the public mempool.
+	///
+	/// Used for fills that must land within the next few blocks. Providers
+	/// without a private route submit the transaction like any other.
+	async fn submit_private(
+		&self,
+		tx: Transaction,
+		signature: &Signature,
+	) -> Result<TransactionHash, DeliveryError> {
+		self.submit(tx, signature).await
+	}
+
 	/// Waits for a transaction to be confirmed with the specified number of confirmations.
 	///
 	/// Blocks until the transaction has received the required number of confirmations
@@ -73,6 +151,126 @@ pub trait DeliveryInterface: Send + Sync {
 		&self,
 		hash: &TransactionHash,
 	) -> Result<TransactionReceipt, DeliveryError>;
+
+	/// Re-submits a pending transaction with new fee parameters.
+	///
+	/// The replacement reuses the original nonce, so only one of the two
+	/// transactions can be mined. Fees are raised to at least the minimum
+	/// increase nodes require to accept a replacement. Returns the hash of the
+	/// replacement transaction.
+	async fn replace(
+		&self,
+		hash: &TransactionHash,
+		gas: GasParams,
+	) -> Result<TransactionHash, DeliveryError>;
+
+	/// Cancels a pending transaction.
+	///
+	/// Replaces it with a zero-value transfer to the sender at the same nonce
+	/// and the minimum fee increase. Returns the hash of the cancelling
+	/// transaction.
+	async fn cancel(&self, hash: &TransactionHash) -> Result<TransactionHash, DeliveryError>;
+
+	/// Executes a transaction as a read-only call without submitting it.
+	///
+	/// Returns the raw return data, allowing view functions such as token
+	/// allowances and balances to be queried through the delivery provider.
+	async fn call(&self, tx: &Transaction) -> Result<Vec<u8>, DeliveryError>;
+
+	/// Simulates a transaction sent by `from` against the latest block.
+	///
+	/// Fails with [`DeliveryError::SimulationFailed`] carrying the revert
+	/// reason if the transaction would revert. Providers unable to simulate
+	/// transactions accept every transaction by default.
+	async fn simulate(&self, _tx: &Transaction, _from: &Address) -> Result<(), DeliveryError> {
+		Ok(())
+	}
+
+	/// Returns the current gas price in the chain's gas token.
+	async fn get_gas_price(&self) -> Result<U256, DeliveryError>;
+
+	/// Returns base fees and priority fee percentiles of recent blocks.
+	///
+	/// Covers the latest `block_count` blocks, with priority fees reported at
+	/// each of the given percentiles.
+	async fn fee_history(
+		&self,
+		block_count: u64,
+		reward_percentiles: &[f64],
+	) -> Result<FeeHistory, DeliveryError>;
+
+	/// Returns the native (gas token) balance of an address.
+	async fn get_native_balance(&self, address: &Address) -> Result<U256, DeliveryError>;
+
+	/// Returns the contract code deployed at an address, empty for accounts
+	/// without code.
+	async fn get_code(&self, address: &Address) -> Result<Vec<u8>, DeliveryError>;
+
+	/// Returns the number of the latest block.
+	async fn get_block_number(&self) -> Result<u64, DeliveryError>;
+
+	/// Returns the hash of the canonical block at a height, or `None` if the
+	/// chain has not reached it.
+	async fn get_block_hash(&self, number: u64) -> Result<Option<Vec<u8>>, DeliveryError>;
+
+	/// Probes the provider's RPC endpoints and returns those that became
+	/// unhealthy since the last check, including failures seen while serving
+	/// requests.
+	///
+	/// Providers with a single endpoint report nothing by default.
+	async fn check_endpoints(&self) -> Vec<EndpointFailure> {
+		Vec::new()
+	}
+}
+
+/// Payer of the gas of a provider's transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GasPayer {
+	/// The solver's account.
+	Account,
+	/// Another address, such as a smart account executing the transactions.
+	Address(Address),
+	/// A sponsor, leaving nothing for the solver to pay.
+	Sponsored,
+}
+
+/// Confirmations required for transactions on a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmationPolicy {
+	/// Number of confirmations after which a transaction is final.
+	pub confirmations: u64,
+	/// How long to wait for the confirmations, if bounded by the solver
+	/// rather than the provider.
+	pub timeout: Option<Duration>,
+}
+
+/// Number of submitted transactions whose chain is remembered.
+const TRACKED_TRANSACTIONS: usize = 10_000;
+
+/// Chains of recently submitted transactions, keyed by hash.
+///
+/// The oldest entries are dropped once the capacity is reached; lookups for
+/// their hashes fall back to asking every provider.
+#[derive(Default)]
+struct TransactionChains {
+	/// Chain of each tracked transaction.
+	chains: HashMap<Vec<u8>, u64>,
+	/// Tracked hashes, oldest first.
+	order: VecDeque<Vec<u8>>,
+}
+
+impl TransactionChains {
+	/// Remembers the chain a transaction was submitted to.
+	fn insert(&mut self, hash: &TransactionHash, chain_id: u64) {
+		if self.chains.insert(hash.0.clone(), chain_id).is_none() {
+			self.order.push_back(hash.0.clone());
+		}
+		while self.order.len() > TRACKED_TRANSACTIONS {
+			if let Some(oldest) = self.order.pop_front() {
+				self.chains.remove(&oldest);
+			}
+		}
+	}
 }
 
 /// Service that manages transaction delivery across multiple blockchain networks.
@@ -87,6 +285,21 @@ pub struct DeliveryService {
 	account: Arc<AccountService>,
 	/// Default number of confirmations required for transactions.
 	min_confirmations: u64,
+	/// Confirmation policies of chains not using the default, keyed by chain ID.
+	confirmation_policies: HashMap<u64, ConfirmationPolicy>,
+	/// Per-chain metadata used to denominate fees in each chain's gas token.
+	networks: NetworkRegistry,
+	/// Gas estimators keyed by chain ID.
+	estimators: std::collections::HashMap<u64, Box<dyn GasEstimator>>,
+	/// Cache of immutable lookups, if enabled.
+	cache: Option<RpcCache>,
+	/// Whether transactions are simulated before they are signed and submitted.
+	simulate: bool,
+	/// Whether transactions are simulated instead of submitted.
+	dry_run: bool,
+	/// Chains of submitted transactions, so receipts are only requested from
+	/// the chain a transaction was submitted to.
+	transaction_chains: Mutex<TransactionChains>,
 }
 
 impl DeliveryService {
@@ -103,84 +316,586 @@ impl DeliveryService {
 			providers,
 			account,
 			min_confirmations,
+			confirmation_policies: HashMap::new(),
+			networks: NetworkRegistry::default(),
+			estimators: std::collections::HashMap::new(),
+			cache: None,
+			simulate: false,
+			dry_run: false,
+			transaction_chains: Mutex::new(TransactionChains::default()),
+		}
+	}
+
+	/// Sets the confirmation policies of chains not using the default number
+	/// of confirmations or the provider's timeout, keyed by chain ID.
+	pub fn with_confirmation_policies(
+		mut self,
+		policies: HashMap<u64, ConfirmationPolicy>,
+	) -> Self {
+		self.confirmation_policies = policies;
+		self
+	}
+
+	/// Returns the confirmation policy of a chain.
+	pub fn confirmation_policy(&self, chain_id: u64) -> ConfirmationPolicy {
+		self.confirmation_policies
+			.get(&chain_id)
+			.copied()
+			.unwrap_or(ConfirmationPolicy {
+				confirmations: self.min_confirmations,
+				timeout: None,
+			})
+	}
+
+	/// Sets the cache used for immutable lookups such as final receipts.
+	pub fn with_cache(mut self, cache: RpcCache) -> Self {
+		self.cache = Some(cache);
+		self
+	}
+
+	/// Sets the gas estimators used to price transactions, keyed by chain ID.
+	///
+	/// Chains without an estimator leave fees to the provider.
+	pub fn with_gas_estimators(
+		mut self,
+		estimators: std::collections::HashMap<u64, Box<dyn GasEstimator>>,
+	) -> Self {
+		self.estimators = estimators;
+		self
+	}
+
+	/// Simulates every transaction before submitting it, rejecting those that
+	/// would revert instead of paying gas for them.
+	pub fn with_simulation(mut self, simulate: bool) -> Self {
+		self.simulate = simulate;
+		self
+	}
+
+	/// Refuses to submit, replace or cancel transactions, failing with
+	/// [`DeliveryError::DryRun`] once a transaction passed simulation.
+	pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+		self.dry_run = dry_run;
+		self
+	}
+
+	/// Sets the network registry used to resolve each chain's gas token.
+	pub fn with_networks(mut self, networks: NetworkRegistry) -> Self {
+		self.networks = networks;
+		self
+	}
+
+	/// Returns the IDs of all chains this service has a provider for.
+	pub fn chain_ids(&self) -> Vec<u64> {
+		self.providers.keys().copied().collect()
+	}
+
+	/// Returns the number of the latest block on a chain.
+	pub async fn block_number(&self, chain_id: u64) -> Result<u64, DeliveryError> {
+		self.providers
+			.get(&chain_id)
+			.ok_or(DeliveryError::NoProviderAvailable)?
+			.get_block_number()
+			.await
+	}
+
+	/// Returns the hash of the canonical block at a height on a chain.
+	pub async fn block_hash(
+		&self,
+		chain_id: u64,
+		number: u64,
+	) -> Result<Option<Vec<u8>>, DeliveryError> {
+		self.providers
+			.get(&chain_id)
+			.ok_or(DeliveryError::NoProviderAvailable)?
+			.get_block_hash(number)
+			.await
+	}
+
+	/// Probes the RPC endpoints of a chain.
+	///
+	/// Returns the endpoints that became unhealthy since the last check.
+	pub async fn check_endpoints(
+		&self,
+		chain_id: u64,
+	) -> Result<Vec<EndpointFailure>, DeliveryError> {
+		Ok(self.provider(chain_id)?.check_endpoints().await)
+	}
+
+	/// Returns the network registry used by this service.
+	pub fn networks(&self) -> &NetworkRegistry {
+		&self.networks
+	}
+
+	/// Returns the provider for a chain.
+	fn provider(&self, chain_id: u64) -> Result<&dyn DeliveryInterface, DeliveryError> {
+		self.providers
+			.get(&chain_id)
+			.map(|provider| provider.as_ref())
+			.ok_or(DeliveryError::NoProviderAvailable)
+	}
+
+	/// Estimates fee parameters for a transaction on a chain.
+	///
+	/// Uses the chain's gas estimator, falling back to the node's gas price for
+	/// chains without one.
+	pub async fn estimate_gas(&self, chain_id: u64) -> Result<GasParams, DeliveryError> {
+		let provider = self.provider(chain_id)?;
+		match self.estimators.get(&chain_id) {
+			Some(estimator) => estimator.estimate(provider).await,
+			None => Ok(GasParams {
+				gas_price: Some(provider.get_gas_price().await?),
+				..Default::default()
+			}),
 		}
 	}
 
+	/// Estimates the maximum fee of a transaction in the chain's gas token.
+	///
+	/// Uses the transaction's gas limit and the chain's estimated gas price.
+	pub async fn estimate_fee(&self, tx: &Transaction) -> Result<GasTokenAmount, DeliveryError> {
+		let gas_price = self
+			.estimate_gas(tx.chain_id)
+			.await?
+			.max_price()
+			.unwrap_or_default();
+		let gas_limit = U256::from(tx.gas_limit.unwrap_or_default());
+		Ok(self
+			.networks
+			.gas_amount(tx.chain_id, gas_price.saturating_mul(gas_limit)))
+	}
+
+	/// Returns the gas token balance of an address on a chain.
+	pub async fn gas_balance(
+		&self,
+		chain_id: u64,
+		address: &Address,
+	) -> Result<GasTokenAmount, DeliveryError> {
+		let balance = self.provider(chain_id)?.get_native_balance(address).await?;
+		Ok(self.networks.gas_amount(chain_id, balance))
+	}
+
+	/// Ensures the solver holds enough gas token to pay for a transaction.
+	///
+	/// The balance checked is that of the chain's gas payer, which is the
+	/// solver's account unless the provider says otherwise. When the account
+	/// pays, it also has to hold the value sent with the transaction.
+	/// Transactions without a gas limit cannot be estimated and, like
+	/// sponsored ones, always pass.
+	pub async fn ensure_gas_balance(&self, tx: &Transaction) -> Result<(), DeliveryError> {
+		if tx.gas_limit.is_none() {
+			return Ok(());
+		}
+
+		let (address, value) = match self.provider(tx.chain_id)?.gas_payer() {
+			GasPayer::Account => {
+				let address = self
+					.account
+					.get_address()
+					.await
+					.map_err(|e| DeliveryError::Network(e.to_string()))?;
+				(address, tx.value)
+			}
+			GasPayer::Address(address) => (address, U256::ZERO),
+			GasPayer::Sponsored => return Ok(()),
+		};
+		let fee = self.estimate_fee(tx).await?;
+		let balance = self.gas_balance(tx.chain_id, &address).await?;
+
+		if balance.amount < fee.amount.saturating_add(value) {
+			let required = self
+				.networks
+				.gas_amount(tx.chain_id, fee.amount.saturating_add(value));
+			return Err(DeliveryError::InsufficientGasBalance(format!(
+				"chain {} requires {} but solver holds {}",
+				tx.chain_id, required, balance
+			)));
+		}
+		Ok(())
+	}
+
 	/// Delivers a transaction to the appropriate blockchain network.
 	///
 	/// This method:
 	/// 1. Selects the appropriate provider based on the transaction's chain ID
-	/// 2. Signs the transaction using the account service
-	/// 3. Submits the signed transaction through the provider
+	/// 2. Prices transactions without fees using the chain's gas estimator
+	/// 3. Simulates the transaction, if enabled, failing with
+	///    [`DeliveryError::SimulationFailed`] if it would revert
+	/// 4. Signs the transaction using the account service
+	/// 5. Submits the signed transaction through the provider
 	pub async fn deliver(&self, tx: Transaction) -> Result<TransactionHash, DeliveryError> {
+		self.deliver_via(tx, false).await
+	}
+
+	/// Delivers a transaction through the chain's private route, falling
+	/// back to a regular submission on chains without one.
+	pub async fn deliver_private(&self, tx: Transaction) -> Result<TransactionHash, DeliveryError> {
+		self.deliver_via(tx, true).await
+	}
+
+	/// Prices, signs and submits a transaction, privately if requested.
+	async fn deliver_via(
+		&self,
+		mut tx: Transaction,
+		private: bool,
+	) -> Result<TransactionHash, DeliveryError> {
 		// Get the provider for the transaction's chain ID
 		let provider = self
 			.providers
 			.get(&tx.chain_id)
 			.ok_or(DeliveryError::NoProviderAvailable)?;
 
-		// Sign transaction
-		let signature = self
+		// Apply estimated fees unless the caller already priced the transaction
+		let unpriced = tx.gas_price.is_none() && tx.max_fee_per_gas.is_none();
+		if let Some(estimator) = self.estimators.get(&tx.chain_id).filter(|_| unpriced) {
+			match estimator.estimate(provider.as_ref()).await {
+				Ok(gas) => {
+					let to_u128 = |fee: U256| u128::try_from(fee).unwrap_or(u128::MAX);
+					tx.gas_price = gas.gas_price.map(to_u128);
+					tx.max_fee_per_gas = gas.max_fee_per_gas.map(to_u128);
+					tx.max_priority_fee_per_gas = gas.max_priority_fee_per_gas.map(to_u128);
+				}
+				Err(e) => {
+					tracing::warn!(
+						chain_id = tx.chain_id,
+						error = %e,
+						"Gas estimation failed, leaving fees to the provider"
+					);
+				}
+			}
+		}
+
+		// Reject transactions that would revert before they cost any gas
+		if self.simulate || self.dry_run {
+			self.simulate(&tx).await?;
+		}
+		if self.dry_run {
+			return Err(DeliveryError::DryRun);
+		}
+
+		// Sign transaction, unless the provider signs the message it builds
+		let signature = if provider.signs_transactions() {
+			Signature(Vec::new())
+		} else {
+			self.account
+				.sign(&tx)
+				.await
+				.map_err(|e| DeliveryError::Network(e.to_string()))?
+		};
+
+		// Submit using the chain-specific provider
+		let chain_id = tx.chain_id;
+		let hash = if private {
+			provider.submit_private(tx, &signature).await?
+		} else {
+			provider.submit(tx, &signature).await?
+		};
+		self.track_transaction(&hash, chain_id);
+		Ok(hash)
+	}
+
+	/// Simulates a transaction sent from the solver's account.
+	///
+	/// Fails with [`DeliveryError::SimulationFailed`] carrying the revert
+	/// reason if the transaction would revert. Providers that cannot simulate
+	/// transactions report success.
+	pub async fn simulate(&self, tx: &Transaction) -> Result<(), DeliveryError> {
+		let from = self
 			.account
-			.sign(&tx)
+			.get_address()
 			.await
 			.map_err(|e| DeliveryError::Network(e.to_string()))?;
+		self.provider(tx.chain_id)?.simulate(tx, &from).await
+	}
 
-		// Submit using the chain-specific provider
-		provider.submit(tx, &signature).await
+	/// Remembers the chain a transaction was submitted to.
+	pub fn track_transaction(&self, hash: &TransactionHash, chain_id: u64) {
+		self.transaction_chains
+			.lock()
+			.unwrap()
+			.insert(hash, chain_id);
 	}
 
-	/// Waits for a transaction to be confirmed with the specified number of confirmations.
+	/// Returns the chain a transaction was submitted to, if it is tracked.
+	pub fn transaction_chain(&self, hash: &TransactionHash) -> Option<u64> {
+		self.transaction_chains
+			.lock()
+			.unwrap()
+			.chains
+			.get(&hash.0)
+			.copied()
+	}
+
+	/// Re-submits a pending transaction on a chain with new fee parameters.
+	pub async fn replace(
+		&self,
+		chain_id: u64,
+		hash: &TransactionHash,
+		gas: GasParams,
+	) -> Result<TransactionHash, DeliveryError> {
+		if self.dry_run {
+			return Err(DeliveryError::DryRun);
+		}
+		let replacement = self.provider(chain_id)?.replace(hash, gas).await?;
+		self.track_transaction(&replacement, chain_id);
+		Ok(replacement)
+	}
+
+	/// Cancels a pending transaction on a chain.
+	pub async fn cancel(
+		&self,
+		chain_id: u64,
+		hash: &TransactionHash,
+	) -> Result<TransactionHash, DeliveryError> {
+		if self.dry_run {
+			return Err(DeliveryError::DryRun);
+		}
+		let cancellation = self.provider(chain_id)?.cancel(hash).await?;
+		self.track_transaction(&cancellation, chain_id);
+		Ok(cancellation)
+	}
+
+	/// Returns fee parameters escalated above the chain's current estimate.
 	///
-	/// This method first checks which provider has the transaction, then waits for confirmations
-	/// on that specific provider to avoid timeout issues.
-	pub async fn confirm(
+	/// Every estimated fee is raised by `bump_bps` basis points. Legacy
+	/// estimates are also used as the EIP-1559 max fee, leaving the priority
+	/// fee to the provider.
+	pub async fn escalated_gas(
 		&self,
+		chain_id: u64,
+		bump_bps: u64,
+	) -> Result<GasParams, DeliveryError> {
+		let estimate = self.estimate_gas(chain_id).await?;
+		let escalate =
+			|fee: U256| fee.saturating_mul(U256::from(10_000 + bump_bps)) / U256::from(10_000u64);
+		Ok(GasParams {
+			gas_price: estimate.max_price().map(escalate),
+			max_fee_per_gas: estimate.max_price().map(escalate),
+			max_priority_fee_per_gas: estimate.max_priority_fee_per_gas.map(escalate),
+		})
+	}
+
+	/// Prices a transaction `bump_bps` basis points above the chain's current
+	/// estimate, so that it outbids other transactions for inclusion in the
+	/// next blocks.
+	pub async fn escalate_fees(
+		&self,
+		tx: &mut Transaction,
+		bump_bps: u64,
+	) -> Result<(), DeliveryError> {
+		let gas = self.escalated_gas(tx.chain_id, bump_bps).await?;
+		let to_u128 = |fee: U256| u128::try_from(fee).unwrap_or(u128::MAX);
+		match gas.max_priority_fee_per_gas {
+			Some(priority_fee) => {
+				tx.gas_price = None;
+				tx.max_fee_per_gas = gas.max_fee_per_gas.map(to_u128);
+				tx.max_priority_fee_per_gas = Some(to_u128(priority_fee));
+			}
+			None => tx.gas_price = gas.gas_price.map(to_u128),
+		}
+		Ok(())
+	}
+
+	/// Executes a read-only call on the transaction's chain.
+	///
+	/// Selects the provider based on the transaction's chain ID. No signature
+	/// is required since nothing is submitted.
+	pub async fn call(&self, tx: &Transaction) -> Result<Vec<u8>, DeliveryError> {
+		self.provider(tx.chain_id)?.call(tx).await
+	}
+
+	/// Executes a read-only call whose result can never change.
+	///
+	/// Results are cached, so only use this for calls such as token decimals
+	/// and symbols whose return value is fixed once the contract is deployed.
+	pub async fn call_immutable(&self, tx: &Transaction) -> Result<Vec<u8>, DeliveryError> {
+		let Some(cache) = &self.cache else {
+			return self.call(tx).await;
+		};
+
+		let to = tx
+			.to
+			.as_ref()
+			.map(|to| hex::encode(&to.0))
+			.unwrap_or_default();
+		let key = format!("call:{}:{}:{}", tx.chain_id, to, hex::encode(&tx.data));
+		cache.get_or_fetch(&key, |_| true, || self.call(tx)).await
+	}
+
+	/// Returns whether contract code is deployed at an address on a chain.
+	///
+	/// Only positive results are cached, since code may still be deployed to
+	/// an address that has none yet.
+	pub async fn has_code(&self, chain_id: u64, address: &Address) -> Result<bool, DeliveryError> {
+		let provider = self.provider(chain_id)?;
+		let fetch =
+			|| async { Ok::<_, DeliveryError>(!provider.get_code(address).await?.is_empty()) };
+
+		match &self.cache {
+			Some(cache) => {
+				let key = format!("code:{}:{}", chain_id, hex::encode(&address.0));
+				cache.get_or_fetch(&key, |has_code| *has_code, fetch).await
+			}
+			None => fetch().await,
+		}
+	}
+
+	/// Returns the cached receipt of a transaction, if it is final.
+	async fn cached_receipt(&self, hash: &TransactionHash) -> Option<TransactionReceipt> {
+		let cache = self.cache.as_ref()?;
+		cache.get(&Self::receipt_key(hash)).await
+	}
+
+	/// Caches a receipt once it is buried deeper than its chain's number of
+	/// confirmations, after which it is considered final.
+	async fn cache_receipt_if_final(
+		&self,
+		chain_id: u64,
+		provider: &dyn DeliveryInterface,
+		receipt: &TransactionReceipt,
+	) {
+		let Some(cache) = &self.cache else {
+			return;
+		};
+		let Ok(current_block) = provider.get_block_number().await else {
+			return;
+		};
+
+		let confirmations = self.confirmation_policy(chain_id).confirmations;
+		if current_block.saturating_sub(receipt.block_number) >= confirmations {
+			cache.put(&Self::receipt_key(&receipt.hash), receipt).await;
+		}
+	}
+
+	/// Waits for a transaction to reach `confirmations` on a chain's provider,
+	/// giving up after the chain's confirmation timeout if it has one.
+	async fn wait_for_confirmation(
+		&self,
+		chain_id: u64,
+		provider: &dyn DeliveryInterface,
 		hash: &TransactionHash,
 		confirmations: u64,
 	) -> Result<TransactionReceipt, DeliveryError> {
-		// First, quickly check which provider has the transaction
-		let mut provider_with_tx = None;
+		let wait = provider.wait_for_confirmation(hash, confirmations);
+		let receipt = match self.confirmation_policy(chain_id).timeout {
+			Some(timeout) => tokio::time::timeout(timeout, wait).await.map_err(|_| {
+				DeliveryError::Network(format!(
+					"Timeout waiting for {} confirmations after {} seconds",
+					confirmations,
+					timeout.as_secs()
+				))
+			})??,
+			None => wait.await?,
+		};
+		self.cache_receipt_if_final(chain_id, provider, &receipt)
+			.await;
+		Ok(receipt)
+	}
+
+	/// Returns the chain and provider of a transaction.
+	///
+	/// Transactions submitted through this service are routed to their own
+	/// chain. Other transactions are looked for on every chain.
+	async fn find_transaction(
+		&self,
+		hash: &TransactionHash,
+	) -> Option<(u64, &dyn DeliveryInterface)> {
+		if let Some(chain_id) = self.transaction_chain(hash) {
+			if let Some(provider) = self.providers.get(&chain_id) {
+				return Some((chain_id, provider.as_ref()));
+			}
+		}
 
 		for (chain_id, provider) in self.providers.iter() {
 			// Just check if the transaction exists, don't wait for confirmations yet
-			match provider.get_receipt(hash).await {
-				Ok(_) => {
-					provider_with_tx = Some((*chain_id, provider));
-					break;
-				}
-				Err(_) => continue,
+			if provider.get_receipt(hash).await.is_ok() {
+				return Some((*chain_id, provider.as_ref()));
 			}
 		}
+		None
+	}
 
-		// If we found a provider with the transaction, wait for confirmations
-		if let Some((_chain_id, provider)) = provider_with_tx {
-			provider.wait_for_confirmation(hash, confirmations).await
-		} else {
-			Err(DeliveryError::NoProviderAvailable)
+	/// Drops a cached receipt after its block was reorganized away.
+	pub async fn forget_receipt(&self, hash: &TransactionHash) {
+		if let Some(cache) = &self.cache {
+			cache.remove(&Self::receipt_key(hash)).await;
 		}
 	}
 
-	/// Waits for a transaction to be confirmed with the default number of confirmations.
+	/// Returns the cache key of a transaction receipt.
+	fn receipt_key(hash: &TransactionHash) -> String {
+		format!("receipt:{}", hex::encode(&hash.0))
+	}
+
+	/// Waits for a transaction to be confirmed with the specified number of confirmations.
 	///
-	/// Uses the min_confirmations value configured for this service.
+	/// This method first resolves the transaction's chain, from where it was submitted or else by
+	/// asking every provider, then waits for confirmations on that specific provider to avoid
+	/// timeout issues. Final receipts are served from the
+	/// cache when available.
+	pub async fn confirm(
+		&self,
+		hash: &TransactionHash,
+		confirmations: u64,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		// Cached receipts are final on their own chain, which is not known yet
+		let final_everywhere = self
+			.confirmation_policies
+			.values()
+			.map(|policy| policy.confirmations)
+			.fold(self.min_confirmations, u64::min);
+		if confirmations <= final_everywhere {
+			if let Some(receipt) = self.cached_receipt(hash).await {
+				return Ok(receipt);
+			}
+		}
+
+		let (chain_id, provider) = self
+			.find_transaction(hash)
+			.await
+			.ok_or(DeliveryError::NoProviderAvailable)?;
+		self.wait_for_confirmation(chain_id, provider, hash, confirmations)
+			.await
+	}
+
+	/// Waits for a transaction to be confirmed with the number of
+	/// confirmations of the chain it was submitted to.
 	pub async fn confirm_with_default(
 		&self,
 		hash: &TransactionHash,
 	) -> Result<TransactionReceipt, DeliveryError> {
-		// Use configured confirmations
-		self.confirm(hash, self.min_confirmations).await
+		if let Some(receipt) = self.cached_receipt(hash).await {
+			return Ok(receipt);
+		}
+
+		let (chain_id, provider) = self
+			.find_transaction(hash)
+			.await
+			.ok_or(DeliveryError::NoProviderAvailable)?;
+		let confirmations = self.confirmation_policy(chain_id).confirmations;
+		self.wait_for_confirmation(chain_id, provider, hash, confirmations)
+			.await
 	}
 
 	/// Checks the current status of a transaction.
 	///
 	/// Returns true if the transaction was successful, false if it failed.
-	/// This method tries all providers until one recognizes the transaction.
+	/// Transactions submitted through this service are looked up on their own
+	/// chain. For other transactions, this method tries all providers until
+	/// one recognizes the transaction, unless its final receipt is cached.
 	pub async fn get_status(&self, hash: &TransactionHash) -> Result<bool, DeliveryError> {
+		if let Some(chain_id) = self.transaction_chain(hash) {
+			return self.get_status_on(chain_id, hash).await;
+		}
+		if let Some(receipt) = self.cached_receipt(hash).await {
+			return Ok(receipt.success);
+		}
+
 		// Try all providers until one recognizes the transaction
-		for (_chain_id, provider) in self.providers.iter() {
+		for (chain_id, provider) in self.providers.iter() {
 			match provider.get_receipt(hash).await {
 				Ok(receipt) => {
+					self.cache_receipt_if_final(*chain_id, provider.as_ref(), &receipt)
+						.await;
 					return Ok(receipt.success);
 				}
 				Err(_) => {
@@ -191,4 +906,58 @@ impl DeliveryService {
 
 		Err(DeliveryError::NoProviderAvailable)
 	}
+
+	/// Checks the current status of a transaction on a known chain.
+	///
+	/// Unlike [`get_status`](Self::get_status), only the chain's own provider is
+	/// queried, so an unresponsive chain cannot delay lookups for other chains.
+	pub async fn get_status_on(
+		&self,
+		chain_id: u64,
+		hash: &TransactionHash,
+	) -> Result<bool, DeliveryError> {
+		self.receipt_on(chain_id, hash)
+			.await
+			.map(|receipt| receipt.success)
+	}
+
+	/// Returns the receipt of a mined transaction on a known chain.
+	pub async fn receipt_on(
+		&self,
+		chain_id: u64,
+		hash: &TransactionHash,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		if let Some(receipt) = self.cached_receipt(hash).await {
+			return Ok(receipt);
+		}
+
+		let provider = self
+			.providers
+			.get(&chain_id)
+			.ok_or(DeliveryError::NoProviderAvailable)?;
+		let receipt = provider.get_receipt(hash).await?;
+		self.cache_receipt_if_final(chain_id, provider.as_ref(), &receipt)
+			.await;
+		Ok(receipt)
+	}
+
+	/// Waits for a transaction on a known chain to reach the chain's number of
+	/// confirmations.
+	pub async fn confirm_on(
+		&self,
+		chain_id: u64,
+		hash: &TransactionHash,
+	) -> Result<TransactionReceipt, DeliveryError> {
+		if let Some(receipt) = self.cached_receipt(hash).await {
+			return Ok(receipt);
+		}
+
+		let provider = self
+			.providers
+			.get(&chain_id)
+			.ok_or(DeliveryError::NoProviderAvailable)?;
+		let confirmations = self.confirmation_policy(chain_id).confirmations;
+		self.wait_for_confirmation(chain_id, provider.as_ref(), hash, confirmations)
+			.await
+	}
 }
@@ -0,0 +1,21 @@
+//! Accounts of the devnet.
+//!
+//! These are Anvil's default development accounts, derived from its default
+//! mnemonic, so they are funded with ether on every devnet chain.
+
+/// Private key of the solver, Anvil's account #0.
+pub const SOLVER_PRIVATE_KEY: &str =
+	"0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Address of the solver.
+pub const SOLVER_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+/// Private key of the user opening intents, Anvil's account #1.
+pub const USER_PRIVATE_KEY: &str =
+	"0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+/// Address of the user opening intents.
+pub const USER_ADDRESS: &str = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+
+/// Address receiving the outputs of intents, Anvil's account #2.
+pub const RECIPIENT_ADDRESS: &str = "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC";
@@ -0,0 +1,97 @@
+//! Anvil nodes of the devnet.
+
+use crate::{foundry, DevnetError};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::{Child, Command};
+
+/// Attempts made to reach a starting node before giving up.
+const READY_ATTEMPTS: u32 = 50;
+
+/// Delay between attempts to reach a starting node.
+const READY_INTERVAL: Duration = Duration::from_millis(200);
+
+/// A local Anvil node, killed when dropped.
+pub struct AnvilNode {
+	/// Chain ID of the node.
+	pub chain_id: u64,
+	/// HTTP RPC endpoint of the node.
+	pub rpc_url: String,
+	/// The running `anvil` process.
+	_process: Child,
+}
+
+impl AnvilNode {
+	/// Spawns a node for `chain_id` on a free local port, mining a block
+	/// every `block_time` seconds, and waits until it serves requests.
+	///
+	/// The node is configured like the demo chains: funded development
+	/// accounts, a zero base fee and a raised contract size limit for the
+	/// settlers.
+	pub async fn spawn(chain_id: u64, block_time: u64) -> Result<Self, DevnetError> {
+		let port = free_port()?;
+		let process = Command::new(foundry::binary("anvil"))
+			.args([
+				"--chain-id",
+				&chain_id.to_string(),
+				"--port",
+				&port.to_string(),
+				"--accounts",
+				"10",
+				"--balance",
+				"10000",
+				"--gas-limit",
+				"30000000",
+				"--code-size-limit",
+				"50000",
+				"--base-fee",
+				"0",
+				"--gas-price",
+				"1000000000",
+				"--auto-impersonate",
+				"--block-time",
+				&block_time.to_string(),
+			])
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.kill_on_drop(true)
+			.spawn()
+			.map_err(|e| DevnetError::Anvil(format!("Failed to spawn anvil: {}", e)))?;
+
+		let node = Self {
+			chain_id,
+			rpc_url: format!("http://127.0.0.1:{}", port),
+			_process: process,
+		};
+		node.wait_ready().await?;
+		Ok(node)
+	}
+
+	/// Waits until the node answers with its chain ID.
+	async fn wait_ready(&self) -> Result<(), DevnetError> {
+		for _ in 0..READY_ATTEMPTS {
+			let chain_id =
+				foundry::run("cast", &["chain-id", "--rpc-url", &self.rpc_url], None).await;
+			if let Ok(chain_id) = chain_id {
+				if chain_id == self.chain_id.to_string() {
+					return Ok(());
+				}
+			}
+			tokio::time::sleep(READY_INTERVAL).await;
+		}
+		Err(DevnetError::Anvil(format!(
+			"Node of chain {} at {} did not come up",
+			self.chain_id, self.rpc_url
+		)))
+	}
+}
+
+/// Returns a local port no process listens on.
+fn free_port() -> Result<u16, DevnetError> {
+	let listener = std::net::TcpListener::bind("127.0.0.1:0")
+		.map_err(|e| DevnetError::Anvil(format!("Failed to find a free port: {}", e)))?;
+	listener
+		.local_addr()
+		.map(|addr| addr.port())
+		.map_err(|e| DevnetError::Anvil(format!("Failed to find a free port: {}", e)))
+}
@@ -0,0 +1,169 @@
+//! Deployment of the settler contracts to the devnet.
+
+use crate::accounts::{SOLVER_ADDRESS, SOLVER_PRIVATE_KEY, USER_ADDRESS, USER_PRIVATE_KEY};
+use crate::{foundry, AnvilNode, DevnetError};
+use std::path::Path;
+
+/// Source of the mintable token deployed on both chains.
+const TEST_TOKEN: &str = concat!(
+	env!("CARGO_MANIFEST_DIR"),
+	"/contracts/TestToken.sol:TestToken"
+);
+
+/// Amount of test tokens, in the token's smallest unit, minted to the user on
+/// the origin chain and to the solver on the destination chain.
+pub const FUNDING_AMOUNT: &str = "100000000000000000000";
+
+/// Largest token allowance, granted to the settlers.
+const MAX_ALLOWANCE: &str =
+	"115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+/// Addresses of the contracts deployed to the devnet.
+#[derive(Debug, Clone)]
+pub struct Deployment {
+	/// Test token on the origin chain, deposited by users.
+	pub origin_token: String,
+	/// Oracle on the origin chain, attesting every fill.
+	pub oracle: String,
+	/// Input settler on the origin chain, where intents are opened.
+	pub input_settler: String,
+	/// Test token on the destination chain, delivered by the solver.
+	pub destination_token: String,
+	/// Output settler on the destination chain, where intents are filled.
+	pub output_settler: String,
+}
+
+impl Deployment {
+	/// Deploys the settlers, oracle and test tokens from a checkout of the
+	/// oif-contracts repository, then funds the user and the solver.
+	///
+	/// The user holds test tokens on the origin chain and has approved the
+	/// input settler; the solver holds test tokens on the destination chain
+	/// and has approved the output settler.
+	pub async fn deploy(
+		contracts_dir: &Path,
+		origin: &AnvilNode,
+		destination: &AnvilNode,
+	) -> Result<Self, DevnetError> {
+		let origin_token = create(contracts_dir, origin, TEST_TOKEN).await?;
+		let oracle = create(
+			contracts_dir,
+			origin,
+			"test/mocks/AlwaysYesOracle.sol:AlwaysYesOracle",
+		)
+		.await?;
+		let input_settler = create(
+			contracts_dir,
+			origin,
+			"src/input/7683/InputSettler7683.sol:InputSettler7683",
+		)
+		.await?;
+		let destination_token = create(contracts_dir, destination, TEST_TOKEN).await?;
+		let output_settler = create(
+			contracts_dir,
+			destination,
+			"src/output/coin/OutputSettler7683.sol:OutputInputSettler7683",
+		)
+		.await?;
+
+		let deployment = Self {
+			origin_token,
+			oracle,
+			input_settler,
+			destination_token,
+			output_settler,
+		};
+		deployment.fund(origin, destination).await?;
+		Ok(deployment)
+	}
+
+	/// Mints test tokens to the user and the solver and approves the settlers
+	/// to spend them.
+	async fn fund(&self, origin: &AnvilNode, destination: &AnvilNode) -> Result<(), DevnetError> {
+		mint(origin, &self.origin_token, USER_ADDRESS).await?;
+		approve(
+			origin,
+			USER_PRIVATE_KEY,
+			&self.origin_token,
+			&self.input_settler,
+		)
+		.await?;
+		mint(destination, &self.destination_token, SOLVER_ADDRESS).await?;
+		approve(
+			destination,
+			SOLVER_PRIVATE_KEY,
+			&self.destination_token,
+			&self.output_settler,
+		)
+		.await
+	}
+}
+
+/// Deploys a contract with `forge create` and returns its address.
+async fn create(
+	contracts_dir: &Path,
+	node: &AnvilNode,
+	contract: &str,
+) -> Result<String, DevnetError> {
+	let output = foundry::run(
+		"forge",
+		&[
+			"create",
+			contract,
+			"--rpc-url",
+			&node.rpc_url,
+			"--private-key",
+			SOLVER_PRIVATE_KEY,
+			"--broadcast",
+			"--json",
+		],
+		Some(contracts_dir),
+	)
+	.await?;
+	let deployed: serde_json::Value = serde_json::from_str(&output).map_err(|e| {
+		DevnetError::Deployment(format!("Unexpected output deploying {}: {}", contract, e))
+	})?;
+	deployed
+		.get("deployedTo")
+		.and_then(|address| address.as_str())
+		.map(str::to_string)
+		.ok_or_else(|| {
+			DevnetError::Deployment(format!(
+				"No address deploying {} on chain {}",
+				contract, node.chain_id
+			))
+		})
+}
+
+/// Mints [`FUNDING_AMOUNT`] test tokens to an account.
+async fn mint(node: &AnvilNode, token: &str, to: &str) -> Result<(), DevnetError> {
+	foundry::send(
+		&node.rpc_url,
+		SOLVER_PRIVATE_KEY,
+		token,
+		"mint(address,uint256)",
+		&[to, FUNDING_AMOUNT],
+	)
+	.await
+	.map(|_| ())
+	.map_err(|e| DevnetError::Deployment(format!("Failed to mint to {}: {}", to, e)))
+}
+
+/// Approves `spender` to spend all test tokens of the key's account.
+async fn approve(
+	node: &AnvilNode,
+	private_key: &str,
+	token: &str,
+	spender: &str,
+) -> Result<(), DevnetError> {
+	foundry::send(
+		&node.rpc_url,
+		private_key,
+		token,
+		"approve(address,uint256)",
+		&[spender, MAX_ALLOWANCE],
+	)
+	.await
+	.map(|_| ())
+	.map_err(|e| DevnetError::Deployment(format!("Failed to approve {}: {}", spender, e)))
+}
@@ -0,0 +1,226 @@
+//! The devnet: two chains with the settlers deployed.
+
+use crate::accounts::{SOLVER_ADDRESS, SOLVER_PRIVATE_KEY, USER_PRIVATE_KEY};
+use crate::intents::ORDER_DATA_TYPE;
+use crate::{foundry, AnvilNode, Deployment, DevnetError, DevnetSolver, IntentSpec};
+use alloy_primitives::{hex, U256};
+use solver_config::Config;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// Environment variable pointing to a checkout of the oif-contracts
+/// repository.
+pub const CONTRACTS_DIR_VAR: &str = "OIF_CONTRACTS_DIR";
+
+/// Options of a devnet.
+#[derive(Debug, Clone)]
+pub struct DevnetOptions {
+	/// Chain ID of the origin chain.
+	pub origin_chain_id: u64,
+	/// Chain ID of the destination chain.
+	pub destination_chain_id: u64,
+	/// Seconds between blocks on both chains.
+	pub block_time: u64,
+	/// Checkout of the oif-contracts repository the settlers are built from.
+	pub contracts_dir: Option<PathBuf>,
+}
+
+impl Default for DevnetOptions {
+	/// The chain IDs of the demo setup, with a block every second and the
+	/// contracts at `OIF_CONTRACTS_DIR`.
+	fn default() -> Self {
+		Self {
+			origin_chain_id: 31337,
+			destination_chain_id: 31338,
+			block_time: 1,
+			contracts_dir: std::env::var_os(CONTRACTS_DIR_VAR).map(PathBuf::from),
+		}
+	}
+}
+
+/// An origin and a destination chain with the settlers deployed and the
+/// accounts funded, torn down when dropped.
+pub struct Devnet {
+	/// Chain where intents are opened and settled.
+	pub origin: AnvilNode,
+	/// Chain where intents are filled.
+	pub destination: AnvilNode,
+	/// Contracts deployed to the chains.
+	pub deployment: Deployment,
+	/// Directory holding the solver's storage.
+	storage_dir: TempDir,
+}
+
+impl Devnet {
+	/// Starts a devnet with the default options.
+	pub async fn start() -> Result<Self, DevnetError> {
+		Self::start_with(DevnetOptions::default()).await
+	}
+
+	/// Starts both chains, deploys the contracts and funds the accounts.
+	pub async fn start_with(options: DevnetOptions) -> Result<Self, DevnetError> {
+		let contracts_dir = options.contracts_dir.ok_or_else(|| {
+			DevnetError::Deployment(format!(
+				"{} must point to a checkout of oif-contracts",
+				CONTRACTS_DIR_VAR
+			))
+		})?;
+		let storage_dir = tempfile::tempdir()
+			.map_err(|e| DevnetError::Solver(format!("Failed to create storage: {}", e)))?;
+
+		let origin = AnvilNode::spawn(options.origin_chain_id, options.block_time).await?;
+		let destination =
+			AnvilNode::spawn(options.destination_chain_id, options.block_time).await?;
+		let deployment = Deployment::deploy(&contracts_dir, &origin, &destination).await?;
+		tracing::info!(
+			origin = %origin.rpc_url,
+			destination = %destination.rpc_url,
+			"Devnet started"
+		);
+
+		Ok(Self {
+			origin,
+			destination,
+			deployment,
+			storage_dir,
+		})
+	}
+
+	/// Returns the configuration of a solver filling intents on the devnet.
+	///
+	/// Tests may adjust it before passing it to
+	/// [`start_solver_with`](Self::start_solver_with). Solvers started from it
+	/// share the devnet's storage, so a restarted solver sees what its
+	/// predecessor stored.
+	pub fn solver_config(&self) -> Result<Config, DevnetError> {
+		let storage_path = self.storage_dir.path().join("storage");
+		let config = format!(
+			r#"
+[solver]
+id = "oif-solver-devnet"
+monitoring_timeout_minutes = 1
+
+[storage]
+backend = "file"
+[storage.config]
+storage_path = "{storage_path}"
+
+[account]
+provider = "local"
+[account.config]
+private_key = "{solver_key}"
+
+[delivery]
+min_confirmations = 1
+[delivery.providers.origin]
+rpc_url = "{origin_rpc}"
+private_key = "{solver_key}"
+chain_id = {origin_chain}
+[delivery.providers.destination]
+rpc_url = "{destination_rpc}"
+private_key = "{solver_key}"
+chain_id = {destination_chain}
+
+[discovery]
+[discovery.sources.origin_eip7683]
+rpc_url = "{origin_rpc}"
+settler_addresses = ["{input_settler}"]
+
+[order]
+[order.implementations.eip7683]
+output_settler_address = "{output_settler}"
+input_settler_address = "{input_settler}"
+solver_address = "{solver_address}"
+[order.execution_strategy]
+strategy_type = "simple"
+[order.execution_strategy.config]
+max_gas_price_gwei = 100
+
+[settlement]
+[settlement.implementations.eip7683]
+rpc_url = "{destination_rpc}"
+oracle_address = "{oracle}"
+dispute_period_seconds = 1
+"#,
+			storage_path = storage_path.display(),
+			solver_key = SOLVER_PRIVATE_KEY,
+			solver_address = SOLVER_ADDRESS,
+			origin_rpc = self.origin.rpc_url,
+			origin_chain = self.origin.chain_id,
+			destination_rpc = self.destination.rpc_url,
+			destination_chain = self.destination.chain_id,
+			input_settler = self.deployment.input_settler,
+			output_settler = self.deployment.output_settler,
+			oracle = self.deployment.oracle,
+		);
+		config
+			.parse()
+			.map_err(|e| DevnetError::Solver(format!("Invalid devnet configuration: {}", e)))
+	}
+
+	/// Starts a solver with the devnet's [configuration](Self::solver_config).
+	pub fn start_solver(&self) -> Result<DevnetSolver, DevnetError> {
+		self.start_solver_with(self.solver_config()?)
+	}
+
+	/// Starts a solver with the given configuration.
+	pub fn start_solver_with(&self, config: Config) -> Result<DevnetSolver, DevnetError> {
+		DevnetSolver::start(config)
+	}
+
+	/// Opens an intent as the user on the origin chain, and returns the hash
+	/// of the opening transaction.
+	pub async fn open_intent(&self, spec: &IntentSpec) -> Result<String, DevnetError> {
+		let expiry = spec.expiry();
+		let order_data = spec.order_data(&self.deployment, self.destination.chain_id, expiry)?;
+		let order = format!(
+			"({},{},0x{})",
+			expiry,
+			ORDER_DATA_TYPE,
+			hex::encode(order_data)
+		);
+		foundry::send(
+			&self.origin.rpc_url,
+			USER_PRIVATE_KEY,
+			&self.deployment.input_settler,
+			"open((uint32,bytes32,bytes))",
+			&[&order],
+		)
+		.await
+	}
+
+	/// Returns the test token balance of an account on a chain of the
+	/// devnet.
+	pub async fn token_balance(
+		&self,
+		node: &AnvilNode,
+		account: &str,
+	) -> Result<U256, DevnetError> {
+		let token = if node.chain_id == self.origin.chain_id {
+			&self.deployment.origin_token
+		} else {
+			&self.deployment.destination_token
+		};
+		let balance = foundry::run(
+			"cast",
+			&[
+				"call",
+				token,
+				"balanceOf(address)(uint256)",
+				account,
+				"--rpc-url",
+				&node.rpc_url,
+			],
+			None,
+		)
+		.await?;
+		// cast appends the value in scientific notation, as in "100 [1e2]"
+		let balance = balance.split_whitespace().next().unwrap_or_default();
+		U256::from_str_radix(balance, 10).map_err(|_| {
+			DevnetError::Command(
+				"cast call".to_string(),
+				format!("Invalid balance {}", balance),
+			)
+		})
+	}
+}
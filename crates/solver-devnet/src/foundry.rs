@@ -0,0 +1,76 @@
+//! Runs Foundry's command line tools.
+
+use crate::DevnetError;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// Returns the path of a Foundry binary.
+///
+/// Binaries installed by foundryup are preferred over those on the `PATH`,
+/// as the demo scripts do.
+pub(crate) fn binary(name: &str) -> PathBuf {
+	if let Some(home) = std::env::var_os("HOME") {
+		let installed = Path::new(&home).join(".foundry").join("bin").join(name);
+		if installed.exists() {
+			return installed;
+		}
+	}
+	PathBuf::from(name)
+}
+
+/// Runs a Foundry binary to completion and returns its standard output.
+pub(crate) async fn run(
+	name: &str,
+	args: &[&str],
+	current_dir: Option<&Path>,
+) -> Result<String, DevnetError> {
+	let command_line = format!("{} {}", name, args.first().copied().unwrap_or_default());
+	let mut command = Command::new(binary(name));
+	command.args(args);
+	if let Some(dir) = current_dir {
+		command.current_dir(dir);
+	}
+	let output = command
+		.output()
+		.await
+		.map_err(|e| DevnetError::Command(command_line.clone(), e.to_string()))?;
+	if !output.status.success() {
+		return Err(DevnetError::Command(
+			command_line,
+			String::from_utf8_lossy(&output.stderr).trim().to_string(),
+		));
+	}
+	Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Sends a transaction with `cast send`, and returns its hash.
+pub(crate) async fn send(
+	rpc_url: &str,
+	private_key: &str,
+	to: &str,
+	signature: &str,
+	args: &[&str],
+) -> Result<String, DevnetError> {
+	let mut command = vec!["send", to, signature];
+	command.extend_from_slice(args);
+	command.extend_from_slice(&["--rpc-url", rpc_url, "--private-key", private_key, "--json"]);
+	let output = run("cast", &command, None).await?;
+	let receipt: serde_json::Value = serde_json::from_str(&output)
+		.map_err(|e| DevnetError::Command(format!("cast send {}", signature), e.to_string()))?;
+	if receipt.get("status").and_then(|s| s.as_str()) != Some("0x1") {
+		return Err(DevnetError::Command(
+			format!("cast send {}", signature),
+			"Transaction reverted".to_string(),
+		));
+	}
+	receipt
+		.get("transactionHash")
+		.and_then(|hash| hash.as_str())
+		.map(str::to_string)
+		.ok_or_else(|| {
+			DevnetError::Command(
+				format!("cast send {}", signature),
+				"Receipt has no transaction hash".to_string(),
+			)
+		})
+}
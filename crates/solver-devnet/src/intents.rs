@@ -0,0 +1,109 @@
+//! Intents opened on the devnet.
+
+use crate::accounts::RECIPIENT_ADDRESS;
+use crate::{Deployment, DevnetError};
+use alloy_primitives::{Address, FixedBytes, U256};
+use alloy_sol_types::{sol, SolValue};
+
+/// EIP-712 typehash of `MandateERC7683`, identifying the order data of
+/// on-chain orders.
+pub const ORDER_DATA_TYPE: &str =
+	"0x532668680e4ed97945ec5ed6aee3633e99abe764fd2d2861903dc7c109b00e82";
+
+sol! {
+	/// Output of an order, delivered on the destination chain.
+	struct MandateOutput {
+		bytes32 oracle;
+		bytes32 settler;
+		uint256 chainId;
+		bytes32 token;
+		uint256 amount;
+		bytes32 recipient;
+		bytes call;
+		bytes context;
+	}
+
+	/// Order data of on-chain EIP-7683 orders.
+	struct MandateERC7683 {
+		uint32 expiry;
+		address localOracle;
+		uint256[2][] inputs;
+		MandateOutput[] outputs;
+	}
+}
+
+/// Intent swapping test tokens from the origin to the destination chain.
+#[derive(Debug, Clone)]
+pub struct IntentSpec {
+	/// Tokens the user deposits on the origin chain.
+	pub amount_in: U256,
+	/// Tokens the recipient receives on the destination chain.
+	pub amount_out: U256,
+	/// Seconds from now after which the intent can no longer be filled.
+	pub expires_in: u64,
+	/// Address receiving the outputs.
+	pub recipient: String,
+}
+
+impl Default for IntentSpec {
+	/// One whole test token in and out, expiring in an hour.
+	fn default() -> Self {
+		Self {
+			amount_in: U256::from(10u64).pow(U256::from(18u64)),
+			amount_out: U256::from(10u64).pow(U256::from(18u64)),
+			expires_in: 3600,
+			recipient: RECIPIENT_ADDRESS.to_string(),
+		}
+	}
+}
+
+impl IntentSpec {
+	/// Returns the unix timestamp at which the intent expires.
+	pub fn expiry(&self) -> u32 {
+		let now = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_secs();
+		(now + self.expires_in) as u32
+	}
+
+	/// Encodes the order data of the intent, paying out on
+	/// `destination_chain_id` through the deployment's output settler.
+	pub fn order_data(
+		&self,
+		deployment: &Deployment,
+		destination_chain_id: u64,
+		expiry: u32,
+	) -> Result<Vec<u8>, DevnetError> {
+		let order = MandateERC7683 {
+			expiry,
+			localOracle: parse_address(&deployment.oracle)?,
+			inputs: vec![[
+				U256::from_be_slice(
+					parse_address(&deployment.origin_token)?
+						.into_word()
+						.as_slice(),
+				),
+				self.amount_in,
+			]],
+			outputs: vec![MandateOutput {
+				oracle: FixedBytes::ZERO,
+				settler: parse_address(&deployment.output_settler)?.into_word(),
+				chainId: U256::from(destination_chain_id),
+				token: parse_address(&deployment.destination_token)?.into_word(),
+				amount: self.amount_out,
+				recipient: parse_address(&self.recipient)?.into_word(),
+				call: Default::default(),
+				context: Default::default(),
+			}],
+		};
+		Ok(order.abi_encode())
+	}
+}
+
+/// Parses a hex address.
+fn parse_address(address: &str) -> Result<Address, DevnetError> {
+	address
+		.parse()
+		.map_err(|_| DevnetError::Deployment(format!("Invalid address {}", address)))
+}
@@ -0,0 +1,68 @@
+//! Local devnet harness for end-to-end tests of the OIF solver.
+//!
+//! The harness spins up an origin and a destination Anvil chain, deploys the
+//! EIP-7683 settler contracts and a test token on them, and funds the user
+//! and solver accounts, mirroring the demo setup in `scripts/demo`. Tests
+//! then open intents as the user and run the full solver engine against the
+//! chains, so that discovery, filling and settlement are exercised together.
+//!
+//! Foundry's `anvil`, `forge` and `cast` binaries must be installed, and the
+//! settlers are built from a checkout of the oif-contracts repository found
+//! at `OIF_CONTRACTS_DIR`.
+//!
+//! ```no_run
+//! # async fn fill() -> Result<(), solver_devnet::DevnetError> {
+//! use solver_devnet::{Devnet, IntentSpec};
+//! use solver_types::{SettlementEvent, SolverEvent};
+//! use std::time::Duration;
+//!
+//! let devnet = Devnet::start().await?;
+//! let mut solver = devnet.start_solver()?;
+//! devnet.open_intent(&IntentSpec::default()).await?;
+//! solver
+//!     .wait_for_event("settlement", Duration::from_secs(120), |event| {
+//!         matches!(
+//!             event,
+//!             SolverEvent::Settlement(SettlementEvent::Completed { .. })
+//!         )
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use thiserror::Error;
+
+pub mod accounts;
+pub mod anvil;
+pub mod contracts;
+pub mod devnet;
+mod foundry;
+pub mod intents;
+pub mod solver;
+
+pub use anvil::AnvilNode;
+pub use contracts::Deployment;
+pub use devnet::{Devnet, DevnetOptions};
+pub use intents::IntentSpec;
+pub use solver::DevnetSolver;
+
+/// Errors that can occur while running the devnet.
+#[derive(Debug, Error)]
+pub enum DevnetError {
+	/// Error that occurs when a Foundry command cannot be run or fails.
+	#[error("Command `{0}` failed: {1}")]
+	Command(String, String),
+	/// Error that occurs when an Anvil node does not come up.
+	#[error("Anvil error: {0}")]
+	Anvil(String),
+	/// Error that occurs when deploying or funding contracts.
+	#[error("Deployment error: {0}")]
+	Deployment(String),
+	/// Error that occurs when building or running the solver.
+	#[error("Solver error: {0}")]
+	Solver(String),
+	/// Error that occurs when an awaited condition is not met in time.
+	#[error("Timed out waiting for {0}")]
+	Timeout(String),
+}
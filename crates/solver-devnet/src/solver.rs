@@ -0,0 +1,160 @@
+//! The solver engine running against the devnet.
+
+use crate::DevnetError;
+use serde::de::DeserializeOwned;
+use solver_config::Config;
+use solver_core::{SolverBuilder, SolverEngine, SolverError};
+use solver_storage::StorageError;
+use solver_types::SolverEvent;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Interval at which storage is polled while waiting for a value.
+const STORAGE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Builds a solver engine with the implementations the devnet deploys for.
+pub fn build_engine(config: Config) -> Result<SolverEngine, SolverError> {
+	use solver_account::implementations::local::create_account;
+	use solver_delivery::implementations::evm::alloy::create_http_delivery;
+	use solver_discovery::implementations::onchain::_7683::create_discovery;
+	use solver_order::implementations::{
+		standards::_7683::create_order_impl, strategies::simple::create_strategy,
+	};
+	use solver_settlement::implementations::direct::create_settlement;
+	use solver_storage::implementations::file::create_storage;
+
+	SolverBuilder::new(config)
+		.with_storage_factory("file", create_storage)
+		.with_account_factory(create_account)
+		.with_delivery_factory("origin", create_http_delivery)
+		.with_delivery_factory("destination", create_http_delivery)
+		.with_discovery_factory("origin_eip7683", create_discovery)
+		.with_order_factory("eip7683", create_order_impl)
+		.with_settlement_factory("eip7683", create_settlement)
+		.with_strategy_factory("simple", create_strategy)
+		.build()
+}
+
+/// A solver engine running in the background, stopped when dropped.
+pub struct DevnetSolver {
+	/// The running engine.
+	engine: Arc<SolverEngine>,
+	/// Events published by the engine since it started.
+	events: broadcast::Receiver<SolverEvent>,
+	/// Task running the engine's main loop.
+	task: JoinHandle<Result<(), SolverError>>,
+}
+
+impl DevnetSolver {
+	/// Builds an engine from `config` and starts running it.
+	///
+	/// The engine's events are subscribed to before it starts, so that none
+	/// are missed by [`wait_for_event`](Self::wait_for_event).
+	pub fn start(config: Config) -> Result<Self, DevnetError> {
+		let engine =
+			Arc::new(build_engine(config).map_err(|e| DevnetError::Solver(e.to_string()))?);
+		let events = engine.event_bus().subscribe();
+		let task = tokio::spawn({
+			let engine = engine.clone();
+			async move { engine.run().await }
+		});
+		Ok(Self {
+			engine,
+			events,
+			task,
+		})
+	}
+
+	/// Returns the running engine.
+	pub fn engine(&self) -> &Arc<SolverEngine> {
+		&self.engine
+	}
+
+	/// Waits for the engine to publish an event matching `predicate`, and
+	/// returns it.
+	///
+	/// Events not matching are consumed, so successive calls wait for events
+	/// in the order they are published. `what` names the awaited event in the
+	/// timeout error.
+	pub async fn wait_for_event<F>(
+		&mut self,
+		what: &str,
+		timeout: Duration,
+		mut predicate: F,
+	) -> Result<SolverEvent, DevnetError>
+	where
+		F: FnMut(&SolverEvent) -> bool,
+	{
+		let deadline = tokio::time::Instant::now() + timeout;
+		loop {
+			if self.task.is_finished() {
+				return Err(DevnetError::Solver(format!(
+					"Engine stopped while waiting for {}",
+					what
+				)));
+			}
+			match tokio::time::timeout_at(deadline, self.events.recv()).await {
+				Ok(Ok(event)) if predicate(&event) => return Ok(event),
+				Ok(Ok(_)) => {}
+				Ok(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+					tracing::warn!(skipped, "Devnet solver missed events");
+				}
+				Ok(Err(broadcast::error::RecvError::Closed)) => {
+					return Err(DevnetError::Solver(format!(
+						"Event bus closed while waiting for {}",
+						what
+					)));
+				}
+				Err(_) => return Err(DevnetError::Timeout(what.to_string())),
+			}
+		}
+	}
+
+	/// Reads a value the engine stored, if present.
+	pub async fn stored<T: DeserializeOwned>(
+		&self,
+		namespace: &str,
+		id: &str,
+	) -> Result<Option<T>, DevnetError> {
+		match self.engine.storage().retrieve(namespace, id).await {
+			Ok(value) => Ok(Some(value)),
+			Err(StorageError::NotFound) => Ok(None),
+			Err(e) => Err(DevnetError::Solver(e.to_string())),
+		}
+	}
+
+	/// Waits until the engine stores a value matching `predicate`, and
+	/// returns it.
+	pub async fn wait_for_stored<T, F>(
+		&self,
+		namespace: &str,
+		id: &str,
+		timeout: Duration,
+		mut predicate: F,
+	) -> Result<T, DevnetError>
+	where
+		T: DeserializeOwned,
+		F: FnMut(&T) -> bool,
+	{
+		let deadline = tokio::time::Instant::now() + timeout;
+		loop {
+			if let Some(value) = self.stored::<T>(namespace, id).await? {
+				if predicate(&value) {
+					return Ok(value);
+				}
+			}
+			if tokio::time::Instant::now() >= deadline {
+				return Err(DevnetError::Timeout(format!("{}/{}", namespace, id)));
+			}
+			tokio::time::sleep(STORAGE_POLL_INTERVAL).await;
+		}
+	}
+}
+
+impl Drop for DevnetSolver {
+	fn drop(&mut self) {
+		self.task.abort();
+	}
+}
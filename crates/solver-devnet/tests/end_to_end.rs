@@ -0,0 +1,93 @@
+//! Runs the solver end to end against a local devnet.
+//!
+//! These tests need Foundry and a checkout of oif-contracts at
+//! `OIF_CONTRACTS_DIR`, so they are ignored by default. Run them with
+//! `cargo test -p solver-devnet -- --ignored`.
+
+use solver_devnet::accounts::{RECIPIENT_ADDRESS, SOLVER_ADDRESS};
+use solver_devnet::{Devnet, IntentSpec};
+use solver_types::{
+	DetailedIntentStatus, DiscoveryEvent, SettlementEvent, SolverEvent, TransactionHash,
+};
+use std::time::Duration;
+
+/// How long the solver gets to discover an intent.
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long the solver gets to fill and claim an order.
+const SETTLEMENT_TIMEOUT: Duration = Duration::from_secs(180);
+
+#[tokio::test]
+#[ignore = "requires Foundry and OIF_CONTRACTS_DIR"]
+async fn fills_and_claims_opened_intent() {
+	let devnet = Devnet::start().await.expect("Devnet starts");
+	let mut solver = devnet.start_solver().expect("Solver starts");
+	let spec = IntentSpec::default();
+	let recipient_before = devnet
+		.token_balance(&devnet.destination, RECIPIENT_ADDRESS)
+		.await
+		.unwrap();
+	let solver_before = devnet
+		.token_balance(&devnet.origin, SOLVER_ADDRESS)
+		.await
+		.unwrap();
+
+	devnet.open_intent(&spec).await.expect("Intent opens");
+	let validated = solver
+		.wait_for_event("validated order", DISCOVERY_TIMEOUT, |event| {
+			matches!(
+				event,
+				SolverEvent::Discovery(DiscoveryEvent::IntentValidated { .. })
+			)
+		})
+		.await
+		.unwrap();
+	let SolverEvent::Discovery(DiscoveryEvent::IntentValidated { order, .. }) = validated else {
+		unreachable!()
+	};
+	let order_id = order.id.clone();
+
+	solver
+		.wait_for_event("completed order", SETTLEMENT_TIMEOUT, |event| {
+			matches!(
+				event,
+				SolverEvent::Settlement(SettlementEvent::Completed { order_id: id })
+					if *id == order_id
+			)
+		})
+		.await
+		.unwrap();
+
+	// The fill and the claim are recorded against the order
+	solver
+		.wait_for_stored::<DetailedIntentStatus, _>(
+			"order_status",
+			&order_id,
+			DISCOVERY_TIMEOUT,
+			|status| matches!(status, DetailedIntentStatus::Completed),
+		)
+		.await
+		.unwrap();
+	assert!(solver
+		.stored::<TransactionHash>("fills", &order_id)
+		.await
+		.unwrap()
+		.is_some());
+	assert!(solver
+		.stored::<TransactionHash>("claims", &order_id)
+		.await
+		.unwrap()
+		.is_some());
+
+	// The recipient got the outputs and the solver was paid the inputs
+	let recipient_after = devnet
+		.token_balance(&devnet.destination, RECIPIENT_ADDRESS)
+		.await
+		.unwrap();
+	assert_eq!(recipient_after - recipient_before, spec.amount_out);
+	let solver_after = devnet
+		.token_balance(&devnet.origin, SOLVER_ADDRESS)
+		.await
+		.unwrap();
+	assert_eq!(solver_after - solver_before, spec.amount_in);
+}
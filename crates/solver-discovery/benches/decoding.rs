@@ -0,0 +1,55 @@
+//! Benchmarks of decoding EIP-7683 `Open` event logs into intents, done for
+//! every order opened on a monitored settler.
+
+use alloy_primitives::{Address, FixedBytes, Log as PrimLog, U256};
+use alloy_rpc_types::Log;
+use alloy_sol_types::SolEvent;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use solver_discovery::implementations::onchain::_7683::Eip7683Discovery;
+use solver_types::eip7683::{Open, Output, ResolvedCrossChainOrder};
+
+/// Builds the log of an `Open` event for an order with the given number of
+/// outputs.
+fn open_log(outputs: usize) -> Log {
+	let output = Output {
+		token: FixedBytes::left_padding_from(&[0x9f; 20]),
+		amount: U256::from(10u64.pow(18)),
+		recipient: FixedBytes::left_padding_from(&[0x3c; 20]),
+		chainId: U256::from(31338),
+	};
+	let event = Open {
+		orderId: FixedBytes::repeat_byte(1),
+		order: ResolvedCrossChainOrder {
+			user: Address::repeat_byte(0xf3),
+			originChainId: U256::from(31337),
+			openDeadline: 0,
+			fillDeadline: u32::MAX,
+			orderId: FixedBytes::repeat_byte(1),
+			maxSpent: vec![output.clone(); outputs],
+			minReceived: vec![output],
+			fillInstructions: Vec::new(),
+		},
+	};
+	Log {
+		inner: PrimLog {
+			address: Address::repeat_byte(0xe7),
+			data: event.encode_log_data(),
+		},
+		block_number: Some(1),
+		..Default::default()
+	}
+}
+
+fn decoding(c: &mut Criterion) {
+	let mut group = c.benchmark_group("intent_decoding");
+	for outputs in [1, 8] {
+		let log = open_log(outputs);
+		group.bench_with_input(BenchmarkId::new("open_event", outputs), &log, |b, log| {
+			b.iter(|| Eip7683Discovery::parse_open_event(log).unwrap())
+		});
+	}
+	group.finish();
+}
+
+criterion_group!(benches, decoding);
+criterion_main!(benches);
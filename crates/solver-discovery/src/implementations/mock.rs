@@ -0,0 +1,172 @@
+//! Mock discovery source for tests.
+//!
+//! Intents are not found anywhere: tests hand them to the source, either up
+//! front to be discovered as soon as monitoring starts, or later through a
+//! [`MockIntents`] handle kept once the source is handed to the solver.
+
+use crate::{DiscoveryError, DiscoveryInterface, IntakeError, IntentSender};
+use async_trait::async_trait;
+use solver_types::testing::MockBehavior;
+use solver_types::{ConfigSchema, Field, FieldType, Intent, Schema};
+use std::sync::{Arc, Mutex};
+
+/// Discovery source emitting the intents tests give it.
+///
+/// Scripted failures surface as [`DiscoveryError::Connection`].
+pub struct MockDiscovery {
+	/// Intents discovered as soon as monitoring starts.
+	initial: Mutex<Vec<Intent>>,
+	/// Whether intents submitted to the solver are accepted.
+	accepts_submissions: bool,
+	/// Intake of the running source, shared with its handles.
+	intents: MockIntents,
+}
+
+/// Handle emitting intents through a running mock discovery source.
+#[derive(Clone)]
+pub struct MockIntents {
+	/// Intake given when monitoring started, until it stops.
+	sender: Arc<Mutex<Option<IntentSender>>>,
+	/// Latencies, scripted failures and call counts.
+	behavior: Arc<MockBehavior>,
+}
+
+impl MockIntents {
+	/// Hands an intent to the intake, as if it was just discovered.
+	///
+	/// Fails if the source is not monitoring or the intake refuses the
+	/// intent.
+	pub async fn emit(&self, intent: Intent) -> Result<(), DiscoveryError> {
+		let sender = self.sender.lock().unwrap().clone().ok_or_else(|| {
+			DiscoveryError::Connection("Mock source is not monitoring".to_string())
+		})?;
+		sender.send(intent).await.map_err(|e| match e {
+			IntakeError::Closed => DiscoveryError::Connection(e.to_string()),
+			e => DiscoveryError::Rejected(e.to_string()),
+		})
+	}
+
+	/// Returns the source's behaviour, to script failures and count calls.
+	pub fn behavior(&self) -> Arc<MockBehavior> {
+		self.behavior.clone()
+	}
+}
+
+impl MockDiscovery {
+	/// Creates a mock source without intents.
+	pub fn new() -> Self {
+		Self {
+			initial: Mutex::new(Vec::new()),
+			accepts_submissions: true,
+			intents: MockIntents {
+				sender: Arc::new(Mutex::new(None)),
+				behavior: Arc::new(MockBehavior::new()),
+			},
+		}
+	}
+
+	/// Discovers `intents` as soon as monitoring starts.
+	pub fn with_intents(self, intents: Vec<Intent>) -> Self {
+		*self.initial.lock().unwrap() = intents;
+		self
+	}
+
+	/// Sets whether intents submitted to the solver are accepted.
+	pub fn with_submissions(mut self, accepts_submissions: bool) -> Self {
+		self.accepts_submissions = accepts_submissions;
+		self
+	}
+
+	/// Replaces the source's behaviour.
+	pub fn with_behavior(mut self, behavior: MockBehavior) -> Self {
+		self.intents.behavior = Arc::new(behavior);
+		self
+	}
+
+	/// Returns a handle emitting intents once the source is monitoring.
+	pub fn intents(&self) -> MockIntents {
+		self.intents.clone()
+	}
+
+	/// Plays a call to an operation.
+	async fn call(&self, operation: &str) -> Result<(), DiscoveryError> {
+		self.intents
+			.behavior
+			.call(operation)
+			.await
+			.map_err(DiscoveryError::Connection)
+	}
+}
+
+impl Default for MockDiscovery {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Configuration schema for MockDiscovery.
+pub struct MockDiscoverySchema;
+
+impl ConfigSchema for MockDiscoverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let mut optional = vec![Field::new("accept_submissions", FieldType::Boolean)];
+		optional.extend(MockBehavior::config_fields());
+		Schema::new(vec![], optional).validate(config)
+	}
+}
+
+#[async_trait]
+impl DiscoveryInterface for MockDiscovery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(MockDiscoverySchema)
+	}
+
+	async fn start_monitoring(&self, sender: IntentSender) -> Result<(), DiscoveryError> {
+		self.call("start_monitoring").await?;
+		{
+			let mut current = self.intents.sender.lock().unwrap();
+			if current.is_some() {
+				return Err(DiscoveryError::AlreadyMonitoring);
+			}
+			*current = Some(sender.clone());
+		}
+
+		let initial = std::mem::take(&mut *self.initial.lock().unwrap());
+		for intent in initial {
+			let _ = sender.send(intent).await;
+		}
+		Ok(())
+	}
+
+	async fn stop_monitoring(&self) -> Result<(), DiscoveryError> {
+		self.call("stop_monitoring").await?;
+		self.intents.sender.lock().unwrap().take();
+		Ok(())
+	}
+
+	async fn submit(&self, intent: Intent) -> Result<(), DiscoveryError> {
+		if !self.accepts_submissions {
+			return Err(DiscoveryError::SubmissionUnsupported);
+		}
+		self.call("submit").await?;
+		self.intents.emit(intent).await
+	}
+}
+
+/// Factory function to create a mock discovery source from configuration.
+///
+/// Optional configuration parameters:
+/// - `accept_submissions`: Whether submitted intents are accepted (default: true)
+/// - `latency_ms`, `operation_latency_ms`, `fail_first`: see [`MockBehavior::from_config`]
+pub fn create_discovery(config: &toml::Value) -> Box<dyn DiscoveryInterface> {
+	let accepts_submissions = config
+		.get("accept_submissions")
+		.and_then(|v| v.as_bool())
+		.unwrap_or(true);
+
+	Box::new(
+		MockDiscovery::new()
+			.with_submissions(accepts_submissions)
+			.with_behavior(MockBehavior::from_config(config)),
+	)
+}
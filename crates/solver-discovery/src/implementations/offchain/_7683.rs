@@ -1,130 +1,202 @@
-//! ERC-7683 Off-chain Intent Discovery API Implementation
-//!
-//! This module implements an HTTP API server that accepts ERC-7683 cross-chain intents
-//! directly from users or other systems. It provides an endpoint for receiving
-//! gasless cross-chain orders that follow the ERC-7683 standard.
-//!
-//! ## Overview
-//!
-//! The ERC-7683 standard defines a universal cross-chain intent framework that enables:
-//! - Users to express cross-chain intents with gasless order submission
-//! - Solvers/fillers to discover and fulfill these intents across chains
-//! - Settlement through standardized contracts on origin and destination chains
-//!
-//! ## Architecture
-//!
-//! This implementation:
-//! 1. Spins up an HTTP API server with intent submission endpoints
-//! 2. Receives GaslessCrossChainOrder intents via POST requests
-//! 3. Validates and normalizes the intent data
-//! 4. Publishes validated intents to the solver's event bus
-//! 5. Enables the delivery service to pick up and process these intents
-//!
-//! ## Intent Flow
-//!
-//! 1. **Submission**: Users POST GaslessCrossChainOrder to `/intents` endpoint
-//! 2. **Validation**: Verify intent structure matches ERC-7683 standard
-//! 3. **Normalization**: Convert to internal Intent representation
-//! 4. **Publication**: Emit to event bus for downstream processing
-//! 5. **Response**: Return intent ID and status to submitter
-//!
-//! ## Implementation Requirements
-//!
-//! To complete this implementation:
-//!
-//! 1. **API Server Setup**
-//!    - Create HTTP server (e.g., using Axum, Actix-web, or Warp)
-//!    - Define POST `/intents` endpoint
-//!    - Implement request parsing and validation
-//!    - Add health check and metrics endpoints
-//!
-//! 2. **Intent Recognition**
-//!    - Parse GaslessCrossChainOrder structure from request body
-//!    - Validate all required fields are present
-//!    - Check signature validity (if provided)
-//!    - Verify deadlines haven't expired
-//!
-//! 3. **Event Bus Integration**
-//!    - Convert validated intents to internal Intent type
-//!    - Publish to the configured event bus topic
-//!    - Handle retry logic for failed publications
-//!
-//! ## GaslessCrossChainOrder Structure
-//!
-//! The standard ERC-7683 order structure that this module processes:
-//!
-//! ```solidity
-//! struct GaslessCrossChainOrder {
-//!     address originSettler;      // Settlement contract on origin chain
-//!     address user;              // User initiating the cross-chain swap
-//!     uint256 nonce;            // Replay protection nonce
-//!     uint256 originChainId;    // Chain ID where order originates
-//!     uint32 openDeadline;      // Deadline to open the order
-//!     uint32 fillDeadline;      // Deadline to fill on destination
-//!     bytes32 orderDataType;    // EIP-712 typehash for order data
-//!     bytes orderData;          // Order-specific data (tokens, amounts, etc.)
-//! }
-//! ```
-//!
-//! ## Configuration
-//!
-//! Expected configuration parameters:
-//! - `api_port`: Port to bind the HTTP server (default: 8080)
-//! - `api_host`: Host address to bind (default: 0.0.0.0)
-//! - `auth_token`: Optional bearer token for API authentication
-//! - `rate_limit`: Max requests per minute per IP (default: 100)
-//! - `event_bus_topic`: Where to publish discovered intents
-//!
-//! ## API Endpoints
-//!
-//! - `POST /intent` - Submit a new GaslessCrossChainOrder
-//! - `GET /intent/{orderId}` - Get the status of submitted intent
-//!
-//! ## Example Usage
-//!
-//! ```rust
-//! // Initialize the discovery API server
-//! let discovery = Erc7683OffchainDiscovery::new(config)?;
-//!
-//! // Start the API server
-//! discovery.start_server().await?;
-//!
-//! // Server now accepts POST requests with GaslessCrossChainOrder
-//! // at http://localhost:8080/intent
-//! ```
-//!
-//! ## Example API Request
-//!
-//! ```bash
-//! curl -X POST http://localhost:8080/intent \
-//!   -H "Content-Type: application/json" \
-//!   -H "Authorization: Bearer YOUR_TOKEN" \
-//!   -d '{
-//!     "originSettler": "0x...",
-//!     "user": "0x...",
-//!     "nonce": "123",
-//!     "originChainId": "1",
-//!     "openDeadline": 1234567890,
-//!     "fillDeadline": 1234567900,
-//!     "orderDataType": "0x...",
-//!     "orderData": "0x..."
-//!   }'
-//! ```
-//!
-//! ## Security Considerations
-//!
-//! - Validate all intent data before processing
-//! - Verify signatures match the claimed user address
-//! - Check deadlines haven't expired
-//! - Rate limit API requests to prevent abuse
-//! - Use secure storage for API credentials
-//!
-//! ## TODO Implementation Steps
-//!
-//! 1. Define the API server structure and routes
-//! 2. Implement GaslessCrossChainOrder request handler
-//! 3. Create intent parser and validator
-//! 4. Integrate with solver's event bus
-//! 5. Add authentication and rate limiting middleware
-//! 6. Implement monitoring and metrics collection
-//! 7. Write comprehensive tests for all endpoints
+//! ERC-7683 off-chain intent discovery.
+//!
+//! Gasless orders are signed by users and handed to the solver over HTTP
+//! rather than opened on-chain. This source is the entry point for them:
+//! submitted orders are normalized into intents and handed to the
+//! same intake as intents found on-chain, so they are queued, validated
+//! and executed like any other.
+//!
+//! The HTTP endpoint lives in the API server. It converts each submission
+//! with [`intent_from_order`], has the order implementation verify the
+//! signature, and then submits the intent to this source.
+
+use crate::{DiscoveryError, DiscoveryInterface, IntakeError, IntentSender};
+use async_trait::async_trait;
+use solver_types::{
+	parse_signature, ConfigSchema, CrossChainOrder, Field, FieldType, Intent, IntentMetadata,
+	Schema,
+};
+use std::sync::Mutex;
+
+pub use solver_types::OFFCHAIN_SOURCE;
+
+/// EIP-7683 off-chain discovery implementation.
+///
+/// Accepts submitted intents while monitoring and forwards them to the
+/// solver. Submissions for settlers outside the configured set are refused.
+pub struct Eip7683OffchainDiscovery {
+	/// Lowercased settler addresses accepted, or empty to accept any.
+	settler_addresses: Vec<String>,
+	/// Intake intents are forwarded to while monitoring.
+	sender: Mutex<Option<IntentSender>>,
+}
+
+impl Eip7683OffchainDiscovery {
+	/// Creates an off-chain discovery source accepting orders for the given
+	/// settlers.
+	pub fn new(settler_addresses: Vec<String>) -> Self {
+		Self {
+			settler_addresses: settler_addresses
+				.into_iter()
+				.map(|address| address.to_lowercase())
+				.collect(),
+			sender: Mutex::new(None),
+		}
+	}
+}
+
+/// Converts a submitted gasless order into an intent.
+///
+/// Rejects orders without a well-formed signature and orders whose deadlines
+/// have passed. The signature itself is verified against the order by the
+/// order implementation, which knows the signing domain.
+pub fn intent_from_order(order: &CrossChainOrder) -> Result<Intent, DiscoveryError> {
+	let now = std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap()
+		.as_secs();
+
+	if order.fill_deadline <= now {
+		return Err(DiscoveryError::Rejected(
+			"Fill deadline has passed".to_string(),
+		));
+	}
+
+	parse_signature(&order.signature).map_err(|e| DiscoveryError::Rejected(e.to_string()))?;
+
+	let mut data = order.order_data.clone();
+	let fields = data
+		.as_object_mut()
+		.ok_or_else(|| DiscoveryError::Rejected("orderData must be an object".to_string()))?;
+
+	let order_id: [u8; 32] = fields
+		.get("order_id")
+		.cloned()
+		.and_then(|value| serde_json::from_value(value).ok())
+		.ok_or_else(|| DiscoveryError::Rejected("orderData is missing order_id".to_string()))?;
+
+	if fields.get("fill_deadline").and_then(|v| v.as_u64()) != Some(order.fill_deadline) {
+		return Err(DiscoveryError::Rejected(
+			"fillDeadline does not match orderData".to_string(),
+		));
+	}
+	if let Some(expires) = fields.get("expires").and_then(|v| v.as_u64()) {
+		if expires <= now {
+			return Err(DiscoveryError::Rejected("Order has expired".to_string()));
+		}
+	}
+
+	// The order implementation expects the signature alongside the order data
+	fields.insert(
+		"signature".to_string(),
+		serde_json::Value::String(order.signature.clone()),
+	);
+
+	Ok(Intent {
+		id: hex::encode(order_id),
+		source: OFFCHAIN_SOURCE.to_string(),
+		standard: "eip7683".to_string(),
+		metadata: IntentMetadata {
+			requires_auction: false,
+			exclusive_until: None,
+			discovered_at: now,
+			settler: Some(order.settlement_contract.to_lowercase()),
+			block_number: None,
+			transaction_hash: None,
+			custom: order.metadata.clone(),
+		},
+		data,
+	})
+}
+
+/// Configuration schema for EIP-7683 off-chain discovery.
+pub struct Eip7683OffchainDiscoverySchema;
+
+impl ConfigSchema for Eip7683OffchainDiscoverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![],
+			// Optional fields
+			vec![Field::new(
+				"settler_addresses",
+				FieldType::Array(Box::new(FieldType::String)),
+			)],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl DiscoveryInterface for Eip7683OffchainDiscovery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(Eip7683OffchainDiscoverySchema)
+	}
+
+	async fn start_monitoring(&self, sender: IntentSender) -> Result<(), DiscoveryError> {
+		let mut current = self.sender.lock().unwrap();
+		if current.is_some() {
+			return Err(DiscoveryError::AlreadyMonitoring);
+		}
+		*current = Some(sender);
+		Ok(())
+	}
+
+	async fn stop_monitoring(&self) -> Result<(), DiscoveryError> {
+		self.sender.lock().unwrap().take();
+		Ok(())
+	}
+
+	async fn submit(&self, intent: Intent) -> Result<(), DiscoveryError> {
+		if !self.settler_addresses.is_empty() {
+			let settler = intent.metadata.settler.as_deref().unwrap_or_default();
+			if !self
+				.settler_addresses
+				.iter()
+				.any(|address| address == settler)
+			{
+				return Err(DiscoveryError::Rejected(format!(
+					"Unsupported settlement contract: {}",
+					settler
+				)));
+			}
+		}
+
+		let sender = self
+			.sender
+			.lock()
+			.unwrap()
+			.clone()
+			.ok_or_else(|| DiscoveryError::Rejected("Solver is not running".to_string()))?;
+		sender.submit(intent).await.map_err(|e| match e {
+			IntakeError::Full => DiscoveryError::Rejected("Solver is at capacity".to_string()),
+			IntakeError::Closed => DiscoveryError::Rejected("Solver is shutting down".to_string()),
+			IntakeError::Spill(e) => DiscoveryError::Connection(e.to_string()),
+			IntakeError::Duplicate => {
+				DiscoveryError::Rejected("Intent was already submitted".to_string())
+			}
+			IntakeError::RateLimited(_) => {
+				DiscoveryError::Rejected("Too many intents submitted".to_string())
+			}
+		})
+	}
+}
+
+/// Factory function to create an off-chain discovery source from configuration.
+///
+/// Optional configuration parameters:
+/// - `settler_addresses`: Settler addresses orders may be submitted for
+pub fn create_discovery(config: &toml::Value) -> Box<dyn DiscoveryInterface> {
+	let settler_addresses = config
+		.get("settler_addresses")
+		.and_then(|v| v.as_array())
+		.map(|arr| {
+			arr.iter()
+				.filter_map(|v| v.as_str().map(String::from))
+				.collect()
+		})
+		.unwrap_or_default();
+
+	Box::new(Eip7683OffchainDiscovery::new(settler_addresses))
+}
@@ -3,52 +3,29 @@
 //! This module provides concrete implementations of the DiscoveryInterface trait,
 //! currently supporting on-chain EIP-7683 event monitoring using the Alloy library.
 
-use crate::{DiscoveryError, DiscoveryInterface};
-use alloy_primitives::{Address as AlloyAddress, Log as PrimLog, LogData, U256};
-use alloy_provider::{Provider, RootProvider};
+use crate::{DiscoveryError, DiscoveryInterface, IntentSender};
+use alloy_primitives::{Address as AlloyAddress, U256};
+use alloy_provider::{Provider, ProviderBuilder, RootProvider, WsConnect};
 use alloy_rpc_types::{Filter, Log};
-use alloy_sol_types::{sol, SolEvent};
+use alloy_sol_types::SolEvent;
 use alloy_transport_http::Http;
 use async_trait::async_trait;
-use solver_types::{ConfigSchema, Field, FieldType, Intent, IntentMetadata, Schema};
+use solver_types::eip7683::Open;
+use solver_types::{
+	decode_rpc_log, ConfigSchema, Field, FieldType, Intent, IntentMetadata, Schema,
+};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::{mpsc, Mutex};
+use tracing::Instrument;
 
-// Solidity type definitions for EIP-7683 cross-chain order events.
-//
-// These types match the on-chain contract ABI for proper event decoding.
-sol! {
-	/// Output specification for cross-chain orders.
-	struct Output {
-		bytes32 token;
-		uint256 amount;
-		bytes32 recipient;
-		uint256 chainId;
-	}
-
-	/// Fill instruction for cross-chain execution.
-	struct FillInstruction {
-		uint64 destinationChainId;
-		bytes32 destinationSettler;
-		bytes originData;
-	}
+/// Time spent polling over HTTP before reconnecting a lost WebSocket.
+const WS_RECONNECT_INTERVAL: Duration = Duration::from_secs(30);
 
-	/// Resolved cross-chain order structure.
-	struct ResolvedCrossChainOrder {
-		address user;
-		uint256 originChainId;
-		uint32 openDeadline;
-		uint32 fillDeadline;
-		bytes32 orderId;
-		Output[] maxSpent;
-		Output[] minReceived;
-		FillInstruction[] fillInstructions;
-	}
-
-	/// Event emitted when a new cross-chain order is opened.
-	event Open(bytes32 indexed orderId, ResolvedCrossChainOrder order);
-}
+/// Default number of blocks fetched per log query.
+const DEFAULT_CHUNK_SIZE: u64 = 1_000;
 
 /// EIP-7683 on-chain discovery implementation.
 ///
@@ -57,8 +34,14 @@ sol! {
 pub struct Eip7683Discovery {
 	/// The Alloy provider for blockchain interaction.
 	provider: RootProvider<Http<reqwest::Client>>,
+	/// WebSocket endpoint for log subscriptions, if configured.
+	ws_url: Option<String>,
 	/// Contract addresses to monitor for Open events.
 	settler_addresses: Vec<AlloyAddress>,
+	/// First block to scan for Open events, if configured.
+	from_block: Option<u64>,
+	/// Maximum number of blocks fetched per log query.
+	chunk_size: u64,
 	/// The last processed block number.
 	last_block: Arc<Mutex<u64>>,
 	/// Flag indicating if monitoring is active.
@@ -71,10 +54,18 @@ impl Eip7683Discovery {
 	/// Creates a new EIP-7683 discovery instance.
 	///
 	/// Configures monitoring for the specified settler contract addresses
-	/// on the blockchain accessible via the RPC URL.
+	/// on the blockchain accessible via the RPC URL. When a WebSocket URL is
+	/// given, new events are received through subscriptions rather than polling.
+	///
+	/// Monitoring starts after the current block unless `from_block` is given,
+	/// in which case earlier blocks are scanned first, `chunk_size` blocks at
+	/// a time.
 	pub async fn new(
 		rpc_url: &str,
+		ws_url: Option<String>,
 		settler_addresses: Vec<String>,
+		from_block: Option<u64>,
+		chunk_size: u64,
 	) -> Result<Self, DiscoveryError> {
 		// Create provider
 		let provider = RootProvider::new_http(
@@ -95,15 +86,21 @@ impl Eip7683Discovery {
 			));
 		}
 
-		// Get current block
-		let current_block = provider.get_block_number().await.map_err(|e| {
-			DiscoveryError::Connection(format!("Failed to get block number: {}", e))
-		})?;
+		// Start after the current block unless told to scan from an earlier one
+		let last_block = match from_block {
+			Some(from_block) => from_block.saturating_sub(1),
+			None => provider.get_block_number().await.map_err(|e| {
+				DiscoveryError::Connection(format!("Failed to get block number: {}", e))
+			})?,
+		};
 
 		Ok(Self {
 			provider,
+			ws_url,
 			settler_addresses: addresses,
-			last_block: Arc::new(Mutex::new(current_block)),
+			from_block,
+			chunk_size: chunk_size.max(1),
+			last_block: Arc::new(Mutex::new(last_block)),
 			is_monitoring: Arc::new(AtomicBool::new(false)),
 			stop_signal: Arc::new(Mutex::new(None)),
 		})
@@ -113,15 +110,9 @@ impl Eip7683Discovery {
 	///
 	/// Decodes the EIP-7683 event data and converts it into the internal
 	/// Intent format used by the solver.
-	async fn parse_open_event(&self, log: &Log) -> Result<Intent, DiscoveryError> {
-		// Convert RPC log to primitives log for decoding
-		let prim_log = PrimLog {
-			address: log.address(),
-			data: LogData::new_unchecked(log.topics().to_vec(), log.data().data.clone()),
-		};
-
+	pub fn parse_open_event(log: &Log) -> Result<Intent, DiscoveryError> {
 		// Decode the Open event
-		let open_event = Open::decode_log(&prim_log, true)
+		let open_event = decode_rpc_log::<Open>(log)
 			.map_err(|e| DiscoveryError::Connection(format!("Failed to decode event: {}", e)))?;
 
 		let order = &open_event.order;
@@ -179,6 +170,12 @@ impl Eip7683Discovery {
 					.duration_since(std::time::UNIX_EPOCH)
 					.unwrap()
 					.as_secs(),
+				settler: Some(format!("0x{}", hex::encode(log.address()))),
+				block_number: log.block_number,
+				transaction_hash: log
+					.transaction_hash
+					.map(|hash| format!("0x{}", hex::encode(hash))),
+				custom: Default::default(),
 			},
 			data: order_data,
 		})
@@ -186,75 +183,224 @@ impl Eip7683Discovery {
 
 	/// Main monitoring loop for discovering new intents.
 	///
-	/// Polls the blockchain for new Open events and sends discovered
-	/// intents through the provided channel.
+	/// Without a WebSocket URL, polls the blockchain for new Open events over
+	/// HTTP. With one, subscribes to new heads and Open logs instead, falling
+	/// back to HTTP polling while the WebSocket connection is down and
+	/// resubscribing once it can be re-established.
 	async fn monitoring_loop(
 		provider: RootProvider<Http<reqwest::Client>>,
+		ws_url: Option<String>,
 		settler_addresses: Vec<AlloyAddress>,
 		last_block: Arc<Mutex<u64>>,
-		sender: mpsc::UnboundedSender<Intent>,
+		chunk_size: u64,
+		sender: IntentSender,
 		mut stop_rx: mpsc::Receiver<()>,
 	) {
+		loop {
+			if let Some(ws_url) = &ws_url {
+				match Self::subscription_loop(
+					ws_url,
+					&provider,
+					&settler_addresses,
+					&last_block,
+					chunk_size,
+					&sender,
+					&mut stop_rx,
+				)
+				.await
+				{
+					Ok(()) => break,
+					Err(e) => {
+						tracing::warn!(error = %e, "WebSocket discovery unavailable, polling over HTTP");
+					}
+				}
+			}
+
+			// Poll until stopped, or until it is time to retry the WebSocket
+			let retry_after = ws_url.as_ref().map(|_| WS_RECONNECT_INTERVAL);
+			if Self::polling_loop(
+				&provider,
+				&settler_addresses,
+				&last_block,
+				chunk_size,
+				&sender,
+				&mut stop_rx,
+				retry_after,
+			)
+			.await
+			{
+				break;
+			}
+		}
+	}
+
+	/// Polls for new Open events over HTTP.
+	///
+	/// Returns true if monitoring was stopped, or false once `retry_after` has
+	/// passed.
+	async fn polling_loop(
+		provider: &RootProvider<Http<reqwest::Client>>,
+		settler_addresses: &[AlloyAddress],
+		last_block: &Mutex<u64>,
+		chunk_size: u64,
+		sender: &IntentSender,
+		stop_rx: &mut mpsc::Receiver<()>,
+		retry_after: Option<Duration>,
+	) -> bool {
 		// TODO: make this configurable
-		let mut interval = tokio::time::interval(std::time::Duration::from_secs(3));
+		let mut interval = tokio::time::interval(Duration::from_secs(3));
+		let started = tokio::time::Instant::now();
 
 		loop {
 			tokio::select! {
 				_ = interval.tick() => {
-					let mut last_block_num = last_block.lock().await;
-
-					// Get current block
-					let current_block = match provider.get_block_number().await {
-						Ok(block) => block,
-						Err(e) => {
-							tracing::error!("Failed to get block number: {}", e);
-							continue;
-						}
-					};
-
-					if current_block <= *last_block_num {
-						continue; // No new blocks
+					if retry_after.is_some_and(|retry_after| started.elapsed() >= retry_after) {
+						return false;
 					}
+					if let Err(e) = Self::poll_once(provider, settler_addresses, last_block, chunk_size, sender).await {
+						tracing::error!("{}", e);
+					}
+				}
+				_ = stop_rx.recv() => {
+					return true;
+				}
+			}
+		}
+	}
 
-					// Create filter for Open events
-					let open_sig = Open::SIGNATURE_HASH;
+	/// Fetches Open events from the blocks after the last processed one up to
+	/// the current block over HTTP.
+	///
+	/// Blocks are fetched `chunk_size` at a time, so that a long backlog does
+	/// not exceed the node's log query limits. The last processed block moves
+	/// forward after each chunk.
+	async fn poll_once(
+		provider: &RootProvider<Http<reqwest::Client>>,
+		settler_addresses: &[AlloyAddress],
+		last_block: &Mutex<u64>,
+		chunk_size: u64,
+		sender: &IntentSender,
+	) -> Result<(), DiscoveryError> {
+		// Get current block
+		let current_block = provider.get_block_number().await.map_err(|e| {
+			DiscoveryError::Connection(format!("Failed to get block number: {}", e))
+		})?;
 
-					let filter = Filter::new()
-						.address(settler_addresses.clone())
-						.event_signature(vec![open_sig])
-						.from_block(*last_block_num + 1)
-						.to_block(current_block);
+		let mut from_block = *last_block.lock().await + 1;
+		if current_block < from_block {
+			return Ok(()); // No new blocks
+		}
 
-					// Get logs
-					let logs = match provider.get_logs(&filter).await {
-						Ok(logs) => logs,
-						Err(_) => {
-							continue;
-						}
-					};
-
-					// Parse logs into intents
-					for log in logs {
-						if let Ok(intent) = Self::parse_open_event(&Eip7683Discovery {
-							provider: provider.clone(),
-							settler_addresses: settler_addresses.clone(),
-							last_block: last_block.clone(),
-							is_monitoring: Arc::new(AtomicBool::new(true)),
-							stop_signal: Arc::new(Mutex::new(None)),
-						}, &log).await {
-							let _ = sender.send(intent);
-						}
-					}
+		let backfilling = current_block - from_block >= chunk_size;
+		if backfilling {
+			tracing::info!(
+				from_block,
+				to_block = current_block,
+				"Backfilling Open events"
+			);
+		}
 
-					// Update last block
-					*last_block_num = current_block;
+		while from_block <= current_block {
+			let to_block = current_block.min(from_block + chunk_size - 1);
+			let filter = Self::open_filter(settler_addresses)
+				.from_block(from_block)
+				.to_block(to_block);
+
+			// Get logs
+			let logs = provider
+				.get_logs(&filter)
+				.await
+				.map_err(|e| DiscoveryError::Connection(format!("Failed to get logs: {}", e)))?;
+
+			// Parse logs into intents
+			for log in logs {
+				if let Ok(intent) = Self::parse_open_event(&log) {
+					let _ = sender.send(intent).await;
 				}
+			}
+
+			// Update last block
+			*last_block.lock().await = to_block;
+			from_block = to_block + 1;
+		}
+
+		if backfilling {
+			tracing::info!(last_block = current_block, "Backfill complete");
+		}
+		Ok(())
+	}
+
+	/// Receives Open events through WebSocket subscriptions.
+	///
+	/// Blocks missed before the subscriptions are in place are fetched over
+	/// HTTP first. Returns `Ok` if monitoring was stopped, or an error once the
+	/// connection is lost.
+	async fn subscription_loop(
+		ws_url: &str,
+		provider: &RootProvider<Http<reqwest::Client>>,
+		settler_addresses: &[AlloyAddress],
+		last_block: &Mutex<u64>,
+		chunk_size: u64,
+		sender: &IntentSender,
+		stop_rx: &mut mpsc::Receiver<()>,
+	) -> Result<(), DiscoveryError> {
+		let ws = ProviderBuilder::new()
+			.on_ws(WsConnect::new(ws_url))
+			.await
+			.map_err(|e| DiscoveryError::Connection(format!("Failed to connect: {}", e)))?;
+
+		let mut heads = ws
+			.subscribe_blocks()
+			.await
+			.map_err(|e| DiscoveryError::Connection(format!("Failed to subscribe: {}", e)))?;
+		let mut logs = ws
+			.subscribe_logs(&Self::open_filter(settler_addresses))
+			.await
+			.map_err(|e| DiscoveryError::Connection(format!("Failed to subscribe: {}", e)))?;
+
+		// Catch up on blocks produced while not subscribed
+		Self::poll_once(provider, settler_addresses, last_block, chunk_size, sender).await?;
+		tracing::info!("Subscribed to Open events over WebSocket");
+
+		loop {
+			tokio::select! {
+				log = logs.recv() => match log {
+					Ok(log) => {
+						if let Ok(intent) = Self::parse_open_event(&log) {
+							let _ = sender.send(intent).await;
+						}
+					}
+					Err(RecvError::Lagged(skipped)) => {
+						tracing::warn!(skipped, "Log subscription lagged");
+					}
+					Err(RecvError::Closed) => {
+						return Err(DiscoveryError::Connection("Log subscription closed".to_string()));
+					}
+				},
+				head = heads.recv() => match head {
+					// Logs for the block arrive through the log subscription
+					Ok(header) => {
+						let mut last_block_num = last_block.lock().await;
+						*last_block_num = (*last_block_num).max(header.number);
+					}
+					Err(RecvError::Lagged(_)) => {}
+					Err(RecvError::Closed) => {
+						return Err(DiscoveryError::Connection("Block subscription closed".to_string()));
+					}
+				},
 				_ = stop_rx.recv() => {
-					break;
+					return Ok(());
 				}
 			}
 		}
 	}
+
+	/// Returns a filter matching Open events emitted by the settlers.
+	fn open_filter(settler_addresses: &[AlloyAddress]) -> Filter {
+		Filter::new()
+			.address(settler_addresses.to_vec())
+			.event_signature(vec![Open::SIGNATURE_HASH])
+	}
 }
 
 /// Configuration schema for EIP-7683 discovery.
@@ -298,13 +444,28 @@ impl ConfigSchema for Eip7683DiscoverySchema {
 			],
 			// Optional fields
 			vec![
+				Field::new("ws_url", FieldType::String).with_validator(|value| {
+					let url = value.as_str().unwrap();
+					if url.starts_with("ws://") || url.starts_with("wss://") {
+						Ok(())
+					} else {
+						Err("WebSocket URL must start with ws:// or wss://".to_string())
+					}
+				}),
 				Field::new(
-					"start_block",
+					"from_block",
 					FieldType::Integer {
 						min: Some(0),
 						max: None,
 					},
 				),
+				Field::new(
+					"backfill_chunk_size",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
 				Field::new(
 					"block_confirmations",
 					FieldType::Integer {
@@ -324,10 +485,7 @@ impl DiscoveryInterface for Eip7683Discovery {
 	fn config_schema(&self) -> Box<dyn ConfigSchema> {
 		Box::new(Eip7683DiscoverySchema)
 	}
-	async fn start_monitoring(
-		&self,
-		sender: mpsc::UnboundedSender<Intent>,
-	) -> Result<(), DiscoveryError> {
+	async fn start_monitoring(&self, sender: IntentSender) -> Result<(), DiscoveryError> {
 		if self.is_monitoring.load(Ordering::SeqCst) {
 			return Err(DiscoveryError::AlreadyMonitoring);
 		}
@@ -337,12 +495,29 @@ impl DiscoveryInterface for Eip7683Discovery {
 
 		// Spawn monitoring task
 		let provider = self.provider.clone();
+		let ws_url = self.ws_url.clone();
 		let settler_addresses = self.settler_addresses.clone();
 		let last_block = self.last_block.clone();
+		let chunk_size = self.chunk_size;
 
-		tokio::spawn(async move {
-			Self::monitoring_loop(provider, settler_addresses, last_block, sender, stop_rx).await;
-		});
+		// Keep the monitoring task's logs attributable to this source
+		let span = tracing::info_span!(
+			"discovery_monitor",
+			source = "eip7683",
+			settlers = settler_addresses.len()
+		);
+		tokio::spawn(
+			Self::monitoring_loop(
+				provider,
+				ws_url,
+				settler_addresses,
+				last_block,
+				chunk_size,
+				sender,
+				stop_rx,
+			)
+			.instrument(span),
+		);
 
 		self.is_monitoring.store(true, Ordering::SeqCst);
 		Ok(())
@@ -360,6 +535,33 @@ impl DiscoveryInterface for Eip7683Discovery {
 		self.is_monitoring.store(false, Ordering::SeqCst);
 		Ok(())
 	}
+
+	async fn backfill(&self, blocks: u64) {
+		if self.is_monitoring.load(Ordering::SeqCst) {
+			return;
+		}
+
+		let mut last_block = self.last_block.lock().await;
+		*last_block = last_block.saturating_sub(blocks);
+	}
+
+	async fn last_processed_block(&self) -> Option<u64> {
+		Some(*self.last_block.lock().await)
+	}
+
+	async fn resume_from(&self, block: u64) {
+		if self.is_monitoring.load(Ordering::SeqCst) {
+			return;
+		}
+
+		// A configured starting block later than the checkpoint takes precedence
+		let block = match self.from_block {
+			Some(from_block) => block.max(from_block.saturating_sub(1)),
+			None => block,
+		};
+		*self.last_block.lock().await = block;
+		tracing::info!(last_block = block, "Resuming discovery from checkpoint");
+	}
 }
 
 /// Factory function to create an EIP-7683 discovery provider from configuration.
@@ -368,6 +570,11 @@ impl DiscoveryInterface for Eip7683Discovery {
 /// instance. Required configuration parameters:
 /// - `rpc_url`: The HTTP RPC endpoint URL
 /// - `settler_addresses`: Array of contract addresses to monitor
+///
+/// Optional configuration parameters:
+/// - `ws_url`: WebSocket endpoint used to subscribe to new events
+/// - `from_block`: First block to scan for events, defaults to the current block
+/// - `backfill_chunk_size`: Blocks fetched per log query (default: 1000)
 pub fn create_discovery(config: &toml::Value) -> Box<dyn DiscoveryInterface> {
 	let rpc_url = config
 		.get("rpc_url")
@@ -384,10 +591,27 @@ pub fn create_discovery(config: &toml::Value) -> Box<dyn DiscoveryInterface> {
 		})
 		.unwrap_or_default();
 
+	let ws_url = config
+		.get("ws_url")
+		.and_then(|v| v.as_str())
+		.map(String::from);
+
+	let from_block = config
+		.get("from_block")
+		.and_then(|v| v.as_integer())
+		.map(|v| v as u64);
+
+	let chunk_size = config
+		.get("backfill_chunk_size")
+		.and_then(|v| v.as_integer())
+		.map(|v| v as u64)
+		.unwrap_or(DEFAULT_CHUNK_SIZE);
+
 	// Create discovery service synchronously
 	let discovery = tokio::task::block_in_place(|| {
-		tokio::runtime::Handle::current()
-			.block_on(async { Eip7683Discovery::new(rpc_url, settler_addresses).await })
+		tokio::runtime::Handle::current().block_on(async {
+			Eip7683Discovery::new(rpc_url, ws_url, settler_addresses, from_block, chunk_size).await
+		})
 	});
 
 	Box::new(discovery.expect("Failed to create discovery service"))
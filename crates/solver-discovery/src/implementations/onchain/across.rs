@@ -0,0 +1,350 @@
+//! Discovery of Across V3 deposits.
+//!
+//! Depositors open Across orders by depositing into the SpokePool of the
+//! origin chain, which emits `V3FundsDeposited`. This source polls a
+//! SpokePool for these events and turns each deposit into an intent of the
+//! `across` standard, identified by the deposit's relay hash, the key the
+//! destination SpokePool tracks the fill status of the deposit under.
+
+use crate::{DiscoveryError, DiscoveryInterface, IntentSender};
+use alloy_primitives::{keccak256, Address as AlloyAddress, U256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{Filter, Log};
+use alloy_sol_types::{sol, SolEvent, SolValue};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use solver_types::{
+	decode_rpc_log, ConfigSchema, Field, FieldType, Intent, IntentMetadata, Schema,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tracing::Instrument;
+
+/// Interval between polls for new deposits.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+sol! {
+	/// Relay data identifying a V3 deposit, hashed into its relay hash.
+	struct V3RelayData {
+		address depositor;
+		address recipient;
+		address exclusiveRelayer;
+		address inputToken;
+		address outputToken;
+		uint256 inputAmount;
+		uint256 outputAmount;
+		uint256 originChainId;
+		uint32 depositId;
+		uint32 fillDeadline;
+		uint32 exclusivityDeadline;
+		bytes message;
+	}
+
+	/// Event emitted by the origin SpokePool for every V3 deposit.
+	event V3FundsDeposited(
+		address inputToken,
+		address outputToken,
+		uint256 inputAmount,
+		uint256 outputAmount,
+		uint256 indexed destinationChainId,
+		uint32 indexed depositId,
+		uint32 quoteTimestamp,
+		uint32 fillDeadline,
+		uint32 exclusivityDeadline,
+		address indexed depositor,
+		address recipient,
+		address exclusiveRelayer,
+		bytes message
+	);
+}
+
+/// Across V3 deposit discovery implementation.
+pub struct AcrossDiscovery {
+	/// The Alloy provider of the origin chain.
+	provider: RootProvider<Http<reqwest::Client>>,
+	/// Chain ID of the origin chain.
+	origin_chain_id: u64,
+	/// SpokePool to monitor for deposits.
+	spoke_pool_address: AlloyAddress,
+	/// The last processed block number.
+	last_block: Arc<Mutex<u64>>,
+	/// Flag indicating if monitoring is active.
+	is_monitoring: Arc<AtomicBool>,
+	/// Channel for signaling monitoring shutdown.
+	stop_signal: Arc<Mutex<Option<mpsc::Sender<()>>>>,
+}
+
+impl AcrossDiscovery {
+	/// Creates a discovery source for deposits into the given SpokePool.
+	pub async fn new(rpc_url: &str, spoke_pool_address: &str) -> Result<Self, DiscoveryError> {
+		let provider = RootProvider::new_http(
+			rpc_url
+				.parse()
+				.map_err(|e| DiscoveryError::Connection(format!("Invalid RPC URL: {}", e)))?,
+		);
+		let spoke_pool_address = spoke_pool_address.parse().map_err(|e| {
+			DiscoveryError::Connection(format!("Invalid spoke pool address: {}", e))
+		})?;
+
+		let origin_chain_id = provider
+			.get_chain_id()
+			.await
+			.map_err(|e| DiscoveryError::Connection(format!("Failed to get chain ID: {}", e)))?;
+		let current_block = provider.get_block_number().await.map_err(|e| {
+			DiscoveryError::Connection(format!("Failed to get block number: {}", e))
+		})?;
+
+		Ok(Self {
+			provider,
+			origin_chain_id,
+			spoke_pool_address,
+			last_block: Arc::new(Mutex::new(current_block)),
+			is_monitoring: Arc::new(AtomicBool::new(false)),
+			stop_signal: Arc::new(Mutex::new(None)),
+		})
+	}
+
+	/// Parses a V3FundsDeposited event log into an Intent.
+	pub fn parse_deposit_event(log: &Log, origin_chain_id: u64) -> Result<Intent, DiscoveryError> {
+		let deposit = decode_rpc_log::<V3FundsDeposited>(log)
+			.map_err(|e| DiscoveryError::Connection(format!("Failed to decode event: {}", e)))?;
+
+		let relay_data = V3RelayData {
+			depositor: deposit.depositor,
+			recipient: deposit.recipient,
+			exclusiveRelayer: deposit.exclusiveRelayer,
+			inputToken: deposit.inputToken,
+			outputToken: deposit.outputToken,
+			inputAmount: deposit.inputAmount,
+			outputAmount: deposit.outputAmount,
+			originChainId: U256::from(origin_chain_id),
+			depositId: deposit.depositId,
+			fillDeadline: deposit.fillDeadline,
+			exclusivityDeadline: deposit.exclusivityDeadline,
+			message: deposit.message.clone(),
+		};
+		let relay_hash = keccak256((relay_data, deposit.destinationChainId).abi_encode_params());
+
+		let order_data = serde_json::json!({
+			"depositor": deposit.depositor.to_string(),
+			"recipient": deposit.recipient.to_string(),
+			"exclusive_relayer": deposit.exclusiveRelayer.to_string(),
+			"input_token": deposit.inputToken.to_string(),
+			"output_token": deposit.outputToken.to_string(),
+			"input_amount": deposit.inputAmount.to_string(),
+			"output_amount": deposit.outputAmount.to_string(),
+			"origin_chain_id": origin_chain_id,
+			"destination_chain_id": deposit.destinationChainId.to::<u64>(),
+			"deposit_id": deposit.depositId,
+			"quote_timestamp": deposit.quoteTimestamp,
+			"fill_deadline": deposit.fillDeadline,
+			"exclusivity_deadline": deposit.exclusivityDeadline,
+			"message": format!("0x{}", hex::encode(&deposit.message)),
+		});
+
+		Ok(Intent {
+			id: hex::encode(relay_hash),
+			source: "across".to_string(),
+			standard: "across".to_string(),
+			metadata: IntentMetadata {
+				requires_auction: false,
+				exclusive_until: None,
+				discovered_at: std::time::SystemTime::now()
+					.duration_since(std::time::UNIX_EPOCH)
+					.unwrap()
+					.as_secs(),
+				settler: Some(format!("0x{}", hex::encode(log.address()))),
+				block_number: log.block_number,
+				transaction_hash: log
+					.transaction_hash
+					.map(|hash| format!("0x{}", hex::encode(hash))),
+				custom: Default::default(),
+			},
+			data: order_data,
+		})
+	}
+
+	/// Polls for new deposits until stopped.
+	async fn monitoring_loop(
+		provider: RootProvider<Http<reqwest::Client>>,
+		origin_chain_id: u64,
+		spoke_pool_address: AlloyAddress,
+		last_block: Arc<Mutex<u64>>,
+		sender: IntentSender,
+		mut stop_rx: mpsc::Receiver<()>,
+	) {
+		let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+		loop {
+			tokio::select! {
+				_ = interval.tick() => {
+					if let Err(e) = Self::poll_once(&provider, origin_chain_id, spoke_pool_address, &last_block, &sender).await {
+						tracing::error!("{}", e);
+					}
+				}
+				_ = stop_rx.recv() => {
+					break;
+				}
+			}
+		}
+	}
+
+	/// Fetches deposits from the blocks after the last processed one up to
+	/// the current block.
+	async fn poll_once(
+		provider: &RootProvider<Http<reqwest::Client>>,
+		origin_chain_id: u64,
+		spoke_pool_address: AlloyAddress,
+		last_block: &Mutex<u64>,
+		sender: &IntentSender,
+	) -> Result<(), DiscoveryError> {
+		let mut last_block_num = last_block.lock().await;
+
+		let current_block = provider.get_block_number().await.map_err(|e| {
+			DiscoveryError::Connection(format!("Failed to get block number: {}", e))
+		})?;
+		if current_block <= *last_block_num {
+			return Ok(());
+		}
+
+		let filter = Filter::new()
+			.address(spoke_pool_address)
+			.event_signature(V3FundsDeposited::SIGNATURE_HASH)
+			.from_block(*last_block_num + 1)
+			.to_block(current_block);
+		let logs = provider
+			.get_logs(&filter)
+			.await
+			.map_err(|e| DiscoveryError::Connection(format!("Failed to get logs: {}", e)))?;
+
+		for log in logs {
+			match Self::parse_deposit_event(&log, origin_chain_id) {
+				Ok(intent) => {
+					let _ = sender.send(intent).await;
+				}
+				Err(e) => tracing::warn!(error = %e, "Skipping undecodable deposit"),
+			}
+		}
+
+		*last_block_num = current_block;
+		Ok(())
+	}
+}
+
+/// Configuration schema for Across discovery.
+pub struct AcrossDiscoverySchema;
+
+impl ConfigSchema for AcrossDiscoverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("rpc_url", FieldType::String).with_validator(|value| {
+					let url = value.as_str().unwrap();
+					if url.starts_with("http://") || url.starts_with("https://") {
+						Ok(())
+					} else {
+						Err("RPC URL must start with http:// or https://".to_string())
+					}
+				}),
+				Field::new("spoke_pool_address", FieldType::String).with_validator(|value| {
+					let addr = value.as_str().unwrap();
+					if addr.len() != 42 || !addr.starts_with("0x") {
+						return Err(
+							"spoke_pool_address must be a valid Ethereum address".to_string()
+						);
+					}
+					Ok(())
+				}),
+			],
+			// Optional fields
+			vec![],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl DiscoveryInterface for AcrossDiscovery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(AcrossDiscoverySchema)
+	}
+
+	async fn start_monitoring(&self, sender: IntentSender) -> Result<(), DiscoveryError> {
+		if self.is_monitoring.load(Ordering::SeqCst) {
+			return Err(DiscoveryError::AlreadyMonitoring);
+		}
+
+		let (stop_tx, stop_rx) = mpsc::channel(1);
+		*self.stop_signal.lock().await = Some(stop_tx);
+
+		let span = tracing::info_span!(
+			"discovery_monitor",
+			source = "across",
+			chain_id = self.origin_chain_id
+		);
+		tokio::spawn(
+			Self::monitoring_loop(
+				self.provider.clone(),
+				self.origin_chain_id,
+				self.spoke_pool_address,
+				self.last_block.clone(),
+				sender,
+				stop_rx,
+			)
+			.instrument(span),
+		);
+
+		self.is_monitoring.store(true, Ordering::SeqCst);
+		Ok(())
+	}
+
+	async fn stop_monitoring(&self) -> Result<(), DiscoveryError> {
+		if !self.is_monitoring.load(Ordering::SeqCst) {
+			return Ok(());
+		}
+
+		if let Some(stop_tx) = self.stop_signal.lock().await.take() {
+			let _ = stop_tx.send(()).await;
+		}
+
+		self.is_monitoring.store(false, Ordering::SeqCst);
+		Ok(())
+	}
+
+	async fn backfill(&self, blocks: u64) {
+		if self.is_monitoring.load(Ordering::SeqCst) {
+			return;
+		}
+
+		let mut last_block = self.last_block.lock().await;
+		*last_block = last_block.saturating_sub(blocks);
+	}
+}
+
+/// Factory function to create an Across discovery source from configuration.
+///
+/// Required configuration parameters:
+/// - `rpc_url`: The HTTP RPC endpoint URL of the origin chain
+/// - `spoke_pool_address`: Address of the origin chain's SpokePool
+pub fn create_discovery(config: &toml::Value) -> Box<dyn DiscoveryInterface> {
+	let rpc_url = config
+		.get("rpc_url")
+		.and_then(|v| v.as_str())
+		.expect("rpc_url is required");
+
+	let spoke_pool_address = config
+		.get("spoke_pool_address")
+		.and_then(|v| v.as_str())
+		.expect("spoke_pool_address is required");
+
+	let discovery = tokio::task::block_in_place(|| {
+		tokio::runtime::Handle::current()
+			.block_on(async { AcrossDiscovery::new(rpc_url, spoke_pool_address).await })
+	});
+
+	Box::new(discovery.expect("Failed to create discovery service"))
+}
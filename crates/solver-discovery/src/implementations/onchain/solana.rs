@@ -0,0 +1,559 @@
+//! Discovery of orders opened on a Solana intent program.
+//!
+//! The intent program emits an `OrderOpened` event, in Anchor's format, for
+//! every order a user opens: the event's 8-byte discriminator followed by its
+//! borsh-encoded fields, logged as a `Program data:` line. This source polls
+//! the signatures of the program's transactions, reads the events from the
+//! logs of each successful one and turns them into intents of the
+//! `solana_intents` standard. Slots stand in for block numbers, so
+//! checkpoints and backfills work as they do for EVM chains.
+
+use crate::{DiscoveryError, DiscoveryInterface, IntentSender};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use solver_types::{
+	program_data_logs, ConfigSchema, Field, FieldType, Intent, IntentMetadata, Schema,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tracing::Instrument;
+
+/// Interval between polls for new orders.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Time the RPC endpoint has to answer a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Signatures fetched per request while catching up.
+const SIGNATURES_PAGE_SIZE: usize = 1000;
+
+/// Discriminator of the `OrderOpened` event, the first 8 bytes of the
+/// SHA-256 hash of "event:OrderOpened".
+const ORDER_OPENED_DISCRIMINATOR: [u8; 8] = [2, 254, 197, 24, 41, 166, 225, 56];
+
+/// Length of an `OrderOpened` event after its discriminator.
+const ORDER_OPENED_LENGTH: usize = 200;
+
+/// Signature of a transaction that touched the program.
+#[derive(Deserialize)]
+struct SignatureInfo {
+	signature: String,
+	slot: u64,
+	err: Option<serde_json::Value>,
+}
+
+/// Transaction as returned by `getTransaction`.
+#[derive(Deserialize)]
+struct RpcTransaction {
+	meta: Option<RpcTransactionMeta>,
+}
+
+/// Execution outcome of a transaction.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTransactionMeta {
+	log_messages: Option<Vec<String>>,
+}
+
+/// JSON-RPC response envelope.
+#[derive(Deserialize)]
+struct RpcResponse {
+	result: Option<serde_json::Value>,
+	error: Option<RpcErrorObject>,
+}
+
+/// Error object of a JSON-RPC response.
+#[derive(Deserialize)]
+struct RpcErrorObject {
+	message: String,
+}
+
+/// Order opened on the intent program, as emitted in its event.
+struct OrderOpened {
+	order_id: [u8; 32],
+	user: [u8; 32],
+	input_mint: [u8; 32],
+	input_amount: u64,
+	destination_chain_id: u64,
+	output_token: [u8; 32],
+	output_amount: u128,
+	recipient: [u8; 32],
+	fill_deadline: u32,
+	expires: u32,
+}
+
+impl OrderOpened {
+	/// Decodes an event, returning `None` for other events of the program.
+	fn decode(data: &[u8]) -> Option<Self> {
+		let fields = data.strip_prefix(&ORDER_OPENED_DISCRIMINATOR)?;
+		if fields.len() < ORDER_OPENED_LENGTH {
+			return None;
+		}
+
+		let mut offset = 0;
+		let mut take = |length: usize| {
+			let bytes = &fields[offset..offset + length];
+			offset += length;
+			bytes
+		};
+		Some(Self {
+			order_id: take(32).try_into().ok()?,
+			user: take(32).try_into().ok()?,
+			input_mint: take(32).try_into().ok()?,
+			input_amount: u64::from_le_bytes(take(8).try_into().ok()?),
+			destination_chain_id: u64::from_le_bytes(take(8).try_into().ok()?),
+			output_token: take(32).try_into().ok()?,
+			output_amount: u128::from_le_bytes(take(16).try_into().ok()?),
+			recipient: take(32).try_into().ok()?,
+			fill_deadline: u32::from_le_bytes(take(4).try_into().ok()?),
+			expires: u32::from_le_bytes(take(4).try_into().ok()?),
+		})
+	}
+}
+
+/// JSON-RPC client of the cluster the program is deployed on.
+#[derive(Clone)]
+struct SolanaRpc {
+	/// HTTP client for JSON-RPC requests.
+	client: reqwest::Client,
+	/// JSON-RPC endpoint of the cluster.
+	rpc_url: String,
+	/// Commitment level the program's transactions are read at.
+	commitment: String,
+}
+
+impl SolanaRpc {
+	/// Sends a JSON-RPC request and decodes its result.
+	async fn request<T: DeserializeOwned>(
+		&self,
+		method: &str,
+		params: serde_json::Value,
+	) -> Result<T, DiscoveryError> {
+		let request = json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"method": method,
+			"params": params,
+		});
+		let body = self
+			.client
+			.post(&self.rpc_url)
+			.header(reqwest::header::CONTENT_TYPE, "application/json")
+			.body(request.to_string())
+			.send()
+			.await
+			.map_err(|e| DiscoveryError::Connection(format!("{} failed: {}", method, e)))?
+			.bytes()
+			.await
+			.map_err(|e| DiscoveryError::Connection(format!("{} failed: {}", method, e)))?;
+
+		let response: RpcResponse = serde_json::from_slice(&body).map_err(|e| {
+			DiscoveryError::Connection(format!("Invalid {} response: {}", method, e))
+		})?;
+		if let Some(error) = response.error {
+			return Err(DiscoveryError::Connection(format!(
+				"{} failed: {}",
+				method, error.message
+			)));
+		}
+		serde_json::from_value(response.result.unwrap_or_default())
+			.map_err(|e| DiscoveryError::Connection(format!("Invalid {} response: {}", method, e)))
+	}
+
+	/// Returns the latest slot at the configured commitment level.
+	async fn slot(&self) -> Result<u64, DiscoveryError> {
+		self.request("getSlot", json!([{ "commitment": self.commitment }]))
+			.await
+	}
+}
+
+/// Solana intent program discovery implementation.
+pub struct SolanaDiscovery {
+	/// Client of the cluster.
+	rpc: SolanaRpc,
+	/// Chain ID the solver knows the cluster by.
+	origin_chain_id: u64,
+	/// Intent program to monitor, base58-encoded.
+	program_id: String,
+	/// The last processed slot.
+	last_slot: Arc<Mutex<u64>>,
+	/// Flag indicating if monitoring is active.
+	is_monitoring: Arc<AtomicBool>,
+	/// Channel for signaling monitoring shutdown.
+	stop_signal: Arc<Mutex<Option<mpsc::Sender<()>>>>,
+}
+
+impl SolanaDiscovery {
+	/// Creates a discovery source for orders opened on the given program,
+	/// starting at `from_slot` or else at the current slot.
+	pub async fn new(
+		rpc_url: &str,
+		program_id: &str,
+		origin_chain_id: u64,
+		commitment: &str,
+		from_slot: Option<u64>,
+	) -> Result<Self, DiscoveryError> {
+		let client = reqwest::Client::builder()
+			.timeout(REQUEST_TIMEOUT)
+			.build()
+			.map_err(|e| DiscoveryError::Connection(e.to_string()))?;
+		let rpc = SolanaRpc {
+			client,
+			rpc_url: rpc_url.to_string(),
+			commitment: commitment.to_string(),
+		};
+
+		let last_slot = match from_slot {
+			Some(slot) => slot.saturating_sub(1),
+			None => rpc.slot().await?,
+		};
+
+		Ok(Self {
+			rpc,
+			origin_chain_id,
+			program_id: program_id.to_string(),
+			last_slot: Arc::new(Mutex::new(last_slot)),
+			is_monitoring: Arc::new(AtomicBool::new(false)),
+			stop_signal: Arc::new(Mutex::new(None)),
+		})
+	}
+
+	/// Converts an `OrderOpened` event into an Intent.
+	fn order_intent(
+		order: &OrderOpened,
+		origin_chain_id: u64,
+		program_id: &str,
+		signature: &SignatureInfo,
+	) -> Intent {
+		let order_data = json!({
+			"user": bs58::encode(order.user).into_string(),
+			"input_mint": bs58::encode(order.input_mint).into_string(),
+			"input_amount": order.input_amount.to_string(),
+			"origin_chain_id": origin_chain_id,
+			"destination_chain_id": order.destination_chain_id,
+			"output_token": format!("0x{}", hex::encode(order.output_token)),
+			"output_amount": order.output_amount.to_string(),
+			"recipient": format!("0x{}", hex::encode(order.recipient)),
+			"fill_deadline": order.fill_deadline,
+			"expires": order.expires,
+		});
+
+		Intent {
+			id: hex::encode(order.order_id),
+			source: "solana".to_string(),
+			standard: "solana_intents".to_string(),
+			metadata: IntentMetadata {
+				requires_auction: false,
+				exclusive_until: None,
+				discovered_at: std::time::SystemTime::now()
+					.duration_since(std::time::UNIX_EPOCH)
+					.unwrap()
+					.as_secs(),
+				settler: Some(program_id.to_string()),
+				block_number: Some(signature.slot),
+				transaction_hash: bs58::decode(&signature.signature)
+					.into_vec()
+					.ok()
+					.map(|hash| format!("0x{}", hex::encode(hash))),
+				custom: Default::default(),
+			},
+			data: order_data,
+		}
+	}
+
+	/// Polls for new orders until stopped.
+	async fn monitoring_loop(
+		rpc: SolanaRpc,
+		origin_chain_id: u64,
+		program_id: String,
+		last_slot: Arc<Mutex<u64>>,
+		sender: IntentSender,
+		mut stop_rx: mpsc::Receiver<()>,
+	) {
+		let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+		loop {
+			tokio::select! {
+				_ = interval.tick() => {
+					if let Err(e) = Self::poll_once(&rpc, origin_chain_id, &program_id, &last_slot, &sender).await {
+						tracing::error!("{}", e);
+					}
+				}
+				_ = stop_rx.recv() => {
+					break;
+				}
+			}
+		}
+	}
+
+	/// Fetches the orders opened in the program's transactions after the last
+	/// processed slot.
+	///
+	/// Signatures are listed newest first, so they are paged back until the
+	/// last processed slot and then handled oldest first.
+	async fn poll_once(
+		rpc: &SolanaRpc,
+		origin_chain_id: u64,
+		program_id: &str,
+		last_slot: &Mutex<u64>,
+		sender: &IntentSender,
+	) -> Result<(), DiscoveryError> {
+		let mut last_slot_num = last_slot.lock().await;
+
+		let mut signatures: Vec<SignatureInfo> = Vec::new();
+		let mut before: Option<String> = None;
+		loop {
+			let page: Vec<SignatureInfo> = rpc
+				.request(
+					"getSignaturesForAddress",
+					json!([
+						program_id,
+						{
+							"limit": SIGNATURES_PAGE_SIZE,
+							"before": before,
+							"commitment": rpc.commitment,
+						}
+					]),
+				)
+				.await?;
+			let full = page.len() == SIGNATURES_PAGE_SIZE;
+
+			let mut caught_up = false;
+			for signature in page {
+				if signature.slot <= *last_slot_num {
+					caught_up = true;
+					break;
+				}
+				before = Some(signature.signature.clone());
+				signatures.push(signature);
+			}
+			if caught_up || !full {
+				break;
+			}
+		}
+
+		for signature in signatures.iter().rev() {
+			if signature.err.is_some() {
+				continue;
+			}
+
+			let transaction: Option<RpcTransaction> = match rpc
+				.request(
+					"getTransaction",
+					json!([
+						signature.signature,
+						{
+							"encoding": "json",
+							"commitment": rpc.commitment,
+							"maxSupportedTransactionVersion": 0,
+						}
+					]),
+				)
+				.await
+			{
+				Ok(transaction) => transaction,
+				Err(e) => {
+					// Resume with this transaction's slot on the next poll
+					*last_slot_num = (*last_slot_num).max(signature.slot.saturating_sub(1));
+					return Err(e);
+				}
+			};
+			let logs = transaction
+				.and_then(|transaction| transaction.meta)
+				.and_then(|meta| meta.log_messages)
+				.unwrap_or_default();
+
+			for log in program_data_logs(&logs) {
+				if bs58::encode(&log.address.0).into_string() != program_id {
+					continue;
+				}
+				if let Some(order) = OrderOpened::decode(&log.data) {
+					let _ = sender
+						.send(Self::order_intent(
+							&order,
+							origin_chain_id,
+							program_id,
+							signature,
+						))
+						.await;
+				}
+			}
+		}
+
+		if let Some(newest) = signatures.first() {
+			*last_slot_num = newest.slot;
+		}
+		Ok(())
+	}
+}
+
+/// Configuration schema for Solana discovery.
+pub struct SolanaDiscoverySchema;
+
+impl ConfigSchema for SolanaDiscoverySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("rpc_url", FieldType::String).with_validator(|value| {
+					let url = value.as_str().unwrap();
+					if url.starts_with("http://") || url.starts_with("https://") {
+						Ok(())
+					} else {
+						Err("RPC URL must start with http:// or https://".to_string())
+					}
+				}),
+				Field::new("program_id", FieldType::String).with_validator(|value| {
+					match bs58::decode(value.as_str().unwrap()).into_vec() {
+						Ok(bytes) if bytes.len() == 32 => Ok(()),
+						_ => Err("program_id must be a base58-encoded public key".to_string()),
+					}
+				}),
+				Field::new(
+					"chain_id",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+			],
+			// Optional fields
+			vec![
+				Field::new("commitment", FieldType::String).with_validator(|value| {
+					match value.as_str().unwrap() {
+						"confirmed" | "finalized" => Ok(()),
+						_ => Err("commitment must be confirmed or finalized".to_string()),
+					}
+				}),
+				Field::new(
+					"from_slot",
+					FieldType::Integer {
+						min: Some(0),
+						max: None,
+					},
+				),
+			],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl DiscoveryInterface for SolanaDiscovery {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(SolanaDiscoverySchema)
+	}
+
+	async fn start_monitoring(&self, sender: IntentSender) -> Result<(), DiscoveryError> {
+		if self.is_monitoring.load(Ordering::SeqCst) {
+			return Err(DiscoveryError::AlreadyMonitoring);
+		}
+
+		let (stop_tx, stop_rx) = mpsc::channel(1);
+		*self.stop_signal.lock().await = Some(stop_tx);
+
+		let span = tracing::info_span!(
+			"discovery_monitor",
+			source = "solana",
+			chain_id = self.origin_chain_id
+		);
+		tokio::spawn(
+			Self::monitoring_loop(
+				self.rpc.clone(),
+				self.origin_chain_id,
+				self.program_id.clone(),
+				self.last_slot.clone(),
+				sender,
+				stop_rx,
+			)
+			.instrument(span),
+		);
+
+		self.is_monitoring.store(true, Ordering::SeqCst);
+		Ok(())
+	}
+
+	async fn stop_monitoring(&self) -> Result<(), DiscoveryError> {
+		if !self.is_monitoring.load(Ordering::SeqCst) {
+			return Ok(());
+		}
+
+		if let Some(stop_tx) = self.stop_signal.lock().await.take() {
+			let _ = stop_tx.send(()).await;
+		}
+
+		self.is_monitoring.store(false, Ordering::SeqCst);
+		Ok(())
+	}
+
+	async fn backfill(&self, blocks: u64) {
+		if self.is_monitoring.load(Ordering::SeqCst) {
+			return;
+		}
+
+		let mut last_slot = self.last_slot.lock().await;
+		*last_slot = last_slot.saturating_sub(blocks);
+	}
+
+	async fn last_processed_block(&self) -> Option<u64> {
+		Some(*self.last_slot.lock().await)
+	}
+
+	async fn resume_from(&self, block: u64) {
+		if self.is_monitoring.load(Ordering::SeqCst) {
+			return;
+		}
+
+		*self.last_slot.lock().await = block;
+	}
+}
+
+/// Factory function to create a Solana discovery source from configuration.
+///
+/// Required configuration parameters:
+/// - `rpc_url`: The JSON-RPC endpoint URL of the cluster
+/// - `program_id`: The base58-encoded address of the intent program
+/// - `chain_id`: The chain ID the solver knows the cluster by
+///
+/// Optional configuration parameters:
+/// - `commitment`: Commitment level orders are read at (default: "confirmed")
+/// - `from_slot`: Slot to scan from on first start (default: the current slot)
+pub fn create_discovery(config: &toml::Value) -> Box<dyn DiscoveryInterface> {
+	let rpc_url = config
+		.get("rpc_url")
+		.and_then(|v| v.as_str())
+		.expect("rpc_url is required");
+
+	let program_id = config
+		.get("program_id")
+		.and_then(|v| v.as_str())
+		.expect("program_id is required");
+
+	let chain_id = config
+		.get("chain_id")
+		.and_then(|v| v.as_integer())
+		.expect("chain_id is required") as u64;
+
+	let commitment = config
+		.get("commitment")
+		.and_then(|v| v.as_str())
+		.unwrap_or("confirmed");
+
+	let from_slot = config
+		.get("from_slot")
+		.and_then(|v| v.as_integer())
+		.map(|slot| slot as u64);
+
+	let discovery = tokio::task::block_in_place(|| {
+		tokio::runtime::Handle::current().block_on(async {
+			SolanaDiscovery::new(rpc_url, program_id, chain_id, commitment, from_slot).await
+		})
+	});
+
+	Box::new(discovery.expect("Failed to create Solana discovery service"))
+}
@@ -0,0 +1,598 @@
+//! Bounded intake of discovered intents.
+//!
+//! Discovery sources hand every intent they find to the intake, and the
+//! engine takes them from it as fast as it can handle them. The intake holds
+//! a bounded number of intents in memory. Once it is full, its overflow
+//! policy decides what happens to newly discovered intents: the oldest
+//! waiting intent is evicted, the new intent is refused, or the new intent
+//! is spilled to storage and taken back once the engine catches up.
+//!
+//! The intake is saturated from the moment it overflows until it drains to
+//! half its capacity. Each saturation is counted, so that the engine can
+//! report it.
+//!
+//! Before an intent is queued, it is checked against the IDs of recently
+//! queued intents, since the same order can be found on-chain and submitted
+//! off-chain. It then has to fit within the rate limit of its source and the
+//! global rate limit. Duplicates are counted and refused. Intents exceeding a
+//! rate limit are counted and held back until the limit allows them, except
+//! for intents submitted by users, which are refused so the user can retry.
+
+use solver_storage::{StorageError, StorageService};
+use solver_types::{Intent, RateLimit, RateLimiter};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::Notify;
+
+/// Storage namespace holding spilled intents, keyed by spill sequence.
+const SPILL_NAMESPACE: &str = "discovery_spill";
+
+/// Sequence numbering spilled intents in the order they were spilled.
+const SPILL_SEQUENCE: &str = "discovery_spill";
+
+/// What happens to intents discovered while the intake is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+	/// The longest-waiting intent is evicted to make room.
+	DropOldest,
+	/// The new intent is refused.
+	Reject,
+	/// The new intent is held in storage until there is room.
+	Spill,
+}
+
+impl std::fmt::Display for OverflowPolicy {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::DropOldest => write!(f, "drop_oldest"),
+			Self::Reject => write!(f, "reject"),
+			Self::Spill => write!(f, "spill"),
+		}
+	}
+}
+
+/// Errors that can occur when handing an intent to the intake.
+#[derive(Debug, Error)]
+pub enum IntakeError {
+	/// The intake is full and refuses new intents.
+	#[error("Intake queue is full")]
+	Full,
+	/// The intake no longer has a receiver.
+	#[error("Intake queue is closed")]
+	Closed,
+	/// The intake is full and the intent could not be spilled to storage.
+	#[error("Failed to spill intent: {0}")]
+	Spill(#[from] StorageError),
+	/// An intent with the same ID was queued recently.
+	#[error("Duplicate intent")]
+	Duplicate,
+	/// The intent's source, or the intake as a whole, exceeded its rate
+	/// limit.
+	#[error("Rate limit of source {0} exceeded")]
+	RateLimited(String),
+}
+
+/// Deduplication and rate limits applied to intents before they are queued.
+#[derive(Debug, Clone, Default)]
+pub struct IntakeLimits {
+	/// Number of recently queued intent IDs remembered to refuse duplicates.
+	/// Duplicates are not checked if zero.
+	pub dedup_window: usize,
+	/// Intents per second queued from all sources together, if limited.
+	pub max_per_second: Option<f64>,
+	/// Intents per second queued from each source, keyed by the source
+	/// named in the intents.
+	pub source_max_per_second: HashMap<String, f64>,
+}
+
+/// Counters describing the intake.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IntakeStats {
+	/// Intents waiting in memory.
+	pub depth: usize,
+	/// Intents waiting in storage.
+	pub spilled: usize,
+	/// Number of times the intake became saturated.
+	pub saturations: u64,
+	/// Intents evicted to make room for newer ones.
+	pub dropped_total: u64,
+	/// Intents refused because the intake was full.
+	pub rejected_total: u64,
+	/// Intents spilled to storage because the intake was full.
+	pub spilled_total: u64,
+	/// Intents refused because an intent with the same ID was queued
+	/// recently.
+	pub duplicate_total: u64,
+	/// Intents delayed or refused because of a rate limit.
+	pub rate_limited_total: u64,
+}
+
+/// Why the gate refused an intent.
+enum Refusal {
+	/// An intent with the same ID was queued recently.
+	Duplicate,
+	/// A rate limit allows the intent only after waiting this long.
+	RateLimited(Duration),
+}
+
+/// Checks applied to intents before they are queued.
+struct Gate {
+	/// IDs of recently queued intents.
+	seen: HashSet<String>,
+	/// The same IDs, oldest first, to forget them once the window is full.
+	seen_order: VecDeque<String>,
+	/// Number of IDs remembered.
+	dedup_window: usize,
+	/// Rate limit of all sources together, if any.
+	global: Option<RateLimit>,
+	/// Rate limits keyed by source.
+	sources: HashMap<String, RateLimit>,
+	/// Token buckets of the limits, keyed by source, or `None` for the
+	/// global limit.
+	limiter: RateLimiter<Option<String>>,
+}
+
+impl Gate {
+	/// Creates the checks described by `limits`.
+	fn new(limits: IntakeLimits) -> Self {
+		Self {
+			seen: HashSet::new(),
+			seen_order: VecDeque::new(),
+			dedup_window: limits.dedup_window,
+			global: limits.max_per_second.map(RateLimit::per_second),
+			sources: limits
+				.source_max_per_second
+				.into_iter()
+				.map(|(source, rate)| (source, RateLimit::per_second(rate)))
+				.collect(),
+			limiter: RateLimiter::new(),
+		}
+	}
+
+	/// Admits an intent, remembering its ID, or returns why it is refused.
+	///
+	/// Duplicates are refused before they take from any rate limit, and an
+	/// intent only takes from its limits once all of them allow it.
+	fn admit(&mut self, intent: &Intent, now: Instant) -> Result<(), Refusal> {
+		if self.dedup_window > 0 && self.seen.contains(&intent.id) {
+			return Err(Refusal::Duplicate);
+		}
+
+		let source = Some(intent.source.clone());
+		let limits = [
+			(source, self.sources.get(&intent.source).copied()),
+			(None, self.global),
+		];
+		let wait = limits
+			.iter()
+			.filter_map(|(key, limit)| Some(self.limiter.wait_time(key, (*limit)?, now)))
+			.max()
+			.unwrap_or_default();
+		if !wait.is_zero() {
+			return Err(Refusal::RateLimited(wait));
+		}
+		// Every limit allows the intent, so none of the takes fail
+		for (key, limit) in &limits {
+			if let Some(limit) = limit {
+				self.limiter.try_take(key, *limit, now).ok();
+			}
+		}
+
+		if self.dedup_window > 0 {
+			self.seen.insert(intent.id.clone());
+			self.seen_order.push_back(intent.id.clone());
+			while self.seen_order.len() > self.dedup_window {
+				if let Some(id) = self.seen_order.pop_front() {
+					self.seen.remove(&id);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Forgets the ID of an intent, so that it is admitted again.
+	fn forget(&mut self, intent_id: &str) {
+		if self.seen.remove(intent_id) {
+			self.seen_order.retain(|id| id != intent_id);
+		}
+	}
+}
+
+/// Intents waiting in the intake.
+#[derive(Default)]
+struct Buffer {
+	/// Intents waiting in memory, oldest first.
+	queue: VecDeque<Intent>,
+	/// Spill sequences of the intents waiting in storage, oldest first.
+	spilled: VecDeque<u64>,
+	/// Whether the intake overflowed and has not drained since.
+	saturated: bool,
+}
+
+/// State shared by the sending and receiving halves of the intake.
+struct Shared {
+	/// Intents waiting in the intake.
+	buffer: Mutex<Buffer>,
+	/// Intents held in memory before the intake overflows.
+	capacity: usize,
+	/// What happens to intents discovered while the intake is full.
+	policy: OverflowPolicy,
+	/// Storage holding spilled intents.
+	storage: Arc<StorageService>,
+	/// Deduplication and rate limits.
+	gate: Mutex<Gate>,
+	/// Wakes the receiver when intents arrive or the last sender goes away.
+	notify: Notify,
+	/// Number of live senders.
+	senders: AtomicUsize,
+	/// Whether the receiver was dropped.
+	closed: AtomicBool,
+	/// Number of times the intake became saturated.
+	saturations: AtomicU64,
+	/// Intents evicted to make room for newer ones.
+	dropped: AtomicU64,
+	/// Intents refused because the intake was full.
+	rejected: AtomicU64,
+	/// Intents spilled to storage because the intake was full.
+	spilled: AtomicU64,
+	/// Intents refused as duplicates.
+	duplicates: AtomicU64,
+	/// Intents delayed or refused because of a rate limit.
+	rate_limited: AtomicU64,
+}
+
+impl Shared {
+	/// Marks the intake saturated, counting the saturation if it is new.
+	fn saturate(&self, buffer: &mut Buffer) {
+		if !buffer.saturated {
+			buffer.saturated = true;
+			self.saturations.fetch_add(1, Ordering::Relaxed);
+			tracing::warn!(
+				capacity = self.capacity,
+				policy = %self.policy,
+				"Discovery intake saturated"
+			);
+		}
+	}
+}
+
+/// Creates an intake holding up to `capacity` intents in memory, with
+/// `policy` applied to intents discovered while it is full and `limits`
+/// to every intent.
+///
+/// Intents a previous run spilled to `storage` and never took back are
+/// queued ahead of anything discovered by this run.
+pub async fn channel(
+	capacity: usize,
+	policy: OverflowPolicy,
+	limits: IntakeLimits,
+	storage: Arc<StorageService>,
+) -> Result<(IntentSender, IntentReceiver), StorageError> {
+	let mut spilled: Vec<u64> = storage
+		.list_keys(SPILL_NAMESPACE, "")
+		.await?
+		.iter()
+		.filter_map(|key| key.parse().ok())
+		.collect();
+	spilled.sort_unstable();
+	if !spilled.is_empty() {
+		tracing::info!(count = spilled.len(), "Recovered spilled intents");
+	}
+
+	let shared = Arc::new(Shared {
+		buffer: Mutex::new(Buffer {
+			queue: VecDeque::new(),
+			spilled: spilled.into(),
+			saturated: false,
+		}),
+		capacity,
+		policy,
+		storage,
+		gate: Mutex::new(Gate::new(limits)),
+		notify: Notify::new(),
+		senders: AtomicUsize::new(1),
+		closed: AtomicBool::new(false),
+		saturations: AtomicU64::new(0),
+		dropped: AtomicU64::new(0),
+		rejected: AtomicU64::new(0),
+		spilled: AtomicU64::new(0),
+		duplicates: AtomicU64::new(0),
+		rate_limited: AtomicU64::new(0),
+	});
+	Ok((
+		IntentSender {
+			shared: shared.clone(),
+		},
+		IntentReceiver {
+			shared,
+			taken: None,
+		},
+	))
+}
+
+/// Sending half of the intake, shared by the discovery sources.
+pub struct IntentSender {
+	shared: Arc<Shared>,
+}
+
+impl IntentSender {
+	/// Hands a discovered intent to the intake.
+	///
+	/// Waits while the intent exceeds a rate limit, so that intents found
+	/// on-chain are delayed rather than lost. Fails if the intent duplicates
+	/// a recent one, if the intake is full and refuses new intents, if the
+	/// intent could not be spilled, or if the engine stopped taking intents.
+	/// Intents evicted or refused are logged here, so sources may ignore the
+	/// result.
+	pub async fn send(&self, intent: Intent) -> Result<(), IntakeError> {
+		self.enqueue(intent, true).await
+	}
+
+	/// Hands an intent submitted by a user to the intake.
+	///
+	/// Unlike [`send`](Self::send), fails rather than waiting if the intent
+	/// exceeds a rate limit, so the submitter can be told to retry.
+	pub async fn submit(&self, intent: Intent) -> Result<(), IntakeError> {
+		self.enqueue(intent, false).await
+	}
+
+	/// Queues an intent, waiting out rate limits if `wait` is set.
+	async fn enqueue(&self, intent: Intent, wait: bool) -> Result<(), IntakeError> {
+		let shared = &self.shared;
+		let mut delayed = false;
+		loop {
+			if shared.closed.load(Ordering::Acquire) {
+				return Err(IntakeError::Closed);
+			}
+
+			let admitted = shared.gate.lock().unwrap().admit(&intent, Instant::now());
+			match admitted {
+				Ok(()) => break,
+				Err(Refusal::Duplicate) => {
+					shared.duplicates.fetch_add(1, Ordering::Relaxed);
+					tracing::debug!(
+						intent_id = %intent.id,
+						source = %intent.source,
+						"Discovery intake refused duplicate intent"
+					);
+					return Err(IntakeError::Duplicate);
+				}
+				Err(Refusal::RateLimited(delay)) => {
+					if !delayed {
+						shared.rate_limited.fetch_add(1, Ordering::Relaxed);
+					}
+					if !wait {
+						tracing::warn!(
+							intent_id = %intent.id,
+							source = %intent.source,
+							"Discovery intake rate limited intent"
+						);
+						return Err(IntakeError::RateLimited(intent.source.clone()));
+					}
+					if !delayed {
+						tracing::debug!(
+							intent_id = %intent.id,
+							source = %intent.source,
+							delay_ms = delay.as_millis() as u64,
+							"Discovery intake delaying rate limited intent"
+						);
+						delayed = true;
+					}
+					tokio::time::sleep(delay).await;
+				}
+			}
+		}
+
+		let overflow = {
+			let mut buffer = shared.buffer.lock().unwrap();
+			// While spilling, intents only bypass storage once earlier spills
+			// were taken back, so that they are received in discovery order
+			let spilling = shared.policy == OverflowPolicy::Spill && !buffer.spilled.is_empty();
+			if buffer.queue.len() < shared.capacity && !spilling {
+				buffer.queue.push_back(intent);
+				None
+			} else {
+				shared.saturate(&mut buffer);
+				match shared.policy {
+					OverflowPolicy::DropOldest => {
+						if let Some(evicted) = buffer.queue.pop_front() {
+							shared.dropped.fetch_add(1, Ordering::Relaxed);
+							tracing::warn!(
+								intent_id = %evicted.id,
+								"Discovery intake full, dropped oldest intent"
+							);
+						}
+						buffer.queue.push_back(intent);
+						None
+					}
+					OverflowPolicy::Reject => {
+						shared.gate.lock().unwrap().forget(&intent.id);
+						shared.rejected.fetch_add(1, Ordering::Relaxed);
+						tracing::warn!(
+							intent_id = %intent.id,
+							"Discovery intake full, rejected intent"
+						);
+						return Err(IntakeError::Full);
+					}
+					OverflowPolicy::Spill => Some(intent),
+				}
+			}
+		};
+
+		if let Some(intent) = overflow {
+			let sequence = shared.storage.next_sequence(SPILL_SEQUENCE).await?;
+			if let Err(e) = shared
+				.storage
+				.store(SPILL_NAMESPACE, &spill_key(sequence), &intent)
+				.await
+			{
+				tracing::error!(
+					intent_id = %intent.id,
+					error = %e,
+					"Failed to spill intent, dropping it"
+				);
+				shared.gate.lock().unwrap().forget(&intent.id);
+				return Err(e.into());
+			}
+			shared.buffer.lock().unwrap().spilled.push_back(sequence);
+			shared.spilled.fetch_add(1, Ordering::Relaxed);
+		}
+		shared.notify.notify_one();
+		Ok(())
+	}
+}
+
+impl Clone for IntentSender {
+	fn clone(&self) -> Self {
+		self.shared.senders.fetch_add(1, Ordering::Relaxed);
+		Self {
+			shared: self.shared.clone(),
+		}
+	}
+}
+
+impl Drop for IntentSender {
+	fn drop(&mut self) {
+		if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+			self.shared.notify.notify_one();
+		}
+	}
+}
+
+/// Receiving half of the intake, owned by the engine.
+pub struct IntentReceiver {
+	shared: Arc<Shared>,
+	/// Spill sequence of an intent taken back from storage whose entry is
+	/// yet to be removed.
+	taken: Option<u64>,
+}
+
+impl IntentReceiver {
+	/// Waits for the next intent, oldest first.
+	///
+	/// Returns `None` once every sender is gone and the intake is drained.
+	/// Cancelling the wait loses no intents.
+	pub async fn recv(&mut self) -> Option<Intent> {
+		if let Some(sequence) = self.taken {
+			let key = spill_key(sequence);
+			if let Err(e) = self.shared.storage.remove(SPILL_NAMESPACE, &key).await {
+				tracing::warn!(sequence, error = %e, "Failed to remove spilled intent");
+			}
+			self.taken = None;
+		}
+
+		loop {
+			if let Some(intent) = self.try_recv() {
+				return Some(intent);
+			}
+			let next_spilled = self.shared.buffer.lock().unwrap().spilled.front().copied();
+			if let Some(sequence) = next_spilled {
+				if let Some(intent) = self.unspill(sequence).await {
+					return Some(intent);
+				}
+				continue;
+			}
+			if self.shared.senders.load(Ordering::Acquire) == 0 {
+				return None;
+			}
+			self.shared.notify.notified().await;
+		}
+	}
+
+	/// Takes the next intent waiting in memory, if any.
+	pub fn try_recv(&mut self) -> Option<Intent> {
+		let mut buffer = self.shared.buffer.lock().unwrap();
+		let intent = buffer.queue.pop_front()?;
+		if buffer.saturated
+			&& buffer.spilled.is_empty()
+			&& buffer.queue.len() <= self.shared.capacity / 2
+		{
+			buffer.saturated = false;
+			tracing::info!("Discovery intake drained");
+		}
+		Some(intent)
+	}
+
+	/// Takes a spilled intent back from storage.
+	///
+	/// Returns `None` if the intent is gone from storage. Its entry is only
+	/// removed by the next wait, so that a wait cancelled before the intent
+	/// is returned leaves it in place.
+	async fn unspill(&mut self, sequence: u64) -> Option<Intent> {
+		let key = spill_key(sequence);
+		let intent = match self.shared.storage.retrieve(SPILL_NAMESPACE, &key).await {
+			Ok(intent) => Some(intent),
+			Err(StorageError::NotFound) => None,
+			Err(e) => {
+				tracing::error!(sequence, error = %e, "Failed to read spilled intent, dropping it");
+				None
+			}
+		};
+
+		let mut buffer = self.shared.buffer.lock().unwrap();
+		if buffer.spilled.front() == Some(&sequence) {
+			buffer.spilled.pop_front();
+		}
+		self.taken = Some(sequence);
+		intent
+	}
+
+	/// Returns the number of intents waiting, in memory and in storage.
+	pub fn len(&self) -> usize {
+		let buffer = self.shared.buffer.lock().unwrap();
+		buffer.queue.len() + buffer.spilled.len()
+	}
+
+	/// Returns whether no intents are waiting.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns the number of intents held in memory before the intake
+	/// overflows.
+	pub fn capacity(&self) -> usize {
+		self.shared.capacity
+	}
+
+	/// Returns the policy applied to intents discovered while the intake is
+	/// full.
+	pub fn policy(&self) -> OverflowPolicy {
+		self.shared.policy
+	}
+
+	/// Forgets that an intent was queued, so that it is accepted when it is
+	/// discovered again, as after a reorg removed it.
+	pub fn forget(&self, intent_id: &str) {
+		self.shared.gate.lock().unwrap().forget(intent_id);
+	}
+
+	/// Returns the intake's current counters.
+	pub fn stats(&self) -> IntakeStats {
+		let buffer = self.shared.buffer.lock().unwrap();
+		IntakeStats {
+			depth: buffer.queue.len(),
+			spilled: buffer.spilled.len(),
+			saturations: self.shared.saturations.load(Ordering::Relaxed),
+			dropped_total: self.shared.dropped.load(Ordering::Relaxed),
+			rejected_total: self.shared.rejected.load(Ordering::Relaxed),
+			spilled_total: self.shared.spilled.load(Ordering::Relaxed),
+			duplicate_total: self.shared.duplicates.load(Ordering::Relaxed),
+			rate_limited_total: self.shared.rate_limited.load(Ordering::Relaxed),
+		}
+	}
+}
+
+impl Drop for IntentReceiver {
+	fn drop(&mut self) {
+		self.shared.closed.store(true, Ordering::Release);
+	}
+}
+
+/// Returns the storage key of a spilled intent, padded so that keys sort in
+/// spill order.
+fn spill_key(sequence: u64) -> String {
+	format!("{:020}", sequence)
+}
@@ -5,16 +5,27 @@
 //! on-chain event monitoring, off-chain APIs, or other intent sources.
 
 use async_trait::async_trait;
-use solver_types::{ConfigSchema, Intent};
+use solver_types::{Classify, ConfigSchema, ErrorCategory, ErrorClass, Intent};
 use thiserror::Error;
-use tokio::sync::mpsc;
+
+pub mod intake;
+
+pub use intake::{
+	IntakeError, IntakeLimits, IntakeStats, IntentReceiver, IntentSender, OverflowPolicy,
+};
 
 /// Re-export implementations
 pub mod implementations {
 	pub mod onchain {
 		pub mod _7683;
+		pub mod across;
+		pub mod solana;
+	}
+	pub mod offchain {
+		pub mod _7683;
 	}
-	pub mod offchain {}
+	#[cfg(feature = "testing")]
+	pub mod mock;
 }
 
 /// Errors that can occur during intent discovery operations.
@@ -26,6 +37,23 @@ pub enum DiscoveryError {
 	/// Error that occurs when trying to start monitoring on an already active source.
 	#[error("Already monitoring")]
 	AlreadyMonitoring,
+	/// Error that occurs when a submitted intent is refused.
+	#[error("Submission rejected: {0}")]
+	Rejected(String),
+	/// Error that occurs when no source accepts submitted intents.
+	#[error("Submissions not supported")]
+	SubmissionUnsupported,
+}
+
+impl Classify for DiscoveryError {
+	fn class(&self) -> ErrorClass {
+		match self {
+			Self::Connection(_) => ErrorClass::transient(ErrorCategory::Chain),
+			Self::Rejected(_) => ErrorClass::permanent(ErrorCategory::Validation),
+			Self::SubmissionUnsupported => ErrorClass::permanent(ErrorCategory::Configuration),
+			Self::AlreadyMonitoring => ErrorClass::permanent(ErrorCategory::Internal),
+		}
+	}
 }
 
 /// Trait defining the interface for intent discovery sources.
@@ -44,18 +72,46 @@ pub trait DiscoveryInterface: Send + Sync {
 
 	/// Starts monitoring for new intents from this source.
 	///
-	/// Discovered intents are sent through the provided channel. The implementation
+	/// Discovered intents are handed to the provided intake. The implementation
 	/// should continue monitoring until stop_monitoring is called or an error occurs.
-	async fn start_monitoring(
-		&self,
-		sender: mpsc::UnboundedSender<Intent>,
-	) -> Result<(), DiscoveryError>;
+	async fn start_monitoring(&self, sender: IntentSender) -> Result<(), DiscoveryError>;
 
 	/// Stops monitoring for new intents from this source.
 	///
 	/// This method should cleanly shut down any active monitoring tasks
 	/// and release associated resources.
 	async fn stop_monitoring(&self) -> Result<(), DiscoveryError>;
+
+	/// Moves the starting point of monitoring back by the given number of
+	/// blocks, so that intents published while the solver was down are found.
+	///
+	/// Only takes effect before monitoring starts. Sources that are not
+	/// block-based ignore it.
+	async fn backfill(&self, _blocks: u64) {}
+
+	/// Returns the last block whose events were handed on, for block-based
+	/// sources.
+	///
+	/// Persisted by the solver so that monitoring can resume from it after a
+	/// restart.
+	async fn last_processed_block(&self) -> Option<u64> {
+		None
+	}
+
+	/// Resumes monitoring with the block after a checkpointed one, scanning
+	/// any blocks produced since before switching to live monitoring.
+	///
+	/// Only takes effect before monitoring starts. Sources that are not
+	/// block-based ignore it.
+	async fn resume_from(&self, _block: u64) {}
+
+	/// Accepts an intent submitted directly to the solver, forwarding it
+	/// to the intake given to `start_monitoring`.
+	///
+	/// Only sources that receive intents off-chain accept submissions.
+	async fn submit(&self, _intent: Intent) -> Result<(), DiscoveryError> {
+		Err(DiscoveryError::SubmissionUnsupported)
+	}
 }
 
 /// Service that manages multiple intent discovery sources.
@@ -63,40 +119,76 @@ pub trait DiscoveryInterface: Send + Sync {
 /// The DiscoveryService coordinates multiple discovery sources, allowing
 /// the solver to find intents from various channels simultaneously.
 pub struct DiscoveryService {
-	/// Collection of discovery sources to monitor.
-	sources: Vec<Box<dyn DiscoveryInterface>>,
+	/// Collection of discovery sources to monitor, keyed by name.
+	sources: Vec<(String, Box<dyn DiscoveryInterface>)>,
 }
 
 impl DiscoveryService {
-	/// Creates a new DiscoveryService with the specified sources.
+	/// Creates a new DiscoveryService with the specified named sources.
 	///
 	/// Each source will be monitored independently when monitoring is started.
-	pub fn new(sources: Vec<Box<dyn DiscoveryInterface>>) -> Self {
+	/// Names identify the sources' checkpoints across restarts.
+	pub fn new(sources: Vec<(String, Box<dyn DiscoveryInterface>)>) -> Self {
 		Self { sources }
 	}
 
 	/// Starts monitoring on all configured discovery sources.
 	///
-	/// All discovered intents from any source will be sent through the
-	/// provided channel. If any source fails to start, the entire operation
+	/// All discovered intents from any source will be handed to the
+	/// provided intake. If any source fails to start, the entire operation
 	/// fails and no sources will be monitoring.
-	pub async fn start_all(
-		&self,
-		sender: mpsc::UnboundedSender<Intent>,
-	) -> Result<(), DiscoveryError> {
-		for source in &self.sources {
+	pub async fn start_all(&self, sender: IntentSender) -> Result<(), DiscoveryError> {
+		for (_, source) in &self.sources {
 			source.start_monitoring(sender.clone()).await?;
 		}
 		Ok(())
 	}
 
+	/// Moves the starting point of all sources back by the given number of
+	/// blocks before monitoring starts.
+	pub async fn backfill_all(&self, blocks: u64) {
+		for (_, source) in &self.sources {
+			source.backfill(blocks).await;
+		}
+	}
+
+	/// Returns the last processed block of each block-based source, keyed by
+	/// source name.
+	pub async fn checkpoints(&self) -> Vec<(String, u64)> {
+		let mut checkpoints = Vec::new();
+		for (name, source) in &self.sources {
+			if let Some(block) = source.last_processed_block().await {
+				checkpoints.push((name.clone(), block));
+			}
+		}
+		checkpoints
+	}
+
+	/// Resumes a source from its checkpointed block before monitoring starts.
+	pub async fn resume(&self, name: &str, block: u64) {
+		if let Some((_, source)) = self.sources.iter().find(|(source, _)| source == name) {
+			source.resume_from(block).await;
+		}
+	}
+
+	/// Hands a submitted intent to the first source that accepts submissions.
+	pub async fn submit(&self, intent: Intent) -> Result<(), DiscoveryError> {
+		for (_, source) in &self.sources {
+			match source.submit(intent.clone()).await {
+				Err(DiscoveryError::SubmissionUnsupported) => continue,
+				result => return result,
+			}
+		}
+		Err(DiscoveryError::SubmissionUnsupported)
+	}
+
 	/// Stops monitoring on all active discovery sources.
 	///
 	/// This method attempts to stop all sources, even if some fail.
 	/// The first error encountered is returned, but all sources are
 	/// attempted to be stopped.
 	pub async fn stop_all(&self) -> Result<(), DiscoveryError> {
-		for source in &self.sources {
+		for (_, source) in &self.sources {
 			source.stop_monitoring().await?;
 		}
 		Ok(())
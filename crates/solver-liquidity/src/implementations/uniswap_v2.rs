@@ -0,0 +1,177 @@
+//! Liquidity source implementations for the solver service.
+//!
+//! This module provides a concrete implementation of the LiquidityInterface trait
+//! backed by a Uniswap V2 compatible router, quoting swaps through `getAmountsOut`.
+
+use crate::{LiquidityError, LiquidityInterface};
+use alloy_primitives::{Address as AlloyAddress, U256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{TransactionInput, TransactionRequest};
+use alloy_sol_types::{sol, SolCall};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use solver_types::{Address, ConfigSchema, Field, FieldType, Schema};
+
+// Solidity type definitions for Uniswap V2 router quotes.
+sol! {
+	/// Subset of the Uniswap V2 router used for quoting swaps.
+	interface IUniswapV2Router {
+		function getAmountsOut(uint256 amountIn, address[] path) external view returns (uint256[] amounts);
+	}
+}
+
+/// Uniswap V2 router based liquidity source.
+///
+/// Quotes direct swaps from a token into the configured quote token using the
+/// router's `getAmountsOut` view function.
+pub struct UniswapV2Liquidity {
+	/// The Alloy provider for blockchain interaction.
+	provider: RootProvider<Http<reqwest::Client>>,
+	/// Address of the Uniswap V2 compatible router.
+	router: AlloyAddress,
+	/// Token that quotes are denominated in.
+	quote_token: AlloyAddress,
+}
+
+impl UniswapV2Liquidity {
+	/// Creates a new UniswapV2Liquidity instance.
+	pub fn new(rpc_url: &str, router: &str, quote_token: &str) -> Result<Self, LiquidityError> {
+		let provider = RootProvider::new_http(
+			rpc_url
+				.parse()
+				.map_err(|e| LiquidityError::Network(format!("Invalid RPC URL: {}", e)))?,
+		);
+
+		let router = router
+			.parse::<AlloyAddress>()
+			.map_err(|e| LiquidityError::Network(format!("Invalid router address: {}", e)))?;
+		let quote_token = quote_token
+			.parse::<AlloyAddress>()
+			.map_err(|e| LiquidityError::Network(format!("Invalid quote token address: {}", e)))?;
+
+		Ok(Self {
+			provider,
+			router,
+			quote_token,
+		})
+	}
+}
+
+/// Configuration schema for UniswapV2Liquidity.
+pub struct UniswapV2LiquiditySchema;
+
+impl ConfigSchema for UniswapV2LiquiditySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("rpc_url", FieldType::String).with_validator(|value| {
+					let url = value.as_str().unwrap();
+					if url.starts_with("http://") || url.starts_with("https://") {
+						Ok(())
+					} else {
+						Err("RPC URL must start with http:// or https://".to_string())
+					}
+				}),
+				Field::new(
+					"chain_id",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+				Field::new("router_address", FieldType::String).with_validator(|value| {
+					let addr = value.as_str().unwrap();
+					if addr.len() != 42 || !addr.starts_with("0x") {
+						return Err("router_address must be a valid Ethereum address".to_string());
+					}
+					Ok(())
+				}),
+				Field::new("quote_token", FieldType::String).with_validator(|value| {
+					let addr = value.as_str().unwrap();
+					if addr.len() != 42 || !addr.starts_with("0x") {
+						return Err("quote_token must be a valid Ethereum address".to_string());
+					}
+					Ok(())
+				}),
+			],
+			// Optional fields
+			vec![],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl LiquidityInterface for UniswapV2Liquidity {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(UniswapV2LiquiditySchema)
+	}
+
+	fn quote_token(&self) -> Address {
+		Address(self.quote_token.as_slice().to_vec())
+	}
+
+	async fn get_amount_out(&self, token: &Address, amount: U256) -> Result<U256, LiquidityError> {
+		if token.0.len() != 20 {
+			return Err(LiquidityError::NoLiquidity(hex::encode(&token.0)));
+		}
+		let token_address = AlloyAddress::from_slice(&token.0);
+
+		let call_data = IUniswapV2Router::getAmountsOutCall {
+			amountIn: amount,
+			path: vec![token_address, self.quote_token],
+		}
+		.abi_encode();
+
+		let request = TransactionRequest::default()
+			.to(self.router)
+			.input(TransactionInput::new(call_data.into()));
+
+		// The router reverts when no pair exists for the path
+		let result = self
+			.provider
+			.call(&request)
+			.await
+			.map_err(|_| LiquidityError::NoLiquidity(hex::encode(&token.0)))?;
+
+		let decoded = IUniswapV2Router::getAmountsOutCall::abi_decode_returns(&result, true)
+			.map_err(|e| LiquidityError::Network(format!("Failed to decode quote: {}", e)))?;
+
+		decoded
+			.amounts
+			.last()
+			.copied()
+			.ok_or_else(|| LiquidityError::NoLiquidity(hex::encode(&token.0)))
+	}
+}
+
+/// Factory function to create a Uniswap V2 liquidity source from configuration.
+///
+/// Required configuration parameters:
+/// - `rpc_url`: The HTTP RPC endpoint URL
+/// - `chain_id`: The chain the router is deployed on
+/// - `router_address`: Address of the Uniswap V2 compatible router
+/// - `quote_token`: Token quotes are denominated in (e.g. WETH)
+pub fn create_liquidity(config: &toml::Value) -> Box<dyn LiquidityInterface> {
+	let rpc_url = config
+		.get("rpc_url")
+		.and_then(|v| v.as_str())
+		.expect("rpc_url is required");
+
+	let router = config
+		.get("router_address")
+		.and_then(|v| v.as_str())
+		.expect("router_address is required");
+
+	let quote_token = config
+		.get("quote_token")
+		.and_then(|v| v.as_str())
+		.expect("quote_token is required");
+
+	Box::new(
+		UniswapV2Liquidity::new(rpc_url, router, quote_token)
+			.expect("Failed to create liquidity source"),
+	)
+}
@@ -0,0 +1,223 @@
+//! Tracking of the solver's token inventory.
+//!
+//! The inventory service polls the solver's native balance on every chain it
+//! operates on, and its balance of each tracked ERC-20 token, caching the
+//! results for strategies and the API. Balances falling below a configured
+//! threshold are logged as warnings, once when they drop and once when they
+//! recover, so operators can top the solver up before fills start failing.
+
+use crate::LiquidityError;
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_types::{Address, InventoryBalance};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Source of on-chain balances.
+///
+/// Implemented by the engine on top of the chains it delivers transactions
+/// to, so that the inventory shares their RPC connections.
+#[async_trait]
+pub trait BalanceReader: Send + Sync {
+	/// Returns the balance of a chain's native gas token held by `owner`.
+	async fn native_balance(&self, chain_id: u64, owner: &Address) -> Result<U256, LiquidityError>;
+
+	/// Returns the balance of an ERC-20 token held by `owner`.
+	async fn token_balance(
+		&self,
+		chain_id: u64,
+		token: &Address,
+		owner: &Address,
+	) -> Result<U256, LiquidityError>;
+}
+
+/// A tracked balance, keyed by chain ID and token, `None` for the native
+/// gas token.
+type BalanceKey = (u64, Option<Address>);
+
+/// Last polled value of a tracked balance.
+struct CachedBalance {
+	/// Balance in the token's smallest unit.
+	amount: U256,
+	/// When the balance was polled.
+	fetched_at: Instant,
+	/// Unix timestamp of the poll.
+	updated_at: u64,
+}
+
+/// Service polling and caching the solver's balances.
+pub struct InventoryService {
+	/// Source the balances are read from.
+	reader: Box<dyn BalanceReader>,
+	/// Chains whose native balance is tracked.
+	chains: Vec<u64>,
+	/// ERC-20 tokens tracked, keyed by chain ID.
+	tokens: HashMap<u64, Vec<Address>>,
+	/// Balances below which a warning is logged.
+	thresholds: HashMap<BalanceKey, U256>,
+	/// Last polled balances.
+	balances: Mutex<HashMap<BalanceKey, CachedBalance>>,
+	/// Whether a refresh is running, so that slow polls do not pile up.
+	refreshing: AtomicBool,
+}
+
+impl InventoryService {
+	/// Creates a service tracking native balances on `chains` and the given
+	/// tokens, without low-balance thresholds.
+	pub fn new(
+		reader: Box<dyn BalanceReader>,
+		chains: Vec<u64>,
+		tokens: HashMap<u64, Vec<Address>>,
+	) -> Self {
+		Self {
+			reader,
+			chains,
+			tokens,
+			thresholds: HashMap::new(),
+			balances: Mutex::new(HashMap::new()),
+			refreshing: AtomicBool::new(false),
+		}
+	}
+
+	/// Warns when a balance drops below `min_balance`.
+	///
+	/// `token` is `None` for the chain's native gas token. Tokens given a
+	/// threshold are tracked even if not listed when the service was created.
+	pub fn with_threshold(
+		mut self,
+		chain_id: u64,
+		token: Option<Address>,
+		min_balance: U256,
+	) -> Self {
+		if let Some(token) = &token {
+			let tokens = self.tokens.entry(chain_id).or_default();
+			if !tokens.contains(token) {
+				tokens.push(token.clone());
+			}
+		}
+		self.thresholds.insert((chain_id, token), min_balance);
+		self
+	}
+
+	/// Polls every tracked balance of `owner`.
+	///
+	/// Balances that cannot be read keep their last value. Does nothing if
+	/// a refresh is already running.
+	pub async fn refresh(&self, owner: &Address) {
+		if self.refreshing.swap(true, Ordering::SeqCst) {
+			return;
+		}
+
+		let keys: Vec<BalanceKey> = self
+			.chains
+			.iter()
+			.map(|chain_id| (*chain_id, None))
+			.chain(self.tokens.iter().flat_map(|(chain_id, tokens)| {
+				tokens
+					.iter()
+					.map(move |token| (*chain_id, Some(token.clone())))
+			}))
+			.collect();
+		for key in keys {
+			let result = match &key.1 {
+				None => self.reader.native_balance(key.0, owner).await,
+				Some(token) => self.reader.token_balance(key.0, token, owner).await,
+			};
+			match result {
+				Ok(amount) => self.update(key, amount),
+				Err(e) => {
+					tracing::warn!(chain_id = key.0, error = %e, "Failed to poll inventory balance");
+				}
+			}
+		}
+
+		self.refreshing.store(false, Ordering::SeqCst);
+	}
+
+	/// Caches a polled balance, warning when it crosses its threshold.
+	fn update(&self, key: BalanceKey, amount: U256) {
+		let mut balances = self.balances.lock().unwrap();
+		if let Some(min_balance) = self.thresholds.get(&key) {
+			let was_low = balances
+				.get(&key)
+				.map(|previous| previous.amount < *min_balance);
+			let low = amount < *min_balance;
+			let token = key.1.as_ref().map_or_else(
+				|| "native".to_string(),
+				|token| format!("0x{}", hex::encode(&token.0)),
+			);
+			match (was_low, low) {
+				(Some(false) | None, true) => tracing::warn!(
+					chain_id = key.0,
+					token = %token,
+					balance = %amount,
+					min_balance = %min_balance,
+					"Inventory balance below threshold"
+				),
+				(Some(true), false) => tracing::info!(
+					chain_id = key.0,
+					token = %token,
+					balance = %amount,
+					"Inventory balance recovered"
+				),
+				_ => {}
+			}
+		}
+
+		balances.insert(
+			key,
+			CachedBalance {
+				amount,
+				fetched_at: Instant::now(),
+				updated_at: std::time::SystemTime::now()
+					.duration_since(std::time::UNIX_EPOCH)
+					.unwrap()
+					.as_secs(),
+			},
+		);
+	}
+
+	/// Returns a balance polled less than `max_age` ago.
+	///
+	/// `token` is `None` for the chain's native gas token.
+	pub fn balance(
+		&self,
+		chain_id: u64,
+		token: Option<&Address>,
+		max_age: Duration,
+	) -> Option<U256> {
+		self.balances
+			.lock()
+			.unwrap()
+			.get(&(chain_id, token.cloned()))
+			.filter(|balance| balance.fetched_at.elapsed() < max_age)
+			.map(|balance| balance.amount)
+	}
+
+	/// Returns every polled balance, ordered by chain with the native
+	/// balance first.
+	pub fn balances(&self) -> Vec<InventoryBalance> {
+		let balances = self.balances.lock().unwrap();
+		let mut inventory: Vec<InventoryBalance> = balances
+			.iter()
+			.map(|(key, balance)| {
+				let min_balance = self.thresholds.get(key).copied();
+				InventoryBalance {
+					chain_id: key.0,
+					token: key
+						.1
+						.as_ref()
+						.map(|token| format!("0x{}", hex::encode(&token.0))),
+					balance: balance.amount,
+					min_balance,
+					low: min_balance.is_some_and(|min_balance| balance.amount < min_balance),
+					updated_at: balance.updated_at,
+				}
+			})
+			.collect();
+		inventory.sort_by(|a, b| (a.chain_id, &a.token).cmp(&(b.chain_id, &b.token)));
+		inventory
+	}
+}
@@ -0,0 +1,179 @@
+//! Liquidity module for the OIF solver system.
+//!
+//! This module provides abstractions for querying on-chain liquidity so the
+//! solver can judge whether the tokens it receives from an order can be
+//! recycled (re-swapped) without excessive price impact. Liquidity sources are
+//! configured per chain and queried through a common interface.
+//!
+//! It also tracks the solver's own token inventory, polling its balances on
+//! every chain so that strategies, the API and operators see what the solver
+//! holds.
+
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_types::{Address, ConfigSchema, TokenAmount};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// Re-export implementations
+pub mod implementations {
+	pub mod uniswap_v2;
+}
+
+pub mod inventory;
+
+/// Basis point denominator used for price impact calculations.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Errors that can occur during liquidity operations.
+#[derive(Debug, Error)]
+pub enum LiquidityError {
+	/// Error that occurs during network communication.
+	#[error("Network error: {0}")]
+	Network(String),
+	/// Error that occurs when no liquidity source is configured for a chain.
+	#[error("No liquidity source for chain {0}")]
+	NoSourceAvailable(u64),
+	/// Error that occurs when there is no route to swap a token.
+	#[error("No liquidity for token 0x{0}")]
+	NoLiquidity(String),
+	/// Error that occurs when swapping an amount would exceed the allowed price impact.
+	#[error(
+		"Price impact of {impact_bps} bps for token 0x{token} exceeds maximum of {max_bps} bps"
+	)]
+	PriceImpactTooHigh {
+		token: String,
+		impact_bps: u64,
+		max_bps: u64,
+	},
+}
+
+/// Trait defining the interface for liquidity sources.
+///
+/// A liquidity source quotes how much of a chain's quote asset (e.g. WETH or
+/// USDC) would be received when swapping a given amount of a token. Quotes for
+/// different sizes are compared to derive price impact.
+#[async_trait]
+pub trait LiquidityInterface: Send + Sync {
+	/// Returns the configuration schema for this liquidity implementation.
+	///
+	/// This allows each implementation to define its own configuration requirements
+	/// with specific validation rules. The schema is used to validate TOML configuration
+	/// before initializing the liquidity source.
+	fn config_schema(&self) -> Box<dyn ConfigSchema>;
+
+	/// Returns the token that all quotes are denominated in.
+	fn quote_token(&self) -> Address;
+
+	/// Quotes the amount of quote token received for swapping `amount` of `token`.
+	async fn get_amount_out(&self, token: &Address, amount: U256) -> Result<U256, LiquidityError>;
+}
+
+/// Service that checks price impact across per-chain liquidity sources.
+///
+/// The LiquidityService compares a quote for the full amount against a quote
+/// for a small reference amount. The relative shortfall of the full quote is
+/// the price impact the solver would incur when recycling the tokens.
+pub struct LiquidityService {
+	/// Map of chain IDs to their liquidity sources.
+	sources: HashMap<u64, Box<dyn LiquidityInterface>>,
+	/// Maximum acceptable price impact in basis points.
+	max_price_impact_bps: u64,
+	/// Tokens deep enough that they are never checked, keyed by chain ID.
+	liquid_tokens: HashMap<u64, HashSet<Address>>,
+}
+
+impl LiquidityService {
+	/// Creates a new LiquidityService with the specified sources and limits.
+	pub fn new(
+		sources: HashMap<u64, Box<dyn LiquidityInterface>>,
+		max_price_impact_bps: u64,
+		liquid_tokens: HashMap<u64, HashSet<Address>>,
+	) -> Self {
+		Self {
+			sources,
+			max_price_impact_bps,
+			liquid_tokens,
+		}
+	}
+
+	/// Returns whether a token is exempt from liquidity checks.
+	///
+	/// Configured liquid tokens and each source's own quote token are exempt.
+	fn is_exempt(&self, asset: &TokenAmount) -> bool {
+		if self
+			.liquid_tokens
+			.get(&asset.chain_id)
+			.is_some_and(|tokens| tokens.contains(&asset.token))
+		{
+			return true;
+		}
+
+		self.sources
+			.get(&asset.chain_id)
+			.is_some_and(|source| source.quote_token() == asset.token)
+	}
+
+	/// Computes the price impact, in basis points, of swapping the given asset.
+	///
+	/// Exempt tokens and zero amounts have no price impact.
+	pub async fn price_impact_bps(&self, asset: &TokenAmount) -> Result<u64, LiquidityError> {
+		if asset.amount.is_zero() || self.is_exempt(asset) {
+			return Ok(0);
+		}
+
+		let source = self
+			.sources
+			.get(&asset.chain_id)
+			.ok_or(LiquidityError::NoSourceAvailable(asset.chain_id))?;
+
+		// Quote a 1% slice of the amount as the marginal price reference
+		let reference_amount = (asset.amount / U256::from(100u64)).max(U256::from(1u64));
+		let (reference_out, full_out) = futures::try_join!(
+			source.get_amount_out(&asset.token, reference_amount),
+			source.get_amount_out(&asset.token, asset.amount),
+		)?;
+
+		if reference_out.is_zero() {
+			return Err(LiquidityError::NoLiquidity(hex::encode(&asset.token.0)));
+		}
+
+		// Output we would receive if the marginal price held for the full amount
+		let expected_out = reference_out * asset.amount / reference_amount;
+		if full_out >= expected_out {
+			return Ok(0);
+		}
+
+		let shortfall = expected_out - full_out;
+		let impact = shortfall * U256::from(BPS_DENOMINATOR) / expected_out;
+		Ok(impact.to::<u64>())
+	}
+
+	/// Returns the maximum acceptable price impact in basis points.
+	pub fn max_price_impact_bps(&self) -> u64 {
+		self.max_price_impact_bps
+	}
+
+	/// Ensures every asset can be recycled within the configured price impact.
+	///
+	/// Quotes all assets concurrently and returns the highest price impact,
+	/// or an error describing the first asset that exceeds the limit.
+	pub async fn check_recyclable(&self, assets: &[TokenAmount]) -> Result<u64, LiquidityError> {
+		let impacts =
+			futures::future::try_join_all(assets.iter().map(|asset| self.price_impact_bps(asset)))
+				.await?;
+
+		let mut highest = 0;
+		for (asset, impact_bps) in assets.iter().zip(impacts) {
+			if impact_bps > self.max_price_impact_bps {
+				return Err(LiquidityError::PriceImpactTooHigh {
+					token: hex::encode(&asset.token.0),
+					impact_bps,
+					max_bps: self.max_price_impact_bps,
+				});
+			}
+			highest = highest.max(impact_bps);
+		}
+		Ok(highest)
+	}
+}
@@ -0,0 +1,186 @@
+//! Token prices read from Chainlink feeds.
+//!
+//! Each priced token is mapped to a Chainlink aggregator answering in USD,
+//! deployed either on the token's chain or on another chain the solver
+//! delivers to. Answers are scaled by the aggregator's decimals. An answer
+//! that was not updated within the staleness limit, or that comes from an
+//! incomplete round, is rejected rather than used, so that strategies never
+//! price orders on a feed that stopped updating.
+
+use crate::{OracleError, PriceOracle};
+use alloy_primitives::{hex, Address as AlloyAddress, U256};
+use alloy_sol_types::{sol, SolCall};
+use async_trait::async_trait;
+use solver_delivery::DeliveryService;
+use solver_types::{Address, Transaction};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Solidity type definitions for the Chainlink aggregator functions read.
+sol! {
+	interface IAggregatorV3 {
+		function decimals() external view returns (uint8);
+		function latestRoundData() external view returns (
+			uint80 roundId,
+			int256 answer,
+			uint256 startedAt,
+			uint256 updatedAt,
+			uint80 answeredInRound
+		);
+	}
+}
+
+/// A Chainlink aggregator pricing a token.
+struct Feed {
+	/// Chain the aggregator is deployed on.
+	chain_id: u64,
+	/// Address of the aggregator.
+	aggregator: Address,
+}
+
+/// Price oracle reading USD prices from Chainlink aggregators.
+pub struct ChainlinkPriceOracle {
+	/// Delivery service the aggregators are called through.
+	delivery: Arc<DeliveryService>,
+	/// Feeds keyed by chain ID and token address.
+	feeds: HashMap<(u64, Address), Feed>,
+	/// Maximum age of an answer.
+	max_staleness: Duration,
+	/// Oracle consulted for tokens without a feed.
+	fallback: Option<Arc<dyn PriceOracle>>,
+}
+
+impl ChainlinkPriceOracle {
+	/// Creates an oracle without feeds, rejecting answers older than
+	/// `max_staleness`.
+	pub fn new(delivery: Arc<DeliveryService>, max_staleness: Duration) -> Self {
+		Self {
+			delivery,
+			feeds: HashMap::new(),
+			max_staleness,
+			fallback: None,
+		}
+	}
+
+	/// Prices a token with the aggregator at `aggregator` on `feed_chain_id`.
+	pub fn with_feed(
+		mut self,
+		chain_id: u64,
+		token: Address,
+		feed_chain_id: u64,
+		aggregator: Address,
+	) -> Self {
+		self.feeds.insert(
+			(chain_id, token),
+			Feed {
+				chain_id: feed_chain_id,
+				aggregator,
+			},
+		);
+		self
+	}
+
+	/// Prices tokens without a feed through another oracle.
+	pub fn with_fallback(mut self, fallback: Arc<dyn PriceOracle>) -> Self {
+		self.fallback = Some(fallback);
+		self
+	}
+
+	/// Calls a view function of an aggregator.
+	async fn call(
+		&self,
+		feed: &Feed,
+		data: Vec<u8>,
+		immutable: bool,
+	) -> Result<Vec<u8>, OracleError> {
+		let tx = Transaction {
+			to: Some(feed.aggregator.clone()),
+			data,
+			value: U256::ZERO,
+			chain_id: feed.chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+		};
+		let result = if immutable {
+			self.delivery.call_immutable(&tx).await
+		} else {
+			self.delivery.call(&tx).await
+		};
+		result.map_err(|e| {
+			OracleError::Network(format!(
+				"Failed to call aggregator 0x{}: {}",
+				hex::encode(&feed.aggregator.0),
+				e
+			))
+		})
+	}
+
+	/// Reads the latest answer of a feed, scaled to whole units.
+	async fn latest_answer(&self, feed: &Feed) -> Result<f64, OracleError> {
+		let decode_error = |e: alloy_sol_types::Error| {
+			OracleError::InvalidPrice(format!("Failed to decode aggregator response: {}", e))
+		};
+
+		// Decimals are fixed when the aggregator is deployed
+		let decimals = self
+			.call(feed, IAggregatorV3::decimalsCall {}.abi_encode(), true)
+			.await?;
+		let decimals = IAggregatorV3::decimalsCall::abi_decode_returns(&decimals, true)
+			.map_err(decode_error)?
+			._0;
+
+		let round = self
+			.call(
+				feed,
+				IAggregatorV3::latestRoundDataCall {}.abi_encode(),
+				false,
+			)
+			.await?;
+		let round = IAggregatorV3::latestRoundDataCall::abi_decode_returns(&round, true)
+			.map_err(decode_error)?;
+
+		let aggregator = AlloyAddress::from_slice(&feed.aggregator.0);
+		if round.updatedAt.is_zero() || round.answeredInRound < round.roundId {
+			return Err(OracleError::InvalidPrice(format!(
+				"Aggregator {} has not completed its latest round",
+				aggregator
+			)));
+		}
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap()
+			.as_secs();
+		let age = now.saturating_sub(round.updatedAt.saturating_to::<u64>());
+		if age > self.max_staleness.as_secs() {
+			return Err(OracleError::InvalidPrice(format!(
+				"Aggregator {} answer is stale, last updated {}s ago",
+				aggregator, age
+			)));
+		}
+		if !round.answer.is_positive() {
+			return Err(OracleError::InvalidPrice(format!(
+				"Aggregator {} answered a non-positive price",
+				aggregator
+			)));
+		}
+
+		Ok(f64::from(round.answer.into_raw()) / 10f64.powi(decimals as i32))
+	}
+}
+
+#[async_trait]
+impl PriceOracle for ChainlinkPriceOracle {
+	async fn usd_price(&self, chain_id: u64, token: &Address) -> Result<Option<f64>, OracleError> {
+		match self.feeds.get(&(chain_id, token.clone())) {
+			Some(feed) => Ok(Some(self.latest_answer(feed).await?)),
+			None => match &self.fallback {
+				Some(fallback) => fallback.usd_price(chain_id, token).await,
+				None => Ok(None),
+			},
+		}
+	}
+}
@@ -0,0 +1,42 @@
+//! Price oracles for the OIF solver system.
+//!
+//! Execution strategies and the quote API value orders in USD. This module
+//! defines the interface they read token prices through, along with the
+//! oracles the solver ships: fixed prices from the configuration and
+//! Chainlink aggregator feeds.
+
+use async_trait::async_trait;
+use solver_types::Address;
+use thiserror::Error;
+
+/// Re-export implementations
+pub mod implementations {
+	pub mod chainlink;
+	pub mod fixed;
+}
+
+/// Errors that can occur while reading a price.
+#[derive(Debug, Error)]
+pub enum OracleError {
+	/// Error that occurs when the price source cannot be reached.
+	#[error("Network error: {0}")]
+	Network(String),
+	/// Error that occurs when the price source answers with an unusable price.
+	#[error("Invalid price: {0}")]
+	InvalidPrice(String),
+}
+
+/// Source of token prices for execution strategies and quotes.
+#[async_trait]
+pub trait PriceOracle: Send + Sync {
+	/// Returns the price of one whole token in USD, or `None` if the oracle
+	/// does not price the token.
+	///
+	/// Gas tokens are priced under [`gas_token`].
+	async fn usd_price(&self, chain_id: u64, token: &Address) -> Result<Option<f64>, OracleError>;
+}
+
+/// Returns the address chains' gas tokens are priced under, the zero address.
+pub fn gas_token() -> Address {
+	Address(vec![0u8; 20])
+}
@@ -0,0 +1,167 @@
+//! Benchmarks of the per-order work done between discovery and submission:
+//! validating an intent into an order and encoding its fill calldata.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use solver_order::features::StandardFeatures;
+use solver_order::implementations::standards::_7683::Eip7683OrderImpl;
+use solver_order::implementations::strategies::simple::SimpleStrategy;
+use solver_order::{OrderInterface, OrderService};
+use solver_types::{ExecutionParams, Intent, IntentMetadata, Order};
+use std::collections::{HashMap, HashSet};
+
+const OUTPUT_SETTLER: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+const INPUT_SETTLER: &str = "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512";
+const SOLVER: &str = "0x70997970c51812dc3a010c7d01b50e0d17dc79c8";
+
+/// Builds an EIP-7683 intent with the given number of outputs, all on the
+/// destination chain.
+fn intent(id: u8, outputs: usize) -> Intent {
+	let now = std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.unwrap()
+		.as_secs();
+	let outputs: Vec<_> = (0..outputs)
+		.map(|_| {
+			serde_json::json!({
+				"token": "0x9fe46736679d2d9a65f0992f2272de9f3c7fa6e0",
+				"amount": "0xde0b6b3a7640000",
+				"recipient": "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+				"chain_id": 31338,
+			})
+		})
+		.collect();
+	Intent {
+		id: format!("{:064x}", id),
+		source: "eip7683".to_string(),
+		standard: "eip7683".to_string(),
+		metadata: IntentMetadata {
+			requires_auction: false,
+			exclusive_until: None,
+			discovered_at: now,
+			settler: Some(INPUT_SETTLER.to_string()),
+			block_number: Some(1),
+			transaction_hash: None,
+			custom: HashMap::new(),
+		},
+		data: serde_json::json!({
+			"user": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+			"nonce": 0,
+			"origin_chain_id": 31337,
+			"destination_chain_id": 31338,
+			"expires": now + 3600,
+			"fill_deadline": now + 3600,
+			"local_oracle": "0x0000000000000000000000000000000000000000",
+			"inputs": [[
+				"0x9fe46736679d2d9a65f0992f2272de9f3c7fa6e0",
+				"0xde0b6b3a7640000",
+			]],
+			"order_id": vec![id; 32],
+			"settle_gas_limit": 200000,
+			"fill_gas_limit": 200000,
+			"outputs": outputs,
+		}),
+	}
+}
+
+fn order_impl() -> Eip7683OrderImpl {
+	Eip7683OrderImpl::new(
+		OUTPUT_SETTLER.to_string(),
+		INPUT_SETTLER.to_string(),
+		SOLVER.to_string(),
+	)
+	.with_bundle_fills(true)
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+	tokio::runtime::Builder::new_current_thread()
+		.build()
+		.unwrap()
+}
+
+fn params() -> ExecutionParams {
+	ExecutionParams {
+		gas_price: alloy_primitives::U256::from(1_000_000_000u64),
+		priority_fee: None,
+		urgency: None,
+		fill_amount: None,
+	}
+}
+
+fn validation(c: &mut Criterion) {
+	let rt = runtime();
+	let mut implementations: HashMap<String, Box<dyn OrderInterface>> = HashMap::new();
+	implementations.insert("eip7683".to_string(), Box::new(order_impl()));
+	let features = HashMap::from([(
+		"eip7683".to_string(),
+		StandardFeatures {
+			multi_output: true,
+			..Default::default()
+		},
+	)]);
+	let service = OrderService::new(implementations, Box::new(SimpleStrategy::new(100)))
+		.with_features(features, HashSet::new());
+	let implementation = order_impl();
+
+	let mut group = c.benchmark_group("order_validation");
+	for outputs in [1, 8] {
+		let intent = intent(1, outputs);
+		group.bench_with_input(
+			BenchmarkId::new("implementation", outputs),
+			&intent,
+			|b, intent| b.iter(|| rt.block_on(implementation.validate_intent(intent)).unwrap()),
+		);
+		// Full validation adds the routing, capability and intake checks
+		group.bench_with_input(
+			BenchmarkId::new("service", outputs),
+			&intent,
+			|b, intent| b.iter(|| rt.block_on(service.preview_intent(intent)).unwrap()),
+		);
+	}
+	group.finish();
+}
+
+fn fill_encoding(c: &mut Criterion) {
+	let rt = runtime();
+	let implementation = order_impl();
+	let params = params();
+	let orders: Vec<Order> = (1..=16)
+		.map(|id| {
+			rt.block_on(implementation.validate_intent(&intent(id, 1)))
+				.unwrap()
+		})
+		.collect();
+
+	let fill_amount = implementation.order_assets(&orders[0]).unwrap().outputs[0].amount;
+
+	let mut group = c.benchmark_group("fill_encoding");
+	group.bench_function("single", |b| {
+		b.iter(|| {
+			rt.block_on(implementation.generate_fill_transaction(&orders[0], &params, fill_amount))
+				.unwrap()
+		})
+	});
+	for size in [4, 16] {
+		group.bench_with_input(BenchmarkId::new("bundle", size), &size, |b, size| {
+			b.iter_batched(
+				|| {
+					orders[..*size]
+						.iter()
+						.map(|order| (order, &params))
+						.collect::<Vec<_>>()
+				},
+				|fills| {
+					rt.block_on(implementation.generate_bundle_fill_transaction(&fills))
+						.unwrap()
+				},
+				BatchSize::SmallInput,
+			)
+		});
+	}
+	group.bench_function("order_assets", |b| {
+		b.iter(|| implementation.order_assets(&orders[0]).unwrap())
+	});
+	group.finish();
+}
+
+criterion_group!(benches, validation, fill_encoding);
+criterion_main!(benches);
@@ -0,0 +1,99 @@
+//! Order classification for the solver system.
+//!
+//! Orders are grouped into classes based on the assets they move and how they
+//! were opened. Classes let the solver apply class-specific behaviour, such as
+//! a dedicated execution strategy for multi-output or gasless orders.
+
+use serde::{Deserialize, Serialize};
+use solver_types::OrderAssets;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// A class an order can belong to.
+///
+/// An order belongs to several classes at once, e.g. a gasless cross-chain
+/// order with multiple outputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderClass {
+	/// All inputs and outputs are on a single chain.
+	SameChain,
+	/// Inputs and outputs span more than one chain.
+	CrossChain,
+	/// The solver must deliver more than one output.
+	MultiOutput,
+	/// The order was opened off-chain with a user signature.
+	Gasless,
+}
+
+impl OrderClass {
+	/// Classes ordered from most to least specific.
+	///
+	/// When class-specific behaviour is configured for several classes of an
+	/// order, the first matching class in this list wins.
+	pub const PRECEDENCE: [OrderClass; 4] = [
+		OrderClass::Gasless,
+		OrderClass::MultiOutput,
+		OrderClass::SameChain,
+		OrderClass::CrossChain,
+	];
+
+	/// Returns the configuration name of this class.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			OrderClass::SameChain => "same_chain",
+			OrderClass::CrossChain => "cross_chain",
+			OrderClass::MultiOutput => "multi_output",
+			OrderClass::Gasless => "gasless",
+		}
+	}
+}
+
+impl FromStr for OrderClass {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"same_chain" => Ok(OrderClass::SameChain),
+			"cross_chain" => Ok(OrderClass::CrossChain),
+			"multi_output" => Ok(OrderClass::MultiOutput),
+			"gasless" => Ok(OrderClass::Gasless),
+			_ => Err(format!("Unknown order class: {}", s)),
+		}
+	}
+}
+
+/// Classifies orders from their standard-agnostic asset view.
+pub struct OrderClassifier;
+
+impl OrderClassifier {
+	/// Returns every class the order belongs to.
+	///
+	/// `gasless` is reported by the order's standard implementation since it
+	/// cannot be derived from the assets alone.
+	pub fn classify(assets: &OrderAssets, gasless: bool) -> HashSet<OrderClass> {
+		let mut classes = HashSet::new();
+
+		let chains: HashSet<u64> = assets
+			.inputs
+			.iter()
+			.chain(assets.outputs.iter())
+			.map(|asset| asset.chain_id)
+			.collect();
+		if chains.len() > 1 {
+			classes.insert(OrderClass::CrossChain);
+		} else {
+			classes.insert(OrderClass::SameChain);
+		}
+
+		if assets.outputs.len() > 1 {
+			classes.insert(OrderClass::MultiOutput);
+		}
+
+		if gasless {
+			classes.insert(OrderClass::Gasless);
+		}
+
+		classes
+	}
+}
@@ -0,0 +1,62 @@
+//! Per-standard feature flags for risky order capabilities.
+//!
+//! Some orders need more from the solver than a plain token transfer: outputs
+//! that execute a call at the recipient, tokens that charge a fee on transfer,
+//! or several outputs that must all be delivered. Each of these is off unless
+//! enabled for the order's standard, and intents needing a disabled capability
+//! are rejected during validation.
+
+use solver_types::{Address, OrderAssets};
+use std::collections::HashSet;
+
+/// Risky capabilities enabled for an order standard.
+///
+/// All capabilities are disabled by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StandardFeatures {
+	/// Accept outputs that execute a call at the recipient.
+	pub call_bearing_outputs: bool,
+	/// Accept orders moving tokens known to charge a fee on transfer.
+	pub fee_on_transfer_tokens: bool,
+	/// Accept orders with more than one output.
+	pub multi_output: bool,
+}
+
+impl StandardFeatures {
+	/// Checks an order against the enabled capabilities.
+	///
+	/// Returns a description of the first disabled capability the order needs.
+	pub fn check(
+		&self,
+		assets: &OrderAssets,
+		has_output_calls: bool,
+		fee_on_transfer_tokens: &HashSet<(u64, Address)>,
+	) -> Result<(), String> {
+		if has_output_calls && !self.call_bearing_outputs {
+			return Err("call-bearing outputs are not enabled".to_string());
+		}
+
+		if assets.outputs.len() > 1 && !self.multi_output {
+			return Err("multi-output orders are not enabled".to_string());
+		}
+
+		if !self.fee_on_transfer_tokens {
+			let fee_on_transfer = assets
+				.inputs
+				.iter()
+				.chain(assets.outputs.iter())
+				.find(|asset| {
+					fee_on_transfer_tokens.contains(&(asset.chain_id, asset.token.clone()))
+				});
+			if let Some(asset) = fee_on_transfer {
+				return Err(format!(
+					"fee-on-transfer token 0x{} on chain {} is not enabled",
+					hex::encode(&asset.token.0),
+					asset.chain_id
+				));
+			}
+		}
+
+		Ok(())
+	}
+}
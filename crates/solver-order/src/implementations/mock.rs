@@ -0,0 +1,210 @@
+//! Mock order standard for tests.
+//!
+//! Orders of the mock standard carry their assets in the clear, as
+//! [`MockOrderData`] in the intent's data, so that tests can build intents
+//! without encoding them. Fills and claims are calls to a settler address
+//! carrying the order ID, which the mock delivery provider mines as is.
+
+use crate::{OrderError, OrderInterface};
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solver_types::testing::MockBehavior;
+use solver_types::{
+	Address, ConfigSchema, ExecutionParams, Field, FieldType, FillProof, Intent, Order,
+	OrderAssets, Schema, TokenAmount, Transaction,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Settler of mock orders not configured with one.
+const DEFAULT_SETTLER: [u8; 20] = [0x5e; 20];
+
+/// Gas limit of mock fill and claim transactions.
+const MOCK_GAS_LIMIT: u64 = 100_000;
+
+/// Data of a mock order, as found in its intent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockOrderData {
+	/// Tokens the solver receives when claiming.
+	pub inputs: Vec<TokenAmount>,
+	/// Tokens the solver delivers when filling.
+	pub outputs: Vec<TokenAmount>,
+	/// Timestamp after which the order can no longer be filled.
+	pub fill_deadline: u64,
+}
+
+/// Order standard accepting intents carrying [`MockOrderData`].
+///
+/// Scripted failures surface as [`OrderError::ValidationFailed`].
+pub struct MockOrder {
+	/// Contract fills and claims are sent to, on every chain.
+	settler: Address,
+	/// Latencies, scripted failures and call counts.
+	behavior: Arc<MockBehavior>,
+}
+
+impl MockOrder {
+	/// Creates a mock standard sending fills and claims to `settler`.
+	pub fn new(settler: Address) -> Self {
+		Self {
+			settler,
+			behavior: Arc::new(MockBehavior::new()),
+		}
+	}
+
+	/// Replaces the standard's behaviour.
+	pub fn with_behavior(mut self, behavior: MockBehavior) -> Self {
+		self.behavior = Arc::new(behavior);
+		self
+	}
+
+	/// Returns the standard's behaviour, to script failures and count calls
+	/// once the standard is handed to the solver.
+	pub fn behavior(&self) -> Arc<MockBehavior> {
+		self.behavior.clone()
+	}
+
+	/// Plays a call to an operation.
+	async fn call(&self, operation: &str) -> Result<(), OrderError> {
+		self.behavior
+			.call(operation)
+			.await
+			.map_err(OrderError::ValidationFailed)
+	}
+
+	/// Builds a call to the settler on `chain_id` carrying `data`.
+	fn settler_call(&self, chain_id: u64, data: Vec<u8>) -> Transaction {
+		Transaction {
+			to: Some(self.settler.clone()),
+			data,
+			value: U256::ZERO,
+			chain_id,
+			nonce: None,
+			gas_limit: Some(MOCK_GAS_LIMIT),
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+		}
+	}
+}
+
+impl Default for MockOrder {
+	fn default() -> Self {
+		Self::new(Address(DEFAULT_SETTLER.to_vec()))
+	}
+}
+
+/// Parses the data of a mock order.
+fn order_data(data: &serde_json::Value) -> Result<MockOrderData, OrderError> {
+	serde_json::from_value(data.clone())
+		.map_err(|e| OrderError::ValidationFailed(format!("Failed to parse order data: {}", e)))
+}
+
+/// Configuration schema for MockOrder.
+pub struct MockOrderSchema;
+
+impl ConfigSchema for MockOrderSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let mut optional = vec![Field::new("settler_address", FieldType::String)];
+		optional.extend(MockBehavior::config_fields());
+		Schema::new(vec![], optional).validate(config)
+	}
+}
+
+#[async_trait]
+impl OrderInterface for MockOrder {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(MockOrderSchema)
+	}
+
+	async fn validate_intent(&self, intent: &Intent) -> Result<Order, OrderError> {
+		self.call("validate_intent").await?;
+		let data = order_data(&intent.data)?;
+		if data.outputs.is_empty() {
+			return Err(OrderError::ValidationFailed(
+				"Order has no outputs".to_string(),
+			));
+		}
+
+		Ok(Order {
+			id: intent.id.clone(),
+			standard: intent.standard.clone(),
+			created_at: intent.metadata.discovered_at,
+			data: intent.data.clone(),
+			implementation: None,
+			metadata: intent.metadata.custom.clone(),
+			exclusive_until: intent.metadata.exclusive_until,
+			routes: Vec::new(),
+		})
+	}
+
+	fn order_assets(&self, order: &Order) -> Result<OrderAssets, OrderError> {
+		let data = order_data(&order.data)?;
+		Ok(OrderAssets {
+			inputs: data.inputs,
+			outputs: data.outputs,
+		})
+	}
+
+	fn fill_deadline(&self, order: &Order) -> Result<u64, OrderError> {
+		Ok(order_data(&order.data)?.fill_deadline)
+	}
+
+	/// Calls the settler on the chain of the first output with the order ID.
+	async fn generate_fill_transaction(
+		&self,
+		order: &Order,
+		_params: &ExecutionParams,
+		_fill_amount: U256,
+	) -> Result<Transaction, OrderError> {
+		self.call("generate_fill_transaction").await?;
+		let data = order_data(&order.data)?;
+		let output = data
+			.outputs
+			.first()
+			.ok_or_else(|| OrderError::ValidationFailed("Order has no outputs".to_string()))?;
+		Ok(self.settler_call(output.chain_id, order.id.as_bytes().to_vec()))
+	}
+
+	/// Calls the settler on the chain of the first input with the order ID
+	/// and the hash of the fill.
+	async fn generate_claim_transaction(
+		&self,
+		order: &Order,
+		fill_proof: &FillProof,
+		_claim_recipients: &HashMap<u64, Address>,
+	) -> Result<Transaction, OrderError> {
+		self.call("generate_claim_transaction").await?;
+		let data = order_data(&order.data)?;
+		let chain_id = data
+			.inputs
+			.first()
+			.or(data.outputs.first())
+			.map(|asset| asset.chain_id)
+			.unwrap_or_default();
+
+		let mut call = order.id.as_bytes().to_vec();
+		call.extend_from_slice(&fill_proof.tx_hash.0);
+		Ok(self.settler_call(chain_id, call))
+	}
+}
+
+/// Factory function to create a mock order standard from configuration.
+///
+/// Optional configuration parameters:
+/// - `settler_address`: Hex address fills and claims are sent to
+/// - `latency_ms`, `operation_latency_ms`, `fail_first`: see [`MockBehavior::from_config`]
+pub fn create_order_impl(config: &toml::Value) -> Box<dyn OrderInterface> {
+	let settler = config
+		.get("settler_address")
+		.and_then(|v| v.as_str())
+		.map(|address| {
+			let address = hex::decode(address.trim_start_matches("0x"))
+				.expect("settler_address of mock order must be hex");
+			Address(address)
+		})
+		.unwrap_or_else(|| Address(DEFAULT_SETTLER.to_vec()));
+
+	Box::new(MockOrder::new(settler).with_behavior(MockBehavior::from_config(config)))
+}
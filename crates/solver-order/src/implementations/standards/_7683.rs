@@ -4,15 +4,19 @@
 //! for EIP-7683 cross-chain orders, including transaction generation for
 //! filling and claiming orders.
 
+use crate::signatures::SignatureWorkers;
 use crate::{OrderError, OrderInterface};
-use alloy_primitives::{Address as AlloyAddress, FixedBytes, U256};
-use alloy_sol_types::{sol, SolCall, SolValue};
+use alloy_primitives::{Address as AlloyAddress, FixedBytes, B256, U256};
+use alloy_sol_types::{sol, Eip712Domain, SolCall, SolStruct, SolValue};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use solver_types::eip7683::OutputFilled;
 use solver_types::{
-	Address, ConfigSchema, ExecutionParams, Field, FieldType, FillProof, Intent, Order, Schema,
-	Transaction,
+	is_event, parse_signature, verify_signer, Address, ConfigSchema, ExecutionParams, Field,
+	FieldType, FillProof, FillRoute, Intent, Order, OrderAssets, Schema, TokenAmount, Transaction,
+	TransactionReceipt, OFFCHAIN_SOURCE,
 };
+use std::collections::HashMap;
 
 // Solidity type definitions for EIP-7683 contract interactions.
 sol! {
@@ -33,6 +37,16 @@ sol! {
 		function fill(bytes32 orderId, bytes originData, bytes fillerData) external;
 	}
 
+	/// Fill records kept by the output settler for every output it fills.
+	interface IOutputSettler {
+		function getFillRecord(bytes32 orderId, MandateOutput output) external view returns (bytes32 payloadHash);
+	}
+
+	/// Multicall interface of output settlers that batch calls from one sender.
+	interface IMulticall {
+		function multicall(bytes[] data) external returns (bytes[] results);
+	}
+
 	/// Order structure for finaliseSelf.
 	struct OrderStruct {
 		address user;
@@ -45,9 +59,49 @@ sol! {
 		MandateOutput[] outputs;
 	}
 
-	/// IInputSettler interface for finalizing orders.
+	/// Order signed by the user of a gasless order, hashed per EIP-712.
+	struct StandardOrder {
+		address user;
+		uint256 nonce;
+		uint256 originChainId;
+		uint32 expires;
+		uint32 fillDeadline;
+		address inputOracle;
+		uint256[2][] inputs;
+		MandateOutput[] outputs;
+	}
+
+	/// Token and amount a Permit2 signature transfer may pull.
+	struct TokenPermissions {
+		address token;
+		uint256 amount;
+	}
+
+	/// Order terms a Permit2 transfer commits the user to, as its witness.
+	struct Permit2Witness {
+		uint32 expires;
+		address inputOracle;
+		MandateOutput[] outputs;
+	}
+
+	/// Permit2 batch transfer signed by the user of a Permit2 order, hashed per EIP-712.
+	struct PermitBatchWitnessTransferFrom {
+		TokenPermissions[] permitted;
+		address spender;
+		uint256 nonce;
+		uint256 deadline;
+		Permit2Witness witness;
+	}
+
+	/// IInputSettler interface for opening and finalizing orders.
+	///
+	/// `openFor` escrows the inputs of an order signed by its sponsor,
+	/// pulling them with a Permit2 or EIP-3009 signature prefixed with its
+	/// scheme.
 	interface IInputSettler {
+		function openFor(StandardOrder order, address sponsor, bytes signature) external;
 		function finaliseSelf(OrderStruct order, uint32[] timestamps, bytes32 solver) external;
+		function finaliseTo(OrderStruct order, uint32[] timestamps, bytes32 solver, bytes32 destination, bytes call) external;
 	}
 }
 
@@ -69,6 +123,55 @@ pub struct Eip7683OrderData {
 	pub settle_gas_limit: u64,
 	pub fill_gas_limit: u64,
 	pub outputs: Vec<Output>,
+	/// Chains the order may be filled on, when the user lets the solver pick
+	/// among them. `destination_chain_id` holds the chain picked so far, and
+	/// only the outputs on it are delivered.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub destination_chain_ids: Vec<u64>,
+	/// User signature for orders opened off-chain; absent for on-chain opens.
+	#[serde(default)]
+	pub signature: Option<String>,
+	/// Permit used to pull inputs at claim instead of escrowing them at open.
+	#[serde(default)]
+	pub permit: Option<PermitData>,
+}
+
+/// Scheme of a permit authorising the pull of a user's input tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermitScheme {
+	/// Uniswap Permit2 signature transfer.
+	Permit2,
+	/// EIP-3009 `receiveWithAuthorization`.
+	Eip3009,
+}
+
+/// Permit parameters needed to pull a gasless order's inputs at claim.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermitData {
+	/// Scheme the permit was signed for.
+	pub scheme: PermitScheme,
+	/// Permit nonce (Permit2 unordered nonce or EIP-3009 authorization nonce).
+	pub nonce: U256,
+	/// Unix timestamp after which the permit can no longer be used.
+	pub deadline: u64,
+	/// Hex-encoded signature of the user over the permit.
+	pub signature: String,
+}
+
+impl PermitData {
+	/// Returns the signature `openFor` takes: the permit signature prefixed
+	/// with the byte identifying its scheme.
+	fn settler_signature(&self) -> Result<Vec<u8>, OrderError> {
+		let signature = hex::decode(self.signature.trim_start_matches("0x")).map_err(|e| {
+			OrderError::ValidationFailed(format!("Invalid permit signature: {}", e))
+		})?;
+		let scheme = match self.scheme {
+			PermitScheme::Permit2 => 0x00,
+			PermitScheme::Eip3009 => 0x01,
+		};
+		Ok([vec![scheme], signature].concat())
+	}
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,6 +180,85 @@ pub struct Output {
 	pub amount: U256,
 	pub recipient: String,
 	pub chain_id: u64,
+	/// Hex-encoded call executed at the recipient after delivery, if any.
+	#[serde(default)]
+	pub call: Option<String>,
+}
+
+impl Output {
+	/// Returns the decoded call data, empty for plain transfers.
+	fn call_data(&self) -> Result<Vec<u8>, OrderError> {
+		match &self.call {
+			Some(call) => hex::decode(call.trim_start_matches("0x"))
+				.map_err(|e| OrderError::ValidationFailed(format!("Invalid output call: {}", e))),
+			None => Ok(Vec::new()),
+		}
+	}
+}
+
+/// Payload the user of a gasless order signs.
+enum SignedPayload {
+	/// The order itself, in the input settler's domain.
+	Order(StandardOrder, Eip712Domain),
+	/// A Permit2 transfer of the order's inputs witnessing the order, in
+	/// Permit2's domain.
+	Permit2(PermitBatchWitnessTransferFrom, Eip712Domain),
+}
+
+impl SignedPayload {
+	/// Returns the EIP-712 hash the user signed.
+	fn signing_hash(&self) -> B256 {
+		match self {
+			Self::Order(order, domain) => order.eip712_signing_hash(domain),
+			Self::Permit2(transfer, domain) => transfer.eip712_signing_hash(domain),
+		}
+	}
+}
+
+/// Checks that a gasless order was signed by its user.
+///
+/// Hashes the signed payload per EIP-712 and recovers the signer, so it is
+/// CPU-bound and meant to run on a signature worker.
+fn verify_order_signature(
+	payload: &SignedPayload,
+	user: AlloyAddress,
+	signature: &str,
+) -> Result<(), OrderError> {
+	verify_signer(&payload.signing_hash(), signature, user)
+		.map_err(|e| OrderError::ValidationFailed(format!("Invalid user signature: {}", e)))
+}
+
+/// Returns the outputs of an order as token amounts on their chains.
+fn output_amounts(order_data: &Eip7683OrderData) -> Result<Vec<TokenAmount>, OrderError> {
+	order_data
+		.outputs
+		.iter()
+		.map(|output| {
+			let token = hex::decode(output.token.trim_start_matches("0x")).map_err(|e| {
+				OrderError::ValidationFailed(format!("Invalid token address: {}", e))
+			})?;
+			Ok(TokenAmount {
+				chain_id: output.chain_id,
+				token: Address(token),
+				amount: output.amount,
+			})
+		})
+		.collect()
+}
+
+/// Canonical address of the Permit2 contract.
+const PERMIT2_ADDRESS: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+/// EIP-712 domain that gasless orders are signed in.
+///
+/// The chain and verifying contract are the order's origin chain and the
+/// input settler.
+#[derive(Debug, Clone)]
+pub struct SignatureDomain {
+	/// Name of the signing domain.
+	pub name: String,
+	/// Version of the signing domain.
+	pub version: String,
 }
 
 /// EIP-7683 order implementation.
@@ -90,6 +272,19 @@ pub struct Eip7683OrderImpl {
 	input_settler_address: Address,
 	/// Address of the solver for claiming rewards.
 	solver_address: Address,
+	/// Whether the output settler exposes `multicall`, allowing several
+	/// fills in one transaction.
+	bundle_fills: bool,
+	/// Whether the input settler exposes `finaliseTo`, allowing rewards to
+	/// be claimed to an address other than the solver.
+	finalise_to: bool,
+	/// Domain that gasless orders are signed in. Signers are only verified
+	/// when a domain is configured.
+	signature_domain: Option<SignatureDomain>,
+	/// Workers verifying the signatures of gasless orders.
+	signatures: SignatureWorkers,
+	/// Permit2 contract that Permit2 orders are signed for.
+	permit2_address: Address,
 }
 
 impl Eip7683OrderImpl {
@@ -107,7 +302,277 @@ impl Eip7683OrderImpl {
 			solver_address: Address(
 				hex::decode(solver.trim_start_matches("0x")).expect("Invalid solver address"),
 			),
+			bundle_fills: false,
+			finalise_to: false,
+			signature_domain: None,
+			signatures: SignatureWorkers::default(),
+			permit2_address: Address(
+				hex::decode(PERMIT2_ADDRESS.trim_start_matches("0x"))
+					.expect("Invalid Permit2 address"),
+			),
+		}
+	}
+
+	/// Sets the Permit2 contract that Permit2 orders are signed for, on
+	/// chains where it is not deployed at its canonical address.
+	pub fn with_permit2_address(mut self, permit2: String) -> Self {
+		self.permit2_address = Address(
+			hex::decode(permit2.trim_start_matches("0x")).expect("Invalid Permit2 address"),
+		);
+		self
+	}
+
+	/// Enables verification of gasless order signers in the given domain,
+	/// running on the given workers.
+	pub fn with_signature_verification(
+		mut self,
+		domain: SignatureDomain,
+		signatures: SignatureWorkers,
+	) -> Self {
+		self.signature_domain = Some(domain);
+		self.signatures = signatures;
+		self
+	}
+
+	/// Enables filling several orders in one output settler `multicall`.
+	pub fn with_bundle_fills(mut self, bundle_fills: bool) -> Self {
+		self.bundle_fills = bundle_fills;
+		self
+	}
+
+	/// Enables claiming rewards to a configured recipient through the input
+	/// settler's `finaliseTo`.
+	pub fn with_finalise_to(mut self, finalise_to: bool) -> Self {
+		self.finalise_to = finalise_to;
+		self
+	}
+
+	/// Builds the output an order delivers on its destination chain, as the
+	/// output settler fills it.
+	///
+	/// Returns the parsed order data along with the output.
+	fn destination_output(
+		&self,
+		order: &Order,
+	) -> Result<(Eip7683OrderData, MandateOutput), OrderError> {
+		let order_data: Eip7683OrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+
+		// Check if this is a same-chain order
+		if order_data.origin_chain_id == order_data.destination_chain_id {
+			return Err(OrderError::ValidationFailed(
+				"Same-chain orders are not supported".to_string(),
+			));
+		}
+
+		// Get the output for the destination chain
+		let output = order_data
+			.outputs
+			.iter()
+			.find(|o| o.chain_id == order_data.destination_chain_id)
+			.ok_or_else(|| {
+				OrderError::ValidationFailed("No output found for destination chain".to_string())
+			})?;
+
+		// Create the MandateOutput struct for the fill operation
+		let mandate_output = MandateOutput {
+			oracle: FixedBytes::<32>::from([0u8; 32]), // No oracle for direct fills
+			settler: {
+				let mut bytes32 = [0u8; 32];
+				bytes32[12..32].copy_from_slice(&self.output_settler_address.0);
+				FixedBytes::<32>::from(bytes32)
+			},
+			chainId: U256::from(output.chain_id),
+			token: {
+				let token_hex = output.token.trim_start_matches("0x");
+				let token_bytes = hex::decode(token_hex).map_err(|e| {
+					OrderError::ValidationFailed(format!("Invalid token address: {}", e))
+				})?;
+				let mut bytes32 = [0u8; 32];
+				bytes32[12..32].copy_from_slice(&token_bytes);
+				FixedBytes::<32>::from(bytes32)
+			},
+			amount: output.amount,
+			recipient: {
+				let recipient_hex = output.recipient.trim_start_matches("0x");
+				let recipient_bytes = hex::decode(recipient_hex).map_err(|e| {
+					OrderError::ValidationFailed(format!("Invalid recipient address: {}", e))
+				})?;
+				let mut bytes32 = [0u8; 32];
+				bytes32[12..32].copy_from_slice(&recipient_bytes);
+				FixedBytes::<32>::from(bytes32)
+			},
+			call: output.call_data()?.into(), // Empty for direct transfers
+			context: vec![].into(),           // Empty context
+		};
+
+		Ok((order_data, mandate_output))
+	}
+
+	/// Encodes the settler call filling an order on its destination chain.
+	///
+	/// Returns the parsed order data along with the call data and the value
+	/// sent with it. An output in the chain's gas token, at the zero address,
+	/// is paid by sending its amount along with the call.
+	fn fill_call(&self, order: &Order) -> Result<(Eip7683OrderData, Vec<u8>, U256), OrderError> {
+		let (order_data, mandate_output) = self.destination_output(order)?;
+		let value = if mandate_output.token == FixedBytes::<32>::ZERO {
+			mandate_output.amount
+		} else {
+			U256::ZERO
+		};
+
+		// Encode fill data
+		let fill_data = IDestinationSettler::fillCall {
+			orderId: FixedBytes::<32>::from(order_data.order_id),
+			originData: mandate_output.abi_encode().into(),
+			fillerData: {
+				// FillerData should contain the solver address as bytes32
+				let mut solver_bytes32 = [0u8; 32];
+				solver_bytes32[12..32].copy_from_slice(&self.solver_address.0);
+				solver_bytes32.to_vec().into()
+			},
+		}
+		.abi_encode();
+
+		Ok((order_data, fill_data, value))
+	}
+
+	/// Builds the order a gasless order's user signed.
+	fn signed_order(&self, order_data: &Eip7683OrderData) -> Result<StandardOrder, OrderError> {
+		let user = hex::decode(order_data.user.trim_start_matches("0x"))
+			.map_err(|e| OrderError::ValidationFailed(format!("Invalid user address: {}", e)))?;
+		let oracle = hex::decode(order_data.local_oracle.trim_start_matches("0x"))
+			.map_err(|e| OrderError::ValidationFailed(format!("Invalid oracle address: {}", e)))?;
+		if user.len() != 20 || oracle.len() != 20 {
+			return Err(OrderError::ValidationFailed(
+				"User and oracle must be 20-byte addresses".to_string(),
+			));
 		}
+
+		Ok(StandardOrder {
+			user: AlloyAddress::from_slice(&user),
+			nonce: U256::from(order_data.nonce),
+			originChainId: U256::from(order_data.origin_chain_id),
+			expires: order_data.expires,
+			fillDeadline: order_data.fill_deadline,
+			inputOracle: AlloyAddress::from_slice(&oracle),
+			inputs: order_data.inputs.clone(),
+			outputs: self.mandate_outputs(order_data),
+		})
+	}
+
+	/// Builds the payload the user of a signed order signed, if it can be
+	/// verified.
+	///
+	/// Permit2 orders are signed as a Permit2 transfer witnessing the order.
+	/// Other orders are signed in the input settler's domain, which is only
+	/// known if configured.
+	fn signed_payload(
+		&self,
+		order: StandardOrder,
+		order_data: &Eip7683OrderData,
+	) -> Option<SignedPayload> {
+		let chain_id = U256::from(order_data.origin_chain_id);
+		let input_settler = AlloyAddress::from_slice(&self.input_settler_address.0);
+
+		if let Some(permit) = order_data
+			.permit
+			.as_ref()
+			.filter(|permit| permit.scheme == PermitScheme::Permit2)
+		{
+			let transfer = PermitBatchWitnessTransferFrom {
+				permitted: order
+					.inputs
+					.iter()
+					.map(|[token, amount]| TokenPermissions {
+						token: AlloyAddress::from_word(B256::from(token.to_be_bytes::<32>())),
+						amount: *amount,
+					})
+					.collect(),
+				spender: input_settler,
+				nonce: permit.nonce,
+				deadline: U256::from(permit.deadline),
+				witness: Permit2Witness {
+					expires: order.expires,
+					inputOracle: order.inputOracle,
+					outputs: order.outputs,
+				},
+			};
+			let domain = Eip712Domain::new(
+				Some("Permit2".into()),
+				None,
+				Some(chain_id),
+				Some(AlloyAddress::from_slice(&self.permit2_address.0)),
+				None,
+			);
+			return Some(SignedPayload::Permit2(transfer, domain));
+		}
+
+		self.signature_domain.as_ref().map(|domain| {
+			let domain = Eip712Domain::new(
+				Some(domain.name.clone().into()),
+				Some(domain.version.clone().into()),
+				Some(chain_id),
+				Some(input_settler),
+				None,
+			);
+			SignedPayload::Order(order, domain)
+		})
+	}
+
+	/// Builds the outputs of an order as committed to by the input settler.
+	fn mandate_outputs(&self, order_data: &Eip7683OrderData) -> Vec<MandateOutput> {
+		order_data
+			.outputs
+			.iter()
+			.map(|output| {
+				// Convert addresses to bytes32
+				let oracle_bytes32 = FixedBytes::<32>::from([0u8; 32]); // No oracle
+
+				let settler_bytes32 = {
+					let mut bytes32 = [0u8; 32];
+					if output.chain_id == order_data.origin_chain_id {
+						// Use input settler for origin chain
+						bytes32[12..32].copy_from_slice(&self.input_settler_address.0);
+					} else {
+						// Use output settler for other chains
+						bytes32[12..32].copy_from_slice(&self.output_settler_address.0);
+					}
+					FixedBytes::<32>::from(bytes32)
+				};
+
+				let token_bytes32 = {
+					let token_hex = output.token.trim_start_matches("0x");
+					let token_bytes = hex::decode(token_hex).unwrap_or_else(|_| vec![0; 20]);
+					let mut bytes32 = [0u8; 32];
+					bytes32[12..32].copy_from_slice(&token_bytes);
+					FixedBytes::<32>::from(bytes32)
+				};
+
+				let recipient_bytes32 = {
+					let recipient_hex = output.recipient.trim_start_matches("0x");
+					let recipient_bytes =
+						hex::decode(recipient_hex).unwrap_or_else(|_| vec![0; 20]);
+					let mut bytes32 = [0u8; 32];
+					bytes32[12..32].copy_from_slice(&recipient_bytes);
+					FixedBytes::<32>::from(bytes32)
+				};
+
+				MandateOutput {
+					oracle: oracle_bytes32,
+					settler: settler_bytes32,
+					chainId: U256::from(output.chain_id),
+					token: token_bytes32,
+					amount: output.amount,
+					recipient: recipient_bytes32,
+					call: output.call_data().unwrap_or_default().into(),
+					context: vec![].into(),
+				}
+			})
+			.collect()
 	}
 }
 
@@ -146,7 +611,26 @@ impl ConfigSchema for Eip7683OrderSchema {
 				}),
 			],
 			// Optional fields
-			vec![],
+			vec![
+				Field::new("bundle_fills", FieldType::Boolean),
+				Field::new("finalise_to", FieldType::Boolean),
+				Field::new("signature_domain_name", FieldType::String),
+				Field::new("signature_domain_version", FieldType::String),
+				Field::new("permit2_address", FieldType::String).with_validator(|value| {
+					let addr = value.as_str().unwrap();
+					if addr.len() != 42 || !addr.starts_with("0x") {
+						return Err("permit2_address must be a valid Ethereum address".to_string());
+					}
+					Ok(())
+				}),
+				Field::new(
+					"signature_workers",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+			],
 		);
 
 		schema.validate(config)
@@ -159,6 +643,12 @@ impl OrderInterface for Eip7683OrderImpl {
 		Box::new(Eip7683OrderSchema)
 	}
 
+	/// Rewards are paid to an arbitrary destination through the input
+	/// settler's `finaliseTo`, if the settler is configured to expose it.
+	fn supports_claim_recipient(&self) -> bool {
+		self.finalise_to
+	}
+
 	/// Validates an EIP-7683 intent and converts it to an order.
 	async fn validate_intent(&self, intent: &Intent) -> Result<Order, OrderError> {
 		if intent.standard != "eip7683" {
@@ -183,6 +673,87 @@ impl OrderInterface for Eip7683OrderImpl {
 			return Err(OrderError::ValidationFailed("Order expired".to_string()));
 		}
 
+		// Output calls must be well-formed to be committed to in fills and claims
+		for output in &order_data.outputs {
+			output.call_data()?;
+		}
+
+		// Reject malformed signatures before anything else looks at the order
+		if let Some(signature) = &order_data.signature {
+			parse_signature(signature).map_err(|e| {
+				OrderError::ValidationFailed(format!("Invalid user signature: {}", e))
+			})?;
+		}
+
+		// Off-chain intents are vouched for by nothing but their signature
+		let offchain = intent.source == OFFCHAIN_SOURCE;
+		if offchain && order_data.signature.is_none() {
+			return Err(OrderError::ValidationFailed(
+				"Off-chain intents require a user signature".to_string(),
+			));
+		}
+
+		// Hashing and recovery are CPU-bound, so they run on a signature worker
+		if let Some(signature) = &order_data.signature {
+			let signed = self.signed_order(&order_data)?;
+			let user = signed.user;
+			match self.signed_payload(signed, &order_data) {
+				Some(payload) => {
+					let signature = signature.clone();
+					self.signatures
+						.run(move || verify_order_signature(&payload, user, &signature))
+						.await?;
+				}
+				None if offchain => {
+					return Err(OrderError::ValidationFailed(
+						"Off-chain intents cannot be verified without a signature domain"
+							.to_string(),
+					));
+				}
+				None => {}
+			}
+		}
+
+		// Inputs pulled by permit at claim need the user's signature over the permit
+		if let Some(permit) = &order_data.permit {
+			permit.settler_signature()?;
+			if (now as u64) > permit.deadline {
+				return Err(OrderError::ValidationFailed("Permit expired".to_string()));
+			}
+		}
+
+		// Every chain the order may be filled on is a route, if there are several
+		let mut routes = Vec::new();
+		if !order_data.destination_chain_ids.is_empty() {
+			if !order_data
+				.destination_chain_ids
+				.contains(&order_data.destination_chain_id)
+			{
+				return Err(OrderError::ValidationFailed(
+					"Destination chain is not among the allowed destinations".to_string(),
+				));
+			}
+			let outputs = output_amounts(&order_data)?;
+			for &chain_id in &order_data.destination_chain_ids {
+				let route_outputs: Vec<_> = outputs
+					.iter()
+					.filter(|output| output.chain_id == chain_id)
+					.cloned()
+					.collect();
+				if chain_id == order_data.origin_chain_id || route_outputs.is_empty() {
+					continue;
+				}
+				routes.push(FillRoute {
+					chain_id,
+					outputs: route_outputs,
+					gas_limit: Some(order_data.fill_gas_limit),
+				});
+			}
+			if routes.len() < 2 {
+				routes.clear();
+			}
+		}
+
 		// Create order
 		Ok(Order {
 			id: intent.id.clone(),
@@ -190,85 +761,132 @@ impl OrderInterface for Eip7683OrderImpl {
 			created_at: intent.metadata.discovered_at,
 			data: serde_json::to_value(&order_data)
 				.map_err(|e| OrderError::ValidationFailed(format!("Failed to serialize: {}", e)))?,
+			implementation: None,
+			metadata: intent.metadata.custom.clone(),
+			exclusive_until: intent.metadata.exclusive_until,
+			routes,
 		})
 	}
 
-	/// Generates a transaction to fill an EIP-7683 order on the destination chain.
-	async fn generate_fill_transaction(
-		&self,
-		order: &Order,
-		_params: &ExecutionParams,
-	) -> Result<Transaction, OrderError> {
+	/// Points the order at the route's chain, whose outputs are then filled.
+	fn apply_route(&self, order: &mut Order, route: &FillRoute) -> Result<(), OrderError> {
+		let mut order_data: Eip7683OrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+		if !order_data.destination_chain_ids.contains(&route.chain_id) {
+			return Err(OrderError::ValidationFailed(format!(
+				"Chain {} is not among the order's destinations",
+				route.chain_id
+			)));
+		}
+
+		order_data.destination_chain_id = route.chain_id;
+		order.data = serde_json::to_value(&order_data)
+			.map_err(|e| OrderError::ValidationFailed(format!("Failed to serialize: {}", e)))?;
+		Ok(())
+	}
+
+	/// Extracts inputs (origin chain) and outputs (per output chain) from the order data.
+	fn order_assets(&self, order: &Order) -> Result<OrderAssets, OrderError> {
 		let order_data: Eip7683OrderData =
 			serde_json::from_value(order.data.clone()).map_err(|e| {
 				OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
 			})?;
 
-		// Check if this is a same-chain order
-		if order_data.origin_chain_id == order_data.destination_chain_id {
-			return Err(OrderError::ValidationFailed(
-				"Same-chain orders are not supported".to_string(),
-			));
+		// Inputs are encoded as [token, amount] with the token address in the low 20 bytes
+		let inputs = order_data
+			.inputs
+			.iter()
+			.map(|[token, amount]| TokenAmount {
+				chain_id: order_data.origin_chain_id,
+				token: Address(token.to_be_bytes::<32>()[12..32].to_vec()),
+				amount: *amount,
+			})
+			.collect();
+
+		// Outputs on destinations the solver did not pick are never delivered
+		let mut outputs = output_amounts(&order_data)?;
+		if !order_data.destination_chain_ids.is_empty() {
+			outputs.retain(|output| output.chain_id == order_data.destination_chain_id);
 		}
 
-		// Get the output for the destination chain
-		let output = order_data
+		Ok(OrderAssets { inputs, outputs })
+	}
+
+	/// Orders carrying a user signature were opened off-chain (gasless).
+	fn is_gasless(&self, order: &Order) -> bool {
+		serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+			.map(|order_data| order_data.signature.is_some())
+			.unwrap_or(false)
+	}
+
+	fn user(&self, order: &Order) -> Option<Address> {
+		let order_data: Eip7683OrderData = serde_json::from_value(order.data.clone()).ok()?;
+		hex::decode(order_data.user.trim_start_matches("0x"))
+			.ok()
+			.map(Address)
+	}
+
+	fn recipients(&self, order: &Order) -> Vec<Address> {
+		let Ok(order_data) = serde_json::from_value::<Eip7683OrderData>(order.data.clone()) else {
+			return Vec::new();
+		};
+		order_data
 			.outputs
 			.iter()
-			.find(|o| o.chain_id == order_data.destination_chain_id)
-			.ok_or_else(|| {
-				OrderError::ValidationFailed("No output found for destination chain".to_string())
+			.filter_map(|output| hex::decode(output.recipient.trim_start_matches("0x")).ok())
+			// Recipients may be given as left-padded bytes32
+			.map(|bytes| Address(bytes[bytes.len().saturating_sub(20)..].to_vec()))
+			.collect()
+	}
+
+	fn has_output_calls(&self, order: &Order) -> bool {
+		serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+			.map(|order_data| {
+				order_data
+					.outputs
+					.iter()
+					.any(|output| output.call_data().map_or(true, |call| !call.is_empty()))
+			})
+			.unwrap_or(false)
+	}
+
+	/// Inputs are refunded to the user once the order expires, so claims must
+	/// land before then. Inputs pulled by permit must also be pulled before
+	/// the permit's deadline.
+	fn claim_deadline(&self, order: &Order) -> Option<u64> {
+		serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+			.ok()
+			.map(|order_data| match &order_data.permit {
+				Some(permit) => permit.deadline.min(order_data.expires as u64),
+				None => order_data.expires as u64,
+			})
+	}
+
+	/// Reads the fill deadline from the order data.
+	fn fill_deadline(&self, order: &Order) -> Result<u64, OrderError> {
+		let order_data: Eip7683OrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
 			})?;
 
-		// Create the MandateOutput struct for the fill operation
-		let mandate_output = MandateOutput {
-			oracle: FixedBytes::<32>::from([0u8; 32]), // No oracle for direct fills
-			settler: {
-				let mut bytes32 = [0u8; 32];
-				bytes32[12..32].copy_from_slice(&self.output_settler_address.0);
-				FixedBytes::<32>::from(bytes32)
-			},
-			chainId: U256::from(output.chain_id),
-			token: {
-				let token_hex = output.token.trim_start_matches("0x");
-				let token_bytes = hex::decode(token_hex).map_err(|e| {
-					OrderError::ValidationFailed(format!("Invalid token address: {}", e))
-				})?;
-				let mut bytes32 = [0u8; 32];
-				bytes32[12..32].copy_from_slice(&token_bytes);
-				FixedBytes::<32>::from(bytes32)
-			},
-			amount: output.amount,
-			recipient: {
-				let recipient_hex = output.recipient.trim_start_matches("0x");
-				let recipient_bytes = hex::decode(recipient_hex).map_err(|e| {
-					OrderError::ValidationFailed(format!("Invalid recipient address: {}", e))
-				})?;
-				let mut bytes32 = [0u8; 32];
-				bytes32[12..32].copy_from_slice(&recipient_bytes);
-				FixedBytes::<32>::from(bytes32)
-			},
-			call: vec![].into(),    // Empty for direct transfers
-			context: vec![].into(), // Empty context
-		};
+		Ok(order_data.fill_deadline as u64)
+	}
 
-		// Encode fill data
-		let fill_data = IDestinationSettler::fillCall {
-			orderId: FixedBytes::<32>::from(order_data.order_id),
-			originData: mandate_output.abi_encode().into(),
-			fillerData: {
-				// FillerData should contain the solver address as bytes32
-				let mut solver_bytes32 = [0u8; 32];
-				solver_bytes32[12..32].copy_from_slice(&self.solver_address.0);
-				solver_bytes32.to_vec().into()
-			},
-		}
-		.abi_encode();
+	/// Generates a transaction to fill an EIP-7683 order on the destination chain.
+	async fn generate_fill_transaction(
+		&self,
+		order: &Order,
+		_params: &ExecutionParams,
+		_fill_amount: U256,
+	) -> Result<Transaction, OrderError> {
+		let (order_data, fill_data, value) = self.fill_call(order)?;
 
 		Ok(Transaction {
 			to: Some(self.output_settler_address.clone()),
 			data: fill_data,
-			value: U256::ZERO,
+			value,
 			chain_id: order_data.destination_chain_id,
 			nonce: None,
 			gas_limit: Some(order_data.fill_gas_limit),
@@ -278,11 +896,120 @@ impl OrderInterface for Eip7683OrderImpl {
 		})
 	}
 
+	/// Bundles are filled through the output settler's `multicall`, if enabled.
+	fn supports_fill_bundles(&self) -> bool {
+		self.bundle_fills
+	}
+
+	/// Generates an output settler `multicall` filling every order in turn.
+	///
+	/// The settler skips orders that are already filled without reverting, so
+	/// the gas limit is the sum of the orders' fill gas limits. `multicall`
+	/// cannot forward value to each call, so orders with outputs in the
+	/// chain's gas token are not bundled.
+	async fn generate_bundle_fill_transaction(
+		&self,
+		fills: &[(&Order, &ExecutionParams)],
+	) -> Result<Transaction, OrderError> {
+		if !self.bundle_fills {
+			return Err(OrderError::ValidationFailed(
+				"Fill bundles are not enabled for this settler".to_string(),
+			));
+		}
+
+		let mut chain_id = None;
+		let mut gas_limit = 0u64;
+		let mut calls = Vec::with_capacity(fills.len());
+		for (order, _) in fills {
+			let (order_data, fill_data, value) = self.fill_call(order)?;
+			if !value.is_zero() {
+				return Err(OrderError::ValidationFailed(
+					"Fill bundles cannot carry native outputs".to_string(),
+				));
+			}
+			if *chain_id.get_or_insert(order_data.destination_chain_id)
+				!= order_data.destination_chain_id
+			{
+				return Err(OrderError::ValidationFailed(
+					"Fill bundle spans several destination chains".to_string(),
+				));
+			}
+			gas_limit = gas_limit.saturating_add(order_data.fill_gas_limit);
+			calls.push(fill_data.into());
+		}
+		let chain_id = chain_id
+			.ok_or_else(|| OrderError::ValidationFailed("Empty fill bundle".to_string()))?;
+
+		Ok(Transaction {
+			to: Some(self.output_settler_address.clone()),
+			data: IMulticall::multicallCall { data: calls }.abi_encode(),
+			value: U256::ZERO,
+			chain_id,
+			nonce: None,
+			gas_limit: Some(gas_limit),
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+		})
+	}
+
+	/// An order of the bundle was filled if the output settler emitted
+	/// `OutputFilled` for it.
+	fn bundle_fill_results(&self, orders: &[&Order], receipt: &TransactionReceipt) -> Vec<bool> {
+		let filled: Vec<&[u8]> = receipt
+			.logs
+			.iter()
+			.filter(|log| log.address == self.output_settler_address)
+			.filter(|log| is_event::<OutputFilled>(&log.topics))
+			.filter_map(|log| log.topics.get(1).map(|topic| topic.as_slice()))
+			.collect();
+
+		orders
+			.iter()
+			.map(|order| {
+				receipt.success
+					&& serde_json::from_value::<Eip7683OrderData>(order.data.clone())
+						.is_ok_and(|data| filled.contains(&data.order_id.as_slice()))
+			})
+			.collect()
+	}
+
+	/// Asks the output settler for the fill record of the order's destination
+	/// output.
+	fn fill_check_call(&self, order: &Order) -> Result<Option<Transaction>, OrderError> {
+		let (order_data, mandate_output) = self.destination_output(order)?;
+
+		Ok(Some(Transaction {
+			to: Some(self.output_settler_address.clone()),
+			data: IOutputSettler::getFillRecordCall {
+				orderId: FixedBytes::<32>::from(order_data.order_id),
+				output: mandate_output,
+			}
+			.abi_encode(),
+			value: U256::ZERO,
+			chain_id: order_data.destination_chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+		}))
+	}
+
+	/// The output was filled if the settler holds a non-zero fill record.
+	fn is_filled(&self, _order: &Order, result: &[u8]) -> Result<bool, OrderError> {
+		let record = IOutputSettler::getFillRecordCall::abi_decode_returns(result, true)
+			.map_err(|e| OrderError::ValidationFailed(format!("Invalid fill record: {}", e)))?;
+
+		Ok(!record.payloadHash.is_zero())
+	}
+
 	/// Generates a transaction to claim rewards for a filled order on the origin chain.
 	async fn generate_claim_transaction(
 		&self,
 		order: &Order,
 		fill_proof: &FillProof,
+		claim_recipients: &HashMap<u64, Address>,
 	) -> Result<Transaction, OrderError> {
 		let order_data: Eip7683OrderData =
 			serde_json::from_value(order.data.clone()).map_err(|e| {
@@ -312,54 +1039,7 @@ impl OrderInterface for Eip7683OrderImpl {
 		let inputs: Vec<[U256; 2]> = order_data.inputs.clone();
 
 		// Create outputs array (MandateOutput structs)
-		let outputs: Vec<MandateOutput> = order_data
-			.outputs
-			.iter()
-			.map(|output| {
-				// Convert addresses to bytes32
-				let oracle_bytes32 = FixedBytes::<32>::from([0u8; 32]); // No oracle
-
-				let settler_bytes32 = {
-					let mut bytes32 = [0u8; 32];
-					if output.chain_id == order_data.origin_chain_id {
-						// Use input settler for origin chain
-						bytes32[12..32].copy_from_slice(&self.input_settler_address.0);
-					} else {
-						// Use output settler for other chains
-						bytes32[12..32].copy_from_slice(&self.output_settler_address.0);
-					}
-					FixedBytes::<32>::from(bytes32)
-				};
-
-				let token_bytes32 = {
-					let token_hex = output.token.trim_start_matches("0x");
-					let token_bytes = hex::decode(token_hex).unwrap_or_else(|_| vec![0; 20]);
-					let mut bytes32 = [0u8; 32];
-					bytes32[12..32].copy_from_slice(&token_bytes);
-					FixedBytes::<32>::from(bytes32)
-				};
-
-				let recipient_bytes32 = {
-					let recipient_hex = output.recipient.trim_start_matches("0x");
-					let recipient_bytes =
-						hex::decode(recipient_hex).unwrap_or_else(|_| vec![0; 20]);
-					let mut bytes32 = [0u8; 32];
-					bytes32[12..32].copy_from_slice(&recipient_bytes);
-					FixedBytes::<32>::from(bytes32)
-				};
-
-				MandateOutput {
-					oracle: oracle_bytes32,
-					settler: settler_bytes32,
-					chainId: U256::from(output.chain_id),
-					token: token_bytes32,
-					amount: output.amount,
-					recipient: recipient_bytes32,
-					call: vec![].into(),
-					context: vec![].into(),
-				}
-			})
-			.collect();
+		let outputs = self.mandate_outputs(&order_data);
 
 		// Build the order struct
 		let order_struct = OrderStruct {
@@ -381,13 +1061,57 @@ impl OrderInterface for Eip7683OrderImpl {
 		solver_bytes32[12..32].copy_from_slice(&self.solver_address.0);
 		let solver = FixedBytes::<32>::from(solver_bytes32);
 
-		// Encode the finaliseSelf call
-		let call_data = IInputSettler::finaliseSelfCall {
-			order: order_struct,
-			timestamps,
-			solver,
-		}
-		.abi_encode();
+		// Encode the finalise call, routing rewards to the configured recipient if any
+		let claim_recipient = claim_recipients
+			.get(&order_data.origin_chain_id)
+			.filter(|_| self.finalise_to);
+		let finalise = match claim_recipient {
+			Some(recipient) => {
+				let mut destination_bytes32 = [0u8; 32];
+				destination_bytes32[12..32].copy_from_slice(&recipient.0);
+				IInputSettler::finaliseToCall {
+					order: order_struct,
+					timestamps,
+					solver,
+					destination: FixedBytes::<32>::from(destination_bytes32),
+					call: vec![].into(),
+				}
+				.abi_encode()
+			}
+			None => IInputSettler::finaliseSelfCall {
+				order: order_struct,
+				timestamps,
+				solver,
+			}
+			.abi_encode(),
+		};
+
+		// Inputs that were not escrowed at open are pulled with the user's
+		// permit by opening the order, in the same transaction as the finalise
+		let call_data = match &order_data.permit {
+			Some(permit) => {
+				let now = std::time::SystemTime::now()
+					.duration_since(std::time::UNIX_EPOCH)
+					.unwrap()
+					.as_secs();
+				if now > permit.deadline {
+					return Err(OrderError::ValidationFailed(
+						"Permit expired before the claim".to_string(),
+					));
+				}
+				let open = IInputSettler::openForCall {
+					order: self.signed_order(&order_data)?,
+					sponsor: user_address,
+					signature: permit.settler_signature()?.into(),
+				}
+				.abi_encode();
+				IMulticall::multicallCall {
+					data: vec![open.into(), finalise.into()],
+				}
+				.abi_encode()
+			}
+			None => finalise,
+		};
 
 		Ok(Transaction {
 			to: Some(self.input_settler_address.clone()),
@@ -409,6 +1133,18 @@ impl OrderInterface for Eip7683OrderImpl {
 /// - `output_settler_address`: Address of the output settler contract
 /// - `input_settler_address`: Address of the input settler contract
 /// - `solver_address`: Address of the solver for claiming rewards
+///
+/// Optional configuration parameters:
+/// - `bundle_fills`: Whether the output settler supports `multicall` fill bundles
+/// - `finalise_to`: Whether the input settler supports `finaliseTo`, which
+///   claim recipients require
+/// - `signature_domain_name`: EIP-712 domain name gasless orders are signed in;
+///   enables signer verification, and is required to accept off-chain intents
+///   other than Permit2 orders
+/// - `signature_domain_version`: EIP-712 domain version, defaults to "1"
+/// - `permit2_address`: Permit2 contract Permit2 orders are signed for,
+///   defaults to its canonical address
+/// - `signature_workers`: Signatures verified at once, defaults to the CPU count
 pub fn create_order_impl(config: &toml::Value) -> Box<dyn OrderInterface> {
 	let output_settler = config
 		.get("output_settler_address")
@@ -425,9 +1161,46 @@ pub fn create_order_impl(config: &toml::Value) -> Box<dyn OrderInterface> {
 		.and_then(|v| v.as_str())
 		.expect("solver_address is required");
 
-	Box::new(Eip7683OrderImpl::new(
+	let bundle_fills = config
+		.get("bundle_fills")
+		.and_then(|v| v.as_bool())
+		.unwrap_or(false);
+
+	let finalise_to = config
+		.get("finalise_to")
+		.and_then(|v| v.as_bool())
+		.unwrap_or(false);
+
+	let mut order_impl = Eip7683OrderImpl::new(
 		output_settler.to_string(),
 		input_settler.to_string(),
 		solver_address.to_string(),
-	))
+	)
+	.with_bundle_fills(bundle_fills)
+	.with_finalise_to(finalise_to);
+
+	if let Some(permit2) = config.get("permit2_address").and_then(|v| v.as_str()) {
+		order_impl = order_impl.with_permit2_address(permit2.to_string());
+	}
+
+	if let Some(name) = config.get("signature_domain_name").and_then(|v| v.as_str()) {
+		let version = config
+			.get("signature_domain_version")
+			.and_then(|v| v.as_str())
+			.unwrap_or("1");
+		let workers = config
+			.get("signature_workers")
+			.and_then(|v| v.as_integer())
+			.map(|workers| SignatureWorkers::new(workers as usize))
+			.unwrap_or_default();
+		order_impl = order_impl.with_signature_verification(
+			SignatureDomain {
+				name: name.to_string(),
+				version: version.to_string(),
+			},
+			workers,
+		);
+	}
+
+	Box::new(order_impl)
 }
@@ -0,0 +1,388 @@
+//! Order implementation for Across V3 deposits.
+//!
+//! A V3 deposit on an origin chain SpokePool is filled by calling
+//! `fillV3Relay` on the destination chain SpokePool with the deposit's relay
+//! data. Relayers do not claim inputs themselves: Across bundles the fills of
+//! each period into a relayer refund root, which is relayed to the SpokePool
+//! of the repayment chain, and its leaves pay relayers back there. Fills ask
+//! to be repaid on the origin chain, in the deposit's input token.
+
+use crate::{OrderError, OrderInterface};
+use alloy_primitives::{keccak256, Address as AlloyAddress, U256};
+use alloy_sol_types::{sol, SolCall, SolValue};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solver_types::{
+	Address, ConfigSchema, ExecutionParams, Field, FieldType, FillProof, Intent, Order,
+	OrderAssets, Schema, TokenAmount, Transaction,
+};
+use std::collections::HashMap;
+
+/// Gas limit of fills when not configured.
+const DEFAULT_FILL_GAS_LIMIT: u64 = 200_000;
+
+/// Fill status the SpokePool records for a relay once it was filled.
+const FILL_STATUS_FILLED: u64 = 2;
+
+sol! {
+	/// Relay data identifying a V3 deposit.
+	struct V3RelayData {
+		address depositor;
+		address recipient;
+		address exclusiveRelayer;
+		address inputToken;
+		address outputToken;
+		uint256 inputAmount;
+		uint256 outputAmount;
+		uint256 originChainId;
+		uint32 depositId;
+		uint32 fillDeadline;
+		uint32 exclusivityDeadline;
+		bytes message;
+	}
+
+	/// SpokePool interface for filling V3 deposits.
+	interface ISpokePool {
+		function fillV3Relay(V3RelayData relayData, uint256 repaymentChainId) external;
+		function fillStatuses(bytes32 relayHash) external view returns (uint256 status);
+	}
+}
+
+/// Across V3 deposit data, as emitted in `V3FundsDeposited`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcrossOrderData {
+	pub depositor: String,
+	pub recipient: String,
+	/// Relayer the deposit is exclusive to until `exclusivity_deadline`, or
+	/// the zero address.
+	pub exclusive_relayer: String,
+	pub input_token: String,
+	pub output_token: String,
+	pub input_amount: U256,
+	pub output_amount: U256,
+	pub origin_chain_id: u64,
+	pub destination_chain_id: u64,
+	pub deposit_id: u32,
+	pub quote_timestamp: u32,
+	pub fill_deadline: u32,
+	pub exclusivity_deadline: u32,
+	/// Message passed to the recipient with the output, hex encoded.
+	#[serde(default)]
+	pub message: String,
+}
+
+impl AcrossOrderData {
+	/// Builds the relay data the SpokePool identifies the deposit by.
+	pub fn relay_data(&self) -> Result<V3RelayData, OrderError> {
+		let address = |name: &str, value: &str| {
+			value
+				.parse::<AlloyAddress>()
+				.map_err(|e| OrderError::ValidationFailed(format!("Invalid {}: {}", name, e)))
+		};
+		let message = hex::decode(self.message.trim_start_matches("0x"))
+			.map_err(|e| OrderError::ValidationFailed(format!("Invalid message: {}", e)))?;
+
+		Ok(V3RelayData {
+			depositor: address("depositor", &self.depositor)?,
+			recipient: address("recipient", &self.recipient)?,
+			exclusiveRelayer: address("exclusive relayer", &self.exclusive_relayer)?,
+			inputToken: address("input token", &self.input_token)?,
+			outputToken: address("output token", &self.output_token)?,
+			inputAmount: self.input_amount,
+			outputAmount: self.output_amount,
+			originChainId: U256::from(self.origin_chain_id),
+			depositId: self.deposit_id,
+			fillDeadline: self.fill_deadline,
+			exclusivityDeadline: self.exclusivity_deadline,
+			message: message.into(),
+		})
+	}
+}
+
+/// Parses the Across deposit data of an order.
+fn order_data(order: &Order) -> Result<AcrossOrderData, OrderError> {
+	serde_json::from_value(order.data.clone())
+		.map_err(|e| OrderError::ValidationFailed(format!("Failed to parse order data: {}", e)))
+}
+
+/// Parses a hex encoded address.
+fn parse_address(address: &str) -> Result<Address, OrderError> {
+	hex::decode(address.trim_start_matches("0x"))
+		.map(Address)
+		.map_err(|e| OrderError::ValidationFailed(format!("Invalid address: {}", e)))
+}
+
+/// Across V3 order implementation.
+pub struct AcrossOrderImpl {
+	/// Address of the SpokePool on destination chains.
+	spoke_pool_address: Address,
+	/// Address of the solver, which relays fills and is repaid.
+	solver_address: Address,
+	/// Gas limit of fill transactions.
+	fill_gas_limit: u64,
+}
+
+impl AcrossOrderImpl {
+	/// Creates a new Across order implementation.
+	pub fn new(spoke_pool: String, solver: String) -> Self {
+		Self {
+			spoke_pool_address: Address(
+				hex::decode(spoke_pool.trim_start_matches("0x"))
+					.expect("Invalid spoke pool address"),
+			),
+			solver_address: Address(
+				hex::decode(solver.trim_start_matches("0x")).expect("Invalid solver address"),
+			),
+			fill_gas_limit: DEFAULT_FILL_GAS_LIMIT,
+		}
+	}
+
+	/// Sets the gas limit of fill transactions.
+	pub fn with_fill_gas_limit(mut self, fill_gas_limit: u64) -> Self {
+		self.fill_gas_limit = fill_gas_limit;
+		self
+	}
+}
+
+/// Configuration schema for the Across order implementation.
+pub struct AcrossOrderSchema;
+
+impl ConfigSchema for AcrossOrderSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let address = |field: &str| {
+			Field::new(field, FieldType::String).with_validator(|value| {
+				let addr = value.as_str().unwrap();
+				if addr.len() != 42 || !addr.starts_with("0x") {
+					return Err("Must be a valid Ethereum address".to_string());
+				}
+				Ok(())
+			})
+		};
+
+		let schema = Schema::new(
+			// Required fields
+			vec![address("spoke_pool_address"), address("solver_address")],
+			// Optional fields
+			vec![Field::new(
+				"fill_gas_limit",
+				FieldType::Integer {
+					min: Some(21_000),
+					max: None,
+				},
+			)],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl OrderInterface for AcrossOrderImpl {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(AcrossOrderSchema)
+	}
+
+	/// Validates an Across deposit and converts it to an order.
+	///
+	/// Deposits still exclusive to another relayer are rejected. Deposits
+	/// exclusive to the solver must be filled before exclusivity ends.
+	async fn validate_intent(&self, intent: &Intent) -> Result<Order, OrderError> {
+		if intent.standard != "across" {
+			return Err(OrderError::ValidationFailed(
+				"Not an Across order".to_string(),
+			));
+		}
+
+		let order_data: AcrossOrderData =
+			serde_json::from_value(intent.data.clone()).map_err(|e| {
+				OrderError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+		let relay_data = order_data.relay_data()?;
+
+		let now = std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_secs();
+		if now > order_data.fill_deadline as u64 {
+			return Err(OrderError::ValidationFailed("Order expired".to_string()));
+		}
+
+		let solver = AlloyAddress::from_slice(&self.solver_address.0);
+		let exclusive =
+			!relay_data.exclusiveRelayer.is_zero() && now < order_data.exclusivity_deadline as u64;
+		let exclusive_until = match exclusive {
+			true if relay_data.exclusiveRelayer != solver => {
+				return Err(OrderError::ValidationFailed(format!(
+					"Deposit is exclusive to {} until {}",
+					relay_data.exclusiveRelayer, order_data.exclusivity_deadline
+				)));
+			}
+			true => Some(order_data.exclusivity_deadline as u64),
+			false => None,
+		};
+
+		Ok(Order {
+			id: intent.id.clone(),
+			standard: intent.standard.clone(),
+			created_at: intent.metadata.discovered_at,
+			data: serde_json::to_value(&order_data)
+				.map_err(|e| OrderError::ValidationFailed(format!("Failed to serialize: {}", e)))?,
+			implementation: None,
+			metadata: intent.metadata.custom.clone(),
+			exclusive_until,
+			routes: Vec::new(),
+		})
+	}
+
+	fn user(&self, order: &Order) -> Option<Address> {
+		parse_address(&order_data(order).ok()?.depositor).ok()
+	}
+
+	fn recipients(&self, order: &Order) -> Vec<Address> {
+		order_data(order)
+			.ok()
+			.and_then(|order_data| parse_address(&order_data.recipient).ok())
+			.into_iter()
+			.collect()
+	}
+
+	/// Deposits with a message call `handleV3AcrossMessage` on the recipient.
+	fn has_output_calls(&self, order: &Order) -> bool {
+		order_data(order)
+			.is_ok_and(|order_data| !order_data.message.trim_start_matches("0x").is_empty())
+	}
+
+	/// The input is deposited on the origin chain and the output paid on the
+	/// destination chain.
+	fn order_assets(&self, order: &Order) -> Result<OrderAssets, OrderError> {
+		let order_data = order_data(order)?;
+		Ok(OrderAssets {
+			inputs: vec![TokenAmount {
+				chain_id: order_data.origin_chain_id,
+				token: parse_address(&order_data.input_token)?,
+				amount: order_data.input_amount,
+			}],
+			outputs: vec![TokenAmount {
+				chain_id: order_data.destination_chain_id,
+				token: parse_address(&order_data.output_token)?,
+				amount: order_data.output_amount,
+			}],
+		})
+	}
+
+	fn fill_deadline(&self, order: &Order) -> Result<u64, OrderError> {
+		Ok(order_data(order)?.fill_deadline as u64)
+	}
+
+	/// Generates a `fillV3Relay` call on the destination SpokePool, asking to
+	/// be repaid on the origin chain.
+	async fn generate_fill_transaction(
+		&self,
+		order: &Order,
+		_params: &ExecutionParams,
+		_fill_amount: U256,
+	) -> Result<Transaction, OrderError> {
+		let order_data = order_data(order)?;
+		let call_data = ISpokePool::fillV3RelayCall {
+			relayData: order_data.relay_data()?,
+			repaymentChainId: U256::from(order_data.origin_chain_id),
+		}
+		.abi_encode();
+
+		Ok(Transaction {
+			to: Some(self.spoke_pool_address.clone()),
+			data: call_data,
+			value: U256::ZERO,
+			chain_id: order_data.destination_chain_id,
+			nonce: None,
+			gas_limit: Some(self.fill_gas_limit),
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+		})
+	}
+
+	/// Asks the destination SpokePool for the fill status of the relay.
+	///
+	/// Relays are keyed by the hash of their relay data and destination chain.
+	fn fill_check_call(&self, order: &Order) -> Result<Option<Transaction>, OrderError> {
+		let order_data = order_data(order)?;
+		let relay_hash = keccak256(
+			(
+				order_data.relay_data()?,
+				U256::from(order_data.destination_chain_id),
+			)
+				.abi_encode(),
+		);
+
+		Ok(Some(Transaction {
+			to: Some(self.spoke_pool_address.clone()),
+			data: ISpokePool::fillStatusesCall {
+				relayHash: relay_hash,
+			}
+			.abi_encode(),
+			value: U256::ZERO,
+			chain_id: order_data.destination_chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+		}))
+	}
+
+	/// Slow fill requests leave the relay open to fast fills.
+	fn is_filled(&self, _order: &Order, result: &[u8]) -> Result<bool, OrderError> {
+		let status = ISpokePool::fillStatusesCall::abi_decode_returns(result, true)
+			.map_err(|e| OrderError::ValidationFailed(format!("Invalid fill status: {}", e)))?;
+
+		Ok(status.status == U256::from(FILL_STATUS_FILLED))
+	}
+
+	/// Across repays relayers through relayer refund leaves.
+	fn requires_claim(&self) -> bool {
+		false
+	}
+
+	async fn generate_claim_transaction(
+		&self,
+		_order: &Order,
+		_fill_proof: &FillProof,
+		_claim_recipients: &HashMap<u64, Address>,
+	) -> Result<Transaction, OrderError> {
+		Err(OrderError::ValidationFailed(
+			"Across relayers are repaid through relayer refund leaves".to_string(),
+		))
+	}
+}
+
+/// Factory function to create an Across order implementation from configuration.
+///
+/// Required configuration parameters:
+/// - `spoke_pool_address`: Address of the SpokePool on destination chains
+/// - `solver_address`: Address of the solver, which relays and is repaid
+///
+/// Optional configuration parameters:
+/// - `fill_gas_limit`: Gas limit of fill transactions (default: 200000)
+pub fn create_order_impl(config: &toml::Value) -> Box<dyn OrderInterface> {
+	let spoke_pool = config
+		.get("spoke_pool_address")
+		.and_then(|v| v.as_str())
+		.expect("spoke_pool_address is required");
+
+	let solver_address = config
+		.get("solver_address")
+		.and_then(|v| v.as_str())
+		.expect("solver_address is required");
+
+	let fill_gas_limit = config
+		.get("fill_gas_limit")
+		.and_then(|v| v.as_integer())
+		.map(|limit| limit as u64)
+		.unwrap_or(DEFAULT_FILL_GAS_LIMIT);
+
+	Box::new(
+		AcrossOrderImpl::new(spoke_pool.to_string(), solver_address.to_string())
+			.with_fill_gas_limit(fill_gas_limit),
+	)
+}
@@ -0,0 +1,233 @@
+//! Composite execution strategy chaining several strategies.
+//!
+//! Each child strategy looks at an order from one angle, such as the time
+//! left before its deadline, the tokens it spends or its profitability. The
+//! composite strategy asks its children in the configured order and combines
+//! their decisions in one of two ways:
+//!
+//! - `all_must_pass`: the order is executed only if every child executes it,
+//!   with the gas price of the last child and the strictest of the other
+//!   parameters any child asked for. The first child to skip or defer the
+//!   order decides, so cheap filters belong first and the strategy pricing
+//!   the fill last.
+//! - `first_decision_wins`: the first child to execute or skip the order
+//!   decides. Children deferring the order leave the decision to the next
+//!   one; if all of them defer it, it is deferred for the shortest time any
+//!   of them asked for.
+
+use async_trait::async_trait;
+use solver_types::{
+	ConfigSchema, ExecutionContext, ExecutionDecision, ExecutionParams, Field, FieldType,
+	FillUrgency, Order, OrderOutcome, Schema,
+};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::ExecutionStrategy;
+
+/// Builds a child strategy from its strategy type and configuration.
+pub type ChildStrategyBuilder<'a> =
+	dyn Fn(&str, &toml::Value) -> Result<Box<dyn ExecutionStrategy>, String> + 'a;
+
+/// How the decisions of child strategies are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinationMode {
+	/// Every child must execute the order.
+	AllMustPass,
+	/// The first child executing or skipping the order decides.
+	FirstDecisionWins,
+}
+
+impl FromStr for CombinationMode {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"all_must_pass" => Ok(Self::AllMustPass),
+			"first_decision_wins" => Ok(Self::FirstDecisionWins),
+			other => Err(format!(
+				"Unknown combination mode '{}', expected all_must_pass or first_decision_wins",
+				other
+			)),
+		}
+	}
+}
+
+/// Execution strategy combining the decisions of child strategies.
+pub struct CompositeStrategy {
+	/// How child decisions are combined.
+	mode: CombinationMode,
+	/// Child strategies in evaluation order, with their strategy types.
+	strategies: Vec<(String, Box<dyn ExecutionStrategy>)>,
+}
+
+impl CompositeStrategy {
+	/// Creates a composite of the given strategies, evaluated in order.
+	pub fn new(
+		mode: CombinationMode,
+		strategies: Vec<(String, Box<dyn ExecutionStrategy>)>,
+	) -> Self {
+		Self { mode, strategies }
+	}
+}
+
+/// Merges the parameters of a child executing an order into those of the
+/// children before it.
+///
+/// The gas price of the later child wins, as the strategy pricing the fill
+/// comes last. Everything else a child asks for is kept: the higher priority
+/// fee, the more urgent handling and the smaller fill amount.
+fn merge_params(merged: ExecutionParams, child: ExecutionParams) -> ExecutionParams {
+	ExecutionParams {
+		gas_price: child.gas_price,
+		priority_fee: merged.priority_fee.max(child.priority_fee),
+		urgency: match (merged.urgency, child.urgency) {
+			(Some(a), Some(b)) => Some(FillUrgency {
+				priority_fee_bump_bps: a.priority_fee_bump_bps.max(b.priority_fee_bump_bps),
+				private: a.private || b.private,
+			}),
+			(a, b) => a.or(b),
+		},
+		fill_amount: match (merged.fill_amount, child.fill_amount) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		},
+	}
+}
+
+/// Configuration schema for CompositeStrategy.
+///
+/// Children are only checked for a type here; their configuration is
+/// validated by the schema of their own strategy when they are built.
+pub struct CompositeStrategySchema;
+
+impl ConfigSchema for CompositeStrategySchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![Field::new(
+				"strategies",
+				FieldType::Array(Box::new(FieldType::Table(Schema::new(
+					vec![Field::new("strategy_type", FieldType::String)],
+					vec![Field::new(
+						"config",
+						FieldType::Table(Schema::new(vec![], vec![])),
+					)],
+				)))),
+			)
+			.with_validator(|value| {
+				if value.as_array().unwrap().is_empty() {
+					return Err("At least one strategy is required".to_string());
+				}
+				Ok(())
+			})],
+			// Optional fields
+			vec![
+				Field::new("mode", FieldType::String).with_validator(|value| {
+					value
+						.as_str()
+						.unwrap()
+						.parse::<CombinationMode>()
+						.map(|_| ())
+				}),
+			],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl ExecutionStrategy for CompositeStrategy {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(CompositeStrategySchema)
+	}
+
+	async fn should_execute(&self, order: &Order, context: &ExecutionContext) -> ExecutionDecision {
+		let mut params = None;
+		let mut deferral: Option<Duration> = None;
+
+		for (strategy_type, strategy) in &self.strategies {
+			let decision = strategy.should_execute(order, context).await;
+			match (self.mode, decision) {
+				(CombinationMode::AllMustPass, ExecutionDecision::Execute(child_params)) => {
+					params = Some(match params {
+						Some(merged) => merge_params(merged, child_params),
+						None => child_params,
+					});
+				}
+				(CombinationMode::FirstDecisionWins, ExecutionDecision::Defer(duration)) => {
+					deferral = Some(deferral.map_or(duration, |shortest| shortest.min(duration)));
+				}
+				(_, decision) => {
+					tracing::debug!(
+						order_id = %order.id,
+						strategy = %strategy_type,
+						decision = ?decision,
+						"Strategy decided order"
+					);
+					return decision;
+				}
+			}
+		}
+
+		match (params, deferral) {
+			(Some(params), _) => ExecutionDecision::Execute(params),
+			(None, Some(duration)) => ExecutionDecision::Defer(duration),
+			(None, None) => ExecutionDecision::Skip("No execution strategy decided".to_string()),
+		}
+	}
+
+	async fn on_order_outcome(&self, order: &Order, outcome: &OrderOutcome) {
+		for (_, strategy) in &self.strategies {
+			strategy.on_order_outcome(order, outcome).await;
+		}
+	}
+}
+
+/// Factory function to create a composite strategy from configuration.
+///
+/// Child strategies are built by `build_child` from their strategy type and
+/// configuration, so that any registered strategy can be chained.
+///
+/// Configuration parameters:
+/// - `mode`: `all_must_pass` or `first_decision_wins` (default: all_must_pass)
+/// - `strategies`: Ordered tables of a `strategy_type` and, optionally, the
+///   child's `config`
+pub fn create_composite_strategy(
+	config: &toml::Value,
+	build_child: &ChildStrategyBuilder<'_>,
+) -> Result<Box<dyn ExecutionStrategy>, String> {
+	CompositeStrategySchema
+		.validate(config)
+		.map_err(|e| e.to_string())?;
+
+	let mode = config
+		.get("mode")
+		.and_then(|v| v.as_str())
+		.unwrap_or("all_must_pass")
+		.parse()?;
+
+	let mut strategies = Vec::new();
+	for child in config
+		.get("strategies")
+		.and_then(|v| v.as_array())
+		.into_iter()
+		.flatten()
+	{
+		let strategy_type = child
+			.get("strategy_type")
+			.and_then(|v| v.as_str())
+			.unwrap_or_default();
+		let child_config = child
+			.get("config")
+			.cloned()
+			.unwrap_or_else(|| toml::Value::Table(Default::default()));
+		strategies.push((
+			strategy_type.to_string(),
+			build_child(strategy_type, &child_config)?,
+		));
+	}
+
+	Ok(Box::new(CompositeStrategy::new(mode, strategies)))
+}
@@ -0,0 +1,292 @@
+//! Filtering execution strategies.
+//!
+//! These strategies only rule orders out, executing every other order with
+//! the context's gas price and no further adjustments. They are meant to be
+//! chained ahead of a pricing strategy in a composite strategy.
+
+use async_trait::async_trait;
+use solver_types::{
+	Address, ConfigSchema, ExecutionContext, ExecutionDecision, ExecutionParams, Field, FieldType,
+	Order, Schema,
+};
+use std::collections::{HashMap, HashSet};
+
+use crate::ExecutionStrategy;
+
+/// Returns the decision of a filter letting an order through.
+fn pass(context: &ExecutionContext) -> ExecutionDecision {
+	ExecutionDecision::Execute(ExecutionParams {
+		gas_price: context.gas_price,
+		priority_fee: None,
+		urgency: None,
+		fill_amount: None,
+	})
+}
+
+/// Strategy skipping orders too close to their deadline to be filled safely.
+pub struct ExpiryFilter {
+	/// Seconds that must be left before the deadline.
+	min_seconds_remaining: u64,
+}
+
+impl ExpiryFilter {
+	/// Creates a filter skipping orders with less than `min_seconds_remaining`
+	/// seconds left before their deadline.
+	pub fn new(min_seconds_remaining: u64) -> Self {
+		Self {
+			min_seconds_remaining,
+		}
+	}
+}
+
+/// Configuration schema for ExpiryFilter.
+pub struct ExpiryFilterSchema;
+
+impl ConfigSchema for ExpiryFilterSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![Field::new(
+				"min_seconds_remaining",
+				FieldType::Integer {
+					min: Some(0),
+					max: None,
+				},
+			)],
+			// Optional fields
+			vec![],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl ExecutionStrategy for ExpiryFilter {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(ExpiryFilterSchema)
+	}
+
+	async fn should_execute(
+		&self,
+		_order: &Order,
+		context: &ExecutionContext,
+	) -> ExecutionDecision {
+		// Orders without a known deadline cannot run out of time
+		let Some(deadline) = context.deadline else {
+			return pass(context);
+		};
+		let remaining = deadline.saturating_sub(context.timestamp);
+		if remaining < self.min_seconds_remaining {
+			return ExecutionDecision::Skip(format!(
+				"Only {}s left before the deadline, {}s required",
+				remaining, self.min_seconds_remaining
+			));
+		}
+		pass(context)
+	}
+}
+
+/// Strategy skipping orders whose outputs include tokens outside an
+/// allowlist.
+pub struct TokenAllowlist {
+	/// Allowed output tokens keyed by chain ID. Chains without an entry
+	/// allow no tokens.
+	tokens: HashMap<u64, HashSet<Address>>,
+}
+
+impl TokenAllowlist {
+	/// Creates a filter allowing only the given output tokens, keyed by chain
+	/// ID.
+	pub fn new(tokens: HashMap<u64, HashSet<Address>>) -> Self {
+		Self { tokens }
+	}
+}
+
+/// Parses a hex token address.
+fn parse_token(value: &str) -> Option<Address> {
+	let bytes = hex::decode(value.trim_start_matches("0x")).ok()?;
+	(bytes.len() == 20).then_some(Address(bytes))
+}
+
+/// Configuration schema for TokenAllowlist.
+pub struct TokenAllowlistSchema;
+
+impl ConfigSchema for TokenAllowlistSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("tokens", FieldType::Table(Schema::new(vec![], vec![]))).with_validator(
+					|value| {
+						for (chain_id, tokens) in value.as_table().unwrap() {
+							if chain_id.parse::<u64>().is_err() {
+								return Err(format!("Invalid chain ID '{}'", chain_id));
+							}
+							let Some(tokens) = tokens.as_array() else {
+								return Err(format!(
+									"Tokens of chain {} must be an array of addresses",
+									chain_id
+								));
+							};
+							for token in tokens {
+								if token.as_str().and_then(parse_token).is_none() {
+									return Err(format!(
+										"Invalid token address {} on chain {}",
+										token, chain_id
+									));
+								}
+							}
+						}
+						Ok(())
+					},
+				),
+			],
+			// Optional fields
+			vec![],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl ExecutionStrategy for TokenAllowlist {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(TokenAllowlistSchema)
+	}
+
+	async fn should_execute(
+		&self,
+		_order: &Order,
+		context: &ExecutionContext,
+	) -> ExecutionDecision {
+		for output in &context.remaining_outputs {
+			let allowed = self
+				.tokens
+				.get(&output.chain_id)
+				.is_some_and(|tokens| tokens.contains(&output.token));
+			if !allowed {
+				return ExecutionDecision::Skip(format!(
+					"Token 0x{} on chain {} is not allowlisted",
+					hex::encode(&output.token.0),
+					output.chain_id
+				));
+			}
+		}
+		pass(context)
+	}
+}
+
+/// Strategy skipping orders the validation pipeline scored as too risky.
+///
+/// Orders without a risk score, such as those decided while no risk
+/// validators are configured, are let through.
+pub struct RiskFilter {
+	/// Highest risk score of orders that are filled.
+	max_risk_score: u64,
+}
+
+impl RiskFilter {
+	/// Creates a filter skipping orders scored above `max_risk_score`.
+	pub fn new(max_risk_score: u64) -> Self {
+		Self { max_risk_score }
+	}
+}
+
+/// Configuration schema for RiskFilter.
+pub struct RiskFilterSchema;
+
+impl ConfigSchema for RiskFilterSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![Field::new(
+				"max_risk_score",
+				FieldType::Integer {
+					min: Some(0),
+					max: Some(100),
+				},
+			)],
+			// Optional fields
+			vec![],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl ExecutionStrategy for RiskFilter {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(RiskFilterSchema)
+	}
+
+	async fn should_execute(
+		&self,
+		_order: &Order,
+		context: &ExecutionContext,
+	) -> ExecutionDecision {
+		match context.risk_score {
+			Some(score) if u64::from(score) > self.max_risk_score => {
+				ExecutionDecision::Skip(format!(
+					"Risk score {} exceeds the maximum of {}",
+					score, self.max_risk_score
+				))
+			}
+			_ => pass(context),
+		}
+	}
+}
+
+/// Factory function to create an expiry filter from configuration.
+///
+/// Configuration parameters:
+/// - `min_seconds_remaining`: Seconds that must be left before an order's
+///   deadline for it to be filled
+pub fn create_expiry_filter(config: &toml::Value) -> Box<dyn ExecutionStrategy> {
+	let min_seconds_remaining = config
+		.get("min_seconds_remaining")
+		.and_then(|v| v.as_integer())
+		.unwrap_or(0) as u64;
+
+	Box::new(ExpiryFilter::new(min_seconds_remaining))
+}
+
+/// Factory function to create a token allowlist from configuration.
+///
+/// Configuration parameters:
+/// - `tokens`: Table of chain IDs to the addresses of the output tokens the
+///   solver fills orders with
+pub fn create_token_allowlist(config: &toml::Value) -> Box<dyn ExecutionStrategy> {
+	let tokens = config
+		.get("tokens")
+		.and_then(|v| v.as_table())
+		.into_iter()
+		.flatten()
+		.filter_map(|(chain_id, tokens)| {
+			let tokens = tokens
+				.as_array()?
+				.iter()
+				.filter_map(|token| token.as_str().and_then(parse_token))
+				.collect();
+			Some((chain_id.parse().ok()?, tokens))
+		})
+		.collect();
+
+	Box::new(TokenAllowlist::new(tokens))
+}
+
+/// Factory function to create a risk filter from configuration.
+///
+/// Configuration parameters:
+/// - `max_risk_score`: Highest risk score, from 0 to 100, of the orders
+///   that are filled
+pub fn create_risk_filter(config: &toml::Value) -> Box<dyn ExecutionStrategy> {
+	let max_risk_score = config
+		.get("max_risk_score")
+		.and_then(|v| v.as_integer())
+		.unwrap_or(100) as u64;
+
+	Box::new(RiskFilter::new(max_risk_score))
+}
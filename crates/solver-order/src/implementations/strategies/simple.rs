@@ -5,19 +5,38 @@
 use alloy_primitives::U256;
 use async_trait::async_trait;
 use solver_types::{
-	ConfigSchema, ExecutionContext, ExecutionDecision, ExecutionParams, Field, FieldType, Order,
-	Schema,
+	ConfigSchema, ExecutionContext, ExecutionDecision, ExecutionParams, Field, FieldType,
+	FillUrgency, Order, Schema,
 };
 
 use crate::ExecutionStrategy;
 
+/// A point of the urgency curve.
+#[derive(Debug, Clone, Copy)]
+pub struct UrgencyStep {
+	/// Seconds left before the deadline at which the step applies.
+	pub within_seconds: u64,
+	/// Handling of fills this close to their deadline.
+	pub urgency: FillUrgency,
+}
+
+/// Basis points in one whole.
+const BPS_DENOMINATOR: u64 = 10_000;
+
 /// Simple execution strategy that considers gas price limits.
 ///
 /// This strategy executes orders when gas prices are below a configured
-/// maximum, deferring execution when prices are too high.
+/// maximum, deferring execution when prices are too high. Orders close to
+/// their deadline are executed regardless, with fees escalated according to
+/// the urgency curve. Orders the solver lacks the inventory for are filled
+/// partially, when their standard allows it.
 pub struct SimpleStrategy {
 	/// Maximum gas price the solver is willing to pay.
 	max_gas_price: U256,
+	/// Urgency curve, most urgent step first.
+	urgency_curve: Vec<UrgencyStep>,
+	/// Smallest partial fill, in basis points of the remaining amount.
+	min_partial_fill_bps: u64,
 }
 
 impl SimpleStrategy {
@@ -25,7 +44,48 @@ impl SimpleStrategy {
 	pub fn new(max_gas_price_gwei: u64) -> Self {
 		Self {
 			max_gas_price: U256::from(max_gas_price_gwei) * U256::from(10u64.pow(9)),
+			urgency_curve: Vec::new(),
+			min_partial_fill_bps: 1_000,
+		}
+	}
+
+	/// Sets the smallest partial fill, in basis points of the remaining
+	/// amount of the order.
+	pub fn with_min_partial_fill_bps(mut self, min_partial_fill_bps: u64) -> Self {
+		self.min_partial_fill_bps = min_partial_fill_bps;
+		self
+	}
+
+	/// Escalates fills close to their deadline according to `curve`.
+	pub fn with_urgency_curve(mut self, mut curve: Vec<UrgencyStep>) -> Self {
+		curve.sort_by_key(|step| step.within_seconds);
+		self.urgency_curve = curve;
+		self
+	}
+
+	/// Returns the handling of a fill given the time left before its deadline.
+	fn urgency(&self, context: &ExecutionContext) -> Option<FillUrgency> {
+		let remaining = context.deadline?.saturating_sub(context.timestamp);
+		self.urgency_curve
+			.iter()
+			.find(|step| remaining <= step.within_seconds)
+			.map(|step| step.urgency)
+	}
+
+	/// Returns the amount to fill when the solver's inventory covers only
+	/// part of the order's remaining first output.
+	///
+	/// Returns `None` when the inventory covers the whole remainder, or when
+	/// the standard does not allow partial fills.
+	fn partial_amount(&self, context: &ExecutionContext) -> Option<U256> {
+		if !context.partial_fills {
+			return None;
 		}
+		let output = context.remaining_outputs.first()?;
+		let balance = context
+			.token_balances
+			.get(&(output.chain_id, output.token.clone()))?;
+		(*balance < output.amount).then_some(*balance)
 	}
 }
 
@@ -38,13 +98,44 @@ impl ConfigSchema for SimpleStrategySchema {
 			// Required fields
 			vec![],
 			// Optional fields
-			vec![Field::new(
-				"max_gas_price_gwei",
-				FieldType::Integer {
-					min: Some(1),
-					max: None,
-				},
-			)],
+			vec![
+				Field::new(
+					"max_gas_price_gwei",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+				Field::new(
+					"urgency_curve",
+					FieldType::Array(Box::new(FieldType::Table(Schema::new(
+						vec![
+							Field::new(
+								"within_seconds",
+								FieldType::Integer {
+									min: Some(1),
+									max: None,
+								},
+							),
+							Field::new(
+								"priority_fee_bump_bps",
+								FieldType::Integer {
+									min: Some(0),
+									max: None,
+								},
+							),
+						],
+						vec![Field::new("private", FieldType::Boolean)],
+					)))),
+				),
+				Field::new(
+					"min_partial_fill_bps",
+					FieldType::Integer {
+						min: Some(1),
+						max: Some(BPS_DENOMINATOR as i64),
+					},
+				),
+			],
 		);
 
 		schema.validate(config)
@@ -62,13 +153,27 @@ impl ExecutionStrategy for SimpleStrategy {
 		_order: &Order,
 		context: &ExecutionContext,
 	) -> ExecutionDecision {
-		if context.gas_price > self.max_gas_price {
+		// A deferred fill this close to its deadline would miss it
+		let urgency = self.urgency(context);
+		if urgency.is_none() && context.gas_price > self.max_gas_price {
 			return ExecutionDecision::Defer(std::time::Duration::from_secs(60));
 		}
 
+		// Fill what the inventory allows, unless too little to be worth the gas
+		let fill_amount = self.partial_amount(context);
+		if let (Some(amount), Some(output)) = (fill_amount, context.remaining_outputs.first()) {
+			let minimum =
+				output.amount * U256::from(self.min_partial_fill_bps) / U256::from(BPS_DENOMINATOR);
+			if amount.is_zero() || amount < minimum {
+				return ExecutionDecision::Defer(std::time::Duration::from_secs(60));
+			}
+		}
+
 		ExecutionDecision::Execute(ExecutionParams {
 			gas_price: context.gas_price,
 			priority_fee: Some(U256::from(2) * U256::from(10u64.pow(9))), // 2 gwei priority
+			urgency,
+			fill_amount,
 		})
 	}
 }
@@ -77,11 +182,50 @@ impl ExecutionStrategy for SimpleStrategy {
 ///
 /// Configuration parameters:
 /// - `max_gas_price_gwei`: Maximum gas price in gwei (default: 100)
+/// - `urgency_curve`: Steps of `within_seconds`, `priority_fee_bump_bps` and
+///   optionally `private`, escalating fills within that many seconds of
+///   their deadline (default: none)
+/// - `min_partial_fill_bps`: Smallest partial fill of an order the solver
+///   lacks the inventory for, in basis points of its remaining amount
+///   (default: 1000)
 pub fn create_strategy(config: &toml::Value) -> Box<dyn ExecutionStrategy> {
 	let max_gas_price = config
 		.get("max_gas_price_gwei")
 		.and_then(|v| v.as_integer())
 		.unwrap_or(100) as u64;
 
-	Box::new(SimpleStrategy::new(max_gas_price))
+	let urgency_curve = config
+		.get("urgency_curve")
+		.and_then(|v| v.as_array())
+		.map(|steps| {
+			steps
+				.iter()
+				.filter_map(|step| {
+					Some(UrgencyStep {
+						within_seconds: step.get("within_seconds")?.as_integer()? as u64,
+						urgency: FillUrgency {
+							priority_fee_bump_bps: step
+								.get("priority_fee_bump_bps")?
+								.as_integer()? as u64,
+							private: step
+								.get("private")
+								.and_then(|v| v.as_bool())
+								.unwrap_or(false),
+						},
+					})
+				})
+				.collect()
+		})
+		.unwrap_or_default();
+
+	let min_partial_fill_bps = config
+		.get("min_partial_fill_bps")
+		.and_then(|v| v.as_integer())
+		.unwrap_or(1_000) as u64;
+
+	Box::new(
+		SimpleStrategy::new(max_gas_price)
+			.with_urgency_curve(urgency_curve)
+			.with_min_partial_fill_bps(min_partial_fill_bps),
+	)
 }
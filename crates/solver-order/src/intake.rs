@@ -0,0 +1,97 @@
+//! Cheap intake filters applied before any RPC-consuming work.
+//!
+//! Validating an order is only the first step of handling it; building its
+//! execution context, checking liquidity and running the strategy all query
+//! chains. An attacker flooding the solver with tiny orders, or with many
+//! orders from the same user, could otherwise exhaust the solver's RPC budget
+//! and delay legitimate orders. These filters reject such orders using only
+//! the order data itself.
+
+use alloy_primitives::U256;
+use solver_types::{Address, OrderAssets, RateLimit, RateLimiter};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Rejects orders that are too small or arrive too often from one user.
+#[derive(Default)]
+pub struct IntakeFilter {
+	/// Minimum input amounts keyed by chain ID and token address.
+	/// Tokens without an entry are accepted in any amount.
+	min_input_amounts: HashMap<(u64, Address), U256>,
+	/// Orders accepted per user per minute, if limited.
+	max_orders_per_user_per_minute: Option<u32>,
+	/// Token buckets of the users that placed orders recently.
+	users: Mutex<RateLimiter<Address>>,
+}
+
+impl IntakeFilter {
+	/// Creates a new intake filter.
+	pub fn new(
+		min_input_amounts: HashMap<(u64, Address), U256>,
+		max_orders_per_user_per_minute: Option<u32>,
+	) -> Self {
+		Self {
+			min_input_amounts,
+			max_orders_per_user_per_minute,
+			users: Mutex::new(RateLimiter::new()),
+		}
+	}
+
+	/// Checks an order against the intake filters.
+	///
+	/// Orders that pass count towards their user's quota, so the rate cap is
+	/// checked last. Returns a description of the first filter the order fails.
+	pub fn check(&self, assets: &OrderAssets, user: Option<&Address>) -> Result<(), String> {
+		self.apply(assets, user, true)
+	}
+
+	/// Checks an order against the intake filters without counting it
+	/// towards its user's quota.
+	pub fn peek(&self, assets: &OrderAssets, user: Option<&Address>) -> Result<(), String> {
+		self.apply(assets, user, false)
+	}
+
+	/// Checks an order against the intake filters, counting it towards its
+	/// user's quota if `count` is set and it passes.
+	fn apply(
+		&self,
+		assets: &OrderAssets,
+		user: Option<&Address>,
+		count: bool,
+	) -> Result<(), String> {
+		for input in &assets.inputs {
+			let minimum = self
+				.min_input_amounts
+				.get(&(input.chain_id, input.token.clone()));
+			if let Some(minimum) = minimum.filter(|minimum| input.amount < **minimum) {
+				return Err(format!(
+					"input of {} of token 0x{} on chain {} is below the minimum of {}",
+					input.amount,
+					hex::encode(&input.token.0),
+					input.chain_id,
+					minimum
+				));
+			}
+		}
+
+		if let (Some(quota), Some(user)) = (self.max_orders_per_user_per_minute, user) {
+			let limit = RateLimit::per_minute(quota);
+			let now = Instant::now();
+			let mut users = self.users.lock().unwrap();
+			let allowed = match count {
+				true => users.try_take(user, limit, now).is_ok(),
+				false => users.wait_time(user, limit, now).is_zero(),
+			};
+			if !allowed {
+				return Err(format!(
+					"user 0x{} exceeded {} orders per minute",
+					hex::encode(&user.0),
+					quota
+				));
+			}
+		}
+
+		Ok(())
+	}
+}
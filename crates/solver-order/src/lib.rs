@@ -4,24 +4,44 @@
 //! generation for filling and claiming orders. It supports multiple order
 //! standards and pluggable execution strategies.
 
+use crate::classification::{OrderClass, OrderClassifier};
+use crate::features::StandardFeatures;
+use crate::intake::IntakeFilter;
+use crate::policy::IntentPolicy;
+use crate::stablecoin::StablecoinFastPath;
+use alloy_primitives::U256;
 use async_trait::async_trait;
 use solver_types::{
-	ConfigSchema, ExecutionContext, ExecutionDecision, ExecutionParams, FillProof, Intent, Order,
-	Transaction,
+	Address, Classify, ConfigSchema, ErrorCategory, ErrorClass, ExecutionContext,
+	ExecutionDecision, ExecutionParams, FillProof, FillRoute, Intent, Order, OrderAssets,
+	OrderOutcome, Transaction, TransactionReceipt,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
 /// Re-export implementations
 pub mod implementations {
+	#[cfg(feature = "testing")]
+	pub mod mock;
 	pub mod standards {
 		pub mod _7683;
+		pub mod across;
 	}
 	pub mod strategies {
+		pub mod composite;
+		pub mod filters;
 		pub mod simple;
 	}
 }
 
+pub mod classification;
+pub mod features;
+pub mod intake;
+pub mod policy;
+pub mod signatures;
+pub mod stablecoin;
+
 /// Errors that can occur during order processing operations.
 #[derive(Debug, Error)]
 pub enum OrderError {
@@ -34,6 +54,21 @@ pub enum OrderError {
 	/// Error that occurs when the order cannot be satisfied given current conditions.
 	#[error("Cannot satisfy order")]
 	CannotSatisfyOrder,
+	/// Error that occurs when the order breaks the operator's intent policy.
+	#[error("Policy violation: {0}")]
+	PolicyViolation(String),
+}
+
+impl Classify for OrderError {
+	fn class(&self) -> ErrorClass {
+		match self {
+			// The solver's balance may be replenished before the order expires
+			Self::InsufficientBalance => ErrorClass::transient(ErrorCategory::Validation),
+			Self::ValidationFailed(_) | Self::CannotSatisfyOrder | Self::PolicyViolation(_) => {
+				ErrorClass::permanent(ErrorCategory::Validation)
+			}
+		}
+	}
 }
 
 /// Trait defining the interface for order standard implementations.
@@ -56,24 +91,161 @@ pub trait OrderInterface: Send + Sync {
 	/// is well-formed and can be processed by the solver.
 	async fn validate_intent(&self, intent: &Intent) -> Result<Order, OrderError>;
 
+	/// Returns whether the order was opened off-chain with a user signature.
+	///
+	/// Gasless orders are opened by the solver on the user's behalf. The
+	/// default implementation reports every order as opened on-chain.
+	fn is_gasless(&self, _order: &Order) -> bool {
+		false
+	}
+
+	/// Returns the address of the user who created the order, if the standard
+	/// identifies one.
+	///
+	/// Used to rate limit orders per user. The default implementation
+	/// identifies no user.
+	fn user(&self, _order: &Order) -> Option<Address> {
+		None
+	}
+
+	/// Returns the addresses the order pays out to.
+	///
+	/// Checked against the denylisted addresses of the intent policy. The
+	/// default implementation reports none.
+	fn recipients(&self, _order: &Order) -> Vec<Address> {
+		Vec::new()
+	}
+
+	/// Returns whether any output of the order executes a call at its recipient.
+	///
+	/// The default implementation reports plain transfers only.
+	fn has_output_calls(&self, _order: &Order) -> bool {
+		false
+	}
+
+	/// Extracts the input and output assets of an order.
+	///
+	/// Gives standard-agnostic components (risk checks, liquidity checks,
+	/// accounting) access to the tokens an order moves without parsing
+	/// standard-specific order data.
+	fn order_assets(&self, order: &Order) -> Result<OrderAssets, OrderError>;
+
+	/// Returns the unix timestamp after which the order can no longer be filled.
+	fn fill_deadline(&self, order: &Order) -> Result<u64, OrderError>;
+
+	/// Returns whether orders of this standard can be filled in several
+	/// transactions, each delivering part of the outputs.
+	///
+	/// The default implementation only fills orders in full.
+	fn supports_partial_fill(&self) -> bool {
+		false
+	}
+
+	/// Points an order at one of its [routes](Order::routes), so that it is
+	/// filled on the route's chain.
+	///
+	/// The default implementation supports no routes.
+	fn apply_route(&self, _order: &mut Order, _route: &FillRoute) -> Result<(), OrderError> {
+		Err(OrderError::ValidationFailed(
+			"Standard does not support fill routes".to_string(),
+		))
+	}
+
 	/// Generates a transaction to fill the given order.
 	///
 	/// Creates a blockchain transaction that will execute the order fill
-	/// according to the standard's requirements.
+	/// according to the standard's requirements. `fill_amount` is the amount
+	/// of the order's first output to deliver, with other outputs scaled pro
+	/// rata. It is the full amount unless the standard supports partial fills.
 	async fn generate_fill_transaction(
 		&self,
 		order: &Order,
 		params: &ExecutionParams,
+		fill_amount: U256,
 	) -> Result<Transaction, OrderError>;
 
+	/// Returns whether the settler can fill several orders in one transaction.
+	///
+	/// The default implementation fills every order on its own.
+	fn supports_fill_bundles(&self) -> bool {
+		false
+	}
+
+	/// Generates a single transaction filling all of the given orders.
+	///
+	/// The orders share a destination chain. A settler that cannot fill one of
+	/// them should still fill the rest, so that one lost race does not revert
+	/// the whole bundle.
+	async fn generate_bundle_fill_transaction(
+		&self,
+		_fills: &[(&Order, &ExecutionParams)],
+	) -> Result<Transaction, OrderError> {
+		Err(OrderError::ValidationFailed(
+			"Fill bundles are not supported".to_string(),
+		))
+	}
+
+	/// Determines which orders of a confirmed bundle were filled.
+	///
+	/// Returns one flag per order, in the order the bundle was generated in.
+	/// The default implementation treats every order as filled if the
+	/// transaction succeeded.
+	fn bundle_fill_results(&self, orders: &[&Order], receipt: &TransactionReceipt) -> Vec<bool> {
+		vec![receipt.success; orders.len()]
+	}
+
+	/// Generates a read-only call asking the destination settler whether an
+	/// order was already filled.
+	///
+	/// Lets the solver notice that another solver won the race for an order
+	/// before paying gas for a fill that can only fail. The default
+	/// implementation returns `None` for standards whose settlers cannot be
+	/// asked.
+	fn fill_check_call(&self, _order: &Order) -> Result<Option<Transaction>, OrderError> {
+		Ok(None)
+	}
+
+	/// Decodes the result of the [fill check call](Self::fill_check_call),
+	/// returning whether the order was already filled.
+	fn is_filled(&self, _order: &Order, _result: &[u8]) -> Result<bool, OrderError> {
+		Ok(false)
+	}
+
+	/// Returns whether this standard allows claimed rewards to be paid out
+	/// to an address other than the solver.
+	///
+	/// Implementations that return false ignore configured claim recipients.
+	fn supports_claim_recipient(&self) -> bool {
+		false
+	}
+
+	/// Returns whether the solver submits a transaction to claim its rewards.
+	///
+	/// Standards whose protocol pays solvers back on its own return false;
+	/// their orders complete once settlement reports the claim ready, and
+	/// claim transactions are never generated for them.
+	fn requires_claim(&self) -> bool {
+		true
+	}
+
+	/// Returns the unix timestamp after which the solver can no longer claim
+	/// the order's rewards, if the standard bounds the claim window.
+	///
+	/// The default implementation reports an unbounded claim window.
+	fn claim_deadline(&self, _order: &Order) -> Option<u64> {
+		None
+	}
+
 	/// Generates a transaction to claim rewards for a filled order.
 	///
 	/// Creates a blockchain transaction that will claim any rewards or fees
-	/// owed to the solver for successfully filling the order.
+	/// owed to the solver for successfully filling the order. When a recipient
+	/// is configured for the claim chain, rewards are sent to that address.
 	async fn generate_claim_transaction(
 		&self,
 		order: &Order,
 		fill_proof: &FillProof,
+		claim_recipients: &HashMap<u64, Address>,
 	) -> Result<Transaction, OrderError>;
 }
 
@@ -95,6 +267,13 @@ pub trait ExecutionStrategy: Send + Sync {
 	/// Returns an ExecutionDecision indicating whether to execute now,
 	/// skip the order, or defer execution to a later time.
 	async fn should_execute(&self, order: &Order, context: &ExecutionContext) -> ExecutionDecision;
+
+	/// Receives the realized outcome of an order this strategy decided to execute.
+	///
+	/// Called by the engine once an order completes or fails, allowing adaptive
+	/// strategies to learn from realized profit and latency. The default
+	/// implementation ignores the feedback.
+	async fn on_order_outcome(&self, _order: &Order, _outcome: &OrderOutcome) {}
 }
 
 /// Service that manages order processing with multiple implementations and strategies.
@@ -102,10 +281,31 @@ pub trait ExecutionStrategy: Send + Sync {
 /// The OrderService coordinates between different order standard implementations
 /// and applies the configured execution strategy to make filling decisions.
 pub struct OrderService {
-	/// Map of standard names to their implementations.
+	/// Map of implementation names to their implementations.
+	/// Intents are routed to the implementation named after their standard
+	/// unless a settler route applies.
 	implementations: HashMap<String, Box<dyn OrderInterface>>,
+	/// Map of lowercase settler addresses to the implementation handling
+	/// intents opened on that settler.
+	settler_routes: HashMap<String, String>,
 	/// The execution strategy to use for making filling decisions.
-	strategy: Box<dyn ExecutionStrategy>,
+	/// Swapped when the strategy configuration is reloaded.
+	strategy: RwLock<Arc<dyn ExecutionStrategy>>,
+	/// Strategies that replace the default strategy for specific order classes.
+	class_strategies: RwLock<HashMap<OrderClass, Arc<dyn ExecutionStrategy>>>,
+	/// Map of chain IDs to the addresses that claimed rewards are sent to.
+	claim_recipients: HashMap<u64, Address>,
+	/// Optional fast path for orders that only move equivalent stablecoins.
+	stablecoin_fast_path: Option<StablecoinFastPath>,
+	/// Risky capabilities enabled per order standard.
+	/// Standards without an entry have every capability disabled.
+	features: HashMap<String, StandardFeatures>,
+	/// Tokens known to charge a fee on transfer, keyed by chain ID and address.
+	fee_on_transfer_tokens: HashSet<(u64, Address)>,
+	/// Cheap filters rejecting spam before any RPC-consuming work.
+	intake: IntakeFilter,
+	/// Chains, tokens and addresses orders may touch.
+	policy: IntentPolicy,
 }
 
 impl OrderService {
@@ -116,49 +316,402 @@ impl OrderService {
 	) -> Self {
 		Self {
 			implementations,
-			strategy,
+			strategy: RwLock::new(Arc::from(strategy)),
+			class_strategies: RwLock::new(HashMap::new()),
+			settler_routes: HashMap::new(),
+			claim_recipients: HashMap::new(),
+			stablecoin_fast_path: None,
+			features: HashMap::new(),
+			fee_on_transfer_tokens: HashSet::new(),
+			intake: IntakeFilter::default(),
+			policy: IntentPolicy::default(),
 		}
 	}
 
+	/// Sets the strategy used for orders of the given class.
+	///
+	/// When an order belongs to several classes with a configured strategy,
+	/// the most specific class according to `OrderClass::PRECEDENCE` wins.
+	pub fn with_class_strategy(
+		mut self,
+		class: OrderClass,
+		strategy: Box<dyn ExecutionStrategy>,
+	) -> Self {
+		self.class_strategies
+			.get_mut()
+			.unwrap()
+			.insert(class, Arc::from(strategy));
+		self
+	}
+
+	/// Replaces the default and class-specific strategies of a running
+	/// service.
+	///
+	/// Decisions already in progress finish with the strategies they started
+	/// with.
+	pub fn replace_strategies(
+		&self,
+		strategy: Box<dyn ExecutionStrategy>,
+		class_strategies: HashMap<OrderClass, Box<dyn ExecutionStrategy>>,
+	) {
+		*self.strategy.write().unwrap() = Arc::from(strategy);
+		*self.class_strategies.write().unwrap() = class_strategies
+			.into_iter()
+			.map(|(class, strategy)| (class, Arc::from(strategy)))
+			.collect();
+	}
+
+	/// Routes intents opened on specific settler contracts to named implementations.
+	///
+	/// Lets several implementations of the same standard coexist, e.g. for
+	/// different settler versions.
+	pub fn with_settler_routes(mut self, settler_routes: HashMap<String, String>) -> Self {
+		self.settler_routes = settler_routes
+			.into_iter()
+			.map(|(settler, implementation)| (settler.to_lowercase(), implementation))
+			.collect();
+		self
+	}
+
+	/// Sets the per-chain addresses that claimed rewards are sent to.
+	pub fn with_claim_recipients(mut self, claim_recipients: HashMap<u64, Address>) -> Self {
+		self.claim_recipients = claim_recipients;
+		self
+	}
+
+	/// Enables the stablecoin-equivalence fast path.
+	pub fn with_stablecoin_fast_path(mut self, fast_path: StablecoinFastPath) -> Self {
+		self.stablecoin_fast_path = Some(fast_path);
+		self
+	}
+
+	/// Sets the risky capabilities enabled per order standard and the tokens
+	/// known to charge a fee on transfer.
+	pub fn with_features(
+		mut self,
+		features: HashMap<String, StandardFeatures>,
+		fee_on_transfer_tokens: HashSet<(u64, Address)>,
+	) -> Self {
+		self.features = features;
+		self.fee_on_transfer_tokens = fee_on_transfer_tokens;
+		self
+	}
+
+	/// Sets the filters rejecting spam orders during validation.
+	pub fn with_intake_filter(mut self, intake: IntakeFilter) -> Self {
+		self.intake = intake;
+		self
+	}
+
+	/// Sets the policy deciding which chains, tokens and addresses orders
+	/// may touch.
+	pub fn with_policy(mut self, policy: IntentPolicy) -> Self {
+		self.policy = policy;
+		self
+	}
+
+	/// Returns the intent policy, whose remote lists are refreshed through it.
+	pub fn policy(&self) -> &IntentPolicy {
+		&self.policy
+	}
+
+	/// Returns the names of order implementations that cannot pay claims out
+	/// to a configured recipient.
+	pub fn implementations_without_claim_recipient(&self) -> Vec<String> {
+		self.implementations
+			.iter()
+			.filter(|(_, implementation)| !implementation.supports_claim_recipient())
+			.map(|(name, _)| name.clone())
+			.collect()
+	}
+
 	/// Validates an intent using the appropriate standard implementation.
 	///
-	/// Selects the implementation based on the intent's standard field
-	/// and delegates validation to that implementation.
+	/// Selects the implementation routed to the intent's settler if one is
+	/// configured, or otherwise the implementation named after the intent's
+	/// standard, and delegates validation to it. The selected implementation
+	/// is recorded on routed orders so later steps use the same one.
+	///
+	/// Orders breaking the intent policy or needing a capability that is not
+	/// enabled for their standard are rejected regardless of the
+	/// implementation, as are orders failing the intake filters. None of these
+	/// checks query a chain.
 	pub async fn validate_intent(&self, intent: &Intent) -> Result<Order, OrderError> {
-		let implementation = self.implementations.get(&intent.standard).ok_or_else(|| {
+		self.validate(intent, true).await
+	}
+
+	/// Validates an intent like [`validate_intent`](Self::validate_intent),
+	/// without counting it towards its user's intake quota.
+	///
+	/// Used to preview how an intent would be handled.
+	pub async fn preview_intent(&self, intent: &Intent) -> Result<Order, OrderError> {
+		self.validate(intent, false).await
+	}
+
+	/// Validates an intent, counting it towards its user's intake quota if
+	/// `count` is set.
+	async fn validate(&self, intent: &Intent, count: bool) -> Result<Order, OrderError> {
+		let (routed, implementation) = self.implementation_for_intent(intent)?;
+
+		let mut order = implementation.validate_intent(intent).await?;
+		order.implementation = routed.cloned();
+
+		let features = self
+			.features
+			.get(&order.standard)
+			.copied()
+			.unwrap_or_default();
+		let assets = implementation.order_assets(&order)?;
+		let user = implementation.user(&order);
+		self.policy
+			.check(&assets, user.as_ref(), &implementation.recipients(&order))
+			.map_err(OrderError::PolicyViolation)?;
+
+		features
+			.check(
+				&assets,
+				implementation.has_output_calls(&order),
+				&self.fee_on_transfer_tokens,
+			)
+			.map_err(|reason| {
+				OrderError::ValidationFailed(format!("{} for {}", reason, order.standard))
+			})?;
+
+		let intake = if count {
+			self.intake.check(&assets, user.as_ref())
+		} else {
+			self.intake.peek(&assets, user.as_ref())
+		};
+		intake.map_err(OrderError::ValidationFailed)?;
+
+		Ok(order)
+	}
+
+	/// Checks an intent against its standard alone, without the capability
+	/// and intake checks of full validation.
+	///
+	/// Used to vet intents submitted directly to the solver, such as signature
+	/// and deadlines, before they are queued. Intake limits are left to the
+	/// validation the queued intent goes through, so it is not counted twice.
+	pub async fn verify_intent(&self, intent: &Intent) -> Result<(), OrderError> {
+		let (_, implementation) = self.implementation_for_intent(intent)?;
+		implementation.validate_intent(intent).await.map(|_| ())
+	}
+
+	/// Returns the implementation an intent is routed to, along with the
+	/// route's implementation name if it was routed by settler.
+	fn implementation_for_intent(
+		&self,
+		intent: &Intent,
+	) -> Result<(Option<&String>, &dyn OrderInterface), OrderError> {
+		let routed = intent
+			.metadata
+			.settler
+			.as_ref()
+			.and_then(|settler| self.settler_routes.get(&settler.to_lowercase()));
+		let name = routed.unwrap_or(&intent.standard);
+		let implementation = self.implementations.get(name).ok_or_else(|| {
 			OrderError::ValidationFailed(format!("Unknown standard: {}", intent.standard))
 		})?;
+		Ok((routed, implementation.as_ref()))
+	}
+
+	/// Returns the implementation responsible for an order.
+	fn implementation_for(&self, order: &Order) -> Result<&dyn OrderInterface, OrderError> {
+		let name = order.implementation.as_ref().unwrap_or(&order.standard);
+		self.implementations
+			.get(name)
+			.map(|implementation| implementation.as_ref())
+			.ok_or_else(|| OrderError::ValidationFailed("Unknown standard".into()))
+	}
+
+	/// Classifies an order using the appropriate standard implementation.
+	pub fn classify(&self, order: &Order) -> Result<HashSet<OrderClass>, OrderError> {
+		let implementation = self.implementation_for(order)?;
+
+		let assets = implementation.order_assets(order)?;
+		Ok(OrderClassifier::classify(
+			&assets,
+			implementation.is_gasless(order),
+		))
+	}
+
+	/// Selects the strategy responsible for an order.
+	///
+	/// Falls back to the default strategy when the order cannot be classified
+	/// or none of its classes has a dedicated strategy.
+	fn strategy_for(&self, order: &Order) -> Arc<dyn ExecutionStrategy> {
+		let default = || self.strategy.read().unwrap().clone();
+		let class_strategies = self.class_strategies.read().unwrap();
+		if class_strategies.is_empty() {
+			return default();
+		}
+
+		let Ok(classes) = self.classify(order) else {
+			return default();
+		};
 
-		implementation.validate_intent(intent).await
+		OrderClass::PRECEDENCE
+			.iter()
+			.filter(|class| classes.contains(class))
+			.find_map(|class| class_strategies.get(class))
+			.cloned()
+			.unwrap_or_else(default)
 	}
 
-	/// Determines whether an order should be executed using the configured strategy.
+	/// Determines whether an order should be executed using the strategy for its class.
 	pub async fn should_execute(
 		&self,
 		order: &Order,
 		context: &ExecutionContext,
 	) -> ExecutionDecision {
-		self.strategy.should_execute(order, context).await
+		self.strategy_for(order)
+			.should_execute(order, context)
+			.await
+	}
+
+	/// Decides an order on the stablecoin fast path when it applies.
+	///
+	/// Returns `None` when the fast path is disabled or the order moves tokens
+	/// outside a single stablecoin group; callers then fall back to the
+	/// regular checks and `should_execute`.
+	pub fn evaluate_fast_path(
+		&self,
+		order: &Order,
+		context: &ExecutionContext,
+	) -> Option<ExecutionDecision> {
+		let fast_path = self.stablecoin_fast_path.as_ref()?;
+		let assets = self.order_assets(order).ok()?;
+		fast_path.evaluate(&assets, context)
+	}
+
+	/// Forwards the realized outcome of an order to the strategy for its class.
+	pub async fn on_order_outcome(&self, order: &Order, outcome: &OrderOutcome) {
+		self.strategy_for(order)
+			.on_order_outcome(order, outcome)
+			.await
 	}
 
-	/// Generates a fill transaction for the given order.
+	/// Extracts the input and output assets of an order.
+	///
+	/// Uses the appropriate standard implementation to parse the order data.
+	pub fn order_assets(&self, order: &Order) -> Result<OrderAssets, OrderError> {
+		let implementation = self.implementation_for(order)?;
+
+		implementation.order_assets(order)
+	}
+
+	/// Returns the user who created an order, if its standard identifies one.
+	pub fn user(&self, order: &Order) -> Option<Address> {
+		self.implementation_for(order).ok()?.user(order)
+	}
+
+	/// Returns the fill deadline of an order.
+	///
+	/// Uses the appropriate standard implementation to parse the order data.
+	pub fn fill_deadline(&self, order: &Order) -> Result<u64, OrderError> {
+		let implementation = self.implementation_for(order)?;
+
+		implementation.fill_deadline(order)
+	}
+
+	/// Returns whether an order can be filled in several transactions.
+	pub fn supports_partial_fill(&self, order: &Order) -> bool {
+		self.implementation_for(order)
+			.is_ok_and(|implementation| implementation.supports_partial_fill())
+	}
+
+	/// Points an order at one of its routes.
+	pub fn apply_route(&self, order: &mut Order, route: &FillRoute) -> Result<(), OrderError> {
+		let implementation = self.implementation_for(order)?;
+
+		implementation.apply_route(order, route)
+	}
+
+	/// Generates a fill transaction delivering `fill_amount` of the order's
+	/// first output.
 	///
 	/// Uses the appropriate standard implementation to create the transaction.
 	pub async fn generate_fill_transaction(
 		&self,
 		order: &Order,
 		params: &ExecutionParams,
+		fill_amount: U256,
 	) -> Result<Transaction, OrderError> {
-		let implementation = self
-			.implementations
-			.get(&order.standard)
-			.ok_or_else(|| OrderError::ValidationFailed("Unknown standard".into()))?;
+		let implementation = self.implementation_for(order)?;
 
 		implementation
-			.generate_fill_transaction(order, params)
+			.generate_fill_transaction(order, params, fill_amount)
 			.await
 	}
 
+	/// Returns whether an order can be filled together with others.
+	pub fn supports_fill_bundles(&self, order: &Order) -> bool {
+		self.implementation_for(order)
+			.is_ok_and(|implementation| implementation.supports_fill_bundles())
+	}
+
+	/// Generates a single transaction filling several orders.
+	///
+	/// All orders must be handled by the same implementation.
+	pub async fn generate_bundle_fill_transaction(
+		&self,
+		fills: &[(&Order, &ExecutionParams)],
+	) -> Result<Transaction, OrderError> {
+		let (first, _) = fills
+			.first()
+			.ok_or_else(|| OrderError::ValidationFailed("Empty fill bundle".into()))?;
+		let name = first.implementation.as_ref().unwrap_or(&first.standard);
+		if fills
+			.iter()
+			.any(|(order, _)| order.implementation.as_ref().unwrap_or(&order.standard) != name)
+		{
+			return Err(OrderError::ValidationFailed(
+				"Fill bundle mixes order implementations".into(),
+			));
+		}
+		let implementation = self.implementation_for(first)?;
+
+		implementation.generate_bundle_fill_transaction(fills).await
+	}
+
+	/// Determines which orders of a confirmed bundle were filled.
+	///
+	/// Orders whose implementation is unknown are reported as not filled.
+	pub fn bundle_fill_results(
+		&self,
+		orders: &[&Order],
+		receipt: &TransactionReceipt,
+	) -> Vec<bool> {
+		match orders.first().map(|order| self.implementation_for(order)) {
+			Some(Ok(implementation)) => implementation.bundle_fill_results(orders, receipt),
+			_ => vec![false; orders.len()],
+		}
+	}
+
+	/// Generates a read-only call checking whether an order was already
+	/// filled, if its standard supports it.
+	pub fn fill_check_call(&self, order: &Order) -> Result<Option<Transaction>, OrderError> {
+		self.implementation_for(order)?.fill_check_call(order)
+	}
+
+	/// Decodes the result of an order's fill check call.
+	pub fn is_filled(&self, order: &Order, result: &[u8]) -> Result<bool, OrderError> {
+		self.implementation_for(order)?.is_filled(order, result)
+	}
+
+	/// Returns whether a claim transaction must be submitted for an order.
+	pub fn requires_claim(&self, order: &Order) -> bool {
+		self.implementation_for(order)
+			.map_or(true, |implementation| implementation.requires_claim())
+	}
+
+	/// Returns the time after which an order can no longer be claimed, if
+	/// its standard bounds the claim window.
+	pub fn claim_deadline(&self, order: &Order) -> Option<u64> {
+		self.implementation_for(order).ok()?.claim_deadline(order)
+	}
+
 	/// Generates a claim transaction for a filled order.
 	///
 	/// Uses the appropriate standard implementation to create the transaction.
@@ -167,13 +720,10 @@ impl OrderService {
 		order: &Order,
 		proof: &FillProof,
 	) -> Result<Transaction, OrderError> {
-		let implementation = self
-			.implementations
-			.get(&order.standard)
-			.ok_or_else(|| OrderError::ValidationFailed("Unknown standard".into()))?;
+		let implementation = self.implementation_for(order)?;
 
 		implementation
-			.generate_claim_transaction(order, proof)
+			.generate_claim_transaction(order, proof, &self.claim_recipients)
 			.await
 	}
 }
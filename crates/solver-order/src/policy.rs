@@ -0,0 +1,207 @@
+//! Policy rejecting intents by chain, token and address.
+//!
+//! Operators may only serve some chains, refuse to handle certain tokens or
+//! be bound to stay clear of sanctioned addresses. The policy checks the
+//! chains and tokens an order moves, and the addresses of its user and
+//! recipients, against configured lists. Like the intake filters it only
+//! looks at the order data, so rejected orders cost no RPC requests.
+//!
+//! Denied tokens and addresses can also be published at a remote URL, such
+//! as a sanctions list maintained by a compliance provider. The remote lists
+//! add to the configured ones and are refreshed periodically; if a refresh
+//! fails, the lists fetched last stay in force.
+
+use serde::Deserialize;
+use solver_types::{Address, OrderAssets};
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Time the remote list has to answer a refresh.
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Chains, tokens and addresses an order is checked against.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyLists {
+	/// Chains orders may touch. Every chain is allowed if empty.
+	pub allowed_chains: HashSet<u64>,
+	/// Chains orders may not touch.
+	pub denied_chains: HashSet<u64>,
+	/// Tokens orders may move, keyed by chain ID. Chains without an entry
+	/// allow any token.
+	pub allowed_tokens: HashMap<u64, HashSet<Address>>,
+	/// Tokens orders may not move, keyed by chain ID and address.
+	pub denied_tokens: HashSet<(u64, Address)>,
+	/// Addresses orders may not come from or pay out to.
+	pub denied_addresses: HashSet<Address>,
+}
+
+/// Denylists as published at the remote URL.
+#[derive(Debug, Deserialize)]
+struct RemoteLists {
+	/// Map of chain IDs to denied token addresses.
+	#[serde(default)]
+	denied_tokens: HashMap<String, Vec<String>>,
+	/// Denied addresses.
+	#[serde(default)]
+	denied_addresses: Vec<String>,
+}
+
+/// Denylists fetched from the remote URL.
+#[derive(Debug, Default)]
+struct RemoteDenylist {
+	/// Denied tokens keyed by chain ID and address.
+	tokens: HashSet<(u64, Address)>,
+	/// Denied addresses.
+	addresses: HashSet<Address>,
+}
+
+/// Parses a hex address, ignoring its case and `0x` prefix.
+fn parse_address(address: &str) -> Option<Address> {
+	hex::decode(address.trim().trim_start_matches("0x"))
+		.ok()
+		.map(Address)
+}
+
+/// Rejects orders touching chains, tokens or addresses the operator does not
+/// deal with.
+#[derive(Default)]
+pub struct IntentPolicy {
+	/// Configured lists.
+	lists: PolicyLists,
+	/// URL denylists are fetched from, if any.
+	remote_url: Option<String>,
+	/// Denylists fetched last from the remote URL.
+	remote: RwLock<RemoteDenylist>,
+	/// HTTP client for remote list requests.
+	client: reqwest::Client,
+}
+
+impl IntentPolicy {
+	/// Creates a policy enforcing the given lists.
+	pub fn new(lists: PolicyLists) -> Self {
+		Self {
+			lists,
+			..Default::default()
+		}
+	}
+
+	/// Adds the denylists published at `url`, fetched on each
+	/// [`refresh`](Self::refresh).
+	pub fn with_remote_list(mut self, url: String) -> Self {
+		self.remote_url = Some(url);
+		self
+	}
+
+	/// Returns the URL denylists are fetched from, if any.
+	pub fn remote_url(&self) -> Option<&str> {
+		self.remote_url.as_deref()
+	}
+
+	/// Checks an order's assets, user and recipients against the policy.
+	///
+	/// Returns a description of the first rule the order breaks.
+	pub fn check(
+		&self,
+		assets: &OrderAssets,
+		user: Option<&Address>,
+		recipients: &[Address],
+	) -> Result<(), String> {
+		let remote = self.remote.read().unwrap();
+
+		for (side, asset) in assets
+			.inputs
+			.iter()
+			.map(|input| ("input", input))
+			.chain(assets.outputs.iter().map(|output| ("output", output)))
+		{
+			let chain_id = asset.chain_id;
+			if self.lists.denied_chains.contains(&chain_id)
+				|| (!self.lists.allowed_chains.is_empty()
+					&& !self.lists.allowed_chains.contains(&chain_id))
+			{
+				return Err(format!("chain {} is not supported", chain_id));
+			}
+
+			let token = (chain_id, asset.token.clone());
+			let allowed = self
+				.lists
+				.allowed_tokens
+				.get(&chain_id)
+				.is_none_or(|tokens| tokens.contains(&asset.token));
+			if !allowed
+				|| self.lists.denied_tokens.contains(&token)
+				|| remote.tokens.contains(&token)
+			{
+				return Err(format!(
+					"{} token 0x{} on chain {} is not allowed",
+					side,
+					hex::encode(&asset.token.0),
+					chain_id
+				));
+			}
+		}
+
+		for (role, address) in user
+			.map(|user| ("user", user))
+			.into_iter()
+			.chain(recipients.iter().map(|recipient| ("recipient", recipient)))
+		{
+			if self.lists.denied_addresses.contains(address) || remote.addresses.contains(address) {
+				return Err(format!(
+					"{} 0x{} is denylisted",
+					role,
+					hex::encode(&address.0)
+				));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Fetches the denylists from the remote URL, replacing those fetched
+	/// before.
+	///
+	/// Does nothing without a remote URL. Returns the number of denied tokens
+	/// and addresses fetched.
+	pub async fn refresh(&self) -> Result<usize, String> {
+		let Some(url) = &self.remote_url else {
+			return Ok(0);
+		};
+
+		let body = self
+			.client
+			.get(url)
+			.timeout(REMOTE_TIMEOUT)
+			.send()
+			.await
+			.and_then(|response| response.error_for_status())
+			.map_err(|e| format!("Failed to fetch policy lists: {}", e))?
+			.text()
+			.await
+			.map_err(|e| format!("Failed to fetch policy lists: {}", e))?;
+		let lists: RemoteLists =
+			serde_json::from_str(&body).map_err(|e| format!("Invalid policy lists: {}", e))?;
+
+		let mut denylist = RemoteDenylist::default();
+		for (chain_id, tokens) in &lists.denied_tokens {
+			let chain_id = chain_id
+				.parse::<u64>()
+				.map_err(|_| format!("Invalid chain ID '{}' in policy lists", chain_id))?;
+			for token in tokens {
+				let token = parse_address(token)
+					.ok_or_else(|| format!("Invalid token '{}' in policy lists", token))?;
+				denylist.tokens.insert((chain_id, token));
+			}
+		}
+		for address in &lists.denied_addresses {
+			let address = parse_address(address)
+				.ok_or_else(|| format!("Invalid address '{}' in policy lists", address))?;
+			denylist.addresses.insert(address);
+		}
+
+		let entries = denylist.tokens.len() + denylist.addresses.len();
+		*self.remote.write().unwrap() = denylist;
+		Ok(entries)
+	}
+}
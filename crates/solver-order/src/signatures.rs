@@ -0,0 +1,52 @@
+//! Worker pool for CPU-bound signature verification.
+//!
+//! Hashing the EIP-712 payload of a gasless order and recovering its signer
+//! take far longer than the rest of validation. Run inline, a burst of signed
+//! orders would stall the async runtime, so verification runs on blocking
+//! threads instead. The number of verifications running at once is bounded so
+//! that a burst cannot starve other blocking work.
+
+use crate::OrderError;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Bounded pool of blocking threads verifying signatures.
+pub struct SignatureWorkers {
+	/// Permits limiting the number of verifications running at once.
+	permits: Arc<Semaphore>,
+}
+
+impl Default for SignatureWorkers {
+	/// Creates a pool with one worker per available CPU.
+	fn default() -> Self {
+		let workers = std::thread::available_parallelism()
+			.map(|workers| workers.get())
+			.unwrap_or(1);
+		Self::new(workers)
+	}
+}
+
+impl SignatureWorkers {
+	/// Creates a pool running at most `workers` verifications at once.
+	pub fn new(workers: usize) -> Self {
+		Self {
+			permits: Arc::new(Semaphore::new(workers.max(1))),
+		}
+	}
+
+	/// Runs a verification job on a worker, waiting for one to become free.
+	pub async fn run<F, T>(&self, job: F) -> Result<T, OrderError>
+	where
+		F: FnOnce() -> Result<T, OrderError> + Send + 'static,
+		T: Send + 'static,
+	{
+		let _permit =
+			self.permits.acquire().await.map_err(|_| {
+				OrderError::ValidationFailed("Signature workers shut down".to_string())
+			})?;
+
+		tokio::task::spawn_blocking(job).await.map_err(|e| {
+			OrderError::ValidationFailed(format!("Signature verification aborted: {}", e))
+		})?
+	}
+}
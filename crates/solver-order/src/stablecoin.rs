@@ -0,0 +1,124 @@
+//! Stablecoin-equivalence fast path for execution decisions.
+//!
+//! Orders that only move equivalent stablecoins (e.g. USDC on one chain for
+//! USDC on another) can be decided by comparing normalized amounts directly.
+//! This avoids oracle calls, liquidity checks, and strategy evaluation on the
+//! highest-volume corridors where latency matters most.
+//!
+//! The margin is compared without pricing gas, so orders are deferred while
+//! gas is above the configured maximum instead of being filled at a loss.
+
+use alloy_primitives::U256;
+use solver_types::{Address, ExecutionContext, ExecutionDecision, ExecutionParams, OrderAssets};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Decimals that all amounts are normalized to before comparison.
+const NORMALIZED_DECIMALS: u8 = 36;
+
+/// Basis point denominator used for margin calculations.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Time an order is deferred by while gas is too expensive.
+const GAS_DEFERRAL: Duration = Duration::from_secs(60);
+
+/// A token registered in a stablecoin equivalence group.
+#[derive(Debug, Clone)]
+pub struct StablecoinToken {
+	/// Name of the equivalence group the token belongs to.
+	pub group: String,
+	/// Number of decimals used by the token.
+	pub decimals: u8,
+}
+
+/// Fast decision path for orders that swap equivalent stablecoins.
+pub struct StablecoinFastPath {
+	/// Minimum spread between inputs and outputs in basis points.
+	min_margin_bps: u64,
+	/// Registered stablecoins keyed by chain ID and token address.
+	tokens: HashMap<(u64, Address), StablecoinToken>,
+	/// Gas price above which orders are deferred, if limited.
+	max_gas_price: Option<U256>,
+}
+
+impl StablecoinFastPath {
+	/// Creates a new fast path with the given margin and token registry.
+	pub fn new(min_margin_bps: u64, tokens: HashMap<(u64, Address), StablecoinToken>) -> Self {
+		Self {
+			min_margin_bps,
+			tokens,
+			max_gas_price: None,
+		}
+	}
+
+	/// Defers orders while the gas price is above `max_gas_price`.
+	pub fn with_max_gas_price(mut self, max_gas_price: U256) -> Self {
+		self.max_gas_price = Some(max_gas_price);
+		self
+	}
+
+	/// Evaluates an order on the fast path.
+	///
+	/// Returns `None` when the order does not exclusively move tokens from a
+	/// single equivalence group, in which case the regular path applies.
+	pub fn evaluate(
+		&self,
+		assets: &OrderAssets,
+		context: &ExecutionContext,
+	) -> Option<ExecutionDecision> {
+		if assets.inputs.is_empty() || assets.outputs.is_empty() {
+			return None;
+		}
+
+		let mut group: Option<&str> = None;
+		let mut input_total = U256::ZERO;
+		let mut output_total = U256::ZERO;
+
+		for (asset, is_input) in assets
+			.inputs
+			.iter()
+			.map(|a| (a, true))
+			.chain(assets.outputs.iter().map(|a| (a, false)))
+		{
+			let token = self.tokens.get(&(asset.chain_id, asset.token.clone()))?;
+			match group {
+				Some(name) if name != token.group => return None,
+				_ => group = Some(&token.group),
+			}
+
+			let scale = U256::from(10u64).pow(U256::from(NORMALIZED_DECIMALS - token.decimals));
+			let normalized = asset.amount.checked_mul(scale)?;
+			if is_input {
+				input_total = input_total.checked_add(normalized)?;
+			} else {
+				output_total = output_total.checked_add(normalized)?;
+			}
+		}
+
+		// Inputs must cover outputs plus the configured margin
+		let required = output_total
+			.checked_mul(U256::from(BPS_DENOMINATOR + self.min_margin_bps))?
+			/ U256::from(BPS_DENOMINATOR);
+		if input_total < required {
+			return Some(ExecutionDecision::Skip(format!(
+				"Stablecoin margin below minimum of {} bps",
+				self.min_margin_bps
+			)));
+		}
+
+		// The margin does not cover gas, so wait for gas to come down
+		if self
+			.max_gas_price
+			.is_some_and(|max_gas_price| context.gas_price > max_gas_price)
+		{
+			return Some(ExecutionDecision::Defer(GAS_DEFERRAL));
+		}
+
+		Some(ExecutionDecision::Execute(ExecutionParams {
+			gas_price: context.gas_price,
+			priority_fee: None,
+			urgency: None,
+			fill_amount: None,
+		}))
+	}
+}
@@ -0,0 +1,17 @@
+//! Embeds the git commit the solver is built from, reported by GET /info.
+
+use std::process::Command;
+
+fn main() {
+	let commit = Command::new("git")
+		.args(["rev-parse", "HEAD"])
+		.output()
+		.ok()
+		.filter(|output| output.status.success())
+		.and_then(|output| String::from_utf8(output.stdout).ok());
+	if let Some(commit) = commit {
+		println!("cargo:rustc-env=SOLVER_GIT_COMMIT={}", commit.trim());
+	}
+	println!("cargo:rerun-if-changed=../../.git/HEAD");
+	println!("cargo:rerun-if-changed=../../.git/refs");
+}
@@ -0,0 +1,166 @@
+//! OIF Solver Cluster View Implementation
+//!
+//! Teams running a solver per region or per chain group can let one instance
+//! aggregate the others. The instance serving the view queries the readiness
+//! and statistics endpoints of its configured peers concurrently and combines
+//! them with its own into a single response. Peers that do not answer within
+//! the timeout are reported as unreachable rather than failing the view.
+
+use alloy_primitives::U256;
+use solver_config::ClusterConfig;
+use solver_core::SolverEngine;
+use solver_types::{
+	CapitalUtilization, ClusterInstance, ClusterResponse, ReadinessResponse, StatsResponse,
+};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Builds the cluster view from this instance and its peers.
+pub async fn process_cluster_request(
+	cluster: &ClusterConfig,
+	solver: &SolverEngine,
+) -> ClusterResponse {
+	let client = reqwest::Client::builder()
+		.timeout(Duration::from_millis(cluster.timeout_ms))
+		.build()
+		.unwrap_or_default();
+
+	let peers: Vec<_> = cluster
+		.peers
+		.iter()
+		.map(|url| tokio::spawn(fetch_instance(client.clone(), url.clone())))
+		.collect();
+
+	let mut instances = vec![ClusterInstance {
+		url: "self".to_string(),
+		reachable: true,
+		readiness: Some(solver.readiness().await),
+		stats: Some(StatsResponse {
+			capital: solver.capital_utilization(),
+		}),
+		error: None,
+	}];
+	for (peer, url) in peers.into_iter().zip(&cluster.peers) {
+		instances.push(
+			peer.await
+				.unwrap_or_else(|e| unreachable(url, e.to_string())),
+		);
+	}
+
+	ClusterResponse {
+		ready: instances.iter().all(|instance| {
+			instance
+				.readiness
+				.as_ref()
+				.is_some_and(|readiness| readiness.ready)
+		}),
+		capital: combine_capital(&instances),
+		instances,
+	}
+}
+
+/// Queries the readiness and statistics of a peer.
+///
+/// The readiness endpoint answers 503 with a body while the peer is not
+/// ready, so the body is read regardless of the status.
+async fn fetch_instance(client: reqwest::Client, url: String) -> ClusterInstance {
+	let base = url.trim_end_matches('/');
+	let readiness = match fetch::<ReadinessResponse>(&client, &format!("{}/readyz", base)).await {
+		Ok(readiness) => readiness,
+		Err(e) => return unreachable(&url, e),
+	};
+	let stats = match fetch::<StatsResponse>(&client, &format!("{}/api/stats", base)).await {
+		Ok(stats) => stats,
+		Err(e) => return unreachable(&url, e),
+	};
+
+	ClusterInstance {
+		url,
+		reachable: true,
+		readiness: Some(readiness),
+		stats: Some(stats),
+		error: None,
+	}
+}
+
+/// Fetches and parses a JSON document from a peer.
+async fn fetch<T: serde::de::DeserializeOwned>(
+	client: &reqwest::Client,
+	url: &str,
+) -> Result<T, String> {
+	client
+		.get(url)
+		.send()
+		.await
+		.map_err(|e| format!("Request to {} failed: {}", url, e))?
+		.json()
+		.await
+		.map_err(|e| format!("Invalid response from {}: {}", url, e))
+}
+
+/// Builds the status of a peer that could not be reached.
+fn unreachable(url: &str, error: String) -> ClusterInstance {
+	ClusterInstance {
+		url: url.to_string(),
+		reachable: false,
+		readiness: None,
+		stats: None,
+		error: Some(error),
+	}
+}
+
+/// Sums the capital utilization of the instances per chain and token.
+///
+/// Averages are weighted by the turnarounds of each instance.
+fn combine_capital(instances: &[ClusterInstance]) -> Vec<CapitalUtilization> {
+	let mut combined: BTreeMap<(u64, String), (CapitalUtilization, f64)> = BTreeMap::new();
+	for capital in instances
+		.iter()
+		.filter_map(|instance| instance.stats.as_ref())
+		.flat_map(|stats| &stats.capital)
+	{
+		let key = (capital.chain_id, capital.token.to_lowercase());
+		let weighted =
+			capital.average_turnaround_seconds.unwrap_or_default() * capital.turnarounds as f64;
+		let (total, total_weighted) = combined.entry(key).or_insert_with(|| {
+			(
+				CapitalUtilization {
+					chain_id: capital.chain_id,
+					token: capital.token.clone(),
+					in_flight: U256::ZERO,
+					idle: U256::ZERO,
+					utilization: 0.0,
+					in_flight_orders: 0,
+					turnarounds: 0,
+					average_turnaround_seconds: None,
+					max_turnaround_seconds: None,
+				},
+				0.0,
+			)
+		});
+		total.in_flight = total.in_flight.saturating_add(capital.in_flight);
+		total.idle = total.idle.saturating_add(capital.idle);
+		total.in_flight_orders += capital.in_flight_orders;
+		total.turnarounds += capital.turnarounds;
+		total.max_turnaround_seconds =
+			match (total.max_turnaround_seconds, capital.max_turnaround_seconds) {
+				(Some(a), Some(b)) => Some(a.max(b)),
+				(a, b) => a.or(b),
+			};
+		*total_weighted += weighted;
+	}
+
+	combined
+		.into_values()
+		.map(|(mut total, weighted)| {
+			let held = total.in_flight.saturating_add(total.idle);
+			if !held.is_zero() {
+				total.utilization = f64::from(total.in_flight) / f64::from(held);
+			}
+			if total.turnarounds > 0 {
+				total.average_turnaround_seconds = Some(weighted / total.turnarounds as f64);
+			}
+			total
+		})
+		.collect()
+}
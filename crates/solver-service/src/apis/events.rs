@@ -0,0 +1,139 @@
+//! OIF Solver Live Event Stream Implementation
+//!
+//! Dashboards and aggregators watch fills and claims as they happen instead of
+//! polling the order endpoints. Every WebSocket client gets its own filtered
+//! subscription to the solver's event bus and receives the events matching
+//! its filters as JSON text messages. A client too slow to keep up skips the
+//! events it missed; they can still be read from GET /api/events.
+
+use actix_ws::{Message, MessageStream, Session};
+use solver_core::{metrics::event_kind, SolverEngine};
+use solver_types::{EventStreamQuery, SolverEvent, StreamedEvent};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, warn};
+
+/// Filters an event stream client subscribed with.
+struct EventFilter {
+	/// Order the events must concern, if filtered by order.
+	order_id: Option<String>,
+	/// Kinds the events must be of, if filtered by kind.
+	kinds: Option<Vec<String>>,
+	/// Chain the events must happen on, if filtered by chain.
+	chain_id: Option<u64>,
+}
+
+impl EventFilter {
+	/// Creates the filter described by the stream's query parameters.
+	fn new(query: EventStreamQuery) -> Self {
+		Self {
+			order_id: query.order_id,
+			kinds: query.kinds.map(|kinds| {
+				kinds
+					.split(',')
+					.map(|kind| kind.trim().to_string())
+					.filter(|kind| !kind.is_empty())
+					.collect()
+			}),
+			chain_id: query.chain_id,
+		}
+	}
+
+	/// Returns whether an event is of a wanted kind and on the wanted chain.
+	///
+	/// These checks are cheap, so they run as the event bus delivers the
+	/// event, and events failing them never reach the client's channel.
+	fn matches(&self, solver: &SolverEngine, event: &SolverEvent) -> bool {
+		if let Some(kinds) = &self.kinds {
+			let kind = event_kind(event);
+			if !kinds.iter().any(|wanted| wanted == kind) {
+				return false;
+			}
+		}
+		self.chain_id.is_none() || solver.event_chain(event) == self.chain_id
+	}
+
+	/// Returns the event to send if it concerns the wanted order.
+	///
+	/// Looking up the orders of an event may hit storage, so it happens on
+	/// the client's task rather than while the event is delivered.
+	async fn apply(&self, solver: &SolverEngine, event: SolverEvent) -> Option<StreamedEvent> {
+		let kind = event_kind(&event);
+		let chain_id = solver.event_chain(&event);
+		let order_ids = solver.event_orders(&event).await;
+		if let Some(order_id) = &self.order_id {
+			if !order_ids.contains(order_id) {
+				return None;
+			}
+		}
+
+		Some(StreamedEvent {
+			kind: kind.to_string(),
+			timestamp: chrono::Utc::now().timestamp() as u64,
+			order_ids,
+			chain_id,
+			event,
+		})
+	}
+}
+
+/// Streams the solver's events matching `query` to a WebSocket client until
+/// either side closes the connection.
+pub async fn stream_events(
+	solver: Arc<SolverEngine>,
+	query: EventStreamQuery,
+	mut session: Session,
+	mut messages: MessageStream,
+) {
+	let filter = Arc::new(EventFilter::new(query));
+	let mut events = solver.event_bus().subscribe_filtered({
+		let solver = solver.clone();
+		let filter = filter.clone();
+		move |event| filter.matches(&solver, event)
+	});
+
+	loop {
+		tokio::select! {
+			event = events.recv() => match event {
+				Ok(event) => {
+					let Some(streamed) = filter.apply(&solver, event).await else {
+						continue;
+					};
+					let text = match serde_json::to_string(&streamed) {
+						Ok(text) => text,
+						Err(e) => {
+							warn!("Failed to serialize streamed event: {}", e);
+							continue;
+						}
+					};
+					if session.text(text).await.is_err() {
+						return;
+					}
+				}
+				Err(RecvError::Lagged(skipped)) => {
+					warn!(skipped, "Event stream client fell behind, skipping events");
+				}
+				Err(RecvError::Closed) => break,
+			},
+			message = messages.recv() => match message {
+				Some(Ok(Message::Ping(bytes))) => {
+					if session.pong(&bytes).await.is_err() {
+						return;
+					}
+				}
+				Some(Ok(Message::Close(reason))) => {
+					let _ = session.close(reason).await;
+					return;
+				}
+				Some(Ok(_)) => {}
+				Some(Err(e)) => {
+					debug!("Event stream client protocol error: {}", e);
+					break;
+				}
+				None => break,
+			},
+		}
+	}
+
+	let _ = session.close(None).await;
+}
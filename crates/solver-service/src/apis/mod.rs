@@ -3,4 +3,7 @@
 //! This module contains the implementation of the HTTP API endpoints
 //! for the OIF Solver.
 
+pub mod cluster;
+pub mod events;
+pub mod orders;
 pub mod quote; 
\ No newline at end of file
@@ -0,0 +1,61 @@
+//! OIF Solver Order Submission API Implementation
+//!
+//! Accepts signed gasless ERC-7683 orders over HTTP. Each order is converted
+//! into an intent, checked for a valid signature and live deadlines, and handed
+//! to the off-chain discovery source, from where it is processed like any
+//! intent discovered on-chain. The returned intent ID is the order ID, which
+//! can be polled at `GET /api/orders/{id}` once the order has been validated.
+//!
+//! An accepted response acknowledges that the order was persisted and will be
+//! processed. Submitters that did not receive a response can safely retry:
+//! orders already received are acknowledged again without being processed
+//! twice.
+
+use solver_core::SolverEngine;
+use solver_discovery::implementations::offchain::_7683::intent_from_order;
+use solver_types::{CrossChainOrder, IntentStatus, SubmitIntentResponse};
+use tracing::info;
+
+/// Processes a submitted order and reports whether it was accepted.
+///
+/// Rejected orders carry the reason in the response message, and an empty
+/// intent ID if the order could not be parsed far enough to identify it.
+pub async fn process_order_submission(
+	order: CrossChainOrder,
+	solver: &SolverEngine,
+) -> SubmitIntentResponse {
+	let intent = match intent_from_order(&order) {
+		Ok(intent) => intent,
+		Err(e) => return rejected(String::new(), e.to_string()),
+	};
+	let intent_id = intent.id.clone();
+
+	match solver.submit_intent(intent).await {
+		Ok(true) => {
+			info!(intent_id = %intent_id, "Accepted submitted order");
+			SubmitIntentResponse {
+				intent_id,
+				status: IntentStatus::Accepted,
+				message: None,
+			}
+		}
+		Ok(false) => {
+			info!(intent_id = %intent_id, "Acknowledged resubmitted order");
+			SubmitIntentResponse {
+				intent_id,
+				status: IntentStatus::Accepted,
+				message: Some("Order already received".to_string()),
+			}
+		}
+		Err(e) => rejected(intent_id, e.to_string()),
+	}
+}
+
+/// Builds the response for a rejected submission.
+fn rejected(intent_id: String, message: String) -> SubmitIntentResponse {
+	SubmitIntentResponse {
+		intent_id,
+		status: IntentStatus::Rejected,
+		message: Some(message),
+	}
+}
@@ -190,9 +190,11 @@
 
 use alloy_primitives::U256;
 use solver_core::SolverEngine;
+use solver_oracles::{gas_token, PriceOracle};
 use solver_types::{
-    AssetAmount, AvailableInput, GetQuoteRequest, GetQuoteResponse, QuoteOption, 
-    QuotePreference, SettlementOrder, SettlementType,
+    smallest_units, whole_tokens, Address, AssetAmount, AvailableInput, ExecutionContext,
+    GetQuoteRequest, GetQuoteResponse, OrderAssets, QuoteOption, QuotePreference, RouteQuoteQuery,
+    SettlementOrder, SettlementType, TokenAmount,
 };
 use thiserror::Error;
 use tracing::info;
@@ -204,7 +206,6 @@ pub enum QuoteError {
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
     #[error("Unsupported asset: {0}")]
-    #[allow(dead_code)]
     UnsupportedAsset(String),
     #[error("Insufficient liquidity for requested amount")]
     InsufficientLiquidity,
@@ -212,7 +213,6 @@ pub enum QuoteError {
     #[allow(dead_code)]
     SolverCapacityExceeded,
     #[error("Internal error: {0}")]
-    #[allow(dead_code)]
     Internal(String),
 }
 
@@ -240,6 +240,204 @@ pub async fn process_quote_request(
     Ok(GetQuoteResponse { quotes })
 }
 
+/// Computes the solver's executable quote for a single route.
+///
+/// The quoted output is worth the input at oracle prices, less the gas of
+/// filling on the destination chain and claiming on the origin chain and the
+/// configured margin. Routes the solver has no prices for, or cannot deliver
+/// from its inventory, are refused rather than quoted.
+pub async fn process_route_quote(
+    query: RouteQuoteQuery,
+    solver: &SolverEngine,
+) -> Result<GetQuoteResponse, QuoteError> {
+    if query.amount == U256::ZERO {
+        return Err(QuoteError::InvalidRequest(
+            "Input amount must be greater than zero".to_string(),
+        ));
+    }
+    validate_asset_address(&query.input_token)?;
+    validate_asset_address(&query.output_token)?;
+    let input_token = parse_address(&query.input_token)?;
+    let output_token = parse_address(&query.output_token)?;
+
+    let config = solver
+        .current_config()
+        .api
+        .as_ref()
+        .map(|api| api.quote.clone())
+        .unwrap_or_default();
+    let settler_address = |key: &str| {
+        solver
+            .config()
+            .order
+            .implementations
+            .get("eip7683")
+            .and_then(|implementation| implementation.get(key))
+            .and_then(|address| address.as_str())
+            .map(|address| address.to_string())
+            .ok_or_else(|| QuoteError::Internal(format!("No EIP-7683 {} configured", key)))
+    };
+    let input_settler = settler_address("input_settler_address")?;
+    let output_settler = settler_address("output_settler_address")?;
+
+    let assets = OrderAssets {
+        inputs: vec![TokenAmount {
+            chain_id: query.origin_chain_id,
+            token: input_token.clone(),
+            amount: query.amount,
+        }],
+        outputs: vec![TokenAmount {
+            chain_id: query.destination_chain_id,
+            token: output_token.clone(),
+            amount: U256::ZERO,
+        }],
+    };
+    let context = solver.market_context(&assets).await;
+
+    let oracle = solver.price_oracle();
+    let input_price =
+        usd_price(oracle, query.origin_chain_id, &input_token, &query.input_token).await?;
+    let output_price =
+        usd_price(oracle, query.destination_chain_id, &output_token, &query.output_token).await?;
+    let input_decimals = token_decimals(solver, query.origin_chain_id, &input_token).await?;
+    let output_decimals = token_decimals(solver, query.destination_chain_id, &output_token).await?;
+
+    let fill_cost = gas_cost_usd(
+        solver,
+        &context,
+        query.destination_chain_id,
+        config.fill_gas_limit,
+    )
+    .await?;
+    let claim_cost = gas_cost_usd(
+        solver,
+        &context,
+        query.origin_chain_id,
+        config.claim_gas_limit,
+    )
+    .await?;
+
+    let input_usd = whole_tokens(query.amount, input_decimals) * input_price;
+    let margin_usd = input_usd * config.margin_bps as f64 / 10_000.0;
+    let output_usd = input_usd - fill_cost - claim_cost - margin_usd;
+    if output_usd <= 0.0 {
+        return Err(QuoteError::InvalidRequest(
+            "Input amount does not cover execution costs".to_string(),
+        ));
+    }
+    let output_amount = smallest_units(output_usd / output_price, output_decimals);
+
+    // Only quote what can be delivered from inventory
+    let balance = context
+        .token_balances
+        .get(&(query.destination_chain_id, output_token))
+        .copied()
+        .unwrap_or_default();
+    if balance < output_amount {
+        return Err(QuoteError::InsufficientLiquidity);
+    }
+
+    let valid_until = chrono::Utc::now().timestamp() as u64 + config.validity_seconds;
+    let order_data = serde_json::json!({
+        "originChainId": query.origin_chain_id,
+        "inputs": [[query.input_token, query.amount.to_string()]],
+        "outputs": [{
+            "settler": output_settler,
+            "chainId": query.destination_chain_id,
+            "token": query.output_token,
+            "amount": output_amount.to_string(),
+        }],
+        "fillDeadline": valid_until + config.eta_seconds,
+    });
+
+    info!(
+        origin_chain_id = query.origin_chain_id,
+        destination_chain_id = query.destination_chain_id,
+        output_amount = %output_amount,
+        "Quoted route"
+    );
+
+    Ok(GetQuoteResponse {
+        quotes: vec![QuoteOption {
+            orders: SettlementOrder {
+                settler: input_settler,
+                data: order_data,
+            },
+            required_allowances: vec![AssetAmount {
+                asset: query.input_token,
+                amount: query.amount,
+            }],
+            valid_until,
+            eta: config.eta_seconds,
+            total_fee_usd: input_usd - output_usd,
+            quote_id: Uuid::new_v4().to_string(),
+            settlement_type: SettlementType::Escrow,
+        }],
+    })
+}
+
+/// Parses a hex address.
+fn parse_address(address: &str) -> Result<Address, QuoteError> {
+    address
+        .parse::<alloy_primitives::Address>()
+        .map(|address| Address(address.to_vec()))
+        .map_err(|e| QuoteError::InvalidRequest(format!("Invalid address {}: {}", address, e)))
+}
+
+/// Returns the USD price of one whole token, refusing tokens the oracle
+/// does not price.
+async fn usd_price(
+    oracle: &dyn PriceOracle,
+    chain_id: u64,
+    token: &Address,
+    asset: &str,
+) -> Result<f64, QuoteError> {
+    oracle
+        .usd_price(chain_id, token)
+        .await
+        .map_err(|e| QuoteError::Internal(format!("Failed to price {}: {}", asset, e)))?
+        .ok_or_else(|| QuoteError::UnsupportedAsset(format!("No price for {}", asset)))
+}
+
+/// Returns the decimals of a token.
+async fn token_decimals(
+    solver: &SolverEngine,
+    chain_id: u64,
+    token: &Address,
+) -> Result<u8, QuoteError> {
+    solver
+        .token_metadata(chain_id, token)
+        .await
+        .map(|metadata| metadata.decimals)
+        .map_err(|e| QuoteError::Internal(format!("Failed to read token decimals: {}", e)))
+}
+
+/// Returns the USD cost of spending `gas_limit` gas on a chain at its
+/// current fee estimate.
+async fn gas_cost_usd(
+    solver: &SolverEngine,
+    context: &ExecutionContext,
+    chain_id: u64,
+    gas_limit: u64,
+) -> Result<f64, QuoteError> {
+    let gas_price = context
+        .gas_estimates
+        .get(&chain_id)
+        .and_then(|estimate| estimate.max_price())
+        .ok_or_else(|| QuoteError::Internal(format!("No fee estimate for chain {}", chain_id)))?;
+    let gas_token_price = usd_price(
+        solver.price_oracle(),
+        chain_id,
+        &gas_token(),
+        &format!("the gas token of chain {}", chain_id),
+    )
+    .await?;
+    let decimals = solver.networks().gas_token(chain_id).decimals;
+
+    let cost = gas_price.saturating_mul(U256::from(gas_limit));
+    Ok(whole_tokens(cost, decimals) * gas_token_price)
+}
+
 /// Validates the incoming quote request.
 fn validate_quote_request(request: &GetQuoteRequest) -> Result<(), QuoteError> {
     // Check that we have at least one input
@@ -461,7 +659,7 @@ fn calculate_fees_and_timing(
 }
 
 /// Sorts quotes based on user preference.
-fn sort_quotes_by_preference(quotes: &mut Vec<QuoteOption>, preference: &Option<QuotePreference>) {
+fn sort_quotes_by_preference(quotes: &mut [QuoteOption], preference: &Option<QuotePreference>) {
     match preference {
         Some(QuotePreference::Price) => {
             // Sort by lowest fee first
@@ -4,24 +4,52 @@
 //! validates, executes, and settles cross-chain orders. It uses a modular
 //! architecture with pluggable implementations for different components.
 
-use clap::Parser;
+use alloy_primitives::hex;
+use clap::{Parser, Subcommand};
 use solver_config::Config;
+use solver_core::reload::ConfigWatcher;
 use solver_core::{SolverBuilder, SolverEngine};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use tracing_subscriber::{reload, EnvFilter, Registry};
 
 mod apis;
+mod rate_limit;
 mod server;
 
 // Import implementations from individual crates
 use solver_account::implementations::local::create_account;
-use solver_delivery::implementations::evm::alloy::create_http_delivery;
+use solver_delivery::implementations::evm::{
+	alloy::create_http_delivery, flashbots::create_flashbots_delivery,
+	oz_relayer::create_relayer_delivery, user_operation::create_user_operation_delivery,
+};
+use solver_delivery::implementations::solana::create_solana_delivery;
+use solver_delivery::DeliveryInterface;
+use solver_discovery::implementations::offchain::_7683::create_discovery as create_offchain_discovery;
 use solver_discovery::implementations::onchain::_7683::create_discovery;
+use solver_discovery::implementations::onchain::across::create_discovery as create_across_discovery;
+use solver_discovery::implementations::onchain::solana::create_discovery as create_solana_discovery;
+use solver_liquidity::implementations::uniswap_v2::create_liquidity;
 use solver_order::implementations::{
-	standards::_7683::create_order_impl, strategies::simple::create_strategy,
+	standards::_7683::create_order_impl,
+	standards::across::create_order_impl as create_across_order_impl,
+	strategies::filters::{create_expiry_filter, create_risk_filter, create_token_allowlist},
+	strategies::simple::create_strategy,
+};
+use solver_settlement::implementations::{
+	across::create_across_settlement, direct::create_settlement,
+	layerzero::create_layerzero_settlement,
+};
+use solver_settlement::SettlementInterface;
+use solver_storage::implementations::{
+	file::create_storage, memory::create_storage as create_memory_storage,
+	sqlite::create_storage as create_sqlite_storage,
+};
+use solver_validators::implementations::{
+	deadline::create_deadline_validator, open_orders::create_open_orders_validator,
+	order_size::create_order_size_validator, user_exposure::create_user_exposure_validator,
 };
-use solver_settlement::implementations::direct::create_settlement;
-use solver_storage::implementations::file::create_storage;
 
 /// Command-line arguments for the solver service.
 #[derive(Parser, Debug)]
@@ -34,6 +62,35 @@ struct Args {
 	/// Log level (trace, debug, info, warn, error)
 	#[arg(short, long, default_value = "info")]
 	log_level: String,
+
+	/// Command to run instead of the solver
+	#[command(subcommand)]
+	command: Option<Command>,
+}
+
+/// Maintenance commands for the solver service.
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Recommend initial balances per chain and token from the configured corridors
+	Bootstrap {
+		/// Pull token shortfalls from the configured treasury
+		#[arg(long)]
+		execute: bool,
+	},
+	/// List the orders that were in flight at a past time, from the event journal
+	InFlight {
+		/// Unix timestamp in seconds or RFC 3339 time, e.g. 2024-05-01T14:32:00Z
+		#[arg(long)]
+		at: String,
+	},
+	/// Report risky settings in the configuration without starting the solver
+	Lint,
+	/// List orders held in the dead-letter queue
+	DeadLetters {
+		/// Schedule a dead-lettered order to be re-driven by the running solver
+		#[arg(long, value_name = "ORDER_ID")]
+		redrive: Option<String>,
+	},
 }
 
 /// Main entry point for the solver service.
@@ -49,17 +106,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let args = Args::parse();
 
 	// Initialize tracing with env filter
-	use tracing_subscriber::{fmt, EnvFilter};
+	use tracing_subscriber::{fmt, prelude::*};
 
-	// Create env filter with default from args
+	// Create env filter with default from args, reloadable when the
+	// configuration sets a log level
 	let default_directive = args.log_level.to_string();
 	let env_filter =
 		EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_directive));
+	let (env_filter, log_filter) = reload::Layer::new(env_filter);
 
-	fmt()
-		.with_env_filter(env_filter)
-		.with_thread_ids(true)
-		.with_target(true)
+	tracing_subscriber::registry()
+		.with(env_filter)
+		.with(fmt::layer().with_thread_ids(true).with_target(true))
 		.init();
 
 	tracing::info!("Started solver");
@@ -67,12 +125,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	// Load configuration
 	let config = Config::from_file(args.config.to_str().unwrap())?;
 	tracing::info!("Loaded configuration [{}]", config.solver.id);
+	apply_log_level(&log_filter, &args.log_level, &config);
+
+	if let Some(Command::Lint) = args.command {
+		return lint_config(&config);
+	}
+	for warning in config.lint() {
+		tracing::warn!(code = warning.code, path = %warning.path, "{}", warning.message);
+	}
 
 	// Build solver engine with implementations
 	let solver = build_solver(config.clone())?;
 	let solver = Arc::new(solver);
 	tracing::info!("Loaded solver engine");
 
+	match args.command {
+		Some(Command::Bootstrap { execute }) => {
+			return bootstrap_inventory(&solver, execute).await;
+		}
+		Some(Command::InFlight { at }) => return print_in_flight(&solver, &at).await,
+		Some(Command::DeadLetters { redrive }) => {
+			return manage_dead_letters(&solver, redrive.as_deref()).await;
+		}
+		Some(Command::Lint) | None => {}
+	}
+
+	// Apply configuration changes while the solver runs
+	tokio::spawn(watch_config(
+		Arc::clone(&solver),
+		args.config.clone(),
+		config
+			.reload
+			.as_ref()
+			.map(|reload| Duration::from_secs(reload.watch_interval_seconds)),
+		log_filter,
+		args.log_level.clone(),
+	));
+
 	// Start API server if enabled
 	if let Some(api_config) = &config.api {
 		if api_config.enabled {
@@ -109,33 +198,327 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 	Ok(())
 }
 
+/// Handle for swapping the log filter at runtime.
+type LogFilter = reload::Handle<EnvFilter, Registry>;
+
+/// Sets the log filter to the configured log level, or back to the
+/// command-line level if the configuration sets none.
+///
+/// Does nothing when RUST_LOG is set, which always takes precedence.
+fn apply_log_level(log_filter: &LogFilter, default_level: &str, config: &Config) {
+	if std::env::var_os(EnvFilter::DEFAULT_ENV).is_some() {
+		return;
+	}
+	let level = config.solver.log_level.as_deref().unwrap_or(default_level);
+	let filter = match EnvFilter::try_new(level) {
+		Ok(filter) => filter,
+		Err(e) => {
+			tracing::warn!(level, error = %e, "Ignoring invalid log level");
+			return;
+		}
+	};
+	if let Err(e) = log_filter.reload(filter) {
+		tracing::warn!(error = %e, "Failed to apply log level");
+	}
+}
+
+/// Reloads the configuration file whenever SIGHUP is received or, if
+/// watching is enabled, the file changes, and applies the reloadable settings
+/// to the running solver.
+///
+/// Invalid configurations and changes that require a restart are logged and
+/// leave the running configuration untouched.
+async fn watch_config(
+	solver: Arc<SolverEngine>,
+	path: PathBuf,
+	poll_interval: Option<Duration>,
+	log_filter: LogFilter,
+	default_level: String,
+) {
+	let mut watcher = ConfigWatcher::new(&path, poll_interval);
+	loop {
+		watcher.changed().await;
+
+		let config = match Config::from_file(path.to_str().unwrap()) {
+			Ok(config) => config,
+			Err(e) => {
+				tracing::warn!(error = %e, "Ignoring invalid configuration");
+				continue;
+			}
+		};
+		match solver.reload_config(config.clone()) {
+			Ok(changes) => {
+				if changes.iter().any(|section| section == "solver.log_level") {
+					apply_log_level(&log_filter, &default_level, &config);
+				}
+			}
+			Err(e) => tracing::warn!(error = %e, "Rejected configuration reload"),
+		}
+	}
+}
+
 /// Builds the solver engine with all necessary implementations.
 ///
 /// This function wires up all the concrete implementations for:
-/// - Storage backends (e.g., in-memory, Redis)
+/// - Storage backends (e.g., file, SQLite)
 /// - Account providers (e.g., local keys, AWS KMS)
 /// - Delivery mechanisms (e.g., HTTP RPC, WebSocket)
 /// - Discovery sources (e.g., on-chain events, off-chain APIs)
-/// - Order implementations (e.g., EIP-7683)
+/// - Order implementations (e.g., EIP-7683, Across V3)
 /// - Settlement mechanisms (e.g., direct settlement)
 /// - Execution strategies (e.g., always execute, limit orders)
+/// - Liquidity sources (e.g., Uniswap V2 routers)
+/// - Risk validators (e.g., order size limits, exposure caps)
 fn build_solver(config: Config) -> Result<SolverEngine, Box<dyn std::error::Error>> {
 	let builder = SolverBuilder::new(config)
         // Storage implementations
-        .with_storage_factory(create_storage)
+        .with_storage_factory("file", create_storage)
+        .with_storage_factory("sqlite", create_sqlite_storage)
+        .with_storage_factory("memory", create_memory_storage)
         // Account implementations
         .with_account_factory(create_account)
         // Delivery implementations
-        .with_delivery_factory("origin", create_http_delivery)
-        .with_delivery_factory("destination", create_http_delivery)
+        .with_delivery_factory("origin", create_delivery)
+        .with_delivery_factory("destination", create_delivery)
+        .with_delivery_factory("solana", create_solana_delivery)
         // Discovery implementations
         .with_discovery_factory("origin_eip7683", create_discovery)
+        .with_discovery_factory("offchain_eip7683", create_offchain_discovery)
+        .with_discovery_factory("origin_across", create_across_discovery)
+        .with_discovery_factory("solana_intents", create_solana_discovery)
         // Order implementations
         .with_order_factory("eip7683", create_order_impl)
+        .with_order_factory("across", create_across_order_impl)
         // Settlement implementations
-        .with_settlement_factory("eip7683", create_settlement)
-        // Strategy implementation
-        .with_strategy_factory(create_strategy);
+        .with_settlement_factory("eip7683", create_settlement_impl)
+        .with_settlement_factory("across", create_across_settlement)
+        // Strategy implementations
+        .with_strategy_factory("simple", create_strategy)
+        .with_strategy_factory("expiry", create_expiry_filter)
+        .with_strategy_factory("token_allowlist", create_token_allowlist)
+        .with_strategy_factory("risk", create_risk_filter)
+        // Liquidity implementations
+        .with_liquidity_factory("uniswap_v2", create_liquidity)
+        // Risk validators
+        .with_validator_factory("order_size", create_order_size_validator)
+        .with_validator_factory("user_exposure", create_user_exposure_validator)
+        .with_validator_factory("open_orders", create_open_orders_validator)
+        .with_validator_factory("deadline", create_deadline_validator);
 
 	Ok(builder.build()?)
 }
+
+/// Creates a delivery provider submitting publicly or, when a provider sets
+/// `submission`, through a private relay, a bundler or a relayer service.
+fn create_delivery(config: &toml::Value) -> Box<dyn DeliveryInterface> {
+	match config.get("submission").and_then(|v| v.as_str()) {
+		Some("private") => create_flashbots_delivery(config),
+		Some("user_operation") => create_user_operation_delivery(config),
+		Some("oz_relayer") => create_relayer_delivery(config),
+		_ => create_http_delivery(config),
+	}
+}
+
+/// Creates a settlement implementation claiming after a dispute period or,
+/// when configured with `attestation = "layerzero"`, once the fill's
+/// LayerZero attestation has been delivered.
+fn create_settlement_impl(config: &toml::Value) -> Box<dyn SettlementInterface> {
+	match config.get("attestation").and_then(|v| v.as_str()) {
+		Some("layerzero") => create_layerzero_settlement(config),
+		_ => create_settlement(config),
+	}
+}
+
+/// Prints the orders that were in flight at a past time and their stages.
+async fn print_in_flight(solver: &SolverEngine, at: &str) -> Result<(), Box<dyn std::error::Error>> {
+	let timestamp = match at.parse::<u64>() {
+		Ok(timestamp) => timestamp,
+		Err(_) => chrono::DateTime::parse_from_rfc3339(at)
+			.map_err(|e| format!("Invalid time '{}': {}", at, e))?
+			.timestamp()
+			.max(0) as u64,
+	};
+	let format_time = |timestamp: u64| {
+		chrono::DateTime::from_timestamp(timestamp as i64, 0)
+			.map(|time| time.to_rfc3339())
+			.unwrap_or_else(|| timestamp.to_string())
+	};
+
+	let orders = solver.orders_in_flight_at(timestamp).await?;
+	println!("{} orders in flight at {}", orders.len(), format_time(timestamp));
+	println!("{:<66} {:<14} {:<25} {:<66}", "ORDER", "STAGE", "SINCE", "TRANSACTION");
+	for order in &orders {
+		println!(
+			"{:<66} {:<14} {:<25} {:<66}",
+			order.order_id,
+			format!("{:?}", order.stage),
+			format_time(order.since),
+			order
+				.tx_hash
+				.as_ref()
+				.map(|hash| format!("0x{}", hex::encode(&hash.0)))
+				.unwrap_or_default(),
+		);
+	}
+
+	Ok(())
+}
+
+/// Lists dead-lettered orders, or schedules one to be re-driven.
+///
+/// A re-drive only takes effect once a solver running on the same storage
+/// checks its dead-letter queue.
+async fn manage_dead_letters(
+	solver: &SolverEngine,
+	redrive: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+	if let Some(order_id) = redrive {
+		return match solver.redrive_dead_letter(order_id).await? {
+			Some(_) => {
+				println!("Scheduled {} to be re-driven", order_id);
+				Ok(())
+			}
+			None => Err(format!("Order {} is not dead-lettered", order_id).into()),
+		};
+	}
+
+	let format_time = |timestamp: u64| {
+		chrono::DateTime::from_timestamp(timestamp as i64, 0)
+			.map(|time| time.to_rfc3339())
+			.unwrap_or_else(|| timestamp.to_string())
+	};
+
+	let dead_letters = solver.dead_letters().await?;
+	println!("{} dead-lettered orders", dead_letters.len());
+	println!(
+		"{:<66} {:<11} {:<25} {:<7} {:<25} ERROR",
+		"ORDER", "STAGE", "FAILED", "RETRIES", "NEXT RETRY"
+	);
+	for dead_letter in &dead_letters {
+		println!(
+			"{:<66} {:<11} {:<25} {:<7} {:<25} {}",
+			dead_letter.order_id,
+			format!("{:?}", dead_letter.stage),
+			format_time(dead_letter.failed_at),
+			dead_letter.retries,
+			dead_letter
+				.next_retry_at
+				.map(format_time)
+				.unwrap_or_else(|| "-".to_string()),
+			dead_letter.error,
+		);
+	}
+
+	Ok(())
+}
+
+/// Prints warnings for risky settings in the configuration.
+fn lint_config(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+	let warnings = config.lint();
+	if warnings.is_empty() {
+		println!("No risky settings found");
+		return Ok(());
+	}
+
+	println!("{} warnings", warnings.len());
+	for warning in &warnings {
+		println!("{}", warning);
+	}
+	Ok(())
+}
+
+/// Prints recommended initial balances and optionally funds shortfalls.
+///
+/// Token shortfalls are pulled from the configured treasury when `execute` is
+/// set. Gas token shortfalls are only reported since they must be funded
+/// manually.
+async fn bootstrap_inventory(
+	solver: &SolverEngine,
+	execute: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let recommendations = solver.assess_inventory().await?;
+
+	println!(
+		"{:<10} {:<56} {:>30} {:>30} {:>30}",
+		"CHAIN", "ASSET", "RECOMMENDED", "CURRENT", "SHORTFALL"
+	);
+	for recommendation in &recommendations {
+		let chain_id = recommendation.chain_id;
+		let (asset, recommended, current, shortfall) = match &recommendation.token {
+			Some(token) => match &recommendation.metadata {
+				Some(metadata) => (
+					format!("{} (0x{})", metadata.symbol, hex::encode(&token.0)),
+					metadata.format_amount(recommendation.recommended),
+					metadata.format_amount(recommendation.current),
+					metadata.format_amount(recommendation.shortfall()),
+				),
+				None => (
+					format!("0x{}", hex::encode(&token.0)),
+					recommendation.recommended.to_string(),
+					recommendation.current.to_string(),
+					recommendation.shortfall().to_string(),
+				),
+			},
+			None => {
+				let networks = solver.networks();
+				(
+					networks.gas_token(chain_id).symbol,
+					networks
+						.gas_amount(chain_id, recommendation.recommended)
+						.to_string(),
+					networks.gas_amount(chain_id, recommendation.current).to_string(),
+					networks
+						.gas_amount(chain_id, recommendation.shortfall())
+						.to_string(),
+				)
+			}
+		};
+		println!(
+			"{:<10} {:<56} {:>30} {:>30} {:>30}",
+			chain_id, asset, recommended, current, shortfall
+		);
+	}
+
+	if !execute {
+		return Ok(());
+	}
+
+	for funding in solver.fund_inventory(&recommendations).await? {
+		let chain_id = funding.recommendation.chain_id;
+		let token = funding
+			.recommendation
+			.token
+			.as_ref()
+			.map(|token| hex::encode(&token.0))
+			.unwrap_or_default();
+		match funding.result {
+			Ok(tx_hash) => tracing::info!(
+				chain_id,
+				token = %token,
+				amount = %funding.recommendation.shortfall(),
+				tx_hash = %hex::encode(&tx_hash.0),
+				"Submitted funding transfer"
+			),
+			Err(e) => tracing::warn!(
+				chain_id,
+				token = %token,
+				error = %e,
+				"Failed to fund inventory"
+			),
+		}
+	}
+
+	for recommendation in &recommendations {
+		if recommendation.token.is_none() && !recommendation.shortfall().is_zero() {
+			tracing::warn!(
+				chain_id = recommendation.chain_id,
+				shortfall = %solver
+					.networks()
+					.gas_amount(recommendation.chain_id, recommendation.shortfall()),
+				"Gas token must be funded manually"
+			);
+		}
+	}
+
+	Ok(())
+}
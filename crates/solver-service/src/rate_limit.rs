@@ -0,0 +1,135 @@
+//! Rate limiting for the OIF Solver API.
+//!
+//! Request counters are kept in the solver's storage layer rather than in
+//! process memory, so limits survive restarts and are shared by every replica
+//! that points at the same storage backend. Counters use fixed one-minute
+//! windows, are incremented atomically by the backend, and expire through the
+//! storage TTL once their window has passed.
+//! Quotas are read from the configuration on every request, so reloading the
+//! configuration changes them without resetting the counters.
+
+use actix_web::HttpRequest;
+use alloy_primitives::{hex, keccak256};
+use solver_config::RateLimitConfig;
+use solver_storage::StorageService;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// Storage namespace for rate limit counters.
+const NAMESPACE: &str = "rate_limits";
+
+/// Header clients use to identify themselves with an API key.
+const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Length of a rate limiting window in seconds.
+const WINDOW_SECS: u64 = 60;
+
+/// Returned when a client has exhausted its quota for the current window.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimited {
+	/// Seconds until the current window resets.
+	pub retry_after: u64,
+}
+
+/// Storage-backed rate limiter keyed by API key or client IP.
+pub struct RateLimiter {
+	/// Storage service holding the request counters.
+	storage: Arc<StorageService>,
+}
+
+impl RateLimiter {
+	/// Creates a new RateLimiter keeping its counters in `storage`.
+	pub fn new(storage: Arc<StorageService>) -> Self {
+		Self { storage }
+	}
+
+	/// Records a request and checks it against the client's quota under
+	/// `config`.
+	///
+	/// Clients presenting a configured API key are limited by that key's
+	/// quota; all other clients are limited by IP. Storage failures are
+	/// logged and the request is allowed, so an unavailable backend does not
+	/// take the API down with it.
+	pub async fn check(
+		&self,
+		config: &RateLimitConfig,
+		req: &HttpRequest,
+	) -> Result<(), RateLimited> {
+		let (identity, quota) = identify(config, req);
+
+		let now = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap()
+			.as_secs();
+		let window = now / WINDOW_SECS;
+		let retry_after = (window + 1) * WINDOW_SECS - now;
+		let counter_id = format!("{}:{}", identity, window);
+
+		// Counters outlive their window only until the storage TTL expires them
+		let count = match self
+			.storage
+			.increment(
+				NAMESPACE,
+				&counter_id,
+				Some(Duration::from_secs(retry_after)),
+			)
+			.await
+		{
+			Ok(count) => count,
+			Err(e) => {
+				warn!("Failed to update rate limit counter: {}", e);
+				return Ok(());
+			}
+		};
+
+		if count > u64::from(quota) {
+			return Err(RateLimited { retry_after });
+		}
+
+		Ok(())
+	}
+
+	/// Removes the counters of past windows every window, until the task
+	/// is dropped.
+	///
+	/// Expired counters already read as missing, but every client leaves one
+	/// behind per window, so they are purged rather than left to accumulate
+	/// in backends that only remove expired values on request.
+	pub async fn purge_periodically(self: Arc<Self>) {
+		let mut ticks = tokio::time::interval(Duration::from_secs(WINDOW_SECS));
+		loop {
+			ticks.tick().await;
+			match self.storage.purge_expired(NAMESPACE).await {
+				Ok(purged) if !purged.is_empty() => {
+					debug!(purged = purged.len(), "Purged expired rate limit counters")
+				}
+				Ok(_) => {}
+				Err(e) => warn!("Failed to purge rate limit counters: {}", e),
+			}
+		}
+	}
+}
+
+/// Determines the identity and quota that apply to a request.
+///
+/// API keys are hashed so raw keys never reach the storage backend.
+fn identify(config: &RateLimitConfig, req: &HttpRequest) -> (String, u32) {
+	let api_key = req
+		.headers()
+		.get(API_KEY_HEADER)
+		.and_then(|value| value.to_str().ok());
+
+	if let Some((key, quota)) = api_key.and_then(|key| config.api_keys.get_key_value(key)) {
+		return (format!("key-{}", hex::encode(keccak256(key))), *quota);
+	}
+
+	let ip = req
+		.peer_addr()
+		.map(|addr| addr.ip().to_string())
+		.unwrap_or_else(|| "unknown".to_string());
+	(
+		format!("ip-{}", ip),
+		config.requests_per_minute.saturating_add(config.burst_size),
+	)
+}
@@ -7,20 +7,47 @@
 use actix_cors::Cors;
 use actix_web::{
     middleware::Logger,
-    web::{self, Data, Json},
-    App, HttpResponse, HttpServer, Result as ActixResult,
+    web::{self, Data, Json, Query},
+    App, HttpRequest, HttpResponse, HttpServer, Result as ActixResult,
 };
-use solver_config::ApiConfig;
+use solver_config::{ApiConfig, ClusterConfig};
+use crate::rate_limit::{RateLimited, RateLimiter};
+use solver_core::control::{ControlCommand, ControlError};
 use solver_core::SolverEngine;
-use solver_types::{ErrorResponse, GetQuoteRequest};
+use solver_types::{
+    CrossChainOrder, DeadLettersResponse, ErrorResponse, EventStreamQuery, ExecutePreviewRequest, GetQuoteRequest, IntentStatus, InventoryResponse, PageQuery, PnlQuery, PrioritiesResponse, RouteQuoteQuery,
+    SetPrioritiesRequest, SolverInfoResponse, StatsResponse,
+};
 use std::sync::Arc;
 use tracing::{info, warn};
 
+/// Number of items returned by list endpoints when no limit is given.
+const DEFAULT_PAGE_SIZE: usize = 50;
+
 /// Shared application state for the API server.
 #[derive(Clone)]
 pub struct AppState {
     /// Reference to the solver engine for processing requests.
     pub solver: Arc<SolverEngine>,
+    /// Rate limiter for incoming requests, applied while rate limiting is
+    /// configured.
+    pub rate_limiter: Arc<RateLimiter>,
+    /// Bearer token required for admin endpoints, if admin access is enabled.
+    pub admin_token: Option<String>,
+    /// Peers aggregated into the cluster view, if cluster mode is enabled.
+    pub cluster: Option<ClusterConfig>,
+}
+
+impl AppState {
+    /// Checks a request against the rate limits of the current
+    /// configuration, allowing it if rate limiting is not configured.
+    async fn check_rate_limit(&self, req: &HttpRequest) -> Result<(), RateLimited> {
+        let config = self.solver.current_config();
+        match config.api.as_ref().and_then(|api| api.rate_limiting.as_ref()) {
+            Some(rate_limiting) => self.rate_limiter.check(rate_limiting, req).await,
+            None => Ok(()),
+        }
+    }
 }
 
 /// Starts the HTTP server for the API.
@@ -31,7 +58,14 @@ pub async fn start_server(
     config: ApiConfig,
     solver: Arc<SolverEngine>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let app_state = AppState { solver };
+    let rate_limiter = Arc::new(RateLimiter::new(solver.storage().clone()));
+    tokio::spawn(rate_limiter.clone().purge_periodically());
+    let app_state = AppState {
+        solver,
+        rate_limiter,
+        admin_token: config.admin_token.clone(),
+        cluster: config.cluster.clone(),
+    };
     let bind_address = format!("{}:{}", config.host, config.port);
     
     info!("OIF Solver API server starting on {}", bind_address);
@@ -48,9 +82,43 @@ pub async fn start_server(
                     .allow_any_header()
                     .max_age(3600),
             )
+            .route("/metrics", web::get().to(handle_metrics))
+            .route("/readyz", web::get().to(handle_readyz))
+            .route("/info", web::get().to(handle_info))
+            .route("/ws/events", web::get().to(handle_event_stream))
             .service(
                 web::scope("/api")
                     .route("/quote", web::post().to(handle_quote))
+                    .route("/quote", web::get().to(handle_route_quote))
+                    .route("/orders", web::get().to(handle_list_orders))
+                    .route("/orders", web::post().to(handle_submit_order))
+                    .route("/orders/{id}", web::get().to(handle_get_order))
+                    .route("/orders/{id}/timeline", web::get().to(handle_order_timeline))
+                    .route("/orders/{id}/history", web::get().to(handle_order_history))
+                    .route("/events", web::get().to(handle_list_events))
+                    .route("/stats", web::get().to(handle_stats))
+                    .route("/cluster", web::get().to(handle_cluster))
+                    .route("/inventory", web::get().to(handle_inventory))
+                    .route("/reports/pnl", web::get().to(handle_pnl_report))
+                    .route("/admin/priorities", web::post().to(handle_set_priorities))
+                    .route("/admin/dead-letters", web::get().to(handle_list_dead_letters))
+                    .route(
+                        "/admin/dead-letters/{id}/redrive",
+                        web::post().to(handle_redrive_dead_letter),
+                    )
+                    .route("/admin/intake", web::get().to(handle_intake_status))
+                    .route("/admin/intake/pause", web::post().to(handle_pause_intake))
+                    .route("/admin/intake/resume", web::post().to(handle_resume_intake))
+                    .route("/admin/drain", web::post().to(handle_drain))
+                    .route(
+                        "/admin/orders/{id}/recheck-claim",
+                        web::post().to(handle_recheck_claim),
+                    )
+                    .route("/admin/orders/{id}/claim", web::post().to(handle_trigger_claim))
+                    .route(
+                        "/debug/execute-preview",
+                        web::post().to(handle_execute_preview),
+                    )
             )
     })
     .bind(&bind_address)?
@@ -65,9 +133,21 @@ pub async fn start_server(
 /// This endpoint processes quote requests and returns price estimates
 /// for cross-chain intents following the ERC-7683 standard.
 async fn handle_quote(
+    req: HttpRequest,
     app_state: Data<AppState>,
     request: Json<GetQuoteRequest>,
 ) -> ActixResult<HttpResponse> {
+    if let Err(limited) = app_state.check_rate_limit(&req).await {
+        return Ok(HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", limited.retry_after.to_string()))
+            .json(ErrorResponse {
+                error: "RATE_LIMITED".to_string(),
+                message: "Too many requests".to_string(),
+                details: None,
+                retry_after: Some(limited.retry_after),
+            }));
+    }
+
     match crate::apis::quote::process_quote_request(request.into_inner(), &app_state.solver).await {
         Ok(response) => Ok(HttpResponse::Ok().json(response)),
         Err(e) => {
@@ -80,4 +160,608 @@ async fn handle_quote(
             }))
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Handles GET /quote requests.
+///
+/// Quotes a single route given as query parameters, priced from live gas
+/// estimates and oracle prices.
+async fn handle_route_quote(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+    query: Query<RouteQuoteQuery>,
+) -> ActixResult<HttpResponse> {
+    if let Err(limited) = app_state.check_rate_limit(&req).await {
+        return Ok(HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", limited.retry_after.to_string()))
+            .json(ErrorResponse {
+                error: "RATE_LIMITED".to_string(),
+                message: "Too many requests".to_string(),
+                details: None,
+                retry_after: Some(limited.retry_after),
+            }));
+    }
+
+    match crate::apis::quote::process_route_quote(query.into_inner(), &app_state.solver).await {
+        Ok(response) => Ok(HttpResponse::Ok().json(response)),
+        Err(e) => {
+            warn!("Route quote failed: {}", e);
+            Ok(HttpResponse::BadRequest().json(ErrorResponse {
+                error: "QUOTE_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Handles GET /orders requests.
+///
+/// Returns validated orders in the order they were discovered, paginated
+/// by sequence cursor.
+async fn handle_list_orders(
+    app_state: Data<AppState>,
+    query: Query<PageQuery>,
+) -> ActixResult<HttpResponse> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    match app_state.solver.list_orders(query.cursor, limit).await {
+        Ok(page) => Ok(HttpResponse::Ok().json(page)),
+        Err(e) => {
+            warn!("Order listing failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "STORAGE_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Handles POST /orders requests.
+///
+/// Accepts a signed gasless order and queues it for processing, returning
+/// the order ID to poll.
+async fn handle_submit_order(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+    order: Json<CrossChainOrder>,
+) -> ActixResult<HttpResponse> {
+    if let Err(limited) = app_state.check_rate_limit(&req).await {
+        return Ok(HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", limited.retry_after.to_string()))
+            .json(ErrorResponse {
+                error: "RATE_LIMITED".to_string(),
+                message: "Too many requests".to_string(),
+                details: None,
+                retry_after: Some(limited.retry_after),
+            }));
+    }
+
+    let response =
+        crate::apis::orders::process_order_submission(order.into_inner(), &app_state.solver).await;
+    match response.status {
+        IntentStatus::Accepted => Ok(HttpResponse::Accepted().json(response)),
+        IntentStatus::Rejected => {
+            warn!("Order submission rejected: {:?}", response.message);
+            Ok(HttpResponse::BadRequest().json(response))
+        }
+    }
+}
+
+/// Handles GET /orders/{id} requests.
+///
+/// Returns the order with its status, fill and claim transactions and fill
+/// proof once it has been validated, and 404 until then.
+async fn handle_get_order(
+    app_state: Data<AppState>,
+    order_id: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    match app_state.solver.order_status(&order_id).await {
+        Ok(Some(status)) => Ok(HttpResponse::Ok().json(status)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(ErrorResponse {
+            error: "ORDER_NOT_FOUND".to_string(),
+            message: format!("No validated order {}", order_id),
+            details: None,
+            retry_after: None,
+        })),
+        Err(e) => {
+            warn!("Order lookup failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "STORAGE_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Handles GET /orders/{id}/timeline requests.
+///
+/// Returns the milestones the order reached, with timestamps, durations,
+/// transaction links and failure reasons, ready to render as a timeline.
+async fn handle_order_timeline(
+    app_state: Data<AppState>,
+    order_id: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    match app_state.solver.order_timeline(&order_id).await {
+        Ok(Some(timeline)) => Ok(HttpResponse::Ok().json(timeline)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(ErrorResponse {
+            error: "ORDER_NOT_FOUND".to_string(),
+            message: format!("No events recorded for order {}", order_id),
+            details: None,
+            retry_after: None,
+        })),
+        Err(e) => {
+            warn!("Timeline reconstruction failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "STORAGE_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Handles GET /orders/{id}/history requests.
+///
+/// Returns every event recorded in the order's audit log, with timestamps,
+/// transaction hashes, gas used and errors, in the order they were observed.
+async fn handle_order_history(
+    app_state: Data<AppState>,
+    order_id: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    match app_state.solver.order_history(&order_id).await {
+        Ok(Some(history)) => Ok(HttpResponse::Ok().json(history)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(ErrorResponse {
+            error: "ORDER_NOT_FOUND".to_string(),
+            message: format!("No events recorded for order {}", order_id),
+            details: None,
+            retry_after: None,
+        })),
+        Err(e) => {
+            warn!("Order history retrieval failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "STORAGE_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Handles GET /events requests.
+///
+/// Replays journaled solver events in the order they were observed,
+/// paginated by sequence cursor.
+async fn handle_list_events(
+    app_state: Data<AppState>,
+    query: Query<PageQuery>,
+) -> ActixResult<HttpResponse> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    match app_state.solver.list_events(query.cursor, limit).await {
+        Ok(page) => Ok(HttpResponse::Ok().json(page)),
+        Err(e) => {
+            warn!("Event replay failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "STORAGE_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Handles GET /stats requests.
+///
+/// Reports capital utilization per chain and token: inventory in flight
+/// between fill and claim, idle inventory, and fill-to-claim turnaround.
+async fn handle_stats(app_state: Data<AppState>) -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Ok().json(StatsResponse {
+        capital: app_state.solver.capital_utilization(),
+    }))
+}
+
+/// Handles GET /inventory requests.
+///
+/// Reports the solver's balances as last polled. Responds with 404 unless
+/// inventory polling is configured.
+async fn handle_inventory(app_state: Data<AppState>) -> ActixResult<HttpResponse> {
+    match app_state.solver.inventory() {
+        Some(balances) => Ok(HttpResponse::Ok().json(InventoryResponse { balances })),
+        None => Ok(HttpResponse::NotFound().json(ErrorResponse {
+            error: "NOT_FOUND".to_string(),
+            message: "Inventory polling is not enabled".to_string(),
+            details: None,
+            retry_after: None,
+        })),
+    }
+}
+
+/// Handles GET /reports/pnl requests.
+///
+/// Sums the profit and loss of the orders completed between the optional
+/// `from` and `to` unix timestamps.
+async fn handle_pnl_report(
+    app_state: Data<AppState>,
+    query: Query<PnlQuery>,
+) -> ActixResult<HttpResponse> {
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            return Ok(HttpResponse::BadRequest().json(ErrorResponse {
+                error: "INVALID_RANGE".to_string(),
+                message: "from must not be after to".to_string(),
+                details: None,
+                retry_after: None,
+            }));
+        }
+    }
+
+    match app_state.solver.pnl_report(query.from, query.to).await {
+        Ok(report) => Ok(HttpResponse::Ok().json(report)),
+        Err(e) => {
+            warn!("P&L report failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "STORAGE_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Handles GET /cluster requests.
+///
+/// Combines the readiness and statistics of this instance and its configured
+/// peers. Responds with 404 unless cluster mode is enabled.
+async fn handle_cluster(app_state: Data<AppState>) -> ActixResult<HttpResponse> {
+    let Some(cluster) = &app_state.cluster else {
+        return Ok(HttpResponse::NotFound().json(ErrorResponse {
+            error: "NOT_FOUND".to_string(),
+            message: "Cluster mode is not enabled".to_string(),
+            details: None,
+            retry_after: None,
+        }));
+    };
+
+    Ok(HttpResponse::Ok().json(
+        crate::apis::cluster::process_cluster_request(cluster, &app_state.solver).await,
+    ))
+}
+
+/// Handles POST /admin/priorities requests.
+///
+/// Lets an external system boost or suppress specific orders or discovery
+/// sources for a time window. Requires the configured admin bearer token.
+async fn handle_set_priorities(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+    request: Json<SetPrioritiesRequest>,
+) -> ActixResult<HttpResponse> {
+    if let Some(response) = authorize_admin(&req, &app_state) {
+        return Ok(response);
+    }
+
+    let request = request.into_inner();
+    if request.order_ids.is_empty() && request.sources.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(ErrorResponse {
+            error: "INVALID_REQUEST".to_string(),
+            message: "At least one order ID or source is required".to_string(),
+            details: None,
+            retry_after: None,
+        }));
+    }
+
+    info!(
+        orders = request.order_ids.len(),
+        sources = request.sources.len(),
+        weight = request.weight,
+        duration_seconds = request.duration_seconds,
+        "Updating intent priorities"
+    );
+    let rules = app_state.solver.set_priorities(request);
+    Ok(HttpResponse::Ok().json(PrioritiesResponse { rules }))
+}
+
+/// Handles GET /admin/dead-letters requests.
+///
+/// Lists orders that failed validation, fill or claim, with the error and
+/// when they are next re-driven. Requires the configured admin bearer token.
+async fn handle_list_dead_letters(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    if let Some(response) = authorize_admin(&req, &app_state) {
+        return Ok(response);
+    }
+
+    match app_state.solver.dead_letters().await {
+        Ok(dead_letters) => Ok(HttpResponse::Ok().json(DeadLettersResponse { dead_letters })),
+        Err(e) => {
+            warn!("Dead letter listing failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "STORAGE_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Handles POST /admin/dead-letters/{id}/redrive requests.
+///
+/// Schedules a dead-lettered order to be re-driven on the solver's next
+/// check, even if it has used up its automatic retries. Requires the
+/// configured admin bearer token.
+async fn handle_redrive_dead_letter(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+    order_id: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    if let Some(response) = authorize_admin(&req, &app_state) {
+        return Ok(response);
+    }
+
+    match app_state.solver.redrive_dead_letter(&order_id).await {
+        Ok(Some(dead_letter)) => {
+            info!(order_id = %order_id, "Scheduled dead-lettered order for re-drive");
+            Ok(HttpResponse::Accepted().json(dead_letter))
+        }
+        Ok(None) => Ok(HttpResponse::NotFound().json(ErrorResponse {
+            error: "ORDER_NOT_FOUND".to_string(),
+            message: format!("Order {} is not dead-lettered", order_id),
+            details: None,
+            retry_after: None,
+        })),
+        Err(e) => {
+            warn!("Dead letter re-drive failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "STORAGE_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Handles GET /admin/intake requests.
+///
+/// Reports whether new intents are handled, how many are held, and how many
+/// transactions are still in flight. Requires the configured admin bearer
+/// token.
+async fn handle_intake_status(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    send_control(&req, &app_state, ControlCommand::Status).await
+}
+
+/// Handles POST /admin/intake/pause requests.
+///
+/// Holds newly discovered and submitted intents until intake resumes, while
+/// orders already being worked on continue. Requires the configured admin
+/// bearer token.
+async fn handle_pause_intake(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    send_control(&req, &app_state, ControlCommand::PauseIntake).await
+}
+
+/// Handles POST /admin/intake/resume requests.
+///
+/// Handles held intents and new ones again. Requires the configured admin
+/// bearer token.
+async fn handle_resume_intake(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    send_control(&req, &app_state, ControlCommand::ResumeIntake).await
+}
+
+/// Handles POST /admin/drain requests.
+///
+/// Holds new intents while in-flight transactions confirm; poll
+/// GET /admin/intake until `drained` is true before stopping the solver.
+/// Requires the configured admin bearer token.
+async fn handle_drain(req: HttpRequest, app_state: Data<AppState>) -> ActixResult<HttpResponse> {
+    send_control(&req, &app_state, ControlCommand::Drain).await
+}
+
+/// Handles POST /admin/orders/{id}/recheck-claim requests.
+///
+/// Checks again whether a filled order can be claimed, fetching its fill
+/// proof if it has none, and claims it if so. Requires the configured admin
+/// bearer token.
+async fn handle_recheck_claim(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+    order_id: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let command = ControlCommand::RecheckClaim {
+        order_id: order_id.into_inner(),
+    };
+    send_control(&req, &app_state, command).await
+}
+
+/// Handles POST /admin/orders/{id}/claim requests.
+///
+/// Submits the claim of a filled order right away, even if the settlement
+/// does not report it ready. Requires the configured admin bearer token.
+async fn handle_trigger_claim(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+    order_id: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let command = ControlCommand::Claim {
+        order_id: order_id.into_inner(),
+    };
+    send_control(&req, &app_state, command).await
+}
+
+/// Sends an operator command to the solver engine and responds with its
+/// reply, after checking the admin bearer token.
+async fn send_control(
+    req: &HttpRequest,
+    app_state: &AppState,
+    command: ControlCommand,
+) -> ActixResult<HttpResponse> {
+    if let Some(response) = authorize_admin(req, app_state) {
+        return Ok(response);
+    }
+
+    let e = match app_state.solver.control(command).await {
+        Ok(reply) => return Ok(HttpResponse::Ok().json(reply)),
+        Err(e) => e,
+    };
+    let (mut response, error) = match &e {
+        ControlError::OrderNotFound(_) => (HttpResponse::NotFound(), "ORDER_NOT_FOUND"),
+        ControlError::NotFilled(_) => (HttpResponse::Conflict(), "ORDER_NOT_FILLED"),
+        ControlError::NotRunning => (HttpResponse::ServiceUnavailable(), "NOT_RUNNING"),
+        ControlError::Failed(_) => {
+            warn!("Control command failed: {}", e);
+            (HttpResponse::InternalServerError(), "CONTROL_ERROR")
+        }
+    };
+    Ok(response.json(ErrorResponse {
+        error: error.to_string(),
+        message: e.to_string(),
+        details: None,
+        retry_after: None,
+    }))
+}
+
+/// Handles POST /debug/execute-preview requests.
+///
+/// Runs validation, the execution strategy, fill transaction generation and
+/// an `eth_call` simulation for an order given by ID or as a raw intent, and
+/// returns the result of every step without submitting anything. Requires
+/// the configured admin bearer token.
+async fn handle_execute_preview(
+    req: HttpRequest,
+    app_state: Data<AppState>,
+    request: Json<ExecutePreviewRequest>,
+) -> ActixResult<HttpResponse> {
+    if let Some(response) = authorize_admin(&req, &app_state) {
+        return Ok(response);
+    }
+
+    let request = request.into_inner();
+    if request.order_id.is_some() == request.intent.is_some() {
+        return Ok(HttpResponse::BadRequest().json(ErrorResponse {
+            error: "INVALID_REQUEST".to_string(),
+            message: "Exactly one of orderId and intent must be given".to_string(),
+            details: None,
+            retry_after: None,
+        }));
+    }
+
+    let order_id = request.order_id.clone();
+    match app_state.solver.preview_execution(request).await {
+        Ok(Some(preview)) => Ok(HttpResponse::Ok().json(preview)),
+        Ok(None) => Ok(HttpResponse::NotFound().json(ErrorResponse {
+            error: "ORDER_NOT_FOUND".to_string(),
+            message: format!("Order {} not found", order_id.unwrap_or_default()),
+            details: None,
+            retry_after: None,
+        })),
+        Err(e) => {
+            warn!("Execution preview failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "PREVIEW_ERROR".to_string(),
+                message: e.to_string(),
+                details: None,
+                retry_after: None,
+            }))
+        }
+    }
+}
+
+/// Checks the admin bearer token of a request.
+///
+/// Returns the response to send instead of handling the request when admin
+/// access is disabled or the token does not match.
+fn authorize_admin(req: &HttpRequest, app_state: &AppState) -> Option<HttpResponse> {
+    let Some(admin_token) = &app_state.admin_token else {
+        return Some(HttpResponse::NotFound().finish());
+    };
+
+    let provided = req
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if provided == Some(admin_token.as_str()) {
+        return None;
+    }
+
+    Some(HttpResponse::Unauthorized().json(ErrorResponse {
+        error: "UNAUTHORIZED".to_string(),
+        message: "Missing or invalid admin token".to_string(),
+        details: None,
+        retry_after: None,
+    }))
+}
+
+/// Handles GET /ws/events requests.
+///
+/// Upgrades the connection to a WebSocket streaming the solver's events as
+/// they happen, optionally filtered by order ID, event kind and chain.
+async fn handle_event_stream(
+    req: HttpRequest,
+    body: web::Payload,
+    app_state: Data<AppState>,
+    query: Query<EventStreamQuery>,
+) -> ActixResult<HttpResponse> {
+    let (response, session, messages) = actix_ws::handle(&req, body)?;
+    actix_web::rt::spawn(crate::apis::events::stream_events(
+        app_state.solver.clone(),
+        query.into_inner(),
+        session,
+        messages,
+    ));
+    Ok(response)
+}
+
+/// Handles GET /metrics requests.
+///
+/// Exposes solver engine metrics in the Prometheus text format.
+async fn handle_metrics(app_state: Data<AppState>) -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(app_state.solver.metrics().render()))
+}
+
+/// Handles GET /info requests.
+///
+/// Describes the running build: version, commit and the implementations,
+/// features, standards and chains it was configured with.
+async fn handle_info(app_state: Data<AppState>) -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Ok().json(SolverInfoResponse {
+        solver_id: app_state.solver.config().solver.id.clone(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: option_env!("SOLVER_GIT_COMMIT").map(String::from),
+        capabilities: app_state.solver.capabilities().clone(),
+    }))
+}
+
+/// Handles GET /readyz requests.
+///
+/// Responds with 503 and the unmet preconditions until the solver holds the
+/// balances and approvals it needs to fill orders.
+async fn handle_readyz(app_state: Data<AppState>) -> ActixResult<HttpResponse> {
+    let readiness = app_state.solver.readiness().await;
+    if readiness.ready {
+        Ok(HttpResponse::Ok().json(readiness))
+    } else {
+        Ok(HttpResponse::ServiceUnavailable().json(readiness))
+    }
+}
@@ -0,0 +1,165 @@
+//! Delegation of claims to a third-party claim bot.
+//!
+//! Some operators fill orders from their own infrastructure but leave claiming
+//! to a shared bot that batches claims across solvers. Instead of submitting a
+//! claim, the solver hands the bot the order, its fill proof and the claim
+//! call, together with a signed authorization so the bot can tell that the
+//! solver owning the fill asked for the claim. The bot answers with the hash
+//! of the claim transaction it submitted, which the solver then tracks like
+//! its own.
+
+use crate::SettlementError;
+use alloy_primitives::{hex, keccak256};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solver_types::{Address, FillProof, Signature, Transaction, TransactionHash};
+use std::time::Duration;
+
+/// A claim handed to a claim bot.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimDelegation {
+	/// ID of the order being claimed.
+	pub order_id: String,
+	/// Chain the claim is submitted on.
+	pub chain_id: u64,
+	/// Settler contract the claim calls, as a hex string.
+	pub to: String,
+	/// Calldata of the claim, as a hex string.
+	pub data: String,
+	/// Proof of the fill being claimed.
+	pub fill_proof: FillProof,
+	/// Address of the solver that filled the order, as a hex string.
+	pub solver: String,
+	/// Solver's signature over [`authorization_message`], as a hex string.
+	pub signature: String,
+}
+
+impl ClaimDelegation {
+	/// Builds a delegation of a claim transaction signed by `solver`.
+	pub fn new(
+		order_id: &str,
+		claim_tx: &Transaction,
+		fill_proof: FillProof,
+		solver: &Address,
+		signature: &Signature,
+	) -> Self {
+		Self {
+			order_id: order_id.to_string(),
+			chain_id: claim_tx.chain_id,
+			to: claim_tx
+				.to
+				.as_ref()
+				.map(|to| format!("0x{}", hex::encode(&to.0)))
+				.unwrap_or_default(),
+			data: format!("0x{}", hex::encode(&claim_tx.data)),
+			fill_proof,
+			solver: format!("0x{}", hex::encode(&solver.0)),
+			signature: format!("0x{}", hex::encode(&signature.0)),
+		}
+	}
+}
+
+/// Returns the message a solver signs to authorize a claim.
+///
+/// The message is the keccak256 hash of the order ID, the fill transaction
+/// hash and the hash of the attestation data, concatenated. It is signed as
+/// an EIP-191 personal message.
+pub fn authorization_message(order_id: &str, fill_proof: &FillProof) -> Vec<u8> {
+	let mut preimage = hex::decode(order_id.trim_start_matches("0x"))
+		.unwrap_or_else(|_| order_id.as_bytes().to_vec());
+	preimage.extend_from_slice(&fill_proof.tx_hash.0);
+	preimage.extend_from_slice(
+		keccak256(fill_proof.attestation_data.as_deref().unwrap_or_default()).as_slice(),
+	);
+	keccak256(preimage).to_vec()
+}
+
+/// Trait implemented by claim bots that claims can be delegated to.
+#[async_trait]
+pub trait ClaimDelegate: Send + Sync {
+	/// Hands a claim to the bot, returning the hash of the claim transaction
+	/// it submitted.
+	async fn delegate(
+		&self,
+		delegation: &ClaimDelegation,
+	) -> Result<TransactionHash, SettlementError>;
+}
+
+/// Response of a claim bot accepting a delegation.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DelegationResponse {
+	/// Hash of the submitted claim transaction, as a hex string.
+	tx_hash: String,
+}
+
+/// Claim bot reached over HTTP.
+///
+/// Delegations are POSTed as JSON to the bot's endpoint, which responds with
+/// the hash of the submitted claim as `{"txHash": "0x..."}`.
+pub struct HttpClaimDelegate {
+	/// HTTP client for the bot.
+	client: reqwest::Client,
+	/// URL delegations are POSTed to.
+	endpoint: String,
+	/// Bearer token sent with each delegation, if the bot requires one.
+	auth_token: Option<String>,
+}
+
+impl HttpClaimDelegate {
+	/// Creates a delegate POSTing to `endpoint`, giving up on requests after
+	/// `timeout`.
+	pub fn new(
+		endpoint: String,
+		auth_token: Option<String>,
+		timeout: Duration,
+	) -> Result<Self, SettlementError> {
+		let client = reqwest::Client::builder()
+			.timeout(timeout)
+			.build()
+			.map_err(|e| SettlementError::Delegation(format!("Failed to build client: {}", e)))?;
+		Ok(Self {
+			client,
+			endpoint,
+			auth_token,
+		})
+	}
+}
+
+#[async_trait]
+impl ClaimDelegate for HttpClaimDelegate {
+	async fn delegate(
+		&self,
+		delegation: &ClaimDelegation,
+	) -> Result<TransactionHash, SettlementError> {
+		let body = serde_json::to_string(delegation)
+			.map_err(|e| SettlementError::Delegation(format!("Failed to encode claim: {}", e)))?;
+		let mut request = self
+			.client
+			.post(&self.endpoint)
+			.header("Content-Type", "application/json")
+			.body(body);
+		if let Some(auth_token) = &self.auth_token {
+			request = request.bearer_auth(auth_token);
+		}
+
+		let response = request
+			.send()
+			.await
+			.and_then(|response| response.error_for_status())
+			.map_err(|e| SettlementError::Delegation(format!("Claim bot request failed: {}", e)))?
+			.text()
+			.await
+			.map_err(|e| {
+				SettlementError::Delegation(format!("Failed to read claim bot response: {}", e))
+			})?;
+		let body: DelegationResponse = serde_json::from_str(&response).map_err(|e| {
+			SettlementError::Delegation(format!("Invalid claim bot response: {}", e))
+		})?;
+
+		let tx_hash = hex::decode(body.tx_hash.trim_start_matches("0x"))
+			.map_err(|e| SettlementError::Delegation(format!("Invalid claim hash: {}", e)))?;
+		Ok(TransactionHash(tx_hash))
+	}
+}
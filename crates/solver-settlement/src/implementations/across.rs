@@ -0,0 +1,461 @@
+//! Settlement of Across V3 fills through relayer refunds.
+//!
+//! Across relayers are not paid by claiming against the deposit. The
+//! dataworker bundles the fills of each period into a relayer refund root,
+//! proposes it on the HubPool and, once its liveness period passed without
+//! dispute, relays it to the SpokePool of every repayment chain, which emits
+//! `RelayedRootBundle`. Executing a leaf of the root pays the relayers it
+//! lists and emits `ExecutedRelayerRefundRoot`.
+//!
+//! Fills are repaid on the origin chain in the deposit's input token. A fill
+//! counts as repaid once a leaf refunding the solver in that token was
+//! executed for a root bundle relayed at least a liveness period after the
+//! fill, so that the bundle was proposed after the fill happened.
+
+use crate::{SettlementError, SettlementInterface};
+use alloy_primitives::{hex, Address as AlloyAddress, FixedBytes, U256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{BlockTransactionsKind, Filter};
+use alloy_sol_types::{sol, SolEvent};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use serde::Deserialize;
+use solver_types::{
+	decode_rpc_log, Address, ClaimEstimate, ConfigSchema, Field, FieldType, FillProof, Order,
+	Schema, TokenAmount, TransactionHash,
+};
+use std::collections::{HashMap, HashSet};
+
+sol! {
+	/// Details of how a relay was executed.
+	struct V3RelayExecutionEventInfo {
+		address updatedRecipient;
+		bytes updatedMessage;
+		uint256 updatedOutputAmount;
+		uint8 fillType;
+	}
+
+	/// Emitted by the destination SpokePool for every filled deposit.
+	event FilledV3Relay(
+		address inputToken,
+		address outputToken,
+		uint256 inputAmount,
+		uint256 outputAmount,
+		uint256 repaymentChainId,
+		uint256 indexed originChainId,
+		uint32 indexed depositId,
+		uint32 fillDeadline,
+		uint32 exclusivityDeadline,
+		address exclusiveRelayer,
+		address indexed relayer,
+		address depositor,
+		address recipient,
+		bytes message,
+		V3RelayExecutionEventInfo relayExecutionInfo
+	);
+
+	/// Emitted by a SpokePool when a root bundle is relayed to it.
+	event RelayedRootBundle(uint32 indexed rootBundleId, bytes32 indexed relayerRefundRoot, bytes32 indexed slowRelayRoot);
+
+	/// Emitted by a SpokePool for every executed relayer refund leaf.
+	event ExecutedRelayerRefundRoot(
+		uint256 amountToReturn,
+		uint256 indexed chainId,
+		uint256[] refundAmounts,
+		uint32 indexed rootBundleId,
+		uint32 indexed leafId,
+		address l2TokenAddress,
+		address[] refundAddresses,
+		address caller
+	);
+}
+
+/// Blocks searched per log query when scanning for refunds.
+const LOG_QUERY_BLOCKS: u64 = 2_000;
+
+/// Blocks scanned for refunds on startup when not configured.
+const DEFAULT_LOOKBACK_BLOCKS: u64 = 50_000;
+
+/// Liveness period of root bundle proposals when not configured, in seconds.
+const DEFAULT_BUNDLE_LIVENESS_SECONDS: u64 = 7_200;
+
+/// Across deposit data used for settlement.
+#[derive(Debug, Clone, Deserialize)]
+struct AcrossOrderData {
+	input_token: String,
+	input_amount: U256,
+	origin_chain_id: u64,
+	deposit_id: u32,
+}
+
+/// Root bundles and refunds observed on the repayment chain.
+#[derive(Default)]
+struct RefundScan {
+	/// Last block scanned, if any.
+	scanned_to: Option<u64>,
+	/// Timestamps at which root bundles were relayed, keyed by root bundle ID.
+	relayed_at: HashMap<u32, u64>,
+	/// Root bundles with an executed leaf refunding the solver, along with
+	/// the refunded token.
+	refunded: HashSet<(u32, AlloyAddress)>,
+}
+
+/// Across settlement implementation.
+pub struct AcrossSettlement {
+	/// Provider of the destination chain, where fills happen.
+	provider: RootProvider<Http<reqwest::Client>>,
+	/// Provider of the origin chain, where fills are repaid.
+	origin_provider: RootProvider<Http<reqwest::Client>>,
+	/// SpokePool of the destination chain.
+	spoke_pool_address: AlloyAddress,
+	/// SpokePool of the origin chain.
+	origin_spoke_pool_address: AlloyAddress,
+	/// Address of the solver, which relays fills and is repaid.
+	solver_address: AlloyAddress,
+	/// Liveness period of root bundle proposals in seconds.
+	bundle_liveness_seconds: u64,
+	/// Blocks scanned for refunds before the first check.
+	lookback_blocks: u64,
+	/// Root bundles and refunds observed so far.
+	scan: tokio::sync::Mutex<RefundScan>,
+}
+
+impl AcrossSettlement {
+	/// Creates a new AcrossSettlement instance.
+	pub async fn new(
+		rpc_url: &str,
+		origin_rpc_url: &str,
+		spoke_pool_address: &str,
+		origin_spoke_pool_address: &str,
+		solver_address: &str,
+		bundle_liveness_seconds: u64,
+		lookback_blocks: u64,
+	) -> Result<Self, SettlementError> {
+		let provider = |url: &str| {
+			url.parse()
+				.map(RootProvider::new_http)
+				.map_err(|e| SettlementError::ValidationFailed(format!("Invalid RPC URL: {}", e)))
+		};
+		let address = |name: &str, address: &str| {
+			address
+				.parse::<AlloyAddress>()
+				.map_err(|e| SettlementError::ValidationFailed(format!("Invalid {}: {}", name, e)))
+		};
+
+		Ok(Self {
+			provider: provider(rpc_url)?,
+			origin_provider: provider(origin_rpc_url)?,
+			spoke_pool_address: address("spoke pool address", spoke_pool_address)?,
+			origin_spoke_pool_address: address(
+				"origin spoke pool address",
+				origin_spoke_pool_address,
+			)?,
+			solver_address: address("solver address", solver_address)?,
+			bundle_liveness_seconds,
+			lookback_blocks,
+			scan: tokio::sync::Mutex::new(RefundScan::default()),
+		})
+	}
+
+	/// Records the root bundles and refunds of blocks not scanned yet.
+	async fn scan_refunds(&self, scan: &mut RefundScan) -> Result<(), SettlementError> {
+		let current_block =
+			self.origin_provider.get_block_number().await.map_err(|e| {
+				SettlementError::Network(format!("Failed to get block number: {}", e))
+			})?;
+		let mut from_block = match scan.scanned_to {
+			Some(scanned_to) => scanned_to + 1,
+			None => current_block.saturating_sub(self.lookback_blocks),
+		};
+
+		while from_block <= current_block {
+			let to_block = (from_block + LOG_QUERY_BLOCKS - 1).min(current_block);
+			let filter = Filter::new()
+				.address(self.origin_spoke_pool_address)
+				.event_signature(vec![
+					RelayedRootBundle::SIGNATURE_HASH,
+					ExecutedRelayerRefundRoot::SIGNATURE_HASH,
+				])
+				.from_block(from_block)
+				.to_block(to_block);
+			let logs = self
+				.origin_provider
+				.get_logs(&filter)
+				.await
+				.map_err(|e| SettlementError::Network(format!("Failed to get logs: {}", e)))?;
+
+			for log in logs {
+				if let Ok(relayed) = decode_rpc_log::<RelayedRootBundle>(&log) {
+					let Some(block_number) = log.block_number else {
+						continue;
+					};
+					let block = self
+						.origin_provider
+						.get_block_by_number(block_number.into(), BlockTransactionsKind::Hashes)
+						.await
+						.map_err(|e| {
+							SettlementError::Network(format!("Failed to get block: {}", e))
+						})?
+						.ok_or_else(|| {
+							SettlementError::ValidationFailed("Block not found".to_string())
+						})?;
+					scan.relayed_at
+						.insert(relayed.rootBundleId, block.header.timestamp);
+				} else if let Ok(executed) = decode_rpc_log::<ExecutedRelayerRefundRoot>(&log) {
+					let refunds_solver = executed
+						.refundAddresses
+						.iter()
+						.zip(&executed.refundAmounts)
+						.any(|(address, amount)| {
+							*address == self.solver_address && *amount > U256::ZERO
+						});
+					if refunds_solver {
+						scan.refunded
+							.insert((executed.rootBundleId, executed.l2TokenAddress));
+					}
+				}
+			}
+
+			scan.scanned_to = Some(to_block);
+			from_block = to_block + 1;
+		}
+
+		Ok(())
+	}
+
+	/// Returns whether a refund in `token` was observed for a root bundle
+	/// relayed late enough to include a fill made at `filled_timestamp`.
+	fn is_refunded(&self, scan: &RefundScan, token: AlloyAddress, filled_timestamp: u64) -> bool {
+		let earliest_relay = filled_timestamp + self.bundle_liveness_seconds;
+		scan.refunded
+			.iter()
+			.any(|(root_bundle_id, refunded_token)| {
+				*refunded_token == token
+					&& scan
+						.relayed_at
+						.get(root_bundle_id)
+						.is_some_and(|relayed_at| *relayed_at >= earliest_relay)
+			})
+	}
+}
+
+/// Configuration schema for AcrossSettlement.
+pub struct AcrossSettlementSchema;
+
+impl ConfigSchema for AcrossSettlementSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let url = |field: &str| {
+			Field::new(field, FieldType::String).with_validator(|value| {
+				let url = value.as_str().unwrap();
+				if url.starts_with("http://") || url.starts_with("https://") {
+					Ok(())
+				} else {
+					Err("RPC URL must start with http:// or https://".to_string())
+				}
+			})
+		};
+		let address = |field: &str| {
+			Field::new(field, FieldType::String).with_validator(|value| {
+				let addr = value.as_str().unwrap();
+				if addr.len() != 42 || !addr.starts_with("0x") {
+					return Err("Must be a valid Ethereum address".to_string());
+				}
+				Ok(())
+			})
+		};
+		let count = |field: &str| {
+			Field::new(
+				field,
+				FieldType::Integer {
+					min: Some(0),
+					max: None,
+				},
+			)
+		};
+
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				url("rpc_url"),
+				url("origin_rpc_url"),
+				address("spoke_pool_address"),
+				address("origin_spoke_pool_address"),
+				address("solver_address"),
+			],
+			// Optional fields
+			vec![count("bundle_liveness_seconds"), count("lookback_blocks")],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl SettlementInterface for AcrossSettlement {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(AcrossSettlementSchema)
+	}
+
+	/// Builds a fill proof from the `FilledV3Relay` event of the solver's fill.
+	///
+	/// The proof's attestation data is the deposit's relay hash.
+	async fn get_attestation(
+		&self,
+		order: &Order,
+		tx_hash: &TransactionHash,
+	) -> Result<FillProof, SettlementError> {
+		let order_data: AcrossOrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+
+		let hash = FixedBytes::<32>::from_slice(&tx_hash.0);
+		let receipt = self
+			.provider
+			.get_transaction_receipt(hash)
+			.await
+			.map_err(|e| SettlementError::Network(format!("Failed to get receipt: {}", e)))?
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed("Transaction not found".to_string())
+			})?;
+
+		if !receipt.status() {
+			return Err(SettlementError::ValidationFailed(
+				"Transaction failed".to_string(),
+			));
+		}
+
+		// The fill must be relayed by the solver for the order's deposit
+		let filled = receipt
+			.inner
+			.logs()
+			.iter()
+			.filter(|log| log.address() == self.spoke_pool_address)
+			.filter_map(|log| decode_rpc_log::<FilledV3Relay>(log).ok())
+			.any(|fill| {
+				fill.relayer == self.solver_address
+					&& fill.depositId == order_data.deposit_id
+					&& fill.originChainId == U256::from(order_data.origin_chain_id)
+			});
+		if !filled {
+			return Err(SettlementError::FillMismatch);
+		}
+
+		let tx_block = receipt.block_number.unwrap_or(0);
+		let block = self
+			.provider
+			.get_block_by_number(
+				alloy_rpc_types::BlockNumberOrTag::Number(tx_block),
+				BlockTransactionsKind::Hashes,
+			)
+			.await
+			.map_err(|e| SettlementError::Network(format!("Failed to get block: {}", e)))?
+			.ok_or_else(|| SettlementError::ValidationFailed("Block not found".to_string()))?;
+
+		let relay_hash = hex::decode(&order.id)
+			.map_err(|e| SettlementError::ValidationFailed(format!("Invalid relay hash: {}", e)))?;
+		Ok(FillProof {
+			tx_hash: tx_hash.clone(),
+			block_number: tx_block,
+			oracle_address: self.origin_spoke_pool_address.to_string(),
+			attestation_data: Some(relay_hash),
+			filled_timestamp: block.header.timestamp,
+		})
+	}
+
+	/// Checks whether the fill was repaid on the origin chain.
+	async fn can_claim(&self, order: &Order, fill_proof: &FillProof) -> bool {
+		let Some(token) = serde_json::from_value::<AcrossOrderData>(order.data.clone())
+			.ok()
+			.and_then(|order_data| order_data.input_token.parse::<AlloyAddress>().ok())
+		else {
+			return false;
+		};
+
+		let mut scan = self.scan.lock().await;
+		if self.is_refunded(&scan, token, fill_proof.filled_timestamp) {
+			return true;
+		}
+		if let Err(e) = self.scan_refunds(&mut scan).await {
+			tracing::warn!(error = %e, "Failed to scan Across refunds");
+			return false;
+		}
+		self.is_refunded(&scan, token, fill_proof.filled_timestamp)
+	}
+
+	/// Estimates the refund of the deposit's input on the origin chain.
+	///
+	/// Refunds are paid out by the protocol, so they cost no gas and do not
+	/// expire. LP fees taken from the refund are not accounted for.
+	async fn estimate_claim(
+		&self,
+		order: &Order,
+		_fill_proof: &FillProof,
+	) -> Result<ClaimEstimate, SettlementError> {
+		let order_data: AcrossOrderData =
+			serde_json::from_value(order.data.clone()).map_err(|e| {
+				SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
+			})?;
+		let token = order_data
+			.input_token
+			.parse::<AlloyAddress>()
+			.map_err(|e| {
+				SettlementError::ValidationFailed(format!("Invalid input token: {}", e))
+			})?;
+
+		Ok(ClaimEstimate {
+			reward: vec![TokenAmount {
+				chain_id: order_data.origin_chain_id,
+				token: Address(token.to_vec()),
+				amount: order_data.input_amount,
+			}],
+			gas_estimate: Some(0),
+			deadline: None,
+		})
+	}
+}
+
+/// Factory function to create an Across settlement provider from configuration.
+///
+/// Required configuration parameters:
+/// - `rpc_url`: RPC endpoint of the destination chain, where fills happen
+/// - `origin_rpc_url`: RPC endpoint of the origin chain, where fills are repaid
+/// - `spoke_pool_address`: SpokePool of the destination chain
+/// - `origin_spoke_pool_address`: SpokePool of the origin chain
+/// - `solver_address`: Address of the solver, which relays and is repaid
+///
+/// Optional configuration parameters:
+/// - `bundle_liveness_seconds`: Liveness period of root bundle proposals (default: 7200)
+/// - `lookback_blocks`: Origin blocks searched for refunds on startup (default: 50000)
+pub fn create_across_settlement(config: &toml::Value) -> Box<dyn SettlementInterface> {
+	let get = |field: &str| {
+		config
+			.get(field)
+			.and_then(|v| v.as_str())
+			.unwrap_or_else(|| panic!("{} is required", field))
+	};
+	let count = |field: &str, default: u64| {
+		config
+			.get(field)
+			.and_then(|v| v.as_integer())
+			.map(|value| value as u64)
+			.unwrap_or(default)
+	};
+
+	let settlement = tokio::task::block_in_place(|| {
+		tokio::runtime::Handle::current().block_on(async {
+			AcrossSettlement::new(
+				get("rpc_url"),
+				get("origin_rpc_url"),
+				get("spoke_pool_address"),
+				get("origin_spoke_pool_address"),
+				get("solver_address"),
+				count("bundle_liveness_seconds", DEFAULT_BUNDLE_LIVENESS_SECONDS),
+				count("lookback_blocks", DEFAULT_LOOKBACK_BLOCKS),
+			)
+			.await
+		})
+	});
+
+	Box::new(settlement.expect("Failed to create settlement service"))
+}
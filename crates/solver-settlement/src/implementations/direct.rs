@@ -5,14 +5,16 @@
 //! readiness checks using simple transaction receipt verification without
 //! complex attestation mechanisms.
 
-use crate::{SettlementError, SettlementInterface};
+use crate::{eip7683_claim_estimate, SettlementError, SettlementInterface};
 use alloy_primitives::{Address as AlloyAddress, FixedBytes};
 use alloy_provider::{Provider, RootProvider};
 use alloy_rpc_types::BlockTransactionsKind;
 use alloy_transport_http::Http;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use solver_types::{ConfigSchema, Field, FieldType, FillProof, Order, Schema, TransactionHash};
+use solver_types::{
+	ClaimEstimate, ConfigSchema, Field, FieldType, FillProof, Order, Schema, TransactionHash,
+};
 
 /// Direct settlement implementation.
 ///
@@ -126,9 +128,7 @@ impl SettlementInterface for DirectSettlement {
 			.provider
 			.get_transaction_receipt(hash)
 			.await
-			.map_err(|e| {
-				SettlementError::ValidationFailed(format!("Failed to get receipt: {}", e))
-			})?
+			.map_err(|e| SettlementError::Network(format!("Failed to get receipt: {}", e)))?
 			.ok_or_else(|| {
 				SettlementError::ValidationFailed("Transaction not found".to_string())
 			})?;
@@ -156,9 +156,7 @@ impl SettlementInterface for DirectSettlement {
 				BlockTransactionsKind::Hashes,
 			)
 			.await
-			.map_err(|e| {
-				SettlementError::ValidationFailed(format!("Failed to get block: {}", e))
-			})?;
+			.map_err(|e| SettlementError::Network(format!("Failed to get block: {}", e)))?;
 
 		let block_timestamp = block
 			.ok_or_else(|| SettlementError::ValidationFailed("Block not found".to_string()))?
@@ -210,6 +208,15 @@ impl SettlementInterface for DirectSettlement {
 		// For now, return true if dispute period passed
 		true
 	}
+
+	/// Estimates the claim from the order's inputs and settlement gas limit.
+	async fn estimate_claim(
+		&self,
+		order: &Order,
+		_fill_proof: &FillProof,
+	) -> Result<ClaimEstimate, SettlementError> {
+		eip7683_claim_estimate(order)
+	}
 }
 
 /// Factory function to create a settlement provider from configuration.
@@ -0,0 +1,375 @@
+//! Settlement through fill attestations relayed over LayerZero.
+//!
+//! The fill transaction on the destination chain sends a LayerZero message
+//! carrying the fill attestation to the input oracle on the origin chain. The
+//! message is only usable once the LayerZero endpoint on the origin chain has
+//! delivered it to the oracle, so claims wait until the endpoint's
+//! `PacketDelivered` event for the message has been observed.
+
+use crate::{eip7683_claim_estimate, SettlementError, SettlementInterface};
+use alloy_primitives::{Address as AlloyAddress, FixedBytes};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{BlockTransactionsKind, Filter};
+use alloy_sol_types::{sol, SolEvent};
+use alloy_transport_http::Http;
+use async_trait::async_trait;
+use solver_types::{
+	decode_rpc_log, ClaimEstimate, ConfigSchema, Field, FieldType, FillProof, Order, Schema,
+	TransactionHash,
+};
+use std::collections::HashSet;
+
+sol! {
+	/// Source of a LayerZero message as seen by its receiver.
+	struct Origin {
+		uint32 srcEid;
+		bytes32 sender;
+		uint64 nonce;
+	}
+
+	/// Emitted by the endpoint of the sending chain for every message sent.
+	event PacketSent(bytes encodedPayload, bytes options, address sendLibrary);
+
+	/// Emitted by the endpoint of the receiving chain once a message was
+	/// executed by its receiver.
+	event PacketDelivered(Origin origin, address receiver);
+}
+
+/// Length of an encoded packet header: version, nonce, source endpoint ID,
+/// sender, destination endpoint ID and receiver.
+const PACKET_HEADER_LENGTH: usize = 81;
+
+/// Length of the GUID following the packet header.
+const PACKET_GUID_LENGTH: usize = 32;
+
+/// Blocks searched per log query when scanning for deliveries.
+const LOG_QUERY_BLOCKS: u64 = 2_000;
+
+/// Blocks scanned for deliveries on startup when not configured.
+const DEFAULT_LOOKBACK_BLOCKS: u64 = 5_000;
+
+/// Identifies a LayerZero message on its receiving chain.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PacketKey {
+	/// Endpoint ID of the sending chain.
+	src_eid: u32,
+	/// Sender of the message, left-padded to 32 bytes.
+	sender: [u8; 32],
+	/// Nonce of the message on its path.
+	nonce: u64,
+	/// Receiver of the message.
+	receiver: AlloyAddress,
+}
+
+impl PacketKey {
+	/// Reads the key from an encoded packet.
+	fn from_packet(packet: &[u8]) -> Result<Self, SettlementError> {
+		if packet.len() < PACKET_HEADER_LENGTH + PACKET_GUID_LENGTH {
+			return Err(SettlementError::InvalidProof);
+		}
+
+		let mut sender = [0u8; 32];
+		sender.copy_from_slice(&packet[13..45]);
+		Ok(Self {
+			nonce: u64::from_be_bytes(packet[1..9].try_into().unwrap()),
+			src_eid: u32::from_be_bytes(packet[9..13].try_into().unwrap()),
+			sender,
+			receiver: AlloyAddress::from_slice(&packet[61..81]),
+		})
+	}
+}
+
+/// Deliveries observed on the origin chain.
+#[derive(Default)]
+struct DeliveryScan {
+	/// Last block scanned for deliveries, if any.
+	scanned_to: Option<u64>,
+	/// Messages delivered to their receivers.
+	delivered: HashSet<PacketKey>,
+}
+
+/// LayerZero settlement implementation.
+pub struct LayerZeroSettlement {
+	/// Provider of the destination chain, where fills happen.
+	provider: RootProvider<Http<reqwest::Client>>,
+	/// Provider of the origin chain, where attestations are delivered.
+	origin_provider: RootProvider<Http<reqwest::Client>>,
+	/// Input oracle receiving attestations on the origin chain.
+	oracle_address: AlloyAddress,
+	/// LayerZero endpoint on the destination chain.
+	destination_endpoint: AlloyAddress,
+	/// LayerZero endpoint on the origin chain.
+	origin_endpoint: AlloyAddress,
+	/// Blocks scanned for deliveries before the first check.
+	lookback_blocks: u64,
+	/// Deliveries observed so far.
+	scan: tokio::sync::Mutex<DeliveryScan>,
+}
+
+impl LayerZeroSettlement {
+	/// Creates a new LayerZeroSettlement instance.
+	pub async fn new(
+		rpc_url: &str,
+		origin_rpc_url: &str,
+		oracle_address: &str,
+		destination_endpoint: &str,
+		origin_endpoint: &str,
+		lookback_blocks: u64,
+	) -> Result<Self, SettlementError> {
+		let provider = |url: &str| {
+			url.parse()
+				.map(RootProvider::new_http)
+				.map_err(|e| SettlementError::ValidationFailed(format!("Invalid RPC URL: {}", e)))
+		};
+		let address = |name: &str, address: &str| {
+			address
+				.parse::<AlloyAddress>()
+				.map_err(|e| SettlementError::ValidationFailed(format!("Invalid {}: {}", name, e)))
+		};
+
+		Ok(Self {
+			provider: provider(rpc_url)?,
+			origin_provider: provider(origin_rpc_url)?,
+			oracle_address: address("oracle address", oracle_address)?,
+			destination_endpoint: address("destination endpoint", destination_endpoint)?,
+			origin_endpoint: address("origin endpoint", origin_endpoint)?,
+			lookback_blocks,
+			scan: tokio::sync::Mutex::new(DeliveryScan::default()),
+		})
+	}
+
+	/// Records the deliveries of blocks not scanned yet.
+	async fn scan_deliveries(&self, scan: &mut DeliveryScan) -> Result<(), SettlementError> {
+		let current_block =
+			self.origin_provider.get_block_number().await.map_err(|e| {
+				SettlementError::Network(format!("Failed to get block number: {}", e))
+			})?;
+		let mut from_block = match scan.scanned_to {
+			Some(scanned_to) => scanned_to + 1,
+			None => current_block.saturating_sub(self.lookback_blocks),
+		};
+
+		while from_block <= current_block {
+			let to_block = (from_block + LOG_QUERY_BLOCKS - 1).min(current_block);
+			let filter = Filter::new()
+				.address(self.origin_endpoint)
+				.event_signature(PacketDelivered::SIGNATURE_HASH)
+				.from_block(from_block)
+				.to_block(to_block);
+			let logs = self
+				.origin_provider
+				.get_logs(&filter)
+				.await
+				.map_err(|e| SettlementError::Network(format!("Failed to get logs: {}", e)))?;
+
+			for log in logs {
+				let Ok(delivered) = decode_rpc_log::<PacketDelivered>(&log) else {
+					continue;
+				};
+				scan.delivered.insert(PacketKey {
+					src_eid: delivered.origin.srcEid,
+					sender: delivered.origin.sender.0,
+					nonce: delivered.origin.nonce,
+					receiver: delivered.receiver,
+				});
+			}
+
+			scan.scanned_to = Some(to_block);
+			from_block = to_block + 1;
+		}
+
+		Ok(())
+	}
+}
+
+/// Configuration schema for LayerZeroSettlement.
+pub struct LayerZeroSettlementSchema;
+
+impl ConfigSchema for LayerZeroSettlementSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let url = |field: &str| {
+			Field::new(field, FieldType::String).with_validator(|value| {
+				let url = value.as_str().unwrap();
+				if url.starts_with("http://") || url.starts_with("https://") {
+					Ok(())
+				} else {
+					Err("RPC URL must start with http:// or https://".to_string())
+				}
+			})
+		};
+		let address = |field: &str| {
+			Field::new(field, FieldType::String).with_validator(|value| {
+				let addr = value.as_str().unwrap();
+				if addr.len() != 42 || !addr.starts_with("0x") {
+					return Err("Must be a valid Ethereum address".to_string());
+				}
+				Ok(())
+			})
+		};
+
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				url("rpc_url"),
+				url("origin_rpc_url"),
+				address("oracle_address"),
+				address("destination_endpoint_address"),
+				address("origin_endpoint_address"),
+			],
+			// Optional fields
+			vec![Field::new(
+				"lookback_blocks",
+				FieldType::Integer {
+					min: Some(0),
+					max: None,
+				},
+			)],
+		);
+
+		schema.validate(config)
+	}
+}
+
+#[async_trait]
+impl SettlementInterface for LayerZeroSettlement {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(LayerZeroSettlementSchema)
+	}
+
+	/// Builds a fill proof from the LayerZero message sent by the fill.
+	///
+	/// The proof's attestation data is the encoded packet, which identifies
+	/// the message on the origin chain.
+	async fn get_attestation(
+		&self,
+		_order: &Order,
+		tx_hash: &TransactionHash,
+	) -> Result<FillProof, SettlementError> {
+		let hash = FixedBytes::<32>::from_slice(&tx_hash.0);
+		let receipt = self
+			.provider
+			.get_transaction_receipt(hash)
+			.await
+			.map_err(|e| SettlementError::Network(format!("Failed to get receipt: {}", e)))?
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed("Transaction not found".to_string())
+			})?;
+
+		if !receipt.status() {
+			return Err(SettlementError::ValidationFailed(
+				"Transaction failed".to_string(),
+			));
+		}
+
+		// Find the attestation sent to the input oracle
+		let packet = receipt
+			.inner
+			.logs()
+			.iter()
+			.filter(|log| log.address() == self.destination_endpoint)
+			.filter_map(|log| decode_rpc_log::<PacketSent>(log).ok())
+			.map(|sent| sent.encodedPayload.to_vec())
+			.find(|packet| {
+				PacketKey::from_packet(packet).is_ok_and(|key| key.receiver == self.oracle_address)
+			})
+			.ok_or_else(|| {
+				SettlementError::ValidationFailed(
+					"Fill did not send a LayerZero attestation to the oracle".to_string(),
+				)
+			})?;
+
+		let tx_block = receipt.block_number.unwrap_or(0);
+		let block = self
+			.provider
+			.get_block_by_number(
+				alloy_rpc_types::BlockNumberOrTag::Number(tx_block),
+				BlockTransactionsKind::Hashes,
+			)
+			.await
+			.map_err(|e| SettlementError::Network(format!("Failed to get block: {}", e)))?
+			.ok_or_else(|| SettlementError::ValidationFailed("Block not found".to_string()))?;
+
+		Ok(FillProof {
+			tx_hash: tx_hash.clone(),
+			block_number: tx_block,
+			oracle_address: self.oracle_address.to_string(),
+			attestation_data: Some(packet),
+			filled_timestamp: block.header.timestamp,
+		})
+	}
+
+	/// Checks whether the fill's attestation was delivered to the oracle.
+	async fn can_claim(&self, _order: &Order, fill_proof: &FillProof) -> bool {
+		let Some(key) = fill_proof
+			.attestation_data
+			.as_deref()
+			.and_then(|packet| PacketKey::from_packet(packet).ok())
+		else {
+			return false;
+		};
+
+		let mut scan = self.scan.lock().await;
+		if scan.delivered.contains(&key) {
+			return true;
+		}
+		if let Err(e) = self.scan_deliveries(&mut scan).await {
+			tracing::warn!(error = %e, "Failed to scan LayerZero deliveries");
+			return false;
+		}
+		scan.delivered.contains(&key)
+	}
+
+	/// Estimates the claim from the order's inputs and settlement gas limit.
+	///
+	/// The attestation's message fee is paid when filling, so the claim
+	/// itself only costs the settlement gas.
+	async fn estimate_claim(
+		&self,
+		order: &Order,
+		_fill_proof: &FillProof,
+	) -> Result<ClaimEstimate, SettlementError> {
+		eip7683_claim_estimate(order)
+	}
+}
+
+/// Factory function to create a LayerZero settlement provider from configuration.
+///
+/// Required configuration parameters:
+/// - `rpc_url`: RPC endpoint of the destination chain, where fills happen
+/// - `origin_rpc_url`: RPC endpoint of the origin chain, where attestations arrive
+/// - `oracle_address`: Input oracle receiving attestations on the origin chain
+/// - `destination_endpoint_address`: LayerZero endpoint on the destination chain
+/// - `origin_endpoint_address`: LayerZero endpoint on the origin chain
+///
+/// Optional configuration parameters:
+/// - `lookback_blocks`: Origin blocks searched for deliveries on startup (default: 5000)
+pub fn create_layerzero_settlement(config: &toml::Value) -> Box<dyn SettlementInterface> {
+	let get = |field: &str| {
+		config
+			.get(field)
+			.and_then(|v| v.as_str())
+			.unwrap_or_else(|| panic!("{} is required", field))
+	};
+
+	let lookback_blocks = config
+		.get("lookback_blocks")
+		.and_then(|v| v.as_integer())
+		.map(|blocks| blocks as u64)
+		.unwrap_or(DEFAULT_LOOKBACK_BLOCKS);
+
+	let settlement = tokio::task::block_in_place(|| {
+		tokio::runtime::Handle::current().block_on(async {
+			LayerZeroSettlement::new(
+				get("rpc_url"),
+				get("origin_rpc_url"),
+				get("oracle_address"),
+				get("destination_endpoint_address"),
+				get("origin_endpoint_address"),
+				lookback_blocks,
+			)
+			.await
+		})
+	});
+
+	Box::new(settlement.expect("Failed to create settlement service"))
+}
@@ -0,0 +1,135 @@
+//! Mock settlement for tests.
+//!
+//! Attests every fill without reading any chain, and lets claims through once
+//! a configurable dispute period has passed since the fill was attested.
+
+use crate::{SettlementError, SettlementInterface};
+use async_trait::async_trait;
+use solver_types::testing::MockBehavior;
+use solver_types::{ConfigSchema, Field, FieldType, FillProof, Order, Schema, TransactionHash};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Oracle named in the proofs of mock settlements.
+const MOCK_ORACLE: &str = "0x0000000000000000000000000000000000000000";
+
+/// Settlement attesting every fill.
+///
+/// Scripted failures surface as [`SettlementError::Network`]; scripted
+/// failures of `can_claim` keep the claim from going through.
+pub struct MockSettlement {
+	/// Seconds after an attested fill before its claim goes through.
+	dispute_period_seconds: u64,
+	/// Latencies, scripted failures and call counts.
+	behavior: Arc<MockBehavior>,
+}
+
+impl MockSettlement {
+	/// Creates a mock settlement letting claims through right away.
+	pub fn new() -> Self {
+		Self {
+			dispute_period_seconds: 0,
+			behavior: Arc::new(MockBehavior::new()),
+		}
+	}
+
+	/// Holds claims back until `seconds` after their fill was attested.
+	pub fn with_dispute_period(mut self, seconds: u64) -> Self {
+		self.dispute_period_seconds = seconds;
+		self
+	}
+
+	/// Replaces the settlement's behaviour.
+	pub fn with_behavior(mut self, behavior: MockBehavior) -> Self {
+		self.behavior = Arc::new(behavior);
+		self
+	}
+
+	/// Returns the settlement's behaviour, to script failures and count calls
+	/// once the settlement is handed to the solver.
+	pub fn behavior(&self) -> Arc<MockBehavior> {
+		self.behavior.clone()
+	}
+}
+
+impl Default for MockSettlement {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Returns the current Unix timestamp in seconds.
+fn now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.unwrap_or(0)
+}
+
+/// Configuration schema for MockSettlement.
+pub struct MockSettlementSchema;
+
+impl ConfigSchema for MockSettlementSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let mut optional = vec![Field::new(
+			"dispute_period_seconds",
+			FieldType::Integer {
+				min: Some(0),
+				max: None,
+			},
+		)];
+		optional.extend(MockBehavior::config_fields());
+		Schema::new(vec![], optional).validate(config)
+	}
+}
+
+#[async_trait]
+impl SettlementInterface for MockSettlement {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(MockSettlementSchema)
+	}
+
+	async fn get_attestation(
+		&self,
+		order: &Order,
+		tx_hash: &TransactionHash,
+	) -> Result<FillProof, SettlementError> {
+		self.behavior
+			.call("get_attestation")
+			.await
+			.map_err(SettlementError::Network)?;
+
+		Ok(FillProof {
+			tx_hash: tx_hash.clone(),
+			block_number: 0,
+			oracle_address: MOCK_ORACLE.to_string(),
+			attestation_data: Some(order.id.as_bytes().to_vec()),
+			filled_timestamp: now(),
+		})
+	}
+
+	async fn can_claim(&self, _order: &Order, fill_proof: &FillProof) -> bool {
+		if self.behavior.call("can_claim").await.is_err() {
+			return false;
+		}
+		now() >= fill_proof.filled_timestamp + self.dispute_period_seconds
+	}
+}
+
+/// Factory function to create a mock settlement from configuration.
+///
+/// Optional configuration parameters:
+/// - `dispute_period_seconds`: Seconds after a fill before it can be claimed (default: 0)
+/// - `latency_ms`, `operation_latency_ms`, `fail_first`: see [`MockBehavior::from_config`]
+pub fn create_settlement(config: &toml::Value) -> Box<dyn SettlementInterface> {
+	let dispute_period_seconds = config
+		.get("dispute_period_seconds")
+		.and_then(|v| v.as_integer())
+		.unwrap_or(0) as u64;
+
+	Box::new(
+		MockSettlement::new()
+			.with_dispute_period(dispute_period_seconds)
+			.with_behavior(MockBehavior::from_config(config)),
+	)
+}
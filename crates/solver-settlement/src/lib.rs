@@ -4,14 +4,26 @@
 //! process for solver rewards. It supports different settlement mechanisms
 //! for various order standards.
 
+use alloy_primitives::U256;
 use async_trait::async_trait;
-use solver_types::{ConfigSchema, FillProof, Order, TransactionHash};
+use serde::Deserialize;
+use solver_storage::cache::RpcCache;
+use solver_types::{
+	Address, ClaimEstimate, Classify, ConfigSchema, ErrorCategory, ErrorClass, FillProof, Order,
+	TokenAmount, TransactionHash,
+};
 use std::collections::HashMap;
 use thiserror::Error;
 
+pub mod delegation;
+
 /// Re-export implementations
 pub mod implementations {
+	pub mod across;
 	pub mod direct;
+	pub mod layerzero;
+	#[cfg(feature = "testing")]
+	pub mod mock;
 }
 
 /// Errors that can occur during settlement operations.
@@ -26,6 +38,25 @@ pub enum SettlementError {
 	/// Error that occurs when a fill doesn't match order requirements.
 	#[error("Fill does not match order requirements")]
 	FillMismatch,
+	/// Error that occurs when a claim bot does not accept a delegated claim.
+	#[error("Delegation failed: {0}")]
+	Delegation(String),
+	/// Error that occurs when reading a chain fails.
+	#[error("Network error: {0}")]
+	Network(String),
+}
+
+impl Classify for SettlementError {
+	fn class(&self) -> ErrorClass {
+		match self {
+			Self::Network(_) => ErrorClass::transient(ErrorCategory::Chain),
+			// Claim bots may be briefly unreachable
+			Self::Delegation(_) => ErrorClass::transient(ErrorCategory::Chain),
+			Self::ValidationFailed(_) | Self::InvalidProof | Self::FillMismatch => {
+				ErrorClass::permanent(ErrorCategory::Validation)
+			}
+		}
+	}
 }
 
 /// Trait defining the interface for settlement mechanisms.
@@ -63,6 +94,18 @@ pub trait SettlementInterface: Send + Sync {
 	/// - Solver permissions
 	/// - Reward availability
 	async fn can_claim(&self, order: &Order, fill_proof: &FillProof) -> bool;
+
+	/// Estimates what claiming a filled order pays and costs.
+	///
+	/// Implementations that cannot tell return an empty estimate, which the
+	/// solver treats as a claim of unknown value.
+	async fn estimate_claim(
+		&self,
+		_order: &Order,
+		_fill_proof: &FillProof,
+	) -> Result<ClaimEstimate, SettlementError> {
+		Ok(ClaimEstimate::default())
+	}
 }
 
 /// Service that manages settlement operations with multiple implementations.
@@ -72,12 +115,26 @@ pub trait SettlementInterface: Send + Sync {
 pub struct SettlementService {
 	/// Map of standard names to their settlement implementations.
 	implementations: HashMap<String, Box<dyn SettlementInterface>>,
+	/// Cache of attestations for confirmed fills, if enabled.
+	cache: Option<RpcCache>,
 }
 
 impl SettlementService {
 	/// Creates a new SettlementService with the specified implementations.
 	pub fn new(implementations: HashMap<String, Box<dyn SettlementInterface>>) -> Self {
-		Self { implementations }
+		Self {
+			implementations,
+			cache: None,
+		}
+	}
+
+	/// Sets the cache used for attestations of confirmed fills.
+	///
+	/// Attestations are derived from the fill's receipt and block, which no
+	/// longer change once the fill is confirmed.
+	pub fn with_cache(mut self, cache: RpcCache) -> Self {
+		self.cache = Some(cache);
+		self
 	}
 
 	/// Gets attestation for a filled order using the appropriate settlement implementation.
@@ -94,7 +151,23 @@ impl SettlementService {
 			.get(&order.standard)
 			.ok_or_else(|| SettlementError::ValidationFailed("Unknown standard".into()))?;
 
-		implementation.get_attestation(order, tx_hash).await
+		match &self.cache {
+			Some(cache) => {
+				let key = format!(
+					"attestation:{}:{}",
+					order.standard,
+					alloy_primitives::hex::encode(&tx_hash.0)
+				);
+				cache
+					.get_or_fetch(
+						&key,
+						|_| true,
+						|| implementation.get_attestation(order, tx_hash),
+					)
+					.await
+			}
+			None => implementation.get_attestation(order, tx_hash).await,
+		}
 	}
 
 	/// Checks if an order can be claimed using the appropriate settlement implementation.
@@ -105,4 +178,55 @@ impl SettlementService {
 			false
 		}
 	}
+
+	/// Estimates what claiming an order pays and costs using the appropriate
+	/// settlement implementation.
+	pub async fn estimate_claim(
+		&self,
+		order: &Order,
+		fill_proof: &FillProof,
+	) -> Result<ClaimEstimate, SettlementError> {
+		let implementation = self
+			.implementations
+			.get(&order.standard)
+			.ok_or_else(|| SettlementError::ValidationFailed("Unknown standard".into()))?;
+		implementation.estimate_claim(order, fill_proof).await
+	}
+}
+
+/// Fields of EIP-7683 order data describing what claiming the order pays.
+#[derive(Deserialize)]
+struct Eip7683ClaimData {
+	origin_chain_id: u64,
+	/// Inputs as `[token, amount]`, with the token address in the low 20
+	/// bytes.
+	#[serde(default)]
+	inputs: Vec<[U256; 2]>,
+	#[serde(default)]
+	expires: Option<u32>,
+	#[serde(default)]
+	settle_gas_limit: Option<u64>,
+}
+
+/// Estimates the claim of an EIP-7683 order, which pays the order's inputs
+/// on its origin chain until the order expires.
+pub(crate) fn eip7683_claim_estimate(order: &Order) -> Result<ClaimEstimate, SettlementError> {
+	let order_data: Eip7683ClaimData = serde_json::from_value(order.data.clone()).map_err(|e| {
+		SettlementError::ValidationFailed(format!("Failed to parse order data: {}", e))
+	})?;
+
+	let reward = order_data
+		.inputs
+		.iter()
+		.map(|[token, amount]| TokenAmount {
+			chain_id: order_data.origin_chain_id,
+			token: Address(token.to_be_bytes::<32>()[12..].to_vec()),
+			amount: *amount,
+		})
+		.collect();
+	Ok(ClaimEstimate {
+		reward,
+		gas_estimate: order_data.settle_gas_limit,
+		deadline: order_data.expires.map(u64::from),
+	})
 }
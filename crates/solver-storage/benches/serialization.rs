@@ -0,0 +1,71 @@
+//! Benchmarks of storing and retrieving typed values, which serialize to and
+//! from JSON on every access regardless of the backend.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use solver_storage::implementations::memory::MemoryStorage;
+use solver_storage::StorageService;
+use solver_types::Order;
+use std::collections::HashMap;
+
+/// Builds an order shaped like a validated EIP-7683 order.
+fn order() -> Order {
+	Order {
+		id: "01".repeat(32),
+		standard: "eip7683".to_string(),
+		created_at: 1_700_000_000,
+		data: serde_json::json!({
+			"user": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+			"nonce": 0,
+			"origin_chain_id": 31337,
+			"destination_chain_id": 31338,
+			"expires": 1_700_003_600u32,
+			"fill_deadline": 1_700_003_600u32,
+			"local_oracle": "0x0000000000000000000000000000000000000000",
+			"inputs": [[
+				"0x9fe46736679d2d9a65f0992f2272de9f3c7fa6e0",
+				"0xde0b6b3a7640000",
+			]],
+			"order_id": vec![1; 32],
+			"settle_gas_limit": 200000,
+			"fill_gas_limit": 200000,
+			"outputs": [{
+				"token": "0x9fe46736679d2d9a65f0992f2272de9f3c7fa6e0",
+				"amount": "0xde0b6b3a7640000",
+				"recipient": "0x3c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+				"chain_id": 31338,
+			}],
+		}),
+		implementation: None,
+		metadata: HashMap::new(),
+		exclusive_until: None,
+		routes: Vec::new(),
+	}
+}
+
+fn serialization(c: &mut Criterion) {
+	let rt = tokio::runtime::Builder::new_current_thread()
+		.build()
+		.unwrap();
+	let storage = StorageService::new(Box::new(MemoryStorage::new()));
+	let order = order();
+	rt.block_on(storage.store("orders", &order.id, &order))
+		.unwrap();
+
+	let mut group = c.benchmark_group("storage_serialization");
+	group.bench_function("store_order", |b| {
+		b.iter(|| {
+			rt.block_on(storage.store("orders", &order.id, &order))
+				.unwrap()
+		})
+	});
+	group.bench_function("retrieve_order", |b| {
+		b.iter(|| {
+			rt.block_on(storage.retrieve::<Order>("orders", &order.id))
+				.unwrap()
+		})
+	});
+	group.finish();
+}
+
+criterion_group!(benches, serialization);
+criterion_main!(benches);
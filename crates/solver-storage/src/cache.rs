@@ -0,0 +1,77 @@
+//! Cache for immutable RPC lookups.
+//!
+//! Some chain data never changes once observed: token decimals and symbols,
+//! the presence of contract code, and transaction receipts buried deeper than
+//! finality. Caching it in storage with a long TTL avoids re-fetching it on
+//! every lookup, and lets the cache survive restarts.
+
+use crate::StorageService;
+use serde::{de::DeserializeOwned, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Storage namespace holding cached lookups.
+const RPC_CACHE_NAMESPACE: &str = "rpc_cache";
+
+/// Storage-backed cache of immutable RPC results.
+///
+/// Cache failures never fail a lookup; they only cost an extra RPC call.
+#[derive(Clone)]
+pub struct RpcCache {
+	/// Storage service holding cached values.
+	storage: Arc<StorageService>,
+	/// Time-to-live of cached values.
+	ttl: Duration,
+}
+
+impl RpcCache {
+	/// Creates a new RpcCache whose entries expire after `ttl`.
+	pub fn new(storage: Arc<StorageService>, ttl: Duration) -> Self {
+		Self { storage, ttl }
+	}
+
+	/// Returns a cached value, if present.
+	pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+		self.storage.retrieve(RPC_CACHE_NAMESPACE, key).await.ok()
+	}
+
+	/// Caches a value.
+	pub async fn put<T: Serialize>(&self, key: &str, value: &T) {
+		let _ = self
+			.storage
+			.store_with_ttl(RPC_CACHE_NAMESPACE, key, value, Some(self.ttl))
+			.await;
+	}
+
+	/// Drops a cached value that turned out not to be immutable after all.
+	pub async fn remove(&self, key: &str) {
+		let _ = self.storage.remove(RPC_CACHE_NAMESPACE, key).await;
+	}
+
+	/// Returns a cached value, or fetches and caches it.
+	///
+	/// Values are only cached if `cacheable` returns true, so callers can skip
+	/// results that may still change, such as receipts not yet final.
+	pub async fn get_or_fetch<T, E, F, Fut>(
+		&self,
+		key: &str,
+		cacheable: impl FnOnce(&T) -> bool,
+		fetch: F,
+	) -> Result<T, E>
+	where
+		T: Serialize + DeserializeOwned,
+		F: FnOnce() -> Fut,
+		Fut: Future<Output = Result<T, E>>,
+	{
+		if let Some(value) = self.get(key).await {
+			return Ok(value);
+		}
+
+		let value = fetch().await?;
+		if cacheable(&value) {
+			self.put(key, &value).await;
+		}
+		Ok(value)
+	}
+}
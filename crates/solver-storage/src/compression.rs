@@ -0,0 +1,81 @@
+//! Transparent compression of stored payloads.
+//!
+//! Orders, receipts and proofs are stored as JSON, which compresses well but
+//! adds up quickly in a file backend on a busy solver. A compression policy
+//! makes [`StorageService`] compress payloads with zstd before handing them
+//! to the backend, at a level chosen per namespace.
+//!
+//! Compressed payloads are recognized by the zstd frame magic, which can
+//! never start a JSON document. Payloads written before compression was
+//! enabled, or in namespaces it is disabled for, are read back unchanged, so
+//! a policy can be enabled, changed or dropped on an existing store.
+//!
+//! [`StorageService`]: crate::StorageService
+
+use crate::StorageError;
+use std::collections::HashMap;
+
+/// Bytes starting every zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Which namespaces are compressed, and how hard.
+#[derive(Debug, Clone)]
+pub struct CompressionPolicy {
+	/// zstd level used for namespaces without an override.
+	level: i32,
+	/// Payloads smaller than this many bytes are stored uncompressed.
+	min_size: usize,
+	/// Level overrides keyed by namespace. A level of 0 disables compression.
+	namespaces: HashMap<String, i32>,
+}
+
+impl CompressionPolicy {
+	/// Creates a policy compressing every namespace at `level`, skipping
+	/// payloads smaller than `min_size` bytes.
+	pub fn new(level: i32, min_size: usize) -> Self {
+		Self {
+			level,
+			min_size,
+			namespaces: HashMap::new(),
+		}
+	}
+
+	/// Compresses a namespace at its own level, or not at all if `level` is 0.
+	pub fn with_namespace(mut self, namespace: &str, level: i32) -> Self {
+		self.namespaces.insert(namespace.to_string(), level);
+		self
+	}
+
+	/// Returns the level a namespace is compressed at, if it is compressed.
+	pub fn level(&self, namespace: &str) -> Option<i32> {
+		let level = self
+			.namespaces
+			.get(namespace)
+			.copied()
+			.unwrap_or(self.level);
+		(level != 0).then_some(level)
+	}
+
+	/// Compresses a payload stored in a namespace, if the policy calls for it.
+	pub fn encode(&self, namespace: &str, bytes: Vec<u8>) -> Result<Vec<u8>, StorageError> {
+		match self.level(namespace) {
+			Some(level) if bytes.len() >= self.min_size => zstd::bulk::compress(&bytes, level)
+				.map_err(|e| StorageError::Serialization(format!("Compression failed: {}", e))),
+			_ => Ok(bytes),
+		}
+	}
+}
+
+/// Returns whether a stored payload is compressed.
+pub fn is_compressed(bytes: &[u8]) -> bool {
+	bytes.starts_with(&ZSTD_MAGIC)
+}
+
+/// Decompresses a stored payload, returning uncompressed payloads unchanged.
+pub fn decode(bytes: Vec<u8>) -> Result<Vec<u8>, StorageError> {
+	if !is_compressed(&bytes) {
+		return Ok(bytes);
+	}
+	zstd::stream::decode_all(bytes.as_slice())
+		.map_err(|e| StorageError::Serialization(format!("Decompression failed: {}", e)))
+}
@@ -0,0 +1,107 @@
+//! Seeding of storage backends from JSON snapshots.
+//!
+//! Recovery paths and API handlers read whatever an earlier run left in
+//! storage, which is tedious to reproduce by driving the solver. A snapshot
+//! describes storage contents directly, as a JSON object mapping each
+//! namespace to the values stored under it:
+//!
+//! ```json
+//! {
+//!   "orders": { "<order id>": { "id": "<order id>", "standard": "eip7683", ... } },
+//!   "order_status": { "<order id>": "filling" }
+//! }
+//! ```
+//!
+//! Values are written exactly as [`StorageService::store`] would write them,
+//! so a seeded backend reads back identically through the service. Seeding
+//! goes through [`StorageInterface`] only, so the same snapshot can be loaded
+//! into any backend.
+//!
+//! The crate's `fixtures` directory holds snapshots of orders in common
+//! states, each describing a whole store:
+//!
+//! - `registered.json`: an order validated but not yet decided
+//! - `in_flight.json`: an order whose fill is pending and one filled
+//! - `settled.json`: an order being claimed and one completed
+//! - `failed.json`: an order skipped and one whose fill reverted
+//!
+//! [`StorageService::store`]: crate::StorageService::store
+
+use crate::{StorageError, StorageInterface};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tokio::fs;
+
+/// Storage contents, keyed by namespace and then by id.
+pub type Snapshot = BTreeMap<String, BTreeMap<String, serde_json::Value>>;
+
+/// Writes every value of a snapshot to a backend.
+///
+/// Values already stored under the same keys are overwritten. Returns the
+/// number of values written.
+pub async fn seed(
+	backend: &dyn StorageInterface,
+	snapshot: &Snapshot,
+) -> Result<usize, StorageError> {
+	let mut written = 0;
+	for (namespace, values) in snapshot {
+		for (id, value) in values {
+			let bytes = serde_json::to_vec(value)
+				.map_err(|e| StorageError::Serialization(e.to_string()))?;
+			backend
+				.set_bytes(&format!("{}:{}", namespace, id), bytes, None)
+				.await?;
+			written += 1;
+		}
+	}
+	Ok(written)
+}
+
+/// Reads a snapshot from a JSON file.
+pub async fn read_snapshot(path: &Path) -> Result<Snapshot, StorageError> {
+	let contents = fs::read(path)
+		.await
+		.map_err(|e| StorageError::Backend(format!("Failed to read {}: {}", path.display(), e)))?;
+	serde_json::from_slice(&contents).map_err(|e| {
+		StorageError::Serialization(format!("Invalid snapshot {}: {}", path.display(), e))
+	})
+}
+
+/// Seeds a backend from a snapshot file, or from every `.json` snapshot in a
+/// directory.
+///
+/// Files in a directory are loaded in name order, so later files override
+/// values of earlier ones. Returns the number of values written.
+pub async fn seed_from_path(
+	backend: &dyn StorageInterface,
+	path: &Path,
+) -> Result<usize, StorageError> {
+	if !path.is_dir() {
+		return seed(backend, &read_snapshot(path).await?).await;
+	}
+
+	let mut entries = fs::read_dir(path)
+		.await
+		.map_err(|e| StorageError::Backend(format!("Failed to read {}: {}", path.display(), e)))?;
+	let mut files = Vec::new();
+	while let Some(entry) = entries
+		.next_entry()
+		.await
+		.map_err(|e| StorageError::Backend(e.to_string()))?
+	{
+		let file = entry.path();
+		if file
+			.extension()
+			.is_some_and(|extension| extension == "json")
+		{
+			files.push(file);
+		}
+	}
+	files.sort();
+
+	let mut written = 0;
+	for file in files {
+		written += seed(backend, &read_snapshot(&file).await?).await?;
+	}
+	Ok(written)
+}
@@ -5,23 +5,52 @@
 
 use crate::{StorageError, StorageInterface};
 use async_trait::async_trait;
-use std::path::PathBuf;
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::fs;
+use tokio::sync::Mutex;
+
+/// Expiry metadata stored alongside values written with a TTL.
+#[derive(Debug, Serialize, Deserialize)]
+struct TtlEntry {
+	/// Original, unsanitized storage key.
+	key: String,
+	/// Unix timestamp in seconds after which the value is expired.
+	expires_at: u64,
+}
+
+/// Returns the current unix timestamp in seconds.
+fn now_secs() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
 
 /// File-based storage implementation.
 ///
 /// This implementation stores data as binary files on the filesystem,
 /// providing simple persistence without requiring external dependencies.
+/// Values written with a TTL get a `.ttl` sidecar file recording their
+/// expiry; expired values are treated as missing and removed lazily.
+///
+/// Counters are only incremented atomically among the users of one
+/// FileStorage instance, as files offer no cross-process locking.
 pub struct FileStorage {
 	/// Base directory path for storing files.
 	base_path: PathBuf,
+	/// Serializes counter increments.
+	counters: Mutex<()>,
 }
 
 impl FileStorage {
 	/// Creates a new FileStorage instance with the specified base path.
 	pub fn new(base_path: PathBuf) -> Self {
-		Self { base_path }
+		Self {
+			base_path,
+			counters: Mutex::new(()),
+		}
 	}
 
 	/// Converts a storage key to a filesystem-safe file path.
@@ -29,9 +58,74 @@ impl FileStorage {
 	/// Sanitizes the key by replacing problematic characters and
 	/// appending a .bin extension.
 	fn get_file_path(&self, key: &str) -> PathBuf {
-		// Sanitize key to be filesystem-safe
-		let safe_key = key.replace(['/', ':'], "_");
-		self.base_path.join(format!("{}.bin", safe_key))
+		self.base_path
+			.join(format!("{}.bin", Self::sanitize_key(key)))
+	}
+
+	/// Replaces the characters of a key that are unsafe in file names.
+	fn sanitize_key(key: &str) -> String {
+		key.replace(['/', ':'], "_")
+	}
+
+	/// Returns the path of the TTL sidecar file for a data file.
+	fn get_ttl_path(data_path: &Path) -> PathBuf {
+		data_path.with_extension("ttl")
+	}
+
+	/// Reads the TTL sidecar for a data file, if one exists.
+	async fn read_ttl(ttl_path: &Path) -> Result<Option<TtlEntry>, StorageError> {
+		match fs::read(ttl_path).await {
+			Ok(data) => serde_json::from_slice(&data)
+				.map(Some)
+				.map_err(|e| StorageError::Serialization(e.to_string())),
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(StorageError::Backend(e.to_string())),
+		}
+	}
+
+	/// Writes a data file, creating its directory if needed.
+	///
+	/// Writes to a temporary file first and renames it into place, so readers
+	/// never see a partially written value.
+	async fn write_data(path: &Path, value: Vec<u8>) -> Result<(), StorageError> {
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent)
+				.await
+				.map_err(|e| StorageError::Backend(e.to_string()))?;
+		}
+
+		let temp_path = path.with_extension("tmp");
+		fs::write(&temp_path, value)
+			.await
+			.map_err(|e| StorageError::Backend(e.to_string()))?;
+
+		fs::rename(&temp_path, path)
+			.await
+			.map_err(|e| StorageError::Backend(e.to_string()))
+	}
+
+	/// Removes a file, treating a missing file as success.
+	async fn remove_if_exists(path: &Path) -> Result<(), StorageError> {
+		match fs::remove_file(path).await {
+			Ok(_) => Ok(()),
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+			Err(e) => Err(StorageError::Backend(e.to_string())),
+		}
+	}
+
+	/// Removes the value at the given path if its TTL has elapsed.
+	///
+	/// Returns true if the value was expired and removed.
+	async fn evict_if_expired(&self, data_path: &Path) -> Result<bool, StorageError> {
+		let ttl_path = Self::get_ttl_path(data_path);
+		match Self::read_ttl(&ttl_path).await? {
+			Some(entry) if entry.expires_at <= now_secs() => {
+				Self::remove_if_exists(data_path).await?;
+				Self::remove_if_exists(&ttl_path).await?;
+				Ok(true)
+			}
+			_ => Ok(false),
+		}
 	}
 }
 
@@ -40,6 +134,10 @@ impl StorageInterface for FileStorage {
 	async fn get_bytes(&self, key: &str) -> Result<Vec<u8>, StorageError> {
 		let path = self.get_file_path(key);
 
+		if self.evict_if_expired(&path).await? {
+			return Err(StorageError::NotFound);
+		}
+
 		match fs::read(&path).await {
 			Ok(data) => Ok(data),
 			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(StorageError::NotFound),
@@ -51,46 +149,149 @@ impl StorageInterface for FileStorage {
 		&self,
 		key: &str,
 		value: Vec<u8>,
-		_ttl: Option<Duration>,
+		ttl: Option<Duration>,
 	) -> Result<(), StorageError> {
 		let path = self.get_file_path(key);
+		Self::write_data(&path, value).await?;
 
-		// Create parent directory if it doesn't exist
-		if let Some(parent) = path.parent() {
-			fs::create_dir_all(parent)
-				.await
-				.map_err(|e| StorageError::Backend(e.to_string()))?;
+		// Record expiry in a sidecar file, clearing any TTL from a previous write
+		let ttl_path = Self::get_ttl_path(&path);
+		match ttl {
+			Some(ttl) => {
+				let entry = TtlEntry {
+					key: key.to_string(),
+					expires_at: now_secs().saturating_add(ttl.as_secs()),
+				};
+				let bytes = serde_json::to_vec(&entry)
+					.map_err(|e| StorageError::Serialization(e.to_string()))?;
+				fs::write(&ttl_path, bytes)
+					.await
+					.map_err(|e| StorageError::Backend(e.to_string()))?;
+			}
+			None => Self::remove_if_exists(&ttl_path).await?,
 		}
 
-		// Write atomically by writing to temp file then renaming
-		let temp_path = path.with_extension("tmp");
-		fs::write(&temp_path, value)
-			.await
-			.map_err(|e| StorageError::Backend(e.to_string()))?;
-
-		fs::rename(&temp_path, &path)
-			.await
-			.map_err(|e| StorageError::Backend(e.to_string()))?;
-
-		// TODO: TTL is ignored in this simple implementation
-
 		Ok(())
 	}
 
 	async fn delete(&self, key: &str) -> Result<(), StorageError> {
 		let path = self.get_file_path(key);
 
-		match fs::remove_file(&path).await {
-			Ok(_) => Ok(()),
-			Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
-			Err(e) => Err(StorageError::Backend(e.to_string())),
-		}
+		Self::remove_if_exists(&path).await?;
+		Self::remove_if_exists(&Self::get_ttl_path(&path)).await
 	}
 
 	async fn exists(&self, key: &str) -> Result<bool, StorageError> {
 		let path = self.get_file_path(key);
+		if self.evict_if_expired(&path).await? {
+			return Ok(false);
+		}
 		Ok(path.exists())
 	}
+
+	async fn increment(&self, key: &str, ttl: Option<Duration>) -> Result<u64, StorageError> {
+		let _guard = self.counters.lock().await;
+
+		let count = match self.get_bytes(key).await {
+			Ok(bytes) => serde_json::from_slice::<u64>(&bytes)
+				.map_err(|e| StorageError::Serialization(e.to_string()))?,
+			Err(StorageError::NotFound) => 0,
+			Err(e) => return Err(e),
+		};
+		let next = count + 1;
+		let bytes =
+			serde_json::to_vec(&next).map_err(|e| StorageError::Serialization(e.to_string()))?;
+
+		// Existing counters keep the expiry recorded when they were created
+		if count == 0 {
+			self.set_bytes(key, bytes, ttl).await?;
+		} else {
+			Self::write_data(&self.get_file_path(key), bytes).await?;
+		}
+		Ok(next)
+	}
+
+	/// Lists ids by scanning the storage directory for file names starting
+	/// with the sanitized namespace and prefix.
+	///
+	/// File names do not preserve `:` or `/` within ids, so ids without a TTL
+	/// sidecar recording them are listed with those replaced by `_`; the
+	/// listed ids still address the same values.
+	async fn list_keys(&self, namespace: &str, prefix: &str) -> Result<Vec<String>, StorageError> {
+		let mut entries = match fs::read_dir(&self.base_path).await {
+			Ok(entries) => entries,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+			Err(e) => return Err(StorageError::Backend(e.to_string())),
+		};
+
+		let namespace_prefix = Self::sanitize_key(&format!("{}:", namespace));
+		let file_prefix = Self::sanitize_key(&format!("{}:{}", namespace, prefix));
+		let now = now_secs();
+		let mut ids = Vec::new();
+		while let Some(entry) = entries
+			.next_entry()
+			.await
+			.map_err(|e| StorageError::Backend(e.to_string()))?
+		{
+			let path = entry.path();
+			if path.extension().is_none_or(|ext| ext != "bin") {
+				continue;
+			}
+			let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+				continue;
+			};
+			if !stem.starts_with(&file_prefix) {
+				continue;
+			}
+
+			match Self::read_ttl(&Self::get_ttl_path(&path)).await? {
+				Some(ttl) if ttl.expires_at <= now => continue,
+				Some(ttl) => {
+					if let Some(id) = ttl.key.strip_prefix(&format!("{}:", namespace)) {
+						ids.push(id.to_string());
+					}
+				}
+				None => ids.push(stem[namespace_prefix.len()..].to_string()),
+			}
+		}
+
+		ids.sort();
+		Ok(ids)
+	}
+
+	async fn purge_expired(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+		let mut entries = match fs::read_dir(&self.base_path).await {
+			Ok(entries) => entries,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+			Err(e) => return Err(StorageError::Backend(e.to_string())),
+		};
+
+		let now = now_secs();
+		let mut expired = Vec::new();
+		while let Some(entry) = entries
+			.next_entry()
+			.await
+			.map_err(|e| StorageError::Backend(e.to_string()))?
+		{
+			let ttl_path = entry.path();
+			if ttl_path.extension().is_none_or(|ext| ext != "ttl") {
+				continue;
+			}
+
+			let Some(ttl) = Self::read_ttl(&ttl_path).await? else {
+				continue;
+			};
+			if !ttl.key.starts_with(prefix) || ttl.expires_at > now {
+				continue;
+			}
+
+			Self::remove_if_exists(&ttl_path.with_extension("bin")).await?;
+			Self::remove_if_exists(&ttl_path).await?;
+			expired.push(ttl.key);
+		}
+
+		Ok(expired)
+	}
 }
 
 /// Factory function to create a storage backend from configuration.
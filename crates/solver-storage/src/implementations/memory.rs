@@ -0,0 +1,154 @@
+//! In-memory storage backend.
+//!
+//! Keeps every value in a map for the lifetime of the process. Nothing
+//! survives a restart, which suits tests, local experiments and solvers
+//! whose state is rebuilt from the chain on startup.
+
+use crate::{StorageError, StorageInterface};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Returns the current unix timestamp in seconds.
+fn now_secs() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
+
+/// Value stored in memory, with its expiry if it was written with a TTL.
+struct Entry {
+	/// Stored bytes.
+	value: Vec<u8>,
+	/// Unix timestamp in seconds after which the value is expired.
+	expires_at: Option<u64>,
+}
+
+impl Entry {
+	/// Returns whether the value has expired by `now`.
+	fn is_expired(&self, now: u64) -> bool {
+		self.expires_at.is_some_and(|expires_at| expires_at <= now)
+	}
+}
+
+/// In-memory storage implementation.
+///
+/// Expired values are treated as missing and removed lazily, or by
+/// [`purge_expired`](StorageInterface::purge_expired).
+#[derive(Default)]
+pub struct MemoryStorage {
+	/// Stored values keyed by storage key.
+	entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl MemoryStorage {
+	/// Creates an empty MemoryStorage instance.
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+#[async_trait]
+impl StorageInterface for MemoryStorage {
+	async fn get_bytes(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+		let mut entries = self.entries.lock().unwrap();
+		match entries.get(key) {
+			Some(entry) if entry.is_expired(now_secs()) => {
+				entries.remove(key);
+				Err(StorageError::NotFound)
+			}
+			Some(entry) => Ok(entry.value.clone()),
+			None => Err(StorageError::NotFound),
+		}
+	}
+
+	async fn set_bytes(
+		&self,
+		key: &str,
+		value: Vec<u8>,
+		ttl: Option<Duration>,
+	) -> Result<(), StorageError> {
+		let expires_at = ttl.map(|ttl| now_secs().saturating_add(ttl.as_secs()));
+		self.entries
+			.lock()
+			.unwrap()
+			.insert(key.to_string(), Entry { value, expires_at });
+		Ok(())
+	}
+
+	async fn delete(&self, key: &str) -> Result<(), StorageError> {
+		self.entries.lock().unwrap().remove(key);
+		Ok(())
+	}
+
+	async fn exists(&self, key: &str) -> Result<bool, StorageError> {
+		match self.get_bytes(key).await {
+			Ok(_) => Ok(true),
+			Err(StorageError::NotFound) => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	async fn increment(&self, key: &str, ttl: Option<Duration>) -> Result<u64, StorageError> {
+		let now = now_secs();
+		let mut entries = self.entries.lock().unwrap();
+
+		let current = entries.get(key).filter(|entry| !entry.is_expired(now));
+		let count = match current {
+			Some(entry) => serde_json::from_slice::<u64>(&entry.value)
+				.map_err(|e| StorageError::Serialization(e.to_string()))?,
+			None => 0,
+		};
+		// Existing counters keep the expiry recorded when they were created
+		let expires_at = match current {
+			Some(entry) => entry.expires_at,
+			None => ttl.map(|ttl| now.saturating_add(ttl.as_secs())),
+		};
+
+		let next = count + 1;
+		let value =
+			serde_json::to_vec(&next).map_err(|e| StorageError::Serialization(e.to_string()))?;
+		entries.insert(key.to_string(), Entry { value, expires_at });
+		Ok(next)
+	}
+
+	async fn list_keys(&self, namespace: &str, prefix: &str) -> Result<Vec<String>, StorageError> {
+		let key_prefix = format!("{}:{}", namespace, prefix);
+		let now = now_secs();
+		let mut ids: Vec<String> = self
+			.entries
+			.lock()
+			.unwrap()
+			.iter()
+			.filter(|(key, entry)| key.starts_with(&key_prefix) && !entry.is_expired(now))
+			.map(|(key, _)| key[namespace.len() + 1..].to_string())
+			.collect();
+
+		ids.sort();
+		Ok(ids)
+	}
+
+	async fn purge_expired(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+		let now = now_secs();
+		let mut entries = self.entries.lock().unwrap();
+		let expired: Vec<String> = entries
+			.iter()
+			.filter(|(key, entry)| key.starts_with(prefix) && entry.is_expired(now))
+			.map(|(key, _)| key.clone())
+			.collect();
+		for key in &expired {
+			entries.remove(key);
+		}
+
+		Ok(expired)
+	}
+}
+
+/// Factory function to create an in-memory storage backend.
+///
+/// Takes no configuration parameters.
+pub fn create_storage(_config: &toml::Value) -> Box<dyn StorageInterface> {
+	Box::new(MemoryStorage::new())
+}
@@ -0,0 +1,312 @@
+//! SQLite storage backend for single-node deployments.
+//!
+//! Sits between the file backend and an external database: every value lives
+//! in one embedded database file, written in WAL mode so reads are not
+//! blocked by writes and a crash never leaves a half-written value behind.
+//! Keys are split into their namespace and id, so purging or inspecting a
+//! namespace is an indexed query rather than a scan of every key.
+
+use crate::{StorageError, StorageInterface};
+use async_trait::async_trait;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Schema of the entries table, created if missing.
+const SCHEMA: &str = "
+	CREATE TABLE IF NOT EXISTS entries (
+		namespace TEXT NOT NULL,
+		id TEXT NOT NULL,
+		value BLOB NOT NULL,
+		expires_at INTEGER,
+		PRIMARY KEY (namespace, id)
+	);
+	CREATE INDEX IF NOT EXISTS entries_expires_at ON entries (expires_at)
+		WHERE expires_at IS NOT NULL;
+";
+
+/// How long expired entries are kept before the cleanup task deletes them.
+///
+/// Expired entries already read as missing; keeping them for a while lets the
+/// solver's own sweeps through `purge_expired` see and react to them first.
+const EXPIRED_RETENTION: Duration = Duration::from_secs(600);
+
+/// Returns the current unix timestamp in seconds.
+fn now_secs() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap()
+		.as_secs()
+}
+
+/// Splits a storage key into its namespace and id.
+///
+/// Keys without a namespace are stored under the empty namespace.
+fn split_key(key: &str) -> (&str, &str) {
+	key.split_once(':').unwrap_or(("", key))
+}
+
+/// Maps a SQLite error to a storage error.
+fn backend_error(e: rusqlite::Error) -> StorageError {
+	StorageError::Backend(e.to_string())
+}
+
+/// SQLite-based storage implementation.
+///
+/// Values written with a TTL record their expiry in the same row; expired
+/// values are treated as missing and deleted by a background cleanup task.
+pub struct SqliteStorage {
+	/// Connection to the database, shared with the cleanup task.
+	connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStorage {
+	/// Opens or creates the database at `path` in WAL mode.
+	///
+	/// Starts a cleanup task deleting expired entries every
+	/// `cleanup_interval` if called from within a Tokio runtime.
+	pub fn open(path: &Path, cleanup_interval: Duration) -> Result<Self, StorageError> {
+		if let Some(parent) = path.parent() {
+			std::fs::create_dir_all(parent).map_err(|e| StorageError::Backend(e.to_string()))?;
+		}
+
+		let connection = Connection::open(path).map_err(backend_error)?;
+		connection
+			.pragma_update(None, "journal_mode", "WAL")
+			.map_err(backend_error)?;
+		connection
+			.pragma_update(None, "synchronous", "NORMAL")
+			.map_err(backend_error)?;
+		connection
+			.busy_timeout(Duration::from_secs(5))
+			.map_err(backend_error)?;
+		connection.execute_batch(SCHEMA).map_err(backend_error)?;
+
+		let connection = Arc::new(Mutex::new(connection));
+		if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+			runtime.spawn(clean_up_expired(
+				Arc::downgrade(&connection),
+				cleanup_interval,
+			));
+		}
+		Ok(Self { connection })
+	}
+
+	/// Runs a query against the database on the blocking thread pool.
+	async fn with_connection<T, F>(&self, query: F) -> Result<T, StorageError>
+	where
+		T: Send + 'static,
+		F: FnOnce(&Connection) -> Result<T, rusqlite::Error> + Send + 'static,
+	{
+		let connection = self.connection.clone();
+		tokio::task::spawn_blocking(move || {
+			let connection = connection
+				.lock()
+				.map_err(|_| StorageError::Backend("SQLite connection poisoned".into()))?;
+			query(&connection).map_err(backend_error)
+		})
+		.await
+		.map_err(|e| StorageError::Backend(e.to_string()))?
+	}
+}
+
+/// Deletes entries that expired more than [`EXPIRED_RETENTION`] ago, every
+/// `interval`, until the storage is dropped.
+async fn clean_up_expired(connection: Weak<Mutex<Connection>>, interval: Duration) {
+	let mut ticks = tokio::time::interval(interval);
+	loop {
+		ticks.tick().await;
+		let Some(connection) = connection.upgrade() else {
+			return;
+		};
+
+		let cutoff = now_secs().saturating_sub(EXPIRED_RETENTION.as_secs()) as i64;
+		let result = tokio::task::spawn_blocking(move || {
+			let connection = connection
+				.lock()
+				.map_err(|_| "SQLite connection poisoned".to_string())?;
+			connection
+				.execute(
+					"DELETE FROM entries WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+					params![cutoff],
+				)
+				.map_err(|e| e.to_string())
+		})
+		.await;
+
+		match result {
+			Ok(Ok(0)) => {}
+			Ok(Ok(deleted)) => tracing::debug!(deleted, "Deleted expired SQLite entries"),
+			Ok(Err(e)) => tracing::warn!(error = %e, "Failed to delete expired SQLite entries"),
+			Err(e) => tracing::warn!(error = %e, "SQLite cleanup task failed"),
+		}
+	}
+}
+
+#[async_trait]
+impl StorageInterface for SqliteStorage {
+	async fn get_bytes(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+		let (namespace, id) = split_key(key);
+		let (namespace, id) = (namespace.to_string(), id.to_string());
+		self.with_connection(move |connection| {
+			connection
+				.query_row(
+					"SELECT value FROM entries WHERE namespace = ?1 AND id = ?2
+						AND (expires_at IS NULL OR expires_at > ?3)",
+					params![namespace, id, now_secs() as i64],
+					|row| row.get(0),
+				)
+				.optional()
+		})
+		.await?
+		.ok_or(StorageError::NotFound)
+	}
+
+	async fn set_bytes(
+		&self,
+		key: &str,
+		value: Vec<u8>,
+		ttl: Option<Duration>,
+	) -> Result<(), StorageError> {
+		let (namespace, id) = split_key(key);
+		let (namespace, id) = (namespace.to_string(), id.to_string());
+		let expires_at = ttl.map(|ttl| now_secs().saturating_add(ttl.as_secs()) as i64);
+		self.with_connection(move |connection| {
+			connection.execute(
+				"INSERT INTO entries (namespace, id, value, expires_at) VALUES (?1, ?2, ?3, ?4)
+					ON CONFLICT (namespace, id)
+					DO UPDATE SET value = excluded.value, expires_at = excluded.expires_at",
+				params![namespace, id, value, expires_at],
+			)
+		})
+		.await
+		.map(|_| ())
+	}
+
+	async fn delete(&self, key: &str) -> Result<(), StorageError> {
+		let (namespace, id) = split_key(key);
+		let (namespace, id) = (namespace.to_string(), id.to_string());
+		self.with_connection(move |connection| {
+			connection.execute(
+				"DELETE FROM entries WHERE namespace = ?1 AND id = ?2",
+				params![namespace, id],
+			)
+		})
+		.await
+		.map(|_| ())
+	}
+
+	async fn exists(&self, key: &str) -> Result<bool, StorageError> {
+		let (namespace, id) = split_key(key);
+		let (namespace, id) = (namespace.to_string(), id.to_string());
+		self.with_connection(move |connection| {
+			connection.query_row(
+				"SELECT EXISTS (SELECT 1 FROM entries WHERE namespace = ?1 AND id = ?2
+					AND (expires_at IS NULL OR expires_at > ?3))",
+				params![namespace, id, now_secs() as i64],
+				|row| row.get(0),
+			)
+		})
+		.await
+	}
+
+	/// Increments the counter in a single upsert, so increments are atomic
+	/// across every process sharing the database.
+	async fn increment(&self, key: &str, ttl: Option<Duration>) -> Result<u64, StorageError> {
+		let (namespace, id) = split_key(key);
+		let (namespace, id) = (namespace.to_string(), id.to_string());
+		let expires_at = ttl.map(|ttl| now_secs().saturating_add(ttl.as_secs()) as i64);
+		let value: Vec<u8> = self
+			.with_connection(move |connection| {
+				connection.query_row(
+					"INSERT INTO entries (namespace, id, value, expires_at)
+						VALUES (?1, ?2, CAST('1' AS BLOB), ?4)
+					ON CONFLICT (namespace, id) DO UPDATE SET
+						value = CASE WHEN expires_at IS NOT NULL AND expires_at <= ?3
+							THEN excluded.value
+							ELSE CAST(CAST(value AS INTEGER) + 1 AS BLOB) END,
+						expires_at = CASE WHEN expires_at IS NOT NULL AND expires_at <= ?3
+							THEN excluded.expires_at
+							ELSE expires_at END
+					RETURNING value",
+					params![namespace, id, now_secs() as i64, expires_at],
+					|row| row.get(0),
+				)
+			})
+			.await?;
+		serde_json::from_slice(&value).map_err(|e| StorageError::Serialization(e.to_string()))
+	}
+
+	async fn list_keys(&self, namespace: &str, prefix: &str) -> Result<Vec<String>, StorageError> {
+		let (namespace, prefix) = (namespace.to_string(), prefix.to_string());
+		self.with_connection(move |connection| {
+			let mut statement = connection.prepare(
+				"SELECT id FROM entries WHERE namespace = ?1
+					AND substr(id, 1, length(?2)) = ?2
+					AND (expires_at IS NULL OR expires_at > ?3)
+					ORDER BY id",
+			)?;
+			let ids = statement
+				.query_map(params![namespace, prefix, now_secs() as i64], |row| {
+					row.get::<_, String>(0)
+				})?
+				.collect::<Result<Vec<_>, _>>()?;
+			Ok(ids)
+		})
+		.await
+	}
+
+	async fn purge_expired(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+		let (namespace, id_prefix) = split_key(prefix);
+		let (namespace, id_prefix) = (namespace.to_string(), id_prefix.to_string());
+		self.with_connection(move |connection| {
+			let mut statement = connection.prepare(
+				"DELETE FROM entries WHERE namespace = ?1
+					AND substr(id, 1, length(?2)) = ?2
+					AND expires_at IS NOT NULL AND expires_at <= ?3
+					RETURNING id",
+			)?;
+			let ids = statement
+				.query_map(params![namespace, id_prefix, now_secs() as i64], |row| {
+					row.get::<_, String>(0)
+				})?
+				.collect::<Result<Vec<_>, _>>()?;
+			Ok(ids
+				.into_iter()
+				.map(|id| {
+					if namespace.is_empty() {
+						id
+					} else {
+						format!("{}:{}", namespace, id)
+					}
+				})
+				.collect())
+		})
+		.await
+	}
+}
+
+/// Factory function to create a SQLite storage backend from configuration.
+///
+/// Configuration parameters:
+/// - `database_path`: Path of the database file (default: "./data/solver.db")
+/// - `cleanup_interval_seconds`: Seconds between deletions of expired entries (default: 60)
+pub fn create_storage(config: &toml::Value) -> Box<dyn StorageInterface> {
+	let database_path = config
+		.get("database_path")
+		.and_then(|v| v.as_str())
+		.unwrap_or("./data/solver.db");
+	let cleanup_interval = config
+		.get("cleanup_interval_seconds")
+		.and_then(|v| v.as_integer())
+		.filter(|seconds| *seconds > 0)
+		.unwrap_or(60) as u64;
+
+	let storage = SqliteStorage::open(
+		Path::new(database_path),
+		Duration::from_secs(cleanup_interval),
+	)
+	.expect("Failed to open SQLite database");
+	Box::new(storage)
+}
@@ -6,14 +6,28 @@
 
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Serialize};
+use solver_types::{Classify, ErrorCategory, ErrorClass};
+use std::collections::HashMap;
 use std::time::Duration;
 use thiserror::Error;
+use tokio::sync::Mutex;
+
+pub mod cache;
+pub mod compression;
+pub mod fixtures;
+
+use compression::CompressionPolicy;
 
 /// Re-export implementations
 pub mod implementations {
 	pub mod file;
+	pub mod memory;
+	pub mod sqlite;
 }
 
+/// Namespace where the last allocated value of each sequence is persisted.
+const SEQUENCES_NAMESPACE: &str = "sequences";
+
 /// Errors that can occur during storage operations.
 #[derive(Debug, Error)]
 pub enum StorageError {
@@ -28,6 +42,18 @@ pub enum StorageError {
 	Backend(String),
 }
 
+impl Classify for StorageError {
+	fn class(&self) -> ErrorClass {
+		match self {
+			// A backend may be briefly unavailable, such as a locked database
+			Self::Backend(_) => ErrorClass::transient(ErrorCategory::Storage),
+			Self::NotFound | Self::Serialization(_) => {
+				ErrorClass::permanent(ErrorCategory::Storage)
+			}
+		}
+	}
+}
+
 /// Trait defining the low-level interface for storage backends.
 ///
 /// This trait must be implemented by any storage backend that wants to
@@ -51,6 +77,26 @@ pub trait StorageInterface: Send + Sync {
 
 	/// Checks if a key exists in storage.
 	async fn exists(&self, key: &str) -> Result<bool, StorageError>;
+
+	/// Atomically increments the counter stored at `key` and returns its new
+	/// value.
+	///
+	/// Missing or expired counters are created at 1 with the given TTL. The
+	/// TTL is only set when the counter is created, so it expires a fixed time
+	/// after its first increment however often it is incremented.
+	async fn increment(&self, key: &str, ttl: Option<Duration>) -> Result<u64, StorageError>;
+
+	/// Lists the ids of the unexpired values in a namespace whose id starts
+	/// with `prefix`.
+	async fn list_keys(&self, namespace: &str, prefix: &str) -> Result<Vec<String>, StorageError>;
+
+	/// Removes entries under the given key prefix whose TTL has elapsed.
+	///
+	/// Returns the full keys of the removed entries so callers can react to
+	/// expiry. Backends that expire entries natively may return an empty list.
+	async fn purge_expired(&self, _prefix: &str) -> Result<Vec<String>, StorageError> {
+		Ok(Vec::new())
+	}
 }
 
 /// High-level storage service that provides typed operations.
@@ -61,18 +107,72 @@ pub trait StorageInterface: Send + Sync {
 pub struct StorageService {
 	/// The underlying storage backend implementation.
 	backend: Box<dyn StorageInterface>,
+	/// Last allocated value of each sequence, loaded lazily from the backend.
+	sequences: Mutex<HashMap<String, u64>>,
+	/// Compression applied to stored payloads, if enabled.
+	compression: Option<CompressionPolicy>,
 }
 
 impl StorageService {
 	/// Creates a new StorageService with the specified backend.
 	pub fn new(backend: Box<dyn StorageInterface>) -> Self {
-		Self { backend }
+		Self {
+			backend,
+			sequences: Mutex::new(HashMap::new()),
+			compression: None,
+		}
+	}
+
+	/// Compresses stored payloads according to a policy.
+	///
+	/// Compressed and uncompressed payloads are read back alike, whatever the
+	/// policy, so it only affects what is written from now on.
+	pub fn with_compression(mut self, policy: CompressionPolicy) -> Self {
+		self.compression = Some(policy);
+		self
+	}
+
+	/// Allocates the next value of a named, monotonically increasing sequence.
+	///
+	/// Sequences start at 1 and are persisted after every allocation, so values
+	/// are never reused across restarts. Allocation is serialized, making the
+	/// sequence a total order over everything it is assigned to.
+	pub async fn next_sequence(&self, name: &str) -> Result<u64, StorageError> {
+		let mut sequences = self.sequences.lock().await;
+		let current = match sequences.get(name) {
+			Some(current) => *current,
+			None => self.load_sequence(name).await?,
+		};
+
+		let next = current + 1;
+		self.store(SEQUENCES_NAMESPACE, name, &next).await?;
+		sequences.insert(name.to_string(), next);
+		Ok(next)
+	}
+
+	/// Returns the last allocated value of a sequence, or 0 if none was allocated.
+	pub async fn current_sequence(&self, name: &str) -> Result<u64, StorageError> {
+		let sequences = self.sequences.lock().await;
+		match sequences.get(name) {
+			Some(current) => Ok(*current),
+			None => self.load_sequence(name).await,
+		}
+	}
+
+	/// Reads the persisted value of a sequence from the backend.
+	async fn load_sequence(&self, name: &str) -> Result<u64, StorageError> {
+		match self.retrieve(SEQUENCES_NAMESPACE, name).await {
+			Ok(current) => Ok(current),
+			Err(StorageError::NotFound) => Ok(0),
+			Err(e) => Err(e),
+		}
 	}
 
 	/// Stores a serializable value with optional time-to-live.
 	///
 	/// The namespace and id are combined to form a unique key.
-	/// The data is serialized to JSON before storage.
+	/// The data is serialized to JSON, and compressed if the namespace's
+	/// compression policy calls for it, before storage.
 	pub async fn store_with_ttl<T: Serialize>(
 		&self,
 		namespace: &str,
@@ -81,8 +181,7 @@ impl StorageService {
 		ttl: Option<Duration>,
 	) -> Result<(), StorageError> {
 		let key = format!("{}:{}", namespace, id);
-		let bytes =
-			serde_json::to_vec(data).map_err(|e| StorageError::Serialization(e.to_string()))?;
+		let bytes = self.encode(namespace, data)?;
 		self.backend.set_bytes(&key, bytes, ttl).await
 	}
 
@@ -95,26 +194,48 @@ impl StorageService {
 		id: &str,
 		data: &T,
 	) -> Result<(), StorageError> {
-		let key = format!("{}:{}", namespace, id);
-		let bytes =
-			serde_json::to_vec(data).map_err(|e| StorageError::Serialization(e.to_string()))?;
-		self.backend.set_bytes(&key, bytes, None).await
+		self.store_with_ttl(namespace, id, data, None).await
 	}
 
 	/// Retrieves and deserializes a value from storage.
 	///
 	/// The namespace and id are combined to form the lookup key.
-	/// The retrieved bytes are deserialized from JSON.
+	/// The retrieved bytes are decompressed if needed and deserialized from
+	/// JSON.
 	pub async fn retrieve<T: DeserializeOwned>(
 		&self,
 		namespace: &str,
 		id: &str,
 	) -> Result<T, StorageError> {
 		let key = format!("{}:{}", namespace, id);
-		let bytes = self.backend.get_bytes(&key).await?;
+		let bytes = compression::decode(self.backend.get_bytes(&key).await?)?;
 		serde_json::from_slice(&bytes).map_err(|e| StorageError::Serialization(e.to_string()))
 	}
 
+	/// Serializes a value to JSON and compresses it per the namespace's policy.
+	fn encode<T: Serialize>(&self, namespace: &str, data: &T) -> Result<Vec<u8>, StorageError> {
+		let bytes =
+			serde_json::to_vec(data).map_err(|e| StorageError::Serialization(e.to_string()))?;
+		match &self.compression {
+			Some(policy) => policy.encode(namespace, bytes),
+			None => Ok(bytes),
+		}
+	}
+
+	/// Atomically increments a counter and returns its new value.
+	///
+	/// Counters are created at 1, expiring after `ttl` if given, and are
+	/// stored as JSON numbers so they can also be read with `retrieve`.
+	pub async fn increment(
+		&self,
+		namespace: &str,
+		id: &str,
+		ttl: Option<Duration>,
+	) -> Result<u64, StorageError> {
+		let key = format!("{}:{}", namespace, id);
+		self.backend.increment(&key, ttl).await
+	}
+
 	/// Removes a value from storage.
 	///
 	/// The namespace and id are combined to form the key to delete.
@@ -122,4 +243,50 @@ impl StorageService {
 		let key = format!("{}:{}", namespace, id);
 		self.backend.delete(&key).await
 	}
+
+	/// Returns whether a value exists in storage.
+	pub async fn exists(&self, namespace: &str, id: &str) -> Result<bool, StorageError> {
+		let key = format!("{}:{}", namespace, id);
+		self.backend.exists(&key).await
+	}
+
+	/// Lists the ids of the values in a namespace whose id starts with
+	/// `prefix`.
+	pub async fn list_keys(
+		&self,
+		namespace: &str,
+		prefix: &str,
+	) -> Result<Vec<String>, StorageError> {
+		self.backend.list_keys(namespace, prefix).await
+	}
+
+	/// Retrieves every value in a namespace together with its id.
+	///
+	/// Values removed while the namespace is read are skipped.
+	pub async fn retrieve_all<T: DeserializeOwned>(
+		&self,
+		namespace: &str,
+	) -> Result<Vec<(String, T)>, StorageError> {
+		let mut values = Vec::new();
+		for id in self.list_keys(namespace, "").await? {
+			match self.retrieve(namespace, &id).await {
+				Ok(value) => values.push((id, value)),
+				Err(StorageError::NotFound) => continue,
+				Err(e) => return Err(e),
+			}
+		}
+		Ok(values)
+	}
+
+	/// Removes expired values from a namespace.
+	///
+	/// Returns the ids of the values that were removed.
+	pub async fn purge_expired(&self, namespace: &str) -> Result<Vec<String>, StorageError> {
+		let prefix = format!("{}:", namespace);
+		let keys = self.backend.purge_expired(&prefix).await?;
+		Ok(keys
+			.into_iter()
+			.filter_map(|key| key.strip_prefix(&prefix).map(str::to_string))
+			.collect())
+	}
 }
@@ -0,0 +1,62 @@
+//! Benchmarks of event bus throughput, which every service of the engine
+//! publishes to and every subscriber receives a clone of each event from.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use solver_types::{DiscoveryEvent, EventBus, Order, OrderEvent, SolverEvent};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Events published per iteration.
+const BATCH: usize = 1024;
+
+fn order() -> Arc<Order> {
+	Arc::new(Order {
+		id: "01".repeat(32),
+		standard: "eip7683".to_string(),
+		created_at: 0,
+		data: serde_json::json!({ "outputs": [{ "amount": "1000000000000000000" }] }),
+		implementation: None,
+		metadata: HashMap::new(),
+		exclusive_until: None,
+		routes: Vec::new(),
+	})
+}
+
+fn throughput(c: &mut Criterion) {
+	let order = order();
+	let mut group = c.benchmark_group("event_bus");
+	group.throughput(Throughput::Elements(BATCH as u64));
+	for subscribers in [1, 4, 16] {
+		let bus = EventBus::new(BATCH);
+		let mut receivers: Vec<_> = (0..subscribers).map(|_| bus.subscribe()).collect();
+		group.bench_with_input(
+			BenchmarkId::new("publish_receive", subscribers),
+			&subscribers,
+			|b, _| {
+				b.iter(|| {
+					for i in 0..BATCH {
+						let event = if i % 2 == 0 {
+							SolverEvent::Discovery(DiscoveryEvent::IntentValidated {
+								intent_id: order.id.clone(),
+								order: order.clone(),
+							})
+						} else {
+							SolverEvent::Order(OrderEvent::Skipped {
+								order_id: order.id.clone(),
+								reason: "gas price too high".to_string(),
+							})
+						};
+						bus.publish(event).unwrap();
+					}
+					for receiver in &mut receivers {
+						while receiver.try_recv().is_ok() {}
+					}
+				})
+			},
+		);
+	}
+	group.finish();
+}
+
+criterion_group!(benches, throughput);
+criterion_main!(benches);
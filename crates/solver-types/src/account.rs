@@ -9,7 +9,7 @@ use alloy_rpc_types::TransactionRequest;
 /// Blockchain address representation.
 ///
 /// Stores addresses as raw bytes to support different blockchain formats.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct Address(pub Vec<u8>);
 
 /// Cryptographic signature representation.
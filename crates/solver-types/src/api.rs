@@ -3,9 +3,12 @@
 //! This module defines the request and response types for the OIF Solver API
 //! endpoints, following the ERC-7683 Cross-Chain Intents Standard.
 
+use crate::{
+    ErrorClass, ExecutionParams, FillProof, GasTokenAmount, Intent, Order, SolverEvent, TokenAmount,
+};
 use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Asset amount representation using ERC-7930 interoperable address format.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +45,26 @@ pub struct GetQuoteRequest {
     pub preference: Option<QuotePreference>,
 }
 
+/// Query parameters for a quote on a single route.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteQuoteQuery {
+    /// Chain the user deposits the input on
+    #[serde(rename = "originChainId")]
+    pub origin_chain_id: u64,
+    /// Chain the solver delivers the output on
+    #[serde(rename = "destinationChainId")]
+    pub destination_chain_id: u64,
+    /// Token deposited on the origin chain
+    #[serde(rename = "inputToken")]
+    pub input_token: String,
+    /// Token received on the destination chain
+    #[serde(rename = "outputToken")]
+    pub output_token: String,
+    /// Input amount in the token's smallest unit
+    #[serde(with = "u256_serde")]
+    pub amount: U256,
+}
+
 /// Quote optimization preferences.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -121,6 +144,9 @@ pub struct CrossChainOrder {
     pub order_data: serde_json::Value,
     /// User authorization signature
     pub signature: String,
+    /// Opaque metadata preserved on the order (referral codes, campaign tags)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
 /// Response for intent submission.
@@ -131,7 +157,7 @@ pub struct SubmitIntentResponse {
     pub intent_id: String,
     /// Acceptance status
     pub status: IntentStatus,
-    /// Error details if rejected
+    /// Error details if rejected, or a note if the intent was already received
     pub message: Option<String>,
 }
 
@@ -175,6 +201,677 @@ pub struct IntentStatusResponse {
     pub last_updated: u64,
 }
 
+/// Execution status of a validated order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderStatusResponse {
+    /// Order identifier
+    #[serde(rename = "orderId")]
+    pub order_id: String,
+    /// Current processing status
+    pub status: DetailedIntentStatus,
+    /// The validated order
+    pub order: Order,
+    /// Hash of the latest fill transaction, if submitted
+    #[serde(rename = "fillTxHash")]
+    pub fill_tx_hash: Option<String>,
+    /// Proof of the fill, once it has been attested
+    #[serde(rename = "fillProof")]
+    pub fill_proof: Option<FillProof>,
+    /// Hash of the latest claim transaction, if submitted
+    #[serde(rename = "claimTxHash")]
+    pub claim_tx_hash: Option<String>,
+}
+
+/// Readiness of the solver to fill orders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    /// Whether every precondition is met
+    pub ready: bool,
+    /// Preconditions that are not met
+    pub unmet: Vec<UnmetPrecondition>,
+}
+
+/// Economic precondition for filling orders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Precondition {
+    /// The solver's address is known
+    Account,
+    /// Enough gas token is held on a chain
+    GasBalance,
+    /// Enough of a delivered token is held on a chain
+    Inventory,
+    /// A settler may spend a token the solver delivers
+    Approval,
+    /// Fills are not paused by the gas budget or failure circuit breaker
+    GasBudget,
+}
+
+/// A precondition the solver does not meet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmetPrecondition {
+    /// Precondition that failed
+    pub precondition: Precondition,
+    /// Chain the precondition applies to, if any
+    #[serde(rename = "chainId")]
+    pub chain_id: Option<u64>,
+    /// Token the precondition applies to, if any
+    pub token: Option<String>,
+    /// Why the precondition is not met
+    pub message: String,
+}
+
+/// Lifecycle milestone of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Milestone {
+    Discovered,
+    Validated,
+    Rejected,
+    Invalidated,
+    Deferred,
+    Executing,
+    Skipped,
+    Expired,
+    FilledByOther,
+    FillSubmitted,
+    FillReplaced,
+    FillReorged,
+    Filled,
+    ProofReady,
+    ClaimReady,
+    ClaimSubmitted,
+    ClaimReplaced,
+    ClaimReorged,
+    Claimed,
+    TransactionFailed,
+    Completed,
+}
+
+/// A milestone an order reached and when.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    /// Milestone reached
+    pub milestone: Milestone,
+    /// When the milestone was reached (Unix timestamp)
+    pub timestamp: u64,
+    /// Seconds since the previous milestone
+    #[serde(rename = "durationSeconds")]
+    pub duration_seconds: u64,
+    /// Chain of the transaction involved, if any
+    #[serde(rename = "chainId")]
+    pub chain_id: Option<u64>,
+    /// Hash of the transaction involved, if any
+    #[serde(rename = "txHash")]
+    pub tx_hash: Option<String>,
+    /// Block explorer link to the transaction, if the chain has an explorer
+    #[serde(rename = "txUrl")]
+    pub tx_url: Option<String>,
+    /// Why the order was rejected, skipped or failed at this milestone
+    pub failure: Option<String>,
+}
+
+/// Ordered lifecycle milestones of an order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderTimelineResponse {
+    /// Order identifier
+    #[serde(rename = "orderId")]
+    pub order_id: String,
+    /// Milestones in the order they were reached
+    pub milestones: Vec<TimelineEntry>,
+    /// Seconds from the first milestone to the last
+    #[serde(rename = "totalSeconds")]
+    pub total_seconds: u64,
+}
+
+/// A lifecycle event recorded in an order's audit log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position of the entry in the order's audit log, starting at 1
+    pub sequence: u64,
+    /// When the event was recorded (Unix timestamp)
+    pub timestamp: u64,
+    /// Kind of event, e.g. "transaction_confirmed"
+    pub kind: String,
+    /// Hash of the transaction involved, if any
+    #[serde(rename = "txHash")]
+    pub tx_hash: Option<String>,
+    /// Gas used by the transaction, if it confirmed
+    #[serde(rename = "gasUsed")]
+    pub gas_used: Option<u64>,
+    /// Why the order was rejected, skipped or failed, if it was
+    pub error: Option<String>,
+    /// The event as observed by the solver
+    pub event: SolverEvent,
+}
+
+/// Every lifecycle event recorded for an order, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderHistoryResponse {
+    /// Order identifier
+    #[serde(rename = "orderId")]
+    pub order_id: String,
+    /// Recorded events in the order they were observed
+    pub entries: Vec<AuditEntry>,
+}
+
+/// Target of an externally injected priority rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PriorityTarget {
+    /// A specific order, by order ID
+    Order(String),
+    /// All intents from a discovery source
+    Source(String),
+}
+
+/// A priority adjustment active until its expiry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityRule {
+    /// What the rule applies to
+    pub target: PriorityTarget,
+    /// Score added to matching intents; negative values suppress them
+    pub weight: i64,
+    /// Unix timestamp after which the rule no longer applies
+    #[serde(rename = "expiresAt")]
+    pub expires_at: u64,
+}
+
+/// Request to boost or suppress orders or sources for a time window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetPrioritiesRequest {
+    /// Order IDs to adjust
+    #[serde(rename = "orderIds", default)]
+    pub order_ids: Vec<String>,
+    /// Discovery sources to adjust
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// Score added to matching intents; negative values suppress them
+    pub weight: i64,
+    /// How long the adjustment lasts in seconds
+    #[serde(rename = "durationSeconds")]
+    pub duration_seconds: u64,
+}
+
+/// Currently active priority rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrioritiesResponse {
+    /// Rules that have not yet expired
+    pub rules: Vec<PriorityRule>,
+}
+
+/// Stage of processing an order failed at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FailureStage {
+    /// The intent failed validation
+    Validation,
+    /// The fill transaction failed
+    Fill,
+    /// The claim transaction failed
+    Claim,
+}
+
+/// An order held back after failing, waiting to be re-driven.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// Order identifier
+    #[serde(rename = "orderId")]
+    pub order_id: String,
+    /// Stage the order last failed at
+    pub stage: FailureStage,
+    /// Why the order last failed
+    pub error: String,
+    /// Class of the last failure, if known; permanent failures are only
+    /// re-driven by hand
+    #[serde(rename = "errorClass", default, skip_serializing_if = "Option::is_none")]
+    pub error_class: Option<ErrorClass>,
+    /// When the order last failed (Unix timestamp)
+    #[serde(rename = "failedAt")]
+    pub failed_at: u64,
+    /// Number of times the order has been re-driven
+    pub retries: u32,
+    /// When the order is next re-driven (Unix timestamp), if scheduled
+    #[serde(rename = "nextRetryAt")]
+    pub next_retry_at: Option<u64>,
+    /// The failed intent, kept to re-run validation
+    pub intent: Option<Intent>,
+}
+
+/// Orders held in the dead-letter queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLettersResponse {
+    /// Dead-lettered orders, oldest failure first
+    #[serde(rename = "deadLetters")]
+    pub dead_letters: Vec<DeadLetter>,
+}
+
+/// Whether the solver takes on new intents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IntakeState {
+    /// New intents are handled as they are discovered
+    Running,
+    /// New intents are held until intake resumes
+    Paused,
+    /// New intents are held while in-flight orders finish
+    Draining,
+}
+
+/// Intake state of the solver and the work it still has in flight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntakeStatusResponse {
+    /// Whether new intents are handled
+    pub intake: IntakeState,
+    /// Intents discovered and held while intake is not running
+    #[serde(rename = "heldIntents")]
+    pub held_intents: usize,
+    /// Fill and claim transactions awaiting confirmation
+    #[serde(rename = "pendingTransactions")]
+    pub pending_transactions: usize,
+    /// Whether a drain completed, leaving nothing in flight
+    pub drained: bool,
+}
+
+/// Outcome of an operator claim action on an order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimActionResponse {
+    /// Order identifier
+    #[serde(rename = "orderId")]
+    pub order_id: String,
+    /// Whether the order can be claimed
+    #[serde(rename = "claimReady")]
+    pub claim_ready: bool,
+    /// Whether a claim was submitted or queued
+    #[serde(rename = "claimTriggered")]
+    pub claim_triggered: bool,
+}
+
+/// An order standard the solver accepts, with the risky capabilities
+/// enabled for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupportedStandard {
+    /// Name of the order implementation handling the standard
+    pub name: String,
+    /// Risky capabilities enabled for the standard, e.g. "multiOutput"
+    pub features: Vec<String>,
+}
+
+/// What a solver was built with, collected while it was built.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SolverCapabilities {
+    /// Loaded implementation names keyed by component, e.g. "discovery"
+    pub components: BTreeMap<String, Vec<String>>,
+    /// Optional subsystems enabled by configuration
+    pub features: Vec<String>,
+    /// Order standards the solver accepts
+    pub standards: Vec<SupportedStandard>,
+    /// Chains the solver can submit transactions on
+    #[serde(rename = "chainIds")]
+    pub chain_ids: Vec<u64>,
+}
+
+impl SolverCapabilities {
+    /// Records an implementation loaded for a component.
+    pub fn add_component(&mut self, component: &str, implementation: &str) {
+        let implementations = self.components.entry(component.to_string()).or_default();
+        if !implementations.iter().any(|name| name == implementation) {
+            implementations.push(implementation.to_string());
+            implementations.sort();
+        }
+    }
+}
+
+/// Build information and runtime capabilities of a solver.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolverInfoResponse {
+    /// Identifier of the solver instance
+    #[serde(rename = "solverId")]
+    pub solver_id: String,
+    /// Version of the solver binary
+    pub version: String,
+    /// Git commit the solver binary was built from, if known
+    #[serde(rename = "gitCommit")]
+    pub git_commit: Option<String>,
+    /// What the solver was built with
+    #[serde(flatten)]
+    pub capabilities: SolverCapabilities,
+}
+
+/// Request to preview how an order would be executed.
+///
+/// Exactly one of `orderId` and `intent` must be given.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutePreviewRequest {
+    /// ID of an order known to the solver
+    #[serde(rename = "orderId")]
+    pub order_id: Option<String>,
+    /// Raw intent to preview instead of a known order
+    pub intent: Option<Intent>,
+}
+
+/// Decision of the execution strategy for a previewed order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum PreviewDecision {
+    /// The order would be filled with these parameters
+    Execute { params: ExecutionParams },
+    /// The order would be skipped
+    Skip { reason: String },
+    /// The order would be decided again later
+    Defer {
+        #[serde(rename = "retryAfterSeconds")]
+        retry_after_seconds: u64,
+    },
+}
+
+/// Fill transaction generated for a previewed order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewTransaction {
+    /// Chain the transaction would be submitted on
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+    /// Recipient address as a 0x-prefixed hex string
+    pub to: Option<String>,
+    /// Calldata as a 0x-prefixed hex string
+    pub data: String,
+    /// Value sent in the chain's gas token
+    pub value: U256,
+    /// Gas limit, if set by the order implementation
+    #[serde(rename = "gasLimit")]
+    pub gas_limit: Option<u64>,
+    /// Maximum fee per gas, if set by the order implementation
+    #[serde(rename = "maxFeePerGas")]
+    pub max_fee_per_gas: Option<u128>,
+    /// Maximum priority fee per gas, if set by the order implementation
+    #[serde(rename = "maxPriorityFeePerGas")]
+    pub max_priority_fee_per_gas: Option<u128>,
+}
+
+/// Result of simulating a previewed fill transaction with `eth_call`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewSimulation {
+    /// Whether the call succeeded
+    pub success: bool,
+    /// Data returned by the call as a 0x-prefixed hex string
+    #[serde(rename = "returnData")]
+    pub return_data: Option<String>,
+    /// Error the call failed with
+    pub error: Option<String>,
+}
+
+/// Every step of an order's execution, run without submitting anything.
+///
+/// Steps after a failed one are still run where possible, so a single
+/// preview shows all reasons an order would not be filled. Fields of steps
+/// that could not run are omitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutePreviewResponse {
+    /// ID of the previewed order
+    #[serde(rename = "orderId")]
+    pub order_id: String,
+    /// Order validated from the intent, or the stored order if its intent
+    /// is no longer known
+    pub order: Option<Order>,
+    /// Whether the intent was validated again for the preview
+    pub validated: bool,
+    /// Reason the intent failed validation
+    #[serde(rename = "validationError", skip_serializing_if = "Option::is_none")]
+    pub validation_error: Option<String>,
+    /// Reason the order's inputs could not be recycled
+    #[serde(rename = "liquidityError", skip_serializing_if = "Option::is_none")]
+    pub liquidity_error: Option<String>,
+    /// Fill deadline of the order
+    #[serde(rename = "fillDeadline", skip_serializing_if = "Option::is_none")]
+    pub fill_deadline: Option<u64>,
+    /// Whether the fill deadline has passed
+    pub expired: bool,
+    /// Estimated gas price on the fill chain the strategy was given
+    #[serde(rename = "gasPrice", skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+    /// Whether the order was decided on the stablecoin fast path
+    #[serde(rename = "fastPath")]
+    pub fast_path: bool,
+    /// Decision of the execution strategy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision: Option<PreviewDecision>,
+    /// Generated fill transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<PreviewTransaction>,
+    /// Reason the fill transaction could not be generated
+    #[serde(rename = "transactionError", skip_serializing_if = "Option::is_none")]
+    pub transaction_error: Option<String>,
+    /// Estimated fee of the fill transaction
+    #[serde(rename = "estimatedFee", skip_serializing_if = "Option::is_none")]
+    pub estimated_fee: Option<GasTokenAmount>,
+    /// Reason the solver could not pay for the fill transaction
+    #[serde(rename = "gasBalanceError", skip_serializing_if = "Option::is_none")]
+    pub gas_balance_error: Option<String>,
+    /// Simulation of the fill transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simulation: Option<PreviewSimulation>,
+}
+
+/// Capital utilization of a single token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapitalUtilization {
+    /// Chain the token lives on
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+    /// Token contract address
+    pub token: String,
+    /// Inventory spent on filled orders whose claims have not confirmed yet
+    #[serde(rename = "inFlight", with = "u256_serde")]
+    pub in_flight: U256,
+    /// Inventory held and not committed to any order
+    #[serde(with = "u256_serde")]
+    pub idle: U256,
+    /// Share of the token's inventory that is in flight, between 0 and 1
+    pub utilization: f64,
+    /// Filled orders with inventory of the token in flight
+    #[serde(rename = "inFlightOrders")]
+    pub in_flight_orders: usize,
+    /// Completed round trips from fill to claim
+    pub turnarounds: u64,
+    /// Average time from fill to claim in seconds
+    #[serde(rename = "averageTurnaroundSeconds")]
+    pub average_turnaround_seconds: Option<f64>,
+    /// Longest time from fill to claim in seconds
+    #[serde(rename = "maxTurnaroundSeconds")]
+    pub max_turnaround_seconds: Option<f64>,
+}
+
+/// Solver statistics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsResponse {
+    /// Capital utilization per chain and token
+    pub capital: Vec<CapitalUtilization>,
+}
+
+/// Profit and loss of a completed order.
+///
+/// Amounts are those journaled in the solver's ledger; USD values use the
+/// oracle prices at completion and are absent when a token is not priced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderPnl {
+    /// Order the record belongs to
+    #[serde(rename = "orderId")]
+    pub order_id: String,
+    /// Unix timestamp at which the order completed
+    #[serde(rename = "completedAt")]
+    pub completed_at: u64,
+    /// Tokens received when claiming
+    pub inputs: Vec<TokenAmount>,
+    /// Tokens delivered when filling
+    pub outputs: Vec<TokenAmount>,
+    /// Gas paid for fill transactions
+    #[serde(rename = "fillGas")]
+    pub fill_gas: Vec<GasTokenAmount>,
+    /// Gas paid for claim transactions
+    #[serde(rename = "claimGas")]
+    pub claim_gas: Vec<GasTokenAmount>,
+    /// USD value of the inputs
+    #[serde(rename = "inputUsd")]
+    pub input_usd: Option<f64>,
+    /// USD value of the outputs
+    #[serde(rename = "outputUsd")]
+    pub output_usd: Option<f64>,
+    /// USD value of the fill gas
+    #[serde(rename = "fillGasUsd")]
+    pub fill_gas_usd: Option<f64>,
+    /// USD value of the claim gas
+    #[serde(rename = "claimGasUsd")]
+    pub claim_gas_usd: Option<f64>,
+    /// Inputs less outputs and gas, in USD, if every amount is priced
+    #[serde(rename = "profitUsd")]
+    pub profit_usd: Option<f64>,
+}
+
+/// Query parameters of profit and loss reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlQuery {
+    /// Unix timestamp from which completed orders are included
+    pub from: Option<u64>,
+    /// Unix timestamp before which completed orders are included
+    pub to: Option<u64>,
+}
+
+/// Aggregate profit and loss of the orders completed in a time range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PnlReport {
+    /// Start of the range, if bounded
+    pub from: Option<u64>,
+    /// End of the range, if bounded
+    pub to: Option<u64>,
+    /// Orders completed in the range
+    pub orders: usize,
+    /// Orders whose every amount is priced, making up the USD totals
+    #[serde(rename = "pricedOrders")]
+    pub priced_orders: usize,
+    /// Orders left out of the USD totals because a token is not priced
+    #[serde(rename = "unpricedOrderIds")]
+    pub unpriced_order_ids: Vec<String>,
+    /// USD value of the inputs received
+    #[serde(rename = "inputUsd")]
+    pub input_usd: f64,
+    /// USD value of the outputs delivered
+    #[serde(rename = "outputUsd")]
+    pub output_usd: f64,
+    /// USD value of the gas paid for fills
+    #[serde(rename = "fillGasUsd")]
+    pub fill_gas_usd: f64,
+    /// USD value of the gas paid for claims
+    #[serde(rename = "claimGasUsd")]
+    pub claim_gas_usd: f64,
+    /// Net profit in USD
+    #[serde(rename = "profitUsd")]
+    pub profit_usd: f64,
+}
+
+/// Balance of a token held by the solver, as last polled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryBalance {
+    /// Chain the balance is held on
+    #[serde(rename = "chainId")]
+    pub chain_id: u64,
+    /// Token contract address, or none for the chain's native gas token
+    pub token: Option<String>,
+    /// Balance in the token's smallest unit
+    #[serde(with = "u256_serde")]
+    pub balance: U256,
+    /// Balance below which the solver warns, if configured
+    #[serde(rename = "minBalance")]
+    pub min_balance: Option<U256>,
+    /// Whether the balance is below its minimum
+    pub low: bool,
+    /// Unix timestamp of the poll
+    #[serde(rename = "updatedAt")]
+    pub updated_at: u64,
+}
+
+/// Solver token inventory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryResponse {
+    /// Polled balances, ordered by chain with native balances first
+    pub balances: Vec<InventoryBalance>,
+}
+
+/// Combined view of the solver instances of a cluster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterResponse {
+    /// Whether every instance answered and is ready
+    pub ready: bool,
+    /// Capital utilization per chain and token, summed over the instances that answered
+    pub capital: Vec<CapitalUtilization>,
+    /// Status of each instance, the one serving the view first
+    pub instances: Vec<ClusterInstance>,
+}
+
+/// Status of one solver instance of a cluster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterInstance {
+    /// Base URL of the instance's API, or "self" for the instance serving the view
+    pub url: String,
+    /// Whether the instance answered
+    pub reachable: bool,
+    /// Readiness reported by the instance
+    pub readiness: Option<ReadinessResponse>,
+    /// Statistics reported by the instance
+    pub stats: Option<StatsResponse>,
+    /// Why the instance could not be reached
+    pub error: Option<String>,
+}
+
+/// Filters of a live event stream. Events must match every filter given.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventStreamQuery {
+    /// Only stream events concerning this order
+    #[serde(rename = "orderId")]
+    pub order_id: Option<String>,
+    /// Comma-separated event kinds to stream, such as "transaction_confirmed"
+    #[serde(rename = "type")]
+    pub kinds: Option<String>,
+    /// Only stream events that happened on this chain
+    #[serde(rename = "chainId")]
+    pub chain_id: Option<u64>,
+}
+
+/// An event sent to live event stream clients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamedEvent {
+    /// Kind of the event, such as "transaction_confirmed"
+    pub kind: String,
+    /// When the event was observed, in Unix seconds
+    pub timestamp: u64,
+    /// Orders the event concerns
+    #[serde(rename = "orderIds")]
+    pub order_ids: Vec<String>,
+    /// Chain the event happened on, if known
+    #[serde(rename = "chainId")]
+    pub chain_id: Option<u64>,
+    /// The event
+    pub event: SolverEvent,
+}
+
+/// Query parameters for cursor-paginated list endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageQuery {
+    /// Sequence number of the last item already seen; omit to start from the beginning
+    pub cursor: Option<u64>,
+    /// Maximum number of items to return
+    pub limit: Option<usize>,
+}
+
+/// A page of items from a cursor-paginated list endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// Items in ascending sequence order
+    pub items: Vec<T>,
+    /// Cursor to pass to fetch the next page, if more items exist
+    #[serde(rename = "nextCursor")]
+    pub next_cursor: Option<u64>,
+}
+
 /// API error response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
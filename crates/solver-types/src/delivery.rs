@@ -3,6 +3,8 @@
 //! This module defines types related to blockchain transaction submission
 //! and monitoring, including transaction hashes and receipts.
 
+use crate::Address;
+
 /// Blockchain transaction hash representation.
 ///
 /// Stores transaction hashes as raw bytes to support different blockchain formats.
@@ -21,4 +23,63 @@ pub struct TransactionReceipt {
 	pub block_number: u64,
 	/// Whether the transaction executed successfully.
 	pub success: bool,
+	/// Amount of gas used by the transaction.
+	#[serde(default)]
+	pub gas_used: u64,
+	/// Price paid per unit of gas, in the chain's gas token.
+	#[serde(default)]
+	pub effective_gas_price: alloy_primitives::U256,
+	/// Logs emitted by the transaction.
+	#[serde(default)]
+	pub logs: Vec<TransactionLog>,
+}
+
+/// Log emitted by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TransactionLog {
+	/// Address of the contract that emitted the log.
+	pub address: Address,
+	/// Indexed topics of the log, the first being the event signature.
+	pub topics: Vec<Vec<u8>>,
+	/// Non-indexed data of the log.
+	pub data: Vec<u8>,
+}
+
+/// An RPC endpoint that stopped serving requests.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EndpointFailure {
+	/// Scheme and host of the endpoint, without paths that may hold API keys.
+	pub endpoint: String,
+	/// Error that marked the endpoint unhealthy.
+	pub reason: String,
+}
+
+/// Fee parameters used when re-submitting a pending transaction.
+///
+/// Legacy transactions use `gas_price`; EIP-1559 transactions use the max fee
+/// fields. Fields left as `None` keep the original transaction's value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GasParams {
+	/// Gas price for legacy transactions.
+	pub gas_price: Option<alloy_primitives::U256>,
+	/// Maximum total fee per gas for EIP-1559 transactions.
+	pub max_fee_per_gas: Option<alloy_primitives::U256>,
+	/// Maximum priority fee per gas for EIP-1559 transactions.
+	pub max_priority_fee_per_gas: Option<alloy_primitives::U256>,
+}
+
+impl GasParams {
+	/// Returns the highest price per gas the parameters allow paying.
+	pub fn max_price(&self) -> Option<alloy_primitives::U256> {
+		self.max_fee_per_gas.or(self.gas_price)
+	}
+}
+
+/// Recent fee market history of a chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FeeHistory {
+	/// Base fee per gas of each block, followed by the base fee of the next block.
+	pub base_fee_per_gas: Vec<alloy_primitives::U256>,
+	/// Priority fees paid in each block at the requested percentiles.
+	pub priority_fees: Vec<Vec<alloy_primitives::U256>>,
 }
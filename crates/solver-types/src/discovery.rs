@@ -4,6 +4,13 @@
 //! cross-chain intents before they are validated into orders.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Source name recorded on intents submitted off-chain.
+///
+/// Such intents carry nothing but the user's signature vouching for them, so
+/// order implementations must verify it before accepting the intent.
+pub const OFFCHAIN_SOURCE: &str = "offchain";
 
 /// Represents a discovered cross-chain intent.
 ///
@@ -35,4 +42,17 @@ pub struct IntentMetadata {
 	pub exclusive_until: Option<u64>,
 	/// Timestamp when this intent was discovered.
 	pub discovered_at: u64,
+	/// Address of the settler contract the intent was opened on, if known.
+	#[serde(default)]
+	pub settler: Option<String>,
+	/// Block the intent was opened in, for intents discovered on-chain.
+	#[serde(default)]
+	pub block_number: Option<u64>,
+	/// Hash of the transaction that opened the intent, as a hex string.
+	#[serde(default)]
+	pub transaction_hash: Option<String>,
+	/// Opaque metadata supplied with the intent, such as referral codes or
+	/// campaign tags, carried through to the order unchanged.
+	#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+	pub custom: HashMap<String, String>,
 }
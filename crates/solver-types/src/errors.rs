@@ -0,0 +1,89 @@
+//! Error classification shared by the solver services.
+//!
+//! Each service reports failures through its own error type. To decide what
+//! to do about a failure without parsing its message, every service error
+//! classifies itself by the part of the system it stems from and by whether
+//! retrying the failed operation may succeed.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Part of the system an error stems from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorCategory {
+	/// An RPC endpoint, a transaction or another chain interaction failed.
+	Chain,
+	/// An intent, order or fill was found invalid.
+	Validation,
+	/// Signing with the solver's keys failed.
+	Signing,
+	/// Reading or writing solver state failed.
+	Storage,
+	/// The solver is not configured for the operation.
+	Configuration,
+	/// The solver itself misbehaved.
+	Internal,
+}
+
+impl fmt::Display for ErrorCategory {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let name = match self {
+			Self::Chain => "Chain",
+			Self::Validation => "Validation",
+			Self::Signing => "Signing",
+			Self::Storage => "Storage",
+			Self::Configuration => "Configuration",
+			Self::Internal => "Internal",
+		};
+		f.write_str(name)
+	}
+}
+
+/// Whether retrying a failed operation may succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Retryability {
+	/// The failure may clear up on its own, such as a timed out request.
+	Transient,
+	/// Retrying fails the same way until something is changed by hand.
+	Permanent,
+}
+
+/// Classification of an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ErrorClass {
+	/// Part of the system the error stems from.
+	pub category: ErrorCategory,
+	/// Whether retrying may succeed.
+	pub retryability: Retryability,
+}
+
+impl ErrorClass {
+	/// Class of failures in `category` that may clear up on their own.
+	pub const fn transient(category: ErrorCategory) -> Self {
+		Self {
+			category,
+			retryability: Retryability::Transient,
+		}
+	}
+
+	/// Class of failures in `category` that retrying does not fix.
+	pub const fn permanent(category: ErrorCategory) -> Self {
+		Self {
+			category,
+			retryability: Retryability::Permanent,
+		}
+	}
+
+	/// Returns whether retrying may succeed.
+	pub fn is_transient(&self) -> bool {
+		self.retryability == Retryability::Transient
+	}
+}
+
+/// Errors that know their class.
+pub trait Classify {
+	/// Returns the class of the error.
+	fn class(&self) -> ErrorClass;
+}
@@ -5,8 +5,9 @@
 //! allowing services to react to state changes in other parts of the system.
 
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
 
 use crate::{ExecutionParams, FillProof, Intent, Order, TransactionHash, TransactionReceipt};
 
@@ -26,15 +27,57 @@ pub enum SolverEvent {
 	Settlement(SettlementEvent),
 }
 
+/// A solver event together with its position in the event journal.
+///
+/// Sequence numbers increase monotonically, so replaying events in sequence
+/// order reproduces the order in which they were observed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequencedEvent {
+	/// Position of the event in the journal.
+	pub sequence: u64,
+	/// When the event was journaled, in Unix seconds. Zero for events
+	/// journaled before timestamps were recorded.
+	#[serde(default)]
+	pub timestamp: u64,
+	/// The journaled event.
+	pub event: SolverEvent,
+}
+
+/// Borrowed form of [`SequencedEvent`] that serializes identically, so events
+/// can be journaled without being cloned.
+#[derive(Debug, Serialize)]
+pub struct SequencedEventRef<'a> {
+	/// Position of the event in the journal.
+	pub sequence: u64,
+	/// When the event was journaled, in Unix seconds.
+	pub timestamp: u64,
+	/// The journaled event.
+	pub event: &'a SolverEvent,
+}
+
 /// Events related to intent discovery.
+///
+/// Large payloads are shared rather than copied, since every subscriber of the
+/// event bus receives its own clone of each event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DiscoveryEvent {
 	/// A new intent has been discovered.
-	IntentDiscovered { intent: Intent },
+	IntentDiscovered { intent: Arc<Intent> },
 	/// An intent has been validated and converted to an order.
-	IntentValidated { intent_id: String, order: Order },
+	IntentValidated {
+		intent_id: String,
+		order: Arc<Order>,
+	},
 	/// An intent has been rejected during validation.
 	IntentRejected { intent_id: String, reason: String },
+	/// A previously validated intent was dropped from the chain by a reorg.
+	IntentInvalidated { intent_id: String, reason: String },
+	/// The discovery intake filled up and its overflow policy took effect.
+	IntakeSaturated {
+		depth: usize,
+		capacity: usize,
+		policy: String,
+	},
 }
 
 /// Events related to order processing.
@@ -42,7 +85,7 @@ pub enum DiscoveryEvent {
 pub enum OrderEvent {
 	/// An order is being executed with the specified parameters.
 	Executing {
-		order: Order,
+		order: Arc<Order>,
 		params: ExecutionParams,
 	},
 	/// An order has been skipped due to strategy decision.
@@ -52,6 +95,11 @@ pub enum OrderEvent {
 		order_id: String,
 		retry_after: Duration,
 	},
+	/// An order passed its fill deadline without being executed.
+	Expired { order_id: String },
+	/// Another solver filled an order before the solver's fill was
+	/// broadcast or confirmed.
+	FilledByOther { order_id: String },
 }
 
 /// Events related to transaction delivery.
@@ -62,6 +110,8 @@ pub enum DeliveryEvent {
 		order_id: String,
 		tx_hash: TransactionHash,
 		tx_type: TransactionType,
+		#[serde(default)]
+		chain_id: u64,
 	},
 	/// A transaction has been confirmed on-chain.
 	TransactionConfirmed {
@@ -74,6 +124,37 @@ pub enum DeliveryEvent {
 		tx_hash: TransactionHash,
 		error: String,
 	},
+	/// A stuck transaction has been re-submitted with higher fees.
+	TransactionReplaced {
+		order_id: String,
+		old_tx_hash: TransactionHash,
+		new_tx_hash: TransactionHash,
+		tx_type: TransactionType,
+		chain_id: u64,
+	},
+	/// A confirmed transaction was dropped from the chain by a reorg.
+	TransactionReorged {
+		order_id: String,
+		tx_hash: TransactionHash,
+		tx_type: TransactionType,
+		chain_id: u64,
+	},
+	/// An RPC endpoint of a chain stopped serving requests, and requests
+	/// fail over to the chain's other endpoints.
+	ProviderDegraded {
+		chain_id: u64,
+		endpoint: String,
+		reason: String,
+	},
+	/// New fills were paused because a gas budget or the consecutive
+	/// failure threshold was exceeded. Applies to every chain if no chain
+	/// is given.
+	FillsPaused {
+		chain_id: Option<u64>,
+		reason: String,
+	},
+	/// New fills resumed after being paused.
+	FillsResumed { chain_id: Option<u64> },
 }
 
 /// Events related to settlement operations.
@@ -85,7 +166,10 @@ pub enum SettlementEvent {
 		tx_hash: TransactionHash,
 	},
 	/// Fill proof has been generated and is ready.
-	ProofReady { order_id: String, proof: FillProof },
+	ProofReady {
+		order_id: String,
+		proof: Arc<FillProof>,
+	},
 	/// Order is ready to be claimed.
 	ClaimReady { order_id: String },
 	/// Order settlement has been completed.
@@ -93,7 +177,7 @@ pub enum SettlementEvent {
 }
 
 /// Types of transactions in the solver system.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TransactionType {
 	/// Transaction that fills an order on the destination chain.
 	Fill,
@@ -101,21 +185,118 @@ pub enum TransactionType {
 	Claim,
 }
 
+/// Predicate deciding which events a filtered subscriber receives.
+type EventPredicate = Box<dyn Fn(&SolverEvent) -> bool + Send + Sync>;
+
+/// Subscriber receiving only the events matching its predicate.
+struct FilteredSubscriber {
+	/// Decides which events the subscriber receives.
+	predicate: EventPredicate,
+	/// Channel to the subscriber.
+	sender: broadcast::Sender<SolverEvent>,
+}
+
+/// Channels of the events of each service, for subscribers interested in a
+/// single one.
+struct TopicSenders {
+	/// Channel of discovery events.
+	discovery: broadcast::Sender<DiscoveryEvent>,
+	/// Channel of order events.
+	order: broadcast::Sender<OrderEvent>,
+	/// Channel of delivery events.
+	delivery: broadcast::Sender<DeliveryEvent>,
+	/// Channel of settlement events.
+	settlement: broadcast::Sender<SettlementEvent>,
+}
+
+impl TopicSenders {
+	/// Creates the channels, each with the given capacity.
+	fn new(capacity: usize) -> Self {
+		Self {
+			discovery: broadcast::channel(capacity).0,
+			order: broadcast::channel(capacity).0,
+			delivery: broadcast::channel(capacity).0,
+			settlement: broadcast::channel(capacity).0,
+		}
+	}
+
+	/// Sends an event to the subscribers of its topic, returning whether
+	/// any received it.
+	///
+	/// The event is only cloned when its topic has subscribers.
+	fn send(&self, event: &SolverEvent) -> bool {
+		fn send<T: Clone>(sender: &broadcast::Sender<T>, event: &T) -> bool {
+			sender.receiver_count() > 0 && sender.send(event.clone()).is_ok()
+		}
+
+		match event {
+			SolverEvent::Discovery(event) => send(&self.discovery, event),
+			SolverEvent::Order(event) => send(&self.order, event),
+			SolverEvent::Delivery(event) => send(&self.delivery, event),
+			SolverEvent::Settlement(event) => send(&self.settlement, event),
+		}
+	}
+}
+
 /// Event bus for broadcasting solver events.
 ///
 /// The EventBus provides a pub-sub mechanism for services to communicate
 /// asynchronously. Multiple services can subscribe to receive events while
 /// any service can publish events.
+///
+/// Subscribers interested in the events of a single service subscribe to
+/// that service's topic, and receive its events already unwrapped. Other
+/// subscribers may pass a predicate, which is checked when an event is
+/// delivered, so events they would discard never reach their channel or
+/// count towards its capacity.
+///
+/// A persistent bus hands published events to a journal instead, which
+/// records them before delivering them to subscribers. Unlike the broadcast
+/// channel, the journal queue is unbounded, so no event is dropped while the
+/// journal catches up.
 pub struct EventBus {
 	/// The broadcast channel sender.
 	sender: broadcast::Sender<SolverEvent>,
+	/// Capacity of each subscriber's channel.
+	capacity: usize,
+	/// Per-topic channel senders.
+	topics: Arc<TopicSenders>,
+	/// Subscribers with a predicate, dropped once their receiver is.
+	filtered: Arc<Mutex<Vec<FilteredSubscriber>>>,
+	/// Queue of events awaiting the journal, if the bus is persistent.
+	journal: Option<mpsc::UnboundedSender<SolverEvent>>,
 }
 
 impl EventBus {
 	/// Creates a new EventBus with the specified channel capacity.
 	pub fn new(capacity: usize) -> Self {
 		let (sender, _) = broadcast::channel(capacity);
-		Self { sender }
+		Self {
+			sender,
+			capacity,
+			topics: Arc::new(TopicSenders::new(capacity)),
+			filtered: Arc::new(Mutex::new(Vec::new())),
+			journal: None,
+		}
+	}
+
+	/// Creates a persistent EventBus with the specified channel capacity.
+	///
+	/// Returns the bus and the queue of published events. The journal
+	/// draining the queue must [`deliver`](Self::deliver) each event once it
+	/// is recorded.
+	pub fn persistent(capacity: usize) -> (Self, mpsc::UnboundedReceiver<SolverEvent>) {
+		let (journal, published) = mpsc::unbounded_channel();
+		let bus = Self {
+			journal: Some(journal),
+			..Self::new(capacity)
+		};
+		(bus, published)
+	}
+
+	/// Returns whether published events pass through a journal.
+	pub fn is_persistent(&self) -> bool {
+		self.journal.is_some()
 	}
 
 	/// Creates a new subscriber to receive events.
@@ -123,13 +304,78 @@ impl EventBus {
 		self.sender.subscribe()
 	}
 
+	/// Creates a subscriber receiving only discovery events.
+	pub fn subscribe_discovery(&self) -> broadcast::Receiver<DiscoveryEvent> {
+		self.topics.discovery.subscribe()
+	}
+
+	/// Creates a subscriber receiving only order events.
+	pub fn subscribe_order(&self) -> broadcast::Receiver<OrderEvent> {
+		self.topics.order.subscribe()
+	}
+
+	/// Creates a subscriber receiving only delivery events.
+	pub fn subscribe_delivery(&self) -> broadcast::Receiver<DeliveryEvent> {
+		self.topics.delivery.subscribe()
+	}
+
+	/// Creates a subscriber receiving only settlement events.
+	pub fn subscribe_settlement(&self) -> broadcast::Receiver<SettlementEvent> {
+		self.topics.settlement.subscribe()
+	}
+
+	/// Creates a subscriber receiving only the events matching `predicate`.
+	///
+	/// The predicate runs on the publishing task as each event is delivered,
+	/// so it should be cheap and must not block.
+	pub fn subscribe_filtered<F>(&self, predicate: F) -> broadcast::Receiver<SolverEvent>
+	where
+		F: Fn(&SolverEvent) -> bool + Send + Sync + 'static,
+	{
+		let (sender, receiver) = broadcast::channel(self.capacity);
+		self.filtered.lock().unwrap().push(FilteredSubscriber {
+			predicate: Box::new(predicate),
+			sender,
+		});
+		receiver
+	}
+
 	/// Publishes an event to all subscribers.
 	pub fn publish(
 		&self,
 		event: SolverEvent,
 	) -> Result<(), broadcast::error::SendError<SolverEvent>> {
-		self.sender.send(event)?;
-		Ok(())
+		match &self.journal {
+			Some(journal) => journal
+				.send(event)
+				.map_err(|e| broadcast::error::SendError(e.0)),
+			None => self.deliver(event),
+		}
+	}
+
+	/// Delivers an event to all subscribers, bypassing the journal.
+	///
+	/// Fails only if no subscriber of any kind received the event.
+	pub fn deliver(
+		&self,
+		event: SolverEvent,
+	) -> Result<(), broadcast::error::SendError<SolverEvent>> {
+		let mut delivered = self.topics.send(&event);
+		{
+			let mut filtered = self.filtered.lock().unwrap();
+			filtered.retain(|subscriber| subscriber.sender.receiver_count() > 0);
+			for subscriber in filtered.iter() {
+				if (subscriber.predicate)(&event) {
+					delivered |= subscriber.sender.send(event.clone()).is_ok();
+				}
+			}
+		}
+
+		match self.sender.send(event) {
+			Ok(_) => Ok(()),
+			Err(_) if delivered => Ok(()),
+			Err(e) => Err(e),
+		}
 	}
 }
 
@@ -138,6 +384,10 @@ impl Clone for EventBus {
 	fn clone(&self) -> Self {
 		Self {
 			sender: self.sender.clone(),
+			capacity: self.capacity,
+			topics: self.topics.clone(),
+			filtered: self.filtered.clone(),
+			journal: self.journal.clone(),
 		}
 	}
 }
@@ -12,10 +12,27 @@ pub mod api;
 pub mod delivery;
 /// Intent discovery types for finding and processing new orders.
 pub mod discovery;
+/// Error classification for deciding whether to retry failures.
+pub mod errors;
 /// Event types for inter-service communication.
 pub mod events;
+/// Typed decoding of transaction event logs.
+pub mod logs;
+/// Network metadata types such as per-chain gas tokens.
+pub mod network;
 /// Order processing types including intents, orders, and execution contexts.
 pub mod order;
+/// Signature verification helpers for user-signed intents.
+pub mod signature;
+/// Token bucket rate limiting keyed by what is limited.
+pub mod rate_limit;
+/// Solana instruction and log types for non-EVM chains.
+pub mod solana;
+/// Scripted behaviour of mock service implementations, for tests.
+#[cfg(feature = "testing")]
+pub mod testing;
+/// Conversions between token amounts and whole tokens.
+pub mod units;
 /// Configuration validation types for ensuring type-safe configurations.
 pub mod validation;
 
@@ -24,6 +41,13 @@ pub use account::*;
 pub use api::*;
 pub use delivery::*;
 pub use discovery::*;
+pub use errors::*;
 pub use events::*;
+pub use logs::*;
+pub use network::*;
 pub use order::*;
+pub use rate_limit::*;
+pub use signature::*;
+pub use solana::*;
+pub use units::*;
 pub use validation::*;
@@ -0,0 +1,329 @@
+//! Typed decoding of the event logs transactions emit.
+//!
+//! Discovery finds orders in the logs of the transactions opening them, and
+//! settlement finds fills and attestations in the logs of fill and oracle
+//! transactions. Logs reach them either as RPC logs or as the
+//! [`TransactionLog`]s of a delivery receipt. The helpers here match and
+//! decode both into typed events the same way, and the EIP-7683 events the
+//! solver relies on are declared once in [`eip7683`].
+
+use crate::TransactionLog;
+use alloy_primitives::B256;
+use alloy_sol_types::SolEvent;
+use thiserror::Error;
+
+/// Errors that can occur decoding a log.
+#[derive(Debug, Error)]
+pub enum LogError {
+	/// A topic of the log is not 32 bytes long.
+	#[error("Invalid log topic of {0} bytes")]
+	InvalidTopic(usize),
+	/// The log was not emitted for the expected event, or its fields do not
+	/// decode as the event's.
+	#[error("Failed to decode {event}: {reason}")]
+	Decode {
+		/// Signature of the expected event.
+		event: &'static str,
+		/// Why decoding failed.
+		reason: String,
+	},
+}
+
+/// Events emitted by EIP-7683 settlers.
+pub mod eip7683 {
+	use alloy_sol_types::sol;
+
+	sol! {
+		/// Output specification for cross-chain orders.
+		struct Output {
+			bytes32 token;
+			uint256 amount;
+			bytes32 recipient;
+			uint256 chainId;
+		}
+
+		/// Fill instruction for cross-chain execution.
+		struct FillInstruction {
+			uint64 destinationChainId;
+			bytes32 destinationSettler;
+			bytes originData;
+		}
+
+		/// Resolved cross-chain order structure.
+		struct ResolvedCrossChainOrder {
+			address user;
+			uint256 originChainId;
+			uint32 openDeadline;
+			uint32 fillDeadline;
+			bytes32 orderId;
+			Output[] maxSpent;
+			Output[] minReceived;
+			FillInstruction[] fillInstructions;
+		}
+
+		/// Output as filled by the output settler.
+		struct MandateOutput {
+			bytes32 oracle;
+			bytes32 settler;
+			uint256 chainId;
+			bytes32 token;
+			uint256 amount;
+			bytes32 recipient;
+			bytes call;
+			bytes context;
+		}
+
+		/// Emitted by the input settler when an order is opened.
+		event Open(bytes32 indexed orderId, ResolvedCrossChainOrder order);
+
+		/// Emitted by the output settler for every output it fills.
+		event OutputFilled(bytes32 indexed orderId, bytes32 solver, uint32 timestamp, MandateOutput output, uint256 finalAmount);
+
+		/// Emitted by the input settler when an order's inputs are paid out
+		/// to the solver.
+		event Finalised(bytes32 indexed orderId, bytes32 solver, bytes32 destination);
+	}
+}
+
+/// Returns whether a log with the given topics was emitted for event `E`.
+pub fn is_event<E: SolEvent>(topics: &[impl AsRef<[u8]>]) -> bool {
+	topics
+		.first()
+		.is_some_and(|topic| topic.as_ref() == E::SIGNATURE_HASH.as_slice())
+}
+
+/// Decodes event `E` from the topics and data of a log.
+///
+/// Fails if the log was emitted for another event.
+pub fn decode_event<E: SolEvent>(topics: &[B256], data: &[u8]) -> Result<E, LogError> {
+	E::decode_raw_log(topics.iter().copied(), data, true).map_err(|e| LogError::Decode {
+		event: E::SIGNATURE,
+		reason: e.to_string(),
+	})
+}
+
+/// Decodes event `E` from a log fetched over RPC.
+pub fn decode_rpc_log<E: SolEvent>(log: &alloy_rpc_types::Log) -> Result<E, LogError> {
+	decode_event(log.topics(), &log.data().data)
+}
+
+/// Decodes event `E` from a log of a delivery receipt.
+pub fn decode_transaction_log<E: SolEvent>(log: &TransactionLog) -> Result<E, LogError> {
+	let topics = log
+		.topics
+		.iter()
+		.map(|topic| {
+			B256::try_from(topic.as_slice()).map_err(|_| LogError::InvalidTopic(topic.len()))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	decode_event(&topics, &log.data)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::eip7683::{Finalised, Open, OutputFilled};
+	use super::*;
+	use crate::Address;
+	use alloy_primitives::{hex, U256};
+
+	/// Signature hash of `Open`.
+	const OPEN_TOPIC: &str = "a576d0af275d0c6207ef43ceee8c498a5d7a26b8157a32d3fdf361e64371628c";
+
+	/// Signature hash of `OutputFilled`.
+	const OUTPUT_FILLED_TOPIC: &str =
+		"fef24569acf839f2b5cb23fd59d8a9bcc21650ff711ac1961ca3c5d4681ffe12";
+
+	/// Signature hash of `Finalised`.
+	const FINALISED_TOPIC: &str =
+		"6d1ab3c99edb0b034244c4a410afdfc12e0fef57313ad9bc936138f2b080025b";
+
+	/// ID of the order the logs were emitted for.
+	const ORDER_ID: [u8; 32] = [0x11; 32];
+
+	/// Data of an `Open` log, one ABI word per line.
+	const OPEN_DATA: &[&str] = &[
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"00000000000000000000000070997970c51812dc3a010c7d01b50e0d17dc79c8",
+		"0000000000000000000000000000000000000000000000000000000000007a69",
+		"0000000000000000000000000000000000000000000000000000000066324900",
+		"00000000000000000000000000000000000000000000000000000000f4865700",
+		"1111111111111111111111111111111111111111111111111111111111111111",
+		"0000000000000000000000000000000000000000000000000000000000000100",
+		"00000000000000000000000000000000000000000000000000000000000001a0",
+		"0000000000000000000000000000000000000000000000000000000000000240",
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0000000000000000000000005fbdb2315678afecb367f032d93f642f64180aa3",
+		"0000000000000000000000000000000000000000000000000de0b6b3a7640000",
+		"0000000000000000000000003c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+		"0000000000000000000000000000000000000000000000000000000000007a6a",
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0000000000000000000000005fbdb2315678afecb367f032d93f642f64180aa3",
+		"0000000000000000000000000000000000000000000000000de0b6b3a7640000",
+		"0000000000000000000000003c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+		"0000000000000000000000000000000000000000000000000000000000007a69",
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0000000000000000000000000000000000000000000000000000000000000020",
+		"0000000000000000000000000000000000000000000000000000000000007a6a",
+		"0000000000000000000000009fe46736679d2d9a65f0992f2272de9f3c7fa6e0",
+		"0000000000000000000000000000000000000000000000000000000000000060",
+		"0000000000000000000000000000000000000000000000000000000000000004",
+		"deadbeef00000000000000000000000000000000000000000000000000000000",
+	];
+
+	/// Data of an `OutputFilled` log, one ABI word per line.
+	const OUTPUT_FILLED_DATA: &[&str] = &[
+		"000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+		"00000000000000000000000000000000000000000000000000000000663248c4",
+		"0000000000000000000000000000000000000000000000000000000000000080",
+		"0000000000000000000000000000000000000000000000000de0b6b3a7640000",
+		"0000000000000000000000000165878a594ca255338adfa4d48449f69242eb8f",
+		"0000000000000000000000009fe46736679d2d9a65f0992f2272de9f3c7fa6e0",
+		"0000000000000000000000000000000000000000000000000000000000007a6a",
+		"0000000000000000000000005fbdb2315678afecb367f032d93f642f64180aa3",
+		"0000000000000000000000000000000000000000000000000de0b6b3a7640000",
+		"0000000000000000000000003c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+		"0000000000000000000000000000000000000000000000000000000000000100",
+		"0000000000000000000000000000000000000000000000000000000000000120",
+		"0000000000000000000000000000000000000000000000000000000000000000",
+		"0000000000000000000000000000000000000000000000000000000000000000",
+	];
+
+	/// Data of a `Finalised` log, one ABI word per line.
+	const FINALISED_DATA: &[&str] = &[
+		"000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+		"0000000000000000000000003c44cdddb6a900fa2b585dd299e03d12fa4293bc",
+	];
+
+	/// Returns the topics of a log for an event of the order.
+	fn topics(signature: &str) -> Vec<B256> {
+		vec![
+			B256::from_slice(&hex::decode(signature).unwrap()),
+			B256::from(ORDER_ID),
+		]
+	}
+
+	/// Concatenates ABI words.
+	fn data(words: &[&str]) -> Vec<u8> {
+		hex::decode(words.concat()).unwrap()
+	}
+
+	/// Returns a 32-byte word holding an address.
+	fn address_word(address: &str) -> B256 {
+		let mut word = [0u8; 32];
+		word[12..].copy_from_slice(&hex::decode(address).unwrap());
+		B256::from(word)
+	}
+
+	#[test]
+	fn decodes_open_log() {
+		let open = decode_event::<Open>(&topics(OPEN_TOPIC), &data(OPEN_DATA)).unwrap();
+
+		assert_eq!(open.orderId, B256::from(ORDER_ID));
+		let order = open.order;
+		assert_eq!(
+			order.user.as_slice(),
+			hex::decode("70997970c51812dc3a010c7d01b50e0d17dc79c8").unwrap()
+		);
+		assert_eq!(order.originChainId, U256::from(31337));
+		assert_eq!(order.openDeadline, 1714571520);
+		assert_eq!(order.fillDeadline, 4102444800);
+		assert_eq!(order.orderId, B256::from(ORDER_ID));
+		assert_eq!(order.maxSpent.len(), 1);
+		assert_eq!(order.maxSpent[0].amount, U256::from(10u64.pow(18)));
+		assert_eq!(order.maxSpent[0].chainId, U256::from(31338));
+		assert_eq!(order.minReceived[0].chainId, U256::from(31337));
+		assert_eq!(order.fillInstructions.len(), 1);
+		assert_eq!(order.fillInstructions[0].destinationChainId, 31338);
+		assert_eq!(
+			order.fillInstructions[0].destinationSettler,
+			address_word("9fe46736679d2d9a65f0992f2272de9f3c7fa6e0")
+		);
+		assert_eq!(
+			order.fillInstructions[0].originData.as_ref(),
+			[0xde, 0xad, 0xbe, 0xef]
+		);
+	}
+
+	#[test]
+	fn decodes_output_filled_receipt_log() {
+		let log = TransactionLog {
+			address: Address(vec![0x9f; 20]),
+			topics: topics(OUTPUT_FILLED_TOPIC)
+				.iter()
+				.map(|topic| topic.to_vec())
+				.collect(),
+			data: data(OUTPUT_FILLED_DATA),
+		};
+		assert!(is_event::<OutputFilled>(&log.topics));
+
+		let filled = decode_transaction_log::<OutputFilled>(&log).unwrap();
+		assert_eq!(filled.orderId, B256::from(ORDER_ID));
+		assert_eq!(
+			filled.solver,
+			address_word("f39fd6e51aad88f6f4ce6ab8827279cfffb92266")
+		);
+		assert_eq!(filled.timestamp, 1714571460);
+		assert_eq!(filled.finalAmount, U256::from(10u64.pow(18)));
+		assert_eq!(
+			filled.output.oracle,
+			address_word("0165878a594ca255338adfa4d48449f69242eb8f")
+		);
+		assert_eq!(filled.output.chainId, U256::from(31338));
+		assert_eq!(
+			filled.output.recipient,
+			address_word("3c44cdddb6a900fa2b585dd299e03d12fa4293bc")
+		);
+		assert!(filled.output.call.is_empty());
+		assert!(filled.output.context.is_empty());
+	}
+
+	#[test]
+	fn decodes_finalised_log() {
+		let finalised =
+			decode_event::<Finalised>(&topics(FINALISED_TOPIC), &data(FINALISED_DATA)).unwrap();
+
+		assert_eq!(finalised.orderId, B256::from(ORDER_ID));
+		assert_eq!(
+			finalised.solver,
+			address_word("f39fd6e51aad88f6f4ce6ab8827279cfffb92266")
+		);
+		assert_eq!(
+			finalised.destination,
+			address_word("3c44cdddb6a900fa2b585dd299e03d12fa4293bc")
+		);
+	}
+
+	#[test]
+	fn rejects_logs_of_other_events() {
+		let finalised = topics(FINALISED_TOPIC);
+		assert!(is_event::<Finalised>(&finalised));
+		assert!(!is_event::<OutputFilled>(&finalised));
+		assert!(!is_event::<Open>(&finalised));
+		assert!(!is_event::<Open>(&Vec::<B256>::new()));
+
+		// Data that would decode is still refused under another event's topic
+		let error = decode_event::<Open>(&finalised, &data(OPEN_DATA)).err().unwrap();
+		assert!(matches!(error, LogError::Decode { event, .. } if event == Open::SIGNATURE));
+		assert!(
+			decode_event::<Finalised>(&topics(OUTPUT_FILLED_TOPIC), &data(FINALISED_DATA)).is_err()
+		);
+	}
+
+	#[test]
+	fn rejects_logs_missing_the_indexed_order_id() {
+		let signature_only = &topics(FINALISED_TOPIC)[..1];
+		assert!(decode_event::<Finalised>(signature_only, &data(FINALISED_DATA)).is_err());
+	}
+
+	#[test]
+	fn rejects_receipt_logs_with_invalid_topics() {
+		let log = TransactionLog {
+			address: Address(vec![0x9f; 20]),
+			topics: vec![hex::decode(FINALISED_TOPIC).unwrap(), vec![0x11; 31]],
+			data: data(FINALISED_DATA),
+		};
+
+		let error = decode_transaction_log::<Finalised>(&log).err().unwrap();
+		assert!(matches!(error, LogError::InvalidTopic(31)));
+	}
+}
@@ -0,0 +1,173 @@
+//! Network metadata types for the solver system.
+//!
+//! This module defines per-chain metadata that other components need in order
+//! to reason about a chain, such as which token gas is paid in. Chains that are
+//! not registered are assumed to behave like Ethereum mainnet.
+
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Native token used to pay for gas on a chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasToken {
+	/// Ticker symbol of the token (e.g. "ETH", "POL").
+	pub symbol: String,
+	/// Number of decimals used by the token.
+	pub decimals: u8,
+}
+
+impl Default for GasToken {
+	fn default() -> Self {
+		Self {
+			symbol: "ETH".to_string(),
+			decimals: 18,
+		}
+	}
+}
+
+/// An amount denominated in a chain's gas token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasTokenAmount {
+	/// Chain ID the amount applies to.
+	pub chain_id: u64,
+	/// Token the amount is denominated in.
+	pub token: GasToken,
+	/// Amount in the token's smallest unit.
+	pub amount: U256,
+}
+
+impl fmt::Display for GasTokenAmount {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&format_units(
+			self.amount,
+			self.token.decimals,
+			&self.token.symbol,
+		))
+	}
+}
+
+/// Symbol and decimals of an ERC-20 token, as reported by its contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+	/// Ticker symbol of the token.
+	pub symbol: String,
+	/// Number of decimals used by the token.
+	pub decimals: u8,
+}
+
+impl TokenMetadata {
+	/// Formats an amount in the token's smallest unit as a decimal amount
+	/// followed by the token's symbol.
+	pub fn format_amount(&self, amount: U256) -> String {
+		format_units(amount, self.decimals, &self.symbol)
+	}
+}
+
+/// Formats an amount in a token's smallest unit as a decimal amount followed
+/// by the token's symbol.
+fn format_units(amount: U256, decimals: u8, symbol: &str) -> String {
+	let unit = U256::from(10u64).pow(U256::from(decimals));
+	let whole = amount / unit;
+	let remainder = (amount % unit).to_string();
+	let fraction = format!("{:0>width$}", remainder, width = decimals as usize);
+	let fraction = fraction.trim_end_matches('0');
+
+	if fraction.is_empty() {
+		format!("{} {}", whole, symbol)
+	} else {
+		format!("{}.{} {}", whole, fraction, symbol)
+	}
+}
+
+/// Metadata describing a single chain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkInfo {
+	/// Native token used to pay for gas.
+	pub gas_token: GasToken,
+	/// Base URL of a block explorer for the chain, if known.
+	#[serde(default)]
+	pub explorer_url: Option<String>,
+	/// Average time between blocks, if known.
+	#[serde(default)]
+	pub block_time: Option<Duration>,
+	/// Time between checks of pending transactions, if configured.
+	#[serde(default)]
+	pub poll_interval: Option<Duration>,
+	/// Longest time between checks of a pending transaction, if configured.
+	#[serde(default)]
+	pub max_poll_interval: Option<Duration>,
+}
+
+/// Registry of per-chain network metadata.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkRegistry {
+	/// Network metadata keyed by chain ID.
+	networks: HashMap<u64, NetworkInfo>,
+}
+
+impl NetworkRegistry {
+	/// Creates a registry from per-chain network metadata.
+	pub fn new(networks: HashMap<u64, NetworkInfo>) -> Self {
+		Self { networks }
+	}
+
+	/// Returns the metadata for a chain, falling back to Ethereum defaults.
+	pub fn get(&self, chain_id: u64) -> NetworkInfo {
+		self.networks.get(&chain_id).cloned().unwrap_or_default()
+	}
+
+	/// Returns the gas token of a chain.
+	pub fn gas_token(&self, chain_id: u64) -> GasToken {
+		self.get(chain_id).gas_token
+	}
+
+	/// Returns the average time between blocks of a chain, assuming
+	/// Ethereum's 12 seconds if unknown.
+	pub fn block_time(&self, chain_id: u64) -> Duration {
+		self.networks
+			.get(&chain_id)
+			.and_then(|network| network.block_time)
+			.unwrap_or(Duration::from_secs(12))
+	}
+
+	/// Returns the time between checks of pending transactions on a chain,
+	/// defaulting to its block time but at least a second.
+	pub fn poll_interval(&self, chain_id: u64) -> Duration {
+		self.networks
+			.get(&chain_id)
+			.and_then(|network| network.poll_interval)
+			.unwrap_or_else(|| self.block_time(chain_id).max(Duration::from_secs(1)))
+	}
+
+	/// Returns the longest time between checks of a pending transaction on a
+	/// chain, defaulting to 8 times its poll interval.
+	pub fn max_poll_interval(&self, chain_id: u64) -> Duration {
+		self.networks
+			.get(&chain_id)
+			.and_then(|network| network.max_poll_interval)
+			.unwrap_or_else(|| self.poll_interval(chain_id).saturating_mul(8))
+	}
+
+	/// Returns a block explorer link to a transaction, if the chain has an
+	/// explorer configured.
+	pub fn transaction_url(&self, chain_id: u64, tx_hash: &str) -> Option<String> {
+		let explorer_url = self.networks.get(&chain_id)?.explorer_url.as_ref()?;
+		Some(format!(
+			"{}/tx/{}",
+			explorer_url.trim_end_matches('/'),
+			tx_hash
+		))
+	}
+
+	/// Denominates a raw amount in the gas token of a chain.
+	pub fn gas_amount(&self, chain_id: u64, amount: U256) -> GasTokenAmount {
+		GasTokenAmount {
+			chain_id,
+			token: self.gas_token(chain_id),
+			amount,
+		}
+	}
+}
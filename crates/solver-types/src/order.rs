@@ -6,8 +6,9 @@
 use alloy_primitives::U256;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 
-use crate::{Address, TransactionHash};
+use crate::{Address, GasParams, TransactionHash};
 
 /// Represents a validated cross-chain order.
 ///
@@ -23,6 +24,56 @@ pub struct Order {
 	pub created_at: u64,
 	/// Standard-specific order data in JSON format.
 	pub data: serde_json::Value,
+	/// Name of the order implementation handling this order, when it was routed
+	/// by settler address rather than by standard.
+	#[serde(default)]
+	pub implementation: Option<String>,
+	/// Opaque metadata the intent was submitted with, for attribution by
+	/// downstream consumers. The solver never interprets it.
+	#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+	pub metadata: HashMap<String, String>,
+	/// Timestamp until which the order is exclusive to a solver, if any.
+	#[serde(default)]
+	pub exclusive_until: Option<u64>,
+	/// Alternative routes the order can be filled through, when its standard
+	/// lets the solver pick the destination chain. Empty for orders with a
+	/// single destination.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub routes: Vec<FillRoute>,
+}
+
+/// A destination chain an order can be filled on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FillRoute {
+	/// Chain the fill is submitted on.
+	pub chain_id: u64,
+	/// Tokens the solver delivers when filling on this chain.
+	pub outputs: Vec<TokenAmount>,
+	/// Gas limit of the fill on this chain, if the order specifies one.
+	pub gas_limit: Option<u64>,
+}
+
+/// A token amount on a specific chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenAmount {
+	/// Chain ID the token lives on.
+	pub chain_id: u64,
+	/// Token contract address.
+	pub token: Address,
+	/// Amount in the token's smallest unit.
+	pub amount: U256,
+}
+
+/// Standard-agnostic view of the assets moved by an order.
+///
+/// Inputs are the tokens the solver receives when claiming, outputs are the
+/// tokens the solver delivers when filling.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderAssets {
+	/// Tokens locked by the user and paid to the solver on claim.
+	pub inputs: Vec<TokenAmount>,
+	/// Tokens the solver must deliver to fill the order.
+	pub outputs: Vec<TokenAmount>,
 }
 
 /// Parameters for executing an order.
@@ -34,6 +85,27 @@ pub struct ExecutionParams {
 	pub gas_price: U256,
 	/// Optional priority fee for EIP-1559 transactions.
 	pub priority_fee: Option<U256>,
+	/// Handling of a fill that must land within the next few blocks.
+	#[serde(default)]
+	pub urgency: Option<FillUrgency>,
+	/// Amount of the order's first output to deliver, when the strategy fills
+	/// only part of what remains. Other outputs are filled pro rata. Ignored
+	/// for standards that do not support partial fills.
+	#[serde(default)]
+	pub fill_amount: Option<U256>,
+}
+
+/// Handling of a time-sensitive fill.
+///
+/// Set by strategies when an order is about to pass its deadline, so that
+/// its fill outbids other transactions for inclusion in the next blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FillUrgency {
+	/// Basis points by which to raise the estimated fees.
+	pub priority_fee_bump_bps: u64,
+	/// Whether to submit the fill through the private route, if the fill
+	/// chain has one.
+	pub private: bool,
 }
 
 /// Context information for making execution decisions.
@@ -41,12 +113,31 @@ pub struct ExecutionParams {
 /// Provides current market conditions and solver state to execution strategies.
 #[derive(Debug, Clone)]
 pub struct ExecutionContext {
-	/// Current gas price on the network.
+	/// Estimated gas price on the chain the order is filled on.
 	pub gas_price: U256,
+	/// Estimated fee parameters keyed by chain ID, for the chains the order
+	/// touches.
+	pub gas_estimates: HashMap<u64, GasParams>,
 	/// Current timestamp.
 	pub timestamp: u64,
-	/// Solver's balance across different addresses and tokens.
-	pub solver_balance: HashMap<Address, U256>,
+	/// Time by which the fill must land: the earlier of the order's fill
+	/// deadline and the end of its exclusivity window, when known.
+	pub deadline: Option<u64>,
+	/// Solver's balance of each chain's gas token keyed by chain ID.
+	pub native_balances: HashMap<u64, U256>,
+	/// Solver's balance of the tokens the order outputs, keyed by chain ID
+	/// and token address.
+	pub token_balances: HashMap<(u64, Address), U256>,
+	/// USD price of one whole token keyed by chain ID and token address.
+	/// Empty unless a price oracle is configured.
+	pub token_prices: HashMap<(u64, Address), f64>,
+	/// Outputs the order still needs delivered, net of earlier partial fills.
+	pub remaining_outputs: Vec<TokenAmount>,
+	/// Whether the order's standard accepts partial fills.
+	pub partial_fills: bool,
+	/// Risk score from 0 to 100 the validation pipeline let the order
+	/// through with. `None` unless risk validators are configured.
+	pub risk_score: Option<u32>,
 }
 
 /// Decision made by an execution strategy.
@@ -65,7 +156,7 @@ pub enum ExecutionDecision {
 /// Proof that an order has been filled.
 ///
 /// Contains all information needed to claim rewards for filling an order.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FillProof {
 	/// Transaction hash of the fill.
 	pub tx_hash: TransactionHash,
@@ -78,3 +169,42 @@ pub struct FillProof {
 	/// Address of the oracle that attested to the fill.
 	pub oracle_address: String,
 }
+
+/// What claiming a filled order is expected to pay and cost.
+///
+/// Lets the solver rank claims by value and leave alone claims whose gas
+/// would cost more than they return.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimEstimate {
+	/// Tokens the claim pays out to the solver.
+	pub reward: Vec<TokenAmount>,
+	/// Gas the claim transaction is expected to use, if known. Zero when the
+	/// protocol repays the solver without a claim transaction.
+	pub gas_estimate: Option<u64>,
+	/// Unix timestamp after which the order can no longer be claimed, if the
+	/// settlement has one.
+	pub deadline: Option<u64>,
+}
+
+/// Terminal status of an order that has left the execution pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderOutcomeStatus {
+	/// The order was filled and the claim was confirmed.
+	Completed,
+	/// A fill or claim transaction for the order failed.
+	Failed,
+}
+
+/// Realized outcome of an order, fed back to execution strategies.
+///
+/// Allows adaptive strategies to learn from how their past decisions played out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderOutcome {
+	/// Whether the order completed or failed.
+	pub status: OrderOutcomeStatus,
+	/// Realized profit in USD, net of the gas paid for the order, if it
+	/// could be priced.
+	pub realized_profit_usd: Option<f64>,
+	/// Time elapsed between order creation and the outcome.
+	pub latency: Duration,
+}
@@ -0,0 +1,131 @@
+//! In-memory rate limiting shared by the solver's intake checks.
+//!
+//! Limits are token buckets kept per key, such as a discovery source or a
+//! user. A bucket holds up to a burst of tokens, refills at a steady rate
+//! and is spent one token per admitted item. Keys that have not been seen
+//! for long enough to refill their bucket are forgotten, so limiting many
+//! distinct keys does not grow the limiter without bound.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// Buckets kept before full ones are pruned.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Rate at which a bucket refills and the number of tokens it holds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+	/// Tokens added per second.
+	pub per_second: f64,
+	/// Tokens the bucket holds when full.
+	pub burst: f64,
+}
+
+impl RateLimit {
+	/// Allows `rate` items per second, with bursts of up to a second's worth.
+	pub fn per_second(rate: f64) -> Self {
+		Self {
+			per_second: rate,
+			burst: rate.max(1.0),
+		}
+	}
+
+	/// Allows `quota` items per minute, all of which may arrive at once.
+	pub fn per_minute(quota: u32) -> Self {
+		Self {
+			per_second: f64::from(quota) / 60.0,
+			burst: f64::from(quota),
+		}
+	}
+}
+
+/// Tokens left in one key's bucket.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+	/// Tokens available when the bucket was last updated.
+	tokens: f64,
+	/// When the bucket was last updated.
+	updated: Instant,
+	/// Limit the bucket was last used with.
+	limit: RateLimit,
+}
+
+impl Bucket {
+	/// Returns the tokens available at `now`.
+	fn available(&self, now: Instant) -> f64 {
+		let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+		(self.tokens + elapsed * self.limit.per_second).min(self.limit.burst)
+	}
+}
+
+/// Token buckets keyed by what they limit.
+#[derive(Debug)]
+pub struct RateLimiter<K> {
+	/// Buckets of the keys that spent tokens recently.
+	buckets: HashMap<K, Bucket>,
+}
+
+impl<K> Default for RateLimiter<K> {
+	fn default() -> Self {
+		Self {
+			buckets: HashMap::new(),
+		}
+	}
+}
+
+impl<K: Eq + Hash + Clone> RateLimiter<K> {
+	/// Creates a limiter with every bucket full.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns how long `key` has to wait before it may take a token under
+	/// `limit`, without taking one. Returns zero if it may take one now.
+	pub fn wait_time(&self, key: &K, limit: RateLimit, now: Instant) -> Duration {
+		let available = match self.buckets.get(key) {
+			Some(bucket) => Bucket { limit, ..*bucket }.available(now),
+			None => limit.burst,
+		};
+		if available >= 1.0 {
+			return Duration::ZERO;
+		}
+		if limit.per_second <= 0.0 {
+			return Duration::MAX;
+		}
+		Duration::from_secs_f64((1.0 - available) / limit.per_second)
+	}
+
+	/// Takes a token from the bucket of `key` under `limit`, or returns how
+	/// long to wait until one is available.
+	pub fn try_take(&mut self, key: &K, limit: RateLimit, now: Instant) -> Result<(), Duration> {
+		let wait = self.wait_time(key, limit, now);
+		if !wait.is_zero() {
+			return Err(wait);
+		}
+
+		let tokens = match self.buckets.get(key) {
+			Some(bucket) => Bucket { limit, ..*bucket }.available(now),
+			None => limit.burst,
+		};
+		self.buckets.insert(
+			key.clone(),
+			Bucket {
+				tokens: tokens - 1.0,
+				updated: now,
+				limit,
+			},
+		);
+
+		if self.buckets.len() >= PRUNE_THRESHOLD {
+			self.prune(now);
+		}
+		Ok(())
+	}
+
+	/// Forgets the buckets that refilled, as a missing bucket is full.
+	fn prune(&mut self, now: Instant) {
+		self.buckets
+			.retain(|_, bucket| bucket.available(now) < bucket.limit.burst);
+	}
+}
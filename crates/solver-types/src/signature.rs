@@ -0,0 +1,95 @@
+//! Verification of signatures made by users rather than by the solver.
+//!
+//! Gasless orders reach the solver off-chain with nothing but the user's
+//! signature vouching for them. Before such an order is treated as valid,
+//! the signature is decoded and the address it recovers to over the signed
+//! payload's EIP-712 hash is compared with the order's user. Both 65-byte
+//! signatures and EIP-2098 compact 64-byte signatures are accepted.
+
+use alloy_primitives::{hex, Address as AlloyAddress, PrimitiveSignature, B256};
+use thiserror::Error;
+
+/// Errors that can occur verifying a user signature.
+#[derive(Debug, Error)]
+pub enum SignatureError {
+	/// The signature is not valid hex.
+	#[error("Invalid signature encoding: {0}")]
+	Encoding(String),
+	/// The signature is neither 64 nor 65 bytes long.
+	#[error("Invalid signature length: {0} bytes")]
+	Length(usize),
+	/// The recovery id of a 65-byte signature is out of range.
+	#[error("Invalid signature recovery id")]
+	RecoveryId,
+	/// The r or s component of the signature is zero.
+	#[error("Invalid signature: zero component")]
+	ZeroComponent,
+	/// No signer could be recovered from the signature.
+	#[error("Failed to recover signer: {0}")]
+	Recovery(String),
+	/// The signature was made by someone other than the expected signer.
+	#[error("Signed by {recovered} instead of {expected}")]
+	SignerMismatch {
+		/// Address the signature should recover to.
+		expected: AlloyAddress,
+		/// Address the signature recovers to.
+		recovered: AlloyAddress,
+	},
+}
+
+/// Decodes a hex-encoded user signature.
+///
+/// Only the encoding is checked, not who signed what, so that malformed
+/// signatures are rejected without any hashing or recovery.
+pub fn parse_signature(signature: &str) -> Result<PrimitiveSignature, SignatureError> {
+	let bytes = hex::decode(signature.trim_start_matches("0x"))
+		.map_err(|e| SignatureError::Encoding(e.to_string()))?;
+
+	let (r, s) = match bytes.len() {
+		65 => {
+			if !matches!(bytes[64], 0 | 1 | 27 | 28) {
+				return Err(SignatureError::RecoveryId);
+			}
+			(&bytes[..32], &bytes[32..64])
+		}
+		64 => (&bytes[..32], &bytes[32..64]),
+		len => return Err(SignatureError::Length(len)),
+	};
+	// The parity bit of a compact signature is packed into the top bit of s
+	let s_is_zero = s[0] & 0x7f == 0 && s[1..].iter().all(|byte| *byte == 0);
+	if r.iter().all(|byte| *byte == 0) || s_is_zero {
+		return Err(SignatureError::ZeroComponent);
+	}
+
+	match bytes.len() {
+		64 => Ok(PrimitiveSignature::from_erc2098(&bytes)),
+		_ => PrimitiveSignature::from_raw(&bytes)
+			.map_err(|e| SignatureError::Encoding(e.to_string())),
+	}
+}
+
+/// Recovers the address that signed `hash`.
+///
+/// Recovery is CPU-bound; callers verifying many signatures should run it
+/// off the async runtime.
+pub fn recover_signer(hash: &B256, signature: &str) -> Result<AlloyAddress, SignatureError> {
+	parse_signature(signature)?
+		.recover_address_from_prehash(hash)
+		.map_err(|e| SignatureError::Recovery(e.to_string()))
+}
+
+/// Checks that `signature` over `hash` was made by `expected`.
+pub fn verify_signer(
+	hash: &B256,
+	signature: &str,
+	expected: AlloyAddress,
+) -> Result<(), SignatureError> {
+	let recovered = recover_signer(hash, signature)?;
+	if recovered != expected {
+		return Err(SignatureError::SignerMismatch {
+			expected,
+			recovered,
+		});
+	}
+	Ok(())
+}
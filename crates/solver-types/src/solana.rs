@@ -0,0 +1,177 @@
+//! Solana instruction and log types for the solver system.
+//!
+//! Solver transactions carry a destination and calldata, which fits EVM calls
+//! but not Solana instructions, whose accounts are listed explicitly. A Solana
+//! instruction travels in a [`Transaction`] whose destination is the program
+//! and whose data is the instruction's accounts followed by its own data, so
+//! order implementations can hand Solana instructions to delivery like any
+//! other transaction.
+
+use crate::{Address, Transaction, TransactionLog};
+use alloy_primitives::U256;
+use base64::Engine;
+use thiserror::Error;
+
+/// Prefix of the log lines programs emit event data with.
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Flag marking an account that signs the instruction.
+const SIGNER_FLAG: u8 = 1;
+
+/// Flag marking an account the instruction writes to.
+const WRITABLE_FLAG: u8 = 2;
+
+/// Errors that can occur decoding a Solana instruction.
+#[derive(Debug, Error)]
+pub enum SolanaError {
+	/// The transaction does not hold a Solana instruction.
+	#[error("Invalid Solana instruction: {0}")]
+	InvalidInstruction(String),
+}
+
+/// Account an instruction reads or writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaAccountMeta {
+	/// Public key of the account.
+	pub pubkey: [u8; 32],
+	/// Whether the account must sign the transaction.
+	pub is_signer: bool,
+	/// Whether the instruction writes to the account.
+	pub is_writable: bool,
+}
+
+/// Instruction of a Solana program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolanaInstruction {
+	/// Program executing the instruction.
+	pub program_id: [u8; 32],
+	/// Accounts passed to the program, in order.
+	pub accounts: Vec<SolanaAccountMeta>,
+	/// Instruction data passed to the program.
+	pub data: Vec<u8>,
+}
+
+impl SolanaInstruction {
+	/// Wraps the instruction in a transaction for the given chain.
+	///
+	/// The data of the transaction is the number of accounts, each account's
+	/// public key followed by its signer and writable flags, and then the
+	/// instruction data.
+	pub fn into_transaction(self, chain_id: u64) -> Transaction {
+		let mut data = Vec::with_capacity(1 + self.accounts.len() * 33 + self.data.len());
+		data.push(self.accounts.len() as u8);
+		for account in &self.accounts {
+			data.extend_from_slice(&account.pubkey);
+			let mut flags = 0;
+			if account.is_signer {
+				flags |= SIGNER_FLAG;
+			}
+			if account.is_writable {
+				flags |= WRITABLE_FLAG;
+			}
+			data.push(flags);
+		}
+		data.extend_from_slice(&self.data);
+
+		Transaction {
+			to: Some(Address(self.program_id.to_vec())),
+			data,
+			value: U256::ZERO,
+			chain_id,
+			nonce: None,
+			gas_limit: None,
+			gas_price: None,
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
+		}
+	}
+
+	/// Unwraps the instruction carried by a transaction.
+	pub fn from_transaction(tx: &Transaction) -> Result<Self, SolanaError> {
+		let program_id = tx
+			.to
+			.as_ref()
+			.and_then(|to| <[u8; 32]>::try_from(to.0.as_slice()).ok())
+			.ok_or_else(|| SolanaError::InvalidInstruction("program ID must be 32 bytes".into()))?;
+		if tx.value != U256::ZERO {
+			return Err(SolanaError::InvalidInstruction(
+				"value cannot be attached to an instruction".into(),
+			));
+		}
+
+		let (&count, mut rest) = tx
+			.data
+			.split_first()
+			.ok_or_else(|| SolanaError::InvalidInstruction("missing account count".into()))?;
+		let mut accounts = Vec::with_capacity(count as usize);
+		for _ in 0..count {
+			if rest.len() < 33 {
+				return Err(SolanaError::InvalidInstruction(
+					"truncated account list".into(),
+				));
+			}
+			let (account, remaining) = rest.split_at(33);
+			let mut pubkey = [0u8; 32];
+			pubkey.copy_from_slice(&account[..32]);
+			accounts.push(SolanaAccountMeta {
+				pubkey,
+				is_signer: account[32] & SIGNER_FLAG != 0,
+				is_writable: account[32] & WRITABLE_FLAG != 0,
+			});
+			rest = remaining;
+		}
+
+		Ok(Self {
+			program_id,
+			accounts,
+			data: rest.to_vec(),
+		})
+	}
+}
+
+/// Extracts the event data programs emitted in a transaction's log messages.
+///
+/// Each `Program data:` line becomes a log of the program running when it was
+/// emitted, found by following the `invoke` and `success` or `failed` lines
+/// around it. Lines that are not valid base64 are skipped.
+pub fn program_data_logs(messages: &[String]) -> Vec<TransactionLog> {
+	let mut programs: Vec<Vec<u8>> = Vec::new();
+	let mut logs = Vec::new();
+
+	for message in messages {
+		if let Some(data) = message.strip_prefix(PROGRAM_DATA_PREFIX) {
+			let (Some(program), Ok(data)) = (
+				programs.last(),
+				base64::engine::general_purpose::STANDARD.decode(data.trim()),
+			) else {
+				continue;
+			};
+			logs.push(TransactionLog {
+				address: Address(program.clone()),
+				topics: Vec::new(),
+				data,
+			});
+			continue;
+		}
+
+		let mut words = message.split_whitespace();
+		if words.next() != Some("Program") {
+			continue;
+		}
+		// Skips "Program log:" and "Program return:" lines
+		let (Some(program), Some(action)) = (words.next(), words.next()) else {
+			continue;
+		};
+		if program.ends_with(':') {
+			continue;
+		}
+		match action {
+			"invoke" => programs.push(bs58::decode(program).into_vec().unwrap_or_default()),
+			"success" | "failed:" => {
+				programs.pop();
+			}
+			_ => {}
+		}
+	}
+	logs
+}
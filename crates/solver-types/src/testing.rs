@@ -0,0 +1,163 @@
+//! Scripted behaviour of mock service implementations.
+//!
+//! The mock implementations of the service interfaces, available with the
+//! `testing` feature of each service crate, let embedders of the solver test
+//! their strategies and configuration without chains, RPC endpoints or keys.
+//! Each mock delegates its timing and failures to a [`MockBehavior`], which
+//! delays calls by a configurable latency, fails calls from a script, and
+//! counts the calls made to each operation so that tests can assert on them.
+//!
+//! Operations are named after the interface methods they stand for, such as
+//! `"submit"` or `"get_attestation"`.
+
+use crate::{Field, FieldType};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Reason given for failures scripted from configuration.
+const SCRIPTED_FAILURE: &str = "Scripted failure";
+
+/// Timing, failures and call counts of a mock implementation.
+#[derive(Debug, Default)]
+pub struct MockBehavior {
+	/// Delay of every call without a latency of its own.
+	latency: Duration,
+	/// Delays keyed by operation.
+	operation_latencies: HashMap<String, Duration>,
+	/// Reasons of the upcoming failures of each operation, next first.
+	failures: Mutex<HashMap<String, VecDeque<String>>>,
+	/// Calls made so far keyed by operation.
+	calls: Mutex<HashMap<String, usize>>,
+}
+
+impl MockBehavior {
+	/// Creates a behaviour without latency or failures.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates the behaviour described by a mock's configuration.
+	///
+	/// Reads `latency_ms`, the `operation_latency_ms` table of latencies per
+	/// operation, and the `fail_first` table of the number of calls to each
+	/// operation that fail before it succeeds.
+	pub fn from_config(config: &toml::Value) -> Self {
+		let millis = |value: &toml::Value| {
+			Duration::from_millis(value.as_integer().unwrap_or(0).max(0) as u64)
+		};
+
+		let mut behavior = Self::new();
+		if let Some(latency) = config.get("latency_ms") {
+			behavior.latency = millis(latency);
+		}
+		if let Some(latencies) = config
+			.get("operation_latency_ms")
+			.and_then(|v| v.as_table())
+		{
+			for (operation, latency) in latencies {
+				behavior
+					.operation_latencies
+					.insert(operation.clone(), millis(latency));
+			}
+		}
+		if let Some(failures) = config.get("fail_first").and_then(|v| v.as_table()) {
+			for (operation, times) in failures {
+				let times = times.as_integer().unwrap_or(0).max(0) as usize;
+				behavior.fail_times(operation, times, SCRIPTED_FAILURE);
+			}
+		}
+		behavior
+	}
+
+	/// Returns the configuration fields read by [`from_config`](Self::from_config),
+	/// for the schemas of mock implementations.
+	pub fn config_fields() -> Vec<Field> {
+		vec![
+			Field::new(
+				"latency_ms",
+				FieldType::Integer {
+					min: Some(0),
+					max: None,
+				},
+			),
+			Field::new(
+				"operation_latency_ms",
+				FieldType::Table(crate::Schema::new(vec![], vec![])),
+			),
+			Field::new(
+				"fail_first",
+				FieldType::Table(crate::Schema::new(vec![], vec![])),
+			),
+		]
+	}
+
+	/// Delays every call by `latency`.
+	pub fn with_latency(mut self, latency: Duration) -> Self {
+		self.latency = latency;
+		self
+	}
+
+	/// Delays the calls to one operation by `latency`.
+	pub fn with_operation_latency(mut self, operation: &str, latency: Duration) -> Self {
+		self.operation_latencies
+			.insert(operation.to_string(), latency);
+		self
+	}
+
+	/// Fails the next call to an operation with `reason`.
+	///
+	/// Failures scripted for the same operation are returned in the order
+	/// they were scripted.
+	pub fn fail_next(&self, operation: &str, reason: &str) {
+		self.fail_times(operation, 1, reason);
+	}
+
+	/// Fails the next `times` calls to an operation with `reason`.
+	pub fn fail_times(&self, operation: &str, times: usize, reason: &str) {
+		let mut failures = self.failures.lock().unwrap();
+		let queue = failures.entry(operation.to_string()).or_default();
+		queue.extend(std::iter::repeat_n(reason.to_string(), times));
+	}
+
+	/// Returns the number of calls made to an operation.
+	pub fn calls(&self, operation: &str) -> usize {
+		self.calls
+			.lock()
+			.unwrap()
+			.get(operation)
+			.copied()
+			.unwrap_or(0)
+	}
+
+	/// Plays a call to an operation: counts it, waits for its latency, and
+	/// returns the reason it fails, if a failure is scripted.
+	pub async fn call(&self, operation: &str) -> Result<(), String> {
+		*self
+			.calls
+			.lock()
+			.unwrap()
+			.entry(operation.to_string())
+			.or_default() += 1;
+
+		let latency = self
+			.operation_latencies
+			.get(operation)
+			.copied()
+			.unwrap_or(self.latency);
+		if !latency.is_zero() {
+			tokio::time::sleep(latency).await;
+		}
+
+		match self
+			.failures
+			.lock()
+			.unwrap()
+			.get_mut(operation)
+			.and_then(|queue| queue.pop_front())
+		{
+			Some(reason) => Err(reason),
+			None => Ok(()),
+		}
+	}
+}
@@ -0,0 +1,18 @@
+//! Conversions between token amounts and whole tokens.
+//!
+//! Amounts travel on-chain in a token's smallest unit, while prices are
+//! quoted per whole token. Valuing an amount, or turning a USD value back
+//! into an amount, scales by the token's decimals.
+
+use alloy_primitives::U256;
+
+/// Converts an amount in a token's smallest unit to whole tokens.
+pub fn whole_tokens(amount: U256, decimals: u8) -> f64 {
+	f64::from(amount) / 10f64.powi(decimals as i32)
+}
+
+/// Converts an amount of whole tokens to the token's smallest unit, rounding
+/// down.
+pub fn smallest_units(amount: f64, decimals: u8) -> U256 {
+	U256::try_from((amount * 10f64.powi(decimals as i32)).floor()).unwrap_or_default()
+}
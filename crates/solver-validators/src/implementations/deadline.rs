@@ -0,0 +1,90 @@
+//! Validator requiring time to be left before an order's deadline.
+//!
+//! An order discovered close to its deadline leaves little room for the fill
+//! to land, and a fill landing late costs gas without earning anything. The
+//! deadline is the earlier of the order's fill deadline and the end of its
+//! exclusivity window, when known.
+
+use alloy_primitives::U256;
+use solver_types::{ConfigSchema, Field, FieldType, Schema};
+
+use crate::{utilization, OpenOrders, ValidationRequest, ValidatorInterface, ValidatorResult};
+
+/// Validator rejecting orders too close to their deadline.
+///
+/// The risk score rises as the time left approaches the minimum, reaching
+/// half the maximum score with twice the minimum left.
+pub struct DeadlineValidator {
+	/// Seconds that must be left before the deadline.
+	min_seconds_remaining: u64,
+}
+
+impl DeadlineValidator {
+	/// Creates a validator rejecting orders with less than
+	/// `min_seconds_remaining` seconds left before their deadline.
+	pub fn new(min_seconds_remaining: u64) -> Self {
+		Self {
+			min_seconds_remaining,
+		}
+	}
+}
+
+/// Configuration schema for DeadlineValidator.
+pub struct DeadlineValidatorSchema;
+
+impl ConfigSchema for DeadlineValidatorSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![Field::new(
+				"min_seconds_remaining",
+				FieldType::Integer {
+					min: Some(1),
+					max: None,
+				},
+			)],
+			// Optional fields
+			vec![],
+		);
+
+		schema.validate(config)
+	}
+}
+
+impl ValidatorInterface for DeadlineValidator {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(DeadlineValidatorSchema)
+	}
+
+	fn validate(&self, request: &ValidationRequest, _open_orders: &OpenOrders) -> ValidatorResult {
+		// Orders without a known deadline cannot run out of time
+		let Some(deadline) = request.context.deadline else {
+			return ValidatorResult::Pass(0);
+		};
+		let remaining = deadline.saturating_sub(request.context.timestamp);
+		if remaining < self.min_seconds_remaining {
+			return ValidatorResult::Reject(format!(
+				"{} seconds left before the deadline, at least {} are required",
+				remaining, self.min_seconds_remaining
+			));
+		}
+		ValidatorResult::Pass(utilization(
+			U256::from(self.min_seconds_remaining),
+			U256::from(remaining),
+		))
+	}
+}
+
+/// Factory function to create a deadline validator from configuration.
+///
+/// Configuration parameters:
+/// - `min_seconds_remaining`: Seconds that must be left before an order's
+///   deadline when it is validated
+pub fn create_deadline_validator(config: &toml::Value) -> Box<dyn ValidatorInterface> {
+	let min_seconds_remaining = config
+		.get("min_seconds_remaining")
+		.and_then(|v| v.as_integer())
+		.unwrap_or(0) as u64;
+
+	Box::new(DeadlineValidator::new(min_seconds_remaining))
+}
@@ -0,0 +1,68 @@
+//! Validator requiring the inputs of an order to be re-swappable.
+//!
+//! The solver is paid in the order's input tokens. A long-tail token the
+//! solver cannot swap back without moving the market leaves it holding an
+//! asset worth less than quoted. Price impact is measured by the liquidity
+//! sources configured for each chain, so this validator is built from the
+//! liquidity service rather than from its own configuration.
+
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_liquidity::LiquidityService;
+use solver_types::{ConfigSchema, Schema};
+use std::sync::Arc;
+
+use crate::{utilization, OpenOrders, ValidationRequest, ValidatorInterface, ValidatorResult};
+
+/// Validator rejecting orders whose inputs could not be recycled.
+///
+/// The risk score is the highest price impact of any input, relative to the
+/// maximum the liquidity service accepts.
+pub struct LiquidityValidator {
+	/// Service quoting the price impact of each input.
+	liquidity: Arc<LiquidityService>,
+}
+
+impl LiquidityValidator {
+	/// Creates a validator checking inputs against the given service.
+	pub fn new(liquidity: Arc<LiquidityService>) -> Self {
+		Self { liquidity }
+	}
+}
+
+/// Configuration schema for LiquidityValidator.
+///
+/// The validator is configured through the liquidity service, so it takes
+/// no configuration of its own.
+pub struct LiquidityValidatorSchema;
+
+impl ConfigSchema for LiquidityValidatorSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		Schema::new(vec![], vec![]).validate(config)
+	}
+}
+
+#[async_trait]
+impl ValidatorInterface for LiquidityValidator {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(LiquidityValidatorSchema)
+	}
+
+	async fn check_market(&self, request: &ValidationRequest<'_>) -> ValidatorResult {
+		match self
+			.liquidity
+			.check_recyclable(&request.assets.inputs)
+			.await
+		{
+			Ok(impact_bps) => ValidatorResult::Pass(utilization(
+				U256::from(impact_bps),
+				U256::from(self.liquidity.max_price_impact_bps()),
+			)),
+			Err(e) => ValidatorResult::Reject(e.to_string()),
+		}
+	}
+
+	fn validate(&self, _request: &ValidationRequest, _open_orders: &OpenOrders) -> ValidatorResult {
+		ValidatorResult::Pass(0)
+	}
+}
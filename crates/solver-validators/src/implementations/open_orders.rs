@@ -0,0 +1,129 @@
+//! Validator limiting the number of open orders per chain.
+//!
+//! Every open order on a chain is a fill or claim transaction the solver has
+//! yet to land there. Capping them bounds the nonces, gas and inventory tied
+//! up on a chain when it congests or an RPC provider degrades.
+
+use alloy_primitives::U256;
+use solver_types::{ConfigSchema, Field, FieldType, Schema};
+use std::collections::{HashMap, HashSet};
+
+use crate::{utilization, OpenOrders, ValidationRequest, ValidatorInterface, ValidatorResult};
+
+/// Validator rejecting orders on chains with too many open orders.
+pub struct OpenOrdersValidator {
+	/// Limit for chains without one of their own, if any.
+	default_limit: Option<u64>,
+	/// Limits keyed by chain ID.
+	chain_limits: HashMap<u64, u64>,
+}
+
+impl OpenOrdersValidator {
+	/// Creates a validator allowing `default_limit` open orders per chain,
+	/// or the chain's own limit from `chain_limits`.
+	pub fn new(default_limit: Option<u64>, chain_limits: HashMap<u64, u64>) -> Self {
+		Self {
+			default_limit,
+			chain_limits,
+		}
+	}
+}
+
+/// Configuration schema for OpenOrdersValidator.
+pub struct OpenOrdersValidatorSchema;
+
+impl ConfigSchema for OpenOrdersValidatorSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![],
+			// Optional fields
+			vec![
+				Field::new(
+					"max_open_orders",
+					FieldType::Integer {
+						min: Some(1),
+						max: None,
+					},
+				),
+				Field::new("chains", FieldType::Table(Schema::new(vec![], vec![]))).with_validator(
+					|value| {
+						for (chain_id, limit) in value.as_table().unwrap() {
+							if chain_id.parse::<u64>().is_err() {
+								return Err(format!("Invalid chain ID '{}'", chain_id));
+							}
+							if limit.as_integer().is_none_or(|limit| limit < 1) {
+								return Err(format!(
+									"Limit of chain {} must be a positive integer",
+									chain_id
+								));
+							}
+						}
+						Ok(())
+					},
+				),
+			],
+		);
+
+		schema.validate(config)
+	}
+}
+
+impl ValidatorInterface for OpenOrdersValidator {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(OpenOrdersValidatorSchema)
+	}
+
+	fn validate(&self, request: &ValidationRequest, open_orders: &OpenOrders) -> ValidatorResult {
+		let chains: HashSet<u64> = request
+			.assets
+			.outputs
+			.iter()
+			.map(|output| output.chain_id)
+			.collect();
+
+		let mut score = 0;
+		for chain_id in chains {
+			let Some(limit) = self
+				.chain_limits
+				.get(&chain_id)
+				.copied()
+				.or(self.default_limit)
+			else {
+				continue;
+			};
+			let open = open_orders.count_on_chain(chain_id) as u64;
+			if open >= limit {
+				return ValidatorResult::Reject(format!(
+					"{} orders are already open on chain {}, the limit is {}",
+					open, chain_id, limit
+				));
+			}
+			score = score.max(utilization(U256::from(open + 1), U256::from(limit)));
+		}
+		ValidatorResult::Pass(score)
+	}
+}
+
+/// Factory function to create an open orders validator from configuration.
+///
+/// Configuration parameters:
+/// - `max_open_orders`: Open orders allowed on chains without a limit of
+///   their own (default: unlimited)
+/// - `chains`: Table of chain IDs to the open orders allowed on that chain
+pub fn create_open_orders_validator(config: &toml::Value) -> Box<dyn ValidatorInterface> {
+	let default_limit = config
+		.get("max_open_orders")
+		.and_then(|v| v.as_integer())
+		.map(|limit| limit as u64);
+
+	let chain_limits = config
+		.get("chains")
+		.and_then(|v| v.as_table())
+		.into_iter()
+		.flatten()
+		.filter_map(|(chain_id, limit)| Some((chain_id.parse().ok()?, limit.as_integer()? as u64)))
+		.collect();
+
+	Box::new(OpenOrdersValidator::new(default_limit, chain_limits))
+}
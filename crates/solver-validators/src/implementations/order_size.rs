@@ -0,0 +1,92 @@
+//! Validator limiting the size of single orders.
+//!
+//! Orders are sized by the tokens the solver delivers to fill them, since
+//! that is the capital the solver has at stake until it claims the inputs.
+//! Limits are set per token, in the token's smallest unit, so that no price
+//! source is needed.
+
+use alloy_primitives::U256;
+use solver_types::{Address, ConfigSchema, Field, FieldType, Schema};
+use std::collections::HashMap;
+
+use crate::{
+	parse_token_amounts, utilization, OpenOrders, ValidationRequest, ValidatorInterface,
+	ValidatorResult,
+};
+
+/// Validator rejecting orders delivering more of a token than allowed.
+pub struct OrderSizeValidator {
+	/// Largest amount delivered by a single order, keyed by chain ID and
+	/// token address. Tokens without a limit are not checked.
+	max_amounts: HashMap<(u64, Address), U256>,
+}
+
+impl OrderSizeValidator {
+	/// Creates a validator enforcing the given per-token limits.
+	pub fn new(max_amounts: HashMap<(u64, Address), U256>) -> Self {
+		Self { max_amounts }
+	}
+}
+
+/// Configuration schema for OrderSizeValidator.
+pub struct OrderSizeValidatorSchema;
+
+impl ConfigSchema for OrderSizeValidatorSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("max_amounts", FieldType::Table(Schema::new(vec![], vec![])))
+					.with_validator(|value| parse_token_amounts(value).map(|_| ())),
+			],
+			// Optional fields
+			vec![],
+		);
+
+		schema.validate(config)
+	}
+}
+
+impl ValidatorInterface for OrderSizeValidator {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(OrderSizeValidatorSchema)
+	}
+
+	fn validate(&self, request: &ValidationRequest, _open_orders: &OpenOrders) -> ValidatorResult {
+		let mut score = 0;
+		for output in &request.assets.outputs {
+			let Some(limit) = self
+				.max_amounts
+				.get(&(output.chain_id, output.token.clone()))
+			else {
+				continue;
+			};
+			if output.amount > *limit {
+				return ValidatorResult::Reject(format!(
+					"output of {} of token 0x{} on chain {} exceeds the limit of {}",
+					output.amount,
+					hex::encode(&output.token.0),
+					output.chain_id,
+					limit
+				));
+			}
+			score = score.max(utilization(output.amount, *limit));
+		}
+		ValidatorResult::Pass(score)
+	}
+}
+
+/// Factory function to create an order size validator from configuration.
+///
+/// Configuration parameters:
+/// - `max_amounts`: Table of chain IDs to tables of token addresses to the
+///   largest amount a single order may deliver, as a decimal string in the
+///   token's smallest unit
+pub fn create_order_size_validator(config: &toml::Value) -> Box<dyn ValidatorInterface> {
+	let max_amounts = config
+		.get("max_amounts")
+		.and_then(|value| parse_token_amounts(value).ok())
+		.unwrap_or_default();
+
+	Box::new(OrderSizeValidator::new(max_amounts))
+}
@@ -0,0 +1,101 @@
+//! Validator capping the solver's exposure to a single user.
+//!
+//! A user opening many orders at once ties up the solver's inventory and
+//! concentrates its risk on one counterparty. The exposure to a user is what
+//! the solver delivers across the user's open orders, per token. Orders whose
+//! standard identifies no user are not checked.
+
+use alloy_primitives::U256;
+use solver_types::{Address, ConfigSchema, Field, FieldType, Schema};
+use std::collections::HashMap;
+
+use crate::{
+	parse_token_amounts, utilization, OpenOrders, ValidationRequest, ValidatorInterface,
+	ValidatorResult,
+};
+
+/// Validator rejecting orders that would take the exposure to their user
+/// over a limit.
+pub struct UserExposureValidator {
+	/// Largest amount delivered across a user's open orders, keyed by chain
+	/// ID and token address. Tokens without a cap are not checked.
+	max_amounts: HashMap<(u64, Address), U256>,
+}
+
+impl UserExposureValidator {
+	/// Creates a validator enforcing the given per-token caps.
+	pub fn new(max_amounts: HashMap<(u64, Address), U256>) -> Self {
+		Self { max_amounts }
+	}
+}
+
+/// Configuration schema for UserExposureValidator.
+pub struct UserExposureValidatorSchema;
+
+impl ConfigSchema for UserExposureValidatorSchema {
+	fn validate(&self, config: &toml::Value) -> Result<(), solver_types::ValidationError> {
+		let schema = Schema::new(
+			// Required fields
+			vec![
+				Field::new("max_amounts", FieldType::Table(Schema::new(vec![], vec![])))
+					.with_validator(|value| parse_token_amounts(value).map(|_| ())),
+			],
+			// Optional fields
+			vec![],
+		);
+
+		schema.validate(config)
+	}
+}
+
+impl ValidatorInterface for UserExposureValidator {
+	fn config_schema(&self) -> Box<dyn ConfigSchema> {
+		Box::new(UserExposureValidatorSchema)
+	}
+
+	fn validate(&self, request: &ValidationRequest, open_orders: &OpenOrders) -> ValidatorResult {
+		let Some(user) = request.user else {
+			return ValidatorResult::Pass(0);
+		};
+
+		let mut score = 0;
+		for output in &request.assets.outputs {
+			let Some(limit) = self
+				.max_amounts
+				.get(&(output.chain_id, output.token.clone()))
+			else {
+				continue;
+			};
+			let exposure = open_orders
+				.user_exposure(user, output.chain_id, &output.token)
+				.saturating_add(output.amount);
+			if exposure > *limit {
+				return ValidatorResult::Reject(format!(
+					"exposure of {} to user 0x{} in token 0x{} on chain {} exceeds the cap of {}",
+					exposure,
+					hex::encode(&user.0),
+					hex::encode(&output.token.0),
+					output.chain_id,
+					limit
+				));
+			}
+			score = score.max(utilization(exposure, *limit));
+		}
+		ValidatorResult::Pass(score)
+	}
+}
+
+/// Factory function to create a user exposure validator from configuration.
+///
+/// Configuration parameters:
+/// - `max_amounts`: Table of chain IDs to tables of token addresses to the
+///   largest amount delivered across a user's open orders, as a decimal
+///   string in the token's smallest unit
+pub fn create_user_exposure_validator(config: &toml::Value) -> Box<dyn ValidatorInterface> {
+	let max_amounts = config
+		.get("max_amounts")
+		.and_then(|value| parse_token_amounts(value).ok())
+		.unwrap_or_default();
+
+	Box::new(UserExposureValidator::new(max_amounts))
+}
@@ -0,0 +1,319 @@
+//! Risk validators for the OIF solver system.
+//!
+//! Order validation proves that an order is well formed; the validators
+//! decide whether the solver should take on its risk. Each validator looks at
+//! one dimension of risk, such as the size of the order, the solver's
+//! exposure to its user or the time left to fill it, and either rejects the
+//! order or scores how close it comes to the configured limit.
+//!
+//! Some limits depend on the market rather than on the solver's own book,
+//! such as the liquidity available to re-swap an order's inputs. Validators
+//! check those first, querying the chain as needed, before the limits that
+//! depend on the orders the solver already committed to.
+//!
+//! The validation pipeline runs every registered validator on new orders and
+//! keeps track of the orders it lets through until they finish, so that
+//! limits spanning several orders can be enforced. The risk score of an order
+//! is the highest score of any validator, and is handed to the execution
+//! strategy along with the rest of the execution context.
+
+use alloy_primitives::U256;
+use async_trait::async_trait;
+use solver_types::{Address, ConfigSchema, ExecutionContext, Order, OrderAssets, TokenAmount};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Re-export implementations
+pub mod implementations {
+	pub mod deadline;
+	pub mod liquidity;
+	pub mod open_orders;
+	pub mod order_size;
+	pub mod user_exposure;
+}
+
+/// Risk score of an order at one of the configured limits.
+pub const MAX_RISK_SCORE: u32 = 100;
+
+/// Rejection of an order by one of the pipeline's validators.
+#[derive(Debug, Clone, Error)]
+#[error("Rejected by {validator} validator: {reason}")]
+pub struct Rejection {
+	/// Name of the validator rejecting the order.
+	pub validator: String,
+	/// Limit the order breaks.
+	pub reason: String,
+}
+
+/// Result of a single validator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidatorResult {
+	/// The order is within limits, with a risk score from 0 to
+	/// [`MAX_RISK_SCORE`].
+	Pass(u32),
+	/// The order breaks a limit, for the given reason.
+	Reject(String),
+}
+
+/// Order being validated, along with what the solver knows about it.
+pub struct ValidationRequest<'a> {
+	/// The validated order.
+	pub order: &'a Order,
+	/// Assets moved by the order.
+	pub assets: &'a OrderAssets,
+	/// User who created the order, if its standard identifies one.
+	pub user: Option<&'a Address>,
+	/// Execution context the order will be decided with.
+	pub context: &'a ExecutionContext,
+	/// Whether the order is decided on the stablecoin fast path, which skips
+	/// the market checks.
+	pub fast_path: bool,
+}
+
+/// Order let through by the pipeline that has not finished yet.
+#[derive(Debug, Clone)]
+pub struct OpenOrder {
+	/// User who created the order, if known.
+	pub user: Option<Address>,
+	/// Tokens the solver delivers to fill the order.
+	pub outputs: Vec<TokenAmount>,
+	/// Time after which the order can no longer be filled, if known.
+	pub deadline: Option<u64>,
+	/// Risk score the order was let through with.
+	pub risk_score: u32,
+}
+
+/// Orders let through by the pipeline that have not finished yet.
+///
+/// Orders leave the book when they are released, or once their deadline
+/// passes, so that orders whose outcome is never observed do not count
+/// against limits forever.
+#[derive(Debug, Default)]
+pub struct OpenOrders {
+	/// Open orders keyed by order ID.
+	orders: HashMap<String, OpenOrder>,
+}
+
+impl OpenOrders {
+	/// Returns the number of open orders delivering outputs on a chain.
+	pub fn count_on_chain(&self, chain_id: u64) -> usize {
+		self.orders
+			.values()
+			.filter(|order| {
+				order
+					.outputs
+					.iter()
+					.any(|output| output.chain_id == chain_id)
+			})
+			.count()
+	}
+
+	/// Returns the amount of a token the solver delivers across the open
+	/// orders of a user.
+	pub fn user_exposure(&self, user: &Address, chain_id: u64, token: &Address) -> U256 {
+		self.orders
+			.values()
+			.filter(|order| order.user.as_ref() == Some(user))
+			.flat_map(|order| order.outputs.iter())
+			.filter(|output| output.chain_id == chain_id && &output.token == token)
+			.fold(U256::ZERO, |total, output| {
+				total.saturating_add(output.amount)
+			})
+	}
+
+	/// Drops the orders whose deadline passed by `now`.
+	fn prune(&mut self, now: u64) {
+		self.orders
+			.retain(|_, order| order.deadline.is_none_or(|deadline| deadline > now));
+	}
+}
+
+/// Trait defining the interface for risk validators.
+///
+/// Market checks may query the chain and run before the open orders are
+/// locked. Checks against the open orders only look at the request and the
+/// open orders, so they run without any RPC requests of their own.
+#[async_trait]
+pub trait ValidatorInterface: Send + Sync {
+	/// Returns the configuration schema for this validator.
+	///
+	/// This allows each validator to define its own configuration requirements
+	/// with specific validation rules. The schema is used to validate TOML
+	/// configuration before the validator is added to the pipeline.
+	fn config_schema(&self) -> Box<dyn ConfigSchema>;
+
+	/// Checks an order against the validator's limits on market conditions.
+	///
+	/// Not called for orders on the stablecoin fast path. Passes with a score
+	/// of zero by default.
+	async fn check_market(&self, _request: &ValidationRequest<'_>) -> ValidatorResult {
+		ValidatorResult::Pass(0)
+	}
+
+	/// Checks an order against the validator's limits, given the orders the
+	/// solver already committed to.
+	fn validate(&self, request: &ValidationRequest, open_orders: &OpenOrders) -> ValidatorResult;
+}
+
+/// Risk assessment of an order let through by the pipeline.
+#[derive(Debug, Clone)]
+pub struct RiskAssessment {
+	/// Overall risk score: the highest score of any validator.
+	pub score: u32,
+	/// Risk score of each validator, by name.
+	pub scores: Vec<(String, u32)>,
+}
+
+/// Pipeline running the registered validators on new orders.
+pub struct ValidationPipeline {
+	/// Validators by name, in the order they run.
+	validators: Vec<(String, Box<dyn ValidatorInterface>)>,
+	/// Orders let through that have not finished yet.
+	open_orders: Mutex<OpenOrders>,
+}
+
+impl ValidationPipeline {
+	/// Creates a pipeline running the given validators in name order.
+	pub fn new(mut validators: Vec<(String, Box<dyn ValidatorInterface>)>) -> Self {
+		validators.sort_by(|(a, _), (b, _)| a.cmp(b));
+		Self {
+			validators,
+			open_orders: Mutex::new(OpenOrders::default()),
+		}
+	}
+
+	/// Runs every validator's market checks on an order, without recording
+	/// it as open.
+	///
+	/// The first validator rejecting the order decides. Returns the score of
+	/// each validator otherwise.
+	pub async fn check_market(
+		&self,
+		request: &ValidationRequest<'_>,
+	) -> Result<Vec<(String, u32)>, Rejection> {
+		let mut scores = Vec::with_capacity(self.validators.len());
+		for (name, validator) in &self.validators {
+			let result = if request.fast_path {
+				ValidatorResult::Pass(0)
+			} else {
+				validator.check_market(request).await
+			};
+			scores.push((name.clone(), Self::score(name, result)?));
+		}
+		Ok(scores)
+	}
+
+	/// Runs every validator on an order.
+	///
+	/// Market checks run first, then the checks against the open orders. The
+	/// first validator rejecting the order decides. An order passing all of
+	/// them is recorded as open, counting against the limits of later orders
+	/// until it is [released](Self::release).
+	pub async fn assess(
+		&self,
+		request: &ValidationRequest<'_>,
+	) -> Result<RiskAssessment, Rejection> {
+		let mut scores = self.check_market(request).await?;
+
+		let mut open_orders = self.open_orders.lock().unwrap();
+		open_orders.prune(request.context.timestamp);
+		// An order assessed again must not count against itself
+		open_orders.orders.remove(&request.order.id);
+
+		for ((name, validator), (_, score)) in self.validators.iter().zip(&mut scores) {
+			let result = validator.validate(request, &open_orders);
+			*score = (*score).max(Self::score(name, result)?);
+		}
+
+		let score = scores.iter().map(|(_, score)| *score).max().unwrap_or(0);
+		open_orders.orders.insert(
+			request.order.id.clone(),
+			OpenOrder {
+				user: request.user.cloned(),
+				outputs: request.assets.outputs.clone(),
+				deadline: request.context.deadline,
+				risk_score: score,
+			},
+		);
+		Ok(RiskAssessment { score, scores })
+	}
+
+	/// Returns the risk score of a passing result, or the rejection of a
+	/// failing one.
+	fn score(name: &str, result: ValidatorResult) -> Result<u32, Rejection> {
+		match result {
+			ValidatorResult::Pass(score) => Ok(score.min(MAX_RISK_SCORE)),
+			ValidatorResult::Reject(reason) => Err(Rejection {
+				validator: name.to_string(),
+				reason,
+			}),
+		}
+	}
+
+	/// Returns the risk score an open order was let through with.
+	pub fn risk_score(&self, order_id: &str) -> Option<u32> {
+		self.open_orders
+			.lock()
+			.unwrap()
+			.orders
+			.get(order_id)
+			.map(|order| order.risk_score)
+	}
+
+	/// Stops counting a finished order against the limits.
+	pub fn release(&self, order_id: &str) {
+		self.open_orders.lock().unwrap().orders.remove(order_id);
+	}
+}
+
+/// Returns the risk score of using `amount` out of `limit`.
+///
+/// Scores grow linearly to [`MAX_RISK_SCORE`], reached at the limit.
+pub fn utilization(amount: U256, limit: U256) -> u32 {
+	if limit.is_zero() {
+		return MAX_RISK_SCORE;
+	}
+	let score = amount.saturating_mul(U256::from(MAX_RISK_SCORE)) / limit;
+	score.min(U256::from(MAX_RISK_SCORE)).to::<u32>()
+}
+
+/// Parses a table of chain IDs to tables of token addresses to amounts in
+/// the token's smallest unit, as decimal strings.
+pub(crate) fn parse_token_amounts(
+	value: &toml::Value,
+) -> Result<HashMap<(u64, Address), U256>, String> {
+	let mut amounts = HashMap::new();
+	let Some(chains) = value.as_table() else {
+		return Err("Expected a table of chain IDs".to_string());
+	};
+	for (chain_id, tokens) in chains {
+		let chain_id = chain_id
+			.parse::<u64>()
+			.map_err(|_| format!("Invalid chain ID '{}'", chain_id))?;
+		let Some(tokens) = tokens.as_table() else {
+			return Err(format!(
+				"Amounts of chain {} must be a table of token addresses",
+				chain_id
+			));
+		};
+		for (token, amount) in tokens {
+			let address = hex::decode(token.trim_start_matches("0x"))
+				.ok()
+				.filter(|bytes| bytes.len() == 20)
+				.map(Address)
+				.ok_or_else(|| format!("Invalid token address {} on chain {}", token, chain_id))?;
+			let amount = amount
+				.as_str()
+				.and_then(|amount| U256::from_str_radix(amount, 10).ok())
+				.ok_or_else(|| {
+					format!(
+						"Amount of token {} on chain {} must be a decimal string",
+						token, chain_id
+					)
+				})?;
+			amounts.insert((chain_id, address), amount);
+		}
+	}
+	Ok(amounts)
+}
@@ -0,0 +1,55 @@
+#!/bin/bash
+# check.sh - Run the benchmark suite and fail if any benchmark exceeds its
+# threshold in thresholds.txt.
+#
+# Usage: scripts/bench/check.sh [--no-run]
+#   --no-run  Only check the results of the last run in target/criterion
+
+set -e
+
+ROOT="$(cd "$(dirname "$0")/../.." && pwd)"
+THRESHOLDS="$ROOT/scripts/bench/thresholds.txt"
+RESULTS="$ROOT/target/criterion"
+
+# Crate and benchmark target of each suite
+BENCHES=(
+    "solver-types event_bus"
+    "solver-storage serialization"
+    "solver-discovery decoding"
+    "solver-order order"
+)
+
+if ! command -v jq &> /dev/null; then
+    echo "❌ 'jq' command not found"
+    exit 1
+fi
+
+if [ "$1" != "--no-run" ]; then
+    for bench in "${BENCHES[@]}"; do
+        read -r package target <<< "$bench"
+        (cd "$ROOT" && cargo bench -p "$package" --bench "$target" -- --noplot)
+    done
+fi
+
+failed=0
+while read -r id max_us; do
+    # Skip comments and blank lines
+    [[ -z "$id" || "$id" == \#* ]] && continue
+
+    estimates="$RESULTS/$id/new/estimates.json"
+    if [ ! -f "$estimates" ]; then
+        echo "❌ $id: no results"
+        failed=1
+        continue
+    fi
+
+    mean_us=$(jq '.mean.point_estimate / 1000' "$estimates")
+    if jq -e --argjson mean "$mean_us" --argjson max "$max_us" -n '$mean > $max' > /dev/null; then
+        printf "❌ %-40s %10.2f µs > %s µs\n" "$id" "$mean_us" "$max_us"
+        failed=1
+    else
+        printf "✅ %-40s %10.2f µs\n" "$id" "$mean_us"
+    fi
+done < "$THRESHOLDS"
+
+exit $failed